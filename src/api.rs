@@ -0,0 +1,194 @@
+//! Stable facade over the configuration loader, installation planner, `now` pipeline and
+//! state file, for programs that embed `setup-devbox` as a library.
+
+use std::fmt;
+use std::path::Path;
+
+use crate::commands::now;
+use crate::config::load_configs;
+use crate::core::inventory;
+use crate::core::version_check;
+use crate::engine::install_tools;
+use crate::engine::installation::planner::InstallationPlanner;
+use crate::engine::installers::{github, simulated};
+use crate::schemas::config_manager::ConfigurationManagerProcessor;
+use crate::schemas::tools_types::{InstallationConfiguration, InstallationSummary};
+use crate::state::manager::{load_or_initialize_state, save_devbox_state};
+
+pub use crate::config::ParsedConfigs;
+pub use crate::schemas::path_resolver::PathResolver;
+pub use crate::schemas::state_file::{DevBoxState, ToolState};
+pub use crate::schemas::tools_enums::ToolAction;
+pub use crate::schemas::tools_types::ToolEntry;
+
+/// Why a facade call failed. Details have also been logged.
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// A configuration file could not be read or parsed
+    Config(String),
+    /// The state file could not be read, converted or created
+    State(String),
+    /// The `now` pipeline could not start
+    Apply(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Config(message) => write!(f, "configuration error: {message}"),
+            Error::State(message) => write!(f, "state file error: {message}"),
+            Error::Apply(message) => write!(f, "apply failed: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// What [`apply`] or [`apply_tools`] did to the configured tools.
+#[derive(Debug, Clone, Default)]
+pub struct ApplyOutcome {
+    /// Tools that were newly installed
+    pub installed: Vec<String>,
+    /// Tools that were updated to another version
+    pub updated: Vec<String>,
+    /// Tools left untouched, with the reason
+    pub skipped: Vec<(String, String)>,
+    /// Tools that failed to install, with the error
+    pub failed: Vec<(String, String)>,
+}
+
+impl ApplyOutcome {
+    /// Whether every tool was installed, updated or skipped without an error.
+    pub fn succeeded(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    fn from_summary(summary: Option<InstallationSummary>) -> Self {
+        summary
+            .map(|summary| ApplyOutcome {
+                installed: summary.installed_tools,
+                updated: summary.updated_tools,
+                skipped: summary.skipped_tools,
+                failed: summary.failed_tools,
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// The action `apply` would take for a single tool.
+#[derive(Debug)]
+pub struct PlannedTool {
+    /// Tool name as configured in `tools.yaml`
+    pub name: String,
+    /// What would happen to the tool (install, update, skip, ...)
+    pub action: ToolAction,
+}
+
+/// Options for [`apply`], mirroring the flags of `setup-devbox now`.
+#[derive(Debug, Clone, Default)]
+pub struct ApplyOptions {
+    /// Update every `latest` tool regardless of the update threshold (`--update-latest`)
+    pub update_latest: bool,
+    /// Only report what would change (`--dry-run`)
+    pub dry_run: bool,
+    /// Resume an interrupted run from its checkpoint (`--continue`)
+    pub resume: bool,
+    /// Print what changed since the previous run (`--diff-last`)
+    pub diff_last: bool,
+}
+
+/// Replaces every installer with deterministic fakes driven by the fixture file at `fixtures`
+/// for the rest of the process, like `setup-devbox --simulate`. Call it before resolving
+/// paths, so the state file defaults to the fixture file's directory.
+pub fn simulate(fixtures: &Path) -> Result<(), String> {
+    simulated::configure(fixtures)
+}
+
+/// Loads all configuration files referenced by the resolved paths.
+pub fn load_config(paths: &PathResolver) -> Result<ParsedConfigs, Error> {
+    load_configs(paths).map_err(Error::Config)
+}
+
+/// Loads `state.json`, or an empty state if it does not exist yet.
+pub fn load_state(paths: &PathResolver) -> Result<DevBoxState, Error> {
+    load_or_initialize_state(&paths.state_file().to_path_buf()).map_err(Error::State)
+}
+
+/// Writes `state` to `state.json`. Returns `false` if the file could not be written.
+pub fn save_state(paths: &PathResolver, state: &DevBoxState) -> bool {
+    save_devbox_state(state, paths.state_file())
+}
+
+/// Determines the action for every configured tool without installing anything.
+///
+/// Tools are returned in the order `apply` processes them (by phase, then configuration
+/// order).
+pub fn plan(paths: &PathResolver, update_latest: bool) -> Result<Vec<PlannedTool>, Error> {
+    let Some(tools_config) = load_config(paths)?.tools else {
+        return Ok(Vec::new());
+    };
+    let state = load_state(paths)?;
+    github::reset_release_cache();
+    version_check::reset_cache();
+    inventory::reset_snapshot();
+    let configuration = InstallationConfiguration::new(&tools_config, update_latest, true);
+    let planner =
+        InstallationPlanner::new(&configuration, ConfigurationManagerProcessor::new(paths));
+
+    let mut tools: Vec<&ToolEntry> = tools_config.tools.iter().collect();
+    tools.sort_by_key(|tool| tool.effective_phase());
+
+    Ok(tools
+        .into_iter()
+        .map(|tool| {
+            let (action, _) = planner.determine_required_action(tool, state.tools.get(&tool.name));
+            PlannedTool {
+                name: tool.name.clone(),
+                action,
+            }
+        })
+        .collect())
+}
+
+/// Runs the full `setup-devbox now` pipeline: tools, fonts, shell configuration and
+/// system settings, updating `state.json` as it goes.
+///
+/// Failing tools don't make this an error; they are reported in [`ApplyOutcome::failed`].
+pub fn apply(paths: &PathResolver, options: &ApplyOptions) -> Result<ApplyOutcome, Error> {
+    now::run(
+        paths,
+        options.update_latest,
+        options.dry_run,
+        options.resume,
+        options.diff_last,
+        None,
+    )
+    .map(ApplyOutcome::from_summary)
+    .map_err(Error::Apply)
+}
+
+/// Installs or updates only the named tools, leaving fonts, shell and settings untouched.
+pub fn apply_tools(
+    paths: &PathResolver,
+    names: &[String],
+    options: &ApplyOptions,
+) -> Result<ApplyOutcome, Error> {
+    let Some(mut tools_config) = load_config(paths)?.tools else {
+        return Ok(ApplyOutcome::default());
+    };
+    tools_config.tools.retain(|tool| names.contains(&tool.name));
+    let mut state = load_state(paths)?;
+    github::reset_release_cache();
+    version_check::reset_cache();
+    inventory::reset_snapshot();
+    let summary = install_tools(
+        tools_config,
+        &mut state,
+        paths.state_file(),
+        options.update_latest,
+        options.dry_run,
+        paths,
+        None,
+    );
+    Ok(ApplyOutcome::from_summary(Some(summary)))
+}
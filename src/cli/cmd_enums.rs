@@ -1,4 +1,6 @@
-use crate::cli::type_enums::{ConfigType, SourceType, ValueType};
+use crate::cli::type_enums::{
+    ColorChoice, ConfigType, ListKind, OutputFormat, PromptFormat, SourceType, ValueType,
+};
 use clap::{Parser, Subcommand};
 
 /// Defines the command-line interface (CLI) for 'setup-devbox'.
@@ -10,11 +12,41 @@ use clap::{Parser, Subcommand};
 pub struct Cli {
     /// Enables detailed debug output for troubleshooting and development.
     #[arg(short, long)]
-    pub(crate) debug: bool,
+    pub debug: bool,
+
+    /// Rejects configuration files containing unknown keys (e.g. `rename-to` instead of `rename_to`).
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Refuses commands that would change the machine (`now`, `add`, `remove`, ...).
+    #[arg(long)]
+    pub read_only: bool,
+
+    /// Allows updating, unprotecting or removing tools marked `protected: true`.
+    #[arg(long, requires = "reason")]
+    pub force_protected: bool,
+
+    /// Why protected tools are changed; recorded in the state file.
+    #[arg(long, requires = "force_protected")]
+    pub reason: Option<String>,
+
+    /// Runs post-installation hooks that look dangerous (`rm -rf /`, `curl ... | sh`, `sudo`)
+    /// without `acknowledge_dangerous: true` on their entry.
+    #[arg(long)]
+    pub allow_dangerous_hooks: bool,
+
+    /// When to color output: `auto` (on a terminal, unless `NO_COLOR` is set), `always` or `never`.
+    #[arg(long, default_value = "auto")]
+    pub color: ColorChoice,
+
+    /// Replaces every installer with deterministic fakes driven by this fixture file, so
+    /// `plan` and `now` run without network access or changes to the machine.
+    #[arg(long, value_name = "FIXTURES", hide = true)]
+    pub simulate: Option<String>,
 
     /// Defines available subcommands for 'setup-devbox'.
     #[command(subcommand)]
-    pub(crate) command: Commands,
+    pub command: Commands,
 }
 
 /// Enumerates all supported subcommands with their specific arguments and options.
@@ -38,9 +70,54 @@ pub enum Commands {
         /// This ensures the latest versions are installed regardless of previous installation timestamps.
         #[arg(long)]
         update_latest: bool,
-        /// Show what changes would be made without actually executing them.
+        /// Show what tool changes would be made without actually executing them. Fonts, shell
+        /// configuration and system settings are skipped; use `plan` to review those too.
         #[arg(long)]
         dry_run: bool,
+        /// Resume an interrupted run, skipping tools that already finished.
+        #[arg(long = "continue", conflicts_with = "dry_run")]
+        resume: bool,
+        /// After the run, show what changed since the previous run (new failures, version bumps).
+        #[arg(long)]
+        diff_last: bool,
+        /// Accept the licenses of tools and fonts marked `requires_license_acceptance` without prompting.
+        #[arg(long)]
+        accept_licenses: bool,
+        /// Install tools whose `rename_to` collides with another tool's executable, replacing it.
+        /// Each takeover is recorded in the state file.
+        #[arg(long)]
+        force: bool,
+        /// Apply exactly the actions of a plan saved with `setup-devbox plan --out`.
+        /// Refuses to run if the plan no longer matches the machine.
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["dry_run", "resume", "update_latest"])]
+        from_plan: Option<String>,
+        /// After a run with new PATH entries or aliases, also write them to a snippet in the
+        /// temporary directory that the current shell can source.
+        #[arg(long)]
+        activation_file: bool,
+        /// Only process the tools, fonts and shell entries of these profiles (and the entries
+        /// without `profiles`). Repeat or separate with commas for several.
+        #[arg(long = "profile", env = "SDB_PROFILE", value_delimiter = ',')]
+        profiles: Vec<String>,
+    },
+    /// Shows the ordered list of changes `now` would make, for review before applying.
+    /// Save it with `--out` and apply it with `now --from-plan`.
+    Plan {
+        /// Optional path to a custom configuration file.
+        #[arg(long)]
+        config: Option<String>,
+        /// Optional path to a custom state file.
+        #[arg(long)]
+        state: Option<String>,
+        /// Plan updates of every tool with version "latest", like `now --update-latest`.
+        #[arg(long)]
+        update_latest: bool,
+        /// Print the plan as JSON instead of text.
+        #[arg(long)]
+        json: bool,
+        /// Save the plan as JSON to this file, for `now --from-plan`.
+        #[arg(long, value_name = "FILE")]
+        out: Option<String>,
     },
     /// Bootstraps the development environment by generating default configurations and installing Homebrew.
     /// Useful for initial setup and ensuring basic requirements are met.
@@ -49,6 +126,22 @@ pub enum Commands {
         #[arg(long)]
         config: Option<String>,
     },
+    /// Print a shell script that installs setup-devbox, clones a configuration repository and runs `now`.
+    /// Paste it into a new machine's terminal to provision it in one go.
+    BootstrapScript {
+        /// Git URL of the repository holding config.yaml and the other configuration files.
+        #[arg(long)]
+        repo: String,
+        /// Branch of the repository to clone (defaults to its default branch).
+        #[arg(long)]
+        branch: Option<String>,
+        /// Directory to clone the repository into (defaults to ~/.setup-devbox/configs).
+        #[arg(long, value_name = "DIR")]
+        dir: Option<String>,
+        /// Print a single `sh -c '...'` command instead of a multi-line script.
+        #[arg(long)]
+        one_liner: bool,
+    },
     /// Synchronizes or generates configurations from a state file or remote source.
     /// This allows recreating configuration files from an existing installation state or Gist.
     SyncConfig {
@@ -75,15 +168,45 @@ pub enum Commands {
         /// Edit a specific configuration file [possible values: tools, fonts, shell, settings].
         #[arg(long, conflicts_with = "state")]
         config: Option<ConfigType>,
+        /// Optional path to a custom configuration file.
+        #[arg(long)]
+        config_path: Option<String>,
+        /// Optional path to a custom state file.
+        #[arg(long)]
+        state_path: Option<String>,
     },
     /// Add a new tool, font, setting, or alias to configuration files.
     /// Provides a convenient way to extend configurations without manual file editing.
     Add {
+        /// Optional path to a custom configuration file.
+        #[arg(long, global = true)]
+        config: Option<String>,
+        /// Optional path to a custom state file.
+        #[arg(long, global = true)]
+        state: Option<String>,
+        /// Print the result as 'text', or as a 'json' or 'yaml' document (which does not apply
+        /// changes).
+        #[arg(long, global = true, default_value = "text")]
+        output: OutputFormat,
+        /// Update entries that already exist without asking; required to update them when
+        /// there is no terminal to ask on.
+        #[arg(long, short = 'y', global = true)]
+        yes: bool,
         #[command(subcommand)]
         add_type: AddCommands,
     },
     /// Remove an installed tool, font, alias, or setting
     Remove {
+        /// Optional path to a custom configuration file.
+        #[arg(long, global = true)]
+        config: Option<String>,
+        /// Optional path to a custom state file.
+        #[arg(long, global = true)]
+        state: Option<String>,
+        /// Print the result as 'text', or as a 'json' or 'yaml' document (which does not apply
+        /// changes).
+        #[arg(long, global = true, default_value = "text")]
+        output: OutputFormat,
         #[command(subcommand)]
         item: RemoveCommands,
     },
@@ -113,6 +236,262 @@ pub enum Commands {
         #[arg(long)]
         state: Option<String>,
     },
+    /// Cross-check state, configuration and filesystem and propose fixes for any drift.
+    /// Fixes are confirmed interactively unless `--yes` is given.
+    Reconcile {
+        /// Optional path to a custom configuration file.
+        #[arg(long)]
+        config: Option<String>,
+        /// Optional path to a custom state file.
+        #[arg(long)]
+        state: Option<String>,
+        /// Apply all proposed fixes without prompting.
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Serve a local JSON-RPC socket API (plan, apply, status, logs) for editor
+    /// extensions and GUIs.
+    Daemon {
+        /// Optional path to a custom configuration file.
+        #[arg(long)]
+        config: Option<String>,
+        /// Optional path to a custom state file.
+        #[arg(long)]
+        state: Option<String>,
+        /// Optional path for the socket (defaults to `daemon.sock` next to the state file).
+        #[arg(long)]
+        socket: Option<String>,
+    },
+    /// Open a terminal dashboard to review sync status and install, update or remove items.
+    Ui {
+        /// Optional path to a custom configuration file.
+        #[arg(long)]
+        config: Option<String>,
+        /// Optional path to a custom state file.
+        #[arg(long)]
+        state: Option<String>,
+    },
+    /// Inspect font releases before installing them.
+    Fonts {
+        #[command(subcommand)]
+        action: FontsCommands,
+    },
+    /// Inspect the shell configuration before it is applied.
+    Shellrc {
+        #[command(subcommand)]
+        action: ShellrcCommands,
+    },
+    /// Generate configuration entries for software already installed on this machine.
+    Import {
+        #[command(subcommand)]
+        action: ImportCommands,
+    },
+    /// Print a short provisioning status (in sync, out of date, failures) for a shell prompt.
+    /// Reads only the state file's modification time and the last run summary.
+    PromptStatus {
+        /// Prompt to format the segment for: 'starship' or 'p10k'.
+        #[arg(long, default_value = "starship")]
+        format: PromptFormat,
+        /// Optional path to a custom state file.
+        #[arg(long)]
+        state: Option<String>,
+    },
+    /// List the installed tools, fonts and applied settings from the state file, and the
+    /// aliases of shellrc.yaml.
+    List {
+        /// What to list: 'tools', 'fonts', 'settings' or 'aliases' (default: everything).
+        kind: Option<ListKind>,
+        /// Print the list as 'text' tables, or as a 'json' or 'yaml' document.
+        #[arg(long, default_value = "text")]
+        output: OutputFormat,
+        /// Optional path to a custom configuration file (for aliases).
+        #[arg(long)]
+        config: Option<String>,
+        /// Optional path to a custom state file.
+        #[arg(long)]
+        state: Option<String>,
+    },
+    /// List the licenses of installed tools and fonts.
+    Licenses {
+        /// Optional path to a custom state file.
+        #[arg(long)]
+        state: Option<String>,
+    },
+    /// Reclaim disk space: old Homebrew downloads and leftovers of interrupted downloads.
+    Clean {
+        /// Optional path to a custom configuration file.
+        #[arg(long)]
+        config: Option<String>,
+        /// Remove Homebrew downloads older than this many days (default: `brew_cleanup_prune_days`, or 30).
+        #[arg(long, value_name = "DAYS")]
+        prune: Option<u32>,
+        /// Only report what would be removed.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Show the disk space taken by installed tools and fonts, largest first.
+    Du {
+        /// Optional path to a custom configuration file.
+        #[arg(long)]
+        config: Option<String>,
+        /// Optional path to a custom state file.
+        #[arg(long)]
+        state: Option<String>,
+    },
+    /// Show configured, recorded and installed versions of every tool, and where they disagree.
+    Status {
+        /// Print the status as a 'text' table, or as a 'json' or 'yaml' document.
+        #[arg(long, default_value = "text")]
+        output: OutputFormat,
+        /// Optional path to a custom configuration file.
+        #[arg(long)]
+        config: Option<String>,
+        /// Optional path to a custom state file.
+        #[arg(long)]
+        state: Option<String>,
+    },
+    /// Show the machine facts available to `only_on` conditions, templates and hooks.
+    Facts {
+        /// Optional path to a custom configuration file (for `vpn_check`).
+        #[arg(long)]
+        config: Option<String>,
+        /// Print the facts as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show the effective configuration, state and resolved path of one tool.
+    Info {
+        /// Name of the tool, as in `tools.yaml`.
+        name: String,
+        /// Optional path to a custom configuration file.
+        #[arg(long)]
+        config: Option<String>,
+        /// Optional path to a custom state file.
+        #[arg(long)]
+        state: Option<String>,
+    },
+    /// Show which installed tool a file belongs to, from the recorded install receipts.
+    Owns {
+        /// File to look up, e.g. `~/bin/rg`.
+        path: String,
+        /// Optional path to a custom state file.
+        #[arg(long)]
+        state: Option<String>,
+    },
+    /// Rewrite deprecated configuration fields (e.g. `additional_cmd`) to their current names.
+    MigrateConfig {
+        /// Optional path to a custom configuration file.
+        #[arg(long)]
+        config: Option<String>,
+        /// Show what would change without writing any file.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Create a project from a template registered under `scaffolds:` in `config.yaml`,
+    /// installing its generator first if needed.
+    New {
+        /// Name of the template.
+        #[arg(required_unless_present = "list")]
+        template: Option<String>,
+        /// Directory to create the project in.
+        #[arg(required_unless_present = "list")]
+        dir: Option<String>,
+        /// List the registered templates instead.
+        #[arg(long)]
+        list: bool,
+        /// Optional path to a custom configuration file.
+        #[arg(long)]
+        config: Option<String>,
+        /// Optional path to a custom state file.
+        #[arg(long)]
+        state: Option<String>,
+    },
+    /// Manage the backups of the state file.
+    State {
+        #[command(subcommand)]
+        action: StateCommands,
+    },
+    /// Collect redacted diagnostics (version, platform, configuration, last run, state
+    /// summary, PATH and installer versions) into a tarball to attach to bug reports.
+    SupportBundle {
+        /// Where to write the tarball (default: `setup-devbox-support-<timestamp>.tar.gz`).
+        #[arg(long, short)]
+        output: Option<String>,
+        /// Optional path to a custom configuration file.
+        #[arg(long)]
+        config: Option<String>,
+        /// Optional path to a custom state file.
+        #[arg(long)]
+        state: Option<String>,
+    },
+}
+
+/// Subcommands of `setup-devbox fonts`.
+#[derive(Subcommand)]
+pub enum FontsCommands {
+    /// List the font files of a release with their family, style and size.
+    /// Useful for writing `install_only` patterns before installing.
+    Info {
+        /// Font name from fonts.yaml (e.g. 'FiraCode') or a GitHub repository ('owner/repo').
+        target: String,
+        /// Release tag to inspect (defaults to the font's tag, or the latest release).
+        #[arg(long)]
+        version: Option<String>,
+        /// Archive to inspect when the release contains several fonts.
+        #[arg(long)]
+        asset: Option<String>,
+        /// Optional path to a custom configuration file.
+        #[arg(long)]
+        config: Option<String>,
+    },
+}
+
+/// Subcommands of `setup-devbox shellrc`.
+#[derive(Subcommand)]
+pub enum ShellrcCommands {
+    /// Show, as a diff against the current files, what applying shellrc.yaml would write
+    /// to each RC file. Nothing is written.
+    Preview {
+        /// Optional path to a custom configuration file.
+        #[arg(long)]
+        config: Option<String>,
+    },
+}
+
+/// Subcommands of `setup-devbox state`.
+#[derive(Subcommand)]
+pub enum StateCommands {
+    /// Replace the state file with a backup taken before an earlier save, to roll back a
+    /// corrupted or bad state. The current state is backed up first.
+    Restore {
+        /// Backup to restore, as a file name in `state-backups/` or a path (default: newest).
+        #[arg(long)]
+        from: Option<String>,
+        /// Only list the available backups, newest first.
+        #[arg(long)]
+        list: bool,
+        /// Optional path to a custom state file.
+        #[arg(long)]
+        state: Option<String>,
+    },
+}
+
+/// Subcommands of `setup-devbox import`.
+#[derive(Subcommand)]
+pub enum ImportCommands {
+    /// Scan installed macOS applications and suggest tool entries (Homebrew casks or GitHub
+    /// releases) for them.
+    Apps {
+        /// Directory to scan instead of /Applications and ~/Applications.
+        #[arg(long)]
+        dir: Option<String>,
+        /// Add the suggested entries to tools.yaml instead of only printing them.
+        #[arg(long)]
+        write: bool,
+        /// Optional path to a custom configuration file.
+        #[arg(long)]
+        config: Option<String>,
+    },
 }
 
 /// Enumerates the types of entities that can be added to configuration files.
@@ -139,7 +518,7 @@ pub enum AddCommands {
         /// Specifies the GitHub repository where the tool is hosted.
         #[arg(long)]
         repo: Option<String>,
-        /// Release tag (required for github source).
+        /// Release tag for github sources; without it, `latest` installs the latest release.
         /// Specific version tag to download from the repository.
         #[arg(long)]
         tag: Option<String>,
@@ -192,6 +571,19 @@ pub enum AddCommands {
         /// Allows selective installation of specific font weights or styles.
         #[arg(long, help = "Only install specific sub-fonts (e.g., 'regular mono bold').", value_name = "SUB_FONT_NAMES", num_args(1..))]
         install_only: Vec<String>,
+        /// Nerd Fonts variant to install (e.g., "mono", "propo-bold", "regular").
+        #[arg(long)]
+        variant: Option<String>,
+        /// Install the font for every user of the machine (`scope: system`, needs sudo).
+        #[arg(long)]
+        system: bool,
+    },
+    /// Add or update several tools at once from a tools.yaml snippet.
+    /// The snippet is a list of tool entries or a document with a `tools:` list.
+    Tools {
+        /// File holding the snippet, or `-` to read it from stdin.
+        #[arg(long, value_name = "PATH")]
+        from_file: String,
     },
     /// Add a new setting to settings.yaml configuration (currently macOS only).
     /// System settings are applied using macOS defaults system.
@@ -255,4 +647,10 @@ pub enum RemoveCommands {
         /// Key of the setting
         key: String,
     },
+
+    /// Unload and delete a launch agent
+    LaunchAgent {
+        /// Label of the launch agent to remove
+        label: String,
+    },
 }
@@ -108,3 +108,135 @@ impl fmt::Display for ValueType {
         }
     }
 }
+
+/// Output formats of `setup-devbox prompt-status`, one per supported prompt.
+#[derive(Debug, Clone, Copy)]
+pub enum PromptFormat {
+    Starship, // Plain text for a starship `custom` module
+    P10k,     // zsh prompt escapes for `p10k segment -e`
+}
+
+impl FromStr for PromptFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "starship" => Ok(PromptFormat::Starship),
+            "p10k" | "powerlevel10k" => Ok(PromptFormat::P10k),
+            _ => Err(format!(
+                "Invalid prompt format '{s}'. Must be one of: starship, p10k"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for PromptFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PromptFormat::Starship => write!(f, "starship"),
+            PromptFormat::P10k => write!(f, "p10k"),
+        }
+    }
+}
+
+/// What `setup-devbox list` shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListKind {
+    Tools,    // Installed tools from the state file
+    Fonts,    // Installed fonts from the state file
+    Settings, // Applied settings from the state file
+    Aliases,  // Aliases of shellrc.yaml (aliases are not recorded in the state)
+}
+
+impl FromStr for ListKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "tools" | "tool" => Ok(ListKind::Tools),
+            "fonts" | "font" => Ok(ListKind::Fonts),
+            "settings" | "setting" => Ok(ListKind::Settings),
+            "aliases" | "alias" => Ok(ListKind::Aliases),
+            _ => Err(format!(
+                "Invalid list type '{s}'. Must be one of: tools, fonts, settings, aliases"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ListKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ListKind::Tools => write!(f, "tools"),
+            ListKind::Fonts => write!(f, "fonts"),
+            ListKind::Settings => write!(f, "settings"),
+            ListKind::Aliases => write!(f, "aliases"),
+        }
+    }
+}
+
+/// Output formats of the commands that can print their result as a document (`--output`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text, // Human-readable log lines, tables and summaries
+    Json, // One JSON document on stdout describing the result
+    Yaml, // The same document as YAML
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "yaml" => Ok(OutputFormat::Yaml),
+            _ => Err(format!(
+                "Invalid output format '{s}'. Must be one of: text, json, yaml"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OutputFormat::Text => write!(f, "text"),
+            OutputFormat::Json => write!(f, "json"),
+            OutputFormat::Yaml => write!(f, "yaml"),
+        }
+    }
+}
+
+/// When colored output is used (`--color`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+    Auto,   // Colors on a terminal, unless NO_COLOR is set
+    Always, // Colors even when piped to a file or CI log
+    Never,  // Plain text
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(format!(
+                "Invalid color choice '{s}'. Must be one of: auto, always, never"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ColorChoice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ColorChoice::Auto => write!(f, "auto"),
+            ColorChoice::Always => write!(f, "always"),
+            ColorChoice::Never => write!(f, "never"),
+        }
+    }
+}
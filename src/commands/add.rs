@@ -10,31 +10,41 @@
 //! 2. Locate and update/add items programmatically
 //! 3. Serialize back to YAML with consistent formatting
 //!
-//! This ensures valid YAML output and eliminates formatting bugs at the cost of
-//! not preserving custom formatting or comments.
+//! This ensures valid YAML output and eliminates formatting bugs. List items (tools, fonts,
+//! aliases) are then spliced into the original text, so comments and the order of the rest
+//! of the file survive; when the file's layout is not understood, or the spliced text would
+//! not parse back to the same document, the serialized document is written instead.
 
 use crate::cli::cmd_enums::AddCommands;
 use crate::commands::add_interactive::{
-    prompt_for_alias, prompt_for_font, prompt_for_setting, prompt_for_tool,
+    confirm_update_existing, prompt_for_alias, prompt_for_font, prompt_for_setting, prompt_for_tool,
 };
-use crate::now;
+use crate::commands::load_state_or_exit;
+use crate::commands::now;
+use crate::config::{migrate, strict};
+use crate::core::{output, protected};
 use crate::schemas::path_resolver::PathResolver;
 use crate::schemas::tools_enums::SourceType;
 use crate::schemas::{
-    config_manager::ConfigurationManager, fonts::FontEntry, os_settings::SettingEntry,
-    shell_configuration::AliasEntry, tools_types::ToolEntry,
+    config_manager::ConfigurationManager,
+    fonts::{FontEntry, FontScope},
+    os_settings::SettingEntry,
+    shell_configuration::AliasEntry,
+    tools_types::{Hook, ToolEntry},
 };
 use crate::{log_debug, log_error, log_info, log_warn};
 use colored::Colorize;
 use serde::{Serialize, de::DeserializeOwned};
 use serde_yaml::{self, Value};
 use std::fs;
+use std::io::{self, Read};
 use std::path::PathBuf;
 
 /// Entry point for the 'add' subcommand
 ///
-/// Dispatches to specific add functions based on the command type.
-pub fn run(add_type: AddCommands) {
+/// Dispatches to specific add functions based on the command type. Configuration files are
+/// located through `paths`, so `--config`/`--state` overrides are honored.
+pub fn run(paths: &PathResolver, add_type: AddCommands) {
     match add_type {
         AddCommands::Tool {
             name,
@@ -57,6 +67,7 @@ pub fn run(add_type: AddCommands) {
                 prompt_for_tool(name, version, source, url, repo, tag);
 
             add_tool(
+                paths,
                 final_name,
                 final_version,
                 final_source,
@@ -78,6 +89,8 @@ pub fn run(add_type: AddCommands) {
             repo,
             tag,
             install_only,
+            variant,
+            system,
         } => {
             log_debug!("[SDB] 'Add Font' subcommand detected.");
 
@@ -86,14 +99,22 @@ pub fn run(add_type: AddCommands) {
                 prompt_for_font(name, version, repo, tag);
 
             add_font(
+                paths,
                 final_name,
                 final_version,
                 source,
                 final_repo,
                 final_tag,
                 install_only,
+                variant,
+                system.then_some(FontScope::System),
             );
         }
+        AddCommands::Tools { from_file } => {
+            log_debug!("[SDB] 'Add Tools' subcommand detected.");
+
+            add_tools_from_file(paths, &from_file);
+        }
         AddCommands::Setting {
             domain,
             key,
@@ -105,14 +126,20 @@ pub fn run(add_type: AddCommands) {
             let (final_domain, final_key, final_value, final_type) =
                 prompt_for_setting(domain, key, value, value_type);
 
-            add_setting(final_domain, final_key, final_value, final_type.to_string());
+            add_setting(
+                paths,
+                final_domain,
+                final_key,
+                final_value,
+                final_type.to_string(),
+            );
         }
         AddCommands::Alias { name, value } => {
             log_debug!("[SDB] 'Add Alias' subcommand detected.");
 
             let (final_name, final_value) = prompt_for_alias(name, value);
 
-            add_alias(final_name, final_value);
+            add_alias(paths, final_name, final_value);
         }
     }
 }
@@ -142,7 +169,7 @@ impl ConfigurationUpdater {
     pub fn new(paths: &PathResolver) -> Result<Self, String> {
         log_debug!("[Updater] Initializing ConfigurationUpdater...");
 
-        let config_base = paths.configs_dir();
+        let config_base = paths.config_file_dir();
 
         log_debug!(
             "[Updater] Using config directory: {}",
@@ -242,7 +269,7 @@ impl ConfigurationUpdater {
             item.get(item_key_trimmed).and_then(|v| v.as_str()) == Some(item_identifier)
         });
 
-        let was_update = if let Some(idx) = existing_idx {
+        let (was_update, written_item) = if let Some(idx) = existing_idx {
             // Item exists - perform deep merge with existing data
             log_info!(
                 "[SDB:Add] Existing item '{}' found. Performing deep merge...",
@@ -262,8 +289,8 @@ impl ConfigurationUpdater {
             config_sanitization(&mut merged);
 
             // Replace the existing item with merged version
-            items[idx] = merged;
-            true // Indicates this was an update operation
+            items[idx] = merged.clone();
+            (true, merged) // Indicates this was an update operation
         } else {
             // Item doesn't exist - add as new
             log_info!(
@@ -277,13 +304,32 @@ impl ConfigurationUpdater {
             // Clean up null values before adding
             config_sanitization(&mut new_value);
 
-            items.push(new_value);
-            false // Indicates this was an add operation
+            items.push(new_value.clone());
+            (false, new_value) // Indicates this was an add operation
         };
 
-        // Write back with consistent formatting
-        let output = serde_yaml::to_string(&doc)
-            .map_err(|e| format!("Failed to serialize config: {}", e.to_string().red()))?;
+        // Keep the rest of the file as written when the item can be spliced into it;
+        // otherwise write back with consistent formatting
+        let spliced = splice_list_item(
+            &content,
+            section_name,
+            item_key_trimmed,
+            item_identifier,
+            &written_item,
+        )
+        .filter(|text| serde_yaml::from_str::<Value>(text).is_ok_and(|parsed| parsed == doc));
+        let output = match spliced {
+            Some(text) => text,
+            None => {
+                log_debug!(
+                    "[Updater] Could not splice '{}' into {}; rewriting the file",
+                    item_identifier,
+                    filename
+                );
+                serde_yaml::to_string(&doc)
+                    .map_err(|e| format!("Failed to serialize config: {}", e.to_string().red()))?
+            }
+        };
 
         fs::write(&config_path, output).map_err(|e| {
             format!(
@@ -297,6 +343,44 @@ impl ConfigurationUpdater {
         Ok(was_update)
     }
 
+    /// Counts the list items in a YAML configuration file whose `item_key` is `item_identifier`
+    ///
+    /// # Returns
+    /// * `Result<usize, String>` - Number of matching items (0 when the file does not exist)
+    fn count_list_items(
+        &self,
+        filename: &str,
+        section_key: &str,
+        item_key: &str,
+        item_identifier: &str,
+    ) -> Result<usize, String> {
+        let config_path = self.get_config_path(filename);
+        if !config_path.exists() {
+            return Ok(0);
+        }
+        let content = fs::read_to_string(&config_path)
+            .map_err(|e| format!("Failed to read {}: {}", filename.red(), e.to_string().red()))?;
+        let doc: Value = serde_yaml::from_str(&content).map_err(|e| {
+            format!(
+                "Failed to parse {}: {}",
+                filename.red(),
+                e.to_string().red()
+            )
+        })?;
+        let item_key = item_key.trim_end_matches(':');
+        Ok(doc
+            .get(section_key.trim_end_matches(':'))
+            .and_then(Value::as_sequence)
+            .map_or(0, |items| {
+                items
+                    .iter()
+                    .filter(|item| {
+                        item.get(item_key).and_then(Value::as_str) == Some(item_identifier)
+                    })
+                    .count()
+            }))
+    }
+
     /// Specialized handler for macOS settings due to nested structure
     ///
     /// macOS settings have a nested structure under `settings.macos` which requires
@@ -413,6 +497,7 @@ impl ConfigurationUpdater {
 /// the configuration file.
 ///
 /// # Arguments
+/// * `paths` - Resolved configuration and state locations
 /// * `name` - Name of the tool
 /// * `version` - Version of the tool
 /// * `source` - Source type ("github", "url", etc.)
@@ -427,6 +512,7 @@ impl ConfigurationUpdater {
 /// * `config_paths` - Paths to configuration files for this tool
 #[allow(clippy::too_many_arguments)]
 pub fn add_tool(
+    paths: &PathResolver,
     name: String,
     version: String,
     source: SourceType,
@@ -442,22 +528,13 @@ pub fn add_tool(
 ) {
     log_info!("[SDB::Add::Tool] Preparing to add tool: {}...", name.cyan());
 
-    // Initialize path resolver for configuration directory access
-    let paths = PathResolver::new(None, None).unwrap_or_else(|e| {
-        log_error!(
-            "[SDB::Add::Tool] Failed to initialize path resolver: {}",
-            e.to_string().red()
-        );
-        std::process::exit(1);
-    });
-
     // Initialize configuration updater
-    let updater = ConfigurationUpdater::new(&paths).unwrap_or_else(|e| {
+    let updater = ConfigurationUpdater::new(paths).unwrap_or_else(|e| {
         log_error!(
             "[SDB::Add::Tool] Failed to initialize updater: {}",
             e.to_string().red()
         );
-        std::process::exit(1);
+        exit_failed("tool", &name, vec![e]);
     });
 
     // Create new tool entry from provided parameters
@@ -471,69 +548,336 @@ pub fn add_tool(
         rename_to,
         options,
         executable_path_after_extract,
-        post_installation_hooks,
+        post_installation_hooks: post_installation_hooks
+            .map(|hooks| hooks.into_iter().map(Hook::Command).collect()),
         configuration_manager: ConfigurationManager {
             enabled: enable_config_manager,
-            tools_configuration_paths: config_paths,
+            tools_configuration_paths: config_paths.into_iter().map(Into::into).collect(),
         },
+        smoke_test: None,
+        streaming: None,
+        delta_updates: None,
+        checksum: None,
+        executable_detection: None,
+        exclude_from_search: None,
+        assets: None,
+        headers: None,
+        basic_auth: None,
+        url_command: None,
+        http_method: None,
+        version_check: None,
+        phase: None,
+        shim: None,
+        requires_license_acceptance: None,
+        requires_env: None,
+        notes: None,
+        owner: None,
+        protected: None,
+        only_on: None,
+        profiles: None,
+        rollout: None,
+        acknowledge_dangerous: None,
+        permissions: None,
+        preset: None,
+        path: None,
+        binary: None,
+        app_dir: None,
+        optimize_binary: None,
+        app_bundle: None,
+        wasm_runtime: None,
+        platforms: None,
     };
 
-    // Validate tool restrictions based on source type
-    if let Err(e) = validate_tool_restrictions(&new_tool) {
+    // Changing a protected tool is refused before its entry is touched; the change itself is
+    // recorded when `now` updates the tool.
+    let state = load_state_or_exit(&paths.state_file().to_path_buf());
+    if protected::is_protected(&state, &name) && !protected::is_forced() {
+        log_error!(
+            "[SDB::Add::Tool] '{}' is protected; to change it, pass --force-protected --reason <TEXT>",
+            name.cyan()
+        );
+        exit_failed(
+            "tool",
+            &name,
+            vec!["protected; to change it, pass --force-protected --reason <TEXT>".to_string()],
+        );
+    }
+
+    // Validate tool restrictions based on source type, then the entry as `now` would
+    if let Err(e) = validate_tool_restrictions(&new_tool)
+        .and_then(|()| new_tool.validate().map_err(|e| e.to_string()))
+    {
         log_error!(
             "[SDB::Add::Tool] Validation failed for tool {}: {}",
             name.cyan(),
             e
         );
-        std::process::exit(1);
+        exit_failed("tool", &name, vec![e]);
     }
 
-    // Update or add the tool in configuration
-    match updater.update_or_add_list_item("tools.yaml", "tools:", "name:", &name, &new_tool) {
-        Ok(is_update) => {
-            log_info!(
-                "[SDB::Add::Tool] Successfully {} tool '{}'",
-                if is_update { "updated" } else { "added" },
-                name.cyan()
-            );
+    // An existing entry is only changed once the user agrees to update it in place
+    match updater.count_list_items("tools.yaml", "tools:", "name:", &name) {
+        Ok(0) => {}
+        Ok(count) => {
+            if count > 1 {
+                log_warn!(
+                    "[SDB::Add::Tool] tools.yaml has {} entries named '{}'; only the first is updated",
+                    count,
+                    name.cyan()
+                );
+            }
+            if !confirm_update_existing("Tool", &name) {
+                log_info!(
+                    "[SDB::Add::Tool] Aborted; tools.yaml was left unchanged for '{}'",
+                    name.cyan()
+                );
+                finish(
+                    paths,
+                    AddReport::new("tool", vec![AddedEntry::new(&name, "skipped", &new_tool)]),
+                );
+                return;
+            }
         }
         Err(e) => {
-            log_error!("[SDB::Add::Tool] Failed to update config: {}", e);
-            std::process::exit(1);
+            log_error!("[SDB::Add::Tool] Failed to read config: {}", e);
+            exit_failed("tool", &name, vec![e]);
         }
     }
 
+    // Update or add the tool in configuration
+    let action =
+        match updater.update_or_add_list_item("tools.yaml", "tools:", "name:", &name, &new_tool) {
+            Ok(is_update) => {
+                log_info!(
+                    "[SDB::Add::Tool] Successfully {} tool '{}'",
+                    if is_update { "updated" } else { "added" },
+                    name.cyan()
+                );
+                if is_update { "updated" } else { "added" }
+            }
+            Err(e) => {
+                log_error!("[SDB::Add::Tool] Failed to update config: {}", e);
+                exit_failed("tool", &name, vec![e]);
+            }
+        };
+
     // Apply changes immediately
-    run_now_command();
+    finish(
+        paths,
+        AddReport::new("tool", vec![AddedEntry::new(&name, action, &new_tool)]),
+    );
+}
+
+/// Adds or updates generated tool entries in tools.yaml without applying them.
+///
+/// Used by commands that produce entries in bulk (e.g. `import apps`).
+///
+/// # Returns
+/// * `Result<usize, String>` - Number of entries written, or the first error
+pub(crate) fn write_tool_entries(
+    paths: &PathResolver,
+    tools: &[ToolEntry],
+) -> Result<usize, String> {
+    let updater = ConfigurationUpdater::new(paths)?;
+    for tool in tools {
+        updater.update_or_add_list_item("tools.yaml", "tools:", "name:", &tool.name, tool)?;
+    }
+    Ok(tools.len())
+}
+
+/// Adds or updates every tool entry of a tools.yaml snippet
+///
+/// All entries are validated before any of them is written, so a snippet with one bad entry
+/// leaves tools.yaml untouched. Existing entries are updated in place once confirmed.
+///
+/// # Arguments
+/// * `paths` - Resolved configuration and state locations
+/// * `source` - Path of the snippet, or `-` for stdin
+pub fn add_tools_from_file(paths: &PathResolver, source: &str) {
+    let origin = if source == "-" { "stdin" } else { source };
+    log_info!(
+        "[SDB::Add::Tools] Reading tool entries from {}...",
+        origin.cyan()
+    );
+
+    let text = if source == "-" {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text).map(|_| text)
+    } else {
+        fs::read_to_string(PathResolver::expand_tilde(source))
+    }
+    .unwrap_or_else(|e| {
+        log_error!("[SDB::Add::Tools] Failed to read {}: {}", origin, e);
+        exit_failed(
+            "tools",
+            origin,
+            vec![format!("failed to read {origin}: {e}")],
+        );
+    });
+    let tools = parse_tool_snippet(&text).unwrap_or_else(|e| {
+        log_error!("[SDB::Add::Tools] Invalid snippet in {}: {}", origin, e);
+        exit_failed("tools", origin, vec![e]);
+    });
+    if tools.is_empty() {
+        log_warn!("[SDB::Add::Tools] {} has no tool entries", origin);
+        return;
+    }
+
+    // Check every entry before touching tools.yaml
+    let state = load_state_or_exit(&paths.state_file().to_path_buf());
+    let mut problems = Vec::new();
+    for (index, tool) in tools.iter().enumerate() {
+        if let Err(e) = validate_tool_restrictions(tool)
+            .and_then(|()| tool.validate().map_err(|e| e.to_string()))
+        {
+            problems.push(format!("{}: {e}", tool.name));
+        }
+        if tools[..index].iter().any(|other| other.name == tool.name) {
+            problems.push(format!("{}: listed more than once", tool.name));
+        }
+        if protected::is_protected(&state, &tool.name) && !protected::is_forced() {
+            problems.push(format!(
+                "{}: protected; to change it, pass --force-protected --reason <TEXT>",
+                tool.name
+            ));
+        }
+    }
+    if !problems.is_empty() {
+        for problem in &problems {
+            log_error!("[SDB::Add::Tools] {}", problem);
+        }
+        log_error!(
+            "[SDB::Add::Tools] {} problem(s) found; tools.yaml was left unchanged",
+            problems.len()
+        );
+        exit_failed("tools", origin, problems);
+    }
+
+    let updater = ConfigurationUpdater::new(paths).unwrap_or_else(|e| {
+        log_error!("[SDB::Add::Tools] Failed to initialize updater: {}", e);
+        exit_failed("tools", origin, vec![e]);
+    });
+
+    let (mut added, mut updated) = (0, 0);
+    let mut entries = Vec::new();
+    for tool in &tools {
+        match updater.count_list_items("tools.yaml", "tools:", "name:", &tool.name) {
+            Ok(0) => {}
+            Ok(_) if confirm_update_existing("Tool", &tool.name) => {}
+            Ok(_) => {
+                log_info!(
+                    "[SDB::Add::Tools] Skipping '{}'; its entry was left unchanged",
+                    tool.name.cyan()
+                );
+                entries.push(AddedEntry::new(&tool.name, "skipped", tool));
+                continue;
+            }
+            Err(e) => {
+                log_error!("[SDB::Add::Tools] Failed to read config: {}", e);
+                exit_failed("tools", origin, vec![e]);
+            }
+        }
+        match updater.update_or_add_list_item("tools.yaml", "tools:", "name:", &tool.name, tool) {
+            Ok(true) => {
+                updated += 1;
+                entries.push(AddedEntry::new(&tool.name, "updated", tool));
+            }
+            Ok(false) => {
+                added += 1;
+                entries.push(AddedEntry::new(&tool.name, "added", tool));
+            }
+            Err(e) => {
+                log_error!(
+                    "[SDB::Add::Tools] Failed to write '{}': {}",
+                    tool.name.red(),
+                    e
+                );
+                exit_failed("tools", origin, vec![format!("{}: {e}", tool.name)]);
+            }
+        }
+    }
+
+    log_info!(
+        "[SDB::Add::Tools] Added {} and updated {} tool(s) from {}",
+        added.to_string().green(),
+        updated.to_string().green(),
+        origin.cyan()
+    );
+    finish(paths, AddReport::new("tools", entries));
+}
+
+/// Parses a tools.yaml snippet: a list of tool entries, a single entry, or a document with
+/// a `tools:` list.
+///
+/// Deprecated field names are migrated, and unknown keys are reported like in tools.yaml
+/// (rejected in strict mode, otherwise dropped with a warning).
+fn parse_tool_snippet(text: &str) -> Result<Vec<ToolEntry>, String> {
+    let document: Value = serde_yaml::from_str(text).map_err(|e| e.to_string())?;
+    let tools = match document {
+        Value::Null => return Ok(Vec::new()),
+        Value::Mapping(mut mapping) if mapping.contains_key("tools") => {
+            mapping.remove("tools").unwrap_or(Value::Null)
+        }
+        entry @ Value::Mapping(_) => Value::Sequence(vec![entry]),
+        list => list,
+    };
+    let mut wrapped = Value::Mapping(serde_yaml::Mapping::from_iter([(
+        Value::from("tools"),
+        tools,
+    )]));
+    migrate::migrate_value("tools", &mut wrapped);
+
+    let (snippet, unknown_keys): (ToolSnippet, _) =
+        strict::from_value_tracking_unknown(wrapped, "tools").map_err(|e| e.to_string())?;
+    if strict::is_strict() && !unknown_keys.is_empty() {
+        let keys: Vec<String> = unknown_keys.iter().map(ToString::to_string).collect();
+        return Err(format!("{} (strict mode)", keys.join(", ")));
+    }
+    for key in &unknown_keys {
+        log_warn!(
+            "[SDB::Add::Tools] {} is dropped (use {} to reject unknown keys)",
+            key.to_string().yellow(),
+            "--strict".cyan()
+        );
+    }
+    Ok(snippet.tools)
+}
+
+/// The tool entries of a snippet.
+#[derive(serde::Deserialize)]
+struct ToolSnippet {
+    tools: Vec<ToolEntry>,
 }
 
 /// Adds or updates a font configuration in the fonts.yaml file
 ///
 /// # Arguments
+/// * `paths` - Resolved configuration and state locations
 /// * `name` - Name of the font
 /// * `version` - Version of the font
 /// * `source` - Source type (typically "github")
 /// * `repo` - GitHub repository containing the font
-/// * `tag` - GitHub tag/version
+/// * `tag` - GitHub tag (`None` installs the latest release)
 /// * `install_only` - Specific font files to install (empty for all)
+/// * `variant` - Nerd Fonts variant to install (e.g. `mono-regular`)
+/// * `scope` - Font directory to install to (`None` for the user's)
+#[allow(clippy::too_many_arguments)]
 pub fn add_font(
+    paths: &PathResolver,
     name: String,
     version: String,
     source: String,
     repo: String,
-    tag: String,
+    tag: Option<String>,
     install_only: Vec<String>,
+    variant: Option<String>,
+    scope: Option<FontScope>,
 ) {
     log_info!("[SDB::Add::Font] Preparing to add font: {}...", name.cyan());
 
-    let paths = PathResolver::new(None, None).unwrap_or_else(|e| {
-        log_error!("[SDB::Add::Font] Failed to initialize path resolver: {}", e);
-        std::process::exit(1);
-    });
-
-    let updater = ConfigurationUpdater::new(&paths).unwrap_or_else(|e| {
+    let updater = ConfigurationUpdater::new(paths).unwrap_or_else(|e| {
         log_error!("[SDB::Add::Font] Failed to initialize updater: {}", e);
-        std::process::exit(1);
+        exit_failed("font", &name, vec![e]);
     });
 
     // Create font entry, converting empty install_only to None
@@ -542,39 +886,57 @@ pub fn add_font(
         version: Some(version),
         source,
         repo: Some(repo),
-        tag: Some(tag),
+        tag,
         install_only: if install_only.is_empty() {
             None
         } else {
             Some(install_only)
         },
+        variant,
+        requires_license_acceptance: None,
+        notes: None,
+        owner: None,
+        scope,
+        profiles: None,
     };
 
-    match updater.update_or_add_list_item("fonts.yaml", "fonts:", "name:", &name, &new_font) {
-        Ok(is_update) => {
-            log_info!(
-                "[SDB::Add::Font] Successfully {} font '{}'",
-                if is_update { "updated" } else { "added" },
-                name.cyan()
-            );
-        }
-        Err(e) => {
-            log_error!("[SDB::Add::Font] Failed to update config: {}", e);
-            std::process::exit(1);
-        }
-    }
+    let action =
+        match updater.update_or_add_list_item("fonts.yaml", "fonts:", "name:", &name, &new_font) {
+            Ok(is_update) => {
+                log_info!(
+                    "[SDB::Add::Font] Successfully {} font '{}'",
+                    if is_update { "updated" } else { "added" },
+                    name.cyan()
+                );
+                if is_update { "updated" } else { "added" }
+            }
+            Err(e) => {
+                log_error!("[SDB::Add::Font] Failed to update config: {}", e);
+                exit_failed("font", &name, vec![e]);
+            }
+        };
 
-    run_now_command();
+    finish(
+        paths,
+        AddReport::new("font", vec![AddedEntry::new(&name, action, &new_font)]),
+    );
 }
 
 /// Adds or updates a macOS system setting in the settings.yaml file
 ///
 /// # Arguments
+/// * `paths` - Resolved configuration and state locations
 /// * `domain` - Settings domain (e.g., "NSGlobalDomain")
 /// * `key` - Setting key within the domain
 /// * `value` - Value to set
 /// * `value_type` - Type of value ("bool", "string", "int", "float")
-pub fn add_setting(domain: String, key: String, value: String, value_type: String) {
+pub fn add_setting(
+    paths: &PathResolver,
+    domain: String,
+    key: String,
+    value: String,
+    value_type: String,
+) {
     let setting_name = format!("{domain}.{key}");
     log_info!(
         "[SDB::Add::Setting] Preparing to add setting: {}...",
@@ -587,20 +949,18 @@ pub fn add_setting(domain: String, key: String, value: String, value_type: Strin
             "[SDB::Add::Setting] Invalid value type '{}'. Supported: bool, string, int, float",
             value_type.cyan()
         );
-        std::process::exit(1);
-    }
-
-    let paths = PathResolver::new(None, None).unwrap_or_else(|e| {
-        log_error!(
-            "[SDB::Add::Setting] Failed to initialize path resolver: {}",
-            e
+        exit_failed(
+            "setting",
+            &setting_name,
+            vec![format!(
+                "invalid value type '{value_type}' (supported: bool, string, int, float)"
+            )],
         );
-        std::process::exit(1);
-    });
+    }
 
-    let updater = ConfigurationUpdater::new(&paths).unwrap_or_else(|e| {
+    let updater = ConfigurationUpdater::new(paths).unwrap_or_else(|e| {
         log_error!("[SDB::Add::Setting] Failed to initialize updater: {}", e);
-        std::process::exit(1);
+        exit_failed("setting", &setting_name, vec![e]);
     });
 
     let new_setting = SettingEntry {
@@ -611,67 +971,78 @@ pub fn add_setting(domain: String, key: String, value: String, value_type: Strin
     };
 
     // Use specialized settings handler due to nested structure
-    match updater.update_or_add_setting(&new_setting) {
+    let action = match updater.update_or_add_setting(&new_setting) {
         Ok(is_update) => {
             log_info!(
                 "[SDB::Add::Setting] Successfully {} setting '{}'",
                 if is_update { "updated" } else { "added" },
                 setting_name.cyan()
             );
+            if is_update { "updated" } else { "added" }
         }
         Err(e) => {
             log_error!("[SDB::Add::Setting] Failed to update config: {}", e);
-            std::process::exit(1);
+            exit_failed("setting", &setting_name, vec![e]);
         }
-    }
+    };
 
-    run_now_command();
+    finish(
+        paths,
+        AddReport::new(
+            "setting",
+            vec![AddedEntry::new(&setting_name, action, &new_setting)],
+        ),
+    );
 }
 
 /// Adds or updates a shell alias in the shellrc.yaml file
 ///
 /// # Arguments
+/// * `paths` - Resolved configuration and state locations
 /// * `name` - Name of the alias
 /// * `value` - Command/value of the alias
-pub fn add_alias(name: String, value: String) {
+pub fn add_alias(paths: &PathResolver, name: String, value: String) {
     log_info!(
         "[SDB::Add::Alias] Preparing to add alias: {}...",
         name.cyan()
     );
 
-    let paths = PathResolver::new(None, None).unwrap_or_else(|e| {
-        log_error!(
-            "[SDB::Add::Alias] Failed to initialize path resolver: {}",
-            e
-        );
-        std::process::exit(1);
-    });
-
-    let updater = ConfigurationUpdater::new(&paths).unwrap_or_else(|e| {
+    let updater = ConfigurationUpdater::new(paths).unwrap_or_else(|e| {
         log_error!("[SDB::Add::Alias] Failed to initialize updater: {}", e);
-        std::process::exit(1);
+        exit_failed("alias", &name, vec![e]);
     });
 
     let new_alias = AliasEntry {
         name: name.clone(),
         value,
+        profiles: None,
     };
 
-    match updater.update_or_add_list_item("shellrc.yaml", "aliases:", "name:", &name, &new_alias) {
+    let action = match updater.update_or_add_list_item(
+        "shellrc.yaml",
+        "aliases:",
+        "name:",
+        &name,
+        &new_alias,
+    ) {
         Ok(is_update) => {
             log_info!(
                 "[SDB::Add::Alias] Successfully {} alias '{}'",
                 if is_update { "updated" } else { "added" },
                 name.cyan()
             );
+            if is_update { "updated" } else { "added" }
         }
         Err(e) => {
             log_error!("[SDB::Add::Alias] Failed to update config: {}", e);
-            std::process::exit(1);
+            exit_failed("alias", &name, vec![e]);
         }
-    }
+    };
 
-    run_now_command();
+    finish(
+        paths,
+        AddReport::new("alias", vec![AddedEntry::new(&name, action, &new_alias)]),
+    );
 }
 
 // ============================================================================
@@ -681,8 +1052,9 @@ pub fn add_alias(name: String, value: String) {
 /// Validates tool configuration based on source type restrictions
 ///
 /// Different source types have different required fields:
-/// - "github" requires both `repo` and `tag`
+/// - "github" requires `repo` (without `tag`, the latest release is installed)
 /// - "url" requires `url`
+/// - "oci" requires `repo`
 ///
 /// # Arguments
 /// * `tool` - Tool entry to validate
@@ -691,19 +1063,28 @@ pub fn add_alias(name: String, value: String) {
 /// * `Result<(), String>` - Ok if valid, error message if invalid
 fn validate_tool_restrictions(tool: &ToolEntry) -> Result<(), String> {
     match tool.source {
-        SourceType::Github => {
-            // GitHub sources require repository and tag information
-            if tool.repo.is_none() || tool.tag.is_none() {
-                return Err("Source is 'github', but requires both 'repo' and
-                    'tag' to be provided"
-                    .to_owned());
-            }
+        // GitHub sources require the repository; the tag defaults to the latest release
+        SourceType::Github if tool.repo.is_none() => {
+            return Err(
+                "Source is 'github', but requires 'repo' (owner/name) to be provided".to_owned(),
+            );
         }
-        SourceType::Url => {
-            // URL sources require a download URL
-            if tool.url.is_none() {
-                return Err("Source is 'url', but requires 'url' to be provided".to_owned());
-            }
+        // OCI sources require the artifact's registry reference
+        SourceType::Oci if tool.repo.is_none() => {
+            return Err(
+                "Source is 'oci', but requires 'repo' (e.g. ghcr.io/org/tool) to be provided"
+                    .to_owned(),
+            );
+        }
+        // PATH entries are declared with their directory in tools.yaml
+        SourceType::PathEntry if tool.path.is_none() => {
+            return Err(
+                "Source 'path-entry' needs a 'path'; declare it in tools.yaml instead".to_owned(),
+            );
+        }
+        // URL sources require a download URL
+        SourceType::Url if tool.url.is_none() => {
+            return Err("Source is 'url', but requires 'url' to be provided".to_owned());
         }
         _ => {
             // Other source types don't have specific restrictions
@@ -726,22 +1107,88 @@ fn is_valid_value_type(value_type: &str) -> bool {
     )
 }
 
+/// Result of an `add` command, printed on stdout with `--output json` or `yaml`.
+#[derive(Serialize)]
+struct AddReport {
+    /// Kind of entries: `tool`, `tools` (from a snippet), `font`, `setting` or `alias`.
+    kind: &'static str,
+    /// Whether every entry was validated and written.
+    success: bool,
+    /// Entries handled, in order.
+    entries: Vec<AddedEntry>,
+    /// Validation and write errors.
+    errors: Vec<String>,
+}
+
+impl AddReport {
+    fn new(kind: &'static str, entries: Vec<AddedEntry>) -> Self {
+        AddReport {
+            kind,
+            success: true,
+            entries,
+            errors: Vec::new(),
+        }
+    }
+}
+
+/// One entry of an [`AddReport`].
+#[derive(Serialize)]
+struct AddedEntry {
+    name: String,
+    /// `added`, `updated` or `skipped` (an existing entry the user chose to keep).
+    action: &'static str,
+    /// The entry as given; an update merges it into the existing one.
+    entry: serde_json::Value,
+}
+
+impl AddedEntry {
+    fn new<T: Serialize>(name: &str, action: &'static str, entry: &T) -> Self {
+        AddedEntry {
+            name: name.to_string(),
+            action,
+            entry: serde_json::to_value(entry).unwrap_or_default(),
+        }
+    }
+}
+
+/// Exits after a failed `add`, printing the errors as the result with `--output json` or `yaml`.
+fn exit_failed(kind: &'static str, name: &str, errors: Vec<String>) -> ! {
+    if output::is_structured() {
+        output::print_document(&AddReport {
+            kind,
+            success: false,
+            entries: Vec::new(),
+            errors: errors
+                .into_iter()
+                .map(|error| format!("{name}: {error}"))
+                .collect(),
+        });
+    }
+    std::process::exit(1);
+}
+
+/// Applies a completed `add` by running `now`. With `--output json` or `yaml` the result is
+/// printed instead, and applying the change is left to a later `now`.
+fn finish(paths: &PathResolver, report: AddReport) {
+    if output::is_structured() {
+        output::print_document(&report);
+    } else if report.entries.iter().any(|entry| entry.action != "skipped") {
+        run_now_command(paths);
+    }
+}
+
 /// Executes the 'setup-devbox now' command to apply configuration changes
 ///
 /// This ensures that changes made by add commands are immediately applied
 /// to the system rather than waiting for manual execution.
-fn run_now_command() {
+fn run_now_command(paths: &PathResolver) {
     log_info!(
         "[SDB::Now] Running '{}' to apply changes...",
         "setup-devbox now".cyan()
     );
 
-    match PathResolver::new(None, None) {
-        Ok(paths) => now::run(&paths, false, false),
-        Err(e) => {
-            log_error!("Failed to initialize path resolver: {}", e.red());
-            std::process::exit(1);
-        }
+    if now::run(paths, false, false, false, false, None).is_err() {
+        std::process::exit(1);
     }
 }
 
@@ -791,6 +1238,100 @@ fn deep_merge_values(target: &mut Value, source: &Value) {
     }
 }
 
+/// Splices a list item into the original text of a configuration file.
+///
+/// The item replaces the block of the entry whose `item_key` is `item_identifier` in the
+/// top-level `section` list, or is appended after the list's last entry. Everything else,
+/// comments included, is kept as written.
+///
+/// # Returns
+/// * `Option<String>` - The new file contents, or `None` if the section is not a block list
+fn splice_list_item(
+    content: &str,
+    section: &str,
+    item_key: &str,
+    item_identifier: &str,
+    item: &Value,
+) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let is_blank_or_comment = |line: &str| {
+        let trimmed = line.trim_start();
+        trimmed.is_empty() || trimmed.starts_with('#')
+    };
+    let is_list_item = |line: &str| {
+        let trimmed = line.trim_start();
+        trimmed.starts_with("- ") || trimmed == "-"
+    };
+    let indent_of = |line: &str| line.len() - line.trim_start().len();
+
+    // The section body runs up to the next top-level key
+    let header = format!("{section}:");
+    let start = 1 + lines.iter().position(|line| {
+        !line.starts_with(char::is_whitespace)
+            && line.split(" #").next().map(str::trim_end) == Some(header.as_str())
+    })?;
+    let end = lines[start..]
+        .iter()
+        .position(|line| !is_blank_or_comment(line) && !line.starts_with(char::is_whitespace))
+        .map_or(lines.len(), |offset| start + offset);
+
+    let body = &lines[start..end];
+    let indent = match body.iter().find(|line| !is_blank_or_comment(line)) {
+        Some(line) if is_list_item(line) => indent_of(line),
+        Some(_) => return None, // A flow list (`[]`) or a mapping
+        None => 2,
+    };
+    let block_starts: Vec<usize> = (start..end)
+        .filter(|&i| is_list_item(lines[i]) && indent_of(lines[i]) == indent)
+        .collect();
+    // Each block ends before the next one, without the blank and comment lines leading into it
+    let blocks: Vec<(usize, usize)> = block_starts
+        .iter()
+        .enumerate()
+        .map(|(n, &block_start)| {
+            let mut block_end = block_starts.get(n + 1).copied().unwrap_or(end);
+            while block_end > block_start + 1 && is_blank_or_comment(lines[block_end - 1]) {
+                block_end -= 1;
+            }
+            (block_start, block_end)
+        })
+        .collect();
+
+    let matches = |&&(block_start, block_end): &&(usize, usize)| {
+        let block: String = lines[block_start..block_end]
+            .iter()
+            .map(|line| format!("{}\n", line.get(indent..).unwrap_or(line.trim_start())))
+            .collect();
+        serde_yaml::from_str::<Value>(&block).is_ok_and(|value| {
+            value
+                .as_sequence()
+                .and_then(|items| items.first())
+                .and_then(|entry| entry.get(item_key))
+                .and_then(Value::as_str)
+                == Some(item_identifier)
+        })
+    };
+    let (replace_start, replace_end) = match blocks.iter().find(matches) {
+        Some(&(block_start, block_end)) => (block_start, block_end),
+        None => {
+            let after = blocks.last().map_or(start, |&(_, block_end)| block_end);
+            (after, after)
+        }
+    };
+
+    let rendered = serde_yaml::to_string(&Value::Sequence(vec![item.clone()])).ok()?;
+    let padding = " ".repeat(indent);
+    let mut output: Vec<String> = lines[..replace_start]
+        .iter()
+        .map(|line| line.to_string())
+        .collect();
+    output.extend(rendered.lines().map(|line| format!("{padding}{line}")));
+    output.extend(lines[replace_end..].iter().map(|line| line.to_string()));
+    let mut text = output.join("\n");
+    text.push('\n');
+    Some(text)
+}
+
 /// Recursively remove null values, empty sequences, and empty mappings from YAML
 ///
 /// This function cleans up YAML structures by removing:
@@ -846,3 +1387,116 @@ fn config_sanitization(value: &mut Value) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alias(name: &str, value: &str) -> Value {
+        serde_yaml::to_value(AliasEntry {
+            name: name.to_string(),
+            value: value.to_string(),
+            profiles: None,
+        })
+        .unwrap()
+    }
+
+    const SHELLRC: &str = "\
+# Managed by setup-devbox
+run_commands: []
+aliases:
+  # Listing
+  - name: ll
+    value: ls -la
+
+  # Git
+  - name: gs
+    value: git status
+other: 1
+";
+
+    #[test]
+    fn test_entries_are_written_next_to_custom_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let custom = dir.path().join("team");
+        fs::create_dir_all(&custom).unwrap();
+        fs::write(
+            custom.join("tools.yaml"),
+            "tools:\n  - name: bat\n    source: brew\n",
+        )
+        .unwrap();
+        let paths = PathResolver::new(
+            Some(custom.join("config.yaml").display().to_string()),
+            Some(dir.path().join("state.json").display().to_string()),
+        )
+        .unwrap();
+        assert_eq!(paths.config_file_dir(), custom);
+
+        let rg: ToolEntry =
+            serde_yaml::from_str("name: rg\nsource: github\nrepo: BurntSushi/ripgrep").unwrap();
+        assert_eq!(write_tool_entries(&paths, &[rg]), Ok(1));
+
+        let tools: Value =
+            serde_yaml::from_str(&fs::read_to_string(custom.join("tools.yaml")).unwrap()).unwrap();
+        let names: Vec<&str> = tools["tools"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .filter_map(|tool| tool["name"].as_str())
+            .collect();
+        assert_eq!(names, ["bat", "rg"]);
+    }
+
+    #[test]
+    fn test_splice_list_item_replaces_entry_and_keeps_comments() {
+        let spliced =
+            splice_list_item(SHELLRC, "aliases", "name", "ll", &alias("ll", "lsd -la")).unwrap();
+        assert_eq!(spliced, SHELLRC.replace("value: ls -la", "value: lsd -la"));
+    }
+
+    #[test]
+    fn test_splice_list_item_appends_after_last_entry() {
+        let spliced =
+            splice_list_item(SHELLRC, "aliases", "name", "gd", &alias("gd", "git diff")).unwrap();
+        assert!(
+            spliced
+                .contains("    value: git status\n  - name: gd\n    value: git diff\nother: 1\n")
+        );
+        assert!(spliced.starts_with("# Managed by setup-devbox\n"));
+    }
+
+    #[test]
+    fn test_parse_tool_snippet_accepts_lists_entries_and_documents() {
+        let list = "- name: rg\n  source: brew\n- name: fd\n  source: brew\n";
+        let names = |text: &str| -> Vec<String> {
+            parse_tool_snippet(text)
+                .unwrap()
+                .into_iter()
+                .map(|tool| tool.name)
+                .collect()
+        };
+        assert_eq!(names(list), ["rg", "fd"]);
+        assert_eq!(names("name: rg\nsource: brew\n"), ["rg"]);
+        assert_eq!(names("tools:\n  - name: rg\n    source: brew\n"), ["rg"]);
+        assert!(names("").is_empty());
+        assert!(parse_tool_snippet("- name: rg\n").is_err());
+    }
+
+    #[test]
+    fn test_splice_list_item_rejects_flow_lists() {
+        assert!(
+            splice_list_item(
+                "aliases:\n  []\n",
+                "aliases",
+                "name",
+                "ll",
+                &alias("ll", "ls")
+            )
+            .is_none()
+        );
+        assert!(
+            splice_list_item("aliases: []\n", "aliases", "name", "ll", &alias("ll", "ls"))
+                .is_none()
+        );
+    }
+}
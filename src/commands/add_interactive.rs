@@ -1,6 +1,17 @@
 use crate::cli::type_enums::{SourceType, ValueType};
+use crate::core::output;
+use crate::log_warn;
 use colored::Colorize;
-use dialoguer::{Input, Select};
+use dialoguer::{Confirm, Input, Select};
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ASSUME_YES: AtomicBool = AtomicBool::new(false);
+
+/// Makes `confirm_update_existing` agree without asking for the rest of the run (`add --yes`).
+pub fn configure_assume_yes(yes: bool) {
+    ASSUME_YES.store(yes, Ordering::Relaxed);
+}
 
 /// Prompts the user for tool details, filling in any missing information interactively.
 ///
@@ -29,7 +40,10 @@ pub fn prompt_for_tool(
     Option<String>,
     Option<String>,
 ) {
-    println!("{}", "Interactive Tool Addition".cyan().bold());
+    // The header would break the document printed with `--output json` or `yaml`
+    if !output::is_structured() {
+        println!("{}", "Interactive Tool Addition".cyan().bold());
+    }
 
     // 1. Tool Name
     let tool_name = name.unwrap_or_else(|| {
@@ -50,6 +64,7 @@ pub fn prompt_for_tool(
             SourceType::Go,
             SourceType::Uv,
             SourceType::Url,
+            SourceType::Oci,
         ];
 
         let selection = Select::new()
@@ -86,14 +101,9 @@ pub fn prompt_for_tool(
                         .expect("Failed to read repo"),
                 );
             }
-            if tool_tag.is_none() {
-                // If version is not "latest" and tag is missing, maybe suggest version as tag?
-                // For now, just prompt.
-                let default_tag = if tool_version != "latest" {
-                    format!("v{}", tool_version.trim_start_matches('v'))
-                } else {
-                    "".to_string()
-                };
+            // Without a tag, `latest` installs the latest release at install time
+            if tool_tag.is_none() && tool_version != "latest" {
+                let default_tag = format!("v{}", tool_version.trim_start_matches('v'));
 
                 tool_tag = Some(
                     Input::new()
@@ -104,15 +114,21 @@ pub fn prompt_for_tool(
                 );
             }
         }
-        SourceType::Url => {
-            if tool_url.is_none() {
-                tool_url = Some(
-                    Input::new()
-                        .with_prompt("Download URL")
-                        .interact_text()
-                        .expect("Failed to read URL"),
-                );
-            }
+        SourceType::Oci if tool_repo.is_none() => {
+            tool_repo = Some(
+                Input::new()
+                    .with_prompt("Registry reference (e.g., ghcr.io/org/tool)")
+                    .interact_text()
+                    .expect("Failed to read repo"),
+            );
+        }
+        SourceType::Url if tool_url.is_none() => {
+            tool_url = Some(
+                Input::new()
+                    .with_prompt("Download URL")
+                    .interact_text()
+                    .expect("Failed to read URL"),
+            );
         }
         _ => {}
     }
@@ -133,8 +149,10 @@ pub fn prompt_for_font(
     version: Option<String>,
     repo: Option<String>,
     tag: Option<String>,
-) -> (String, String, String, String) {
-    println!("{}", "Interactive Font Addition".cyan().bold());
+) -> (String, String, String, Option<String>) {
+    if !output::is_structured() {
+        println!("{}", "Interactive Font Addition".cyan().bold());
+    }
 
     let font_name = name.unwrap_or_else(|| {
         Input::new()
@@ -159,18 +177,15 @@ pub fn prompt_for_font(
             .expect("Failed to read version")
     });
 
-    let font_tag = tag.unwrap_or_else(|| {
-        let default_tag = if font_version != "latest" {
-            format!("v{}", font_version.trim_start_matches('v'))
-        } else {
-            "".to_string()
-        };
-
-        Input::new()
-            .with_prompt("Release Tag")
-            .with_initial_text(default_tag)
-            .interact_text()
-            .expect("Failed to read tag")
+    // Without a tag, `latest` installs the latest release at install time
+    let font_tag = tag.or_else(|| {
+        (font_version != "latest").then(|| {
+            Input::new()
+                .with_prompt("Release Tag")
+                .with_initial_text(format!("v{}", font_version.trim_start_matches('v')))
+                .interact_text()
+                .expect("Failed to read tag")
+        })
     });
 
     (font_name, font_version, font_repo, font_tag)
@@ -183,7 +198,9 @@ pub fn prompt_for_setting(
     value: Option<String>,
     value_type: Option<ValueType>,
 ) -> (String, String, String, ValueType) {
-    println!("{}", "Interactive Setting Addition".cyan().bold());
+    if !output::is_structured() {
+        println!("{}", "Interactive Setting Addition".cyan().bold());
+    }
 
     let setting_domain = domain.unwrap_or_else(|| {
         Input::new()
@@ -227,7 +244,9 @@ pub fn prompt_for_setting(
 
 /// Prompts the user for alias details.
 pub fn prompt_for_alias(name: Option<String>, value: Option<String>) -> (String, String) {
-    println!("{}", "Interactive Alias Addition".cyan().bold());
+    if !output::is_structured() {
+        println!("{}", "Interactive Alias Addition".cyan().bold());
+    }
 
     let alias_name = name.unwrap_or_else(|| {
         Input::new()
@@ -245,3 +264,33 @@ pub fn prompt_for_alias(name: Option<String>, value: Option<String>) -> (String,
 
     (alias_name, alias_value)
 }
+
+/// Asks whether an entry already present in the configuration should be updated in place.
+///
+/// With `--yes` the entry is updated without asking. Without it and without a terminal to ask
+/// on (scripts, `--from-file -`), the update is refused and the entry left unchanged.
+///
+/// # Arguments
+/// * `kind` - Kind of entry, for the prompt (e.g. "Tool")
+/// * `name` - Name of the existing entry
+pub fn confirm_update_existing(kind: &str, name: &str) -> bool {
+    if ASSUME_YES.load(Ordering::Relaxed) {
+        return true;
+    }
+    if !std::io::stdin().is_terminal() {
+        log_warn!(
+            "[SDB::Add] {} '{}' already exists; pass --yes to update it without a terminal",
+            kind,
+            name.cyan()
+        );
+        return false;
+    }
+    Confirm::new()
+        .with_prompt(format!(
+            "{kind} '{}' already exists. Update it in place?",
+            name.cyan()
+        ))
+        .default(true)
+        .interact()
+        .unwrap_or(false)
+}
@@ -0,0 +1,136 @@
+//! # Bootstrap Script Command Implementation
+//!
+//! Prints the "new laptop" flow as a script to paste into a fresh machine's terminal: it
+//! downloads the latest setup-devbox release for the platform into `~/bin`, clones (or
+//! pulls) the dotfiles repository holding the configuration, and runs `now` against it.
+
+use crate::core::platform::single_quote;
+use crate::log_debug;
+use crate::schemas::path_resolver::PathResolver;
+
+/// Releases page of setup-devbox; `latest/download/<asset>` redirects to the newest asset.
+const RELEASES_URL: &str = "https://github.com/kodelint/setup-devbox/releases";
+
+/// Entry point for the `bootstrap-script` subcommand.
+pub fn run(repo: &str, branch: Option<&str>, dir: Option<&str>, one_liner: bool) {
+    log_debug!("[SDB::BootstrapScript] Entering bootstrap_script::run()");
+    let statements = statements(repo, branch, dir);
+    if one_liner {
+        println!("sh -c {}", single_quote(&statements.join("; ")));
+    } else {
+        println!("#!/bin/sh");
+        println!(
+            "# Installs setup-devbox, fetches the configuration from {repo} and provisions this machine."
+        );
+        for statement in statements {
+            println!("{statement}");
+        }
+    }
+}
+
+/// The script's statements, each complete on one line so they can be joined into a one-liner.
+fn statements(repo: &str, branch: Option<&str>, dir: Option<&str>) -> Vec<String> {
+    // Without `--dir`, the configuration is cloned where setup-devbox looks for it by default
+    let dir = match dir {
+        None => PathResolver::configs_dir_shell("dir"),
+        Some(dir) => match dir.strip_prefix("~/") {
+            Some(rest) => vec![format!("dir=\"$HOME\"/{}", single_quote(rest))],
+            None => vec![format!("dir={}", single_quote(dir))],
+        },
+    };
+    let branch = branch
+        .map(|branch| format!(" --branch {}", single_quote(branch)))
+        .unwrap_or_default();
+
+    let mut statements = vec![
+        "set -eu".to_string(),
+        "case \"$(uname -s)\" in Darwin) ;; *) echo \"setup-devbox releases are built for macOS; \
+         on other systems run: cargo install --git https://github.com/kodelint/setup-devbox\" >&2; \
+         exit 1 ;; esac"
+            .to_string(),
+        "case \"$(uname -m)\" in arm64|aarch64) arch=aarch64 ;; x86_64) arch=x86_64 ;; \
+         *) echo \"unsupported architecture: $(uname -m)\" >&2; exit 1 ;; esac"
+            .to_string(),
+        "mkdir -p \"$HOME/bin\"".to_string(),
+        format!(
+            "curl -fsSL \"{RELEASES_URL}/latest/download/setup-devbox-macos-$arch\" -o \"$HOME/bin/setup-devbox\""
+        ),
+        "chmod +x \"$HOME/bin/setup-devbox\"".to_string(),
+        "export PATH=\"$HOME/bin:$PATH\"".to_string(),
+    ];
+    statements.extend(dir);
+    statements.extend([
+        format!(
+            "if [ -d \"$dir/.git\" ]; then git -C \"$dir\" pull --ff-only; \
+             else git clone{branch} {} \"$dir\"; fi",
+            single_quote(repo)
+        ),
+        "setup-devbox now --config \"$dir/config.yaml\"".to_string(),
+    ]);
+    statements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_statements() {
+        let script = statements("git@github.com:me/dotfiles.git", Some("main"), None).join("\n");
+        assert!(script.contains("dir=\"${SDB_CONFIG_PATH:-${SETUP_DEVBOX_HOME:-}}\""));
+        assert!(
+            script.contains("git clone --branch 'main' 'git@github.com:me/dotfiles.git' \"$dir\"")
+        );
+
+        let custom = statements("https://example.com/it's.git", None, Some("~/dotfiles/sdb"));
+        assert!(custom.contains(&"dir=\"$HOME\"/'dotfiles/sdb'".to_string()));
+        assert!(
+            custom
+                .iter()
+                .any(|s| s.contains("'https://example.com/it'\\''s.git'"))
+        );
+        assert!(custom.iter().all(|s| !s.contains('\n')));
+    }
+
+    #[test]
+    fn test_default_dir_follows_path_resolution() {
+        let home = tempfile::tempdir().unwrap();
+        let resolve = |vars: &[(&str, &str)]| {
+            let script = PathResolver::configs_dir_shell("dir").join("; ");
+            let output = std::process::Command::new("sh")
+                .args(["-c", &format!("set -eu; {script}; printf %s \"$dir\"")])
+                .env_clear()
+                .env("HOME", home.path())
+                .envs(vars.iter().copied())
+                .output()
+                .unwrap();
+            assert!(output.status.success());
+            String::from_utf8(output.stdout).unwrap()
+        };
+        let home_dir = home.path().display();
+
+        assert_eq!(resolve(&[]), format!("{home_dir}/.setup-devbox/configs"));
+        assert_eq!(
+            resolve(&[("SETUP_DEVBOX_HOME", "/srv/devbox")]),
+            "/srv/devbox/configs"
+        );
+        assert_eq!(
+            resolve(&[
+                ("SDB_CONFIG_PATH", "~/sdb"),
+                ("SETUP_DEVBOX_HOME", "/srv/devbox")
+            ]),
+            format!("{home_dir}/sdb/configs")
+        );
+        assert_eq!(
+            resolve(&[("XDG_CONFIG_HOME", "/xdg")]),
+            "/xdg/setup-devbox/configs"
+        );
+        // An existing ~/.setup-devbox keeps its layout
+        fs::create_dir(home.path().join(".setup-devbox")).unwrap();
+        assert_eq!(
+            resolve(&[("XDG_CONFIG_HOME", "/xdg")]),
+            format!("{home_dir}/.setup-devbox/configs")
+        );
+    }
+}
@@ -7,9 +7,13 @@
 //! separated into two tables: "Updates Available" and "Manual Check Required".
 
 use crate::config::load_single_config;
+use crate::core::metadata_cache;
 use crate::engine::installers::factory::InstallerFactory;
+use crate::engine::installers::github;
 use crate::log_info;
 use crate::schemas::path_resolver::PathResolver;
+use crate::schemas::tools_enums::SourceType;
+use crate::state::run_summary::RunSummary;
 use colored::Colorize;
 use prettytable::{Cell, Row, Table};
 
@@ -28,7 +32,7 @@ use prettytable::{Cell, Row, Table};
 /// 4. **Check Versions**: For each tool, it performs the following:
 ///    - **Skip "latest" or "N/A"**: If the configured version is "latest" or "N/A", the tool
 ///      is automatically added to the "Manual Check Required" table.
-///    - **Get Latest Version**: It calls the `get_latest_version` method of the appropriate
+///    - **Get Latest Version**: It calls the `resolve_latest_version` method of the appropriate
 ///      installer to find the latest available version.
 ///    - **Version Normalization**: Both the configured and latest versions are normalized
 ///      (e.g., stripping a leading 'v' prefix) for accurate comparison.
@@ -59,16 +63,36 @@ pub fn run() {
     let tools_yaml_path = paths.configs_dir().join("tools.yaml");
     let config_filename = "tools.yaml";
 
-    let parsed_configs = load_single_config(&tools_yaml_path, config_filename);
+    let parsed_configs = load_single_config(&tools_yaml_path, config_filename)
+        .unwrap_or_else(|_| std::process::exit(1));
     let installer_factory = InstallerFactory::new();
 
     if let Some(tools_cfg) = parsed_configs.tools {
         let mut updates_available_rows = Vec::new();
         let mut manual_check_rows = Vec::new();
+        let mut outdated_tools = Vec::new();
+        metadata_cache::configure(paths.cache_dir(), tools_cfg.metadata_cache_ttl, false);
+
+        let github_tools: Vec<_> = tools_cfg
+            .tools
+            .iter()
+            .filter(|tool| tool.source == SourceType::Github)
+            .filter(|tool| tool.version.as_deref().is_some_and(|v| v != "latest"))
+            .collect();
+        github::prefetch_releases(&github_tools, true);
 
         for tool in tools_cfg.tools {
             let current_version = tool.version.as_deref().unwrap_or("N/A").to_string();
 
+            if tool.pinned_commit().is_some() {
+                manual_check_rows.push(Row::new(vec![
+                    Cell::new(&tool.name),
+                    Cell::new(&current_version),
+                    Cell::new("Skipped (pinned to a commit)"),
+                ]));
+                continue;
+            }
+
             if current_version.to_lowercase() == "latest" || current_version == "N/A" {
                 manual_check_rows.push(Row::new(vec![
                     Cell::new(&tool.name),
@@ -81,7 +105,7 @@ pub fn run() {
             let source_type = &tool.source;
 
             if let Some(installer) = installer_factory.get_installer(source_type) {
-                match installer.get_latest_version(&tool) {
+                match installer.resolve_latest_version(&tool) {
                     Ok(latest_version) => {
                         let normalized_current = current_version
                             .strip_prefix('v')
@@ -92,6 +116,7 @@ pub fn run() {
                         if normalized_current != normalized_latest
                             && !latest_version.starts_with("Skipped")
                         {
+                            outdated_tools.push(tool.name.clone());
                             updates_available_rows.push(Row::new(vec![
                                 Cell::new(&tool.name),
                                 Cell::new(&current_version),
@@ -122,6 +147,9 @@ pub fn run() {
             }
         }
 
+        // Shown by `prompt-status` until the tools are updated.
+        RunSummary::record_updates_available(&paths.run_summary_file(), outdated_tools);
+
         if !updates_available_rows.is_empty() {
             println!("\n{}", "Updates Available".bold().green());
             let mut updates_table = Table::new();
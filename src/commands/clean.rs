@@ -0,0 +1,149 @@
+//! # Clean Command Implementation
+//!
+//! Reclaims disk space left behind by provisioning runs: old Homebrew downloads and formula
+//! versions (`brew cleanup --prune <days>`), and download or extraction directories that an
+//! interrupted run did not remove from the work directory.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use colored::Colorize;
+
+use crate::commands::load_configs_or_exit;
+use crate::core::disk_usage;
+use crate::engine::installers::brew;
+use crate::fonts::info::format_size;
+use crate::schemas::path_resolver::PathResolver;
+use crate::{log_debug, log_info, log_warn};
+
+/// Prefix of the temporary directories installers create in the work directory.
+const WORK_DIR_PREFIX: &str = "setup-devbox-";
+
+/// Work directory entries younger than this may belong to a run in progress.
+const MIN_LEFTOVER_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Entry point for the `clean` subcommand.
+///
+/// # Arguments
+/// * `paths` - Resolved configuration paths, used to read `tools.yaml`
+/// * `prune_days` - Overrides `brew_cleanup_prune_days` from `tools.yaml`
+/// * `dry_run` - Only report what would be removed
+pub fn run(paths: &PathResolver, prune_days: Option<u32>, dry_run: bool) {
+    log_debug!("[SDB::Clean] Entering clean::run()");
+
+    let tools_config = if paths.config_file().exists() {
+        load_configs_or_exit(paths).tools
+    } else {
+        None
+    };
+    if let Some(tools_config) = &tools_config {
+        PathResolver::configure_work_dir(tools_config.work_dir.as_deref());
+    }
+
+    // Homebrew downloads and old versions
+    if brew_available() {
+        let prune_days = prune_days
+            .or_else(|| tools_config.as_ref()?.brew_cleanup_prune_days)
+            .unwrap_or(brew::DEFAULT_CLEANUP_PRUNE_DAYS);
+        log_info!(
+            "[SDB::Clean] Running {} for downloads older than {} days...",
+            "brew cleanup".cyan(),
+            prune_days
+        );
+        match brew::cleanup(prune_days, dry_run) {
+            Ok(Some(reclaimed)) => log_info!(
+                "[SDB::Clean] Homebrew: {} {}",
+                if dry_run {
+                    "would reclaim"
+                } else {
+                    "reclaimed"
+                },
+                reclaimed.green()
+            ),
+            Ok(None) => log_info!("[SDB::Clean] Homebrew: nothing to remove"),
+            Err(e) => log_warn!("[SDB::Clean] {}", e),
+        }
+    } else {
+        log_debug!("[SDB::Clean] brew is not installed, skipping Homebrew cleanup");
+    }
+
+    // Leftovers of interrupted downloads and extractions
+    let work_dir = PathResolver::temp_root();
+    let leftovers = work_dir_leftovers(&work_dir);
+    if leftovers.is_empty() {
+        log_info!(
+            "[SDB::Clean] Work directory {}: nothing to remove",
+            work_dir.display()
+        );
+        return;
+    }
+
+    let mut reclaimed = 0;
+    for leftover in &leftovers {
+        let size = disk_usage::path_size(leftover);
+        if dry_run {
+            log_info!("[SDB::Clean] Would remove {}", leftover.display());
+            reclaimed += size;
+            continue;
+        }
+        let result = if leftover.is_dir() {
+            fs::remove_dir_all(leftover)
+        } else {
+            fs::remove_file(leftover)
+        };
+        match result {
+            Ok(()) => reclaimed += size,
+            Err(e) => log_warn!(
+                "[SDB::Clean] Failed to remove {}: {}",
+                leftover.display(),
+                e
+            ),
+        }
+    }
+    log_info!(
+        "[SDB::Clean] Work directory {}: {} {} in {} leftover(s)",
+        work_dir.display(),
+        if dry_run {
+            "would reclaim"
+        } else {
+            "reclaimed"
+        },
+        format_size(reclaimed).green(),
+        leftovers.len()
+    );
+}
+
+fn brew_available() -> bool {
+    Command::new("brew")
+        .arg("--version")
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Temporary download and extraction directories left in the work directory. Recent ones
+/// are skipped, as they may belong to a run in progress.
+fn work_dir_leftovers(work_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(work_dir) else {
+        return Vec::new();
+    };
+    let mut leftovers: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with(WORK_DIR_PREFIX)
+        })
+        .filter(|entry| {
+            entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .is_ok_and(|modified| modified.elapsed().unwrap_or_default() > MIN_LEFTOVER_AGE)
+        })
+        .map(|entry| entry.path())
+        .collect();
+    leftovers.sort();
+    leftovers
+}
@@ -0,0 +1,550 @@
+//! # Daemon Command Implementation
+//!
+//! `setup-devbox daemon` keeps a long-running process that editor extensions or a GUI can
+//! drive over a local Unix socket, instead of re-invoking the CLI for every action.
+//!
+//! ## Protocol
+//!
+//! Newline-delimited [JSON-RPC 2.0](https://www.jsonrpc.org/specification): each request
+//! and response is a single JSON object on its own line. Every request must carry the
+//! per-user token from `daemon.token` (created with `0600` permissions next to
+//! `state.json`) in `params.token`:
+//!
+//! ```json
+//! {"jsonrpc":"2.0","id":1,"method":"plan","params":{"token":"<token>"}}
+//! ```
+//!
+//! ## Methods
+//!
+//! - `plan` (`update_latest`): What `now` would do for each tool
+//! - `apply` (`update_latest`, `dry_run`): Runs `now`; log lines are streamed as `log`
+//!   notifications until the final result. Refused in read-only mode, dry runs included
+//! - `status`: Installed tools and whether an apply is running
+//! - `logs` (`follow`): Recent log lines; with `follow`, keeps streaming `log` notifications
+
+use crate::api::{self, ApplyOptions, PathResolver, ToolAction};
+use crate::config::read_only;
+use crate::logger::{LogCapture, enable_capture};
+use crate::{log_debug, log_error, log_info, log_warn};
+use colored::Colorize;
+use serde_json::{Value, json};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const UNAUTHORIZED: i64 = -32001;
+const APPLY_IN_PROGRESS: i64 = -32002;
+const READ_ONLY: i64 = -32003;
+const LOAD_FAILED: i64 = -32004;
+const APPLY_PANICKED: i64 = -32005;
+
+/// State shared by all client connections.
+struct Daemon {
+    paths: PathResolver,
+    token: String,
+    logs: &'static LogCapture,
+    applying: AtomicBool,
+}
+
+/// Marks an apply as running until dropped, so the flag is cleared even if the apply panics.
+struct ApplyingGuard<'a>(&'a AtomicBool);
+
+impl<'a> ApplyingGuard<'a> {
+    /// Returns `None` if another apply is already running.
+    fn acquire(applying: &'a AtomicBool) -> Option<Self> {
+        (!applying.swap(true, Ordering::SeqCst)).then_some(Self(applying))
+    }
+}
+
+impl Drop for ApplyingGuard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
+/// A JSON-RPC error returned to the client.
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<api::Error> for RpcError {
+    fn from(error: api::Error) -> Self {
+        RpcError::new(LOAD_FAILED, error.to_string())
+    }
+}
+
+/// Serialized writes to a client, shared between a request and its log forwarder.
+#[derive(Clone)]
+struct Connection(Arc<Mutex<UnixStream>>);
+
+impl Connection {
+    fn send(&self, message: &Value) -> bool {
+        let mut stream = self.0.lock().unwrap();
+        writeln!(stream, "{message}")
+            .and_then(|_| stream.flush())
+            .is_ok()
+    }
+
+    fn send_log(&self, line: &str) -> bool {
+        self.send(&json!({"jsonrpc": "2.0", "method": "log", "params": {"line": line}}))
+    }
+}
+
+/// Entry point for the `daemon` subcommand.
+pub fn run(config: Option<String>, state: Option<String>, socket: Option<String>) {
+    log_debug!("[SDB::Daemon] Entering daemon::run()");
+
+    let paths = match PathResolver::new(config, state) {
+        Ok(paths) => paths,
+        Err(e) => {
+            log_error!("[SDB::Daemon] Failed to resolve paths: {}", e);
+            return;
+        }
+    };
+
+    let socket_path = socket
+        .map(|s| PathResolver::expand_tilde(&s))
+        .unwrap_or_else(|| paths.base_config_dir().join("daemon.sock"));
+    let token_path = paths.base_config_dir().join("daemon.token");
+
+    let token = match load_or_create_token(&token_path) {
+        Ok(token) => token,
+        Err(e) => {
+            log_error!(
+                "[SDB::Daemon] Failed to prepare token file {}: {}",
+                token_path.display(),
+                e
+            );
+            return;
+        }
+    };
+
+    let listener = match bind_socket(&socket_path) {
+        Ok(listener) => listener,
+        Err(e) => {
+            log_error!(
+                "[SDB::Daemon] Failed to listen on {}: {}",
+                socket_path.display(),
+                e
+            );
+            return;
+        }
+    };
+
+    // Clients receive plain text, not terminal escape codes
+    colored::control::set_override(false);
+
+    let daemon = Arc::new(Daemon {
+        paths,
+        token,
+        logs: enable_capture(),
+        applying: AtomicBool::new(false),
+    });
+
+    log_info!(
+        "[SDB::Daemon] Listening on {} (token: {})",
+        socket_path.display().to_string().cyan(),
+        token_path.display()
+    );
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let daemon = Arc::clone(&daemon);
+                thread::spawn(move || handle_client(&daemon, stream));
+            }
+            Err(e) => log_warn!("[SDB::Daemon] Failed to accept connection: {}", e),
+        }
+    }
+}
+
+/// Reads the per-user token, creating it with owner-only permissions on first start.
+fn load_or_create_token(path: &Path) -> std::io::Result<String> {
+    if let Ok(existing) = fs::read_to_string(path) {
+        let existing = existing.trim().to_string();
+        if !existing.is_empty() {
+            return Ok(existing);
+        }
+    }
+
+    let mut bytes = [0u8; 32];
+    fs::File::open("/dev/urandom")?.read_exact(&mut bytes)?;
+    let token: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    writeln!(file, "{token}")?;
+    Ok(token)
+}
+
+/// Binds the socket, replacing a stale socket file but refusing to replace a live daemon.
+fn bind_socket(path: &PathBuf) -> std::io::Result<UnixListener> {
+    if path.exists() {
+        if UnixStream::connect(path).is_ok() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AddrInUse,
+                "another daemon is already running",
+            ));
+        }
+        fs::remove_file(path)?;
+    }
+    let listener = UnixListener::bind(path)?;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+fn handle_client(daemon: &Daemon, stream: UnixStream) {
+    let reader = match stream.try_clone() {
+        Ok(reader) => BufReader::new(reader),
+        Err(e) => {
+            log_warn!("[SDB::Daemon] Failed to set up connection: {}", e);
+            return;
+        }
+    };
+    let connection = Connection(Arc::new(Mutex::new(stream)));
+
+    for line in reader.lines() {
+        let Ok(line) = line else { break };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (id, outcome) = match serde_json::from_str::<Value>(&line) {
+            Ok(request) => {
+                let id = request.get("id").cloned().unwrap_or(Value::Null);
+                (id, dispatch(daemon, &connection, &request))
+            }
+            Err(e) => (
+                Value::Null,
+                Err(RpcError::new(PARSE_ERROR, format!("Parse error: {e}"))),
+            ),
+        };
+
+        let response = match outcome {
+            Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+            Err(error) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": {"code": error.code, "message": error.message}
+            }),
+        };
+        if !connection.send(&response) {
+            break;
+        }
+    }
+}
+
+fn dispatch(daemon: &Daemon, connection: &Connection, request: &Value) -> Result<Value, RpcError> {
+    let method = request
+        .get("method")
+        .and_then(Value::as_str)
+        .ok_or_else(|| RpcError::new(INVALID_REQUEST, "Missing 'method'"))?;
+    let params = request.get("params").cloned().unwrap_or(json!({}));
+
+    let token = params.get("token").and_then(Value::as_str).unwrap_or("");
+    if !tokens_match(token, &daemon.token) {
+        return Err(RpcError::new(UNAUTHORIZED, "Invalid or missing token"));
+    }
+    let flag = |name: &str| params.get(name).and_then(Value::as_bool).unwrap_or(false);
+
+    log_debug!("[SDB::Daemon] Handling '{}' request", method);
+    match method {
+        "plan" => plan(daemon, flag("update_latest")),
+        "apply" => apply(daemon, connection, flag("update_latest"), flag("dry_run")),
+        "status" => status(daemon),
+        "logs" => Ok(logs(daemon, connection, flag("follow"))),
+        other => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Unknown method '{other}'"),
+        )),
+    }
+}
+
+/// Compares tokens in time independent of where they differ.
+fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+fn plan(daemon: &Daemon, update_latest: bool) -> Result<Value, RpcError> {
+    let tools: Vec<Value> = api::plan(&daemon.paths, update_latest)?
+        .into_iter()
+        .map(|tool| {
+            let (action, reason) = match tool.action {
+                ToolAction::Install => ("install", None),
+                ToolAction::Update => ("update", None),
+                ToolAction::UpdateConfigurationOnly => ("update-configuration", None),
+                ToolAction::Skip(reason) => ("skip", Some(reason)),
+                ToolAction::SkipConfigurationOnly(reason) => ("skip-configuration", Some(reason)),
+            };
+            json!({"name": tool.name, "action": action, "reason": reason})
+        })
+        .collect();
+    Ok(json!({ "tools": tools }))
+}
+
+/// Runs `now`, streaming log lines to the requesting client while it runs.
+fn apply(
+    daemon: &Daemon,
+    connection: &Connection,
+    update_latest: bool,
+    dry_run: bool,
+) -> Result<Value, RpcError> {
+    // Refused for dry runs too, so the flag can never open a way to change the machine
+    if read_only::is_read_only(&daemon.paths) {
+        return Err(RpcError::new(
+            READ_ONLY,
+            "setup-devbox is in read-only mode on this machine; use 'plan' to review changes",
+        ));
+    }
+    let Some(_applying) = ApplyingGuard::acquire(&daemon.applying) else {
+        return Err(RpcError::new(
+            APPLY_IN_PROGRESS,
+            "An apply is already running",
+        ));
+    };
+
+    let receiver = daemon.logs.subscribe();
+    let done = Arc::new(AtomicBool::new(false));
+    let forwarder = {
+        let connection = connection.clone();
+        let done = Arc::clone(&done);
+        thread::spawn(move || {
+            loop {
+                match receiver.recv_timeout(Duration::from_millis(100)) {
+                    Ok(line) => {
+                        connection.send_log(&line);
+                    }
+                    Err(_) if done.load(Ordering::SeqCst) => break,
+                    Err(_) => {}
+                }
+            }
+        })
+    };
+
+    // A panicking apply is reported to the client instead of ending the connection
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+        api::apply(
+            &daemon.paths,
+            &ApplyOptions {
+                update_latest,
+                dry_run,
+                resume: false,
+                diff_last: false,
+            },
+        )
+    }));
+
+    done.store(true, Ordering::SeqCst);
+    let _ = forwarder.join();
+
+    let outcome = outcome.map_err(|payload| {
+        let reason = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        log_error!("[SDB::Daemon] Apply panicked: {}", reason);
+        RpcError::new(APPLY_PANICKED, format!("Apply panicked: {reason}"))
+    })??;
+    let failed: Vec<Value> = outcome
+        .failed
+        .iter()
+        .map(|(name, error)| json!({"name": name, "error": error}))
+        .collect();
+    Ok(json!({
+        "status": if outcome.succeeded() { "completed" } else { "failed" },
+        "dry_run": dry_run,
+        "installed": outcome.installed,
+        "updated": outcome.updated,
+        "failed": failed,
+    }))
+}
+
+fn status(daemon: &Daemon) -> Result<Value, RpcError> {
+    let state = api::load_state(&daemon.paths)?;
+    let mut tools: Vec<Value> = state
+        .tools
+        .iter()
+        .map(|(name, tool)| {
+            json!({
+                "name": name,
+                "version": tool.version,
+                "install_method": tool.install_method,
+                "owner": tool.owner,
+                "notes": tool.notes,
+            })
+        })
+        .collect();
+    tools.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+
+    Ok(json!({
+        "applying": daemon.applying.load(Ordering::SeqCst),
+        "state_file": daemon.paths.state_file().display().to_string(),
+        "tools": tools,
+        "fonts": state.fonts.len(),
+    }))
+}
+
+/// Returns recent log lines; with `follow`, streams new lines until the client disconnects.
+fn logs(daemon: &Daemon, connection: &Connection, follow: bool) -> Value {
+    if !follow {
+        return json!({ "lines": daemon.logs.recent() });
+    }
+
+    let receiver = daemon.logs.subscribe();
+    for line in daemon.logs.recent() {
+        if !connection.send_log(&line) {
+            return Value::Null;
+        }
+    }
+    for line in receiver {
+        if !connection.send_log(&line) {
+            break;
+        }
+    }
+    log_debug!("[SDB::Daemon] Log follower disconnected");
+    Value::Null
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("abc123", "abc123"));
+        assert!(!tokens_match("abc124", "abc123"));
+        assert!(!tokens_match("abc12", "abc123"));
+        assert!(!tokens_match("", "abc123"));
+    }
+
+    #[test]
+    fn test_applying_guard_is_released_on_panic() {
+        let applying = AtomicBool::new(false);
+        let guard = ApplyingGuard::acquire(&applying).unwrap();
+        assert!(ApplyingGuard::acquire(&applying).is_none());
+        drop(guard);
+
+        let result = panic::catch_unwind(|| {
+            let _guard = ApplyingGuard::acquire(&applying).unwrap();
+            panic!("installer bug");
+        });
+        assert!(result.is_err());
+        assert!(ApplyingGuard::acquire(&applying).is_some());
+    }
+
+    #[test]
+    fn test_protocol_framing_and_auth() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("tools.yaml");
+        fs::write(&config, "tools: []\n").unwrap();
+        let paths = PathResolver::new(
+            Some(config.display().to_string()),
+            Some(dir.path().join("state.json").display().to_string()),
+        )
+        .unwrap();
+        let daemon = Daemon {
+            paths,
+            token: "secret".to_string(),
+            logs: enable_capture(),
+            applying: AtomicBool::new(false),
+        };
+
+        let (mut client, server) = UnixStream::pair().unwrap();
+        let requests = [
+            "",
+            "not json",
+            r#"{"jsonrpc":"2.0","id":1,"method":"status","params":{"token":"wrong"}}"#,
+            r#"{"jsonrpc":"2.0","id":2,"method":"status"}"#,
+            r#"{"jsonrpc":"2.0","id":3,"method":"reboot","params":{"token":"secret"}}"#,
+            r#"{"jsonrpc":"2.0","id":4,"params":{"token":"secret"}}"#,
+            r#"{"jsonrpc":"2.0","id":5,"method":"status","params":{"token":"secret"}}"#,
+        ];
+        for request in requests {
+            writeln!(client, "{request}").unwrap();
+        }
+        client.shutdown(std::net::Shutdown::Write).unwrap();
+        handle_client(&daemon, server);
+
+        let responses: Vec<Value> = BufReader::new(client)
+            .lines()
+            .map(|line| serde_json::from_str(&line.unwrap()).unwrap())
+            .collect();
+        // Blank lines are skipped; every other line gets exactly one response, in order
+        assert_eq!(responses.len(), 6);
+        let error_code = |response: &Value| response["error"]["code"].as_i64();
+        assert_eq!(responses[0]["id"], Value::Null);
+        assert_eq!(error_code(&responses[0]), Some(PARSE_ERROR));
+        assert_eq!(error_code(&responses[1]), Some(UNAUTHORIZED));
+        assert_eq!(error_code(&responses[2]), Some(UNAUTHORIZED));
+        assert_eq!(error_code(&responses[3]), Some(METHOD_NOT_FOUND));
+        assert_eq!(error_code(&responses[4]), Some(INVALID_REQUEST));
+        assert_eq!(responses[5]["id"], 5);
+        assert_eq!(responses[5]["result"]["applying"], false);
+        assert_eq!(responses[5]["result"]["tools"], json!([]));
+    }
+
+    #[test]
+    fn test_read_only_refuses_dry_run_apply() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config.yaml");
+        fs::write(&config, "tools: tools.yaml\nread_only: true\n").unwrap();
+        let paths = PathResolver::new(
+            Some(config.display().to_string()),
+            Some(dir.path().join("state.json").display().to_string()),
+        )
+        .unwrap();
+        let daemon = Daemon {
+            paths,
+            token: "secret".to_string(),
+            logs: enable_capture(),
+            applying: AtomicBool::new(false),
+        };
+
+        let (mut client, server) = UnixStream::pair().unwrap();
+        writeln!(
+            client,
+            r#"{{"jsonrpc":"2.0","id":1,"method":"apply","params":{{"token":"secret","dry_run":true}}}}"#
+        )
+        .unwrap();
+        client.shutdown(std::net::Shutdown::Write).unwrap();
+        handle_client(&daemon, server);
+
+        let mut response = String::new();
+        BufReader::new(client).read_line(&mut response).unwrap();
+        let response: Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response["error"]["code"].as_i64(), Some(READ_ONLY));
+    }
+}
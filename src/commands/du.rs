@@ -0,0 +1,172 @@
+//! # Du Command Implementation
+//!
+//! Reports how much disk space installed tools and fonts take, largest first, together with
+//! the `~/.setup-devbox` directory, its cache, and files in the bin directory that no tool
+//! accounts for. Sizes are measured now; entries whose files are gone show the size recorded
+//! at installation for reference.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+use walkdir::WalkDir;
+
+use crate::commands::load_state_or_exit;
+use crate::core::{delta, disk_usage};
+use crate::fonts::info::format_size;
+use crate::log_debug;
+use crate::schemas::path_resolver::PathResolver;
+use crate::schemas::state_file::DevBoxState;
+
+/// One line of the usage breakdown.
+struct Usage {
+    name: String,
+    kind: &'static str,
+    bytes: u64,
+    /// Size recorded at installation.
+    recorded: Option<u64>,
+}
+
+/// Entry point for the `du` subcommand.
+pub fn run(paths: &PathResolver) {
+    log_debug!("[SDB::Du] Entering du::run()");
+
+    let state: DevBoxState = load_state_or_exit(&paths.state_file().to_path_buf());
+    let seed_dir = delta::seed_dir(paths.cache_dir());
+
+    let mut tracked = BTreeSet::new();
+    let mut usages: Vec<Usage> = Vec::new();
+    for (name, tool_state) in &state.tools {
+        let tool_paths = disk_usage::tool_paths(tool_state, Some(seed_dir.join(name)));
+        let bytes = tool_paths
+            .iter()
+            .map(|path| disk_usage::path_size(path))
+            .sum();
+        tracked.extend(tool_paths);
+        usages.push(Usage {
+            name: name.clone(),
+            kind: "tool",
+            bytes,
+            recorded: tool_state.disk_usage_bytes,
+        });
+    }
+    for (name, font_state) in &state.fonts {
+        tracked.extend(font_state.files.iter().map(PathBuf::from));
+        usages.push(Usage {
+            name: name.clone(),
+            kind: "font",
+            bytes: disk_usage::font_size(font_state),
+            recorded: font_state.disk_usage_bytes,
+        });
+    }
+    usages.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+
+    if usages.is_empty() {
+        println!("No tools or fonts are installed.");
+    } else {
+        for usage in &usages {
+            let missing = match usage.recorded {
+                Some(recorded) if usage.bytes == 0 && recorded > 0 => format!(
+                    "  (files missing, {} at installation)",
+                    format_size(recorded)
+                )
+                .yellow()
+                .to_string(),
+                _ => String::new(),
+            };
+            println!(
+                "  {:>10}  {:<4}  {}{}",
+                format_size(usage.bytes),
+                usage.kind,
+                usage.name,
+                missing
+            );
+        }
+    }
+
+    let tools_total: u64 = usages
+        .iter()
+        .filter(|usage| usage.kind == "tool")
+        .map(|usage| usage.bytes)
+        .sum();
+    let fonts_total: u64 = usages
+        .iter()
+        .filter(|usage| usage.kind == "font")
+        .map(|usage| usage.bytes)
+        .sum();
+    let base_dir = paths.base_config_dir();
+    let base_total = disk_usage::path_size(base_dir);
+    let cache_total = disk_usage::path_size(paths.cache_dir());
+
+    println!();
+    println!(
+        "  {:>10}  tools ({})",
+        format_size(tools_total),
+        state.tools.len()
+    );
+    println!(
+        "  {:>10}  fonts ({})",
+        format_size(fonts_total),
+        state.fonts.len()
+    );
+    println!(
+        "  {:>10}  {} (state, configurations, receipts)",
+        format_size(base_total),
+        base_dir.display()
+    );
+    println!(
+        "  {:>10}  {} (release metadata and delta update downloads)",
+        format_size(cache_total),
+        paths.cache_dir().display()
+    );
+    if let Some(bin_dir) = PathResolver::get_user_bin_dir() {
+        println!(
+            "  {:>10}  {} not installed by setup-devbox",
+            format_size(untracked_size(&bin_dir, &tracked)),
+            bin_dir.display()
+        );
+    }
+
+    // The cache usually lives inside the base directory; don't count it twice
+    let cache_outside = if paths.cache_dir().starts_with(base_dir) {
+        0
+    } else {
+        cache_total
+    };
+    let total = format!(
+        "  {:>10}  total",
+        format_size(tools_total + fonts_total + base_total + cache_outside)
+    );
+    println!("{}", total.green().bold());
+}
+
+/// Size of the files below `dir` that no installed tool accounts for.
+fn untracked_size(dir: &Path, tracked: &BTreeSet<PathBuf>) -> u64 {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| !entry.path().ancestors().any(|path| tracked.contains(path)))
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_untracked_size_skips_tracked_files_and_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let bin = dir.path();
+        fs::write(bin.join("rg"), [0u8; 100]).unwrap();
+        fs::write(bin.join("hand-copied"), [0u8; 7]).unwrap();
+        fs::create_dir(bin.join("app")).unwrap();
+        fs::write(bin.join("app/data"), [0u8; 40]).unwrap();
+
+        let tracked = BTreeSet::from([bin.join("rg"), bin.join("app")]);
+        assert_eq!(untracked_size(bin, &tracked), 7);
+    }
+}
@@ -13,20 +13,21 @@ use std::process::Command;
 /// # Arguments
 /// * `edit_state` - Boolean flag indicating if the state file should be edited
 /// * `config_type` - Optional config type to edit (tools, fonts, shell, settings)
-pub fn run(edit_state: bool, config_type: Option<String>) {
+/// * `paths` - Resolved configuration and state locations
+pub fn run(edit_state: bool, config_type: Option<String>, paths: &PathResolver) {
     log_debug!("[Edit] Starting edit command execution");
     log_debug!("[Edit] Edit state requested: {}", edit_state);
     log_debug!("[Edit] Config type requested: {:?}", config_type);
 
     if edit_state {
         log_debug!("[Edit] Handling state file edit request");
-        handle_state_edit();
+        handle_state_edit(paths);
     } else if let Some(config_type) = config_type {
         log_debug!(
             "[Edit] Handling config file edit request for type: {}",
             config_type
         );
-        handle_config_edit(&config_type);
+        handle_config_edit(&config_type, paths);
     } else {
         // This should not happen due to clap validation, but adding as safety
         log_error!("[Edit] Neither state nor config type was specified");
@@ -41,7 +42,7 @@ pub fn run(edit_state: bool, config_type: Option<String>) {
 
 /// Handles editing the state file
 /// Shows warning about this being a "break glass" mechanism
-fn handle_state_edit() {
+fn handle_state_edit(paths: &PathResolver) {
     log_debug!("[Edit] Preparing to edit state file");
     println!();
     // Show warning about editing state file
@@ -83,12 +84,7 @@ fn handle_state_edit() {
     );
     println!();
 
-    let state_file_path = get_config_file_path_with_fallback("state", |home_dir| {
-        let mut path = PathBuf::from(home_dir);
-        path.push(".setup-devbox");
-        path.push("state.json");
-        path
-    });
+    let state_file_path = paths.state_file().to_path_buf();
 
     log_debug!("[Edit] State file path resolved to: {:?}", state_file_path);
 
@@ -143,10 +139,11 @@ fn handle_state_edit() {
 ///
 /// # Arguments
 /// * `config_type` - The type of config to edit (tools, fonts, shell, settings)
-fn handle_config_edit(config_type: &str) {
+/// * `paths` - Resolved configuration and state locations
+fn handle_config_edit(config_type: &str, paths: &PathResolver) {
     log_debug!("[Edit] Starting config file edit for type: {}", config_type);
 
-    let config_file_path = get_config_file_path(config_type);
+    let config_file_path = get_config_file_path(config_type, paths);
     log_debug!(
         "[Edit] Config file path resolved to: {:?}",
         config_file_path
@@ -240,15 +237,8 @@ fn handle_config_edit(config_type: &str) {
     );
     log_info!("[Edit] Auto-running 'now' command to apply configuration changes");
 
-    // Import and call the now command
-    // crate::commands::now::run(None, None, false);
-    match PathResolver::new(None, None) {
-        // crate::commands::now::run(None, None, false);
-        Ok(paths) => crate::commands::now::run(&paths, false, false),
-        Err(e) => {
-            log_error!("Failed to initialize path resolver: {}", e);
-            std::process::exit(1);
-        }
+    if crate::commands::now::run(paths, false, false, false, false, None).is_err() {
+        std::process::exit(1);
     }
 
     log_info!(
@@ -290,17 +280,18 @@ fn get_file_content_hash(file_path: &PathBuf) -> Option<String> {
 ///
 /// # Arguments
 /// * `config_type` - The type of config file to get path for
+/// * `paths` - Resolved configuration and state locations
 ///
 /// # Returns
 /// PathBuf pointing to the specific configuration file
-fn get_config_file_path(config_type: &str) -> PathBuf {
+fn get_config_file_path(config_type: &str, paths: &PathResolver) -> PathBuf {
     log_debug!(
         "[Edit] Resolving config file path for type: {}",
         config_type
     );
 
     // Read and parse the main config file to get paths to individual config files
-    let main_config_path = get_main_config_path();
+    let main_config_path = paths.config_file().to_path_buf();
     log_debug!("[Edit] Main config file path: {:?}", main_config_path);
 
     match read_config_paths(&main_config_path) {
@@ -336,68 +327,6 @@ fn get_config_file_path(config_type: &str) -> PathBuf {
     }
 }
 
-/// Gets the path to a configuration file with fallback logic
-/// Used for files that don't have their paths defined in config.yaml
-///
-/// # Arguments
-/// * `file_type` - The type of file to get path for
-/// * `fallback_fn` - Function to generate fallback path if SDB_CONFIG_DIR is not set
-///
-/// # Returns
-/// PathBuf pointing to the configuration file
-fn get_config_file_path_with_fallback<F>(file_type: &str, fallback_fn: F) -> PathBuf
-where
-    F: Fn(&str) -> PathBuf,
-{
-    log_debug!("[Edit] Resolving {} file path", file_type);
-
-    // Check for SDB_CONFIG_PATH environment variable first
-    if let Ok(config_dir) = env::var("SDB_CONFIG_PATH") {
-        log_debug!(
-            "[Edit] Using SDB_CONFIG_PATH environment variable: {}",
-            config_dir
-        );
-        let mut path = PathBuf::from(config_dir);
-        if file_type == "config" {
-            path.push("configs");
-            path.push("config.yaml");
-        } else {
-            path.push(format!("{file_type}.json"));
-        }
-        return path;
-    }
-
-    // Fall back to default location using the provided function
-    let home_dir = env::var("HOME").unwrap_or_else(|_| {
-        log_error!("[Edit] HOME environment variable not found");
-        eprintln!("{}", "Error: HOME environment variable not found".red());
-        std::process::exit(1);
-    });
-
-    log_debug!(
-        "[Edit] Using default location in HOME directory: {}",
-        home_dir
-    );
-    let path = fallback_fn(&home_dir);
-
-    log_debug!("[Edit] Final {} file path: {:?}", file_type, path);
-    path
-}
-
-/// Gets the path to the main config.yaml file
-///
-/// # Returns
-/// PathBuf pointing to the main configuration file
-fn get_main_config_path() -> PathBuf {
-    get_config_file_path_with_fallback("config", |home_dir| {
-        let mut path = PathBuf::from(home_dir);
-        path.push(".setup-devbox");
-        path.push("configs");
-        path.push("config.yaml");
-        path
-    })
-}
-
 /// Reads and parses the main config.yaml file to extract paths to individual config files
 ///
 /// # Arguments
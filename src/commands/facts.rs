@@ -0,0 +1,38 @@
+//! # Facts Command Implementation
+//!
+//! Prints the machine facts `only_on` conditions, `{{ facts.* }}` placeholders and hooks
+//! can use, so conditions can be checked before they are written.
+
+use std::fs;
+
+use colored::Colorize;
+
+use crate::core::facts;
+use crate::log_debug;
+use crate::schemas::common::MainConfig;
+use crate::schemas::path_resolver::PathResolver;
+
+/// Entry point for the `facts` subcommand.
+pub fn run(paths: &PathResolver, json: bool) {
+    log_debug!("[SDB::Facts] Entering facts::run()");
+
+    // The `vpn` fact needs `vpn_check` from config.yaml
+    let main_config = fs::read_to_string(paths.config_file())
+        .ok()
+        .and_then(|content| serde_yaml::from_str::<MainConfig>(&content).ok());
+    facts::configure_vpn_check(main_config.and_then(|config| config.vpn_check));
+
+    let facts = facts::facts();
+    if json {
+        match serde_json::to_string_pretty(&*facts) {
+            Ok(content) => println!("{content}"),
+            Err(e) => eprintln!("Failed to serialize facts: {e}"),
+        }
+        return;
+    }
+
+    let width = facts.keys().map(String::len).max().unwrap_or(0);
+    for (name, value) in facts.iter() {
+        println!("  {:width$}  {}", name.cyan(), value.bold(), width = width);
+    }
+}
@@ -0,0 +1,241 @@
+//! # Fonts Command Implementation
+//!
+//! `setup-devbox fonts info <name|repo>` previews a font release before installing it: it
+//! downloads the archive to a temporary directory, lists the contained font files with the
+//! family and style read from their `name` tables, and estimates the installed size. Files
+//! selected by the font's `install_only` patterns or `variant` in `fonts.yaml` are marked, so
+//! they can be tuned before a full Nerd Fonts archive lands in the font directory.
+
+use crate::cli::cmd_enums::FontsCommands;
+use crate::commands::load_configs_or_exit;
+use crate::core::assets::download_file;
+use crate::core::metadata_cache;
+use crate::engine::installers::fonts::{NERD_FONTS_REPO, selects_font_file};
+use crate::engine::installers::github::{fetch_github_release, fetch_latest_github_release};
+use crate::fonts::info::{FontFileInfo, format_size, inspect_zip};
+use crate::schemas::common::Release;
+use crate::schemas::fonts::FontEntry;
+use crate::schemas::path_resolver::PathResolver;
+use crate::{log_debug, log_error, log_info, log_warn};
+use colored::Colorize;
+use prettytable::{Cell, Row, Table};
+use std::fs;
+
+/// Entry point for the `fonts` subcommand.
+pub fn run(action: FontsCommands) {
+    match action {
+        FontsCommands::Info {
+            target,
+            version,
+            asset,
+            config,
+        } => info(&target, version, asset, config),
+    }
+}
+
+fn info(target: &str, version: Option<String>, asset: Option<String>, config: Option<String>) {
+    log_debug!("[SDB::Fonts::Info] Entering fonts::info() for {}", target);
+
+    let paths = match PathResolver::new(config, None) {
+        Ok(paths) => paths,
+        Err(e) => {
+            log_error!("[SDB::Fonts::Info] Failed to resolve paths: {}", e);
+            std::process::exit(1);
+        }
+    };
+    metadata_cache::configure(paths.cache_dir(), None, false);
+
+    // `owner/repo` is looked up directly; anything else is a font name from fonts.yaml
+    let entry = if target.contains('/') || !paths.config_file().exists() {
+        None
+    } else {
+        load_configs_or_exit(&paths).fonts.and_then(|fonts_cfg| {
+            fonts_cfg
+                .fonts
+                .into_iter()
+                .find(|font| font.name.eq_ignore_ascii_case(target))
+        })
+    };
+    let repo = if let Some(repo) = entry.as_ref().and_then(|font| font.repo.clone()) {
+        repo
+    } else if target.contains('/') {
+        target.to_string()
+    } else {
+        log_info!(
+            "[SDB::Fonts::Info] {} is not in fonts.yaml, looking it up in {}",
+            target.bold(),
+            NERD_FONTS_REPO.cyan()
+        );
+        NERD_FONTS_REPO.to_string()
+    };
+    let tag = version.or_else(|| entry.as_ref().and_then(|font| font.tag.clone()));
+
+    let release = match tag.as_deref() {
+        Some(tag) => fetch_github_release(&repo, tag),
+        None => fetch_latest_github_release(&repo),
+    };
+    let release = match release {
+        Ok(release) => release,
+        Err(e) => {
+            log_error!(
+                "[SDB::Fonts::Info] Could not fetch the release of {}: {}",
+                repo,
+                e
+            );
+            std::process::exit(1);
+        }
+    };
+
+    let asset_name = match asset {
+        Some(asset) => asset,
+        None if !target.contains('/') => format!("{}.zip", target.replace(' ', "")),
+        None => {
+            list_archives(&repo, &release);
+            return;
+        }
+    };
+    let Some(asset) = release
+        .assets
+        .iter()
+        .find(|candidate| candidate.name.eq_ignore_ascii_case(&asset_name))
+    else {
+        log_error!(
+            "[SDB::Fonts::Info] {} has no asset named {} in release {}",
+            repo,
+            asset_name.red(),
+            release.tag_name
+        );
+        list_archives(&repo, &release);
+        std::process::exit(1);
+    };
+
+    let temp_dir =
+        PathResolver::temp_root().join(format!("setup-devbox-font-info-{}", std::process::id()));
+    let archive_path = temp_dir.join(&asset.name);
+    log_info!(
+        "[SDB::Fonts::Info] Downloading {} ({}) to inspect it...",
+        asset.name.bold(),
+        format_size(asset.size)
+    );
+    let fonts = fs::create_dir_all(&temp_dir)
+        .and_then(|_| download_file(&asset.browser_download_url, &archive_path))
+        .and_then(|_| inspect_zip(&archive_path));
+    let _ = fs::remove_dir_all(&temp_dir);
+    let fonts = match fonts {
+        Ok(fonts) => fonts,
+        Err(e) => {
+            log_error!("[SDB::Fonts::Info] Failed to inspect {}: {}", asset.name, e);
+            std::process::exit(1);
+        }
+    };
+
+    // Only fonts from fonts.yaml with `install_only` or `variant` install a subset
+    let selection = entry
+        .as_ref()
+        .filter(|font| font.install_only.is_some() || font.variant.is_some());
+    print_fonts(&fonts, selection);
+
+    let selected: Vec<&FontFileInfo> = fonts
+        .iter()
+        .filter(|font| selection.is_none_or(|entry| selects_font_file(entry, &font.file)))
+        .collect();
+    println!();
+    println!("  {:<24} {} {}", "Release:".bold(), repo, release.tag_name);
+    println!(
+        "  {:<24} {}",
+        "Download size:".bold(),
+        format_size(asset.size)
+    );
+    println!("  {:<24} {}", "Font files:".bold(), fonts.len());
+    println!(
+        "  {:<24} {} ({} file(s){})",
+        "Estimated install size:".bold(),
+        format_size(selected.iter().map(|font| font.size).sum()).green(),
+        selected.len(),
+        if selection.is_some() {
+            " selected by install_only/variant"
+        } else {
+            ""
+        }
+    );
+    if selection.is_none() {
+        println!(
+            "\n  Tip: limit what gets installed with {} or {} in fonts.yaml, e.g. {}",
+            "install_only".cyan(),
+            "variant".cyan(),
+            "variant: mono-regular".cyan()
+        );
+    }
+}
+
+fn print_fonts(fonts: &[FontFileInfo], selection: Option<&FontEntry>) {
+    if fonts.is_empty() {
+        log_warn!("[SDB::Fonts::Info] The archive contains no .ttf or .otf files.");
+        return;
+    }
+
+    let mut table = Table::new();
+    table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+    let mut header = vec![
+        Cell::new("File").style_spec("b"),
+        Cell::new("Family").style_spec("b"),
+        Cell::new("Style").style_spec("b"),
+        Cell::new("Size").style_spec("b"),
+    ];
+    if selection.is_some() {
+        header.push(Cell::new("Installed").style_spec("b"));
+    }
+    table.add_row(Row::new(header));
+
+    for font in fonts {
+        let mut row = vec![
+            Cell::new(&font.file),
+            Cell::new(font.family.as_deref().unwrap_or("-")),
+            Cell::new(font.style.as_deref().unwrap_or("-")),
+            Cell::new(&format_size(font.size)),
+        ];
+        if let Some(entry) = selection {
+            row.push(if selects_font_file(entry, &font.file) {
+                Cell::new("yes").style_spec("Fg")
+            } else {
+                Cell::new("no")
+            });
+        }
+        table.add_row(Row::new(row));
+    }
+
+    println!("\n{}", "Font files".bold().green());
+    table.printstd();
+}
+
+/// Lists the font archives of a release, for repositories that publish several fonts.
+fn list_archives(repo: &str, release: &Release) {
+    let mut table = Table::new();
+    table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+    table.add_row(Row::new(vec![
+        Cell::new("Asset").style_spec("b"),
+        Cell::new("Size").style_spec("b"),
+    ]));
+    for asset in release
+        .assets
+        .iter()
+        .filter(|asset| asset.name.to_lowercase().ends_with(".zip"))
+    {
+        table.add_row(Row::new(vec![
+            Cell::new(&asset.name),
+            Cell::new(&format_size(asset.size)),
+        ]));
+    }
+
+    println!(
+        "\n{} {} {}",
+        "Font archives in".bold().green(),
+        repo,
+        release.tag_name
+    );
+    table.printstd();
+    println!(
+        "\n  Inspect one with {}",
+        format!("setup-devbox fonts info {repo} --asset <name>.zip").cyan()
+    );
+}
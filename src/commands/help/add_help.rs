@@ -161,7 +161,7 @@ fn add_add_detailed_info(output: &mut String) {
     .unwrap();
     writeln!(
         output,
-        "  {}  Source type [brew, github, rustup, cargo, pip, go, url, uv]\n",
+        "  {}  Source type [brew, github, rustup, cargo, pip, go, url, oci, uv]\n",
         "--source <SOURCE>".cyan()
     )
     .unwrap();
@@ -307,10 +307,22 @@ fn add_add_detailed_info(output: &mut String) {
     .unwrap();
     writeln!(
         output,
-        "  {}  Font variants to install (can be used multiple times)\n",
+        "  {}  Font variants to install (can be used multiple times)",
         "--install-only <VARIANT>".cyan()
     )
     .unwrap();
+    writeln!(
+        output,
+        "  {}  Nerd Fonts variant, e.g. mono, propo-bold, regular",
+        "--variant <VARIANT>".cyan()
+    )
+    .unwrap();
+    writeln!(
+        output,
+        "  {}  Install for every user into /Library/Fonts (needs sudo)\n",
+        "--system".cyan()
+    )
+    .unwrap();
 
     writeln!(output, "{}", "Examples:".bold().magenta()).unwrap();
 
@@ -0,0 +1,33 @@
+use colored::Colorize;
+
+pub fn show_bootstrap_script_help(detailed: bool) {
+    println!("{}", "setup-devbox bootstrap-script".bold().blue());
+    println!("Print a script that sets up a new machine from a configuration repository.");
+    println!();
+    println!("{}", "Usage:".bold().yellow());
+    println!("  setup-devbox bootstrap-script --repo <URL> [OPTIONS]");
+    println!();
+    println!("{}", "Options:".bold().yellow());
+    println!("  --repo <URL>      Git repository holding config.yaml and the other files.");
+    println!("  --branch <NAME>   Branch to clone (default: the repository's default branch).");
+    println!("  --dir <DIR>       Clone destination (default: ~/.setup-devbox/configs).");
+    println!("  --one-liner       Print a single 'sh -c' command instead of a script.");
+
+    if detailed {
+        println!();
+        println!("The script downloads the latest setup-devbox release for the machine's");
+        println!("architecture into ~/bin, clones the repository (or pulls it when it is already");
+        println!("there) and runs 'setup-devbox now' with its config.yaml. Release binaries are");
+        println!("published for macOS; on a fresh Mac, 'git' asks to install the Command Line");
+        println!("Tools first.");
+        println!();
+        println!("{}", "Examples:".bold().yellow());
+        println!("  setup-devbox bootstrap-script --repo https://github.com/me/dotfiles.git");
+        println!(
+            "  setup-devbox bootstrap-script --repo git@github.com:me/dotfiles.git --one-liner"
+        );
+        println!(
+            "  setup-devbox bootstrap-script --repo <URL> --dir ~/dotfiles/devbox > bootstrap.sh"
+        );
+    }
+}
@@ -0,0 +1,32 @@
+use colored::Colorize;
+
+pub fn show_clean_help(detailed: bool) {
+    println!("{}", "setup-devbox clean".bold().blue());
+    println!("Reclaim disk space left behind by provisioning runs.");
+    println!();
+    println!("{}", "Usage:".bold().yellow());
+    println!("  setup-devbox clean [OPTIONS]");
+    println!();
+    println!("{}", "Options:".bold().yellow());
+    println!("  --prune <DAYS>    Remove Homebrew downloads older than DAYS (default: 30).");
+    println!("  --dry-run         Only report what would be removed.");
+    println!("  --config <PATH>   Use custom configuration file.");
+
+    if detailed {
+        println!();
+        println!("'clean' runs 'brew cleanup --prune <DAYS>' when Homebrew is installed, removing");
+        println!("old bottles and formula versions, and deletes download and extraction");
+        println!("directories that interrupted runs left in the work directory. The space");
+        println!("reclaimed is reported for each. The prune age defaults to");
+        println!("'brew_cleanup_prune_days' in tools.yaml.");
+        println!();
+        println!(
+            "Set 'brew_cleanup: true' in tools.yaml to run 'brew cleanup' automatically after"
+        );
+        println!("runs that installed or updated brew tools.");
+        println!();
+        println!("{}", "Examples:".bold().yellow());
+        println!("  setup-devbox clean --dry-run");
+        println!("  setup-devbox clean --prune 7");
+    }
+}
@@ -0,0 +1,41 @@
+use colored::Colorize;
+
+pub fn show_daemon_help(detailed: bool) {
+    println!("{}", "setup-devbox daemon".bold().blue());
+    println!("Serve a local JSON-RPC API for editor extensions and GUIs.");
+    println!();
+    println!("Listens on a Unix socket and accepts newline-delimited JSON-RPC 2.0");
+    println!("requests, so clients can plan and apply installations and follow their");
+    println!("progress without re-invoking the CLI.");
+    println!();
+    println!("{}", "Usage:".bold().yellow());
+    println!("  setup-devbox daemon [OPTIONS]");
+    println!();
+    println!("{}", "Options:".bold().yellow());
+    println!("  --config <PATH>  Use custom configuration file.");
+    println!("  --state <PATH>   Use custom state file.");
+    println!("  --socket <PATH>  Socket path (default: daemon.sock next to state.json).");
+
+    if detailed {
+        println!();
+        println!("{}", "Methods:".bold().yellow());
+        println!("  plan    What 'now' would do for each tool (update_latest)");
+        println!("  apply   Run 'now', streaming 'log' notifications (update_latest, dry_run)");
+        println!("  status  Installed tools and whether an apply is running");
+        println!("  logs    Recent log lines; 'follow' keeps streaming new ones");
+        println!();
+        println!("{}", "Authentication:".bold().yellow());
+        println!("  Every request must pass the token from daemon.token (created with");
+        println!("  0600 permissions on first start) as params.token.");
+        println!();
+        println!("{}", "Examples:".bold().yellow());
+        println!("  # Start the daemon");
+        println!("  setup-devbox daemon");
+        println!();
+        println!("  # Ask for a plan");
+        println!(
+            "  echo '{{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"plan\",\"params\":{{\"token\":\"'$(cat ~/.setup-devbox/daemon.token)'\"}}}}' \\"
+        );
+        println!("    | nc -U ~/.setup-devbox/daemon.sock");
+    }
+}
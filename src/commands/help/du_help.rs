@@ -0,0 +1,24 @@
+use colored::Colorize;
+
+pub fn show_du_help(detailed: bool) {
+    println!("{}", "setup-devbox du".bold().blue());
+    println!("Show the disk space taken by installed tools and fonts, largest first.");
+    println!();
+    println!("{}", "Usage:".bold().yellow());
+    println!("  setup-devbox du [OPTIONS]");
+    println!();
+    println!("{}", "Options:".bold().yellow());
+    println!("  --config <PATH>   Use custom configuration file.");
+    println!("  --state <PATH>    Use custom state file.");
+
+    if detailed {
+        println!();
+        println!("A tool accounts for its executable, extra assets, shim and the download kept");
+        println!("for delta updates. Totals follow for tools, fonts, ~/.setup-devbox and its");
+        println!("cache, and the files in ~/bin that no tool accounts for.");
+        println!();
+        println!("{}", "Examples:".bold().yellow());
+        println!("  setup-devbox du");
+        println!("  setup-devbox du --state ./state.json");
+    }
+}
@@ -0,0 +1,25 @@
+use colored::Colorize;
+
+pub fn show_facts_help(detailed: bool) {
+    println!("{}", "setup-devbox facts".bold().blue());
+    println!("Show the machine facts available to conditions, templates and hooks.");
+    println!();
+    println!("{}", "Usage:".bold().yellow());
+    println!("  setup-devbox facts [OPTIONS]");
+    println!();
+    println!("{}", "Options:".bold().yellow());
+    println!("  --config <PATH>   Use custom configuration file (for vpn_check).");
+    println!("  --json            Print the facts as JSON.");
+
+    if detailed {
+        println!();
+        println!("Facts: hostname, os, arch, cpu_count, ram_gb, virtualized, container, ci and,");
+        println!("when 'vpn_check' is set in config.yaml, vpn (whether that command succeeds).");
+        println!("They are used by 'only_on' conditions of tools, by '{{{{ facts.<name> }}}}'");
+        println!("placeholders in configuration files, and passed to hooks as SDB_FACT_<NAME>.");
+        println!();
+        println!("{}", "Examples:".bold().yellow());
+        println!("  setup-devbox facts");
+        println!("  setup-devbox facts --json");
+    }
+}
@@ -0,0 +1,31 @@
+use colored::Colorize;
+
+pub fn show_fonts_help(detailed: bool) {
+    println!("{}", "setup-devbox fonts".bold().blue());
+    println!("Inspect font releases before installing them.");
+    println!();
+    println!("{}", "Usage:".bold().yellow());
+    println!("  setup-devbox fonts info <NAME|OWNER/REPO> [OPTIONS]");
+    println!();
+    println!("{}", "Options:".bold().yellow());
+    println!("  --version <TAG>   Release to inspect (default: the font's tag, or latest).");
+    println!("  --asset <NAME>    Archive to inspect when a release contains several fonts.");
+    println!("  --config <PATH>   Use custom configuration file.");
+
+    if detailed {
+        println!();
+        println!(
+            "'fonts info' downloads the font archive to a temporary directory and lists every"
+        );
+        println!("font file with the family and style from its name table, its size and the");
+        println!("estimated install size. Fonts defined in fonts.yaml use their repo, tag and");
+        println!("'install_only' patterns and 'variant' (selected files are marked); other names");
+        println!("are looked up in ryanoasis/nerd-fonts. Passing a repository lists its archives.");
+        println!();
+        println!("{}", "Examples:".bold().yellow());
+        println!("  setup-devbox fonts info FiraCode");
+        println!("  setup-devbox fonts info JetBrainsMono --version v3.2.1");
+        println!("  setup-devbox fonts info ryanoasis/nerd-fonts");
+        println!("  setup-devbox fonts info tonsky/FiraCode --asset Fira_Code_v6.2.zip");
+    }
+}
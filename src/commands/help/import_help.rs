@@ -0,0 +1,29 @@
+use colored::Colorize;
+
+pub fn show_import_help(detailed: bool) {
+    println!("{}", "setup-devbox import".bold().blue());
+    println!("Generate configuration entries for software already installed on this machine.");
+    println!();
+    println!("{}", "Usage:".bold().yellow());
+    println!("  setup-devbox import apps [OPTIONS]");
+    println!();
+    println!("{}", "Options:".bold().yellow());
+    println!("  --dir <PATH>      Directory to scan (default: /Applications and ~/Applications).");
+    println!("  --write           Add the suggested entries to tools.yaml.");
+    println!("  --config <PATH>   Use custom configuration file.");
+
+    if detailed {
+        println!();
+        println!("'import apps' scans the application directories for .app bundles and matches");
+        println!("each one against installed Homebrew casks, the Homebrew cask registry and a");
+        println!("list of applications released on GitHub. Matches are printed as tools.yaml");
+        println!("entries (brew with '--cask', or github with the latest release tag); apps that");
+        println!("are already configured or come with macOS are skipped. With --write the");
+        println!("entries are added to tools.yaml; review them before running 'setup-devbox now'.");
+        println!();
+        println!("{}", "Examples:".bold().yellow());
+        println!("  setup-devbox import apps");
+        println!("  setup-devbox import apps --write");
+        println!("  setup-devbox import apps --dir ~/Applications");
+    }
+}
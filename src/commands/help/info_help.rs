@@ -0,0 +1,23 @@
+use colored::Colorize;
+
+pub fn show_info_help(detailed: bool) {
+    println!("{}", "setup-devbox info".bold().blue());
+    println!("Show the effective configuration, state and resolved path of one tool.");
+    println!();
+    println!("{}", "Usage:".bold().yellow());
+    println!("  setup-devbox info <NAME> [OPTIONS]");
+    println!();
+    println!("{}", "Options:".bold().yellow());
+    println!("  --config <PATH>   Use custom configuration file.");
+    println!("  --state <PATH>    Use custom state file.");
+
+    if detailed {
+        println!();
+        println!("Shows the configuration entry after platform overrides, whether its only_on");
+        println!("conditions hold, the state entry, where the executable resolves on PATH,");
+        println!("the managed configuration files and the hooks of the last installation.");
+        println!();
+        println!("{}", "Examples:".bold().yellow());
+        println!("  setup-devbox info ripgrep");
+    }
+}
@@ -0,0 +1,26 @@
+use colored::Colorize;
+
+pub fn show_licenses_help(detailed: bool) {
+    println!("{}", "setup-devbox licenses".bold().blue());
+    println!("List the licenses recorded for installed tools and fonts.");
+    println!();
+    println!("{}", "Usage:".bold().yellow());
+    println!("  setup-devbox licenses [OPTIONS]");
+    println!();
+    println!("{}", "Options:".bold().yellow());
+    println!("  --state <PATH>  Use custom state file.");
+
+    if detailed {
+        println!();
+        println!("Licenses are looked up from the GitHub repository of each tool or font when it");
+        println!(
+            "is installed. Entries with 'requires_license_acceptance: true' are only installed"
+        );
+        println!("after their license was accepted interactively or with 'now --accept-licenses';");
+        println!("the acceptance time is shown in the 'Accepted' column.");
+        println!();
+        println!("{}", "Examples:".bold().yellow());
+        println!("  setup-devbox licenses");
+        println!("  setup-devbox now --accept-licenses");
+    }
+}
@@ -0,0 +1,29 @@
+use colored::Colorize;
+
+pub fn show_list_help(detailed: bool) {
+    println!("{}", "setup-devbox list".bold().blue());
+    println!("List installed tools, fonts and applied settings from the state file.");
+    println!();
+    println!("{}", "Usage:".bold().yellow());
+    println!("  setup-devbox list [tools|fonts|settings|aliases] [OPTIONS]");
+    println!();
+    println!("{}", "Options:".bold().yellow());
+    println!("  --output <FORMAT> Print tables ('text') or one 'json' or 'yaml' document.");
+    println!("  --config <PATH>   Use custom configuration file (for aliases).");
+    println!("  --state <PATH>    Use custom state file.");
+
+    if detailed {
+        println!();
+        println!("Tools are shown with their version, source, install path and when they were");
+        println!("last updated; fonts with their version, source, scope and number of files;");
+        println!("settings with their domain, key, value and type. Aliases are not recorded in");
+        println!("the state file and are read from shellrc.yaml. Without a type, everything is");
+        println!("listed. --output json|yaml prints the same data as one document on stdout.");
+        println!();
+        println!("{}", "Examples:".bold().yellow());
+        println!("  setup-devbox list");
+        println!("  setup-devbox list tools");
+        println!("  setup-devbox list fonts --output json | jq '.fonts[].name'");
+        println!("  setup-devbox list settings --output yaml");
+    }
+}
@@ -0,0 +1,31 @@
+use colored::Colorize;
+
+pub fn show_migrate_config_help(detailed: bool) {
+    println!("{}", "setup-devbox migrate-config".bold().blue());
+    println!("Rewrite deprecated configuration fields to their current names.");
+    println!();
+    println!("Deprecated fields keep working (with a warning) when configurations are");
+    println!("loaded; this command updates the files in place, keeping comments and");
+    println!("layout, and saves the original as <file>.yaml.bak.");
+    println!();
+    println!("{}", "Usage:".bold().yellow());
+    println!("  setup-devbox migrate-config [OPTIONS]");
+    println!();
+    println!("{}", "Options:".bold().yellow());
+    println!("  --config <PATH>  Use custom configuration file.");
+    println!("  --dry-run        Show what would change without writing any file.");
+
+    if detailed {
+        println!();
+        println!("{}", "Renamed fields:".bold().yellow());
+        println!("  additional_cmd            → post_installation_hooks   (tools.yaml)");
+        println!("  tools_configuration_path  → tools_configuration_paths (tools.yaml)");
+        println!();
+        println!("{}", "Examples:".bold().yellow());
+        println!("  # Preview the changes");
+        println!("  setup-devbox migrate-config --dry-run");
+        println!();
+        println!("  # Migrate a single file");
+        println!("  setup-devbox migrate-config --config ~/dotfiles/tools.yaml");
+    }
+}
@@ -1,22 +1,64 @@
 pub mod add_help;
 pub mod bootstrap_help;
+pub mod bootstrap_script_help;
 pub mod check_updates_help;
+pub mod clean_help;
+pub mod daemon_help;
+pub mod du_help;
 pub mod edit_help;
+pub mod facts_help;
+pub mod fonts_help;
+pub mod import_help;
+pub mod info_help;
 pub mod installers_help;
+pub mod licenses_help;
+pub mod list_help;
+pub mod migrate_config_help;
+pub mod new_help;
 pub mod now_help;
+pub mod owns_help;
+pub mod plan_help;
+pub mod prompt_status_help;
+pub mod reconcile_help;
 pub mod remove_help;
 pub mod reset_help;
+pub mod shellrc_help;
+pub mod state_help;
+pub mod status_help;
+pub mod support_bundle_help;
 pub mod sync_config_help;
+pub mod ui_help;
 
 use self::add_help::show_add_help;
 use self::bootstrap_help::show_bootstrap_help;
+use self::bootstrap_script_help::show_bootstrap_script_help;
 use self::check_updates_help::show_check_updates_help;
+use self::clean_help::show_clean_help;
+use self::daemon_help::show_daemon_help;
+use self::du_help::show_du_help;
 use self::edit_help::show_edit_help;
+use self::facts_help::show_facts_help;
+use self::fonts_help::show_fonts_help;
+use self::import_help::show_import_help;
+use self::info_help::show_info_help;
 use self::installers_help::{add_supported_installers, show_installers_help};
+use self::licenses_help::show_licenses_help;
+use self::list_help::show_list_help;
+use self::migrate_config_help::show_migrate_config_help;
+use self::new_help::show_new_help;
 use self::now_help::show_now_help;
+use self::owns_help::show_owns_help;
+use self::plan_help::show_plan_help;
+use self::prompt_status_help::show_prompt_status_help;
+use self::reconcile_help::show_reconcile_help;
 use self::remove_help::show_remove_help;
 use self::reset_help::show_reset_help;
+use self::shellrc_help::show_shellrc_help;
+use self::state_help::show_state_help;
+use self::status_help::show_status_help;
+use self::support_bundle_help::show_support_bundle_help;
 use self::sync_config_help::show_sync_config_help;
+use self::ui_help::show_ui_help;
 use colored::Colorize;
 use std::fmt::Write;
 
@@ -25,8 +67,29 @@ pub fn run(topic: Option<String>, detailed: bool, filter: Option<String>) {
         Some("add") => show_add_help(detailed),
         Some("edit") => show_edit_help(detailed),
         Some("bootstrap") => show_bootstrap_help(detailed),
+        Some("bootstrap-script") => show_bootstrap_script_help(detailed),
         Some("installers") => show_installers_help(detailed, filter),
         Some("now") => show_now_help(detailed),
+        Some("reconcile") => show_reconcile_help(detailed),
+        Some("daemon") => show_daemon_help(detailed),
+        Some("ui") => show_ui_help(detailed),
+        Some("migrate-config") => show_migrate_config_help(detailed),
+        Some("shellrc") => show_shellrc_help(detailed),
+        Some("info") => show_info_help(detailed),
+        Some("du") => show_du_help(detailed),
+        Some("status") => show_status_help(detailed),
+        Some("facts") => show_facts_help(detailed),
+        Some("clean") => show_clean_help(detailed),
+        Some("owns") => show_owns_help(detailed),
+        Some("plan") => show_plan_help(detailed),
+        Some("import") => show_import_help(detailed),
+        Some("prompt-status") => show_prompt_status_help(detailed),
+        Some("fonts") => show_fonts_help(detailed),
+        Some("licenses") => show_licenses_help(detailed),
+        Some("list") => show_list_help(detailed),
+        Some("new") => show_new_help(detailed),
+        Some("support-bundle") => show_support_bundle_help(detailed),
+        Some("state") => show_state_help(detailed),
         Some("remove") => show_remove_help(detailed),
         Some("reset") => show_reset_help(detailed),
         Some("sync-config" | "sync_config") => show_sync_config_help(detailed),
@@ -44,12 +107,42 @@ fn show_unknown_topic_error(topic: &str) {
     eprintln!("{}: Unknown help topic '{}'", "Error".red(), topic);
     println!("\n{}", "Available help topics:".bold().yellow());
 
-    const TOPICS: [(&str, &str); 10] = [
+    const TOPICS: [(&str, &str); 31] = [
         ("add", "Show help for the 'add' command"),
         ("edit", "Show help for the 'edit' command"),
         ("bootstrap", "Show help for the 'bootstrap' command"),
+        (
+            "bootstrap-script",
+            "Show help for the 'bootstrap-script' command",
+        ),
         ("installers", "Show all supported installers"),
         ("now", "Show help for the 'now' command"),
+        ("reconcile", "Show help for the 'reconcile' command"),
+        ("daemon", "Show help for the 'daemon' command"),
+        ("ui", "Show help for the 'ui' command"),
+        (
+            "migrate-config",
+            "Show help for the 'migrate-config' command",
+        ),
+        ("shellrc", "Show help for the 'shellrc' command"),
+        ("info", "Show help for the 'info' command"),
+        ("du", "Show help for the 'du' command"),
+        ("status", "Show help for the 'status' command"),
+        ("facts", "Show help for the 'facts' command"),
+        ("clean", "Show help for the 'clean' command"),
+        ("owns", "Show help for the 'owns' command"),
+        ("plan", "Show help for the 'plan' command"),
+        ("import", "Show help for the 'import' command"),
+        ("prompt-status", "Show help for the 'prompt-status' command"),
+        ("fonts", "Show help for the 'fonts' command"),
+        ("licenses", "Show help for the 'licenses' command"),
+        ("list", "Show help for the 'list' command"),
+        ("new", "Show help for the 'new' command"),
+        (
+            "support-bundle",
+            "Show help for the 'support-bundle' command",
+        ),
+        ("state", "Show help for the 'state' command"),
         ("remove", "Show help for the 'remove' command"),
         ("reset", "Show help for the 'reset' command"),
         ("sync-config", "Show help for the 'sync-config' command"),
@@ -92,12 +185,16 @@ fn show_general_help() {
 fn add_commands_info(output: &mut String) {
     let _ = writeln!(output, "{}", "Commands:".bold().yellow());
 
-    const COMMANDS: [(&str, &str); 10] = [
+    const COMMANDS: [(&str, &str); 31] = [
         (
             "now",
             "Installs and Configures Tools, Fonts, OS Settings and Shell Configs",
         ),
         ("bootstrap", "Bootstraps the development environment"),
+        (
+            "bootstrap-script",
+            "Print a script that sets up a new machine from a config repository",
+        ),
         (
             "sync-config",
             "Synchronizes or generates configurations from a state file",
@@ -118,6 +215,71 @@ fn add_commands_info(output: &mut String) {
             "reset",
             "Reset the installation state (wipes entries from state file)",
         ),
+        (
+            "reconcile",
+            "Cross-check state, configuration and filesystem and fix drift",
+        ),
+        (
+            "daemon",
+            "Serve a local JSON-RPC API for editor extensions and GUIs",
+        ),
+        ("ui", "Open a terminal dashboard for interactive management"),
+        (
+            "migrate-config",
+            "Rewrite deprecated configuration fields to their current names",
+        ),
+        (
+            "shellrc",
+            "Preview the changes shellrc.yaml would make to RC files",
+        ),
+        (
+            "info",
+            "Show the configuration, state and resolved path of one tool",
+        ),
+        (
+            "du",
+            "Show the disk space taken by installed tools and fonts",
+        ),
+        (
+            "status",
+            "Show drift between configuration, state and installed versions",
+        ),
+        (
+            "facts",
+            "Show the machine facts used by conditions and templates",
+        ),
+        (
+            "clean",
+            "Reclaim disk space: old Homebrew downloads and work directory leftovers",
+        ),
+        ("owns", "Show which installed tool a file belongs to"),
+        (
+            "plan",
+            "Show or save the changes 'now' would make, for review",
+        ),
+        (
+            "import",
+            "Suggest tool entries for installed macOS applications",
+        ),
+        (
+            "prompt-status",
+            "Print a provisioning status segment for shell prompts",
+        ),
+        ("fonts", "Inspect font releases before installing them"),
+        ("licenses", "List the licenses of installed tools and fonts"),
+        ("list", "List installed tools, fonts, settings and aliases"),
+        (
+            "new",
+            "Create a project from a template registered in config.yaml",
+        ),
+        (
+            "support-bundle",
+            "Collect redacted diagnostics into a tarball for bug reports",
+        ),
+        (
+            "state",
+            "Restore the state file from a backup taken before an earlier save",
+        ),
         (
             "check-updates",
             "Checks for updates for all tools defined in tools.yaml",
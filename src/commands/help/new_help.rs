@@ -0,0 +1,28 @@
+use colored::Colorize;
+
+pub fn show_new_help(detailed: bool) {
+    println!("{}", "setup-devbox new".bold().blue());
+    println!("Create a project from a template registered in config.yaml.");
+    println!();
+    println!("{}", "Usage:".bold().yellow());
+    println!("  setup-devbox new <TEMPLATE> <DIR> [OPTIONS]");
+    println!("  setup-devbox new --list [OPTIONS]");
+    println!();
+    println!("{}", "Options:".bold().yellow());
+    println!("  --list            List the registered templates.");
+    println!("  --config <PATH>   Use custom configuration file.");
+    println!("  --state <PATH>    Use custom state file.");
+
+    if detailed {
+        println!();
+        println!("Templates are registered under 'scaffolds:' in config.yaml with the generator");
+        println!("that creates projects from them: 'cargo-generate', 'cookiecutter' or 'degit'.");
+        println!("A generator that is not on PATH is installed first (cargo-generate with cargo,");
+        println!("cookiecutter with uv or pip) and recorded in the state file; degit runs through");
+        println!("'npx' when it is not installed.");
+        println!();
+        println!("{}", "Examples:".bold().yellow());
+        println!("  setup-devbox new rust-cli ~/code/my-tool");
+        println!("  setup-devbox new --list");
+    }
+}
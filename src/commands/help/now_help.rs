@@ -43,11 +43,53 @@ pub fn show_now_help(detailed: bool) {
     .unwrap();
     writeln!(
         output,
-        "  {} Force update all tools marked as '{}' version. Overrides the configuration \"update_latest_only_after\"\n",
+        "  {} Force update all tools marked as '{}' version. Overrides the configuration \"update_latest_only_after\"",
         "--update-latest".cyan(),
         "@latest".cyan()
     )
         .unwrap();
+    writeln!(
+        output,
+        "  {} Resume an interrupted run, skipping tools that already finished",
+        "--continue".cyan()
+    )
+    .unwrap();
+    writeln!(
+        output,
+        "  {} Show what changed since the previous run (new failures, version bumps)",
+        "--diff-last".cyan()
+    )
+    .unwrap();
+    writeln!(
+        output,
+        "  {} Accept licenses of entries marked 'requires_license_acceptance' without prompting",
+        "--accept-licenses".cyan()
+    )
+    .unwrap();
+    writeln!(
+        output,
+        "  {} Let a tool's 'rename_to' replace the executable of another installed tool",
+        "--force".cyan()
+    )
+    .unwrap();
+    writeln!(
+        output,
+        "  {} Apply exactly the actions of a plan saved with 'setup-devbox plan --out'",
+        "--from-plan <FILE>".cyan()
+    )
+    .unwrap();
+    writeln!(
+        output,
+        "  {} Also write new PATH entries and aliases to a snippet this shell can source",
+        "--activation-file".cyan()
+    )
+    .unwrap();
+    writeln!(
+        output,
+        "  {} Only process entries of these profiles and entries without 'profiles'\n",
+        "--profile <NAME>".cyan()
+    )
+    .unwrap();
 
     // Conditionally add detailed or basic information based on the flag.
     if detailed {
@@ -138,6 +180,8 @@ pub fn add_now_detailed_examples(output: &mut String) {
         "setup-devbox now",
         "setup-devbox now --config ./my-config.yaml",
         "setup-devbox now --update-latest",
+        "setup-devbox now --continue",
+        "setup-devbox now --diff-last",
         "setup-devbox now --config custom.yaml --state custom-state.json",
     ];
 
@@ -0,0 +1,25 @@
+use colored::Colorize;
+
+pub fn show_owns_help(detailed: bool) {
+    println!("{}", "setup-devbox owns".bold().blue());
+    println!("Show which installed tool a file belongs to.");
+    println!();
+    println!("{}", "Usage:".bold().yellow());
+    println!("  setup-devbox owns <PATH> [OPTIONS]");
+    println!();
+    println!("{}", "Options:".bold().yellow());
+    println!("  --state <PATH>    Use custom state file.");
+
+    if detailed {
+        println!();
+        println!("Every installation records a receipt in ~/.setup-devbox/receipts/<tool>.json");
+        println!("listing the files it created or modified (executable, completions, man pages,");
+        println!("assets, shims, configuration files) with their checksums. 'owns' looks the file");
+        println!("up in these receipts and reports whether it changed since it was installed.");
+        println!("'setup-devbox remove' uses the receipt to delete every file the tool created.");
+        println!();
+        println!("{}", "Examples:".bold().yellow());
+        println!("  setup-devbox owns ~/bin/rg");
+        println!("  setup-devbox owns ~/.local/share/man/man1/fd.1");
+    }
+}
@@ -0,0 +1,36 @@
+use colored::Colorize;
+
+pub fn show_plan_help(detailed: bool) {
+    println!("{}", "setup-devbox plan".bold().blue());
+    println!("Show the ordered list of changes 'setup-devbox now' would make.");
+    println!();
+    println!("{}", "Usage:".bold().yellow());
+    println!("  setup-devbox plan [OPTIONS]");
+    println!();
+    println!("{}", "Options:".bold().yellow());
+    println!("  --config <PATH>   Use custom configuration file.");
+    println!("  --state <PATH>    Use custom state file.");
+    println!("  --update-latest   Plan updates of every tool with version 'latest'.");
+    println!("  --json            Print the plan as JSON.");
+    println!("  --out <FILE>      Save the plan as JSON for 'now --from-plan'.");
+
+    if detailed {
+        println!();
+        println!(
+            "The plan lists, in the order 'now' applies them, the tools to install, update or"
+        );
+        println!("reconfigure, the fonts to install, the shell RC files to render and the macOS");
+        println!("settings to apply. Nothing is changed.");
+        println!();
+        println!(
+            "'setup-devbox now --from-plan <FILE>' applies exactly the saved actions. It plans"
+        );
+        println!("again first and refuses to run if the result differs, e.g. because tools.yaml");
+        println!("or state.json changed since the plan was reviewed.");
+        println!();
+        println!("{}", "Examples:".bold().yellow());
+        println!("  setup-devbox plan");
+        println!("  setup-devbox plan --out plan.json");
+        println!("  setup-devbox now --from-plan plan.json");
+    }
+}
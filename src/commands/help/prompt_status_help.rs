@@ -0,0 +1,32 @@
+use colored::Colorize;
+
+pub fn show_prompt_status_help(detailed: bool) {
+    println!("{}", "setup-devbox prompt-status".bold().blue());
+    println!("Print a short provisioning status segment for your shell prompt.");
+    println!();
+    println!("{}", "Usage:".bold().yellow());
+    println!("  setup-devbox prompt-status [OPTIONS]");
+    println!();
+    println!("{}", "Options:".bold().yellow());
+    println!("  --format <FORMAT>  starship (default) or p10k.");
+    println!("  --state <PATH>     Use custom state file.");
+
+    if detailed {
+        println!();
+        println!("The segment shows 'devbox ✔' when in sync, 'devbox ⟳' when the state changed");
+        println!("since the last 'now' run or 'check-updates' found updates, 'devbox ✘N' when");
+        println!("N tools failed in the last run, and 'devbox ?' before the first run. It only");
+        println!("reads the state file's modification time and the last run summary.");
+        println!();
+        println!("{}", "Starship (~/.config/starship.toml):".bold().yellow());
+        println!("  [custom.devbox]");
+        println!("  command = \"setup-devbox prompt-status --format starship\"");
+        println!("  when = true");
+        println!();
+        println!("{}", "Powerlevel10k (~/.p10k.zsh):".bold().yellow());
+        println!("  function prompt_devbox() {{");
+        println!("    p10k segment -e -t \"$(setup-devbox prompt-status --format p10k)\"");
+        println!("  }}");
+        println!("  # and add 'devbox' to POWERLEVEL9K_RIGHT_PROMPT_ELEMENTS");
+    }
+}
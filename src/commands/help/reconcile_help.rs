@@ -0,0 +1,34 @@
+use colored::Colorize;
+
+pub fn show_reconcile_help(detailed: bool) {
+    println!("{}", "setup-devbox reconcile".bold().blue());
+    println!("Cross-check state, configuration and filesystem and fix drift.");
+    println!();
+    println!("Finds tools installed outside setup-devbox, orphaned state entries,");
+    println!("missing binaries and managed configuration files that drifted from their");
+    println!("source, and proposes a fix for each one.");
+    println!();
+    println!("{}", "Usage:".bold().yellow());
+    println!("  setup-devbox reconcile [OPTIONS]");
+    println!();
+    println!("{}", "Options:".bold().yellow());
+    println!("  --config <PATH>  Use custom configuration file.");
+    println!("  --state <PATH>   Use custom state file.");
+    println!("  --yes            Apply all proposed fixes without prompting.");
+
+    if detailed {
+        println!();
+        println!("{}", "Fixes:".bold().yellow());
+        println!("  adopt external binary     Track a configured tool already found on PATH");
+        println!("  prune orphaned state      Drop state entries for tools no longer configured");
+        println!("  reinstall missing binary  Reinstall tools whose recorded binary is gone");
+        println!("  re-render config          Re-apply managed configuration files");
+        println!();
+        println!("{}", "Examples:".bold().yellow());
+        println!("  # Review findings and confirm each fix");
+        println!("  setup-devbox reconcile");
+        println!();
+        println!("  # Apply every fix non-interactively (e.g. in CI)");
+        println!("  setup-devbox reconcile --yes");
+    }
+}
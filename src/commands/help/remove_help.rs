@@ -61,11 +61,18 @@ pub fn show_remove_help(detailed: bool) {
     .unwrap();
     writeln!(
         output,
-        "  {}     - WIP: Remove a macOS setting from {} configuration\n",
+        "  {}     - WIP: Remove a macOS setting from {} configuration",
         "setting".green(),
         "settings.yaml".cyan()
     )
     .unwrap();
+    writeln!(
+        output,
+        "  {} - Unload and delete a launch agent from {}\n",
+        "launch-agent".green(),
+        "settings.yaml".cyan()
+    )
+    .unwrap();
 
     // Conditionally add detailed or basic information based on the flag.
     if detailed {
@@ -132,6 +139,17 @@ fn add_remove_basic_info(output: &mut String) {
     .unwrap();
     writeln!(output).unwrap();
 
+    writeln!(output, "  {} Remove a launch agent:", "•".bold()).unwrap();
+    writeln!(
+        output,
+        "  {}",
+        "    setup-devbox remove launch-agent <LABEL>"
+            .cyan()
+            .italic()
+    )
+    .unwrap();
+    writeln!(output).unwrap();
+
     writeln!(
         output,
         "{} Use {} for detailed examples and behavior information.",
@@ -204,7 +222,7 @@ fn add_remove_detailed_info(output: &mut String) {
     writeln!(output, "{}", "Supported Installers:".bold().yellow()).unwrap();
     writeln!(
         output,
-        "  Tools installed via: cargo, pip, brew, go, rustup, uv, github, or url\n"
+        "  Tools installed via: cargo, pip, brew, go, rustup, uv, github, url, or oci\n"
     )
     .unwrap();
 
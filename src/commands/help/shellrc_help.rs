@@ -0,0 +1,27 @@
+use colored::Colorize;
+
+pub fn show_shellrc_help(detailed: bool) {
+    println!("{}", "setup-devbox shellrc".bold().blue());
+    println!("Inspect the shell configuration before it is applied.");
+    println!();
+    println!("{}", "Usage:".bold().yellow());
+    println!("  setup-devbox shellrc preview [OPTIONS]");
+    println!();
+    println!("{}", "Options:".bold().yellow());
+    println!("  --config <PATH>   Use custom configuration file.");
+
+    if detailed {
+        println!();
+        println!(
+            "'shellrc preview' renders the run commands and aliases of shellrc.yaml for every"
+        );
+        println!("configured shell and prints, as a diff against the current RC file, exactly");
+        println!("what 'now' would write. Nothing is written. When existing entries change, the");
+        println!(
+            "file is rebuilt from scratch, which 'now' only does with SDB_RESET_SHELLRC_FILE."
+        );
+        println!();
+        println!("{}", "Examples:".bold().yellow());
+        println!("  setup-devbox shellrc preview");
+    }
+}
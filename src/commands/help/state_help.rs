@@ -0,0 +1,33 @@
+use colored::Colorize;
+
+pub fn show_state_help(detailed: bool) {
+    println!("{}", "setup-devbox state".bold().blue());
+    println!("Roll the state file back to a backup taken before an earlier save.");
+    println!();
+    println!("{}", "Usage:".bold().yellow());
+    println!("  setup-devbox state restore [OPTIONS]");
+    println!();
+    println!("{}", "Options:".bold().yellow());
+    println!("  --from <BACKUP>   Backup to restore: a file name in state-backups/ or a path");
+    println!("                    (default: the newest backup).");
+    println!("  --list            Only list the available backups, newest first.");
+    println!("  --state <PATH>    Use custom state file.");
+
+    if detailed {
+        println!();
+        println!("The state file is always written through a temporary file that is renamed over");
+        println!("it, and before it is replaced its previous contents are copied to");
+        println!(
+            "state-backups/ next to it (~/.setup-devbox/state-backups/state-<timestamp>.json)."
+        );
+        println!("The 10 newest backups are kept; set SDB_STATE_BACKUPS to keep another number.");
+        println!();
+        println!("'state restore' checks that the backup parses before using it and backs up the");
+        println!("current state first, so a restore can be rolled back the same way.");
+        println!();
+        println!("{}", "Examples:".bold().yellow());
+        println!("  setup-devbox state restore");
+        println!("  setup-devbox state restore --list");
+        println!("  setup-devbox state restore --from state-20250101_120000_123456.json");
+    }
+}
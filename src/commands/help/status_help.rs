@@ -0,0 +1,28 @@
+use colored::Colorize;
+
+pub fn show_status_help(detailed: bool) {
+    println!("{}", "setup-devbox status".bold().blue());
+    println!("Show, for every tool, whether configuration, state and system agree.");
+    println!();
+    println!("{}", "Usage:".bold().yellow());
+    println!("  setup-devbox status [OPTIONS]");
+    println!();
+    println!("{}", "Options:".bold().yellow());
+    println!("  --output <FORMAT> Print a table ('text') or one 'json' or 'yaml' document.");
+    println!("  --config <PATH>   Use custom configuration file.");
+    println!("  --state <PATH>    Use custom state file.");
+
+    if detailed {
+        println!();
+        println!("Each row shows the version in tools.yaml, the version in state.json and the");
+        println!("version installed on this machine, as reported by brew, cargo or pip, or by");
+        println!("the executable's '--version' output. The status column names each mismatch:");
+        println!("not installed, untracked, orphaned, missing, config != state, state != system.");
+        println!("Nothing is changed; use 'reconcile' or 'now' to fix what it finds.");
+        println!();
+        println!("{}", "Examples:".bold().yellow());
+        println!("  setup-devbox status");
+        println!("  setup-devbox status --config ./config.yaml --state ./state.json");
+        println!("  setup-devbox status --output json | jq '.tools[] | select(.drift != [])'");
+    }
+}
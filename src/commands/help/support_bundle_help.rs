@@ -0,0 +1,31 @@
+use colored::Colorize;
+
+pub fn show_support_bundle_help(detailed: bool) {
+    println!("{}", "setup-devbox support-bundle".bold().blue());
+    println!("Collect redacted diagnostics into a tarball to attach to bug reports.");
+    println!();
+    println!("{}", "Usage:".bold().yellow());
+    println!("  setup-devbox support-bundle [OPTIONS]");
+    println!();
+    println!("{}", "Options:".bold().yellow());
+    println!("  -o, --output <PATH>   Where to write the tarball");
+    println!("                        (default: setup-devbox-support-<timestamp>.tar.gz).");
+    println!("  --config <PATH>       Use custom configuration file.");
+    println!("  --state <PATH>        Use custom state file.");
+
+    if detailed {
+        println!();
+        println!("The tarball contains the setup-devbox version, OS and architecture, machine");
+        println!("facts (without the hostname), PATH, the versions of the package managers");
+        println!("installers use, config.yaml and the files it links to, the last and previous");
+        println!("run summaries and the version, installer and path of every recorded tool.");
+        println!();
+        println!("Values of keys like token, password or api_key, tokens embedded in text and");
+        println!("credentials in URLs are replaced by <redacted>, and the home directory is");
+        println!("shown as ~. Review the tarball before attaching it anywhere.");
+        println!();
+        println!("{}", "Examples:".bold().yellow());
+        println!("  setup-devbox support-bundle");
+        println!("  setup-devbox support-bundle --output /tmp/devbox-support.tar.gz");
+    }
+}
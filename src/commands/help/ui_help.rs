@@ -0,0 +1,32 @@
+use colored::Colorize;
+
+pub fn show_ui_help(detailed: bool) {
+    println!("{}", "setup-devbox ui".bold().blue());
+    println!("Open a terminal dashboard for interactive management.");
+    println!();
+    println!("Lists configured tools, fonts and settings with their sync status and");
+    println!("lets you select items to install, update or remove.");
+    println!();
+    println!("{}", "Usage:".bold().yellow());
+    println!("  setup-devbox ui [OPTIONS]");
+    println!();
+    println!("{}", "Options:".bold().yellow());
+    println!("  --config <PATH>  Use custom configuration file.");
+    println!("  --state <PATH>   Use custom state file.");
+
+    if detailed {
+        println!();
+        println!("{}", "Keys:".bold().yellow());
+        println!("  ↑/↓ or j/k   Move the cursor");
+        println!("  space        Select or deselect the item under the cursor");
+        println!("  i            Install or update the selected items");
+        println!("  r            Remove the selected items (asks for confirmation)");
+        println!("  a            Apply everything, like 'setup-devbox now'");
+        println!("  R            Refresh statuses");
+        println!("  tab / 1-4    Switch between Tools, Fonts, Settings and Logs");
+        println!("  q / esc      Quit");
+        println!();
+        println!("Actions run in the normal terminal; their log output is shown in the");
+        println!("Logs tab afterwards.");
+    }
+}
@@ -0,0 +1,391 @@
+//! # Import Command Implementation
+//!
+//! `setup-devbox import apps` adopts software that was installed by hand. It scans
+//! `/Applications` and `~/Applications` for `.app` bundles and matches each one against:
+//!
+//! 1. Homebrew casks already installed on this machine (`brew info --installed --cask`),
+//! 2. the Homebrew cask registry, by guessing the cask token from the application name,
+//! 3. a small table of applications known to ship as GitHub releases.
+//!
+//! Matches are printed as suggested `tools.yaml` entries; with `--write` they are added to
+//! the configuration so the next `setup-devbox now` manages them.
+
+use crate::cli::cmd_enums::ImportCommands;
+use crate::commands::add::write_tool_entries;
+use crate::commands::load_configs_or_exit;
+use crate::core::metadata_cache;
+use crate::engine::installers::github::fetch_latest_github_release;
+use crate::schemas::path_resolver::PathResolver;
+use crate::schemas::tools_enums::SourceType;
+use crate::schemas::tools_types::ToolEntry;
+use crate::{log_debug, log_error, log_info, log_warn};
+use colored::Colorize;
+use prettytable::{Cell, Row, Table};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Applications distributed as GitHub releases, by bundle name.
+const KNOWN_GITHUB_APPS: &[(&str, &str)] = &[
+    ("AltTab", "lwouis/alt-tab-macos"),
+    ("Alacritty", "alacritty/alacritty"),
+    ("Hammerspoon", "Hammerspoon/hammerspoon"),
+    ("Ice", "jordanbaird/Ice"),
+    ("IINA", "iina/iina"),
+    ("KeyCastr", "keycastr/keycastr"),
+    ("kitty", "kovidgoyal/kitty"),
+    ("LinearMouse", "linearmouse/linearmouse"),
+    ("Maccy", "p0deje/Maccy"),
+    ("MonitorControl", "MonitorControl/MonitorControl"),
+    ("Rectangle", "rxhanson/Rectangle"),
+    ("Stats", "exelban/stats"),
+    ("WezTerm", "wez/wezterm"),
+];
+
+/// An application bundle found on disk.
+struct InstalledApp {
+    /// Bundle file name without `.app`, e.g. `Visual Studio Code`.
+    name: String,
+    version: Option<String>,
+    bundle_id: Option<String>,
+}
+
+/// A suggested tool entry for an installed application.
+struct Suggestion {
+    app: InstalledApp,
+    entry: ToolEntry,
+    matched_by: &'static str,
+}
+
+/// Entry point for the `import` subcommand.
+pub fn run(action: ImportCommands) {
+    match action {
+        ImportCommands::Apps { dir, write, config } => import_apps(dir, write, config),
+    }
+}
+
+fn import_apps(dir: Option<String>, write: bool, config: Option<String>) {
+    log_debug!("[SDB::Import::Apps] Entering import_apps()");
+
+    let paths = match PathResolver::new(config, None) {
+        Ok(paths) => paths,
+        Err(e) => {
+            log_error!("[SDB::Import::Apps] Failed to resolve paths: {}", e);
+            std::process::exit(1);
+        }
+    };
+    metadata_cache::configure(paths.cache_dir(), None, false);
+
+    let scan_dirs = match dir {
+        Some(dir) => vec![PathBuf::from(dir)],
+        None => {
+            let mut defaults = vec![PathBuf::from("/Applications")];
+            if let Some(home) = dirs::home_dir() {
+                defaults.push(home.join("Applications"));
+            }
+            defaults
+        }
+    };
+    let apps = scan_applications(&scan_dirs);
+    if apps.is_empty() {
+        log_warn!(
+            "[SDB::Import::Apps] No applications found in {}",
+            display_dirs(&scan_dirs)
+        );
+        return;
+    }
+    log_info!(
+        "[SDB::Import::Apps] Found {} applications in {}",
+        apps.len(),
+        display_dirs(&scan_dirs)
+    );
+
+    let configured: HashSet<String> = if paths.config_file().exists() {
+        load_configs_or_exit(&paths)
+            .tools
+            .map(|tools| {
+                tools
+                    .tools
+                    .iter()
+                    .map(|tool| tool.name.to_lowercase())
+                    .collect()
+            })
+            .unwrap_or_default()
+    } else {
+        HashSet::new()
+    };
+
+    let installed_casks = installed_cask_apps();
+    let mut unmatched = Vec::new();
+    let mut suggestions = Vec::new();
+    for app in apps {
+        match suggest(&app, &installed_casks) {
+            Some((entry, matched_by)) => {
+                if configured.contains(&entry.name.to_lowercase()) {
+                    log_debug!(
+                        "[SDB::Import::Apps] {} is already configured as '{}'",
+                        app.name,
+                        entry.name
+                    );
+                    continue;
+                }
+                suggestions.push(Suggestion {
+                    app,
+                    entry,
+                    matched_by,
+                });
+            }
+            None => unmatched.push(app.name),
+        }
+    }
+
+    if suggestions.is_empty() {
+        log_info!("[SDB::Import::Apps] No new applications to import.");
+    } else {
+        print_suggestions(&suggestions);
+    }
+    if !unmatched.is_empty() {
+        println!(
+            "\n{} {}",
+            "No match found for:".dimmed(),
+            unmatched.join(", ").dimmed()
+        );
+    }
+    if suggestions.is_empty() {
+        return;
+    }
+
+    let entries: Vec<ToolEntry> = suggestions.into_iter().map(|s| s.entry).collect();
+    if !write {
+        println!(
+            "\nRun with {} to add these entries to tools.yaml.",
+            "--write".cyan()
+        );
+        return;
+    }
+    match write_tool_entries(&paths, &entries) {
+        Ok(count) => log_info!(
+            "[SDB::Import::Apps] Added {} entries to tools.yaml. Review them, then run {}.",
+            count,
+            "setup-devbox now".cyan()
+        ),
+        Err(e) => {
+            log_error!("[SDB::Import::Apps] Failed to update tools.yaml: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Lists the `.app` bundles directly inside `dirs`, skipping Apple's own applications.
+fn scan_applications(dirs: &[PathBuf]) -> Vec<InstalledApp> {
+    let mut apps: Vec<InstalledApp> = dirs
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "app"))
+        .map(|path| read_app(&path))
+        .filter(|app| {
+            !app.bundle_id
+                .as_deref()
+                .is_some_and(|id| id.starts_with("com.apple."))
+        })
+        .collect();
+    apps.sort_by_key(|app| app.name.to_lowercase());
+    apps.dedup_by(|a, b| a.name == b.name);
+    apps
+}
+
+#[cfg(target_os = "macos")]
+fn read_app(path: &Path) -> InstalledApp {
+    let info = plist::Value::from_file(path.join("Contents/Info.plist")).ok();
+    let field = |key: &str| {
+        info.as_ref()
+            .and_then(|info| info.as_dictionary())
+            .and_then(|dict| dict.get(key))
+            .and_then(|value| value.as_string())
+            .map(str::to_string)
+    };
+    InstalledApp {
+        name: app_name(path),
+        version: field("CFBundleShortVersionString"),
+        bundle_id: field("CFBundleIdentifier"),
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn read_app(path: &Path) -> InstalledApp {
+    InstalledApp {
+        name: app_name(path),
+        version: None,
+        bundle_id: None,
+    }
+}
+
+fn app_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Maps bundle names (e.g. `Visual Studio Code.app`) to the installed casks providing them.
+fn installed_cask_apps() -> HashMap<String, String> {
+    let output = match Command::new("brew")
+        .args(["info", "--json=v2", "--installed", "--cask"])
+        .output()
+    {
+        Ok(output) if output.status.success() => output.stdout,
+        Ok(_) | Err(_) => {
+            log_debug!("[SDB::Import::Apps] Could not list installed Homebrew casks");
+            return HashMap::new();
+        }
+    };
+    let Ok(info) = serde_json::from_slice::<Value>(&output) else {
+        return HashMap::new();
+    };
+
+    let mut apps = HashMap::new();
+    for cask in info["casks"].as_array().into_iter().flatten() {
+        let Some(token) = cask["token"].as_str() else {
+            continue;
+        };
+        for app in cask_app_artifacts(cask) {
+            apps.insert(app, token.to_string());
+        }
+    }
+    apps
+}
+
+/// The `.app` bundles a cask installs, from its `artifacts`.
+fn cask_app_artifacts(cask: &Value) -> Vec<String> {
+    cask["artifacts"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|artifact| artifact["app"].as_array())
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::to_string)
+        .collect()
+}
+
+/// Finds a tool entry that installs `app`, with a description of how it was matched.
+fn suggest(
+    app: &InstalledApp,
+    installed_casks: &HashMap<String, String>,
+) -> Option<(ToolEntry, &'static str)> {
+    let bundle = format!("{}.app", app.name);
+    if let Some(token) = installed_casks.get(&bundle) {
+        return Some((cask_entry(token), "installed cask"));
+    }
+
+    let token = cask_token(&app.name);
+    let url = format!("https://formulae.brew.sh/api/cask/{token}.json");
+    match metadata_cache::get(&format!("cask:{token}"), &url) {
+        Ok(body) => {
+            let provides_app = serde_json::from_str::<Value>(&body)
+                .is_ok_and(|cask| cask_app_artifacts(&cask).contains(&bundle));
+            if provides_app {
+                return Some((cask_entry(&token), "cask registry"));
+            }
+        }
+        Err(e) => log_debug!("[SDB::Import::Apps] No cask '{}': {}", token, e),
+    }
+
+    let (_, repo) = KNOWN_GITHUB_APPS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(&app.name))?;
+    Some((github_entry(app, repo), "GitHub release"))
+}
+
+/// Homebrew's naming convention for cask tokens: `Visual Studio Code` → `visual-studio-code`.
+fn cask_token(app_name: &str) -> String {
+    app_name
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .collect()
+}
+
+fn cask_entry(token: &str) -> ToolEntry {
+    let mut entry = ToolEntry::new(token, SourceType::Brew);
+    entry.version = Some("latest".to_string());
+    entry.options = Some(vec!["--cask".to_string()]);
+    entry
+}
+
+fn github_entry(app: &InstalledApp, repo: &str) -> ToolEntry {
+    let name = repo.rsplit('/').next().unwrap_or(repo).to_lowercase();
+    let tag = match fetch_latest_github_release(repo) {
+        Ok(release) => release.tag_name,
+        Err(e) => {
+            let fallback = format!("v{}", app.version.as_deref().unwrap_or("0.0.0"));
+            log_warn!(
+                "[SDB::Import::Apps] Could not look up the latest release of {} ({}); using tag {}, check it before installing",
+                repo,
+                e,
+                fallback
+            );
+            fallback
+        }
+    };
+    let mut entry = ToolEntry::new(name, SourceType::Github);
+    entry.version = Some("latest".to_string());
+    entry.repo = Some(repo.to_string());
+    entry.tag = Some(tag);
+    entry
+}
+
+fn print_suggestions(suggestions: &[Suggestion]) {
+    let mut table = Table::new();
+    table.add_row(Row::new(vec![
+        Cell::new("Application"),
+        Cell::new("Version"),
+        Cell::new("Suggested entry"),
+        Cell::new("Matched by"),
+    ]));
+    for suggestion in suggestions {
+        let source = match &suggestion.entry.repo {
+            Some(repo) => format!("github: {repo}"),
+            None => format!("brew cask: {}", suggestion.entry.name),
+        };
+        table.add_row(Row::new(vec![
+            Cell::new(&suggestion.app.name),
+            Cell::new(suggestion.app.version.as_deref().unwrap_or("-")),
+            Cell::new(&source),
+            Cell::new(suggestion.matched_by),
+        ]));
+    }
+    table.printstd();
+
+    let entries: Vec<&ToolEntry> = suggestions.iter().map(|s| &s.entry).collect();
+    match serde_yaml::to_string(&entries) {
+        Ok(yaml) => println!("\ntools:\n{}", yaml.trim_end()),
+        Err(e) => log_debug!("[SDB::Import::Apps] Failed to render entries: {}", e),
+    }
+}
+
+fn display_dirs(dirs: &[PathBuf]) -> String {
+    dirs.iter()
+        .map(|dir| dir.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cask_token() {
+        assert_eq!(cask_token("Visual Studio Code"), "visual-studio-code");
+        assert_eq!(cask_token("iTerm"), "iterm");
+        assert_eq!(cask_token("Ghostty"), "ghostty");
+    }
+}
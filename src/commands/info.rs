@@ -0,0 +1,321 @@
+//! # Info Command Implementation
+//!
+//! Shows everything setup-devbox knows about one tool in a single view: the effective
+//! configuration entry (after platform overrides), whether its `only_on` conditions hold
+//! here, the recorded state (including the installer version it was installed with, flagged
+//! when that installer has had a major upgrade since), where its executable resolves on
+//! `PATH`, its managed configuration files and the hooks run by the last installation.
+
+use std::path::Path;
+
+use colored::Colorize;
+
+use crate::commands::{load_configs_or_exit, load_state_or_exit};
+use crate::core::installer_version;
+use crate::core::platform::{detect_architecture, detect_os, find_in_path};
+use crate::engine::installation::planner::InstallationPlanner;
+use crate::fonts::info::format_size;
+use crate::schemas::path_resolver::PathResolver;
+use crate::schemas::state_file::{DevBoxState, ToolState};
+use crate::schemas::tools_types::ToolEntry;
+use crate::{log_debug, log_error};
+
+/// Entry point for the `info` subcommand. Exits with status 1 if the tool is neither
+/// configured nor installed.
+pub fn run(paths: &PathResolver, name: &str) {
+    log_debug!("[SDB::Info] Entering info::run()");
+
+    let tool = if paths.config_file().exists() {
+        load_configs_or_exit(paths)
+            .tools
+            .and_then(|config| config.tools.into_iter().find(|tool| tool.name == name))
+    } else {
+        None
+    };
+    let state: DevBoxState = load_state_or_exit(&paths.state_file().to_path_buf());
+    let tool_state = state.tools.get(name);
+
+    if tool.is_none() && tool_state.is_none() {
+        log_error!(
+            "[SDB::Info] '{}' is neither configured nor installed",
+            name.red()
+        );
+        std::process::exit(1);
+    }
+
+    println!("{}", name.bold().blue());
+
+    section(&format!(
+        "Configuration (effective on {}-{})",
+        detect_os(),
+        detect_architecture()
+    ));
+    match &tool {
+        Some(tool) => print_configuration(tool),
+        None => println!("  {}", "not in the configuration".yellow()),
+    }
+
+    section("State");
+    match tool_state {
+        Some(tool_state) => print_state(tool_state),
+        None => println!("  {}", "not installed".yellow()),
+    }
+
+    let executable = tool
+        .as_ref()
+        .and_then(|tool| tool.rename_to.clone())
+        .or_else(|| tool_state.and_then(|tool_state| tool_state.renamed_to.clone()))
+        .unwrap_or_else(|| name.to_string());
+    section("Resolution");
+    print_resolution(&executable, tool_state);
+
+    if let Some(tool) = &tool
+        && tool.configuration_manager.enabled
+    {
+        section("Managed configuration files");
+        for path in &tool.configuration_manager.destination_paths() {
+            let expanded = shellexpand::full(path).map_or(path.clone(), |path| path.into_owned());
+            let status = if Path::new(&expanded).exists() {
+                "present".green()
+            } else {
+                "missing".red()
+            };
+            println!("  {expanded} ({status})");
+        }
+        if let Some(config_state) = tool_state.and_then(ToolState::get_configuration_manager) {
+            field("source sha", &config_state.source_configuration_sha);
+            field("deployed sha", &config_state.destination_configuration_sha);
+        }
+    }
+
+    section("Last hooks");
+    match tool_state.and_then(|tool_state| tool_state.executed_post_installation_hooks.as_ref()) {
+        Some(hooks) if !hooks.is_empty() => {
+            for hook in hooks {
+                println!("  {hook}");
+            }
+        }
+        _ => println!("  {}", "none".dimmed()),
+    }
+}
+
+fn section(title: &str) {
+    println!();
+    println!("{}", title.bold().yellow());
+}
+
+fn field(label: &str, value: &str) {
+    println!("  {:<16} {}", format!("{label}:"), value);
+}
+
+fn print_configuration(tool: &ToolEntry) {
+    match serde_yaml::to_string(tool) {
+        Ok(yaml) => {
+            for line in yaml.lines() {
+                println!("  {line}");
+            }
+        }
+        Err(e) => println!("  {}", format!("cannot display the entry: {e}").red()),
+    }
+    if let Some(reason) = InstallationPlanner::unmet_condition(tool) {
+        println!(
+            "  {}",
+            format!("Skipped on this machine: {reason}").yellow()
+        );
+    }
+}
+
+fn print_state(tool_state: &ToolState) {
+    field("version", &tool_state.version);
+    field(
+        "installed via",
+        &format!(
+            "{} ({})",
+            tool_state.install_method, tool_state.package_type
+        ),
+    );
+    let exists = if Path::new(&tool_state.install_path).exists() {
+        "present".green()
+    } else {
+        "missing".red()
+    };
+    field(
+        "install path",
+        &format!("{} ({exists})", tool_state.install_path),
+    );
+    if let Some(shim) = &tool_state.shim {
+        field("shim", &format!("{} -> {}", shim.path, shim.target));
+    }
+    if let Some(bundle) = &tool_state.app_bundle {
+        field("app bundle", &format!("{} ({})", bundle.path, bundle.cli));
+    }
+    if let Some(origin) = origin(tool_state) {
+        field("origin", &origin);
+    }
+    if let Some(commit) = &tool_state.commit {
+        field("commit", commit);
+    }
+    if let Some(options) = tool_state.options.as_ref().filter(|o| !o.is_empty()) {
+        field("options", &options.join(" "));
+    }
+    if let Some(last_updated) = &tool_state.last_updated {
+        field("last updated", last_updated);
+    }
+    if let Some(duration) = tool_state.install_duration_ms {
+        field("install took", &format!("{:.1}s", duration as f64 / 1000.0));
+    }
+    if let Some(bytes) = tool_state.disk_usage_bytes {
+        field("disk usage", &format_size(bytes));
+    }
+    for asset in tool_state.assets.iter().flatten() {
+        field(
+            "asset",
+            &format!("{} -> {}", asset.name, asset.installed_paths.join(", ")),
+        );
+    }
+    for (permission, acknowledged_at) in tool_state.permissions_acknowledged.iter().flatten() {
+        field(
+            "permission",
+            &format!("{permission} (granted {acknowledged_at})"),
+        );
+    }
+    if let Some(recorded) = &tool_state.installer_version {
+        let current = installer_version::current(&recorded.command);
+        let note = match current {
+            Some(current) if installer_version::is_major_change(&recorded.version, &current) => {
+                format!(
+                    " ({})",
+                    format!("now {current}, a major upgrade since").yellow()
+                )
+            }
+            Some(current) if current != recorded.version => format!(" (now {current})"),
+            Some(_) => String::new(),
+            None => format!(" ({})", "no longer installed".yellow()),
+        };
+        field(
+            "installed with",
+            &format!("{} {}{note}", recorded.command, recorded.version),
+        );
+    }
+    if let Some(license) = &tool_state.license {
+        field("license", &license.name);
+    }
+    if let Some(receipt) = &tool_state.receipt {
+        field("receipt", receipt);
+    }
+}
+
+fn print_resolution(executable: &str, tool_state: Option<&ToolState>) {
+    let Some(resolved) = find_in_path(executable) else {
+        println!("  {} is not on PATH", executable.yellow());
+        return;
+    };
+    let note = match Resolution::of(&resolved, tool_state) {
+        Resolution::Installed => "the installed one".green(),
+        Resolution::Shadows(expected) => format!("shadows {expected}").yellow(),
+        Resolution::Untracked => "not installed by setup-devbox".yellow(),
+    };
+    println!("  {} -> {} ({note})", executable, resolved.display());
+}
+
+/// Where a tool comes from: `repo @ tag`, the repository, or the download URL.
+fn origin(tool_state: &ToolState) -> Option<String> {
+    match (&tool_state.repo, &tool_state.url) {
+        (Some(repo), _) => Some(match &tool_state.tag {
+            Some(tag) => format!("{repo} @ {tag}"),
+            None => repo.clone(),
+        }),
+        (None, Some(url)) => Some(url.clone()),
+        (None, None) => None,
+    }
+}
+
+/// How the executable found on `PATH` relates to the installed tool.
+#[derive(Debug, PartialEq)]
+enum Resolution<'a> {
+    /// `PATH` resolves to the installed executable (or its shim).
+    Installed,
+    /// Another executable comes first on `PATH`, hiding the installed one.
+    Shadows(&'a str),
+    /// The tool is not in state.
+    Untracked,
+}
+
+impl<'a> Resolution<'a> {
+    fn of(resolved: &Path, tool_state: Option<&'a ToolState>) -> Self {
+        let Some(tool_state) = tool_state else {
+            return Resolution::Untracked;
+        };
+        let expected = tool_state
+            .shim
+            .as_ref()
+            .map_or(tool_state.install_path.as_str(), |shim| shim.path.as_str());
+        if Path::new(expected) == resolved {
+            Resolution::Installed
+        } else {
+            Resolution::Shadows(expected)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+
+    fn tool_state(extra: Value) -> ToolState {
+        let mut value = json!({
+            "version": "14.1.0",
+            "install_path": "/home/dev/bin/rg",
+            "installed_by_devbox": true,
+            "install_method": "github",
+            "package_type": "binary",
+        });
+        value
+            .as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_resolution_flags_shadowed_executables() {
+        let installed = tool_state(json!({}));
+        assert_eq!(
+            Resolution::of(Path::new("/home/dev/bin/rg"), Some(&installed)),
+            Resolution::Installed
+        );
+        assert_eq!(
+            Resolution::of(Path::new("/usr/bin/rg"), Some(&installed)),
+            Resolution::Shadows("/home/dev/bin/rg")
+        );
+        assert_eq!(
+            Resolution::of(Path::new("/usr/bin/rg"), None),
+            Resolution::Untracked
+        );
+
+        let shimmed = tool_state(json!({
+            "shim": { "path": "/home/dev/bin/rg", "target": "/opt/rg/rg" },
+            "install_path": "/opt/rg/rg",
+        }));
+        assert_eq!(
+            Resolution::of(Path::new("/home/dev/bin/rg"), Some(&shimmed)),
+            Resolution::Installed
+        );
+    }
+
+    #[test]
+    fn test_origin() {
+        let github = tool_state(json!({ "repo": "BurntSushi/ripgrep", "tag": "14.1.0" }));
+        assert_eq!(
+            origin(&github).as_deref(),
+            Some("BurntSushi/ripgrep @ 14.1.0")
+        );
+        let url = tool_state(json!({ "url": "https://example.com/rg.tar.gz" }));
+        assert_eq!(
+            origin(&url).as_deref(),
+            Some("https://example.com/rg.tar.gz")
+        );
+        assert_eq!(origin(&tool_state(json!({}))), None);
+    }
+}
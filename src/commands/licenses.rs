@@ -0,0 +1,74 @@
+//! # Licenses Command Implementation
+//!
+//! Lists the licenses recorded in the state file for installed tools and fonts, together
+//! with whether (and when) a license that requires acceptance was accepted.
+
+use crate::commands::load_state_or_exit;
+use crate::schemas::path_resolver::PathResolver;
+use crate::schemas::state_file::{DevBoxState, LicenseState};
+use crate::{log_debug, log_error, log_info};
+use colored::Colorize;
+use prettytable::{Cell, Row, Table};
+
+/// Entry point for the `licenses` subcommand.
+pub fn run(state: Option<String>) {
+    log_debug!("[SDB::Licenses] Entering licenses::run()");
+
+    let paths = match PathResolver::new(None, state) {
+        Ok(paths) => paths,
+        Err(e) => {
+            log_error!("[SDB::Licenses] Failed to resolve paths: {}", e);
+            return;
+        }
+    };
+    let state: DevBoxState = load_state_or_exit(&paths.state_file().to_path_buf());
+
+    let mut entries: Vec<(&str, &str, Option<&LicenseState>)> = state
+        .tools
+        .iter()
+        .map(|(name, tool)| (name.as_str(), "tool", tool.license.as_ref()))
+        .chain(
+            state
+                .fonts
+                .iter()
+                .map(|(name, font)| (name.as_str(), "font", font.license.as_ref())),
+        )
+        .collect();
+    if entries.is_empty() {
+        log_info!("[SDB::Licenses] Nothing is installed yet.");
+        return;
+    }
+    entries.sort_by_key(|(name, kind, _)| (*kind, name.to_lowercase()));
+
+    let mut table = Table::new();
+    table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+    table.add_row(Row::new(vec![
+        Cell::new("Name").style_spec("b"),
+        Cell::new("Type").style_spec("b"),
+        Cell::new("License").style_spec("b"),
+        Cell::new("Accepted").style_spec("b"),
+        Cell::new("URL").style_spec("b"),
+    ]));
+    for (name, kind, license) in entries {
+        let row = match license {
+            Some(license) => vec![
+                Cell::new(name),
+                Cell::new(kind),
+                Cell::new(license.spdx_id.as_deref().unwrap_or(&license.name)),
+                Cell::new(license.accepted_at.as_deref().unwrap_or("-")),
+                Cell::new(license.url.as_deref().unwrap_or("-")),
+            ],
+            None => vec![
+                Cell::new(name),
+                Cell::new(kind),
+                Cell::new("unknown").style_spec("Fy"),
+                Cell::new("-"),
+                Cell::new("-"),
+            ],
+        };
+        table.add_row(Row::new(row));
+    }
+
+    println!("\n{}", "Licenses".bold().green());
+    table.printstd();
+}
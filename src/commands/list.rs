@@ -0,0 +1,267 @@
+//! # List Command Implementation
+//!
+//! `setup-devbox list [tools|fonts|settings|aliases]` shows what the state file records as
+//! installed: tools with their version, source, install path and last update, fonts and
+//! applied settings. Aliases are not recorded in the state, so they come from
+//! `shellrc.yaml`. `--output json` and `--output yaml` print the same data as one document
+//! for scripts.
+
+use crate::cli::type_enums::ListKind;
+use crate::commands::{load_configs_or_exit, load_state_or_exit};
+use crate::core::output;
+use crate::core::timestamps::time_since;
+use crate::log_debug;
+use crate::schemas::path_resolver::PathResolver;
+use crate::schemas::state_file::DevBoxState;
+use colored::Colorize;
+use prettytable::{Cell, Row, Table};
+use serde::Serialize;
+
+/// Everything `list` shows; kinds that were not asked for are left out.
+#[derive(Debug, Serialize)]
+struct Listing {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<ToolRow>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fonts: Option<Vec<FontRow>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    settings: Option<Vec<SettingRow>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    aliases: Option<Vec<AliasRow>>,
+}
+
+#[derive(Debug, Serialize)]
+struct ToolRow {
+    name: String,
+    version: String,
+    source: String,
+    install_path: String,
+    last_updated: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct FontRow {
+    name: String,
+    version: String,
+    source: String,
+    scope: String,
+    files: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct SettingRow {
+    domain: String,
+    key: String,
+    value: String,
+    value_type: String,
+}
+
+#[derive(Debug, Serialize)]
+struct AliasRow {
+    name: String,
+    value: String,
+}
+
+/// Entry point for the `list` subcommand.
+pub fn run(paths: &PathResolver, kind: Option<ListKind>) {
+    log_debug!("[SDB::List] Entering list::run() for {:?}", kind);
+
+    let wants = |wanted: ListKind| kind.is_none_or(|kind| kind == wanted);
+    let state: DevBoxState = load_state_or_exit(&paths.state_file().to_path_buf());
+    let mut listing = collect(&state, kind);
+    if wants(ListKind::Aliases) {
+        // Without a configuration there are no aliases; loading it would fail
+        let aliases = if paths.config_file().exists() {
+            load_configs_or_exit(paths)
+                .shell
+                .map(|shell_config| shell_config.aliases)
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        listing.aliases = Some(
+            aliases
+                .into_iter()
+                .map(|alias| AliasRow {
+                    name: alias.name,
+                    value: alias.value,
+                })
+                .collect(),
+        );
+    }
+
+    if output::is_structured() {
+        output::print_document(&listing);
+        return;
+    }
+    print_tables(&listing);
+}
+
+/// Rows of the kinds read from the state file (all of them when `kind` is `None`).
+fn collect(state: &DevBoxState, kind: Option<ListKind>) -> Listing {
+    let wants = |wanted: ListKind| kind.is_none_or(|kind| kind == wanted);
+    Listing {
+        tools: wants(ListKind::Tools).then(|| {
+            state
+                .tools
+                .iter()
+                .map(|(name, tool)| ToolRow {
+                    name: name.clone(),
+                    version: tool.version.clone(),
+                    source: tool.install_method.clone(),
+                    install_path: tool.install_path.clone(),
+                    last_updated: tool.last_updated.clone(),
+                })
+                .collect()
+        }),
+        fonts: wants(ListKind::Fonts).then(|| {
+            state
+                .fonts
+                .iter()
+                .map(|(name, font)| FontRow {
+                    name: name.clone(),
+                    version: font.version.clone(),
+                    source: font.install_method.clone(),
+                    scope: font.scope.unwrap_or_default().to_string(),
+                    files: font.files.clone(),
+                })
+                .collect()
+        }),
+        settings: wants(ListKind::Settings).then(|| {
+            state
+                .settings
+                .values()
+                .map(|setting| SettingRow {
+                    domain: setting.domain.clone(),
+                    key: setting.key.clone(),
+                    value: setting.value.clone(),
+                    value_type: setting.value_type.clone(),
+                })
+                .collect()
+        }),
+        aliases: None,
+    }
+}
+
+fn print_tables(listing: &Listing) {
+    if let Some(tools) = &listing.tools {
+        print_table(
+            "Tools",
+            &["Name", "Version", "Source", "Install Path", "Last Updated"],
+            tools
+                .iter()
+                .map(|tool| {
+                    vec![
+                        tool.name.clone(),
+                        tool.version.clone(),
+                        tool.source.clone(),
+                        tool.install_path.clone(),
+                        tool.last_updated
+                            .as_deref()
+                            .and_then(time_since)
+                            .unwrap_or_else(|| "-".to_string()),
+                    ]
+                })
+                .collect(),
+        );
+    }
+    if let Some(fonts) = &listing.fonts {
+        print_table(
+            "Fonts",
+            &["Name", "Version", "Source", "Scope", "Files"],
+            fonts
+                .iter()
+                .map(|font| {
+                    vec![
+                        font.name.clone(),
+                        font.version.clone(),
+                        font.source.clone(),
+                        font.scope.clone(),
+                        font.files.len().to_string(),
+                    ]
+                })
+                .collect(),
+        );
+    }
+    if let Some(settings) = &listing.settings {
+        print_table(
+            "Settings",
+            &["Domain", "Key", "Value", "Type"],
+            settings
+                .iter()
+                .map(|setting| {
+                    vec![
+                        setting.domain.clone(),
+                        setting.key.clone(),
+                        setting.value.clone(),
+                        setting.value_type.clone(),
+                    ]
+                })
+                .collect(),
+        );
+    }
+    if let Some(aliases) = &listing.aliases {
+        print_table(
+            "Aliases",
+            &["Name", "Command"],
+            aliases
+                .iter()
+                .map(|alias| vec![alias.name.clone(), alias.value.clone()])
+                .collect(),
+        );
+    }
+}
+
+fn print_table(title: &str, headers: &[&str], rows: Vec<Vec<String>>) {
+    println!("\n{} ({})", title.bold().green(), rows.len());
+    if rows.is_empty() {
+        println!("  {}", "none".dimmed());
+        return;
+    }
+    let mut table = Table::new();
+    table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+    table.add_row(Row::new(
+        headers
+            .iter()
+            .map(|header| Cell::new(header).style_spec("b"))
+            .collect(),
+    ));
+    for row in rows {
+        table.add_row(Row::new(row.iter().map(|value| Cell::new(value)).collect()));
+    }
+    table.printstd();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_only_requested_kinds() {
+        let state: DevBoxState = serde_json::from_value(serde_json::json!({
+            "tools": {
+                "rg": {
+                    "version": "14.1.0",
+                    "install_path": "/usr/local/bin/rg",
+                    "installed_by_devbox": true,
+                    "install_method": "github",
+                    "package_type": "binary",
+                    "last_updated": "2025-01-01T00:00:00+00:00"
+                }
+            },
+            "settings": {},
+            "fonts": {}
+        }))
+        .unwrap();
+
+        let listing = collect(&state, Some(ListKind::Tools));
+        let value = serde_json::to_value(&listing).unwrap();
+        assert_eq!(value["tools"][0]["name"], "rg");
+        assert_eq!(value["tools"][0]["source"], "github");
+        assert!(value.get("fonts").is_none());
+
+        let listing = collect(&state, None);
+        assert_eq!(listing.fonts.map(|fonts| fonts.len()), Some(0));
+        assert_eq!(listing.settings.map(|settings| settings.len()), Some(0));
+    }
+}
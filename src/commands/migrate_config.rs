@@ -0,0 +1,181 @@
+//! # Migrate Config Command Implementation
+//!
+//! Rewrites deprecated field names (e.g. `additional_cmd` → `post_installation_hooks`)
+//! in the configuration files in place. Renames are applied to the raw text so comments
+//! and layout are preserved; a rewritten file is only saved if it parses to exactly the
+//! migrated configuration, and the original is kept next to it as `<file>.yaml.bak`.
+
+use crate::config::migrate::{migrate_value, rewrite_text};
+use crate::schemas::common::MainConfig;
+use crate::schemas::path_resolver::PathResolver;
+use crate::{log_debug, log_error, log_info, log_warn};
+use colored::Colorize;
+use serde_yaml::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Entry point for the `migrate-config` subcommand.
+pub fn run(config: Option<String>, dry_run: bool) {
+    log_debug!("[SDB::MigrateConfig] Entering migrate_config::run()");
+
+    let paths = match PathResolver::new(config, None) {
+        Ok(paths) => paths,
+        Err(e) => {
+            log_error!("[SDB::MigrateConfig] Failed to resolve paths: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let files = config_files(&paths);
+    if files.is_empty() {
+        log_warn!("[SDB::MigrateConfig] No configuration files found.");
+        return;
+    }
+
+    let mut migrated = 0;
+    let mut failed = 0;
+    for (config_name, path) in files {
+        match migrate_file(config_name, &path, dry_run) {
+            Ok(true) => migrated += 1,
+            Ok(false) => {}
+            Err(e) => {
+                log_error!(
+                    "[SDB::MigrateConfig] {}: {}",
+                    path.display().to_string().red(),
+                    e
+                );
+                failed += 1;
+            }
+        }
+    }
+
+    if migrated == 0 && failed == 0 {
+        log_info!("[SDB::MigrateConfig] Configuration is up to date. Nothing to migrate.");
+    } else if dry_run {
+        log_info!(
+            "[SDB::MigrateConfig] {} file(s) would be migrated. Run without {} to apply.",
+            migrated,
+            "--dry-run".cyan()
+        );
+    } else {
+        log_info!("[SDB::MigrateConfig] Migrated {} file(s).", migrated);
+    }
+    if failed > 0 {
+        std::process::exit(1);
+    }
+}
+
+/// Lists the configuration files to migrate with the configuration name each one holds.
+fn config_files(paths: &PathResolver) -> Vec<(&'static str, PathBuf)> {
+    let config_file = paths.config_file().to_path_buf();
+    if paths.config_filename() != "config.yaml" {
+        return match config_name_for(paths.config_filename()) {
+            Some(name) => vec![(name, config_file)],
+            None => {
+                log_error!(
+                    "[SDB::MigrateConfig] Unsupported configuration file: {}",
+                    paths.config_filename().red()
+                );
+                Vec::new()
+            }
+        };
+    }
+
+    let main_config: MainConfig = match fs::read_to_string(&config_file)
+        .map_err(|e| e.to_string())
+        .and_then(|contents| serde_yaml::from_str(&contents).map_err(|e| e.to_string()))
+    {
+        Ok(main_config) => main_config,
+        Err(e) => {
+            log_error!(
+                "[SDB::MigrateConfig] Failed to read {}: {}",
+                config_file.display(),
+                e
+            );
+            return Vec::new();
+        }
+    };
+
+    [
+        ("tools", main_config.tools),
+        ("settings", main_config.settings),
+        ("shell config", main_config.shellrc),
+        ("fonts", main_config.fonts),
+    ]
+    .into_iter()
+    .filter_map(|(name, path)| Some((name, PathResolver::expand_tilde(&path?))))
+    .filter(|(_, path)| path.exists())
+    .collect()
+}
+
+fn config_name_for(filename: &str) -> Option<&'static str> {
+    match filename {
+        "tools.yaml" => Some("tools"),
+        "settings.yaml" => Some("settings"),
+        "shellrc.yaml" | "shellac.yaml" => Some("shell config"),
+        "fonts.yaml" => Some("fonts"),
+        _ => None,
+    }
+}
+
+/// Migrates one file. Returns `true` if it contained deprecated fields.
+fn migrate_file(config_name: &str, path: &Path, dry_run: bool) -> Result<bool, String> {
+    let contents = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut expected: Value = serde_yaml::from_str(&contents).map_err(|e| e.to_string())?;
+    let migrations = migrate_value(config_name, &mut expected);
+    if migrations.is_empty() {
+        log_debug!(
+            "[SDB::MigrateConfig] {} has no deprecated fields.",
+            path.display()
+        );
+        return Ok(false);
+    }
+
+    for migration in &migrations {
+        let note = if migration.conflict {
+            format!(
+                " ('{}' is already set; the old value is dropped)",
+                migration.new
+            )
+        } else {
+            String::new()
+        };
+        println!(
+            "  {} {}: {} → {}{}",
+            "•".bold(),
+            path.display(),
+            migration.path.yellow(),
+            migration.new.green(),
+            note
+        );
+    }
+
+    let rewritten = rewrite_text(config_name, &contents);
+    let mut actual: Value = serde_yaml::from_str(&rewritten).map_err(|e| e.to_string())?;
+    let conflicts = migrations.iter().any(|m| m.conflict);
+    if conflicts || !migrate_value(config_name, &mut actual).is_empty() || actual != expected {
+        return Err(format!(
+            "could not be rewritten automatically; rename the fields listed above by hand ({})",
+            if conflicts {
+                "both old and new names are set"
+            } else {
+                "unsupported layout"
+            }
+        ));
+    }
+
+    if dry_run {
+        return Ok(true);
+    }
+
+    let backup = path.with_extension("yaml.bak");
+    fs::copy(path, &backup)
+        .map_err(|e| format!("failed to back up to {}: {e}", backup.display()))?;
+    fs::write(path, rewritten).map_err(|e| e.to_string())?;
+    log_info!(
+        "[SDB::MigrateConfig] Updated {} (backup: {})",
+        path.display().to_string().green(),
+        backup.display()
+    );
+    Ok(true)
+}
@@ -5,19 +5,85 @@
 pub mod add;
 pub mod add_interactive;
 pub mod check_updates;
+// Reclaims disk space left behind by provisioning runs
+pub mod clean;
+// Local socket API for editor extensions and GUIs
+#[cfg(unix)]
+pub mod daemon;
+// Reports the disk space taken by installed tools and fonts
+pub mod du;
 // Help with editing configuration and state file.
 pub mod edit;
 // Manages the creation of default configuration files and initial setup.
 pub mod bootstrap;
+// Prints the new machine setup flow as a shell script
+pub mod bootstrap_script;
+// Shows the machine facts used by conditions and templates
+pub mod facts;
+// Inspects font releases before installing them
+pub mod fonts;
 // Generates help command
 pub mod help;
+// Suggests configuration entries for software installed outside setup-devbox
+pub mod import;
+// Shows the configuration, state and resolution of a single tool
+pub mod info;
+// Lists licenses of installed tools and fonts
+pub mod licenses;
+// Lists installed tools, fonts, settings and aliases
+pub mod list;
+// Rewrites deprecated configuration fields
+pub mod migrate_config;
+// Creates projects from the templates registered in config.yaml
+pub mod new;
+// Provisioning status segment for shell prompts
+pub mod prompt_status;
 // Orchestrates the main setup and installation process.;
 pub mod now;
+// Tells which tool installed a file
+pub mod owns;
+// Shows and saves the changes `now` would make
+pub mod plan;
+// Cross-check state, configuration and filesystem
+pub mod reconcile;
 // Reset the installation state
 pub mod reset;
 // Remove a component (tool, font, setting or alias) from command line
 pub mod remove;
+// Previews the shell configuration before it is applied
+pub mod shellrc;
+// Restores the state file from its backups
+pub mod state;
+// Shows drift between configuration, state and the installed tools
+pub mod status;
+// Collects redacted diagnostics for bug reports
+pub mod support_bundle;
 // Sync configuration files from state file
 pub mod sync;
+// Terminal dashboard for interactive management
+pub mod ui;
 // Displays the version of SDB
 pub mod version;
+
+// =========================================================================== //
+//                        SHARED COMMAND-LAYER HELPERS                         //
+// =========================================================================== //
+
+use std::path::PathBuf;
+
+use crate::config::{ParsedConfigs, load_configs};
+use crate::schemas::path_resolver::PathResolver;
+use crate::schemas::state_file::DevBoxState;
+use crate::state::manager::load_or_initialize_state;
+
+/// Loads the configuration for a command, exiting when it cannot be loaded.
+/// The loader has already logged why.
+pub(crate) fn load_configs_or_exit(paths: &PathResolver) -> ParsedConfigs {
+    load_configs(paths).unwrap_or_else(|_| std::process::exit(1))
+}
+
+/// Loads or initializes the state file for a command, exiting when it cannot be read.
+/// The loader has already logged why.
+pub(crate) fn load_state_or_exit(state_file: &PathBuf) -> DevBoxState {
+    load_or_initialize_state(state_file).unwrap_or_else(|_| std::process::exit(1))
+}
@@ -0,0 +1,348 @@
+//! # New Command Implementation
+//!
+//! Creates a project from a template registered under `scaffolds:` in `config.yaml`, taking
+//! setup-devbox from the machine to the first day of a project. Each template names the
+//! generator that creates projects from it (`cargo-generate`, `cookiecutter` or `degit`); a
+//! generator that is not on `PATH` is installed first through the matching installer and
+//! recorded in the state file, so it is managed like any other tool. `degit` is run through
+//! `npx` instead when it is not installed.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use colored::Colorize;
+
+use crate::config::read_only;
+use crate::core::installer_version;
+use crate::core::platform::find_in_path;
+use crate::engine::installers::factory::InstallerFactory;
+use crate::schemas::common::{MainConfig, Scaffold, ScaffoldGenerator};
+use crate::schemas::path_resolver::PathResolver;
+use crate::schemas::tools_enums::SourceType;
+use crate::schemas::tools_types::ToolEntry;
+use crate::state::manager::{load_or_initialize_state, save_state_to_file};
+use crate::{log_debug, log_error, log_info, log_warn};
+
+/// Entry point for the `new` subcommand. Exits with status 1 if the project could not be
+/// created.
+pub fn run(paths: &PathResolver, template: Option<String>, dir: Option<String>, list: bool) {
+    log_debug!("[SDB::New] Entering new::run()");
+
+    let scaffolds = load_scaffolds(paths.config_file());
+    if list {
+        print_scaffolds(&scaffolds);
+        return;
+    }
+    let (Some(template), Some(dir)) = (template, dir) else {
+        log_error!("[SDB::New] Both a template and a directory are required");
+        std::process::exit(1);
+    };
+
+    let Some(scaffold) = scaffolds.get(&template) else {
+        log_error!(
+            "[SDB::New] No template named '{}' in {}",
+            template.red(),
+            paths.config_file().display()
+        );
+        if !scaffolds.is_empty() {
+            let names: Vec<&str> = scaffolds.keys().map(String::as_str).collect();
+            log_info!("[SDB::New] Available templates: {}", names.join(", "));
+        }
+        std::process::exit(1);
+    };
+
+    let dir = PathResolver::expand_tilde(&dir);
+    if has_entries(&dir) {
+        log_error!(
+            "[SDB::New] {} already exists and is not empty",
+            dir.display().to_string().red()
+        );
+        std::process::exit(1);
+    }
+
+    let launcher = match ensure_generator(paths, scaffold.generator) {
+        Ok(launcher) => launcher,
+        Err(e) => {
+            log_error!("[SDB::New] {}", e);
+            std::process::exit(1);
+        }
+    };
+    let args = generator_args(scaffold, &dir);
+    log_info!(
+        "[SDB::New] Creating {} from '{}' with {}",
+        dir.display().to_string().bold(),
+        template.cyan(),
+        scaffold.generator
+    );
+    log_debug!("[SDB::New] Running {:?} {:?}", launcher, args);
+
+    let (program, launcher_args) = launcher.split_first().expect("launchers are not empty");
+    match Command::new(program)
+        .args(launcher_args)
+        .args(&args)
+        .status()
+    {
+        Ok(status) if status.success() => {
+            log_info!("[SDB::New] Created {}", dir.display().to_string().green())
+        }
+        Ok(status) => {
+            log_error!("[SDB::New] {} exited with {}", scaffold.generator, status);
+            std::process::exit(1);
+        }
+        Err(e) => {
+            log_error!("[SDB::New] Failed to run {}: {}", program, e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// The `scaffolds` of `config.yaml`; none when the file is missing or invalid.
+fn load_scaffolds(config_file: &Path) -> BTreeMap<String, Scaffold> {
+    fs::read_to_string(config_file)
+        .ok()
+        .and_then(|content| serde_yaml::from_str::<MainConfig>(&content).ok())
+        .and_then(|main_config| main_config.scaffolds)
+        .unwrap_or_default()
+}
+
+fn print_scaffolds(scaffolds: &BTreeMap<String, Scaffold>) {
+    if scaffolds.is_empty() {
+        println!("No templates registered; add them under 'scaffolds:' in config.yaml.");
+        return;
+    }
+    let width = scaffolds.keys().map(String::len).max().unwrap_or(0);
+    for (name, scaffold) in scaffolds {
+        println!(
+            "  {:width$}  {:14}  {}",
+            name.cyan(),
+            scaffold.generator.to_string(),
+            scaffold.description.as_deref().unwrap_or(&scaffold.source),
+            width = width
+        );
+    }
+}
+
+/// Whether `dir` exists with something in it (an empty directory can be generated into).
+fn has_entries(dir: &Path) -> bool {
+    dir.exists() && fs::read_dir(dir).map_or(true, |mut entries| entries.next().is_some())
+}
+
+/// Executable a generator is installed as.
+fn executable(generator: ScaffoldGenerator) -> &'static str {
+    match generator {
+        ScaffoldGenerator::Cookiecutter => "cookiecutter",
+        ScaffoldGenerator::CargoGenerate => "cargo-generate",
+        ScaffoldGenerator::Degit => "degit",
+    }
+}
+
+/// Installers a missing generator can be installed with, in order of preference.
+fn install_sources(generator: ScaffoldGenerator) -> &'static [SourceType] {
+    match generator {
+        ScaffoldGenerator::Cookiecutter => &[SourceType::Uv, SourceType::Pip],
+        ScaffoldGenerator::CargoGenerate => &[SourceType::Cargo],
+        ScaffoldGenerator::Degit => &[],
+    }
+}
+
+/// The command line that runs the generator at `executable_path`.
+fn launcher(generator: ScaffoldGenerator, executable_path: &Path) -> Vec<String> {
+    let mut launcher = vec![executable_path.display().to_string()];
+    if generator == ScaffoldGenerator::CargoGenerate {
+        // Run directly rather than as a cargo subcommand, its first argument is `generate`
+        launcher.push("generate".to_string());
+    }
+    launcher
+}
+
+/// Finds the generator, installing it when it is missing.
+///
+/// # Returns
+///
+/// * `Ok(launcher)` - The program and leading arguments that run the generator
+/// * `Err(message)` - If the generator is missing and cannot be installed
+fn ensure_generator(
+    paths: &PathResolver,
+    generator: ScaffoldGenerator,
+) -> Result<Vec<String>, String> {
+    let name = executable(generator);
+    if let Some(path) = find_in_path(name) {
+        return Ok(launcher(generator, &path));
+    }
+    if generator == ScaffoldGenerator::Degit {
+        return find_in_path("npx")
+            .map(|npx| vec![npx.display().to_string(), "--yes".into(), name.into()])
+            .ok_or_else(|| format!("'{name}' is not installed and 'npx' was not found"));
+    }
+
+    log_warn!(
+        "[SDB::New] '{}' is not installed; installing it first",
+        name.yellow()
+    );
+    read_only::ensure_writable(paths, "new")?;
+    let factory = InstallerFactory::new();
+    let (tool, installer) = install_sources(generator)
+        .iter()
+        .map(|source| ToolEntry::new(name, source.clone()))
+        .find_map(|tool| {
+            let installer = factory.get_installer(&tool.source)?;
+            installer.validate(&tool).ok().map(|()| (tool, installer))
+        })
+        .ok_or_else(|| {
+            format!(
+                "'{name}' is not installed and none of its installers ({}) is available",
+                install_sources(generator)
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })?;
+    let mut tool_state = installer
+        .install(&tool)
+        .map_err(|e| format!("Failed to install '{name}': {e}"))?;
+    tool_state.installer_version = installer_version::record(installer.as_ref());
+
+    let install_path = PathBuf::from(&tool_state.install_path);
+    let mut state = load_or_initialize_state(&paths.state_file().to_path_buf())?;
+    state.tools.insert(tool.name.clone(), tool_state);
+    save_state_to_file(&state, paths.state_file());
+    log_info!("[SDB::New] Installed {} with {}", name.green(), tool.source);
+
+    // Installers may record a package name, or a bin directory that is not on PATH yet
+    let path = find_in_path(name)
+        .or_else(|| install_path.is_file().then_some(install_path))
+        .unwrap_or_else(|| PathBuf::from(name));
+    Ok(launcher(generator, &path))
+}
+
+/// Arguments that make the generator create the project from `scaffold` in `dir`.
+fn generator_args(scaffold: &Scaffold, dir: &Path) -> Vec<String> {
+    let dir_name = dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let parent = dir
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let local_source = PathResolver::expand_tilde(&scaffold.source);
+
+    let mut args: Vec<String> = match scaffold.generator {
+        ScaffoldGenerator::CargoGenerate => {
+            let mut args = if local_source.is_dir() {
+                vec!["--path".to_string(), local_source.display().to_string()]
+            } else {
+                vec![scaffold.source.clone()]
+            };
+            args.extend([
+                "--name".to_string(),
+                dir_name,
+                "--destination".to_string(),
+                parent.display().to_string(),
+            ]);
+            args
+        }
+        ScaffoldGenerator::Cookiecutter => vec![
+            scaffold.source.clone(),
+            "--output-dir".to_string(),
+            dir.display().to_string(),
+        ],
+        ScaffoldGenerator::Degit => vec![scaffold.source.clone(), dir.display().to_string()],
+    };
+    args.extend(scaffold.args.iter().cloned());
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scaffold(generator: ScaffoldGenerator, source: &str) -> Scaffold {
+        Scaffold {
+            generator,
+            source: source.to_string(),
+            args: vec!["--verbose".to_string()],
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_load_scaffolds() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config.yaml");
+        fs::write(
+            &config,
+            "tools: tools.yaml\nsettings: settings.yaml\nshellrc: shellrc.yaml\nfonts: fonts.yaml\n\
+             scaffolds:\n  rust-cli:\n    generator: cargo-generate\n    source: gh:rust-cli/cli-template\n",
+        )
+        .unwrap();
+
+        let scaffolds = load_scaffolds(&config);
+        assert_eq!(
+            scaffolds["rust-cli"].generator,
+            ScaffoldGenerator::CargoGenerate
+        );
+        assert!(load_scaffolds(&dir.path().join("missing.yaml")).is_empty());
+    }
+
+    #[test]
+    fn test_generator_args() {
+        let dir = Path::new("/work/my-tool");
+        assert_eq!(
+            generator_args(
+                &scaffold(ScaffoldGenerator::CargoGenerate, "gh:rust-cli/cli-template"),
+                dir
+            ),
+            [
+                "gh:rust-cli/cli-template",
+                "--name",
+                "my-tool",
+                "--destination",
+                "/work",
+                "--verbose"
+            ]
+        );
+        assert_eq!(
+            generator_args(
+                &scaffold(ScaffoldGenerator::Cookiecutter, "gh:org/tpl"),
+                dir
+            ),
+            ["gh:org/tpl", "--output-dir", "/work/my-tool", "--verbose"]
+        );
+        assert_eq!(
+            generator_args(&scaffold(ScaffoldGenerator::Degit, "org/repo/sub"), dir),
+            ["org/repo/sub", "/work/my-tool", "--verbose"]
+        );
+
+        let template = tempfile::tempdir().unwrap();
+        let local = template.path().display().to_string();
+        assert_eq!(
+            generator_args(&scaffold(ScaffoldGenerator::CargoGenerate, &local), dir)[..2],
+            ["--path".to_string(), local]
+        );
+    }
+
+    #[test]
+    fn test_launcher_and_target_directory() {
+        assert_eq!(
+            launcher(
+                ScaffoldGenerator::CargoGenerate,
+                Path::new("/bin/cargo-generate")
+            ),
+            ["/bin/cargo-generate", "generate"]
+        );
+        assert_eq!(
+            launcher(ScaffoldGenerator::Degit, Path::new("/bin/degit")),
+            ["/bin/degit"]
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!has_entries(dir.path()));
+        assert!(!has_entries(&dir.path().join("missing")));
+        fs::write(dir.path().join("README.md"), "").unwrap();
+        assert!(has_entries(dir.path()));
+    }
+}
@@ -9,17 +9,27 @@ use crate::{log_debug, log_info, log_warn};
 // Custom logging macros.
 use colored::Colorize;
 // For colored terminal output.
+use std::path::PathBuf;
 
-use crate::config::{
-    load_master_configs, // Loads configurations from `config.yaml`.
-    load_single_config,  // Loads a single configuration file.
-};
+use crate::config::ParsedConfigs;
+use crate::config::load_configs; // Loads `config.yaml` or a single configuration file.
+use crate::config::profiles;
 use crate::core::backup::backup_directory;
+use crate::core::inventory;
+use crate::core::lifecycle_hooks::{self, LifecycleEvent};
+use crate::core::version_check;
 use crate::engine::install_tools;
+use crate::engine::installers::{github, simulated};
+use crate::engine::plan::Plan;
 use crate::fonts::installer::install_fonts;
+use crate::logger;
 use crate::schemas::path_resolver::PathResolver;
+use crate::schemas::tools_types::InstallationSummary;
 use crate::settings::apply_system_settings;
+use crate::shell::reload;
+use crate::state::checkpoint::RunCheckpoint;
 use crate::state::manager::load_or_initialize_state;
+use crate::state::run_summary::RunSummary;
 
 /// Main entry point for the `now` command.
 ///
@@ -33,15 +43,34 @@ use crate::state::manager::load_or_initialize_state;
 /// # Arguments
 /// * `config_path`: Optional custom path to `config.yaml` or a single config file.
 /// * `state_path`: Optional custom path to `state.json`.
-pub fn run(paths: &PathResolver, update_latest: bool, dry_run: bool) {
+/// * `resume`: Continue an interrupted run from its checkpoint (`--continue`).
+/// * `diff_last`: Print what changed since the previous run (`--diff-last`).
+/// * `plan`: Only apply the actions of a reviewed plan (`--from-plan`).
+///
+/// # Returns
+/// The tool installation summary (`None` without `tools.yaml`), or an error if the state
+/// or configuration files could not be loaded. Loading errors are logged before they are
+/// returned.
+pub fn run(
+    paths: &PathResolver,
+    update_latest: bool,
+    dry_run: bool,
+    resume: bool,
+    diff_last: bool,
+    plan: Option<&Plan>,
+) -> Result<Option<InstallationSummary>, String> {
     log_debug!("[SDB] Entered now::run() function.");
+    // Warnings of an earlier run in this process are not repeated in this run's summary
+    logger::reset_warnings();
+    // Simulated runs (`--simulate`) only write the state next to their fixture file
+    let simulating = simulated::is_active();
 
     if dry_run {
         log_info!(
             "[SDB] '{}' flag is set, simulation mode enabled",
             "Dry Run".bright_magenta()
         );
-    } else {
+    } else if !simulating {
         // Automatically backup configuration directory before changes
         // Use base_config_dir to include configs/ and state.json
         if let Err(e) = backup_directory(paths.base_config_dir()) {
@@ -68,33 +97,77 @@ pub fn run(paths: &PathResolver, update_latest: bool, dry_run: bool) {
     );
 
     // Load existing application state or initialize a new one.
-    let mut state: DevBoxState = load_or_initialize_state(&state_path_resolved.to_path_buf());
+    let mut state: DevBoxState = load_or_initialize_state(&state_path_resolved.to_path_buf())?;
 
     // Load configurations based on the detected config filename.
-    let parsed_configs = if config_filename == "config.yaml" {
-        load_master_configs(&config_path_resolved.to_path_buf())
+    let mut parsed_configs = load_configs(paths)?;
+    // Release metadata and versions cached by an earlier run in this process may be outdated
+    github::reset_release_cache();
+    version_check::reset_cache();
+    inventory::reset_snapshot();
+    if let Some(plan) = plan {
+        log_info!(
+            "[SDB::Now] Applying {} planned action(s) from the plan made at {}",
+            plan.actions.len(),
+            plan.created_at
+        );
+        plan.restrict(&mut parsed_configs);
+    }
+    // `--profile` drops the entries of other profiles
+    profiles::restrict(&mut parsed_configs);
+
+    // Environment of the `config.yaml` lifecycle hooks; dry and simulated runs don't run them.
+    let hook_context = vec![
+        (
+            "SDB_CONFIG_FILE",
+            config_path_resolved.display().to_string(),
+        ),
+        ("SDB_STATE_FILE", state_path_resolved.display().to_string()),
+    ];
+    if !dry_run && !simulating {
+        lifecycle_hooks::run(LifecycleEvent::BeforeRun, &hook_context);
+    }
+
+    // Track finished tools so an interrupted run can be resumed with `--continue`.
+    // Dry runs change nothing, so they never read or write a checkpoint.
+    let mut checkpoint = if dry_run {
+        None
+    } else if resume {
+        Some(RunCheckpoint::resume(&paths.checkpoint_file()))
     } else {
-        load_single_config(&config_path_resolved.to_path_buf(), config_filename)
+        Some(RunCheckpoint::start(&paths.checkpoint_file()))
     };
 
     // Apply configurations and update state for each section.
     // State is saved immediately after each major block if changes occur.
-    if let Some(tools_cfg) = parsed_configs.tools {
+    let mut tools_summary = None;
+    if let Some(tools_cfg) = parsed_configs.tools.take() {
         log_info!("[SDB::Tools] Processing {}...", "Tools".bright_green());
-        install_tools(
+        tools_summary = Some(install_tools(
             tools_cfg,
             &mut state,
             state_path_resolved,
             update_latest,
             dry_run,
             paths,
-        ); // Add paths
+            checkpoint.as_mut(),
+        ));
     } else {
         log_debug!(
             "[SDB::Now] No tool configurations found (tools.yaml missing or empty). Skipping tool installation phase."
         );
     }
 
+    // Only tools are simulated or dry-run; fonts, shell configuration and settings have no
+    // dry-run mode, so they are left alone.
+    if simulating {
+        log_info!("[SDB::Simulate] Skipping fonts, shell configuration and system settings");
+        keep_only_tools(&mut parsed_configs);
+    } else if dry_run {
+        log_info!("[SDB::Now] Dry run: skipping fonts, shell configuration and system settings");
+        keep_only_tools(&mut parsed_configs);
+    }
+
     // Install Fonts.
     if let Some(fonts_cfg) = parsed_configs.fonts {
         install_fonts(fonts_cfg, &mut state, state_path_resolved);
@@ -105,8 +178,11 @@ pub fn run(paths: &PathResolver, update_latest: bool, dry_run: bool) {
     }
 
     // Apply Shell Configuration.
+    let mut written_startup_files = Vec::new();
+    let mut aliases = Vec::new();
     if let Some(shell_cfg) = parsed_configs.shell {
-        apply_shell_configs(shell_cfg);
+        aliases = shell_cfg.aliases.clone();
+        written_startup_files = apply_shell_configs(shell_cfg);
     } else {
         log_debug!(
             "[SDB::Now] No shell configurations found (shellrc.yaml missing or empty). Skipping shell configuration phase."
@@ -122,9 +198,106 @@ pub fn run(paths: &PathResolver, update_latest: bool, dry_run: bool) {
         );
     }
 
+    // The run completed, so there is nothing left to resume.
+    if let Some(checkpoint) = checkpoint {
+        checkpoint.finish();
+    }
+
+    if !dry_run && !simulating {
+        run_final_hooks(hook_context, tools_summary.as_ref());
+        // The shell that started the run still has the old PATH and aliases
+        let executables = new_executables(&state, tools_summary.as_ref());
+        reload::report(&written_startup_files, &executables, &aliases);
+    }
+
+    // Remember the outcome for `prompt-status` and `--diff-last`.
+    if !dry_run {
+        let summary_file = paths.run_summary_file();
+        let summary = RunSummary::record(
+            &summary_file,
+            state_path_resolved,
+            &state,
+            tools_summary.as_ref(),
+        );
+        if diff_last {
+            let previous = RunSummary::load(&RunSummary::previous_path(&summary_file));
+            summary.display_diff(previous.as_ref());
+        }
+    } else if diff_last {
+        log_info!(
+            "[SDB::Now] Dry runs are not recorded; nothing to compare with the previous run."
+        );
+    }
+
     log_info!(
         "[SDB::Now] '{}' command completed!!",
         "setup-devbox now".cyan()
     );
     log_debug!("[SDB::Now] Exited now::run() function.");
+    Ok(tools_summary)
+}
+
+/// Drops the fonts, shell configuration and settings phases from a run.
+fn keep_only_tools(configs: &mut ParsedConfigs) {
+    configs.fonts = None;
+    configs.shell = None;
+    configs.settings = None;
+}
+
+/// Install paths of the tools the run installed or updated.
+fn new_executables(state: &DevBoxState, summary: Option<&InstallationSummary>) -> Vec<PathBuf> {
+    summary
+        .into_iter()
+        .flat_map(|summary| summary.installed_tools.iter().chain(&summary.updated_tools))
+        .filter_map(|name| state.tools.get(name))
+        .map(|tool| PathBuf::from(&tool.install_path))
+        .collect()
+}
+
+/// Runs the `after_run` hooks, and the `on_failure` hooks when any tool failed, with the
+/// tools the run installed, updated and failed to install as comma-separated lists.
+fn run_final_hooks(mut context: Vec<(&str, String)>, summary: Option<&InstallationSummary>) {
+    let failed = summary.is_some_and(|summary| !summary.failed_tools.is_empty());
+    if let Some(summary) = summary {
+        context.push(("SDB_INSTALLED_TOOLS", summary.installed_tools.join(",")));
+        context.push(("SDB_UPDATED_TOOLS", summary.updated_tools.join(",")));
+        let failed_tools: Vec<&str> = summary
+            .failed_tools
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        context.push(("SDB_FAILED_TOOLS", failed_tools.join(",")));
+    }
+    context.push((
+        "SDB_RUN_STATUS",
+        if failed { "failure" } else { "success" }.to_string(),
+    ));
+
+    lifecycle_hooks::run(LifecycleEvent::AfterRun, &context);
+    if failed {
+        lifecycle_hooks::run(LifecycleEvent::OnFailure, &context);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keep_only_tools() {
+        let mut configs = ParsedConfigs {
+            tools: Some(serde_yaml::from_str("tools: []").unwrap()),
+            settings: Some(serde_yaml::from_str("settings: {}").unwrap()),
+            shell: Some(
+                serde_yaml::from_str(
+                    "run_commands:\n  shell: zsh\n  run_commands: []\naliases: []",
+                )
+                .unwrap(),
+            ),
+            fonts: Some(serde_yaml::from_str("fonts: []").unwrap()),
+        };
+        keep_only_tools(&mut configs);
+        assert!(configs.tools.is_some());
+        assert!(configs.fonts.is_none() && configs.shell.is_none() && configs.settings.is_none());
+    }
 }
@@ -0,0 +1,93 @@
+//! # Owns Command Implementation
+//!
+//! Tells which installed tool a file belongs to, using the install receipts recorded in the
+//! state directory, and whether the file changed since it was installed.
+
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+
+use crate::commands::load_state_or_exit;
+use crate::schemas::path_resolver::PathResolver;
+use crate::schemas::state_file::DevBoxState;
+use crate::state::receipts::{self, FileChange, FileIntegrity};
+use crate::{log_debug, log_error};
+
+/// Entry point for the `owns` subcommand. Exits with status 1 if no tool owns `path`.
+pub fn run(path: &str, state: Option<String>) {
+    log_debug!("[SDB::Owns] Entering owns::run()");
+
+    let paths = match PathResolver::new(None, state) {
+        Ok(paths) => paths,
+        Err(e) => {
+            log_error!("[SDB::Owns] Failed to resolve paths: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let state: DevBoxState = load_state_or_exit(&paths.state_file().to_path_buf());
+
+    // Receipts record paths as installers wrote them, which may go through symlinks
+    let mut candidates = vec![absolute(Path::new(path))];
+    if let Ok(canonical) = std::fs::canonicalize(path)
+        && !candidates.contains(&canonical)
+    {
+        candidates.push(canonical);
+    }
+
+    let mut found = false;
+    for candidate in &candidates {
+        for (tool, file) in receipts::owners(&state, candidate) {
+            found = true;
+            let change = match file.change {
+                FileChange::Created => "created",
+                FileChange::Modified => "modified",
+            };
+            let integrity = match file.integrity() {
+                FileIntegrity::Intact => "unchanged since installation".green(),
+                FileIntegrity::Changed => "changed since installation".yellow(),
+                FileIntegrity::Missing => "missing".red(),
+            };
+            println!(
+                "{} is owned by {} ({} by the installation, {})",
+                candidate.display(),
+                tool.bold(),
+                change,
+                integrity
+            );
+        }
+    }
+
+    // Tools installed before receipts were recorded are matched by their executable
+    if !found {
+        for (tool, tool_state) in &state.tools {
+            let shim_path = tool_state.shim.as_ref().map(|shim| shim.path.as_str());
+            if candidates.iter().any(|candidate| {
+                candidate == Path::new(&tool_state.install_path)
+                    || shim_path.is_some_and(|shim| candidate == Path::new(shim))
+            }) {
+                found = true;
+                println!(
+                    "{} is owned by {} (no install receipt recorded)",
+                    path,
+                    tool.bold()
+                );
+            }
+        }
+    }
+
+    if !found {
+        println!("No installed tool owns {path}");
+        std::process::exit(1);
+    }
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    let expanded = PathBuf::from(shellexpand::tilde(&path.to_string_lossy()).into_owned());
+    if expanded.is_absolute() {
+        expanded
+    } else {
+        std::env::current_dir()
+            .map(|cwd| cwd.join(&expanded))
+            .unwrap_or(expanded)
+    }
+}
@@ -0,0 +1,50 @@
+// This file implements `setup-devbox plan`, which shows the changes `now` would make
+// (installs, updates, shell and settings changes) without making them, and saves them for
+// `setup-devbox now --from-plan`.
+
+use std::path::Path;
+
+use colored::Colorize;
+
+use crate::engine::plan::Plan;
+use crate::schemas::path_resolver::PathResolver;
+use crate::{log_debug, log_error, log_info};
+
+/// Prints the plan as text or JSON, and saves it to `out` if given.
+pub fn run(paths: &PathResolver, update_latest: bool, json: bool, out: Option<&str>) {
+    log_debug!("[SDB::Plan] Entered plan::run() function.");
+    let plan = Plan::build(paths, update_latest).unwrap_or_else(|_| std::process::exit(1));
+
+    if json {
+        match serde_json::to_string_pretty(&plan) {
+            Ok(content) => println!("{content}"),
+            Err(e) => log_error!("[SDB::Plan] Failed to serialize the plan: {}", e),
+        }
+    } else {
+        plan.display();
+    }
+
+    if let Some(out) = out {
+        match plan.save(Path::new(out)) {
+            Ok(()) => log_info!(
+                "[SDB::Plan] Plan saved to {}. Apply it with '{}'.",
+                out.cyan(),
+                format!("setup-devbox now --from-plan {out}").cyan()
+            ),
+            Err(e) => {
+                log_error!("[SDB::Plan] {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+/// Loads a saved plan for `now --from-plan`, exiting if it cannot be read or is out of date.
+pub fn load_verified(paths: &PathResolver, plan_path: &str) -> Plan {
+    Plan::load(Path::new(plan_path))
+        .and_then(|plan| plan.verify(paths).map(|()| plan))
+        .unwrap_or_else(|e| {
+            log_error!("[SDB::Now] {}", e);
+            std::process::exit(1);
+        })
+}
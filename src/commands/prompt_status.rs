@@ -0,0 +1,35 @@
+//! # Prompt Status Command Implementation
+//!
+//! Prints a short segment for shell prompts telling whether the devbox is in sync, out
+//! of date or had failures in its last run. It runs on every prompt, so it only reads the
+//! state file's modification time and the summary recorded by the last `now` run; no
+//! configuration is loaded and nothing is printed to stderr.
+
+use crate::cli::type_enums::PromptFormat;
+use crate::schemas::path_resolver::PathResolver;
+use crate::state::run_summary::{ProvisioningStatus, RunSummary, modified_millis};
+
+/// Entry point for the `prompt-status` subcommand.
+pub fn run(format: PromptFormat, state: Option<String>) {
+    let Ok(paths) = PathResolver::new(None, state) else {
+        return;
+    };
+    let summary = RunSummary::load(&paths.run_summary_file());
+    let status = RunSummary::status(summary.as_ref(), modified_millis(paths.state_file()));
+    println!("{}", render(status, format));
+}
+
+/// Renders the segment: plain text for starship (styled by the module), zsh prompt
+/// escapes for powerlevel10k.
+fn render(status: ProvisioningStatus, format: PromptFormat) -> String {
+    let (color, text) = match status {
+        ProvisioningStatus::InSync => ("green", "devbox ✔".to_string()),
+        ProvisioningStatus::OutOfDate => ("yellow", "devbox ⟳".to_string()),
+        ProvisioningStatus::Failed(count) => ("red", format!("devbox ✘{count}")),
+        ProvisioningStatus::Unknown => ("yellow", "devbox ?".to_string()),
+    };
+    match format {
+        PromptFormat::Starship => text,
+        PromptFormat::P10k => format!("%F{{{color}}}{text}%f"),
+    }
+}
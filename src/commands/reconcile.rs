@@ -0,0 +1,395 @@
+//! # Reconcile Command Implementation
+//!
+//! Cross-checks `state.json`, the tool configuration and the filesystem in one pass and
+//! proposes concrete fixes for any drift between them:
+//!
+//! - **Adopt external binary**: a configured tool is not in state, but an executable with
+//!   its name is already on `PATH` (installed outside of `setup-devbox`).
+//! - **Prune orphaned state**: a tool is recorded in state but no longer configured.
+//! - **Reinstall missing binary**: a tool is in state, but its recorded install path is gone.
+//! - **Re-render configuration**: a managed configuration file differs from its source.
+//!
+//! Each fix is confirmed interactively, or applied without prompting with `--yes`.
+
+use crate::commands::{load_configs_or_exit, load_state_or_exit};
+use crate::core::backup::backup_directory;
+use crate::core::installer_version;
+use crate::core::platform::find_in_path;
+use crate::core::protected;
+use crate::engine::configuration::processor::ConfigurationManagerProcessor;
+use crate::engine::installers::factory::InstallerFactory;
+use crate::schemas::path_resolver::PathResolver;
+use crate::schemas::state_file::{DevBoxState, ToolState};
+use crate::schemas::tools_types::ToolEntry;
+use crate::state::manager::save_state_to_file;
+use crate::{log_debug, log_error, log_info, log_warn};
+use colored::Colorize;
+use dialoguer::Confirm;
+use std::fmt;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// A single inconsistency found between state, configuration and the filesystem.
+enum Drift {
+    /// Configured, not in state, but an executable with this name is on `PATH`.
+    ExternalBinary { tool: ToolEntry, path: PathBuf },
+    /// Recorded in state but no longer present in the configuration.
+    OrphanedState { name: String },
+    /// Configured and in state, but the recorded install path no longer exists.
+    MissingBinary {
+        tool: ToolEntry,
+        install_path: String,
+    },
+    /// A managed configuration file differs from its source.
+    StaleConfiguration { tool: ToolEntry },
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Drift::ExternalBinary { tool, path } => write!(
+                f,
+                "'{}' is not tracked but found at {} -> adopt external binary",
+                tool.name,
+                path.display()
+            ),
+            Drift::OrphanedState { name } => write!(
+                f,
+                "'{name}' is in state but not configured -> prune orphaned state"
+            ),
+            Drift::MissingBinary { tool, install_path } => write!(
+                f,
+                "'{}' is tracked but {} is missing -> reinstall missing binary",
+                tool.name, install_path
+            ),
+            Drift::StaleConfiguration { tool } => write!(
+                f,
+                "configuration for '{}' differs from its source -> re-render config",
+                tool.name
+            ),
+        }
+    }
+}
+
+/// Entry point for the `reconcile` subcommand.
+pub fn run(config: Option<String>, state: Option<String>, yes: bool) {
+    log_debug!("[SDB::Reconcile] Entering reconcile::run()");
+
+    let paths = match PathResolver::new(config, state) {
+        Ok(paths) => paths,
+        Err(e) => {
+            log_error!("[SDB::Reconcile] Failed to resolve paths: {}", e);
+            return;
+        }
+    };
+
+    let state_file = paths.state_file().to_path_buf();
+    let mut state: DevBoxState = load_state_or_exit(&state_file);
+    let tools = load_configs_or_exit(&paths)
+        .tools
+        .map(|cfg| cfg.tools)
+        .unwrap_or_default();
+    let config_processor = ConfigurationManagerProcessor::new(&paths);
+
+    let findings = find_drift(&tools, &state, &config_processor);
+    if findings.is_empty() {
+        log_info!(
+            "[SDB::Reconcile] {} State, configuration and filesystem are in sync.",
+            "Success:".green().bold()
+        );
+        return;
+    }
+
+    println!();
+    println!("{}", "Reconcile findings:".bold().yellow());
+    for (index, drift) in findings.iter().enumerate() {
+        println!("  {}. {}", index + 1, drift);
+    }
+    println!();
+
+    if !yes && !std::io::stdin().is_terminal() {
+        log_warn!(
+            "[SDB::Reconcile] Not running interactively; re-run with {} to apply all fixes.",
+            "--yes".cyan()
+        );
+        return;
+    }
+
+    // Snapshot configs and state before changing anything
+    if let Err(e) = backup_directory(paths.base_config_dir()) {
+        log_warn!(
+            "[SDB::Backup] Automatic backup failed: {}. Continuing anyway.",
+            e
+        );
+    }
+
+    let installer_factory = InstallerFactory::new();
+    let mut applied = 0;
+    for drift in &findings {
+        if !yes && !confirm(drift) {
+            continue;
+        }
+        if apply_fix(drift, &mut state, &installer_factory, &config_processor) {
+            applied += 1;
+        }
+    }
+
+    if applied > 0 {
+        save_state_to_file(&state, &state_file);
+    }
+    log_info!(
+        "[SDB::Reconcile] Applied {} of {} fix(es).",
+        applied,
+        findings.len()
+    );
+}
+
+/// Compares configuration, state and filesystem and returns every inconsistency found.
+fn find_drift(
+    tools: &[ToolEntry],
+    state: &DevBoxState,
+    config_processor: &ConfigurationManagerProcessor,
+) -> Vec<Drift> {
+    let mut findings = Vec::new();
+
+    for tool in tools {
+        match state.tools.get(&tool.name) {
+            None => {
+                let binary_name = tool.rename_to.as_deref().unwrap_or(&tool.name);
+                if let Some(path) = find_in_path(binary_name) {
+                    findings.push(Drift::ExternalBinary {
+                        tool: tool.clone(),
+                        path,
+                    });
+                }
+            }
+            Some(tool_state) => {
+                // Only absolute paths can be checked; some installers record package names
+                let install_path = Path::new(&tool_state.install_path);
+                if install_path.is_absolute() && !install_path.exists() {
+                    findings.push(Drift::MissingBinary {
+                        tool: tool.clone(),
+                        install_path: tool_state.install_path.clone(),
+                    });
+                } else if tool.configuration_manager.enabled
+                    && config_processor
+                        .evaluate_configuration_requirements(
+                            &tool.name,
+                            &tool.configuration_manager,
+                            tool_state.get_configuration_manager(),
+                        )
+                        .is_ok_and(|evaluation| evaluation.needs_update)
+                {
+                    findings.push(Drift::StaleConfiguration { tool: tool.clone() });
+                }
+            }
+        }
+    }
+
+    let mut orphaned: Vec<&String> = state
+        .tools
+        .keys()
+        .filter(|name| !tools.iter().any(|tool| &tool.name == *name))
+        .collect();
+    orphaned.sort();
+    findings.extend(
+        orphaned
+            .into_iter()
+            .map(|name| Drift::OrphanedState { name: name.clone() }),
+    );
+
+    findings
+}
+
+fn confirm(drift: &Drift) -> bool {
+    Confirm::new()
+        .with_prompt(format!("Apply fix: {drift}?"))
+        .default(true)
+        .interact()
+        .unwrap_or(false)
+}
+
+/// Applies the fix for a single finding. Returns `true` if state was changed.
+fn apply_fix(
+    drift: &Drift,
+    state: &mut DevBoxState,
+    installer_factory: &InstallerFactory,
+    config_processor: &ConfigurationManagerProcessor,
+) -> bool {
+    match drift {
+        Drift::ExternalBinary { tool, path } => {
+            let mut tool_state = ToolState::new(
+                tool,
+                path,
+                "adopted".to_string(),
+                "binary".to_string(),
+                detect_adopted_version(tool, path),
+                None,
+                None,
+                None,
+            );
+            // Adopted binaries were not installed by setup-devbox and must never be
+            // removed by it.
+            tool_state.installed_by_devbox = false;
+            state.tools.insert(tool.name.clone(), tool_state);
+            log_info!(
+                "[SDB::Reconcile] Adopted {} from {}",
+                tool.name.green(),
+                path.display()
+            );
+            true
+        }
+        Drift::OrphanedState { name } => {
+            if protected::is_protected(state, name)
+                && let Err(refusal) = protected::authorize(state, name, "prune")
+            {
+                log_warn!("[SDB::Reconcile] {}", refusal);
+                return false;
+            }
+            state.tools.remove(name);
+            log_info!("[SDB::Reconcile] Pruned {} from state", name.green());
+            true
+        }
+        Drift::MissingBinary { tool, .. } => {
+            let Some(installer) = installer_factory.get_installer(&tool.source) else {
+                log_error!(
+                    "[SDB::Reconcile] No installer available for source '{}'",
+                    tool.source
+                );
+                return false;
+            };
+            match installer.install(tool) {
+                Ok(mut tool_state) => {
+                    // Keep the configuration manager record from the previous install
+                    if let Some(previous) = state.tools.get(&tool.name) {
+                        tool_state.configuration_manager = previous.configuration_manager.clone();
+                    }
+                    tool_state.installer_version = installer_version::record(installer.as_ref());
+                    state.tools.insert(tool.name.clone(), tool_state);
+                    log_info!("[SDB::Reconcile] Reinstalled {}", tool.name.green());
+                    true
+                }
+                Err(e) => {
+                    log_error!(
+                        "[SDB::Reconcile] Failed to reinstall {}: {}",
+                        tool.name.red(),
+                        e
+                    );
+                    false
+                }
+            }
+        }
+        Drift::StaleConfiguration { tool } => {
+            let Some(tool_state) = state.tools.get_mut(&tool.name) else {
+                return false;
+            };
+            match config_processor.process_tool_configuration(
+                &tool.name,
+                &tool.configuration_manager,
+                tool_state.get_configuration_manager(),
+                None,
+            ) {
+                Ok(Some(config_state)) => {
+                    tool_state.set_configuration_manager(config_state);
+                    log_info!(
+                        "[SDB::Reconcile] Re-rendered configuration for {}",
+                        tool.name.green()
+                    );
+                    true
+                }
+                Ok(None) => false,
+                Err(e) => {
+                    log_error!(
+                        "[SDB::Reconcile] Failed to re-render configuration for {}: {}",
+                        tool.name.red(),
+                        e
+                    );
+                    false
+                }
+            }
+        }
+    }
+}
+
+/// Records the configured version for an adopted binary only when `--version` confirms it;
+/// otherwise the version is unknown and the next `now` run decides whether to reinstall.
+fn detect_adopted_version(tool: &ToolEntry, path: &Path) -> String {
+    let configured = tool
+        .version
+        .as_deref()
+        .filter(|v| *v != "latest")
+        .map(|v| v.strip_prefix('v').unwrap_or(v));
+    let Some(configured) = configured else {
+        return "latest".to_string();
+    };
+    let reported = Command::new(path)
+        .arg("--version")
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+        .unwrap_or_default();
+    if reported.contains(configured) {
+        configured.to_string()
+    } else {
+        "unknown".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tool_state(install_path: &str) -> ToolState {
+        serde_json::from_value(json!({
+            "version": "1.0.0",
+            "install_path": install_path,
+            "installed_by_devbox": true,
+            "install_method": "direct-url",
+            "package_type": "binary",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_find_drift_reports_missing_binaries_and_orphaned_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = PathResolver::new(
+            Some(dir.path().join("config.yaml").display().to_string()),
+            Some(dir.path().join("state.json").display().to_string()),
+        )
+        .unwrap();
+        let present = dir.path().join("present");
+        std::fs::write(&present, "").unwrap();
+
+        let configured = |name: &str| ToolEntry {
+            name: name.to_string(),
+            ..serde_yaml::from_str("name: x\nsource: url").unwrap()
+        };
+        let tools = vec![
+            configured("present"),
+            configured("missing"),
+            configured("sdb-untracked-and-not-on-path"),
+        ];
+        let mut state = DevBoxState::default();
+        state.tools.insert(
+            "present".to_string(),
+            tool_state(&present.display().to_string()),
+        );
+        state.tools.insert(
+            "missing".to_string(),
+            tool_state(&dir.path().join("missing").display().to_string()),
+        );
+        state
+            .tools
+            .insert("removed".to_string(), tool_state("/usr/bin/removed"));
+
+        let findings = find_drift(&tools, &state, &ConfigurationManagerProcessor::new(&paths));
+
+        assert_eq!(findings.len(), 2);
+        assert!(
+            matches!(&findings[0], Drift::MissingBinary { tool, .. } if tool.name == "missing")
+        );
+        assert!(matches!(&findings[1], Drift::OrphanedState { name } if name == "removed"));
+    }
+}
@@ -29,25 +29,28 @@
 //!
 //! ```rust
 //! // Remove a tool
-//! remove_tool("git".to_string());
+//! remove_tool(&paths, "git".to_string());
 //!
 //! // Remove a font
-//! remove_font("JetBrainsMono".to_string());
+//! remove_font(&paths, "JetBrainsMono".to_string());
 //!
 //! // Remove an alias
-//! remove_alias("ll".to_string());
+//! remove_alias(&paths, "ll".to_string());
 //!
 //! // Remove a setting
-//! remove_setting("com.apple.dock".to_string(), "autohide".to_string());
+//! remove_setting(&paths, "com.apple.dock".to_string(), "autohide".to_string());
 //! ```
 
+use crate::commands::load_state_or_exit;
+use crate::core::{output, protected};
 use crate::engine::uninstaller::executors::{ConfigurationCleaner, RemovalResult, RemovalSummary};
 use crate::schemas::common::RemovalOrchestrator;
 use crate::schemas::path_resolver::PathResolver;
 use crate::schemas::state_file::DevBoxState;
-use crate::state::manager::{load_or_initialize_state, save_state_to_file};
+use crate::state::manager::save_state_to_file;
 use crate::{log_debug, log_error, log_info, log_warn};
 use colored::Colorize;
+use serde::Serialize;
 use std::path::PathBuf;
 
 // ============================================================================
@@ -58,34 +61,33 @@ use std::path::PathBuf;
 ///
 /// This function handles the common initialization pattern used by all
 /// removal operations:
-/// 1. Initialize PathResolver to locate system directories
-/// 2. Determine the state file path
-/// 3. Load or initialize the state file
+/// 1. Determine the state file path from the resolved paths
+/// 2. Load or initialize the state file
+///
+/// # Arguments
+///
+/// * `paths` - Resolved configuration and state locations
 ///
 /// # Returns
 ///
-/// * `Ok((PathResolver, PathBuf, DevBoxState))` - Initialized components
+/// * `Ok((PathBuf, DevBoxState))` - Initialized components
 /// * `Err(String)` - If initialization failed
 ///
 /// # Error Handling
 ///
 /// This function converts all errors to formatted strings with color coding
 /// for consistent error reporting throughout the removal system.
-fn initialize_removal_components() -> Result<(PathResolver, PathBuf, DevBoxState), String> {
+fn initialize_removal_components(paths: &PathResolver) -> Result<(PathBuf, DevBoxState), String> {
     log_debug!("[SDB::Remove::Init] Initializing removal components");
 
-    // Initialize path resolver to locate system directories
-    let paths = PathResolver::new(None, None)
-        .map_err(|e| format!("Failed to initialize path resolver: {e}"))?;
-
     // Get the state file path and convert to owned PathBuf
     let state_file_path: PathBuf = paths.state_file().to_path_buf();
 
     // Load existing state or create new state if file doesn't exist
-    let state = load_or_initialize_state(&state_file_path);
+    let state = load_state_or_exit(&state_file_path);
 
     log_debug!("[SDB::Remove::Init] Initialized successfully");
-    Ok((paths, state_file_path, state))
+    Ok((state_file_path, state))
 }
 
 /// Handles the complete lifecycle of state-based removal operations.
@@ -104,11 +106,16 @@ fn initialize_removal_components() -> Result<(PathResolver, PathBuf, DevBoxState
 ///
 /// # Arguments
 ///
+/// * `paths` - Resolved configuration and state locations
 /// * `item_name` - Name of the item to remove
 /// * `item_type` - Type description for logging ("tool" or "font")
 /// * `remove_action` - Closure that executes the removal
-fn handle_state_based_removal<F>(item_name: String, item_type: &str, remove_action: F)
-where
+fn handle_state_based_removal<F>(
+    paths: &PathResolver,
+    item_name: String,
+    item_type: &str,
+    remove_action: F,
+) where
     F: FnOnce(&mut RemovalOrchestrator, &str) -> RemovalResult,
 {
     log_info!(
@@ -118,25 +125,58 @@ where
     );
 
     // Initialize core components
-    let (paths, state_file_path, mut state) = match initialize_removal_components() {
+    let (state_file_path, mut state) = match initialize_removal_components(paths) {
         Ok(components) => components,
         Err(e) => {
             log_error!("[SDB::Remove] Initialization failed: {}", e.red());
-            std::process::exit(1);
+            exit_failed(item_type, &item_name, e);
         }
     };
 
+    // Point out who owns the item before it goes away
+    let (owner, notes) = match item_type {
+        "tool" => state
+            .tools
+            .get(&item_name)
+            .map(|tool| (tool.owner.clone(), tool.notes.clone()))
+            .unwrap_or_default(),
+        "font" => state
+            .fonts
+            .get(&item_name)
+            .map(|font| (font.owner.clone(), font.notes.clone()))
+            .unwrap_or_default(),
+        _ => (None, None),
+    };
+    if let Some(owner) = owner {
+        log_warn!(
+            "[SDB::Remove] {} is owned by {}",
+            item_name.bold(),
+            owner.yellow()
+        );
+    }
+    if let Some(notes) = notes {
+        log_info!("[SDB::Remove] Notes for {}: {}", item_name.bold(), notes);
+    }
+    if item_type == "tool"
+        && protected::is_protected(&state, &item_name)
+        && let Err(refusal) = protected::authorize(&mut state, &item_name, "remove")
+    {
+        log_error!("[SDB::Remove] {}", refusal.red());
+        exit_failed(item_type, &item_name, refusal);
+    }
+
     // Create orchestrator
-    let mut orchestrator = match RemovalOrchestrator::new(&mut state, &paths) {
+    let mut orchestrator = match RemovalOrchestrator::new(&mut state, paths) {
         Ok(orch) => orch,
         Err(e) => {
             log_error!("[SDB::Remove] Failed to create orchestrator: {}", e.red());
-            std::process::exit(1);
+            exit_failed(item_type, &item_name, e);
         }
     };
 
     // Execute removal and build summary
     let mut summary = RemovalSummary::default();
+    let mut report = RemoveReport::new(item_type, &item_name, "removed");
 
     match remove_action(&mut orchestrator, &item_name) {
         RemovalResult::Removed => {
@@ -146,10 +186,10 @@ where
                 item_name.green()
             );
             // Add to appropriate summary list based on type
-            if item_type == "tool" {
-                summary.removed_tools.push(item_name);
-            } else {
-                summary.removed_fonts.push(item_name);
+            match item_type {
+                "tool" => summary.removed_tools.push(item_name),
+                "launch agent" => summary.removed_launch_agents.push(item_name),
+                _ => summary.removed_fonts.push(item_name),
             }
         }
         RemovalResult::NotFound => {
@@ -158,6 +198,7 @@ where
                 item_type,
                 item_name.yellow()
             );
+            report.status = "not_found";
             summary.not_found_items.push(item_name);
         }
         RemovalResult::Failed(reason) => {
@@ -166,12 +207,17 @@ where
                 item_type,
                 reason.red()
             );
+            report.status = "failed";
+            report.error = Some(reason.clone());
             summary.failed_removals.push((item_name, reason));
         }
     }
 
     // Save state if any items were successfully removed
-    if !summary.removed_tools.is_empty() || !summary.removed_fonts.is_empty() {
+    if !summary.removed_tools.is_empty()
+        || !summary.removed_fonts.is_empty()
+        || !summary.removed_launch_agents.is_empty()
+    {
         log_debug!(
             "[SDB::Remove] Saving state to: {}",
             state_file_path.display()
@@ -180,13 +226,17 @@ where
     }
 
     // Display summary to user
-    summary.display();
+    if output::is_structured() {
+        output::print_document(&report);
+    } else {
+        summary.display();
+    }
 }
 
 /// Handles the complete lifecycle of configuration-only removal operations.
 ///
 /// This function encapsulates the common pattern for removing aliases and settings:
-/// 1. Initialize PathResolver and ConfigurationCleaner
+/// 1. Initialize ConfigurationCleaner
 /// 2. Execute removal action
 /// 3. Log appropriate success/warning/error message
 ///
@@ -196,11 +246,16 @@ where
 ///
 /// # Arguments
 ///
+/// * `paths` - Resolved configuration and state locations
 /// * `item_name` - Name of the item to remove
 /// * `item_type` - Type description for logging ("alias" or "setting")
 /// * `removal_action` - Closure that executes the removal
-fn handle_config_removal<F>(item_name: String, item_type: &str, removal_action: F)
-where
+fn handle_config_removal<F>(
+    paths: &PathResolver,
+    item_name: String,
+    item_type: &str,
+    removal_action: F,
+) where
     F: FnOnce(&ConfigurationCleaner) -> Result<bool, String>,
 {
     log_info!(
@@ -209,25 +264,17 @@ where
         item_name.cyan()
     );
 
-    // Initialize path resolver
-    let paths = PathResolver::new(None, None).unwrap_or_else(|e| {
-        log_error!(
-            "[SDB::Remove::Config] Failed to initialize paths: {}",
-            e.red()
-        );
-        std::process::exit(1);
-    });
-
     // Create configuration cleaner
-    let cleaner = ConfigurationCleaner::new(&paths).unwrap_or_else(|e| {
+    let cleaner = ConfigurationCleaner::new(paths).unwrap_or_else(|e| {
         log_error!(
             "[SDB::Remove::Config] Failed to initialize cleaner: {}",
             e.red()
         );
-        std::process::exit(1);
+        exit_failed(item_type, &item_name, e);
     });
 
     // Execute removal and handle result
+    let json = output::is_structured();
     match removal_action(&cleaner) {
         Ok(true) => {
             log_info!(
@@ -235,11 +282,15 @@ where
                 item_type,
                 item_name.green()
             );
-            println!(
-                "\n{} {} removed successfully",
-                "✓".green(),
-                item_name.green()
-            );
+            if json {
+                output::print_document(&RemoveReport::new(item_type, &item_name, "removed"));
+            } else {
+                println!(
+                    "\n{} {} removed successfully",
+                    "✓".green(),
+                    item_name.green()
+                );
+            }
         }
         Ok(false) => {
             log_warn!(
@@ -247,7 +298,11 @@ where
                 item_type,
                 item_name.yellow()
             );
-            println!("\n{} {} not found", "⚠".yellow(), item_name.yellow());
+            if json {
+                output::print_document(&RemoveReport::new(item_type, &item_name, "not_found"));
+            } else {
+                println!("\n{} {} not found", "⚠".yellow(), item_name.yellow());
+            }
         }
         Err(e) => {
             log_error!(
@@ -255,12 +310,47 @@ where
                 item_type,
                 e.red()
             );
-            println!("\n{} Failed: {}", "✗".red(), e.red());
-            std::process::exit(1);
+            if !json {
+                println!("\n{} Failed: {}", "✗".red(), e.red());
+            }
+            exit_failed(item_type, &item_name, e);
         }
     }
 }
 
+/// Result of a `remove` command, printed on stdout with `--output json` or `yaml`.
+#[derive(Serialize)]
+struct RemoveReport {
+    /// Kind of item: `tool`, `font`, `launch agent`, `alias` or `setting`.
+    kind: String,
+    name: String,
+    /// `removed`, `not_found` or `failed`.
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl RemoveReport {
+    fn new(kind: &str, name: &str, status: &'static str) -> Self {
+        RemoveReport {
+            kind: kind.to_string(),
+            name: name.to_string(),
+            status,
+            error: None,
+        }
+    }
+}
+
+/// Exits after a failed removal, printing the error as the result with `--output json` or `yaml`.
+fn exit_failed(kind: &str, name: &str, error: String) -> ! {
+    if output::is_structured() {
+        let mut report = RemoveReport::new(kind, name, "failed");
+        report.error = Some(error);
+        output::print_document(&report);
+    }
+    std::process::exit(1);
+}
+
 // ============================================================================
 //                                 PUBLIC API
 // ============================================================================
@@ -275,24 +365,27 @@ where
 ///
 /// # Arguments
 ///
+/// * `paths` - Resolved configuration and state locations
 /// * `tool_name` - Name or alias of the tool to remove
 ///
 /// # Examples
 ///
 /// ```rust
 /// // Remove by original name
-/// remove_tool("ripgrep".to_string());
+/// remove_tool(&paths, "ripgrep".to_string());
 ///
 /// // Remove by alias (if tool was renamed)
-/// remove_tool("rg".to_string());
+/// remove_tool(&paths, "rg".to_string());
 /// ```
 ///
 /// # Exit Codes
 ///
 /// This function may call `std::process::exit(1)` if critical initialization
 /// fails. Otherwise, it completes gracefully and displays a summary.
-pub fn remove_tool(tool_name: String) {
-    handle_state_based_removal(tool_name, "tool", |orch, name| orch.remove_tool(name));
+pub fn remove_tool(paths: &PathResolver, tool_name: String) {
+    handle_state_based_removal(paths, tool_name, "tool", |orch, name| {
+        orch.remove_tool(name)
+    });
 }
 
 /// Removes a font from the system.
@@ -304,12 +397,13 @@ pub fn remove_tool(tool_name: String) {
 ///
 /// # Arguments
 ///
+/// * `paths` - Resolved configuration and state locations
 /// * `font_name` - Name of the font to remove
 ///
 /// # Examples
 ///
 /// ```rust
-/// remove_font("JetBrainsMono".to_string());
+/// remove_font(&paths, "JetBrainsMono".to_string());
 /// ```
 ///
 /// # Font File Matching
@@ -320,8 +414,31 @@ pub fn remove_tool(tool_name: String) {
 /// - JetBrainsMono-Bold.ttf
 /// - JetBrainsMono-Italic.ttf
 /// - etc.
-pub fn remove_font(font_name: String) {
-    handle_state_based_removal(font_name, "font", |orch, name| orch.remove_font(name));
+pub fn remove_font(paths: &PathResolver, font_name: String) {
+    handle_state_based_removal(paths, font_name, "font", |orch, name| {
+        orch.remove_font(name)
+    });
+}
+
+/// Removes a launch agent installed from the `launch_agents:` section of settings.yaml.
+///
+/// The agent is unloaded, its definition file is deleted, and it is dropped from
+/// both the state file and settings.yaml.
+///
+/// # Arguments
+///
+/// * `paths` - Resolved configuration and state locations
+/// * `label` - Label of the agent to remove
+///
+/// # Examples
+///
+/// ```rust
+/// remove_launch_agent(&paths, "com.example.sync".to_string());
+/// ```
+pub fn remove_launch_agent(paths: &PathResolver, label: String) {
+    handle_state_based_removal(paths, label, "launch agent", |orch, label| {
+        orch.remove_launch_agent(label)
+    });
 }
 
 /// Removes an alias definition from shellrc.yaml.
@@ -332,15 +449,16 @@ pub fn remove_font(font_name: String) {
 ///
 /// # Arguments
 ///
+/// * `paths` - Resolved configuration and state locations
 /// * `alias_name` - Name of the alias to remove
 ///
 /// # Examples
 ///
 /// ```rust
-/// remove_alias("ll".to_string());  // Remove 'll' alias
+/// remove_alias(&paths, "ll".to_string());  // Remove 'll' alias
 /// ```
-pub fn remove_alias(alias_name: String) {
-    handle_config_removal(alias_name.clone(), "alias", |cleaner| {
+pub fn remove_alias(paths: &PathResolver, alias_name: String) {
+    handle_config_removal(paths, alias_name.clone(), "alias", |cleaner| {
         cleaner.remove_list_item("shellrc.yaml", "aliases:", "name:", &alias_name)
     });
 }
@@ -353,6 +471,7 @@ pub fn remove_alias(alias_name: String) {
 ///
 /// # Arguments
 ///
+/// * `paths` - Resolved configuration and state locations
 /// * `domain` - The macOS defaults domain (e.g., "com.apple.dock")
 /// * `key` - The setting key within the domain (e.g., "autohide")
 ///
@@ -360,11 +479,39 @@ pub fn remove_alias(alias_name: String) {
 ///
 /// ```rust
 /// // Remove dock autohide setting
-/// remove_setting("com.apple.dock".to_string(), "autohide".to_string());
+/// remove_setting(&paths, "com.apple.dock".to_string(), "autohide".to_string());
 /// ```
-pub fn remove_setting(domain: String, key: String) {
+pub fn remove_setting(paths: &PathResolver, domain: String, key: String) {
     let setting_name = format!("{domain}.{key}");
-    handle_config_removal(setting_name, "setting", |cleaner| {
+    handle_config_removal(paths, setting_name, "setting", |cleaner| {
         cleaner.remove_setting(&domain, &key)
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_alias_is_removed_from_custom_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let shellrc = dir.path().join("shellrc.yaml");
+        fs::write(
+            &shellrc,
+            "aliases:\n  - name: ll\n    value: ls -la\n  - name: gs\n    value: git status\n",
+        )
+        .unwrap();
+        let paths = PathResolver::new(
+            Some(dir.path().join("config.yaml").display().to_string()),
+            Some(dir.path().join("state.json").display().to_string()),
+        )
+        .unwrap();
+
+        remove_alias(&paths, "ll".to_string());
+
+        let content = fs::read_to_string(&shellrc).unwrap();
+        assert!(!content.contains("name: ll"));
+        assert!(content.contains("name: gs"));
+    }
+}
@@ -4,10 +4,11 @@
 //! It allows users to wipe the entire state or specific tool entries,
 //! forcing the next 'now' run to treat them as new installations.
 
+use crate::commands::load_state_or_exit;
 use crate::core::backup::backup_directory;
 use crate::schemas::path_resolver::PathResolver;
 use crate::schemas::state_file::DevBoxState;
-use crate::state::manager::{load_or_initialize_state, save_state_to_file};
+use crate::state::manager::save_state_to_file;
 use crate::{log_debug, log_error, log_info, log_warn};
 use colored::Colorize;
 use std::path::Path;
@@ -44,7 +45,7 @@ pub fn run(tool: Option<String>, all: bool, state_path: Option<String>) {
     }
 
     // 2. Load current state
-    let mut state: DevBoxState = load_or_initialize_state(&state_file);
+    let mut state: DevBoxState = load_state_or_exit(&state_file);
 
     // 3. Perform reset based on arguments
     if all {
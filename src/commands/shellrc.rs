@@ -0,0 +1,80 @@
+//! # Shellrc Command Implementation
+//!
+//! `setup-devbox shellrc preview` renders what applying `shellrc.yaml` would make of each
+//! shell's RC file and prints it as a diff against the current content, without writing
+//! anything.
+
+use colored::Colorize;
+
+use crate::cli::cmd_enums::ShellrcCommands;
+use crate::commands::load_configs_or_exit;
+use crate::engine::installers::shell_run_commands::preview_shell_configs;
+use crate::schemas::path_resolver::PathResolver;
+use crate::shell::diff::{DiffLine, unified_diff};
+use crate::shell::startup::layout_warnings;
+use crate::{log_debug, log_error};
+
+/// Unchanged lines shown around each change.
+const DIFF_CONTEXT: usize = 3;
+
+/// Entry point for the `shellrc` subcommand.
+pub fn run(action: ShellrcCommands) {
+    match action {
+        ShellrcCommands::Preview { config } => preview(config),
+    }
+}
+
+fn preview(config: Option<String>) {
+    log_debug!("[SDB::Shellrc::Preview] Entering shellrc::preview()");
+
+    let paths = match PathResolver::new(config, None) {
+        Ok(paths) => paths,
+        Err(e) => {
+            log_error!("[SDB::Shellrc::Preview] Failed to resolve paths: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let Some(shell_config) = load_configs_or_exit(&paths).shell else {
+        log_error!("[SDB::Shellrc::Preview] No shellrc.yaml configuration found");
+        std::process::exit(1);
+    };
+
+    for preview in preview_shell_configs(&shell_config) {
+        println!(
+            "{} ({})",
+            preview.rc_path.display().to_string().bold(),
+            preview.shell
+        );
+        let diff = unified_diff(&preview.current, &preview.planned, DIFF_CONTEXT);
+        if diff.is_empty() {
+            println!("  {}", "up to date, nothing would be written".green());
+            println!();
+            continue;
+        }
+        for line in diff {
+            match line {
+                DiffLine::Hunk(header) => println!("{}", header.cyan()),
+                DiffLine::Context(text) => println!(" {text}"),
+                DiffLine::Removed(text) => println!("{}", format!("-{text}").red()),
+                DiffLine::Added(text) => println!("{}", format!("+{text}").green()),
+            }
+        }
+        if preview.regenerate {
+            println!(
+                "  {}",
+                "Existing entries change, so the file is rebuilt from scratch; \
+                 `now` only does this with SDB_RESET_SHELLRC_FILE set."
+                    .yellow()
+            );
+        }
+        println!();
+    }
+
+    // Logs are muted while previewing, so startup file problems are printed here
+    let split = shell_config.split_login_profile == Some(true);
+    for shell in shell_config.run_commands.targets() {
+        for warning in layout_warnings(&shell, split) {
+            println!("{} {}", "warning:".yellow().bold(), warning);
+        }
+    }
+}
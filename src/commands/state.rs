@@ -0,0 +1,63 @@
+//! # State Command Implementation
+//!
+//! Every save of the state file first copies the previous state to `state-backups/` next
+//! to it. `setup-devbox state restore` rolls a corrupted or bad state back to the newest
+//! backup, or to the one given with `--from`; `--list` shows the available backups.
+
+use crate::cli::cmd_enums::StateCommands;
+use crate::schemas::path_resolver::PathResolver;
+use crate::state::backups;
+use crate::{log_debug, log_error, log_info};
+use colored::Colorize;
+
+/// Entry point for the `state` subcommand.
+pub fn run(action: StateCommands) {
+    match action {
+        StateCommands::Restore { from, list, state } => restore(from, list, state),
+    }
+}
+
+fn restore(from: Option<String>, list: bool, state: Option<String>) {
+    log_debug!("[SDB::State::Restore] Entering state::restore()");
+
+    let paths = match PathResolver::new(None, state) {
+        Ok(paths) => paths,
+        Err(e) => {
+            log_error!("[SDB::State::Restore] Failed to resolve paths: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let state_file = paths.state_file();
+
+    if list {
+        let available = backups::list(state_file);
+        if available.is_empty() {
+            log_info!(
+                "[SDB::State::Restore] No backups of {} yet",
+                state_file.display()
+            );
+            return;
+        }
+        println!(
+            "{} {}",
+            "Backups in".bold(),
+            backups::backup_dir(state_file).display().to_string().cyan()
+        );
+        for backup in available {
+            let name = backup.file_name().unwrap_or_default().to_string_lossy();
+            println!("  {}", name);
+        }
+        return;
+    }
+
+    match backups::restore(state_file, from.as_deref()) {
+        Ok(_) => log_info!(
+            "[SDB::State::Restore] The previous state was backed up; run {} to go back to it",
+            "setup-devbox state restore --list".cyan()
+        ),
+        Err(e) => {
+            log_error!("[SDB::State::Restore] Cannot restore the state: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,522 @@
+//! # Status Command Implementation
+//!
+//! Prints one row per tool with the version asked for in `tools.yaml`, the version recorded
+//! in `state.json` and the version actually installed, and flags every tool where the three
+//! disagree. Unlike `reconcile`, nothing is changed.
+//!
+//! The installed version comes from the package manager for brew, cargo and pip tools (one
+//! listing per package manager), and from running the executable with `--version` for the
+//! others. Executables that print no recognizable version are reported as `present`.
+//!
+//! Tools with a `rollout` are listed once more with whether this machine is part of it; a
+//! tool held back by its rollout is not reported as drifted for lagging behind.
+//!
+//! `--output json` and `--output yaml` print the rows as one document for scripts.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use colored::Colorize;
+use regex::Regex;
+use serde::Serialize;
+
+use crate::commands::{load_configs_or_exit, load_state_or_exit};
+use crate::core::inventory::{self, Lookup, PackageManager};
+use crate::core::output;
+use crate::core::platform::find_in_path;
+use crate::core::rollout;
+use crate::log_debug;
+use crate::schemas::path_resolver::PathResolver;
+use crate::schemas::state_file::{DevBoxState, ToolState};
+use crate::schemas::tools_enums::SourceType;
+use crate::schemas::tools_types::ToolEntry;
+
+/// How long `<tool> --version` may run before the installed version counts as unknown.
+const VERSION_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// What was found on the system for a tool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Installed {
+    /// Installed at the given version.
+    Version(String),
+    /// Installed, but its version could not be determined.
+    Present,
+    /// Not found.
+    Missing,
+}
+
+/// A disagreement between configuration, state and system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Drift {
+    /// Configured, not in state and not found on the system.
+    NotInstalled,
+    /// Configured and found on the system, but not in state.
+    Untracked,
+    /// In state but no longer configured.
+    Orphaned,
+    /// In state but not found on the system.
+    Missing,
+    /// The pinned version in the configuration differs from the one in state.
+    ConfigState,
+    /// The version in state differs from the installed one.
+    StateSystem,
+}
+
+impl Drift {
+    fn label(self) -> &'static str {
+        match self {
+            Drift::NotInstalled => "not installed",
+            Drift::Untracked => "untracked",
+            Drift::Orphaned => "orphaned",
+            Drift::Missing => "missing",
+            Drift::ConfigState => "config != state",
+            Drift::StateSystem => "state != system",
+        }
+    }
+}
+
+/// One row of the status table.
+struct ToolStatus {
+    name: String,
+    configured: Option<String>,
+    state: Option<String>,
+    installed: Installed,
+    drift: Vec<Drift>,
+}
+
+/// Result of `status`, printed on stdout with `--output json` or `yaml`.
+#[derive(Debug, Serialize)]
+struct StatusReport {
+    tools: Vec<StatusEntry>,
+    drifted: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct StatusEntry {
+    name: String,
+    configured: Option<String>,
+    state: Option<String>,
+    /// The installed version; `present` if it could not be determined, `null` if missing.
+    installed: Option<String>,
+    drift: Vec<Drift>,
+}
+
+impl StatusReport {
+    fn new(rows: &[ToolStatus]) -> Self {
+        StatusReport {
+            tools: rows
+                .iter()
+                .map(|row| StatusEntry {
+                    name: row.name.clone(),
+                    configured: row.configured.clone(),
+                    state: row.state.clone(),
+                    installed: match &row.installed {
+                        Installed::Version(version) => Some(version.clone()),
+                        Installed::Present => Some("present".to_string()),
+                        Installed::Missing => None,
+                    },
+                    drift: row.drift.clone(),
+                })
+                .collect(),
+            drifted: rows.iter().filter(|row| !row.drift.is_empty()).count(),
+        }
+    }
+}
+
+/// Entry point for the `status` subcommand.
+pub fn run(paths: &PathResolver) {
+    log_debug!("[SDB::Status] Entering status::run()");
+
+    let state: DevBoxState = load_state_or_exit(&paths.state_file().to_path_buf());
+    let tools = load_configs_or_exit(paths)
+        .tools
+        .map(|cfg| cfg.tools)
+        .unwrap_or_default();
+
+    let mut managers: Vec<PackageManager> = tools
+        .iter()
+        .map(|tool| source_of(Some(tool), None))
+        .chain(
+            state
+                .tools
+                .values()
+                .map(|tool_state| source_of(None, Some(tool_state))),
+        )
+        .filter_map(|source| PackageManager::of(&source?))
+        .collect();
+    managers.sort_unstable();
+    managers.dedup();
+    inventory::take_snapshot(&managers);
+
+    let names: BTreeSet<&String> = tools
+        .iter()
+        .map(|tool| &tool.name)
+        .chain(state.tools.keys())
+        .collect();
+    let rows: Vec<ToolStatus> = names
+        .into_iter()
+        .map(|name| {
+            let tool = tools.iter().find(|tool| &tool.name == name);
+            let tool_state = state.tools.get(name);
+            let installed = probe_installed(name, tool, tool_state);
+            let configured =
+                tool.map(|tool| tool.version.as_deref().unwrap_or("latest").to_string());
+            let state_version = tool_state.map(|tool_state| tool_state.version.clone());
+            let mut drift = find_drift(configured.as_deref(), state_version.as_deref(), &installed);
+            // A tool whose rollout has not reached this machine is expected to lag behind
+            if tool.is_some_and(|tool| rollout::held_back(tool).is_some()) {
+                drift.retain(|drift| !matches!(drift, Drift::NotInstalled | Drift::ConfigState));
+            }
+            ToolStatus {
+                name: name.clone(),
+                drift,
+                configured,
+                state: state_version,
+                installed,
+            }
+        })
+        .collect();
+
+    if output::is_structured() {
+        output::print_document(&StatusReport::new(&rows));
+        return;
+    }
+    if rows.is_empty() {
+        println!("No tools are configured or installed.");
+        return;
+    }
+    print_table(&rows);
+    print_rollouts(&tools);
+}
+
+/// Whether this machine is in the canary group of each tool with a `rollout`.
+fn print_rollouts(tools: &[ToolEntry]) {
+    let rolled_out: Vec<&ToolEntry> = tools.iter().filter(|tool| tool.rollout.is_some()).collect();
+    if rolled_out.is_empty() {
+        return;
+    }
+    let cohorts = rollout::cohorts();
+    println!(
+        "\n{} (this machine: bucket {}, cohorts: {})",
+        "Rollouts".bold(),
+        rollout::machine_bucket(),
+        if cohorts.is_empty() {
+            "none".to_string()
+        } else {
+            cohorts.join(", ")
+        }
+    );
+    let width = rolled_out
+        .iter()
+        .map(|tool| tool.name.len())
+        .max()
+        .unwrap_or(0);
+    for tool in rolled_out {
+        let spec = tool.rollout.as_deref().unwrap_or_default();
+        let decision = match rollout::decide(tool) {
+            Some(Ok(true)) => "in the canary group".green(),
+            Some(Ok(false)) => "held back".yellow(),
+            Some(Err(e)) => e.red(),
+            None => continue,
+        };
+        println!("  {:<width$}  {:<8}  {}", tool.name, spec, decision);
+    }
+}
+
+fn print_table(rows: &[ToolStatus]) {
+    let cells: Vec<[String; 5]> = rows
+        .iter()
+        .map(|row| {
+            [
+                row.name.clone(),
+                row.configured.clone().unwrap_or_else(|| "-".to_string()),
+                row.state.clone().unwrap_or_else(|| "-".to_string()),
+                match &row.installed {
+                    Installed::Version(version) => version.clone(),
+                    Installed::Present => "present".to_string(),
+                    Installed::Missing => "-".to_string(),
+                },
+                if row.drift.is_empty() {
+                    "in sync".to_string()
+                } else {
+                    let labels: Vec<&str> = row.drift.iter().map(|drift| drift.label()).collect();
+                    labels.join(", ")
+                },
+            ]
+        })
+        .collect();
+    let header = ["TOOL", "CONFIGURED", "STATE", "INSTALLED", "STATUS"];
+    let widths: Vec<usize> = (0..4)
+        .map(|column| {
+            cells
+                .iter()
+                .map(|row| row[column].len())
+                .chain([header[column].len()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let line = |row: &[String; 5]| {
+        format!(
+            "  {:<w0$}  {:<w1$}  {:<w2$}  {:<w3$}  ",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3]
+        )
+    };
+    println!(
+        "{}{}",
+        line(&header.map(String::from)).bold(),
+        header[4].bold()
+    );
+    for (row, cells) in rows.iter().zip(&cells) {
+        let status = if row.drift.is_empty() {
+            cells[4].green()
+        } else {
+            cells[4].yellow()
+        };
+        println!("{}{}", line(cells), status);
+    }
+
+    let drifted = rows.iter().filter(|row| !row.drift.is_empty()).count();
+    println!();
+    if drifted == 0 {
+        println!(
+            "{}",
+            format!("All {} tool(s) are in sync.", rows.len())
+                .green()
+                .bold()
+        );
+    } else {
+        println!(
+            "{}",
+            format!(
+                "{drifted} of {} tool(s) drifted; run 'setup-devbox reconcile' or 'setup-devbox now' to fix them.",
+                rows.len()
+            )
+            .yellow()
+            .bold()
+        );
+    }
+}
+
+/// Compares the configured, recorded and installed versions of a tool.
+fn find_drift(configured: Option<&str>, state: Option<&str>, installed: &Installed) -> Vec<Drift> {
+    let on_system = *installed != Installed::Missing;
+    let mut drift = Vec::new();
+    match (configured, state) {
+        (Some(_), None) if on_system => drift.push(Drift::Untracked),
+        (Some(_), None) => drift.push(Drift::NotInstalled),
+        (None, Some(_)) => drift.push(Drift::Orphaned),
+        (Some(configured), Some(state)) => {
+            if configured != "latest" && !same_version(configured, state) {
+                drift.push(Drift::ConfigState);
+            }
+        }
+        (None, None) => {}
+    }
+    if let Some(state) = state {
+        match installed {
+            Installed::Missing => drift.push(Drift::Missing),
+            Installed::Version(version) if !same_version(state, version) => {
+                drift.push(Drift::StateSystem)
+            }
+            _ => {}
+        }
+    }
+    drift
+}
+
+/// Whether two version strings name the same release, ignoring a leading `v` and build
+/// suffixes such as Homebrew's revision (`3.12.4_1`).
+fn same_version(a: &str, b: &str) -> bool {
+    let a = a.trim().trim_start_matches('v');
+    let b = b.trim().trim_start_matches('v');
+    let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    longer.starts_with(shorter)
+        && !shorter.is_empty()
+        && longer[shorter.len()..]
+            .chars()
+            .next()
+            .is_none_or(|next| !next.is_ascii_digit() && next != '.')
+}
+
+/// Finds the installed version of a tool, asking its package manager first.
+fn probe_installed(
+    name: &str,
+    tool: Option<&ToolEntry>,
+    tool_state: Option<&ToolState>,
+) -> Installed {
+    if let Some(manager) =
+        source_of(tool, tool_state).and_then(|source| PackageManager::of(&source))
+    {
+        match inventory::lookup(manager, name) {
+            Lookup::Installed(version) => return Installed::Version(version),
+            Lookup::Missing => return Installed::Missing,
+            Lookup::Unknown => {}
+        }
+    }
+
+    let Some(executable) = locate_executable(name, tool, tool_state) else {
+        return Installed::Missing;
+    };
+    match run_version_flag(&executable) {
+        Some(version) => Installed::Version(version),
+        None => Installed::Present,
+    }
+}
+
+/// The source of a tool: from its configuration, or the install method recorded in state.
+fn source_of(tool: Option<&ToolEntry>, tool_state: Option<&ToolState>) -> Option<SourceType> {
+    match (tool, tool_state) {
+        (Some(tool), _) => Some(tool.source.clone()),
+        (None, Some(tool_state)) => {
+            ToolState::normalize_source_type(&tool_state.install_method.to_lowercase())
+                .parse()
+                .ok()
+        }
+        (None, None) => None,
+    }
+}
+
+/// The recorded install path when it still exists, otherwise the tool's executable on `PATH`.
+fn locate_executable(
+    name: &str,
+    tool: Option<&ToolEntry>,
+    tool_state: Option<&ToolState>,
+) -> Option<PathBuf> {
+    if let Some(tool_state) = tool_state {
+        let install_path = Path::new(&tool_state.install_path);
+        if install_path.is_absolute() && install_path.exists() {
+            return Some(install_path.to_path_buf());
+        }
+    }
+    let binary_name = tool
+        .and_then(|tool| tool.rename_to.as_deref())
+        .or(tool_state.and_then(|tool_state| tool_state.renamed_to.as_deref()))
+        .unwrap_or(name);
+    find_in_path(binary_name)
+}
+
+/// Runs `<executable> --version` and extracts the first version number it prints.
+fn run_version_flag(executable: &Path) -> Option<String> {
+    if !executable.is_file() {
+        return None;
+    }
+    let mut child = Command::new(executable)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    let started = Instant::now();
+    while child.try_wait().ok()?.is_none() {
+        if started.elapsed() > VERSION_PROBE_TIMEOUT {
+            log_debug!(
+                "[SDB::Status] '{} --version' did not finish in time",
+                executable.display()
+            );
+            let _ = child.kill();
+            let _ = child.wait();
+            return None;
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    let output = child.wait_with_output().ok()?;
+    extract_version(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn extract_version(text: &str) -> Option<String> {
+    let pattern = Regex::new(r"\d+(?:\.\d+)+(?:[-+][0-9A-Za-z.]+)?").ok()?;
+    pattern.find(text).map(|found| found.as_str().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_drift() {
+        let installed = Installed::Version("14.1.0".to_string());
+        assert!(find_drift(Some("latest"), Some("v14.1.0"), &installed).is_empty());
+        assert!(find_drift(Some("14.1"), Some("14.1"), &Installed::Present).is_empty());
+        assert_eq!(
+            find_drift(Some("14.0.0"), Some("14.1.0"), &installed),
+            vec![Drift::ConfigState]
+        );
+        assert_eq!(
+            find_drift(Some("latest"), Some("13.0.0"), &installed),
+            vec![Drift::StateSystem]
+        );
+        assert_eq!(
+            find_drift(None, Some("14.1.0"), &Installed::Missing),
+            vec![Drift::Orphaned, Drift::Missing]
+        );
+        assert_eq!(
+            find_drift(Some("latest"), None, &Installed::Present),
+            vec![Drift::Untracked]
+        );
+        assert_eq!(
+            find_drift(Some("latest"), None, &Installed::Missing),
+            vec![Drift::NotInstalled]
+        );
+    }
+
+    #[test]
+    fn test_status_report_document() {
+        let rows = [
+            ToolStatus {
+                name: "rg".to_string(),
+                configured: Some("latest".to_string()),
+                state: Some("14.1.0".to_string()),
+                installed: Installed::Version("14.1.0".to_string()),
+                drift: Vec::new(),
+            },
+            ToolStatus {
+                name: "fd".to_string(),
+                configured: None,
+                state: Some("9.0.0".to_string()),
+                installed: Installed::Missing,
+                drift: vec![Drift::Orphaned, Drift::Missing],
+            },
+        ];
+        let value = serde_json::to_value(StatusReport::new(&rows)).unwrap();
+
+        assert_eq!(value["drifted"], 1);
+        assert_eq!(value["tools"][0]["installed"], "14.1.0");
+        assert_eq!(value["tools"][0]["drift"], serde_json::json!([]));
+        assert!(value["tools"][1]["installed"].is_null());
+        assert_eq!(
+            value["tools"][1]["drift"],
+            serde_json::json!(["orphaned", "missing"])
+        );
+    }
+
+    #[test]
+    fn test_same_version_and_extract_version() {
+        assert!(same_version("3.12.4", "3.12.4_1"));
+        assert!(same_version("v1.2.3", "1.2.3"));
+        assert!(!same_version("1.2", "1.20"));
+        assert!(!same_version("1.2.3", "1.2.30"));
+
+        assert_eq!(
+            extract_version("ripgrep 14.1.0 (rev e50df40a19)\n").as_deref(),
+            Some("14.1.0")
+        );
+        assert_eq!(
+            extract_version("go version go1.22.5 darwin/arm64").as_deref(),
+            Some("1.22.5")
+        );
+        assert_eq!(extract_version("no version here"), None);
+    }
+}
@@ -20,6 +20,7 @@
 //! 2. **Transformation**: Convert state data to configuration format
 //! 3. **File Generation**: Write properly formatted YAML files
 
+use crate::core::osx_pkg::DEFAULT_APP_DIR;
 use crate::engine::configuration::processor::ConfigurationManagerState;
 use crate::schemas::config_manager::ConfigurationManager;
 use crate::schemas::fonts::FontEntry;
@@ -28,12 +29,13 @@ use crate::schemas::path_resolver::PathResolver;
 use crate::schemas::shell_configuration::AliasEntry;
 use crate::schemas::state_file::{FontState, SettingState, ToolState};
 use crate::schemas::tools_enums::SourceType;
-use crate::schemas::tools_types::ToolEntry;
+use crate::schemas::tools_types::{AppBundleConfig, Hook, ShimConfig, ToolEntry};
+use crate::state::manager::read_state_file;
 use crate::{log_debug, log_error, log_info};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fs,
     path::{Path, PathBuf},
 };
@@ -54,6 +56,9 @@ pub enum SyncError {
     /// Failed to serialize data to YAML format
     Yaml(serde_yaml::Error),
 
+    /// The state file could not be read or parsed
+    State(String),
+
     /// Path resolution or validation failed
     PathError(String),
 
@@ -85,6 +90,7 @@ impl std::fmt::Display for SyncError {
             SyncError::Io(e) => write!(f, "I/O Error: {}", e.to_string().red()),
             SyncError::Json(e) => write!(f, "JSON Parsing Error: {}", e.to_string().red()),
             SyncError::Yaml(e) => write!(f, "YAML Serialization Error: {}", e.to_string().red()),
+            SyncError::State(s) => write!(f, "State File Error: {}", s.red()),
             SyncError::PathError(s) => write!(f, "Path Error: {}", s.to_string().red()),
             SyncError::Network(s) => write!(f, "Network Error: {}", s.to_string().red()),
         }
@@ -98,11 +104,11 @@ pub type SyncResult<T> = Result<T, SyncError>;
 //                              STATE STRUCTURES
 // ============================================================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug)]
 pub struct AppState {
-    pub tools: HashMap<String, ToolState>,
-    pub settings: HashMap<String, SettingState>,
-    pub fonts: HashMap<String, FontState>,
+    pub tools: BTreeMap<String, ToolState>,
+    pub settings: BTreeMap<String, SettingState>,
+    pub fonts: BTreeMap<String, FontState>,
 }
 
 // ============================================================================
@@ -174,6 +180,16 @@ impl From<&FontState> for FontEntry {
             repo: font_state.repo.clone(),
             tag: font_state.tag.clone(),
             install_only: font_state.install_only.clone(),
+            variant: font_state.variant.clone(),
+            requires_license_acceptance: font_state
+                .license
+                .as_ref()
+                .and_then(|license| license.accepted_at.as_ref())
+                .map(|_| true),
+            notes: font_state.notes.clone(),
+            owner: font_state.owner.clone(),
+            scope: font_state.scope,
+            profiles: None,
         }
     }
 }
@@ -198,11 +214,66 @@ impl ToolEntry {
             executable_path_after_extract: tool_state.executable_path_after_extract.clone(),
             post_installation_hooks: Self::filter_empty_vec(
                 tool_state.executed_post_installation_hooks.clone(),
-            ),
+            )
+            .map(|hooks| hooks.into_iter().map(Hook::Command).collect()),
             configuration_manager: config_manager.unwrap_or_else(|| ConfigurationManager {
                 enabled: false,
                 tools_configuration_paths: Vec::new(),
             }),
+            smoke_test: None,
+            streaming: None,
+            delta_updates: None,
+            checksum: None,
+            executable_detection: None,
+            exclude_from_search: None,
+            assets: None,
+            headers: None,
+            basic_auth: None,
+            url_command: None,
+            http_method: None,
+            version_check: None,
+            phase: None,
+            shim: tool_state.shim.as_ref().map(|shim| ShimConfig {
+                env: shim.env.clone(),
+                working_dir: shim.working_dir.clone(),
+                template: None,
+            }),
+            requires_license_acceptance: tool_state
+                .license
+                .as_ref()
+                .and_then(|license| license.accepted_at.as_ref())
+                .map(|_| true),
+            requires_env: None,
+            notes: tool_state.notes.clone(),
+            owner: tool_state.owner.clone(),
+            protected: tool_state.protected,
+            only_on: None,
+            profiles: None,
+            rollout: None,
+            acknowledge_dangerous: None,
+            permissions: None,
+            preset: None,
+            path: None,
+            binary: None,
+            // Bundles installed elsewhere than the default application directory
+            app_dir: tool_state
+                .app_bundle
+                .as_ref()
+                .and_then(|bundle| Path::new(&bundle.path).parent())
+                .filter(|dir| *dir != Path::new(DEFAULT_APP_DIR))
+                .map(|dir| dir.display().to_string()),
+            optimize_binary: tool_state
+                .optimized_binary
+                .as_ref()
+                .map(|optimized| optimized.method),
+            app_bundle: tool_state
+                .app_bundle
+                .as_ref()
+                .map(|bundle| AppBundleConfig {
+                    cli: Some(bundle.cli.clone()),
+                }),
+            wasm_runtime: None,
+            platforms: None,
         }
     }
 
@@ -229,6 +300,8 @@ impl ToolEntry {
                 log_debug!("[Sync::Tool] Omitting URL for GitHub tool: {}", name);
                 None
             }
+            // The state records the pinned manifest digest, `repo` and `version` suffice
+            SourceType::Oci => None,
             _ => url.clone(),
         }
     }
@@ -245,7 +318,12 @@ impl ToolEntry {
             .filter(|mgr| mgr.enabled)
             .map(|mgr| ConfigurationManager {
                 enabled: mgr.enabled,
-                tools_configuration_paths: mgr.tools_configuration_paths.clone(),
+                tools_configuration_paths: mgr
+                    .tools_configuration_paths
+                    .iter()
+                    .cloned()
+                    .map(Into::into)
+                    .collect(),
             })
     }
 }
@@ -254,6 +332,7 @@ impl ToolEntry {
 //                                FILE GENERATION
 // ============================================================================
 
+#[derive(Default)]
 pub struct FileWriter;
 
 impl FileWriter {
@@ -388,7 +467,7 @@ impl ConfigGenerator {
 
     fn generate_tools_config(
         &self,
-        tools: &HashMap<String, ToolState>,
+        tools: &BTreeMap<String, ToolState>,
         target_path: &Path,
     ) -> SyncResult<()> {
         let tool_entries: Vec<ToolEntry> = tools
@@ -407,7 +486,7 @@ impl ConfigGenerator {
 
     fn generate_fonts_config(
         &self,
-        fonts: &HashMap<String, FontState>,
+        fonts: &BTreeMap<String, FontState>,
         target_path: &Path,
     ) -> SyncResult<()> {
         let font_entries: Vec<FontEntry> = fonts.values().map(FontEntry::from).collect();
@@ -420,7 +499,7 @@ impl ConfigGenerator {
 
     fn generate_settings_config(
         &self,
-        settings: &HashMap<String, SettingState>,
+        settings: &BTreeMap<String, SettingState>,
         target_path: &Path,
     ) -> SyncResult<()> {
         let macos_settings: Vec<SettingEntry> = settings
@@ -438,6 +517,9 @@ impl ConfigGenerator {
                 macos: macos_settings,
                 linux: vec![],
             },
+            // State only records where agents were installed, not their definitions
+            launch_agents: vec![],
+            file_associations: vec![],
         };
 
         self.writer.write_yaml(target_path, &settings_config)?;
@@ -541,8 +623,12 @@ impl SyncOrchestrator {
             )));
         }
 
-        let state_content = fs::read_to_string(&self.state_file_path)?;
-        let app_state: AppState = serde_json::from_str(&state_content)?;
+        let state = read_state_file(&self.state_file_path).map_err(SyncError::State)?;
+        let app_state = AppState {
+            tools: state.tools,
+            settings: state.settings,
+            fonts: state.fonts,
+        };
 
         let generator = ConfigGenerator::new(self.config_dir_path.clone());
         let generated_files = generator.generate_configs(&app_state)?;
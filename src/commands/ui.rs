@@ -0,0 +1,645 @@
+//! # UI Command Implementation
+//!
+//! `setup-devbox ui` is a terminal dashboard listing configured tools, fonts and settings
+//! with their sync status. Items can be selected and installed/updated or removed, and the
+//! output of the last action is available in the Logs tab.
+//!
+//! The dashboard is built on the library facade ([`crate::api`]): statuses come from
+//! [`api::plan`] and the state file, and actions reuse the same code paths as `now` and
+//! `remove`. While an action runs, the dashboard hands the terminal back so installer
+//! output and prompts behave exactly as they do on the command line.
+
+use crate::api::{self, ApplyOptions, DevBoxState, ParsedConfigs, PathResolver, ToolAction};
+use crate::commands::remove;
+use crate::config::read_only;
+use crate::fonts::installer::install_fonts;
+use crate::logger::{enable_capture, set_terminal_output};
+use crate::settings::apply_system_settings;
+use crate::{log_debug, log_error};
+use ratatui::DefaultTerminal;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, Tabs};
+use std::collections::BTreeSet;
+use std::io::BufRead;
+
+/// Dashboard tabs, in display order.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Tab {
+    Tools,
+    Fonts,
+    Settings,
+    Logs,
+}
+
+impl Tab {
+    const ALL: [Tab; 4] = [Tab::Tools, Tab::Fonts, Tab::Settings, Tab::Logs];
+
+    fn title(self) -> &'static str {
+        match self {
+            Tab::Tools => "Tools",
+            Tab::Fonts => "Fonts",
+            Tab::Settings => "Settings",
+            Tab::Logs => "Logs",
+        }
+    }
+
+    fn index(self) -> usize {
+        Tab::ALL.iter().position(|tab| *tab == self).unwrap_or(0)
+    }
+}
+
+/// Sync status of a single item.
+#[derive(Clone, Copy)]
+enum Status {
+    InSync,
+    NotInstalled,
+    UpdateAvailable,
+    ConfigDrift,
+}
+
+impl Status {
+    fn label(self) -> &'static str {
+        match self {
+            Status::InSync => "in sync",
+            Status::NotInstalled => "not installed",
+            Status::UpdateAvailable => "update available",
+            Status::ConfigDrift => "config drift",
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Status::InSync => Color::Green,
+            Status::NotInstalled => Color::Red,
+            Status::UpdateAvailable => Color::Yellow,
+            Status::ConfigDrift => Color::Magenta,
+        }
+    }
+}
+
+/// One row of the Tools, Fonts or Settings table.
+struct Item {
+    /// Identifier used for selection and actions (`domain.key` for settings)
+    name: String,
+    /// Source and configured version or value
+    detail: String,
+    /// Installed version or current value from the state file
+    current: String,
+    status: Status,
+    /// `owner` and `notes` from the configuration, shown for the item under the cursor
+    about: Option<String>,
+}
+
+/// An action chosen from the dashboard, run with the terminal handed back.
+enum Action {
+    Install(Tab, Vec<String>),
+    Remove(Tab, Vec<String>),
+    ApplyAll,
+}
+
+/// The dashboard's hold on the terminal. Dropping it, also while unwinding from a panic,
+/// leaves raw mode and the alternate screen and turns log output to stderr back on.
+struct Dashboard {
+    terminal: DefaultTerminal,
+}
+
+impl Dashboard {
+    fn enter() -> Self {
+        let terminal = ratatui::init();
+        set_terminal_output(false);
+        Self { terminal }
+    }
+}
+
+impl Drop for Dashboard {
+    fn drop(&mut self) {
+        set_terminal_output(true);
+        ratatui::restore();
+    }
+}
+
+struct App {
+    paths: PathResolver,
+    tab: Tab,
+    items: [Vec<Item>; 3],
+    selected: [BTreeSet<String>; 3],
+    tables: [TableState; 3],
+    logs: Vec<String>,
+    log_scroll: u16,
+    message: String,
+    pending_removal: Option<Vec<String>>,
+}
+
+/// Entry point for the `ui` subcommand.
+pub fn run(config: Option<String>, state: Option<String>) {
+    log_debug!("[SDB::UI] Entering ui::run()");
+
+    let paths = match PathResolver::new(config, state) {
+        Ok(paths) => paths,
+        Err(e) => {
+            log_error!("[SDB::UI] Failed to resolve paths: {}", e);
+            return;
+        }
+    };
+
+    let capture = enable_capture();
+    let mut app = App::new(paths);
+
+    let mut dashboard = Dashboard::enter();
+    app.refresh();
+
+    loop {
+        if let Err(e) = dashboard.terminal.draw(|frame| app.draw(frame)) {
+            drop(dashboard);
+            log_error!("[SDB::UI] Failed to draw the dashboard: {}", e);
+            return;
+        }
+
+        let Ok(Event::Key(key)) = event::read() else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match app.handle_key(key.code) {
+            KeyOutcome::Quit => break,
+            KeyOutcome::Continue => {}
+            KeyOutcome::Run(_) if read_only::is_read_only(&app.paths) => {
+                app.message = "Read-only mode: installing and removing is disabled on this machine"
+                    .to_string();
+            }
+            KeyOutcome::Run(action) => {
+                let receiver = capture.subscribe();
+                let result;
+                (dashboard, result) =
+                    run_outside_dashboard(dashboard, || execute(&app.paths, action));
+                app.logs = receiver.try_iter().collect();
+                app.log_scroll = 0;
+                app.message = match result {
+                    Ok(()) => format!(
+                        "Action finished ({} log lines, see Logs tab)",
+                        app.logs.len()
+                    ),
+                    Err(e) => format!("Action failed: {e}"),
+                };
+                app.refresh();
+            }
+        }
+    }
+}
+
+enum KeyOutcome {
+    Continue,
+    Quit,
+    Run(Action),
+}
+
+impl App {
+    fn new(paths: PathResolver) -> Self {
+        App {
+            paths,
+            tab: Tab::Tools,
+            items: [Vec::new(), Vec::new(), Vec::new()],
+            selected: [BTreeSet::new(), BTreeSet::new(), BTreeSet::new()],
+            tables: [
+                TableState::default(),
+                TableState::default(),
+                TableState::default(),
+            ],
+            logs: Vec::new(),
+            log_scroll: 0,
+            message: String::new(),
+            pending_removal: None,
+        }
+    }
+
+    /// Reloads configuration, state and plan so every status reflects the current machine.
+    /// When they cannot be loaded, the previous items stay and the error is shown in the footer.
+    fn refresh(&mut self) {
+        if let Err(e) = self.load_items() {
+            self.message = format!("Refresh failed: {e}");
+            return;
+        }
+        for (index, items) in self.items.iter().enumerate() {
+            self.selected[index].retain(|name| items.iter().any(|item| &item.name == name));
+            let table = &mut self.tables[index];
+            match table.selected() {
+                _ if items.is_empty() => table.select(None),
+                Some(row) if row >= items.len() => table.select(Some(items.len() - 1)),
+                None => table.select(Some(0)),
+                Some(_) => {}
+            }
+        }
+    }
+
+    fn load_items(&mut self) -> Result<(), api::Error> {
+        let configs = api::load_config(&self.paths)?;
+        let state = api::load_state(&self.paths)?;
+        self.items = [
+            tool_items(&self.paths, &configs, &state)?,
+            font_items(&configs, &state),
+            setting_items(&configs, &state),
+        ];
+        Ok(())
+    }
+
+    fn handle_key(&mut self, code: KeyCode) -> KeyOutcome {
+        if let Some(names) = self.pending_removal.take() {
+            if matches!(code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                return KeyOutcome::Run(Action::Remove(self.tab, names));
+            }
+            self.message = "Removal cancelled".to_string();
+            return KeyOutcome::Continue;
+        }
+
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return KeyOutcome::Quit,
+            KeyCode::Tab | KeyCode::Right => self.switch_tab(1),
+            KeyCode::BackTab | KeyCode::Left => self.switch_tab(Tab::ALL.len() - 1),
+            KeyCode::Char(digit @ '1'..='4') => {
+                self.tab = Tab::ALL[digit as usize - '1' as usize];
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.move_cursor(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_cursor(-1),
+            KeyCode::Char(' ') => self.toggle_selection(),
+            KeyCode::Char('R') => {
+                self.refresh();
+                self.message = "Refreshed".to_string();
+            }
+            KeyCode::Char('a') => return KeyOutcome::Run(Action::ApplyAll),
+            KeyCode::Char('i') => {
+                if let Some(names) = self.targets() {
+                    return KeyOutcome::Run(Action::Install(self.tab, names));
+                }
+            }
+            KeyCode::Char('r') => {
+                if let Some(names) = self.targets() {
+                    self.message = format!("Remove {}? (y/N)", names.join(", "));
+                    self.pending_removal = Some(names);
+                }
+            }
+            _ => {}
+        }
+        KeyOutcome::Continue
+    }
+
+    fn switch_tab(&mut self, offset: usize) {
+        self.tab = Tab::ALL[(self.tab.index() + offset) % Tab::ALL.len()];
+    }
+
+    fn move_cursor(&mut self, delta: i32) {
+        if self.tab == Tab::Logs {
+            self.log_scroll = self.log_scroll.saturating_add_signed(delta as i16);
+            return;
+        }
+        let index = self.tab.index();
+        let len = self.items[index].len();
+        if len == 0 {
+            return;
+        }
+        let current = self.tables[index].selected().unwrap_or(0) as i32;
+        let next = (current + delta).clamp(0, len as i32 - 1) as usize;
+        self.tables[index].select(Some(next));
+    }
+
+    fn toggle_selection(&mut self) {
+        let Some(name) = self.current_item().map(|item| item.name.clone()) else {
+            return;
+        };
+        let selected = &mut self.selected[self.tab.index()];
+        if !selected.remove(&name) {
+            selected.insert(name);
+        }
+    }
+
+    fn current_item(&self) -> Option<&Item> {
+        if self.tab == Tab::Logs {
+            return None;
+        }
+        let index = self.tab.index();
+        self.tables[index]
+            .selected()
+            .and_then(|row| self.items[index].get(row))
+    }
+
+    /// The selected items of the current tab, or the item under the cursor if none are.
+    fn targets(&self) -> Option<Vec<String>> {
+        if self.tab == Tab::Logs {
+            return None;
+        }
+        let selected = &self.selected[self.tab.index()];
+        if selected.is_empty() {
+            self.current_item().map(|item| vec![item.name.clone()])
+        } else {
+            Some(selected.iter().cloned().collect())
+        }
+    }
+
+    fn draw(&mut self, frame: &mut ratatui::Frame) {
+        let [tabs_area, body_area, footer_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(3),
+            Constraint::Length(3),
+        ])
+        .areas(frame.area());
+
+        let titles: Vec<String> = Tab::ALL
+            .iter()
+            .enumerate()
+            .map(|(index, tab)| format!("{} {}", index + 1, tab.title()))
+            .collect();
+        frame.render_widget(
+            Tabs::new(titles)
+                .select(self.tab.index())
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(" setup-devbox "),
+                )
+                .highlight_style(
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            tabs_area,
+        );
+
+        if self.tab == Tab::Logs {
+            let text = if self.logs.is_empty() {
+                vec![Line::from("No action has been run from the dashboard yet.")]
+            } else {
+                self.logs
+                    .iter()
+                    .map(|line| Line::from(line.as_str()))
+                    .collect()
+            };
+            frame.render_widget(
+                Paragraph::new(text)
+                    .scroll((self.log_scroll, 0))
+                    .block(Block::default().borders(Borders::ALL).title(" Last run ")),
+                body_area,
+            );
+        } else {
+            let index = self.tab.index();
+            let (detail_header, current_header) = match self.tab {
+                Tab::Settings => ("Desired value", "Applied value"),
+                _ => ("Source / version", "Installed"),
+            };
+            let selected = &self.selected[index];
+            let rows = self.items[index].iter().map(|item| {
+                let mark = if selected.contains(&item.name) {
+                    "[x]"
+                } else {
+                    "[ ]"
+                };
+                Row::new(vec![
+                    Cell::from(mark),
+                    Cell::from(item.name.clone()),
+                    Cell::from(item.detail.clone()),
+                    Cell::from(item.current.clone()),
+                    Cell::from(item.status.label()).style(Style::default().fg(item.status.color())),
+                ])
+            });
+            let table = Table::new(
+                rows,
+                [
+                    Constraint::Length(3),
+                    Constraint::Percentage(25),
+                    Constraint::Percentage(30),
+                    Constraint::Percentage(20),
+                    Constraint::Percentage(25),
+                ],
+            )
+            .header(
+                Row::new(vec!["", "Name", detail_header, current_header, "Status"])
+                    .style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+            let about = self
+                .current_item()
+                .and_then(|item| item.about.clone())
+                .map(|about| format!(" {about} "))
+                .unwrap_or_default();
+            let table = table.block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" {} ", self.tab.title()))
+                    .title_bottom(about),
+            );
+            frame.render_stateful_widget(table, body_area, &mut self.tables[index]);
+        }
+
+        let help = "↑/↓ move  space select  i install/update  r remove  a apply all  R refresh  tab switch  q quit";
+        let footer = if self.message.is_empty() {
+            help.to_string()
+        } else {
+            format!("{}  |  {}", self.message, help)
+        };
+        frame.render_widget(
+            Paragraph::new(footer).block(Block::default().borders(Borders::ALL)),
+            footer_area,
+        );
+    }
+}
+
+fn tool_items(
+    paths: &PathResolver,
+    configs: &ParsedConfigs,
+    state: &DevBoxState,
+) -> Result<Vec<Item>, api::Error> {
+    let Some(tools) = &configs.tools else {
+        return Ok(Vec::new());
+    };
+    Ok(api::plan(paths, false)?
+        .into_iter()
+        .map(|planned| {
+            let tool = tools.tools.iter().find(|tool| tool.name == planned.name);
+            let status = match planned.action {
+                ToolAction::Install => Status::NotInstalled,
+                ToolAction::Update => Status::UpdateAvailable,
+                ToolAction::UpdateConfigurationOnly => Status::ConfigDrift,
+                ToolAction::Skip(_) | ToolAction::SkipConfigurationOnly(_) => Status::InSync,
+            };
+            Item {
+                detail: tool
+                    .map(|tool| {
+                        format!(
+                            "{} {}",
+                            tool.source,
+                            tool.version.as_deref().unwrap_or("latest")
+                        )
+                    })
+                    .unwrap_or_default(),
+                current: state
+                    .tools
+                    .get(&planned.name)
+                    .map(|tool_state| tool_state.version.clone())
+                    .unwrap_or_else(|| "-".to_string()),
+                about: tool.and_then(|tool| about(&tool.owner, &tool.notes)),
+                name: planned.name,
+                status,
+            }
+        })
+        .collect())
+}
+
+fn font_items(configs: &ParsedConfigs, state: &DevBoxState) -> Vec<Item> {
+    let Some(fonts) = &configs.fonts else {
+        return Vec::new();
+    };
+    fonts
+        .fonts
+        .iter()
+        .map(|font| {
+            let installed = state.fonts.get(&font.name);
+            let configured = font.version.as_deref().unwrap_or("latest");
+            let status = match installed {
+                None => Status::NotInstalled,
+                Some(font_state) if configured != "latest" && font_state.version != configured => {
+                    Status::UpdateAvailable
+                }
+                Some(_) => Status::InSync,
+            };
+            Item {
+                name: font.name.clone(),
+                detail: format!("{} {}", font.source, configured),
+                current: installed
+                    .map(|font_state| font_state.version.clone())
+                    .unwrap_or_else(|| "-".to_string()),
+                status,
+                about: about(&font.owner, &font.notes),
+            }
+        })
+        .collect()
+}
+
+fn setting_items(configs: &ParsedConfigs, state: &DevBoxState) -> Vec<Item> {
+    let Some(settings) = &configs.settings else {
+        return Vec::new();
+    };
+    let entries = if cfg!(target_os = "macos") {
+        &settings.settings.macos
+    } else {
+        &settings.settings.linux
+    };
+    entries
+        .iter()
+        .map(|entry| {
+            let name = format!("{}.{}", entry.domain, entry.key);
+            let applied = state.settings.get(&name);
+            let status = match applied {
+                None => Status::NotInstalled,
+                Some(setting) if setting.value != entry.value => Status::UpdateAvailable,
+                Some(_) => Status::InSync,
+            };
+            Item {
+                detail: entry.value.clone(),
+                current: applied
+                    .map(|setting| setting.value.clone())
+                    .unwrap_or_else(|| "-".to_string()),
+                name,
+                status,
+                about: None,
+            }
+        })
+        .collect()
+}
+
+/// Formats `owner` and `notes` of an entry, e.g. `owner: platform-team · pinned for glibc 2.31`.
+fn about(owner: &Option<String>, notes: &Option<String>) -> Option<String> {
+    match (owner, notes) {
+        (Some(owner), Some(notes)) => Some(format!("owner: {owner} · {notes}")),
+        (Some(owner), None) => Some(format!("owner: {owner}")),
+        (None, Some(notes)) => Some(notes.clone()),
+        (None, None) => None,
+    }
+}
+
+/// Restores the normal terminal, runs `task`, waits for Enter and re-enters the dashboard.
+fn run_outside_dashboard<T>(dashboard: Dashboard, task: impl FnOnce() -> T) -> (Dashboard, T) {
+    drop(dashboard);
+
+    let result = task();
+
+    eprintln!();
+    eprintln!("Press Enter to return to the dashboard...");
+    let _ = std::io::stdin().lock().read_line(&mut String::new());
+
+    (Dashboard::enter(), result)
+}
+
+fn execute(paths: &PathResolver, action: Action) -> Result<(), api::Error> {
+    let options = ApplyOptions::default();
+    match action {
+        Action::ApplyAll => {
+            api::apply(paths, &options)?;
+        }
+        Action::Install(Tab::Tools, names) => {
+            api::apply_tools(paths, &names, &options)?;
+        }
+        Action::Install(Tab::Fonts, names) => {
+            if let Some(mut fonts_config) = api::load_config(paths)?.fonts {
+                fonts_config.fonts.retain(|font| names.contains(&font.name));
+                let mut state = api::load_state(paths)?;
+                install_fonts(fonts_config, &mut state, paths.state_file());
+            }
+        }
+        Action::Install(Tab::Settings, names) => {
+            if let Some(mut settings_config) = api::load_config(paths)?.settings {
+                let wanted = |entry: &crate::schemas::os_settings::SettingEntry| {
+                    names.contains(&format!("{}.{}", entry.domain, entry.key))
+                };
+                settings_config.settings.macos.retain(wanted);
+                settings_config.settings.linux.retain(wanted);
+                let mut state = api::load_state(paths)?;
+                apply_system_settings(settings_config, &mut state, paths.state_file());
+            }
+        }
+        Action::Remove(Tab::Tools, names) => names
+            .into_iter()
+            .for_each(|name| remove::remove_tool(paths, name)),
+        Action::Remove(Tab::Fonts, names) => names
+            .into_iter()
+            .for_each(|name| remove::remove_font(paths, name)),
+        Action::Remove(Tab::Settings, names) => {
+            for name in names {
+                if let Some((domain, key)) = name.rsplit_once('.') {
+                    remove::remove_setting(paths, domain.to_string(), key.to_string());
+                }
+            }
+        }
+        Action::Install(Tab::Logs, _) | Action::Remove(Tab::Logs, _) => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_refresh_reports_broken_config_in_footer() {
+        let _hooks = crate::core::lifecycle_hooks::tests::HOOKS_LOCK.lock();
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config.yaml");
+        fs::write(&config, "tools: [unterminated\n").unwrap();
+        let paths = PathResolver::new(
+            Some(config.display().to_string()),
+            Some(dir.path().join("state.json").display().to_string()),
+        )
+        .unwrap();
+
+        let mut app = App::new(paths);
+        app.refresh();
+        assert!(
+            app.message
+                .starts_with("Refresh failed: configuration error")
+        );
+        assert!(app.items.iter().all(Vec::is_empty));
+    }
+}
@@ -0,0 +1,223 @@
+// Configuration migrations. Fields that were renamed in newer releases are still accepted:
+// while a configuration file is loaded, deprecated names are mapped onto the current
+// schema (with a warning), and `setup-devbox migrate-config` rewrites the files in place.
+
+use serde_yaml::{Mapping, Value};
+
+/// A configuration field that was renamed.
+pub struct FieldRename {
+    /// Configuration the field appears in (`tools`, `settings`, `shell config`, `fonts`).
+    pub config: &'static str,
+    /// Keys leading to the mapping that holds the field; `*` matches every list item.
+    pub parent: &'static [&'static str],
+    /// Deprecated field name.
+    pub old: &'static str,
+    /// Current field name.
+    pub new: &'static str,
+    /// The new field is a list; a single value under the old name becomes a one-item list.
+    pub wrap_in_list: bool,
+}
+
+/// Every deprecated field name that is still understood.
+pub const RENAMED_FIELDS: &[FieldRename] = &[
+    FieldRename {
+        config: "tools",
+        parent: &["tools", "*"],
+        old: "additional_cmd",
+        new: "post_installation_hooks",
+        wrap_in_list: false,
+    },
+    FieldRename {
+        config: "tools",
+        parent: &["tools", "*", "configuration_manager"],
+        old: "tools_configuration_path",
+        new: "tools_configuration_paths",
+        wrap_in_list: true,
+    },
+];
+
+/// A deprecated field found in a configuration file and mapped to its current name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppliedMigration {
+    /// Location of the field, e.g. `tools.2.additional_cmd`.
+    pub path: String,
+    pub old: &'static str,
+    pub new: &'static str,
+    /// Both names were present; the deprecated one was dropped in favour of the current one.
+    pub conflict: bool,
+}
+
+/// Rewrites deprecated fields in a parsed configuration to their current names.
+pub fn migrate_value(config_name: &str, value: &mut Value) -> Vec<AppliedMigration> {
+    let mut applied = Vec::new();
+    for rename in RENAMED_FIELDS.iter().filter(|r| r.config == config_name) {
+        visit(value, rename.parent, String::new(), &mut |mapping, path| {
+            if let Some(migration) = rename_field(mapping, rename, path) {
+                applied.push(migration);
+            }
+        });
+    }
+    applied
+}
+
+/// Renames deprecated keys in the raw text of a configuration file, preserving comments
+/// and layout. Single values that must become lists are written in flow style (`[value]`).
+pub fn rewrite_text(config_name: &str, contents: &str) -> String {
+    let renames: Vec<&FieldRename> = RENAMED_FIELDS
+        .iter()
+        .filter(|r| r.config == config_name)
+        .collect();
+
+    let mut rewritten: String = contents
+        .lines()
+        .map(|line| {
+            let body = line.trim_start();
+            let indent = &line[..line.len() - body.len()];
+            // A key may follow a list item marker, e.g. `- additional_cmd:`
+            let key_part = body.strip_prefix("- ").map_or(body, str::trim_start);
+            let item_marker = &body[..body.len() - key_part.len()];
+
+            for rename in &renames {
+                let Some(value) = key_part
+                    .strip_prefix(rename.old)
+                    .and_then(|rest| rest.strip_prefix(':'))
+                else {
+                    continue;
+                };
+                // Keep a trailing comment outside the wrapped value
+                let (value, comment) = match value.find(" #") {
+                    Some(index) => (value[..index].trim(), &value[index..]),
+                    None => (value.trim(), ""),
+                };
+                let value = if rename.wrap_in_list
+                    && !value.is_empty()
+                    && !value.starts_with('[')
+                    && !value.starts_with('#')
+                {
+                    format!(" [{value}]")
+                } else if value.is_empty() {
+                    String::new()
+                } else {
+                    format!(" {value}")
+                };
+                return format!("{indent}{item_marker}{}:{value}{comment}", rename.new);
+            }
+            line.to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if contents.ends_with('\n') {
+        rewritten.push('\n');
+    }
+    rewritten
+}
+
+fn visit(
+    value: &mut Value,
+    parent: &[&str],
+    path: String,
+    apply: &mut dyn FnMut(&mut Mapping, &str),
+) {
+    let Some((first, rest)) = parent.split_first() else {
+        if let Value::Mapping(mapping) = value {
+            apply(mapping, &path);
+        }
+        return;
+    };
+
+    let join = |segment: &str| {
+        if path.is_empty() {
+            segment.to_string()
+        } else {
+            format!("{path}.{segment}")
+        }
+    };
+    match (*first, value) {
+        ("*", Value::Sequence(items)) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                visit(item, rest, join(&index.to_string()), apply);
+            }
+        }
+        (key, Value::Mapping(mapping)) => {
+            if let Some(child) = mapping.get_mut(key) {
+                visit(child, rest, join(key), apply);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn rename_field(
+    mapping: &mut Mapping,
+    rename: &FieldRename,
+    parent_path: &str,
+) -> Option<AppliedMigration> {
+    let old_value = mapping.remove(rename.old)?;
+    let conflict = mapping.contains_key(rename.new);
+    if !conflict {
+        let new_value = match old_value {
+            Value::Sequence(_) | Value::Null => old_value,
+            single if rename.wrap_in_list => Value::Sequence(vec![single]),
+            other => other,
+        };
+        mapping.insert(Value::from(rename.new), new_value);
+    }
+
+    Some(AppliedMigration {
+        path: if parent_path.is_empty() {
+            rename.old.to_string()
+        } else {
+            format!("{parent_path}.{}", rename.old)
+        },
+        old: rename.old,
+        new: rename.new,
+        conflict,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD_TOOLS: &str = "tools:
+  - name: starship # prompt
+    source: brew
+    additional_cmd:
+      - starship preset nerd-font-symbols
+    configuration_manager:
+      enabled: true
+      tools_configuration_path: ~/.config/starship.toml
+";
+
+    #[test]
+    fn test_migrate_value_renames_deprecated_fields() {
+        let mut value: Value = serde_yaml::from_str(OLD_TOOLS).unwrap();
+        let applied = migrate_value("tools", &mut value);
+
+        assert_eq!(applied.len(), 2);
+        assert_eq!(applied[0].path, "tools.0.additional_cmd");
+        let tool = &value["tools"][0];
+        assert!(tool.get("additional_cmd").is_none());
+        assert_eq!(
+            tool["post_installation_hooks"][0],
+            "starship preset nerd-font-symbols"
+        );
+        assert_eq!(
+            tool["configuration_manager"]["tools_configuration_paths"][0],
+            "~/.config/starship.toml"
+        );
+    }
+
+    #[test]
+    fn test_rewrite_text_matches_migrated_value() {
+        let rewritten = rewrite_text("tools", OLD_TOOLS);
+        assert!(rewritten.contains("name: starship # prompt"));
+        assert!(rewritten.contains("tools_configuration_paths: [~/.config/starship.toml]"));
+
+        let mut expected: Value = serde_yaml::from_str(OLD_TOOLS).unwrap();
+        migrate_value("tools", &mut expected);
+        let mut actual: Value = serde_yaml::from_str(&rewritten).unwrap();
+        assert!(migrate_value("tools", &mut actual).is_empty());
+        assert_eq!(actual, expected);
+    }
+}
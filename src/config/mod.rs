@@ -9,6 +9,11 @@
 // data in a structured and reliable manner, driving `devbox`'s behavior for
 // tool installations, environment setup, and font management.
 
+pub mod migrate;
+pub mod profiles;
+pub mod read_only;
+pub mod strict;
+
 // External crate imports:
 use colored::Colorize; // Imports the `Colorize` trait for adding color to console output.
 use std::collections::{HashMap, HashSet, VecDeque};
@@ -21,6 +26,12 @@ use std::fs;
 // Internal module imports:
 // Custom logging macros for consistent, level-based output (debug, error, info, warn).
 use crate::{log_debug, log_error, log_info, log_warn};
+// Machine facts rendered into `{{ facts.* }}` placeholders.
+use crate::core::facts;
+use crate::core::lifecycle_hooks;
+// OS/architecture detection used to resolve per-platform tool overrides.
+use crate::core::platform::{self, detect_architecture, detect_os};
+use crate::core::presets;
 // Importing schema definitions. These structs (e.g., `ToolConfig`, `FontConfig`) define
 // the expected data structure for each type of YAML configuration file, enabling `serde`
 // to correctly parse them. `MainConfig` specifically defines the structure of the primary
@@ -29,7 +40,7 @@ use crate::schemas::common::MainConfig;
 use crate::schemas::fonts::FontConfig;
 use crate::schemas::os_settings::SettingsConfig;
 use crate::schemas::path_resolver::PathResolver;
-use crate::schemas::shell_configuration::ShellConfig;
+use crate::schemas::shell_configuration::{ConfigSection, RunCommandEntry, ShellConfig};
 use crate::schemas::tools_enums::SourceType;
 use crate::schemas::tools_types::{ToolConfig, ToolEntry};
 
@@ -78,6 +89,7 @@ pub fn load_individual_config<T>(
     path_option: Option<&String>,
     config_name: &str,
     bold_name: &str,
+    strict: bool,
 ) -> Option<T>
 where
     T: serde::de::DeserializeOwned + std::fmt::Debug, // Trait bounds for deserialization and debugging.
@@ -97,7 +109,7 @@ where
         match fs::read_to_string(&path) {
             Ok(contents) => {
                 // If file reading is successful, attempt to deserialize the YAML content.
-                match serde_yaml::from_str::<T>(&contents) {
+                match parse_config::<T>(&contents, config_name, strict) {
                     Ok(cfg) => {
                         // Log success message with bolded prefix and colored path.
                         log_debug!(
@@ -141,6 +153,75 @@ where
     }
 }
 
+/// Deserializes a configuration file, reporting keys that no schema field consumes.
+///
+/// Deprecated field names are first mapped to their current names with a warning.
+/// Unknown keys are logged as warnings, or rejected (as a parse error) when `strict` is set.
+/// `config_name` selects the field names used for "did you mean" suggestions.
+fn parse_config<T: serde::de::DeserializeOwned>(
+    contents: &str,
+    config_name: &str,
+    strict: bool,
+) -> Result<T, String> {
+    // Render `{{ facts.* }}` placeholders before anything reads the file
+    let rendered;
+    let contents = if facts::has_placeholders(contents) {
+        rendered = facts::render(contents, &facts::facts())?;
+        rendered.as_str()
+    } else {
+        contents
+    };
+
+    let mut document: serde_yaml::Value =
+        serde_yaml::from_str(contents).map_err(|e| e.to_string())?;
+    let migrations = migrate::migrate_value(config_name, &mut document);
+    for migration in &migrations {
+        log_warn!(
+            "[SDB::ConfigLoader] {} config: '{}' is deprecated, use '{}' instead (run {} to update the file)",
+            config_name,
+            migration.path.yellow(),
+            migration.new.green(),
+            "setup-devbox migrate-config".cyan()
+        );
+        if migration.conflict {
+            log_warn!(
+                "[SDB::ConfigLoader] Both '{}' and '{}' are set; ignoring '{}'.",
+                migration.old,
+                migration.new,
+                migration.old
+            );
+        }
+    }
+
+    // Parsing the text directly keeps line numbers in error messages
+    let parsed = if migrations.is_empty() {
+        strict::from_str_tracking_unknown(contents, config_name)
+    } else {
+        strict::from_value_tracking_unknown(document, config_name)
+    };
+    let (cfg, unknown_keys) = parsed.map_err(|e| e.to_string())?;
+
+    if strict && !unknown_keys.is_empty() {
+        for key in &unknown_keys {
+            log_error!("[SDB::ConfigLoader] {} config: {}", config_name, key);
+        }
+        return Err(format!(
+            "{} unknown key(s) found in strict mode",
+            unknown_keys.len()
+        ));
+    }
+
+    for key in &unknown_keys {
+        log_warn!(
+            "[SDB::ConfigLoader] {} config: {} is ignored (use {} to reject unknown keys)",
+            config_name,
+            key.to_string().yellow(),
+            "--strict".cyan()
+        );
+    }
+    Ok(cfg)
+}
+
 /// Loads all configurations from a master `config.yaml` file and its linked sub-files.
 ///
 /// This is a primary orchestrator function. It first reads and parses the main
@@ -158,37 +239,50 @@ where
 /// * `ParsedConfigs`: A struct containing `Option`s for each type of configuration.
 ///   Each `Option` will be `Some(T)` if the corresponding config
 ///   file was found and successfully parsed, or `None` otherwise.
-///   The function will `std::process::exit(1)` if the master config
-///   cannot be loaded.
-pub fn load_master_configs(config_path_resolved: &PathBuf) -> ParsedConfigs {
+///   Returns an error if the master config cannot be read or parsed.
+pub fn load_master_configs(config_path_resolved: &PathBuf) -> Result<ParsedConfigs, String> {
     log_debug!("[SDB::ConfigLoader] Entering load_master_configs() function.");
     // Attempt to read the contents of the main `config.yaml` file.
     let main_cfg_content = match fs::read_to_string(config_path_resolved) {
         Ok(c) => c, // Successfully read the file.
         Err(e) => {
             // If the main config file cannot be read (e.g., not found, permissions),
-            // this is a critical error, so log and exit the application.
+            // this is a critical error the caller has to handle.
             log_error!(
                 "[SDB::ConfigLoader] Failed to read main config.yaml at {}: {}. Please ensure the file exists and is readable.",
                 config_path_resolved.display().to_string().red(),
                 e
             );
-            std::process::exit(1); // Exit with a non-zero status code to indicate failure.
+            return Err(format!(
+                "failed to read {}: {e}",
+                config_path_resolved.display()
+            ));
         }
     };
 
+    // `strict: true` in config.yaml enables strict mode for every file it links to.
+    let main_config = serde_yaml::from_str::<MainConfig>(&main_cfg_content).ok();
+    let strict = strict::enabled_for(main_config.as_ref());
+    // Hooks of a previously loaded configuration never run for this one
+    lifecycle_hooks::configure(main_config.as_ref().and_then(|config| config.hooks.clone()));
+    platform::configure_arch_fallback(main_config.as_ref().and_then(|config| config.arch_fallback));
+    facts::configure_vpn_check(main_config.and_then(|config| config.vpn_check));
+
     // Attempt to deserialize the content into the `MainConfig` struct.
-    let main_cfg: MainConfig = match serde_yaml::from_str(&main_cfg_content) {
+    let main_cfg: MainConfig = match parse_config(&main_cfg_content, "config", strict) {
         Ok(cfg) => cfg, // Successfully parsed the YAML into MainConfig.
         Err(e) => {
             // If parsing fails (e.g., invalid YAML syntax in main config),
-            // this is also a critical error, so log and exit.
+            // this is also a critical error.
             log_error!(
                 "[SDB::ConfigLoader] Failed to parse main config.yaml at {}: {}. Please check your YAML syntax for errors.",
                 config_path_resolved.display().to_string().red(),
                 e
             );
-            std::process::exit(1); // Exit with a non-zero status code.
+            return Err(format!(
+                "failed to parse {}: {e}",
+                config_path_resolved.display()
+            ));
         }
     };
     // log_debug!("MainConfig loaded: Tools: {}, Fonts: {}, Shell: {} and Settings: {}", main_cfg.fonts.as_deref()); // Log the loaded MainConfig for debugging.
@@ -207,13 +301,17 @@ pub fn load_master_configs(config_path_resolved: &PathBuf) -> ParsedConfigs {
     // Use the `load_individual_config` helper function for each linked configuration file.
     // The `as_ref()` is used to convert `Option<String>` into `Option<&String>`,
     // which is required by `load_individual_config`. This avoids consuming the `String` within the `Option`.
-    let tools_config = load_individual_config(main_cfg.tools.as_ref(), "tools", "[Tools]");
+    let tools_config = load_individual_config(main_cfg.tools.as_ref(), "tools", "[Tools]", strict);
     let settings_config =
-        load_individual_config(main_cfg.settings.as_ref(), "settings", "[Settings]");
+        load_individual_config(main_cfg.settings.as_ref(), "settings", "[Settings]", strict);
     // Note: `shellrc` is the field name in `MainConfig`, but "shell config" is used for clarity in logs.
-    let shell_config =
-        load_individual_config(main_cfg.shellrc.as_ref(), "shell config", "[Shell Config]");
-    let fonts_config = load_individual_config(main_cfg.fonts.as_ref(), "fonts", "[Fonts]");
+    let shell_config = load_individual_config(
+        main_cfg.shellrc.as_ref(),
+        "shell config",
+        "[Shell Config]",
+        strict,
+    );
+    let fonts_config = load_individual_config(main_cfg.fonts.as_ref(), "fonts", "[Fonts]", strict);
 
     log_debug!("[SDB::ConfigLoader] Exiting load_master_configs() function.");
     // Return the `ParsedConfigs` struct containing all loaded sub-configurations.
@@ -225,7 +323,9 @@ pub fn load_master_configs(config_path_resolved: &PathBuf) -> ParsedConfigs {
     };
 
     // Reorder tools based on dependencies before returning
-    reorder_tools_by_dependency(parsed_configs)
+    Ok(reorder_tools_by_dependency(add_path_entries(
+        apply_platform_overrides(parsed_configs),
+    )))
 }
 
 /// Loads a single configuration file directly, bypassing the master `config.yaml`.
@@ -234,7 +334,7 @@ pub fn load_master_configs(config_path_resolved: &PathBuf) -> ParsedConfigs {
 /// to a specific configuration file (e.g., `devbox --config path/to/tools.yaml`).
 /// It determines the type of configuration file based on its filename and attempts
 /// to parse it accordingly. If the file cannot be read or its type is not recognized,
-/// the application will exit.
+/// an error is returned.
 ///
 /// # Arguments
 /// * `config_path_resolved`: A `PathBuf` representing the absolute and resolved path
@@ -246,9 +346,12 @@ pub fn load_master_configs(config_path_resolved: &PathBuf) -> ParsedConfigs {
 /// * `ParsedConfigs`: A struct containing `Option`s for each type of configuration.
 ///   Only the `Option` corresponding to the loaded single config file
 ///   will be `Some(T)`; others will remain `None`.
-///   The function will `std::process::exit(1)` if the single config
-///   cannot be loaded or is of an unsupported type.
-pub fn load_single_config(config_path_resolved: &PathBuf, config_filename: &str) -> ParsedConfigs {
+///   Returns an error if the single config cannot be read or is of an
+///   unsupported type.
+pub fn load_single_config(
+    config_path_resolved: &PathBuf,
+    config_filename: &str,
+) -> Result<ParsedConfigs, String> {
     log_debug!("[SDB::ConfigLoader] Entering load_single_config() function.");
     // Inform the user that a single config file is being loaded.
     log_info!(
@@ -270,10 +373,25 @@ pub fn load_single_config(config_path_resolved: &PathBuf, config_filename: &str)
                 config_path_resolved.display().to_string().red(),
                 e
             );
-            std::process::exit(1); // Exit the application.
+            return Err(format!(
+                "failed to read {}: {e}",
+                config_path_resolved.display()
+            ));
         }
     };
 
+    // A `config.yaml` next to the file still decides strict mode, as it would for linked files.
+    let sibling_main_config = config_path_resolved
+        .parent()
+        .and_then(|dir| fs::read_to_string(dir.join("config.yaml")).ok())
+        .and_then(|content| serde_yaml::from_str::<MainConfig>(&content).ok());
+    let strict = strict::enabled_for(sibling_main_config.as_ref());
+    // Lifecycle hooks are only run for configurations loaded through `config.yaml`, and the
+    // defaults of `arch_fallback` and `vpn_check` apply to single files
+    lifecycle_hooks::configure(None);
+    platform::configure_arch_fallback(None);
+    facts::configure_vpn_check(None);
+
     // Initialize `ParsedConfigs` with all fields set to `None`. Only one will be populated
     // based on the `config_filename`.
     let mut parsed_configs = ParsedConfigs {
@@ -288,7 +406,7 @@ pub fn load_single_config(config_path_resolved: &PathBuf, config_filename: &str)
         "tools.yaml" => {
             log_debug!("[SDB::ConfigLoader] Identified as tools.yaml. Attempting to parse...");
             // Attempt to deserialize as `ToolConfig`.
-            parsed_configs.tools = match serde_yaml::from_str(&contents) {
+            parsed_configs.tools = match parse_config(&contents, "tools", strict) {
                 Ok(cfg) => {
                     log_info!("[SDB::Tools] Successfully parsed tools.yaml.");
                     Some(cfg)
@@ -302,7 +420,7 @@ pub fn load_single_config(config_path_resolved: &PathBuf, config_filename: &str)
         "settings.yaml" => {
             log_debug!("[SDB::ConfigLoader] Identified as settings.yaml. Attempting to parse...");
             // Attempt to deserialize as `SettingsConfig`.
-            parsed_configs.settings = match serde_yaml::from_str(&contents) {
+            parsed_configs.settings = match parse_config(&contents, "settings", strict) {
                 Ok(cfg) => {
                     log_info!("[SDB::Settings] Successfully parsed settings.yaml.");
                     Some(cfg)
@@ -317,7 +435,7 @@ pub fn load_single_config(config_path_resolved: &PathBuf, config_filename: &str)
             // Support for both common shell config filenames.
             log_debug!("Identified as shell config file. Attempting to parse...");
             // Attempt to deserialize as `ShellConfig`.
-            parsed_configs.shell = match serde_yaml::from_str(&contents) {
+            parsed_configs.shell = match parse_config(&contents, "shell config", strict) {
                 Ok(cfg) => {
                     log_info!("[[SDB::ShellCofig] Successfully parsed shell config.");
                     Some(cfg)
@@ -331,7 +449,7 @@ pub fn load_single_config(config_path_resolved: &PathBuf, config_filename: &str)
         "fonts.yaml" => {
             log_debug!("Identified as fonts.yaml. Attempting to parse...");
             // Attempt to deserialize as `FontConfig`.
-            parsed_configs.fonts = match serde_yaml::from_str(&contents) {
+            parsed_configs.fonts = match parse_config(&contents, "fonts", strict) {
                 Ok(cfg) => {
                     log_info!("[SDB::Fonts] Successfully parsed fonts.yaml.");
                     Some(cfg)
@@ -348,12 +466,98 @@ pub fn load_single_config(config_path_resolved: &PathBuf, config_filename: &str)
                 "[SDB::ConfigLoader] Unsupported single config file type: '{}'. Expected 'tools.yaml', 'settings.yaml', 'shellrc.yaml', or 'fonts.yaml'.",
                 other.red()
             );
-            std::process::exit(1); // Exit the application due to an unhandled config type.
+            return Err(format!("unsupported config file type '{other}'"));
         }
     }
     log_debug!("[SDB::ConfigLoader] Exiting single config loader function.");
     // Reorder tools based on dependencies and return.
-    reorder_tools_by_dependency(parsed_configs)
+    Ok(reorder_tools_by_dependency(add_path_entries(
+        apply_platform_overrides(parsed_configs),
+    )))
+}
+
+/// Loads configurations for the resolved paths: through `config.yaml` when that is the
+/// configured file, otherwise as a single configuration file (e.g. `tools.yaml`).
+/// Errors are logged before they are returned.
+pub fn load_configs(paths: &PathResolver) -> Result<ParsedConfigs, String> {
+    let config_path_resolved = paths.config_file().to_path_buf();
+    if paths.config_filename() == "config.yaml" {
+        load_master_configs(&config_path_resolved)
+    } else {
+        load_single_config(&config_path_resolved, paths.config_filename())
+    }
+}
+
+/// Merges each tool's `platforms:` overrides for the current OS and architecture into the
+/// entry, so installers and validation only ever see the resolved fields. Preset defaults
+/// are filled in afterwards.
+pub fn apply_platform_overrides(mut parsed_configs: ParsedConfigs) -> ParsedConfigs {
+    if let Some(ref mut tools_cfg) = parsed_configs.tools {
+        let os = detect_os();
+        let arch = detect_architecture();
+        for tool in &mut tools_cfg.tools {
+            let applied = tool.apply_platform_overrides(&os, &arch);
+            presets::apply(tool);
+            if !applied.is_empty() {
+                log_debug!(
+                    "[SDB::ConfigLoader] Applied platform overrides {} to {}",
+                    applied.join(", "),
+                    tool.name
+                );
+            }
+        }
+    }
+
+    parsed_configs
+}
+
+/// Adds the directory of every `path-entry` tool to the `Paths` section of the shell
+/// configuration, so applying `shellrc.yaml` puts it on `PATH`.
+pub fn add_path_entries(mut parsed_configs: ParsedConfigs) -> ParsedConfigs {
+    // The PATH entry belongs to the same profiles as its tool
+    let directories: Vec<(String, Option<Vec<String>>)> = parsed_configs
+        .tools
+        .iter()
+        .flat_map(|tools_cfg| &tools_cfg.tools)
+        .filter(|tool| tool.source == SourceType::PathEntry)
+        .filter_map(|tool| Some((tool.path.clone()?, tool.profiles.clone())))
+        .collect();
+    if directories.is_empty() {
+        return parsed_configs;
+    }
+
+    let Some(shell_cfg) = parsed_configs.shell.as_mut() else {
+        let names: Vec<&str> = directories.iter().map(|(dir, _)| dir.as_str()).collect();
+        log_warn!(
+            "[SDB::ConfigLoader] No shellrc.yaml is configured; add {} to PATH yourself",
+            names.join(", ").yellow()
+        );
+        return parsed_configs;
+    };
+    for (directory, profiles) in directories {
+        // `~` is not expanded inside quotes, `$HOME` is
+        let directory = match directory.strip_prefix('~') {
+            Some(rest) => format!("$HOME{rest}"),
+            None => directory,
+        };
+        let command = format!("export PATH=\"{directory}:$PATH\"");
+        if shell_cfg
+            .run_commands
+            .run_commands
+            .iter()
+            .any(|entry| entry.command.trim() == command)
+        {
+            continue;
+        }
+        shell_cfg.run_commands.run_commands.push(RunCommandEntry {
+            command,
+            section: ConfigSection::Paths,
+            shells: None,
+            profiles,
+        });
+    }
+
+    parsed_configs
 }
 
 /// Reorders tool entries so that source installers appear before the tools that depend on them.
@@ -631,3 +835,111 @@ fn topological_sort_tools(tools: &[ToolEntry]) -> Vec<ToolEntry> {
         .filter_map(|name| tool_map.get(name).cloned())
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schemas::common::ArchFallback;
+    use std::path::Path;
+
+    const TOOLS_WITH_TYPO: &str = "tools:\n  - name: jq\n    source: brew\n    rename-to: jq2\n";
+
+    fn write_configs(dir: &Path, strict: bool) -> PathBuf {
+        let tools = dir.join("tools.yaml");
+        fs::write(&tools, TOOLS_WITH_TYPO).unwrap();
+        let config = dir.join("config.yaml");
+        fs::write(
+            &config,
+            format!("strict: {strict}\ntools: {}\n", tools.display()),
+        )
+        .unwrap();
+        config
+    }
+
+    #[test]
+    fn test_strict_from_master_config_is_per_load() {
+        let _hooks = lifecycle_hooks::tests::HOOKS_LOCK.lock();
+        let strict_dir = tempfile::tempdir().unwrap();
+        let lenient_dir = tempfile::tempdir().unwrap();
+        let strict_config = write_configs(strict_dir.path(), true);
+        let lenient_config = write_configs(lenient_dir.path(), false);
+
+        assert!(load_master_configs(&strict_config).unwrap().tools.is_none());
+        // The previous `strict: true` does not leak into the next load
+        assert!(
+            load_master_configs(&lenient_config)
+                .unwrap()
+                .tools
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_hooks_are_replaced_on_each_load() {
+        let _hooks = lifecycle_hooks::tests::HOOKS_LOCK.lock();
+        let dir = tempfile::tempdir().unwrap();
+        let with_hooks = dir.path().join("config.yaml");
+        fs::write(
+            &with_hooks,
+            "hooks:\n  after_run:\n    - tmux source-file ~/.tmux.conf\n",
+        )
+        .unwrap();
+        let other_dir = tempfile::tempdir().unwrap();
+        let without_hooks = other_dir.path().join("config.yaml");
+        fs::write(&without_hooks, "strict: false\n").unwrap();
+
+        load_master_configs(&with_hooks).unwrap();
+        assert_eq!(
+            lifecycle_hooks::tests::configured_after_run(),
+            ["tmux source-file ~/.tmux.conf"]
+        );
+        load_master_configs(&without_hooks).unwrap();
+        assert!(lifecycle_hooks::tests::configured_after_run().is_empty());
+    }
+
+    #[test]
+    fn test_arch_fallback_is_replaced_on_each_load() {
+        let _hooks = lifecycle_hooks::tests::HOOKS_LOCK.lock();
+        let dir = tempfile::tempdir().unwrap();
+        let never = dir.path().join("config.yaml");
+        fs::write(&never, "arch_fallback: never\n").unwrap();
+        let other_dir = tempfile::tempdir().unwrap();
+        let unset = other_dir.path().join("config.yaml");
+        fs::write(&unset, "strict: false\n").unwrap();
+
+        load_master_configs(&never).unwrap();
+        assert_eq!(platform::arch_fallback(), ArchFallback::Never);
+        load_master_configs(&unset).unwrap();
+        assert_eq!(platform::arch_fallback(), ArchFallback::PreferNative);
+
+        // A configuration that fails to parse doesn't keep the earlier policy either
+        load_master_configs(&never).unwrap();
+        fs::write(&unset, "arch_fallback: sometimes\n").unwrap();
+        assert!(load_master_configs(&unset).is_err());
+        assert_eq!(platform::arch_fallback(), ArchFallback::PreferNative);
+    }
+
+    #[test]
+    fn test_strict_from_sibling_config_in_single_config_mode() {
+        let _hooks = lifecycle_hooks::tests::HOOKS_LOCK.lock();
+        let strict_dir = tempfile::tempdir().unwrap();
+        let lenient_dir = tempfile::tempdir().unwrap();
+        write_configs(strict_dir.path(), true);
+        write_configs(lenient_dir.path(), false);
+
+        let strict_tools = strict_dir.path().join("tools.yaml");
+        let lenient_tools = lenient_dir.path().join("tools.yaml");
+        assert!(
+            load_single_config(&strict_tools, "tools.yaml")
+                .unwrap()
+                .tools
+                .is_none()
+        );
+        assert!(
+            load_single_config(&lenient_tools, "tools.yaml")
+                .unwrap()
+                .tools
+                .is_some()
+        );
+    }
+}
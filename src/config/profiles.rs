@@ -0,0 +1,163 @@
+// Profiles let one shared configuration serve several machines. Tools, fonts, shell run
+// commands and aliases can declare `profiles: [work, personal, minimal]`; `now --profile work`
+// (or `SDB_PROFILE=work`) only processes the entries of that profile, together with the
+// entries that declare no profiles, which are shared by every machine. Without `--profile`,
+// everything is processed as before.
+
+use std::sync::OnceLock;
+
+use colored::Colorize;
+
+use crate::config::ParsedConfigs;
+use crate::{log_info, log_warn};
+
+static ACTIVE: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Restricts this run to the given profiles (`--profile`); no profiles means no restriction.
+pub fn set_active(profiles: Vec<String>) {
+    let profiles: Vec<String> = profiles
+        .into_iter()
+        .map(|profile| profile.trim().to_string())
+        .filter(|profile| !profile.is_empty())
+        .collect();
+    if !profiles.is_empty() {
+        let _ = ACTIVE.set(profiles);
+    }
+}
+
+/// The profiles this run is restricted to, if any.
+pub fn active() -> Option<&'static [String]> {
+    ACTIVE.get().map(Vec::as_slice)
+}
+
+/// Whether an entry with `profiles` is processed when the run is restricted to `active`:
+/// entries without profiles always are, others when they share a profile with the run.
+pub fn matches(profiles: Option<&[String]>, active: &[String]) -> bool {
+    match profiles {
+        None => true,
+        Some(profiles) => profiles
+            .iter()
+            .any(|profile| active.iter().any(|a| a.eq_ignore_ascii_case(profile))),
+    }
+}
+
+/// Drops the tools, fonts, run commands and aliases that are not in the active profiles.
+/// Does nothing without `--profile`.
+pub fn restrict(configs: &mut ParsedConfigs) {
+    let Some(active) = active() else {
+        return;
+    };
+    log_info!(
+        "[SDB::Profiles] Processing entries of profile(s) {} and entries without profiles",
+        active.join(", ").cyan()
+    );
+    warn_about_unknown(configs, active);
+    restrict_to(configs, active);
+}
+
+fn restrict_to(configs: &mut ParsedConfigs, active: &[String]) {
+    if let Some(tools_config) = configs.tools.as_mut() {
+        tools_config
+            .tools
+            .retain(|tool| matches(tool.profiles.as_deref(), active));
+    }
+    if let Some(fonts_config) = configs.fonts.as_mut() {
+        fonts_config
+            .fonts
+            .retain(|font| matches(font.profiles.as_deref(), active));
+    }
+    if let Some(shell_config) = configs.shell.as_mut() {
+        shell_config
+            .run_commands
+            .run_commands
+            .retain(|entry| matches(entry.profiles.as_deref(), active));
+        shell_config
+            .aliases
+            .retain(|alias| matches(alias.profiles.as_deref(), active));
+    }
+}
+
+/// Warns about requested profiles no entry declares, which are most likely typos.
+fn warn_about_unknown(configs: &ParsedConfigs, active: &[String]) {
+    let declared: Vec<&String> = configs
+        .tools
+        .iter()
+        .flat_map(|tools_config| tools_config.tools.iter().map(|tool| &tool.profiles))
+        .chain(
+            configs
+                .fonts
+                .iter()
+                .flat_map(|fonts_config| fonts_config.fonts.iter().map(|font| &font.profiles)),
+        )
+        .chain(configs.shell.iter().flat_map(|shell_config| {
+            shell_config
+                .run_commands
+                .run_commands
+                .iter()
+                .map(|entry| &entry.profiles)
+                .chain(shell_config.aliases.iter().map(|alias| &alias.profiles))
+        }))
+        .flatten()
+        .flatten()
+        .collect();
+    for profile in active {
+        if !declared.iter().any(|d| d.eq_ignore_ascii_case(profile)) {
+            log_warn!(
+                "[SDB::Profiles] No entry declares profile '{}'; only entries without profiles are processed for it",
+                profile.yellow()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        let active = ["work".to_string()];
+        assert!(matches(None, &active));
+        assert!(matches(
+            Some(&["personal".to_string(), "Work".to_string()]),
+            &active
+        ));
+        assert!(!matches(Some(&["minimal".to_string()]), &active));
+        assert!(!matches(Some(&[]), &active));
+    }
+
+    #[test]
+    fn test_restrict_to() {
+        let mut configs = ParsedConfigs {
+            tools: serde_yaml::from_str(
+                "tools:\n  - name: rg\n    source: brew\n  \
+                 - name: slack\n    source: brew\n    profiles: [work]\n  \
+                 - name: steam\n    source: brew\n    profiles: [personal]\n",
+            )
+            .unwrap(),
+            settings: None,
+            shell: serde_yaml::from_str(
+                "run_commands:\n  shell: zsh\n  run_commands:\n    \
+                 - command: export EDITOR=hx\n      section: Exports\n    \
+                 - command: export AWS_PROFILE=corp\n      section: Exports\n      profiles: [work]\n\
+                 aliases:\n  - name: games\n    value: open -a Steam\n    profiles: [personal]\n",
+            )
+            .unwrap(),
+            fonts: None,
+        };
+        restrict_to(&mut configs, &["work".to_string()]);
+
+        let tools: Vec<&str> = configs
+            .tools
+            .as_ref()
+            .unwrap()
+            .tools
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(tools, ["rg", "slack"]);
+        let shell = configs.shell.as_ref().unwrap();
+        assert_eq!(shell.run_commands.run_commands.len(), 2);
+        assert!(shell.aliases.is_empty());
+    }
+}
@@ -0,0 +1,74 @@
+// Read-only mode for shared machines. With `--read-only`, or `read_only: true` in
+// `config.yaml`, commands that would change the machine (`now`, `add`, `remove`,
+// `edit --state`, `reset`, ...) refuse to run with an explanation, while commands that only
+// report (`plan`, `now --dry-run`, `check-updates`, `licenses`, `prompt-status`) keep working.
+// A dry run only reports tool changes; fonts, shell configuration and settings are skipped. This
+// lets administrators provision lab machines while everyone else can still inspect them.
+
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use colored::Colorize;
+
+use crate::log_error;
+use crate::schemas::common::MainConfig;
+use crate::schemas::path_resolver::PathResolver;
+
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// Enables read-only mode for this run (`--read-only`).
+pub fn set_read_only(enabled: bool) {
+    READ_ONLY.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether this run may not change the machine, either because of `--read-only` or because
+/// the `config.yaml` at `paths` sets `read_only: true`.
+pub fn is_read_only(paths: &PathResolver) -> bool {
+    READ_ONLY.load(Ordering::Relaxed) || config_read_only(paths)
+}
+
+/// Returns an explanation when `command` would change the machine in read-only mode.
+pub fn ensure_writable(paths: &PathResolver, command: &str) -> Result<(), String> {
+    if !is_read_only(paths) {
+        return Ok(());
+    }
+    let reason = if READ_ONLY.load(Ordering::Relaxed) {
+        "--read-only was passed".to_string()
+    } else {
+        format!("{} sets 'read_only: true'", paths.config_file().display())
+    };
+    log_error!(
+        "[SDB::ReadOnly] Ask an administrator to provision it; status commands such as {} remain available.",
+        "setup-devbox plan".cyan()
+    );
+    Err(format!(
+        "'{command}' would change this machine, but setup-devbox is in read-only mode ({reason})"
+    ))
+}
+
+fn config_read_only(paths: &PathResolver) -> bool {
+    fs::read_to_string(paths.config_file())
+        .ok()
+        .and_then(|content| serde_yaml::from_str::<MainConfig>(&content).ok())
+        .is_some_and(|config| config.read_only == Some(true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_read_only_refuses_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config.yaml");
+        let paths = PathResolver::new(Some(config.display().to_string()), None).unwrap();
+
+        fs::write(&config, "tools: tools.yaml\n").unwrap();
+        assert!(ensure_writable(&paths, "now").is_ok());
+
+        fs::write(&config, "tools: tools.yaml\nread_only: true\n").unwrap();
+        let error = ensure_writable(&paths, "now").unwrap_err();
+        assert!(error.contains("'now' would change this machine"));
+        assert!(error.contains("sets 'read_only: true'"));
+    }
+}
@@ -0,0 +1,198 @@
+// Strict configuration mode. serde silently ignores keys it does not know, so a typo like
+// `rename-to:` simply has no effect. This module records every ignored key while a
+// configuration file is deserialized; in strict mode (`--strict`, or `strict: true` in
+// `config.yaml`) the file is rejected and each unknown key is reported together with the
+// closest known field name ("did you mean rename_to?").
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::de::{self, DeserializeOwned, Visitor};
+use serde::forward_to_deserialize_any;
+
+use crate::schemas::common::MainConfig;
+use crate::schemas::config_manager::ConfigurationManager;
+use crate::schemas::fonts::{FontConfig, FontEntry};
+use crate::schemas::os_settings::{
+    FileAssociationEntry, LaunchAgentEntry, OsSpecificSettings, SettingEntry, SettingsConfig,
+};
+use crate::schemas::shell_configuration::{
+    AliasEntry, RunCommandEntry, ShellConfig, ShellRunCommands,
+};
+use crate::schemas::tools_types::{
+    AssetEntry, BasicAuth, PlatformOverride, ToolConfig, ToolEntry, VersionCheck,
+};
+
+static STRICT: AtomicBool = AtomicBool::new(false);
+
+/// Enables or disables strict mode for the whole run (`--strict`).
+pub fn set_strict(enabled: bool) {
+    STRICT.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether `--strict` was passed.
+pub fn is_strict() -> bool {
+    STRICT.load(Ordering::Relaxed)
+}
+
+/// Whether unknown keys are rejected in files loaded with `main_config`: with `--strict`, or
+/// when it sets `strict: true`. Decided for each load, so a strict configuration does not
+/// carry over to the next one loaded by the same process.
+pub fn enabled_for(main_config: Option<&MainConfig>) -> bool {
+    is_strict() || main_config.is_some_and(|config| config.strict == Some(true))
+}
+
+/// A key present in a configuration file that no schema field consumed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownKey {
+    /// Location of the key, e.g. `tools.3.rename-to`.
+    pub path: String,
+    /// Closest known field name, if any is similar enough.
+    pub suggestion: Option<&'static str>,
+}
+
+impl std::fmt::Display for UnknownKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown key '{}'", self.path)?;
+        if let Some(suggestion) = self.suggestion {
+            write!(f, " (did you mean '{suggestion}'?)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Deserializes YAML, returning the value together with every key that was ignored.
+pub fn from_str_tracking_unknown<T: DeserializeOwned>(
+    contents: &str,
+    config_name: &str,
+) -> Result<(T, Vec<UnknownKey>), serde_yaml::Error> {
+    deserialize_tracking_unknown(serde_yaml::Deserializer::from_str(contents), config_name)
+}
+
+/// Same as [`from_str_tracking_unknown`] for an already parsed (e.g. migrated) document.
+pub fn from_value_tracking_unknown<T: DeserializeOwned>(
+    value: serde_yaml::Value,
+    config_name: &str,
+) -> Result<(T, Vec<UnknownKey>), serde_yaml::Error> {
+    deserialize_tracking_unknown(value, config_name)
+}
+
+fn deserialize_tracking_unknown<'de, D, T>(
+    deserializer: D,
+    config_name: &str,
+) -> Result<(T, Vec<UnknownKey>), D::Error>
+where
+    D: de::Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let known = known_fields(config_name);
+    let mut unknown = Vec::new();
+    let value = serde_ignored::deserialize(deserializer, |path| {
+        let path = path.to_string();
+        let key = path.rsplit('.').next().unwrap_or(&path);
+        unknown.push(UnknownKey {
+            suggestion: suggest(key, &known),
+            path,
+        });
+    })?;
+    Ok((value, unknown))
+}
+
+/// Returns the known field name closest to `key`, if it is a plausible typo.
+pub fn suggest(key: &str, known: &[&'static str]) -> Option<&'static str> {
+    let normalized = key.to_lowercase().replace('-', "_");
+    known
+        .iter()
+        .map(|field| (*field, strsim::levenshtein(&normalized, field)))
+        .filter(|(_, distance)| *distance <= (normalized.len() / 3).max(2))
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(field, _)| field)
+}
+
+/// Field names of all schema structs that can appear in the given configuration file.
+fn known_fields(config_name: &str) -> Vec<&'static str> {
+    let mut fields = match config_name {
+        "config" => struct_fields::<MainConfig>(),
+        "tools" => [
+            struct_fields::<ToolConfig>(),
+            struct_fields::<ToolEntry>(),
+            struct_fields::<ConfigurationManager>(),
+            struct_fields::<VersionCheck>(),
+            struct_fields::<BasicAuth>(),
+            struct_fields::<AssetEntry>(),
+            struct_fields::<PlatformOverride>(),
+        ]
+        .concat(),
+        "settings" => [
+            struct_fields::<SettingsConfig>(),
+            struct_fields::<OsSpecificSettings>(),
+            struct_fields::<SettingEntry>(),
+            struct_fields::<LaunchAgentEntry>(),
+            struct_fields::<FileAssociationEntry>(),
+        ]
+        .concat(),
+        "shell config" => [
+            struct_fields::<ShellConfig>(),
+            struct_fields::<ShellRunCommands>(),
+            struct_fields::<RunCommandEntry>(),
+            struct_fields::<AliasEntry>(),
+        ]
+        .concat(),
+        "fonts" => [struct_fields::<FontConfig>(), struct_fields::<FontEntry>()].concat(),
+        _ => Vec::new(),
+    };
+    fields.sort_unstable();
+    fields.dedup();
+    fields
+}
+
+/// Lists the field names serde expects for a struct, without needing a value of it.
+fn struct_fields<T: DeserializeOwned>() -> Vec<&'static str> {
+    let mut fields = Vec::new();
+    let _ = T::deserialize(FieldProbe(&mut fields));
+    fields
+}
+
+/// Deserializer that records the field list of the struct being deserialized and stops.
+struct FieldProbe<'a>(&'a mut Vec<&'static str>);
+
+impl<'de> de::Deserializer<'de> for FieldProbe<'_> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("field probe"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0.extend_from_slice(fields);
+        Err(de::Error::custom("field probe"))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_keys_get_suggestions() {
+        let yaml =
+            "tools:\n  - name: rg\n    source: github\n    rename-to: ripgrep\n    colour: red\n";
+        let (config, unknown): (ToolConfig, _) = from_str_tracking_unknown(yaml, "tools").unwrap();
+
+        assert_eq!(config.tools.len(), 1);
+        assert_eq!(unknown.len(), 2);
+        assert_eq!(unknown[0].path, "tools.0.rename-to");
+        assert_eq!(unknown[0].suggestion, Some("rename_to"));
+        assert_eq!(unknown[1].suggestion, None);
+    }
+}
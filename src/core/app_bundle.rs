@@ -0,0 +1,350 @@
+//! # Application Bundles
+//!
+//! Tools like Zed or Visual Studio Code ship a macOS `.app` bundle with their command line
+//! interface inside it. A `github` or `url` tool with `app_bundle:` is installed as an
+//! application: the bundle found in its `.dmg` or archive is copied to `app_dir`
+//! (`/Applications` by default) and the embedded CLI is symlinked into the bin directory
+//! under the tool's name (or `rename_to`).
+//!
+//! ```yaml
+//! - name: zed
+//!   source: github
+//!   repo: zed-industries/zed
+//!   app_bundle:
+//!     cli: Contents/MacOS/cli
+//! ```
+//!
+//! The link is recorded as the tool's `install_path` and the bundle in its `app_bundle`
+//! state, so removing the tool deletes both.
+
+use std::ffi::OsStr;
+use std::fs;
+use std::io;
+#[cfg(unix)]
+use std::os::unix::fs::symlink;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use colored::Colorize;
+
+use crate::core::compression::sanitize_entry_path;
+use crate::core::osx_pkg::{DEFAULT_APP_DIR, command_for_dir};
+use crate::schemas::path_resolver::PathResolver;
+use crate::schemas::state_file::AppBundleState;
+use crate::schemas::tools_types::ToolEntry;
+use crate::{log_debug, log_error, log_info};
+
+/// Package type recorded for tools installed as an application bundle.
+pub const PACKAGE_TYPE: &str = "macos-app-bundle";
+
+/// How deep an extracted archive is searched for the bundle.
+const MAX_BUNDLE_DEPTH: usize = 3;
+
+/// Where bundles keep a command line interface named after the tool (Visual Studio Code's
+/// `code`, for instance).
+const CLI_DIRS: [&str; 2] = ["Contents/Resources/app/bin", "Contents/Resources/bin"];
+
+/// Directory of the bundle's own executables; its executable named after the tool is usually
+/// the application itself, so a `cli` helper next to it (as Zed ships) is preferred.
+const EXECUTABLE_DIR: &str = "Contents/MacOS";
+
+/// Directory the tool's bundle is installed into: its `app_dir`, or [`DEFAULT_APP_DIR`].
+pub fn app_dir(tool_entry: &ToolEntry) -> PathBuf {
+    tool_entry.app_dir.as_deref().map_or_else(
+        || PathBuf::from(DEFAULT_APP_DIR),
+        PathResolver::expand_tilde,
+    )
+}
+
+/// Installs the bundle found in an extracted archive and links its CLI (see [`link_cli`]).
+pub fn install_extracted(
+    tool_entry: &ToolEntry,
+    extracted_path: &Path,
+    tool_source: &str,
+) -> Option<(String, PathBuf, PathBuf)> {
+    let Some(bundle) = find_bundle(extracted_path, &tool_entry.name) else {
+        log_error!(
+            "[SDB::Tools::{tool_source}::AppBundle] No .app bundle found in the archive of {}",
+            tool_entry.name.red()
+        );
+        return None;
+    };
+    let target = app_dir(tool_entry).join(bundle.file_name()?);
+    if let Err(err) = copy_bundle(&bundle, &target) {
+        log_error!(
+            "[SDB::Tools::{tool_source}::AppBundle] Failed to install {} to {}: {}",
+            bundle.display(),
+            target.display(),
+            err
+        );
+        return None;
+    }
+    log_info!(
+        "[SDB::Tools::{tool_source}::AppBundle] Installed {}",
+        target.display().to_string().green()
+    );
+    link_cli(tool_entry, &target, tool_source)
+}
+
+/// Links the CLI embedded in the installed `bundle` into the bin directory.
+///
+/// # Returns
+///
+/// * `Some((package_type, link_path, bundle))` - The link becomes the tool's `install_path`
+///   and post-installation hooks run in the bundle
+/// * `None` - If the bundle has no CLI or it could not be linked
+pub fn link_cli(
+    tool_entry: &ToolEntry,
+    bundle: &Path,
+    tool_source: &str,
+) -> Option<(String, PathBuf, PathBuf)> {
+    let Some(cli) = find_cli(tool_entry, bundle) else {
+        log_error!(
+            "[SDB::Tools::{tool_source}::AppBundle] No command line interface found in {}; set 'app_bundle.cli' to its path inside the bundle",
+            bundle.display().to_string().red()
+        );
+        return None;
+    };
+    let bin_dir = PathResolver::get_user_bin_dir()?;
+    let link_path = PathResolver::get_final_file_path(&bin_dir, tool_entry);
+    if let Err(err) = replace_link(&cli, &link_path) {
+        log_error!(
+            "[SDB::Tools::{tool_source}::AppBundle] Failed to link {} to {}: {}",
+            link_path.display(),
+            cli.display(),
+            err
+        );
+        return None;
+    }
+    log_info!(
+        "[SDB::Tools::{tool_source}::AppBundle] Linked {} -> {}",
+        link_path.display().to_string().green(),
+        cli.display()
+    );
+    Some((PACKAGE_TYPE.to_string(), link_path, bundle.to_path_buf()))
+}
+
+/// The bundle and CLI an installed link points to.
+pub fn recorded(link_path: &Path) -> Option<AppBundleState> {
+    let cli = fs::read_link(link_path).ok()?;
+    let bundle = cli
+        .ancestors()
+        .find(|path| path.extension().is_some_and(|ext| ext == "app"))?;
+    Some(AppBundleState {
+        path: bundle.display().to_string(),
+        cli: cli.strip_prefix(bundle).ok()?.display().to_string(),
+    })
+}
+
+/// Deletes an installed bundle, through `sudo` when its directory is not writable.
+pub fn remove(bundle: &AppBundleState) -> io::Result<()> {
+    let path = Path::new(&bundle.path);
+    if !path.exists() {
+        return Ok(());
+    }
+    run_in(
+        path.parent().unwrap_or(Path::new("/")),
+        "rm",
+        &["-rf".as_ref(), path.as_os_str()],
+    )
+}
+
+/// Finds the `.app` bundle in an extracted archive, preferring one named after the tool.
+fn find_bundle(dir: &Path, tool_name: &str) -> Option<PathBuf> {
+    let mut bundles = Vec::new();
+    collect_bundles(dir, MAX_BUNDLE_DEPTH, &mut bundles);
+    bundles.sort();
+    let named = bundles.iter().position(|bundle| {
+        bundle
+            .file_stem()
+            .is_some_and(|stem| stem.to_string_lossy().eq_ignore_ascii_case(tool_name))
+    });
+    match named {
+        Some(index) => Some(bundles.swap_remove(index)),
+        None => bundles.into_iter().next(),
+    }
+}
+
+fn collect_bundles(dir: &Path, depth: usize, bundles: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if !path.is_dir() {
+            continue;
+        }
+        if path.extension().is_some_and(|ext| ext == "app") {
+            bundles.push(path);
+        } else if depth > 0 {
+            collect_bundles(&path, depth - 1, bundles);
+        }
+    }
+}
+
+/// The CLI inside `bundle`: the configured `app_bundle.cli`, or the first executable found
+/// among the tool's name (and `rename_to`) in [`CLI_DIRS`], then `cli` and the tool's name in
+/// [`EXECUTABLE_DIR`].
+fn find_cli(tool_entry: &ToolEntry, bundle: &Path) -> Option<PathBuf> {
+    if let Some(cli) = tool_entry
+        .app_bundle
+        .as_ref()
+        .and_then(|config| config.cli.as_deref())
+    {
+        return sanitize_entry_path(Path::new(cli))
+            .map(|relative| bundle.join(relative))
+            .ok()
+            .filter(|path| path.is_file());
+    }
+    let mut names = vec![tool_entry.name.as_str()];
+    names.extend(tool_entry.rename_to.as_deref());
+    let named_in = |dir: &'static str| names.iter().map(move |name| Path::new(dir).join(name));
+    CLI_DIRS
+        .into_iter()
+        .flat_map(named_in)
+        .chain([Path::new(EXECUTABLE_DIR).join("cli")])
+        .chain(named_in(EXECUTABLE_DIR))
+        .map(|relative| bundle.join(relative))
+        .find(|path| path.is_file())
+}
+
+/// Replaces whatever is at `target` with a copy of `bundle`.
+fn copy_bundle(bundle: &Path, target: &Path) -> io::Result<()> {
+    let dir = target.parent().unwrap_or(Path::new(DEFAULT_APP_DIR));
+    fs::create_dir_all(dir)?;
+    if target.exists() {
+        log_debug!(
+            "[SDB::Tools::AppBundle] Replacing existing {}",
+            target.display()
+        );
+        run_in(dir, "rm", &["-rf".as_ref(), target.as_os_str()])?;
+    }
+    run_in(
+        dir,
+        "cp",
+        &["-R".as_ref(), bundle.as_os_str(), target.as_os_str()],
+    )
+}
+
+/// Points `link_path` at `cli`, replacing an earlier link or executable. Where symlinks aren't
+/// available to unprivileged users (Windows) the CLI is copied instead.
+fn replace_link(cli: &Path, link_path: &Path) -> io::Result<()> {
+    if let Some(dir) = link_path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    if fs::symlink_metadata(link_path).is_ok_and(|metadata| !metadata.is_dir()) {
+        fs::remove_file(link_path)?;
+    }
+    #[cfg(unix)]
+    return symlink(cli, link_path);
+    #[cfg(not(unix))]
+    return fs::copy(cli, link_path).map(|_| ());
+}
+
+/// Runs `program` for a change in `dir` (see `command_for_dir`).
+fn run_in(dir: &Path, program: &str, args: &[&OsStr]) -> io::Result<()> {
+    let output = command_for_dir(dir, program)
+        .args(args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "'{program}' failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schemas::tools_types::AppBundleConfig;
+
+    fn tool(cli: Option<&str>) -> ToolEntry {
+        let mut tool: ToolEntry =
+            serde_yaml::from_str("name: zed\nsource: github\nrepo: zed-industries/zed\n").unwrap();
+        tool.app_bundle = Some(AppBundleConfig {
+            cli: cli.map(str::to_string),
+        });
+        tool
+    }
+
+    fn write_executable(path: &Path) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "#!/bin/sh\n").unwrap();
+    }
+
+    #[test]
+    fn test_find_bundle_prefers_tool_name() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("Another.app/Contents")).unwrap();
+        fs::create_dir_all(dir.path().join("zed-macos/Zed.app/Contents/MacOS")).unwrap();
+
+        assert_eq!(
+            find_bundle(dir.path(), "zed"),
+            Some(dir.path().join("zed-macos/Zed.app"))
+        );
+        assert_eq!(
+            find_bundle(dir.path(), "other"),
+            Some(dir.path().join("Another.app"))
+        );
+        assert_eq!(
+            find_bundle(&dir.path().join("zed-macos/Zed.app"), "zed"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_find_cli() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = dir.path().join("Zed.app");
+        write_executable(&bundle.join("Contents/MacOS/zed"));
+        write_executable(&bundle.join("Contents/MacOS/cli"));
+        write_executable(&bundle.join("Contents/MacOS/helper"));
+
+        assert_eq!(
+            find_cli(&tool(None), &bundle),
+            Some(bundle.join("Contents/MacOS/cli"))
+        );
+        assert_eq!(
+            find_cli(&tool(Some("Contents/MacOS/helper")), &bundle),
+            Some(bundle.join("Contents/MacOS/helper"))
+        );
+        assert_eq!(
+            find_cli(&tool(Some("Contents/MacOS/missing")), &bundle),
+            None
+        );
+        assert_eq!(find_cli(&tool(Some("../../etc/passwd")), &bundle), None);
+
+        write_executable(&bundle.join("Contents/Resources/app/bin/zed"));
+        assert_eq!(
+            find_cli(&tool(None), &bundle),
+            Some(bundle.join("Contents/Resources/app/bin/zed"))
+        );
+    }
+
+    #[test]
+    fn test_link_and_remove_bundle() {
+        let dir = tempfile::tempdir().unwrap();
+        let extracted = dir.path().join("extracted");
+        write_executable(&extracted.join("Zed.app/Contents/MacOS/cli"));
+        let bundle = dir.path().join("Applications/Zed.app");
+        copy_bundle(&extracted.join("Zed.app"), &bundle).unwrap();
+
+        let link = dir.path().join("bin/zed");
+        replace_link(&bundle.join("Contents/MacOS/cli"), &link).unwrap();
+        // Linking again replaces the earlier link
+        replace_link(&bundle.join("Contents/MacOS/cli"), &link).unwrap();
+
+        let state = recorded(&link).unwrap();
+        assert_eq!(state.path, bundle.display().to_string());
+        assert_eq!(state.cli, "Contents/MacOS/cli");
+        assert!(recorded(&bundle.join("Contents/MacOS/cli")).is_none());
+
+        remove(&state).unwrap();
+        assert!(!bundle.exists());
+        remove(&state).unwrap();
+    }
+}
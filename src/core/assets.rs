@@ -2,6 +2,7 @@
 //                          STANDARD LIBRARY DEPENDENCIES
 // ============================================================================
 use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::str;
@@ -11,18 +12,28 @@ use std::{fs, io};
 //                             EXTERNAL DEPENDENCIES
 // ============================================================================
 
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use bzip2::read::BzDecoder;
 use colored::Colorize;
 use tempfile::Builder as TempFileBuilder;
+use xz2::read::XzDecoder;
+use zstd::stream::read::Decoder as ZstdDecoder;
 
 // ============================================================================
 //                              INTERNAL IMPORTS
 // ============================================================================
+use crate::core::app_bundle;
 use crate::core::binary::{find_executable, make_executable, move_and_rename_binary};
 use crate::core::compression;
+use crate::core::delta;
+use crate::core::downloads::{self, DownloadError};
 #[cfg(target_os = "macos")]
 use crate::core::osx_pkg::{install_dmg, install_pkg};
+#[cfg(windows)]
+use crate::core::windows;
 use crate::schemas::path_resolver::PathResolver;
-use crate::schemas::tools_types::ToolEntry;
+use crate::schemas::tools_types::{AssetEntry, ToolEntry};
 use crate::{log_debug, log_error, log_info, log_warn};
 
 /// Downloads the asset from the URL to a temporary location.
@@ -54,11 +65,21 @@ pub fn download_url_asset(
     tool_entry: &ToolEntry,
     download_url: &str,
 ) -> Option<(tempfile::TempDir, PathBuf)> {
+    download_url_asset_with_validators(tool_entry, download_url)
+        .map(|(temp_dir, path, _)| (temp_dir, path))
+}
+
+/// Same as `download_url_asset`, additionally returning the response's cache validators
+/// (`ETag` / `Last-Modified`) so callers can issue conditional requests on later runs.
+pub fn download_url_asset_with_validators(
+    tool_entry: &ToolEntry,
+    download_url: &str,
+) -> Option<(tempfile::TempDir, PathBuf, ResponseValidators)> {
     let tool_source = capitalize_first(&tool_entry.source.to_string());
     // Create temporary directory with descriptive prefix
     let temp_dir = match TempFileBuilder::new()
         .prefix(&format!("setup-devbox-install-{}-", tool_entry.name))
-        .tempdir()
+        .tempdir_in(PathResolver::temp_root())
     {
         Ok(dir) => dir,
         Err(e) => {
@@ -71,8 +92,9 @@ pub fn download_url_asset(
         }
     };
 
-    // Extract filename from URL or use tool name as fallback
-    let filename = Path::new(download_url)
+    // Extract filename from URL (ignoring any query string, e.g. on signed URLs)
+    // or use tool name as fallback
+    let filename = Path::new(url_path(download_url))
         .file_name()
         .and_then(|f| f.to_str())
         .map(|s| s.to_string())
@@ -102,16 +124,40 @@ pub fn download_url_asset(
         downloaded_path.display().to_string().yellow()
     );
 
-    // Download file from URL to temporary location
-    if let Err(err) = download_file(download_url, &downloaded_path) {
-        log_error!(
-            "[SDB::Tools::{tool_source}::Downloader] Failed to download {} from {}: {}",
-            tool_entry.name.red(),
-            download_url.red(),
-            err
-        );
-        return None;
-    }
+    // Build the request, including any configured headers, basic auth and HTTP method
+    let request = match build_download_request(tool_entry, download_url) {
+        Ok(request) => request,
+        Err(err) => {
+            log_error!(
+                "[SDB::Tools::{tool_source}::Downloader] Invalid request configuration for {}: {}",
+                tool_entry.name.red(),
+                err
+            );
+            return None;
+        }
+    };
+
+    // Download file from URL to temporary location, reusing the previous download's
+    // unchanged blocks when the tool opted into delta updates
+    let delta_updates = tool_entry.delta_updates == Some(true);
+    let delta = delta_updates
+        .then(|| delta::download(tool_entry, download_url, &downloaded_path))
+        .flatten();
+    let validators = match delta.map_or_else(
+        || download_request(request, download_url, &downloaded_path),
+        Ok,
+    ) {
+        Ok(validators) => validators,
+        Err(err) => {
+            log_error!(
+                "[SDB::Tools::{tool_source}::Downloader] Failed to download {} from {}: {}",
+                tool_entry.name.red(),
+                download_url.red(),
+                err
+            );
+            return None;
+        }
+    };
 
     // Verify downloaded file
     match fs::metadata(&downloaded_path) {
@@ -138,12 +184,84 @@ pub fn download_url_asset(
         }
     }
 
+    if delta_updates {
+        delta::remember(tool_entry, &downloaded_path);
+    }
+
     log_info!(
         "[SDB::Tools::{tool_source}::Downloader] Download completed for {}",
         tool_entry.name.bright_blue()
     );
 
-    Some((temp_dir, downloaded_path))
+    Some((temp_dir, downloaded_path, validators))
+}
+
+/// Builds the HTTP request used to download a tool's asset.
+///
+/// Applies the tool's optional request settings on top of the URL:
+/// - `http_method`: `GET` (default) or `POST`
+/// - `headers`: arbitrary headers, e.g. `Authorization: Bearer ${ARTIFACTORY_TOKEN}`
+/// - `basic_auth`: username/password, sent as an `Authorization: Basic` header
+///
+/// Header values and credentials are expanded with environment variables so secrets can
+/// stay out of `tools.yaml`.
+///
+/// # Returns
+///
+/// * `Ok(ureq::Request)` - The ready-to-send request
+/// * `Err(String)` - If the method is unsupported or a referenced variable is not set
+pub fn build_download_request(tool_entry: &ToolEntry, url: &str) -> Result<ureq::Request, String> {
+    let method = tool_entry
+        .http_method
+        .as_deref()
+        .unwrap_or("GET")
+        .to_uppercase();
+    if method != "GET" && method != "POST" {
+        return Err(format!(
+            "Unsupported http_method '{method}'. Must be one of: GET, POST"
+        ));
+    }
+
+    build_tool_request(tool_entry, &method, url)
+}
+
+/// Builds a request with the tool's `headers` and `basic_auth` applied, using `method`.
+///
+/// Used directly for auxiliary requests (such as `version_check`) that must authenticate
+/// like the download but always use a fixed method.
+pub fn build_tool_request(
+    tool_entry: &ToolEntry,
+    method: &str,
+    url: &str,
+) -> Result<ureq::Request, String> {
+    let expand = |value: &str| {
+        shellexpand::env(value)
+            .map(|v| v.into_owned())
+            .map_err(|e| format!("failed to expand '{value}': {e}"))
+    };
+
+    let mut request = ureq::request(method, url).set("User-Agent", "setup-devbox");
+
+    if let Some(headers) = &tool_entry.headers {
+        for (name, value) in headers {
+            request = request.set(name, &expand(value)?);
+        }
+    }
+
+    if let Some(auth) = &tool_entry.basic_auth {
+        let credentials = format!("{}:{}", expand(&auth.username)?, expand(&auth.password)?);
+        request = request.set(
+            "Authorization",
+            &format!("Basic {}", BASE64_STANDARD.encode(credentials)),
+        );
+    }
+
+    Ok(request)
+}
+
+/// Returns the part of a URL before any query string or fragment.
+pub(crate) fn url_path(url: &str) -> &str {
+    url.split(['?', '#']).next().unwrap_or(url)
 }
 
 /// Downloads a file from a given URL and saves it to a specified destination on the local file system.
@@ -159,55 +277,328 @@ pub fn download_url_asset(
 ///   - `Ok(())` if the download was successful and the file was saved.
 ///   - An `io::Error` if anything went wrong during the HTTP request, file creation, or data copying.
 pub fn download_file(url: &str, dest: &Path) -> io::Result<()> {
+    download_request(ureq::get(url), url, dest).map(|_| ())
+}
+
+/// HTTP cache validators returned by the server for a downloaded asset.
+///
+/// Stored in `ToolState` for `latest` URL tools so the next run can send a conditional
+/// request (`If-None-Match` / `If-Modified-Since`) and skip the download on `304 Not Modified`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResponseValidators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl ResponseValidators {
+    pub(crate) fn from_response(response: &ureq::Response) -> Self {
+        Self {
+            etag: response.header("ETag").map(str::to_string),
+            last_modified: response.header("Last-Modified").map(str::to_string),
+        }
+    }
+}
+
+/// Asks the server whether the asset changed since it was last downloaded.
+///
+/// Sends the tool's usual request (headers, auth, method) with `If-None-Match` and/or
+/// `If-Modified-Since` set from the stored validators. The response body is never read.
+///
+/// # Returns
+///
+/// * `true` - The server answered `304 Not Modified`; the installed copy is current
+/// * `false` - The asset changed, no validators are stored, or the check failed
+pub fn remote_asset_unchanged(
+    tool_entry: &ToolEntry,
+    url: &str,
+    etag: Option<&str>,
+    last_modified: Option<&str>,
+) -> bool {
+    if etag.is_none() && last_modified.is_none() {
+        return false;
+    }
+    let mut request = match build_download_request(tool_entry, url) {
+        Ok(request) => request,
+        Err(_) => return false,
+    };
+    if let Some(etag) = etag {
+        request = request.set("If-None-Match", etag);
+    }
+    if let Some(last_modified) = last_modified {
+        request = request.set("If-Modified-Since", last_modified);
+    }
+
+    match request.call() {
+        Ok(response) => {
+            log_debug!(
+                "[SDB::Utils::Downloader] Conditional request for {} returned HTTP {}",
+                url.blue(),
+                response.status()
+            );
+            response.status() == 304
+        }
+        Err(e) => {
+            log_debug!(
+                "[SDB::Utils::Downloader] Conditional request for {} failed: {}",
+                url.blue(),
+                e
+            );
+            false
+        }
+    }
+}
+
+/// Sends a prepared request and streams the response body to `dest`.
+///
+/// This is the shared implementation behind `download_file` (plain GET) and
+/// `download_url_asset` (which may add headers, basic auth or a different HTTP method).
+/// Redirects are checked by `downloads::send`; transient failures, including bodies shorter
+/// than their `Content-Length`, are retried up to `DOWNLOAD_ATTEMPTS` times.
+pub fn download_request(
+    request: ureq::Request,
+    url: &str,
+    dest: &Path,
+) -> io::Result<ResponseValidators> {
     // Log a debug message indicating the start of the download, coloring the URL for clarity.
     log_debug!(
         "[SDB::Utils::Downloader] Starting download from URL: {}",
         url.blue()
     );
 
-    // Execute the HTTP GET request using the `ureq` library.
-    // `ureq::get(url).call()` sends the request and waits for a response.
-    let response = match ureq::get(url).call() {
-        Ok(res) => res, // If the request was successful, `res` contains the HTTP response.
+    let mut attempt = 1;
+    loop {
+        match download_attempt(request.clone(), dest) {
+            Ok(validators) => {
+                // Log a debug message upon successful download, coloring the destination path.
+                log_debug!(
+                    "[SDB::Utils::Downloader] File downloaded successfully to {}",
+                    dest.to_string_lossy().green()
+                );
+                return Ok(validators);
+            }
+            Err(e) if e.is_retryable() && attempt < downloads::DOWNLOAD_ATTEMPTS => {
+                log_warn!(
+                    "[SDB::Utils::Downloader] {} (attempt {}/{}), retrying...",
+                    e,
+                    attempt,
+                    downloads::DOWNLOAD_ATTEMPTS
+                );
+                std::thread::sleep(std::time::Duration::from_secs(u64::from(attempt)));
+                attempt += 1;
+            }
+            Err(e) => {
+                log_error!(
+                    "[SDB::Utils::Downloader] Download of {} failed: {}",
+                    url.red(),
+                    e
+                );
+                return Err(io::Error::other(e.to_string()));
+            }
+        }
+    }
+}
+
+/// Performs one download attempt, detecting bodies cut off before their `Content-Length`.
+fn download_attempt(
+    request: ureq::Request,
+    dest: &Path,
+) -> Result<ResponseValidators, DownloadError> {
+    let response = downloads::send(request)?;
+    let host = response.get_url().to_string();
+    let host = url::Url::parse(&host)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or(host);
+    let expected: Option<u64> = response
+        .header("Content-Length")
+        .and_then(|length| length.parse().ok());
+    let validators = ResponseValidators::from_response(&response);
+
+    // `File::create` truncates what an earlier, failed attempt left behind.
+    let mut file = File::create(dest)?;
+    let received = match io::copy(&mut response.into_reader(), &mut file) {
+        Ok(received) => received,
+        // ureq fails the read when the body ends before its Content-Length
+        Err(_) if expected.is_some() => file.metadata()?.len(),
         Err(e) => {
-            // If the HTTP request itself failed (e.g., network error, invalid URL, DNS resolution failure).
-            log_error!(
-                "[SDB::Utils::Downloader] HTTP request failed for {}: {}",
-                url.red(),
-                e
-            );
-            // Convert the `ureq` error into a standard `io::Error` for consistent error handling
-            // across the application. `std::io::Error::other` is a generic error kind.
-            return Err(io::Error::other(format!("HTTP error: {e}")));
+            return Err(DownloadError::Network {
+                host,
+                detail: format!("connection lost mid-download: {e}"),
+            });
         }
     };
+    if let Some(expected) = expected
+        && received != expected
+    {
+        return Err(DownloadError::Truncated {
+            host,
+            received,
+            expected,
+        });
+    }
+    Ok(validators)
+}
 
-    // Open the destination file for writing.
-    // `File::create(dest)` will create a new file if `dest` does not exist,
-    // or truncate (empty) an existing file at `dest` if it does.
-    // The `?` operator propagates any `io::Error` that occurs during file creation.
-    let mut file = File::create(dest)?;
+/// Guesses the file type purely from a file name's (compound) extension.
+///
+/// This is the fast, I/O-free part of `detect_file_type`. It is also used before a download
+/// starts (e.g. on a GitHub asset name or URL) to decide whether the asset can be streamed.
+///
+/// # Arguments
+/// * `file_name`: The file name (or last URL segment) to inspect.
+///
+/// # Returns
+/// * `Some(String)` with the actionable file type if the extension is recognised.
+/// * `None` if the name carries no known extension.
+pub fn file_type_from_name(file_name: &str) -> Option<String> {
+    let lower_file_name = file_name.to_lowercase();
 
-    // Get a reader for the response body (the actual data being downloaded from the network).
-    let mut reader = response.into_reader();
-    // Copy all data from the network `reader` directly into our local `file`.
-    // This is an efficient way to stream data from the network to disk.
-    // The `?` operator propagates any `io::Error` that occurs during the copy process (read/write errors).
-    std::io::copy(&mut reader, &mut file)?;
+    // Check for compound extensions (e.g., .tar.gz, .tar.xz) first
+    let file_type = if lower_file_name.ends_with(".tar.gz") || lower_file_name.ends_with(".tgz") {
+        "tar.gz"
+    } else if lower_file_name.ends_with(".tar.xz") || lower_file_name.ends_with(".txz") {
+        "tar.xz"
+    } else if lower_file_name.ends_with(".tar.zst") || lower_file_name.ends_with(".tzst") {
+        "tar.zst"
+    } else if lower_file_name.ends_with(".tar.bz2")
+        || lower_file_name.ends_with(".tbz")
+        || lower_file_name.ends_with(".tbz2")
+    {
+        "tar.bz2"
+    }
+    // Then check for common single extensions. The order here is important
+    // to ensure compound extensions are caught first.
+    else if lower_file_name.ends_with(".zip") {
+        "zip"
+    } else if lower_file_name.ends_with(".tar") {
+        "tar"
+    } else if lower_file_name.ends_with(".gz") {
+        "gz"
+    } else if lower_file_name.ends_with(".bz2") {
+        "bz2"
+    } else if lower_file_name.ends_with(".xz") {
+        "xz"
+    } else if lower_file_name.ends_with(".zst") {
+        "zst"
+    } else if lower_file_name.ends_with(".7z") {
+        "7zip"
+    } else if lower_file_name.ends_with(".pkg") {
+        "pkg" // macOS Package Installer
+    } else if lower_file_name.ends_with(".dmg") {
+        "dmg" // macOS Disk Image
+    } else if lower_file_name.ends_with(".msi") {
+        "msi" // Windows Installer package
+    } else {
+        return None;
+    };
+
+    Some(file_type.to_string())
+}
+
+/// Identifies a file type from its leading bytes (magic numbers).
+///
+/// This is an in-crate replacement for `file --mime-type` so type detection also works in
+/// minimal containers where the `file` utility is not installed. Only the formats that
+/// `setup-devbox` can act on are recognised.
+///
+/// Compressed streams (gzip, bzip2, xz, zstd) are reported by their compression format only;
+/// `detect_file_type_from_magic` peeks inside them to tell `tar.gz` from a plain `gz`.
+///
+/// # Arguments
+/// * `header`: The first bytes of the file (at least 512 bytes to recognise plain tar).
+///
+/// # Returns
+/// * `Some(&str)` with the actionable file type, or `None` if the signature is unknown.
+pub fn file_type_from_magic(header: &[u8]) -> Option<&'static str> {
+    // Offset of the "ustar" magic inside a POSIX tar header block.
+    const TAR_MAGIC_OFFSET: usize = 257;
+
+    let file_type = match header {
+        [b'P', b'K', 0x03, 0x04, ..] | [b'P', b'K', 0x05, 0x06, ..] => "zip",
+        [0x1f, 0x8b, ..] => "gz",
+        [b'B', b'Z', b'h', ..] => "bz2",
+        [0xfd, b'7', b'z', b'X', b'Z', 0x00, ..] => "xz",
+        [0x28, 0xb5, 0x2f, 0xfd, ..] => "zst",
+        [b'x', b'a', b'r', b'!', ..] => "pkg", // xar archive, the container format of macOS .pkg
+        // OLE compound file, the container format of Windows .msi
+        [0xd0, 0xcf, 0x11, 0xe0, 0xa1, 0xb1, 0x1a, 0xe1, ..] => "msi",
+        // ELF, Mach-O (32/64-bit, both endiannesses, universal), PE and scripts with a shebang.
+        [0x7f, b'E', b'L', b'F', ..]
+        | [0xfe, 0xed, 0xfa, 0xce | 0xcf, ..]
+        | [0xce | 0xcf, 0xfa, 0xed, 0xfe, ..]
+        | [0xca, 0xfe, 0xba, 0xbe, ..]
+        | [b'M', b'Z', ..]
+        | [b'#', b'!', ..] => "binary",
+        // WebAssembly modules are installed like binaries and launched through a runtime.
+        [0x00, b'a', b's', b'm', ..] => "binary",
+        _ if header.len() >= TAR_MAGIC_OFFSET + 5
+            && &header[TAR_MAGIC_OFFSET..TAR_MAGIC_OFFSET + 5] == b"ustar" =>
+        {
+            "tar"
+        }
+        _ => return None,
+    };
+    Some(file_type)
+}
+
+/// Detects a file's type by reading its magic bytes, without any external command.
+///
+/// Compressed files are decompressed just far enough (one 512-byte tar header block) to
+/// decide whether they wrap a tar archive, so `foo.tar.gz` served as `download` is still
+/// recognised as `tar.gz`. Disk images are recognised by the `koly` trailer in their last
+/// 512 bytes.
+///
+/// # Returns
+/// * `Some(String)` with the actionable file type, or `None` if the file cannot be read or its
+///   signature is unknown.
+fn detect_file_type_from_magic(path: &Path) -> Option<String> {
+    let mut file = File::open(path).ok()?;
+    let mut header = Vec::with_capacity(512);
+    (&mut file).take(512).read_to_end(&mut header).ok()?;
+
+    // Peeks at the first tar block inside a compressed stream.
+    fn wraps_tar<R: Read>(decoder: R) -> bool {
+        let mut block = Vec::with_capacity(512);
+        decoder.take(512).read_to_end(&mut block).is_ok()
+            && file_type_from_magic(&block) == Some("tar")
+    }
+
+    let file_type = match file_type_from_magic(&header) {
+        Some("gz") if wraps_tar(compression::GzDecoder::new(File::open(path).ok()?)) => "tar.gz",
+        Some("bz2") if wraps_tar(BzDecoder::new(File::open(path).ok()?)) => "tar.bz2",
+        Some("xz") if wraps_tar(XzDecoder::new(File::open(path).ok()?)) => "tar.xz",
+        Some("zst") if wraps_tar(ZstdDecoder::new(File::open(path).ok()?).ok()?) => "tar.zst",
+        Some(file_type) => file_type,
+        None => {
+            // UDIF disk images carry a 512-byte "koly" trailer at the very end of the file.
+            let len = file.metadata().ok()?.len();
+            if len < 512 {
+                return None;
+            }
+            let mut trailer = [0u8; 4];
+            file.seek(SeekFrom::Start(len - 512)).ok()?;
+            file.read_exact(&mut trailer).ok()?;
+            if &trailer != b"koly" {
+                return None;
+            }
+            "dmg"
+        }
+    };
 
-    // Log a debug message upon successful download, coloring the destination path.
     log_debug!(
-        "[SDB::Utils::Downloader] File downloaded successfully to {}",
-        dest.to_string_lossy().green()
+        "[SDB::Utils::FileIdentifier] Magic bytes identify {} as {}",
+        path.display(),
+        file_type.cyan()
     );
-    Ok(()) // Indicate success by returning `Ok(())`.
+    Some(file_type.to_string())
 }
 
 /// Detects the file type of given path.
 ///
 /// This function first attempts to guess the file type based on its extension (fast and common).
-/// If the extension doesn't provide a clear, actionable type, it falls back to using the
-/// `file` command for a deeper inspection of the file's magic bytes.
+/// If the extension doesn't provide a clear, actionable type, it inspects the file's magic
+/// bytes in-process. Only when the signature is unknown does it fall back to the optional
+/// `file` command, so detection keeps working where `file` is not installed.
 ///
 /// The returned string is a simplified, actionable type (e.g., "zip", "tar.gz", "pkg", "dmg", "binary").
 /// This single function replaces both `detect_file_type`.
@@ -219,42 +610,20 @@ pub fn download_file(url: &str, dest: &Path) -> io::Result<()> {
 /// * `String`: A string representing the detected file type.
 pub fn detect_file_type(path: &Path) -> String {
     // 1. Initial quick check based on full filename and compound extensions first
-    if let Some(file_name_str) = path.file_name().and_then(|s| s.to_str()) {
-        let lower_file_name = file_name_str.to_lowercase();
-
-        // Check for compound extensions (e.g., .tar.gz, .tar.xz) first
-        if lower_file_name.ends_with(".tar.gz") {
-            return "tar.gz".to_string();
-        } else if lower_file_name.ends_with(".tar.xz") || lower_file_name.ends_with(".txz") {
-            return "tar.xz".to_string();
-        } else if lower_file_name.ends_with(".tar.bz2")
-            || lower_file_name.ends_with(".tbz")
-            || lower_file_name.ends_with(".tbz2")
-        {
-            return "tar.bz2".to_string();
-        }
-        // Then check for common single extensions. The order here is important
-        // to ensure compound extensions are caught first.
-        else if lower_file_name.ends_with(".zip") {
-            return "zip".to_string();
-        } else if lower_file_name.ends_with(".tar") {
-            return "tar".to_string();
-        } else if lower_file_name.ends_with(".gz") {
-            return "gz".to_string();
-        } else if lower_file_name.ends_with(".bz2") {
-            return "bz2".to_string();
-        } else if lower_file_name.ends_with(".xz") {
-            return "xz".to_string();
-        } else if lower_file_name.ends_with(".7z") {
-            return "7zip".to_string();
-        } else if lower_file_name.ends_with(".pkg") {
-            return "pkg".to_string(); // macOS Package Installer
-        } else if lower_file_name.ends_with(".dmg") {
-            return "dmg".to_string(); // macOS Disk Image
-        }
-    }
-
-    // 2. Fallback to `file` command for deeper inspection (more accurate for binaries, etc.)
+    if let Some(file_type) = path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .and_then(file_type_from_name)
+    {
+        return file_type;
+    }
+
+    // 2. In-crate magic byte inspection
+    if let Some(file_type) = detect_file_type_from_magic(path) {
+        return file_type;
+    }
+
+    // 3. Optional fallback to `file` command for formats we do not recognise ourselves
     let output = match Command::new("file")
         .arg("--mime-type")
         .arg("--brief")
@@ -283,6 +652,7 @@ pub fn detect_file_type(path: &Path) -> String {
         "application/gzip" => "gz".to_string(),
         "application/x-bzip2" => "bz2".to_string(),
         "application/x-xz" => "xz".to_string(),
+        "application/zstd" => "zst".to_string(),
         // Specific handling for macOS installers based on MIME type, but confirm extension as a fallback
         "application/x-xar"
             if path
@@ -344,6 +714,17 @@ pub fn process_asset_by_type(
     let mut working_dir = temp_dir.path().to_path_buf();
     let tool_source = capitalize_first(&tool_entry.source.to_string());
 
+    // Applications are installed as a whole, with their command line interface linked
+    if tool_entry.app_bundle.is_some() {
+        return install_app_bundle(
+            tool_entry,
+            downloaded_path,
+            file_type,
+            temp_dir,
+            &tool_source,
+        );
+    }
+
     // Package type identifier for state tracking
     let package_type: String;
     let final_install_path: PathBuf;
@@ -384,11 +765,16 @@ pub fn process_asset_by_type(
                 "[SDB::Tools::{tool_source}::MacInstaller] Installing .dmg for {}",
                 tool_entry.name.bold()
             );
+            let app_dir = tool_entry
+                .app_dir
+                .as_deref()
+                .map(PathResolver::expand_tilde);
             match install_dmg(
                 downloaded_path,
                 &tool_source,
                 &tool_entry.name,
                 &tool_entry.rename_to,
+                app_dir.as_deref(),
             ) {
                 Ok(path) => {
                     package_type = "macos-dmg-installer".to_string();
@@ -405,13 +791,36 @@ pub fn process_asset_by_type(
             }
         }
 
+        // Windows Installer package - installed per user through msiexec
+        #[cfg(windows)]
+        "msi" => {
+            log_info!(
+                "[SDB::Tools::{tool_source}::WindowsInstaller] Installing .msi for {}",
+                tool_entry.name.bold()
+            );
+            match windows::install_msi(downloaded_path, &tool_source, &tool_entry.name) {
+                Ok(path) => {
+                    package_type = "windows-msi-installer".to_string();
+                    final_install_path = path;
+                }
+                Err(err) => {
+                    log_error!(
+                        "[SDB::Tools::{tool_source}::WindowsInstaller] Failed to install .msi for {}: {}",
+                        tool_entry.name.red(),
+                        err
+                    );
+                    return None;
+                }
+            }
+        }
+
         // Raw binary - direct installation to bin directory
         "binary" => {
             log_debug!(
                 "[SDB::Tools::{tool_source}::BinaryInstaller] Installing binary for {}",
                 tool_entry.name.bold()
             );
-            final_install_path = PathResolver::get_user_home_dir()?;
+            final_install_path = PathResolver::get_user_bin_dir()?;
             // Move binary to installation path
             if let Err(err) = move_and_rename_binary(
                 downloaded_path,
@@ -442,8 +851,8 @@ pub fn process_asset_by_type(
         }
 
         // Archive formats - extract, find executable, and install
-        archive_type @ ("zip" | "tar.gz" | "gz" | "tar.bz2" | "tar" | "tar.xz" | "tar.bz"
-        | "txz" | "tbz2") => {
+        archive_type @ ("zip" | "tar.gz" | "gz" | "bz2" | "xz" | "zst" | "tar.bz2" | "tar"
+        | "tar.xz" | "tar.bz" | "txz" | "tbz2" | "tar.zst" | "tzst") => {
             log_debug!(
                 "[SDB::Tools::{tool_source}::Archiver] Extracting {} archive for {}",
                 archive_type,
@@ -468,57 +877,11 @@ pub fn process_asset_by_type(
                 }
             };
 
-            // Search extracted contents for the executable binary
-            let executable_path = find_executable(
-                &extracted_path,
-                &tool_entry.name,
-                tool_entry.rename_to.as_deref(),
-                tool_source.clone()
-            )
-            .or_else(|| {
-                log_error!(
-                    "[SDB::Tools::{tool_source}::BinaryInstaller] No executable found in archive for {}",
-                    tool_entry.name.red()
-                );
-                log_error!(
-                    "[SDB::Tools::{tool_source}::BinaryInstaller] Expected to find binary named '{}' or similar",
-                    tool_entry.name
-                );
-                None
-            })?;
-
-            // Determine appropriate working directory for post-installation hooks
-            // This is typically the parent directory of the executable
-            working_dir =
-                PathResolver::determine_working_directory(&executable_path, &extracted_path);
-
-            final_install_path = PathResolver::get_user_home_dir()?;
-
-            // Move extracted binary to final installation location
-            if let Err(err) = move_and_rename_binary(
-                &executable_path,
-                &final_install_path,
-                tool_entry,
-                tool_source.clone(),
-            ) {
-                log_error!(
-                    "[SDB::Tools::{tool_source}::BinaryInstaller] Failed to move extracted binary for {}: {}",
-                    tool_entry.name.red(),
-                    err
-                );
-                return None;
-            }
-
-            // Set executable permissions on the installed binary
-            if let Err(err) = make_executable(&final_install_path, tool_entry, tool_source.clone())
-            {
-                log_error!(
-                    "[SDB::Tools::{tool_source}::BinaryInstaller] Failed to make extracted binary executable for {}: {}",
-                    tool_entry.name.red(),
-                    err
-                );
-                return None;
-            }
+            // Locate the executable, move it into place and make it executable
+            let (executable_dir, install_dir) =
+                install_from_extracted(tool_entry, &extracted_path, &tool_source)?;
+            working_dir = executable_dir;
+            final_install_path = install_dir;
 
             package_type = "binary".to_string();
         }
@@ -531,7 +894,7 @@ pub fn process_asset_by_type(
                 tool_entry.name.red()
             );
             log_error!(
-                "[SDB::FileIdentifer] Supported types: binary, zip, tar.gz, tar.xz, tar.bz2, pkg, dmg"
+                "[SDB::FileIdentifer] Supported types: binary, zip, tar.gz, tar.xz, tar.bz2, tar.zst, gz, bz2, xz, zst, pkg, dmg, msi"
             );
             return None;
         }
@@ -543,6 +906,500 @@ pub fn process_asset_by_type(
     Some((package_type, file_path, working_dir))
 }
 
+/// Installs the `.app` bundle shipped in a `.dmg` or archive and links the command line
+/// interface embedded in it into the bin directory (tools with `app_bundle:`).
+///
+/// # Returns
+///
+/// * `Some((package_type, link_path, bundle))` - As returned by `app_bundle::link_cli`
+/// * `None` - If the asset holds no bundle or installing it fails
+fn install_app_bundle(
+    tool_entry: &ToolEntry,
+    downloaded_path: &Path,
+    file_type: &str,
+    temp_dir: &tempfile::TempDir,
+    tool_source: &str,
+) -> Option<(String, PathBuf, PathBuf)> {
+    match file_type {
+        // The bundle keeps its own name; `rename_to` names the linked CLI
+        #[cfg(target_os = "macos")]
+        "dmg" => {
+            let app_dir = app_bundle::app_dir(tool_entry);
+            match install_dmg(
+                downloaded_path,
+                tool_source,
+                &tool_entry.name,
+                &None,
+                Some(&app_dir),
+            ) {
+                Ok(bundle) => app_bundle::link_cli(tool_entry, &bundle, tool_source),
+                Err(err) => {
+                    log_error!(
+                        "[SDB::Tools::{tool_source}::MacInstaller] Failed to install .dmg for {}: {}",
+                        tool_entry.name.red(),
+                        err
+                    );
+                    None
+                }
+            }
+        }
+        "binary" | "pkg" => {
+            log_error!(
+                "[SDB::Tools::{tool_source}::AppBundle] {} ships a {} instead of an application bundle; remove 'app_bundle' from the entry",
+                tool_entry.name.red(),
+                file_type
+            );
+            None
+        }
+        archive_type => {
+            let extracted_path = match compression::extract_archive(
+                downloaded_path,
+                temp_dir.path(),
+                Some(archive_type),
+                "Tools",
+            ) {
+                Ok(path) => path,
+                Err(err) => {
+                    log_error!(
+                        "[SDB::Tools::{tool_source}::Archiver] Failed to extract archive for {}: {}",
+                        tool_entry.name.red(),
+                        err
+                    );
+                    return None;
+                }
+            };
+            app_bundle::install_extracted(tool_entry, &extracted_path, tool_source)
+        }
+    }
+}
+
+/// Installs the tool binary from an already extracted archive directory.
+///
+/// Shared by the buffered (`process_asset_by_type`) and streaming (`process_streamed_asset`)
+/// paths: searches the extracted contents for the executable, moves it into the user's bin
+/// directory (honouring `rename_to`) and sets executable permissions.
+///
+/// # Returns
+///
+/// * `Some((working_dir, final_install_path))` - The directory for post-installation hooks and
+///   the bin directory the executable was installed into
+/// * `None` - If no executable was found or it could not be installed
+fn install_from_extracted(
+    tool_entry: &ToolEntry,
+    extracted_path: &Path,
+    tool_source: &str,
+) -> Option<(PathBuf, PathBuf)> {
+    // An explicit `executable_path_after_extract` wins over any detection strategy
+    let explicit_path = tool_entry
+        .executable_path_after_extract
+        .as_deref()
+        .and_then(|relative| {
+            let candidate = compression::sanitize_entry_path(Path::new(relative))
+                .map(|relative| extracted_path.join(relative))
+                .ok()
+                .filter(|p| p.is_file());
+            if candidate.is_none() {
+                log_warn!(
+                    "[SDB::Tools::{tool_source}::BinaryInstaller] executable_path_after_extract '{}' not found in archive, falling back to detection",
+                    relative.yellow()
+                );
+            }
+            candidate
+        });
+
+    // Search extracted contents for the executable binary
+    let executable_path = explicit_path
+        .or_else(|| {
+            find_executable(
+                extracted_path,
+                &tool_entry.name,
+                tool_entry.rename_to.as_deref(),
+                tool_source.to_string(),
+                tool_entry.executable_detection.unwrap_or_default(),
+                tool_entry.exclude_from_search.as_deref().unwrap_or_default(),
+            )
+        })
+        .or_else(|| {
+        log_error!(
+            "[SDB::Tools::{tool_source}::BinaryInstaller] No executable found in archive for {}",
+            tool_entry.name.red()
+        );
+        log_error!(
+            "[SDB::Tools::{tool_source}::BinaryInstaller] Expected to find binary named '{}' or similar",
+            tool_entry.name
+        );
+        None
+    })?;
+
+    // Determine appropriate working directory for post-installation hooks
+    // This is typically the parent directory of the executable
+    let working_dir = PathResolver::determine_working_directory(&executable_path, extracted_path);
+
+    let final_install_path = PathResolver::get_user_bin_dir()?;
+
+    // Move extracted binary to final installation location
+    if let Err(err) = move_and_rename_binary(
+        &executable_path,
+        &final_install_path,
+        tool_entry,
+        tool_source.to_string(),
+    ) {
+        log_error!(
+            "[SDB::Tools::{tool_source}::BinaryInstaller] Failed to move extracted binary for {}: {}",
+            tool_entry.name.red(),
+            err
+        );
+        return None;
+    }
+
+    // Set executable permissions on the installed binary
+    if let Err(err) = make_executable(&final_install_path, tool_entry, tool_source.to_string()) {
+        log_error!(
+            "[SDB::Tools::{tool_source}::BinaryInstaller] Failed to make extracted binary executable for {}: {}",
+            tool_entry.name.red(),
+            err
+        );
+        return None;
+    }
+
+    Some((working_dir, final_install_path))
+}
+
+// ============================================================================
+//                         STREAMING EXTRACTION PIPELINE
+// ============================================================================
+
+/// Decides whether an asset should be installed through the streaming pipeline.
+///
+/// Streaming pipes the HTTP body straight through the decompressor into the extraction
+/// directory, so the archive itself never touches the disk. It is only used when it is safe:
+/// - the asset type (derived from its name) is a tar-family archive that can be unpacked in
+///   a single forward pass (see `compression::is_streamable_archive`), and
+/// - the tool has not opted out with `streaming: false`.
+///
+/// Everything else (zip, pkg, dmg, raw binaries, unknown names) keeps the buffered path.
+///
+/// # Arguments
+///
+/// * `tool_entry` - The tool being installed
+/// * `asset_name` - The asset file name or download URL used to guess the archive type
+///
+/// # Returns
+///
+/// * `Some(file_type)` - The archive type to stream when streaming applies
+/// * `None` - When the buffered download path must be used
+pub fn streamable_file_type(tool_entry: &ToolEntry, asset_name: &str) -> Option<String> {
+    // Delta updates need the whole asset on disk to seed the next update
+    if tool_entry.streaming == Some(false) || tool_entry.delta_updates == Some(true) {
+        return None;
+    }
+    let path = url_path(asset_name);
+    let name = path.rsplit('/').next().unwrap_or(path);
+    file_type_from_name(name).filter(|ft| compression::is_streamable_archive(ft))
+}
+
+/// Downloads and extracts a tar-family archive in one pass, without buffering it on disk.
+///
+/// The response body reader is handed directly to `compression::unpack_tar_stream`, so
+/// download, decompression and extraction overlap. This roughly halves the disk usage and
+/// wall-clock time for large assets compared to download-then-extract.
+///
+/// # Arguments
+///
+/// * `tool_entry` - The tool being installed (used for naming and error messages)
+/// * `download_url` - The URL to stream from
+/// * `file_type` - The archive type, as returned by `streamable_file_type`
+///
+/// # Returns
+///
+/// * `Some((temp_dir, extracted_path))` - The temporary directory handle and the directory the
+///   archive was unpacked into
+/// * `None` - If the request or extraction fails; callers should fall back to the buffered path
+pub fn stream_url_asset(
+    tool_entry: &ToolEntry,
+    download_url: &str,
+    file_type: &str,
+) -> Option<(tempfile::TempDir, PathBuf, ResponseValidators)> {
+    let tool_source = capitalize_first(&tool_entry.source.to_string());
+    let temp_dir = match TempFileBuilder::new()
+        .prefix(&format!("setup-devbox-install-{}-", tool_entry.name))
+        .tempdir_in(PathResolver::temp_root())
+    {
+        Ok(dir) => dir,
+        Err(e) => {
+            log_error!(
+                "[SDB::Tools::{tool_source}::Downloader] Failed to create temporary directory for {}: {}",
+                tool_entry.name.red(),
+                e
+            );
+            return None;
+        }
+    };
+
+    let extracted_path = temp_dir.path().join("extracted");
+    if let Err(e) = fs::create_dir_all(&extracted_path) {
+        log_error!(
+            "[SDB::Tools::{tool_source}::Downloader] Failed to create extraction directory: {}",
+            e
+        );
+        return None;
+    }
+
+    log_info!(
+        "[SDB::Tools::{tool_source}::Downloader] Streaming: '{}' from '{}'",
+        tool_entry.name.green(),
+        download_url.cyan()
+    );
+
+    let request = match build_download_request(tool_entry, download_url) {
+        Ok(request) => request,
+        Err(e) => {
+            log_warn!(
+                "[SDB::Tools::{tool_source}::Downloader] Invalid request configuration for {}: {}",
+                tool_entry.name.yellow(),
+                e
+            );
+            return None;
+        }
+    };
+
+    let response = match downloads::send(request) {
+        Ok(res) => res,
+        Err(e) => {
+            log_warn!(
+                "[SDB::Tools::{tool_source}::Downloader] Streaming request failed for {}: {}",
+                download_url.yellow(),
+                e
+            );
+            return None;
+        }
+    };
+
+    let validators = ResponseValidators::from_response(&response);
+    if let Err(e) =
+        compression::unpack_tar_stream(response.into_reader(), file_type, &extracted_path, "Tools")
+    {
+        log_warn!(
+            "[SDB::Tools::{tool_source}::Downloader] Streaming extraction failed for {}: {}",
+            tool_entry.name.yellow(),
+            e
+        );
+        return None;
+    }
+
+    log_info!(
+        "[SDB::Tools::{tool_source}::Downloader] Streamed and extracted {}",
+        tool_entry.name.bright_blue()
+    );
+
+    Some((temp_dir, extracted_path, validators))
+}
+
+/// Installs a tool from a directory produced by `stream_url_asset`.
+///
+/// This is the streaming counterpart of the archive branch of `process_asset_by_type` and
+/// returns the same `(package_type, file_path, working_dir)` tuple.
+pub fn process_streamed_asset(
+    tool_entry: &ToolEntry,
+    extracted_path: &Path,
+) -> Option<(String, PathBuf, PathBuf)> {
+    let tool_source = capitalize_first(&tool_entry.source.to_string());
+    if tool_entry.app_bundle.is_some() {
+        return app_bundle::install_extracted(tool_entry, extracted_path, &tool_source);
+    }
+    let (working_dir, final_install_path) =
+        install_from_extracted(tool_entry, extracted_path, &tool_source)?;
+    let file_path = PathResolver::get_final_file_path(&final_install_path, tool_entry);
+    Some(("binary".to_string(), file_path, working_dir))
+}
+
+// ============================================================================
+//                            ADDITIONAL TOOL ASSETS
+// ============================================================================
+
+/// Matches a file or asset name against a pattern.
+///
+/// Patterns containing `*` are treated as wildcard globs anchored at both ends
+/// (`*` matches any run of characters, including none). Patterns without `*` match
+/// as a case-insensitive substring, mirroring how platform matching treats asset names.
+pub fn name_matches_pattern(name: &str, pattern: &str) -> bool {
+    let name = name.to_lowercase();
+    let pattern = pattern.to_lowercase();
+    if !pattern.contains('*') {
+        return name.contains(&pattern);
+    }
+
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let mut rest = name.as_str();
+    for (i, part) in parts.iter().enumerate() {
+        if i == 0 {
+            // The first segment must be a prefix.
+            match rest.strip_prefix(part) {
+                Some(r) => rest = r,
+                None => return false,
+            }
+        } else if i == parts.len() - 1 {
+            // The last segment must be a suffix of what is left.
+            return rest.ends_with(part);
+        } else if let Some(pos) = rest.find(part) {
+            rest = &rest[pos + part.len()..];
+        } else {
+            return false;
+        }
+    }
+    true
+}
+
+/// Downloads and installs one additional asset declared in a tool's `assets:` list.
+///
+/// Archives are extracted; either the whole extracted tree or the single `extract` path
+/// inside it is copied into `destination`. Any other file is copied to `destination`
+/// (into it, when it is a directory). Destinations support `~` and environment variables.
+///
+/// # Arguments
+///
+/// * `tool_entry` - The tool the asset belongs to (used for naming and log messages)
+/// * `asset` - The asset configuration from `tools.yaml`
+/// * `asset_name` - File name of the matched release asset
+/// * `download_url` - The URL to download the asset from
+///
+/// # Returns
+///
+/// * `Some(paths)` - The files or directories written for this asset
+/// * `None` - If download, extraction or copying fails
+pub fn install_extra_asset(
+    tool_entry: &ToolEntry,
+    asset: &AssetEntry,
+    asset_name: &str,
+    download_url: &str,
+) -> Option<Vec<PathBuf>> {
+    let tool_source = capitalize_first(&tool_entry.source.to_string());
+    let (temp_dir, downloaded_path) = download_url_asset(tool_entry, download_url)?;
+    let destination = match PathResolver::expand_path(&asset.destination) {
+        Ok(path) => path,
+        Err(err) => {
+            log_error!(
+                "[SDB::Tools::{tool_source}::Assets] Invalid destination '{}': {}",
+                asset.destination.red(),
+                err
+            );
+            return None;
+        }
+    };
+    let file_type = detect_file_type(&downloaded_path);
+
+    let is_archive = matches!(
+        file_type.as_str(),
+        "zip"
+            | "tar.gz"
+            | "tar.bz2"
+            | "tar.bz"
+            | "tar"
+            | "tar.xz"
+            | "txz"
+            | "tbz2"
+            | "tar.zst"
+            | "tzst"
+    );
+
+    let result = if is_archive {
+        let extracted = match compression::extract_archive(
+            &downloaded_path,
+            temp_dir.path(),
+            Some(&file_type),
+            "Tools",
+        ) {
+            Ok(path) => path,
+            Err(err) => {
+                log_error!(
+                    "[SDB::Tools::{tool_source}::Assets] Failed to extract asset {}: {}",
+                    asset_name.red(),
+                    err
+                );
+                return None;
+            }
+        };
+        match &asset.extract {
+            Some(inner) => {
+                let source = compression::sanitize_entry_path(Path::new(inner))
+                    .map(|relative| extracted.join(relative))
+                    .ok()
+                    .filter(|p| p.exists())
+                    .or_else(|| {
+                        log_error!(
+                            "[SDB::Tools::{tool_source}::Assets] '{}' not found inside asset {}",
+                            inner.red(),
+                            asset_name
+                        );
+                        None
+                    })?;
+                let target = if source.is_file() {
+                    destination.join(source.file_name()?)
+                } else {
+                    destination.clone()
+                };
+                copy_recursively(&source, &target).map(|_| vec![target])
+            }
+            None => copy_recursively(&extracted, &destination).map(|_| vec![destination.clone()]),
+        }
+    } else {
+        let target = if destination.is_dir() || asset.destination.ends_with('/') {
+            destination.join(asset_name)
+        } else {
+            destination.clone()
+        };
+        copy_recursively(&downloaded_path, &target).map(|_| vec![target])
+    };
+
+    match result {
+        Ok(paths) => {
+            log_info!(
+                "[SDB::Tools::{tool_source}::Assets] Installed asset {} for {} into {}",
+                asset_name.green(),
+                tool_entry.name.bold(),
+                destination.display().to_string().cyan()
+            );
+            Some(paths)
+        }
+        Err(err) => {
+            log_error!(
+                "[SDB::Tools::{tool_source}::Assets] Failed to install asset {} into {}: {}",
+                asset_name.red(),
+                destination.display(),
+                err
+            );
+            None
+        }
+    }
+}
+
+/// Copies a file, or a directory tree, from `from` to `to`, creating parents as needed.
+fn copy_recursively(from: &Path, to: &Path) -> io::Result<()> {
+    if from.is_file() {
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(from, to)?;
+        return Ok(());
+    }
+    for entry in walkdir::WalkDir::new(from)
+        .into_iter()
+        .filter_map(Result::ok)
+    {
+        let relative = entry.path().strip_prefix(from).unwrap_or(entry.path());
+        let target = to.join(relative);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)?;
+        } else if entry.file_type().is_file() {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
 fn capitalize_first(s: &str) -> String {
     let mut chars = s.chars();
     match chars.next() {
@@ -550,3 +1407,51 @@ fn capitalize_first(s: &str) -> String {
         Some(first) => first.to_uppercase().collect::<String>() + chars.as_str() + "Installer",
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_matches_pattern() {
+        assert!(name_matches_pattern(
+            "tool-completions.tar.gz",
+            "completions"
+        ));
+        assert!(name_matches_pattern(
+            "tool-1.0-completions.tar.gz",
+            "tool-*-completions.tar.gz"
+        ));
+        assert!(name_matches_pattern("tool.1", "*.1"));
+        assert!(!name_matches_pattern("tool.1.sig", "*.1"));
+        assert!(!name_matches_pattern("other.tar.gz", "tool*"));
+    }
+
+    #[test]
+    fn test_file_type_from_magic() {
+        assert_eq!(file_type_from_magic(b"PK\x03\x04rest"), Some("zip"));
+        assert_eq!(file_type_from_magic(&[0x1f, 0x8b, 0x08]), Some("gz"));
+        assert_eq!(file_type_from_magic(b"BZh91AY"), Some("bz2"));
+        assert_eq!(
+            file_type_from_magic(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]),
+            Some("xz")
+        );
+        assert_eq!(file_type_from_magic(&[0x28, 0xb5, 0x2f, 0xfd]), Some("zst"));
+        assert_eq!(file_type_from_magic(b"xar!\x00\x1c"), Some("pkg"));
+        assert_eq!(file_type_from_magic(b"\x7fELF\x02"), Some("binary"));
+        assert_eq!(
+            file_type_from_magic(&[0xcf, 0xfa, 0xed, 0xfe]),
+            Some("binary")
+        );
+        assert_eq!(file_type_from_magic(b"MZ\x90\x00"), Some("binary"));
+        assert_eq!(
+            file_type_from_magic(&[0xd0, 0xcf, 0x11, 0xe0, 0xa1, 0xb1, 0x1a, 0xe1]),
+            Some("msi")
+        );
+        assert_eq!(file_type_from_magic(b"hello world"), None);
+
+        let mut tar_block = vec![0u8; 512];
+        tar_block[257..262].copy_from_slice(b"ustar");
+        assert_eq!(file_type_from_magic(&tar_block), Some("tar"));
+    }
+}
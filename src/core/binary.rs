@@ -1,3 +1,6 @@
+use crate::core::assets::name_matches_pattern;
+use crate::core::windows;
+use crate::schemas::tools_enums::ExecutableDetection;
 use crate::schemas::tools_types::ToolEntry;
 // Our custom logging macros to give us nicely formatted (and colored!) output
 // for debugging, general information, and errors.
@@ -40,7 +43,15 @@ use walkdir::WalkDir;
 ///    it attempts to set executable permissions and includes it as a candidate.
 /// 5. **Candidate Prioritization:** Collects all potential executables and sorts them,
 ///    prioritizing an exact filename match with the `target_name_lower` (the expected binary name,
-///    considering renames) and then by file size (larger files are often the main binary).
+///    considering renames), then native binaries confirmed by `goblin` over scripts and name-only
+///    matches, and finally by file size (larger files are often the main binary).
+///
+/// With `ExecutableDetection::Strict`, only files named exactly like the tool or its
+/// `rename_to` (optionally with an `.exe` suffix) are considered at all. This avoids picking
+/// helper binaries shipped alongside the main one.
+///
+/// Files matching one of the tool's `exclude_from_search` globs (by file name or by path
+/// inside `dir`) are never considered, whatever the detection strategy.
 ///
 /// # Arguments
 /// * `dir`: The `&Path` to the directory where the search should begin. The function
@@ -50,6 +61,8 @@ use walkdir::WalkDir;
 /// * `rename_to`: An `Option<&str>` specifying an alternative name for the executable
 ///   if it's different from `tool_name` (e.g., "hx" for "helix"). This is
 ///   the primary name targeted during the search and sorting.
+/// * `detection`: The `ExecutableDetection` strategy configured for the tool.
+/// * `exclude`: The tool's `exclude_from_search` globs.
 ///
 /// # Returns
 /// * `Option<PathBuf>`:
@@ -60,12 +73,16 @@ pub fn find_executable(
     tool_name: &str,
     rename_to: Option<&str>,
     tool_source: String,
+    detection: ExecutableDetection,
+    exclude: &[String],
 ) -> Option<PathBuf> {
     // Convert tool name and target (renamed) name to lowercase for case-insensitive comparisons.
     let tool_name_lower = tool_name.to_lowercase();
     let target_name_lower = rename_to.map_or(tool_name_lower.clone(), |s| s.to_lowercase());
-    // Vector to store potential executable candidates, along with their file sizes for sorting.
-    let mut candidates: Vec<(PathBuf, u64)> = Vec::new();
+    let strict = detection == ExecutableDetection::Strict;
+    // Vector to store potential executable candidates, along with their file sizes and whether
+    // `goblin` confirmed a native binary header, for sorting.
+    let mut candidates: Vec<(PathBuf, u64, bool)> = Vec::new();
 
     // Special handling for directories containing a single entry.
     // This optimization attempts to quickly identify the executable if it's the only file/directory.
@@ -77,8 +94,16 @@ pub fn find_executable(
     // If there's exactly one entry in the directory:
     if entries.len() == 1 {
         let sole_path = entries[0].path();
-        // If that single entry is a file:
-        if sole_path.is_file() {
+        let sole_name = sole_path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        // If that single entry is a file (and, in strict mode, carries the expected name):
+        if sole_path.is_file()
+            && (!strict || matches_expected_name(&sole_name, &tool_name_lower, &target_name_lower))
+            && !is_excluded(dir, &sole_path, exclude)
+        {
             log_debug!(
                 "[SDB::Tools::{tool_source}::BinaryInstaller] Single file found, inspecting as potential binary: {}",
                 sole_path.display()
@@ -95,10 +120,10 @@ pub fn find_executable(
                     match Object::parse(&data) {
                         Ok(obj) => {
                             match obj {
-                                // If it's an ELF (Linux) or Mach-O (macOS) executable:
-                                Object::Elf(_) | Object::Mach(_) => {
+                                // If it's an ELF (Linux), Mach-O (macOS) or, on Windows, PE executable:
+                                obj if is_native_object(&obj) => {
                                     log_debug!(
-                                        "[SDB::Tools::{tool_source}::BinaryInstaller] Detected native binary (ELF/Mach-O) in single file: {}",
+                                        "[SDB::Tools::{tool_source}::BinaryInstaller] Detected native binary (ELF/Mach-O/PE) in single file: {}",
                                         sole_path.display()
                                     );
                                     // On Unix-like systems, ensure it's executable.
@@ -120,7 +145,7 @@ pub fn find_executable(
                                     // If it's a confirmed native binary, return it immediately as the most likely candidate.
                                     return Some(sole_path);
                                 }
-                                // For other object types (e.g., PE for Windows, or unknown):
+                                // For other object types (e.g., PE off Windows, or unknown):
                                 _ => {
                                     // Check if the file starts with `#!` (shebang), indicating a script.
                                     if data.starts_with(b"#!") {
@@ -206,7 +231,26 @@ pub fn find_executable(
             continue; // Move to the next file.
         }
 
+        // Files the tool excludes from the search are never candidates.
+        if is_excluded(dir, path, exclude) {
+            log_debug!(
+                "[SDB::Tools::{tool_source}::BinaryInstaller] Skipping {} (matches exclude_from_search)",
+                path.display()
+            );
+            continue;
+        }
+
+        // In strict mode, only files carrying the expected tool name are ever considered.
+        if strict && !matches_expected_name(&file_name, &tool_name_lower, &target_name_lower) {
+            log_debug!(
+                "[SDB::Tools::{tool_source}::BinaryInstaller] Strict detection: skipping {} (name does not match)",
+                file_name
+            );
+            continue;
+        }
+
         let mut add_candidate = false; // Flag to determine if the current file should be a candidate.
+        let mut native_binary = false; // Whether `goblin` confirmed an ELF/Mach-O header.
 
         // Attempt to read file data for `goblin` and shebang checks.
         if let Ok(data) = fs::read(path) {
@@ -217,8 +261,9 @@ pub fn find_executable(
             );
             if let Ok(obj) = Object::parse(&data) {
                 match obj {
-                    // If it's an ELF or Mach-O executable:
-                    Object::Elf(_) | Object::Mach(_) => {
+                    // If it's an ELF, Mach-O or (on Windows) PE executable:
+                    obj if is_native_object(&obj) => {
+                        native_binary = true;
                         if is_executable(path) {
                             log_debug!(
                                 "[SDB::Tools::{tool_source}::BinaryInstaller] Found executable binary (ELF/Mach-O): {}",
@@ -312,7 +357,12 @@ pub fn find_executable(
         // Fallback: If no executable format was confirmed, check for an exact filename match.
         // This is important for tools that might not have standard executable headers or shebangs
         // but are still meant to be executed (e.g., custom scripts without shebangs, or certain Windows executables).
-        if !add_candidate && file_name == target_name_lower {
+        // WebAssembly modules named after the tool (`tool.wasm`) qualify too; they are
+        // launched through a runtime.
+        let wasm_module = file_name
+            .strip_suffix(".wasm")
+            .is_some_and(|stem| stem == tool_name_lower || stem == target_name_lower);
+        if !add_candidate && (file_name == target_name_lower || wasm_module) {
             log_debug!(
                 "[SDB::Tools::{tool_source}::BinaryInstaller] Fallback: Forcing executable candidate for {} (exact name match).",
                 path.display()
@@ -344,12 +394,12 @@ pub fn find_executable(
                 path.display(),
                 size
             );
-            candidates.push((path.to_path_buf(), size));
+            candidates.push((path.to_path_buf(), size, native_binary));
         }
     }
 
     // Sort the collected candidates to prioritize the most likely executable.
-    candidates.sort_by(|(a_path, a_size), (b_path, b_size)| {
+    candidates.sort_by(|(a_path, a_size, a_native), (b_path, b_size, b_native)| {
         let a = a_path
             .file_name()
             .unwrap_or_default()
@@ -369,15 +419,49 @@ pub fn find_executable(
             std::cmp::Ordering::Greater // 'b' comes before 'a'
         } else {
             // Secondary sort criterion (if neither or both match the target name):
-            // Sort by file size in descending order (larger files first), as main executables are often the largest.
-            b_size.cmp(a_size)
+            // Prefer header-confirmed native binaries over scripts and name-only matches,
+            // then sort by file size in descending order, as main executables are often the largest.
+            b_native.cmp(a_native).then_with(|| b_size.cmp(a_size))
         }
     });
 
     // Return the path of the highest-priority candidate.
-    // `into_iter().map(|(p, _)| p).next()` takes the first element (highest priority)
+    // `into_iter().map(|(p, _, _)| p).next()` takes the first element (highest priority)
     // and discards the size, returning only the PathBuf.
-    candidates.into_iter().map(|(p, _)| p).next()
+    candidates.into_iter().map(|(p, _, _)| p).next()
+}
+
+/// Returns `true` if `file_name` (lowercase) is the tool's name or its `rename_to`,
+/// ignoring a trailing `.exe`.
+fn matches_expected_name(file_name: &str, tool_name_lower: &str, target_name_lower: &str) -> bool {
+    let stem = file_name.strip_suffix(".exe").unwrap_or(file_name);
+    stem == tool_name_lower || stem == target_name_lower
+}
+
+/// Returns `true` if `path` matches one of the `exclude_from_search` globs, by file name or
+/// by its `/`-separated path inside `dir` (also tried with a leading `/`, so `*/jre/bin/*`
+/// covers a `jre` directory at the top of the archive too).
+fn is_excluded(dir: &Path, path: &Path, exclude: &[String]) -> bool {
+    if exclude.is_empty() {
+        return false;
+    }
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let relative = path
+        .strip_prefix(dir)
+        .unwrap_or(path)
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    let rooted = format!("/{relative}");
+    exclude.iter().any(|pattern| {
+        [&file_name, &relative, &rooted]
+            .iter()
+            .any(|candidate| name_matches_pattern(candidate, pattern))
+    })
 }
 
 /// Helper function to check if a file has executable permissions.
@@ -390,6 +474,7 @@ pub fn find_executable(
 ///   On Windows, this check is less relevant as executability is primarily determined
 ///   by file extension (`.exe`, `.bat`, etc.) rather than permission bits.
 ///   Returns `false` if metadata cannot be retrieved or it's not executable.
+#[cfg(unix)]
 fn is_executable(path: &Path) -> bool {
     // Get file metadata, specifically permissions.
     fs::metadata(path)
@@ -402,6 +487,24 @@ fn is_executable(path: &Path) -> bool {
         .unwrap_or(false)
 }
 
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .file_name()
+            .is_some_and(|name| windows::has_executable_extension(&name.to_string_lossy()))
+}
+
+/// Whether `goblin` parsed an executable format this platform runs natively: ELF and
+/// Mach-O everywhere (as before), PE only on Windows.
+fn is_native_object(obj: &Object) -> bool {
+    match obj {
+        Object::Elf(_) | Object::Mach(_) => true,
+        Object::PE(_) => cfg!(windows),
+        _ => false,
+    }
+}
+
 /// Moves a file (typically a binary) from a source path to a destination path,
 /// and can also rename it in the process by providing a new filename in the `to` path.
 /// It ensures that the destination's parent directories exist before attempting the move.
@@ -469,16 +572,16 @@ pub fn move_and_rename_binary(
                 "[SDB::Tools::{tool_source}::BinaryInstaller] Destination is a directory, appending new filename: {}",
                 new_name
             );
-            to.join(new_name)
+            to.join(windows::platform_file_name(new_name))
         } else {
             log_debug!(
                 "[SDB::Tools::{tool_source}::BinaryInstaller] Destination appears to be a file path, using parent directory and appending new filename"
             );
             // If 'to' has a parent, join new_name to parent; otherwise use 'to' as is
             if let Some(parent) = to.parent() {
-                parent.join(new_name)
+                parent.join(windows::platform_file_name(new_name))
             } else {
-                PathBuf::from(new_name)
+                PathBuf::from(windows::platform_file_name(new_name))
             }
         }
     } else {
@@ -529,85 +632,127 @@ pub fn move_and_rename_binary(
         log_debug!("[SDB::Tools::{tool_source}::BinaryInstaller] No parent directories to create");
     }
 
-    // Decide operation based on rename_to
-    if tool_entry.rename_to.is_none() {
-        log_debug!(
-            "[SDB::Tools::{tool_source}::BinaryInstaller] Performing simple copy operation (no rename)"
-        );
+    // Stage the binary next to its destination and only promote it once verified, so a
+    // failed or interrupted install never leaves a truncated binary on PATH.
+    // Renamed binaries are moved out of the extraction directory; others are copied.
+    stage_and_promote(
+        from,
+        &final_destination,
+        &tool_entry.name,
+        tool_entry.rename_to.is_some(),
+        &tool_source,
+    )?;
+
+    log_debug!(
+        "[SDB::Tools::{tool_source}::BinaryInstaller] Binary installed successfully to {}",
+        final_destination.to_string_lossy().green()
+    );
+    Ok(())
+}
+
+/// Installs `from` at `destination` through a per-tool staging directory.
+///
+/// The file is first copied (or moved, when `move_source` is set) into
+/// `<destination dir>/.sdb-staging/<tool>/`, which lives on the same filesystem as the
+/// destination. There it is verified (non-empty and the same size as the source) and made
+/// executable, and only then renamed over `destination`. Because the final step is a single
+/// `rename`, `destination` always holds either the previous binary or the complete new one.
+///
+/// On any failure the staging directory is removed and `destination` is left untouched.
+pub fn stage_and_promote(
+    from: &Path,
+    destination: &Path,
+    tool_name: &str,
+    move_source: bool,
+    tool_source: &str,
+) -> io::Result<()> {
+    let parent = destination.parent().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "Destination has no parent directory: {}",
+                destination.display()
+            ),
+        )
+    })?;
+    let file_name = destination.file_name().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Destination has no file name: {}", destination.display()),
+        )
+    })?;
+
+    let staging_root = parent.join(".sdb-staging");
+    let staging_dir = staging_root.join(tool_name);
+    // Leftovers from an interrupted earlier attempt are discarded
+    if staging_dir.exists() {
+        let _ = fs::remove_dir_all(&staging_dir);
+    }
+    fs::create_dir_all(&staging_dir)?;
+    let staged = staging_dir.join(file_name);
+
+    let result = (|| {
+        let expected_len = fs::metadata(from)?.len();
         log_debug!(
-            "[SDB::Tools::{tool_source}::BinaryInstaller] Copying from {} to {}",
+            "[SDB::Tools::{tool_source}::BinaryInstaller] Staging {} at {}",
             from.to_string_lossy().yellow(),
-            final_destination.to_string_lossy().cyan()
+            staged.to_string_lossy().cyan()
         );
+        if move_source {
+            match fs::rename(from, &staged) {
+                Ok(()) => {}
+                // The extraction directory may live on another filesystem
+                Err(e) if e.kind() == io::ErrorKind::CrossesDevices => {
+                    fs::copy(from, &staged)?;
+                    fs::remove_file(from)?;
+                }
+                Err(e) => return Err(e),
+            }
+        } else {
+            fs::copy(from, &staged)?;
+        }
 
-        fs::copy(from, &final_destination)?;
+        verify_staged_binary(&staged, expected_len)?;
+
+        #[cfg(unix)]
+        fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))?;
 
         log_debug!(
-            "[SDB::Tools::{tool_source}::BinaryInstaller] Binary copied successfully to {}",
-            final_destination.to_string_lossy().green()
-        );
-        Ok(())
-    } else {
-        log_debug!("[SDB::Tools::{tool_source}::BinaryInstaller] Performing rename/move operation");
-        log_debug!(
-            "[SDB::Tools::{tool_source}::BinaryInstaller] Attempting fs::rename from {} to {}",
-            from.to_string_lossy().yellow(),
-            final_destination.to_string_lossy().cyan()
+            "[SDB::Tools::{tool_source}::BinaryInstaller] Promoting {} to {}",
+            staged.to_string_lossy(),
+            destination.to_string_lossy().green()
         );
+        fs::rename(&staged, destination)
+    })();
 
-        // Perform the move operation using `fs::rename`.
-        match fs::rename(from, &final_destination) {
-            Ok(_) => {
-                log_debug!(
-                    "[SDB::Tools::{tool_source}::BinaryInstaller] Binary renamed/moved successfully to {}",
-                    final_destination.to_string_lossy().green()
-                );
-                Ok(())
-            }
-            // Handle the specific error case where `fs::rename` fails due to `CrossesDevices`.
-            Err(e) if e.kind() == io::ErrorKind::CrossesDevices => {
-                log_warn!(
-                    "[SDB::Tools::{tool_source}::BinaryInstaller] Cross-device link detected (error: {}), falling back to copy and remove",
-                    e
-                );
-                log_debug!(
-                    "[SDB::Tools::{tool_source}::BinaryInstaller] Fallback: copying from {} to {}",
-                    from.to_string_lossy().yellow(),
-                    final_destination.to_string_lossy().cyan()
-                );
+    // Clean up the staging area whether or not the promotion succeeded
+    let _ = fs::remove_dir_all(&staging_dir);
+    let _ = fs::remove_dir(&staging_root);
 
-                fs::copy(from, &final_destination)?;
-                log_debug!(
-                    "[SDB::Tools::{tool_source}::BinaryInstaller] Fallback: copy completed successfully"
-                );
-                log_debug!(
-                    "[SDB::Tools::{tool_source}::BinaryInstaller] Fallback: removing original file at {}",
-                    from.to_string_lossy()
-                );
+    if let Err(e) = &result {
+        log_error!(
+            "[SDB::Tools::{tool_source}::BinaryInstaller] Failed to install {} to {}: {}",
+            tool_name.red(),
+            destination.to_string_lossy(),
+            e
+        );
+    }
+    result
+}
 
-                fs::remove_file(from)?;
-                log_debug!(
-                    "[SDB::Tools::{tool_source}::BinaryInstaller] Fallback: original file removed from {}",
-                    from.to_string_lossy()
-                );
-                log_debug!(
-                    "[SDB::Tools::{tool_source}::BinaryInstaller] Binary copied and original removed successfully. Final location: {}",
-                    final_destination.to_string_lossy().green()
-                );
-                Ok(())
-            }
-            Err(e) => {
-                log_error!(
-                    "[SDB::Tools::{tool_source}::BinaryInstaller] Failed to rename binary from {} to {}: {} (error kind: {:?})",
-                    from.to_string_lossy(),
-                    final_destination.to_string_lossy(),
-                    e,
-                    e.kind()
-                );
-                Err(e)
-            }
-        }
+/// Checks that a staged binary was written completely before it is promoted.
+fn verify_staged_binary(staged: &Path, expected_len: u64) -> io::Result<()> {
+    let actual_len = fs::metadata(staged)?.len();
+    if actual_len == 0 || actual_len != expected_len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "Staged binary {} is incomplete ({actual_len} of {expected_len} bytes)",
+                staged.display()
+            ),
+        ));
     }
+    Ok(())
 }
 
 /// Makes a given file executable. On Unix-like systems, this is equivalent to `chmod +x file`.
@@ -686,12 +831,94 @@ pub fn make_executable(path: &Path, tool_entry: &ToolEntry, tool_source: String)
     Ok(())
 }
 
-// Provide a dummy implementation for `make_executable` on non-Unix systems to avoid compilation errors.
-// On Windows, executable permissions are often implicit for `.exe` files and not controlled by mode bits.
+// On Windows there are no permission bits: files run by their extension. Making the installed
+// tool runnable means checking it got its `.exe` name and putting the bin directory on `Path`.
 #[cfg(not(unix))]
-pub fn make_executable(_path: &Path) -> io::Result<()> {
+pub fn make_executable(path: &Path, tool_entry: &ToolEntry, tool_source: String) -> io::Result<()> {
+    let file_path = PathResolver::get_final_file_path(path, tool_entry);
+    if !file_path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "[SDB::Tools::{tool_source}::BinaryInstaller] File not found: {}",
+                file_path.display()
+            ),
+        ));
+    }
+    #[cfg(windows)]
+    windows::ensure_on_user_path(path);
     log_debug!(
-        "[Utils] `make_executable` is a no-op on this non-Unix platform (permissions handled differently)."
+        "[SDB::Tools::{tool_source}::BinaryInstaller] {} is ready to run",
+        file_path.to_string_lossy().green()
     );
-    Ok(()) // Return success, as no action is needed or possible on these platforms.
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_executable_skips_excluded_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("app-1.0");
+        fs::create_dir_all(root.join("bin")).unwrap();
+        fs::create_dir_all(root.join("jre/bin")).unwrap();
+        fs::write(root.join("bin/run"), "#!/bin/sh\nexec app\n").unwrap();
+        fs::write(
+            root.join("install.sh"),
+            format!("#!/bin/sh\n{}", "#".repeat(100)),
+        )
+        .unwrap();
+        fs::write(
+            root.join("jre/bin/java"),
+            format!("#!/bin/sh\n{}", "#".repeat(200)),
+        )
+        .unwrap();
+
+        let find = |exclude: &[String]| {
+            find_executable(
+                dir.path(),
+                "app",
+                None,
+                "Test".to_string(),
+                ExecutableDetection::Heuristic,
+                exclude,
+            )
+        };
+        // The largest script wins without exclusions
+        assert_eq!(find(&[]), Some(root.join("jre/bin/java")));
+        assert_eq!(
+            find(&["*/jre/bin/*".to_string(), "install.sh".to_string()]),
+            Some(root.join("bin/run"))
+        );
+    }
+
+    #[test]
+    fn test_stage_and_promote_replaces_destination() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("tool-new");
+        let destination = dir.path().join("bin").join("tool");
+        fs::create_dir_all(destination.parent().unwrap()).unwrap();
+        fs::write(&destination, b"old").unwrap();
+        fs::write(&source, b"new binary").unwrap();
+
+        stage_and_promote(&source, &destination, "tool", false, "Test").unwrap();
+
+        assert_eq!(fs::read(&destination).unwrap(), b"new binary");
+        assert!(source.exists());
+        assert!(!destination.parent().unwrap().join(".sdb-staging").exists());
+    }
+
+    #[test]
+    fn test_stage_and_promote_keeps_destination_on_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let destination = dir.path().join("tool");
+        fs::write(&destination, b"old").unwrap();
+
+        let missing = dir.path().join("missing");
+        assert!(stage_and_promote(&missing, &destination, "tool", true, "Test").is_err());
+        assert_eq!(fs::read(&destination).unwrap(), b"old");
+        assert!(!dir.path().join(".sdb-staging").exists());
+    }
 }
@@ -0,0 +1,268 @@
+//! # Checksum Verification
+//!
+//! Downloaded assets of `github` and `url` tools can be verified against a SHA-256 or SHA-512
+//! digest before they are extracted or installed. The digest comes from the entry's
+//! `checksum` field (`sha256:<hex>`, `sha512:<hex>` or bare hex), or, for GitHub releases,
+//! from a checksum file published next to the asset (`<asset>.sha256`, `checksums.txt`,
+//! `SHA256SUMS`, ...).
+//!
+//! Checksum files are read in the `sha256sum`/`sha512sum` format (`<hex>  <file name>` per
+//! line, optionally with a `*` before the name); a file holding a single bare digest is also
+//! accepted for per-asset sidecars.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use sha2::{Digest, Sha256, Sha512};
+
+use crate::core::assets::build_tool_request;
+use crate::core::downloads;
+use crate::schemas::tools_types::ToolEntry;
+
+/// Digest algorithms accepted in `checksum` and in checksum files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumAlgorithm {
+    Sha256,
+    Sha512,
+}
+
+impl ChecksumAlgorithm {
+    /// The algorithm whose hex digests have `len` characters.
+    fn from_hex_len(len: usize) -> Option<Self> {
+        match len {
+            64 => Some(Self::Sha256),
+            128 => Some(Self::Sha512),
+            _ => None,
+        }
+    }
+
+    fn hex_len(self) -> usize {
+        match self {
+            Self::Sha256 => 64,
+            Self::Sha512 => 128,
+        }
+    }
+}
+
+impl fmt::Display for ChecksumAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sha256 => write!(f, "sha256"),
+            Self::Sha512 => write!(f, "sha512"),
+        }
+    }
+}
+
+/// An expected digest of a downloaded file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checksum {
+    pub algorithm: ChecksumAlgorithm,
+    /// Lowercase hex digest.
+    pub hex: String,
+}
+
+impl Checksum {
+    /// Parses `sha256:<hex>`, `sha512:<hex>` or a bare hex digest, whose algorithm is
+    /// inferred from its length.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        let (algorithm, hex) = match spec.split_once(':') {
+            Some((name, hex)) => {
+                let algorithm = match name.trim().to_ascii_lowercase().as_str() {
+                    "sha256" => ChecksumAlgorithm::Sha256,
+                    "sha512" => ChecksumAlgorithm::Sha512,
+                    other => {
+                        return Err(format!(
+                            "unsupported checksum algorithm '{other}' (expected sha256 or sha512)"
+                        ));
+                    }
+                };
+                (algorithm, hex.trim())
+            }
+            None => {
+                let algorithm = ChecksumAlgorithm::from_hex_len(spec.len()).ok_or_else(|| {
+                    format!(
+                        "'{spec}' is not a sha256 or sha512 digest (expected 64 or 128 hexadecimal characters)"
+                    )
+                })?;
+                (algorithm, spec)
+            }
+        };
+        if hex.len() != algorithm.hex_len() || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!(
+                "'{hex}' is not a valid {algorithm} digest (expected {} hexadecimal characters)",
+                algorithm.hex_len()
+            ));
+        }
+        Ok(Self {
+            algorithm,
+            hex: hex.to_ascii_lowercase(),
+        })
+    }
+
+    /// Hashes the file at `path` and compares it with the expected digest.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The file matches
+    /// * `Err(String)` - The file could not be read or its digest differs
+    pub fn verify_file(&self, path: &Path) -> Result<(), String> {
+        let actual = self
+            .digest_file(path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        if actual == self.hex {
+            Ok(())
+        } else {
+            Err(format!(
+                "checksum mismatch for {}: expected {self}, got {}:{actual}",
+                path.display(),
+                self.algorithm
+            ))
+        }
+    }
+
+    fn digest_file(&self, path: &Path) -> io::Result<String> {
+        let mut file = File::open(path)?;
+        Ok(match self.algorithm {
+            ChecksumAlgorithm::Sha256 => {
+                let mut hasher = Sha256::new();
+                io::copy(&mut file, &mut hasher)?;
+                format!("{:x}", hasher.finalize())
+            }
+            ChecksumAlgorithm::Sha512 => {
+                let mut hasher = Sha512::new();
+                io::copy(&mut file, &mut hasher)?;
+                format!("{:x}", hasher.finalize())
+            }
+        })
+    }
+}
+
+impl fmt::Display for Checksum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.algorithm, self.hex)
+    }
+}
+
+/// Names of the per-asset checksum files a release may publish for `asset_name`, in order of
+/// preference.
+pub fn sidecar_names(asset_name: &str) -> [String; 4] {
+    [
+        format!("{asset_name}.sha256"),
+        format!("{asset_name}.sha256sum"),
+        format!("{asset_name}.sha512"),
+        format!("{asset_name}.sha512sum"),
+    ]
+}
+
+/// Whether `name` looks like a checksum file covering every asset of a release
+/// (`checksums.txt`, `tool_1.2.0_checksums.txt`, `SHA256SUMS`, `sha512sums.txt`, ...).
+pub fn is_checksum_listing(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    !name.ends_with(".sig")
+        && !name.ends_with(".asc")
+        && !name.ends_with(".pem")
+        && (name.contains("checksums")
+            || name.starts_with("sha256sums")
+            || name.starts_with("sha512sums"))
+}
+
+/// Finds the digest of `asset_name` in the contents of a checksum file.
+///
+/// Lines are `<hex> <file name>`, where the name may be prefixed with `*` (binary mode) or a
+/// relative directory. With `allow_bare` set (for per-asset sidecars), a line holding only
+/// a digest also matches.
+pub fn find_in_listing(listing: &str, asset_name: &str, allow_bare: bool) -> Option<Checksum> {
+    listing.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let hex = fields.next()?;
+        let matches = match fields.next() {
+            Some(name) => {
+                let name = name.trim_start_matches('*');
+                name.rsplit('/').next() == Some(asset_name)
+            }
+            None => allow_bare,
+        };
+        matches.then(|| Checksum::parse(hex).ok()).flatten()
+    })
+}
+
+/// Downloads a checksum file, authenticating like the tool's own downloads.
+pub fn fetch_listing(tool_entry: &ToolEntry, url: &str) -> io::Result<String> {
+    let request = build_tool_request(tool_entry, "GET", url).map_err(io::Error::other)?;
+    let response = downloads::send(request).map_err(io::Error::other)?;
+    let mut body = String::new();
+    response.into_reader().read_to_string(&mut body)?;
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const HELLO_SHA256: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+    #[test]
+    fn test_parse_prefixed_and_bare_digests() {
+        let prefixed = Checksum::parse(&format!("SHA256:{}", HELLO_SHA256.to_uppercase())).unwrap();
+        assert_eq!(prefixed.algorithm, ChecksumAlgorithm::Sha256);
+        assert_eq!(prefixed.hex, HELLO_SHA256);
+        assert_eq!(Checksum::parse(HELLO_SHA256).unwrap(), prefixed);
+
+        let sha512 = "a".repeat(128);
+        assert_eq!(
+            Checksum::parse(&sha512).unwrap().algorithm,
+            ChecksumAlgorithm::Sha512
+        );
+        assert!(Checksum::parse(&format!("sha512:{HELLO_SHA256}")).is_err());
+        assert!(Checksum::parse("md5:abc").is_err());
+        assert!(Checksum::parse(&"z".repeat(64)).is_err());
+    }
+
+    #[test]
+    fn test_verify_file() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"hello").unwrap();
+
+        let checksum = Checksum::parse(HELLO_SHA256).unwrap();
+        assert!(checksum.verify_file(file.path()).is_ok());
+        let wrong = Checksum::parse(&"0".repeat(64)).unwrap();
+        assert!(
+            wrong
+                .verify_file(file.path())
+                .unwrap_err()
+                .contains("mismatch")
+        );
+    }
+
+    #[test]
+    fn test_find_in_listing() {
+        let listing = format!(
+            "{}  tool_linux_amd64.tar.gz\n{HELLO_SHA256} *dist/tool_darwin_arm64.tar.gz\n",
+            "1".repeat(64)
+        );
+        assert_eq!(
+            find_in_listing(&listing, "tool_darwin_arm64.tar.gz", false)
+                .unwrap()
+                .hex,
+            HELLO_SHA256
+        );
+        assert!(find_in_listing(&listing, "tool_windows.zip", false).is_none());
+
+        let sidecar = format!("{HELLO_SHA256}\n");
+        assert!(find_in_listing(&sidecar, "tool.tar.gz", false).is_none());
+        assert!(find_in_listing(&sidecar, "tool.tar.gz", true).is_some());
+    }
+
+    #[test]
+    fn test_is_checksum_listing() {
+        assert!(is_checksum_listing("checksums.txt"));
+        assert!(is_checksum_listing("tool_1.2.0_checksums.txt"));
+        assert!(is_checksum_listing("SHA256SUMS"));
+        assert!(!is_checksum_listing("SHA256SUMS.sig"));
+        assert!(!is_checksum_listing("tool_linux_amd64.tar.gz"));
+    }
+}
@@ -21,11 +21,12 @@ use std::fs::File;
 // For working with file paths, specifically to construct installation paths.
 // `std::path::Path` is a powerful type for working with file paths in a robust way.
 // `std::path::PathBuf` provides an OS-agnostic way to build and manipulate file paths.
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 // To get environment variables, like the temporary directory or home directory.
 // `std::env` provides functions to interact with the process's environment.
 // `std::io` contains core input/output functionalities and error types.
 use std::io;
+use std::io::Read;
 // For extracting tar archives.
 // The `tar` crate provides functionality to read and write tar archives.
 pub(crate) use tar::Archive;
@@ -33,6 +34,7 @@ pub(crate) use tar::Archive;
 // The `zip` crate provides functionality to read and write zip archives.
 use xz2::read::XzDecoder;
 use zip::ZipArchive;
+use zstd::stream::read::Decoder as ZstdDecoder;
 
 /// Extracts the contents of a compressed archive (zip, tar.gz, etc.) into a new subdirectory
 /// within the specified destination path. This is a core utility for unpacking downloaded tools.
@@ -93,68 +95,41 @@ pub fn extract_archive(
             let file = File::open(src)?;
             // Create a new `ZipArchive` reader from the opened file.
             let mut archive = ZipArchive::new(file)?;
+            // Reject malicious entries (zip slip, device files) before anything is written.
+            validate_zip_entries(&mut archive, extracting)?;
             // Extract all contents of the zip archive into the `extracted_path`.
             archive.extract(&extracted_path)?;
             log_debug!("[SDB::{extracting}::Extractor] Zip archive extracted successfully.");
         }
-        "tar.gz" => {
-            // Handle specific `tar.gz` files.
-            // Open the gzipped tar file.
-            let tar_gz = File::open(src)?;
-            // Create a `GzDecoder` to decompress the gzip stream.
-            let decompressor = GzDecoder::new(tar_gz);
-            // Create a `tar::Archive` reader from the decompressed stream.
-            let mut archive = Archive::new(decompressor);
-            // Unpack all contents of the tar archive into the `extracted_path`.
-            archive.unpack(&extracted_path)?;
-            log_debug!("[SDB::{extracting}::Extractor] Tar.gz archive extracted successfully.");
-        }
-        "gz" => {
-            // Handle pure `.gz` files (not tarred, typically a single compressed file).
+        "gz" | "bz2" | "xz" | "zst" => {
+            // Handle plain compressed files (not tarred, typically a single compressed binary).
             log_info!(
-                "[SDB::{extracting}::Extractor] Decompressing plain GZ file. Contents will be the original file without tar extraction."
+                "[SDB::{extracting}::Extractor] Decompressing plain {} file. Contents will be the original file without tar extraction.",
+                file_type.to_uppercase()
             );
-            let gz_file = File::open(src)?;
-            let mut decompressor = GzDecoder::new(gz_file);
-            // Determine the output file path by removing the ".gz" extension from the source filename.
+            let compressed = File::open(src)?;
+            let mut decompressor: Box<dyn Read> = match file_type.as_str() {
+                "gz" => Box::new(GzDecoder::new(compressed)),
+                "bz2" => Box::new(BzDecoder::new(compressed)),
+                "xz" => Box::new(XzDecoder::new(compressed)),
+                _ => Box::new(ZstdDecoder::new(compressed)?),
+            };
+            // Determine the output file path by removing the compression extension from the source filename.
             let output_file_path = extracted_path.join(src.file_stem().unwrap_or_default());
             let mut output_file = File::create(&output_file_path)?;
-            // Copy the decompressed data from the `GzDecoder` to the new output file.
+            // Copy the decompressed data to the new output file.
             io::copy(&mut decompressor, &mut output_file)?;
             log_debug!(
-                "[SDB::{extracting}::Extractor] GZ file decompressed successfully to {:?}",
+                "[SDB::{extracting}::Extractor] {} file decompressed successfully to {:?}",
+                file_type.to_uppercase(),
                 output_file_path.display()
             );
         }
-        "tar.bz2" | "tar.bz" => {
-            // Open the bzipped tar file.
-            let tar_bz2 = File::open(src)?;
-            // Create a `BzDecoder` to decompress the bzip2 stream.
-            let decompressor = BzDecoder::new(tar_bz2);
-            // Create a `tar::Archive` reader from the decompressed stream.
-            let mut archive = Archive::new(decompressor);
-            // Unpack all contents of the tar archive into the `extracted_path`.
-            archive.unpack(&extracted_path)?;
-            log_debug!("[SDB::{extracting}::Extractor] Tar.bz2 archive extracted successfully.");
-        }
-        "tar" => {
-            // Handle plain `.tar` archives (uncompressed).
-            // Open the tar file.
-            let tar = File::open(src)?;
-            // Create a `tar::Archive` reader directly from the file.
-            let mut archive = Archive::new(tar);
-            // Unpack all contents of the tar archive into the `extracted_path`.
-            archive.unpack(&extracted_path)?;
-            log_debug!("[SDB::{extracting}::Extractor] Tar archive extracted successfully.");
-        }
-        "tar.xz" | "xz" | "txz" => {
-            // Added support for tar.xz and xz/txz aliases
-            log_debug!("[SDB::{extracting}::Extractor] Decompressing Tar.xz/XZ file.");
-            let tar_xz = File::open(src)?;
-            let decompressor = XzDecoder::new(tar_xz);
-            let mut archive = Archive::new(decompressor);
-            archive.unpack(&extracted_path)?;
-            log_debug!("[SDB::{extracting}::Extractor] Tar.xz archive extracted successfully.");
+        "tar.gz" | "tar.bz2" | "tar.bz" | "tar" | "tar.xz" | "txz" | "tbz2" | "tar.zst"
+        | "tzst" => {
+            // All tar-family archives share the same streaming unpacker; the file on disk
+            // is just another `Read` source for it.
+            unpack_tar_stream(File::open(src)?, &file_type, &extracted_path, extracting)?;
         }
         "binary" => {
             // For standalone binaries like a .exe or uncompressed Mac binary.
@@ -212,3 +187,324 @@ pub fn extract_archive(
     );
     Ok(extracted_path) // Return the path to the directory where contents were extracted.
 }
+
+/// Returns `true` when an archive of the given type can be unpacked in a single forward pass.
+///
+/// Tar-family archives (optionally wrapped in gzip, bzip2, xz or zstd) are read strictly sequentially,
+/// which means the HTTP response body can be piped straight through the decompressor into the
+/// extraction directory without ever landing on disk. Zip archives keep their central directory at
+/// the end of the file and therefore always require the buffered download path.
+pub fn is_streamable_archive(file_type: &str) -> bool {
+    matches!(
+        file_type,
+        "tar.gz" | "tar" | "tar.bz2" | "tar.bz" | "tbz2" | "tar.xz" | "txz" | "tar.zst" | "tzst"
+    )
+}
+
+/// Unpacks a tar-family archive from any `Read` source into `extracted_path`.
+///
+/// This is the shared pipeline behind both extraction modes:
+/// - **Buffered**: `extract_archive` passes an opened `File` from the temporary download.
+/// - **Streaming**: `assets::stream_url_asset` passes the live HTTP response body, so the
+///   archive is decompressed and written to the destination while it is being downloaded.
+///
+/// # Arguments
+/// * `reader`: The raw (still compressed) archive byte stream.
+/// * `file_type`: The archive type as returned by `detect_file_type` (e.g. "tar.gz", "tar.xz").
+/// * `extracted_path`: Existing directory the archive entries are unpacked into.
+/// * `extracting`: Component name used in log prefixes (e.g. "Tools", "Fonts").
+///
+/// # Returns
+/// * `io::Result<()>`: An `io::Error` if decompression or unpacking fails, or if `file_type`
+///   is not a tar-family archive.
+pub fn unpack_tar_stream<R: Read>(
+    reader: R,
+    file_type: &str,
+    extracted_path: &Path,
+    extracting: &str,
+) -> io::Result<()> {
+    match file_type {
+        "tar.gz" => {
+            // Create a `GzDecoder` to decompress the gzip stream and unpack the tar inside it.
+            safe_unpack(
+                Archive::new(GzDecoder::new(reader)),
+                extracted_path,
+                extracting,
+            )?;
+            log_debug!("[SDB::{extracting}::Extractor] Tar.gz archive extracted successfully.");
+        }
+        "tar.bz2" | "tar.bz" | "tbz2" => {
+            // Create a `BzDecoder` to decompress the bzip2 stream.
+            safe_unpack(
+                Archive::new(BzDecoder::new(reader)),
+                extracted_path,
+                extracting,
+            )?;
+            log_debug!("[SDB::{extracting}::Extractor] Tar.bz2 archive extracted successfully.");
+        }
+        "tar" => {
+            // Plain `.tar` archives (uncompressed) are unpacked directly.
+            safe_unpack(Archive::new(reader), extracted_path, extracting)?;
+            log_debug!("[SDB::{extracting}::Extractor] Tar archive extracted successfully.");
+        }
+        "tar.xz" | "txz" => {
+            // Added support for tar.xz and its txz alias
+            log_debug!("[SDB::{extracting}::Extractor] Decompressing Tar.xz file.");
+            safe_unpack(
+                Archive::new(XzDecoder::new(reader)),
+                extracted_path,
+                extracting,
+            )?;
+            log_debug!("[SDB::{extracting}::Extractor] Tar.xz archive extracted successfully.");
+        }
+        "tar.zst" | "tzst" => {
+            // Create a `ZstdDecoder` to decompress the Zstandard stream.
+            safe_unpack(
+                Archive::new(ZstdDecoder::new(reader)?),
+                extracted_path,
+                extracting,
+            )?;
+            log_debug!("[SDB::{extracting}::Extractor] Tar.zst archive extracted successfully.");
+        }
+        other => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("'{other}' is not a tar-family archive"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+//                       ARCHIVE ENTRY SAFETY CHECKS
+// ============================================================================
+
+/// Unpacks a tar archive entry by entry, rejecting anything that could escape `extracted_path`.
+///
+/// Assets can come from arbitrary URLs, so every entry is checked before it is written:
+/// - paths must be relative and must not climb out of the extraction directory (`../`),
+/// - symlink and hardlink targets must resolve inside the extraction directory,
+/// - character/block devices and FIFOs are refused outright.
+///
+/// # Returns
+/// * `io::Result<()>`: An `InvalidData` error naming the offending entry on the first violation.
+fn safe_unpack<R: Read>(
+    mut archive: Archive<R>,
+    extracted_path: &Path,
+    extracting: &str,
+) -> io::Result<()> {
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let entry_path = entry.path()?.into_owned();
+        let relative_path = sanitize_entry_path(&entry_path)
+            .map_err(|e| unsafe_entry_error(extracting, &entry_path, &e.to_string()))?;
+
+        let entry_type = entry.header().entry_type();
+        if entry_type.is_character_special()
+            || entry_type.is_block_special()
+            || entry_type.is_fifo()
+        {
+            return Err(unsafe_entry_error(
+                extracting,
+                &entry_path,
+                "device or special files are not allowed",
+            ));
+        }
+
+        if entry_type.is_symlink() || entry_type.is_hard_link() {
+            let target = entry.link_name()?.ok_or_else(|| {
+                unsafe_entry_error(extracting, &entry_path, "link entry has no target")
+            })?;
+            // Hard link targets are relative to the archive root, symlinks to the entry's parent.
+            let base = if entry_type.is_hard_link() {
+                Path::new("")
+            } else {
+                relative_path.parent().unwrap_or(Path::new(""))
+            };
+            validate_link_target(base, &target).map_err(|_| {
+                unsafe_entry_error(
+                    extracting,
+                    &entry_path,
+                    &format!(
+                        "link target '{}' points outside the extraction directory",
+                        target.display()
+                    ),
+                )
+            })?;
+        }
+
+        // `unpack_in` performs its own containment checks as a second line of defence.
+        entry.unpack_in(extracted_path)?;
+    }
+    Ok(())
+}
+
+/// Checks every zip entry before extraction for zip-slip paths and special files.
+fn validate_zip_entries<R: Read + io::Seek>(
+    archive: &mut ZipArchive<R>,
+    extracting: &str,
+) -> io::Result<()> {
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        let raw_name = PathBuf::from(file.name());
+        let relative_path = sanitize_entry_path(&raw_name)
+            .map_err(|e| unsafe_entry_error(extracting, &raw_name, &e.to_string()))?;
+
+        if let Some(mode) = file.unix_mode() {
+            match mode & 0o170000 {
+                // Character device, block device, FIFO and socket.
+                0o020000 | 0o060000 | 0o010000 | 0o140000 => {
+                    return Err(unsafe_entry_error(
+                        extracting,
+                        &raw_name,
+                        "device or special files are not allowed",
+                    ));
+                }
+                // Symbolic link: the entry content is the link target.
+                0o120000 => {
+                    let mut target = String::new();
+                    file.read_to_string(&mut target)?;
+                    let base = relative_path.parent().unwrap_or(Path::new(""));
+                    validate_link_target(base, Path::new(&target)).map_err(|_| {
+                        unsafe_entry_error(
+                            extracting,
+                            &raw_name,
+                            &format!(
+                                "link target '{target}' points outside the extraction directory"
+                            ),
+                        )
+                    })?;
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Normalizes an archive entry path and ensures it stays inside the extraction root.
+///
+/// `.` components are dropped and `..` components are resolved lexically. Absolute paths,
+/// Windows drive prefixes and any `..` that would climb above the root are rejected.
+///
+/// # Returns
+/// * `Ok(PathBuf)`: The normalized path relative to the extraction directory.
+/// * `Err(io::Error)`: With `InvalidData` if the path escapes the extraction directory.
+pub(crate) fn sanitize_entry_path(path: &Path) -> io::Result<PathBuf> {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => normalized.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "Archive entry '{}' escapes the extraction directory",
+                            path.display()
+                        ),
+                    ));
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Archive entry '{}' uses an absolute path", path.display()),
+                ));
+            }
+        }
+    }
+    Ok(normalized)
+}
+
+/// Ensures a link `target`, interpreted relative to `base`, resolves inside the extraction root.
+pub(crate) fn validate_link_target(base: &Path, target: &Path) -> io::Result<PathBuf> {
+    if target.is_absolute() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Link target '{}' is absolute", target.display()),
+        ));
+    }
+    sanitize_entry_path(&base.join(target))
+}
+
+/// Builds a descriptive error for a rejected archive entry and logs it.
+fn unsafe_entry_error(extracting: &str, entry_path: &Path, reason: &str) -> io::Error {
+    log_error!(
+        "[SDB::{extracting}::Extractor] Refusing to extract unsafe archive entry '{}': {}",
+        entry_path.display().to_string().red(),
+        reason
+    );
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!(
+            "Unsafe archive entry '{}': {}",
+            entry_path.display(),
+            reason
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_entry_path_rejects_traversal() {
+        assert!(sanitize_entry_path(Path::new("../etc/passwd")).is_err());
+        assert!(sanitize_entry_path(Path::new("bin/../../outside")).is_err());
+        assert!(sanitize_entry_path(Path::new("/etc/passwd")).is_err());
+        assert_eq!(
+            sanitize_entry_path(Path::new("./tool/bin/../bin/tool")).unwrap(),
+            PathBuf::from("tool/bin/tool")
+        );
+    }
+
+    /// A tar archive holding `tool/bin/tool` with `content`.
+    fn tar_with_tool(content: &[u8]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "tool/bin/tool", content)
+            .unwrap();
+        builder.into_inner().unwrap()
+    }
+
+    #[test]
+    fn test_extract_zstd_and_xz_archives() {
+        let dir = tempfile::tempdir().unwrap();
+        let tar = tar_with_tool(b"#!/bin/sh\n");
+
+        let tar_zst = dir.path().join("tool.tar.zst");
+        fs::write(&tar_zst, zstd::encode_all(&tar[..], 3).unwrap()).unwrap();
+        let extracted = extract_archive(&tar_zst, &dir.path().join("a"), None, "Tools").unwrap();
+        assert_eq!(
+            fs::read(extracted.join("tool/bin/tool")).unwrap(),
+            b"#!/bin/sh\n"
+        );
+
+        let zst = dir.path().join("tool.zst");
+        fs::write(&zst, zstd::encode_all(&b"binary"[..], 3).unwrap()).unwrap();
+        let extracted = extract_archive(&zst, &dir.path().join("b"), None, "Tools").unwrap();
+        assert_eq!(fs::read(extracted.join("tool")).unwrap(), b"binary");
+
+        let xz = dir.path().join("tool.xz");
+        let mut encoder = xz2::write::XzEncoder::new(Vec::new(), 6);
+        io::Write::write_all(&mut encoder, b"binary").unwrap();
+        fs::write(&xz, encoder.finish().unwrap()).unwrap();
+        let extracted = extract_archive(&xz, &dir.path().join("c"), None, "Tools").unwrap();
+        assert_eq!(fs::read(extracted.join("tool")).unwrap(), b"binary");
+    }
+
+    #[test]
+    fn test_validate_link_target() {
+        assert!(validate_link_target(Path::new("tool/bin"), Path::new("../lib/libx.so")).is_ok());
+        assert!(validate_link_target(Path::new("tool"), Path::new("../../etc/shadow")).is_err());
+        assert!(validate_link_target(Path::new("tool"), Path::new("/usr/bin/env")).is_err());
+    }
+}
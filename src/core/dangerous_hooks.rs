@@ -0,0 +1,154 @@
+//! # Dangerous Hook Commands
+//!
+//! Post-installation hooks run arbitrary shell commands, and shared team configurations are
+//! often applied without reading every hook. Entries whose hooks contain obviously
+//! destructive or risky patterns (`rm -rf /`, `curl ... | sh`, `sudo`, ...) fail validation
+//! unless the entry sets `acknowledge_dangerous: true` or the run passes
+//! `--allow-dangerous-hooks`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ALLOW_DANGEROUS_HOOKS: AtomicBool = AtomicBool::new(false);
+
+/// Allows hooks with dangerous patterns for the rest of the run (`--allow-dangerous-hooks`).
+pub fn configure(allowed: bool) {
+    ALLOW_DANGEROUS_HOOKS.store(allowed, Ordering::Relaxed);
+}
+
+/// Whether `--allow-dangerous-hooks` was passed for this run.
+pub fn is_allowed() -> bool {
+    ALLOW_DANGEROUS_HOOKS.load(Ordering::Relaxed)
+}
+
+/// Shells a downloaded script must not be piped into.
+const SHELLS: &[&str] = &["sh", "bash", "zsh", "fish", "dash", "ksh"];
+
+/// Paths whose recursive removal or permission change wipes the system or the home directory.
+const ROOT_PATHS: &[&str] = &[
+    "/",
+    "/*",
+    "~",
+    "~/",
+    "~/*",
+    "$HOME",
+    "$HOME/",
+    "$HOME/*",
+    "${HOME}",
+    "${HOME}/",
+    "${HOME}/*",
+];
+
+/// Why `command` looks dangerous, or `None` if it doesn't.
+///
+/// This is a coarse pattern check over the command's pipelines, not a shell parser: it
+/// catches the obvious cases and can be fooled by deliberate obfuscation.
+pub fn dangerous_reason(command: &str) -> Option<String> {
+    let compact: String = command.chars().filter(|c| !c.is_whitespace()).collect();
+    if compact.contains(":(){:|:&};:") {
+        return Some("fork bomb".to_string());
+    }
+
+    let normalized = command.replace("&&", ";").replace("||", ";");
+    for pipeline in normalized.split([';', '\n']) {
+        let stages: Vec<Vec<String>> = pipeline
+            .split('|')
+            .map(|stage| {
+                stage
+                    .split_whitespace()
+                    .map(|word| word.trim_matches(|c| c == '"' || c == '\'').to_string())
+                    .collect()
+            })
+            .collect();
+
+        for (index, words) in stages.iter().enumerate() {
+            let Some(program) = words.first().map(String::as_str) else {
+                continue;
+            };
+            let args = &words[1..];
+            if program == "sudo" {
+                return Some("runs a command with sudo".to_string());
+            }
+            if program == "rm"
+                && has_flag(args, 'r', "--recursive")
+                && has_flag(args, 'f', "--force")
+                && targets_root(args)
+            {
+                return Some(format!("removes {} recursively", root_target(args)));
+            }
+            if program == "chmod"
+                && has_flag(args, 'R', "--recursive")
+                && args.iter().any(|arg| arg == "777")
+                && targets_root(args)
+            {
+                return Some(format!("makes {} world-writable", root_target(args)));
+            }
+            if program.starts_with("mkfs") {
+                return Some("formats a file system".to_string());
+            }
+            if program == "dd" && args.iter().any(|arg| arg.starts_with("of=/dev/")) {
+                return Some("writes directly to a device".to_string());
+            }
+            if index > 0
+                && SHELLS.contains(&program)
+                && stages[..index].iter().any(|stage| {
+                    stage
+                        .first()
+                        .is_some_and(|first| first == "curl" || first == "wget")
+                })
+            {
+                return Some(format!("pipes a download into {program}"));
+            }
+        }
+    }
+    None
+}
+
+/// Whether `args` contain the option as a short flag, alone or combined (`-rf`), or long.
+fn has_flag(args: &[String], short: char, long: &str) -> bool {
+    args.iter().any(|arg| {
+        arg == long || (arg.starts_with('-') && !arg.starts_with("--") && arg.contains(short))
+    })
+}
+
+fn targets_root(args: &[String]) -> bool {
+    args.iter().any(|arg| ROOT_PATHS.contains(&arg.as_str()))
+}
+
+fn root_target(args: &[String]) -> &str {
+    args.iter()
+        .find(|arg| ROOT_PATHS.contains(&arg.as_str()))
+        .map_or("/", String::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dangerous_reason() {
+        for command in [
+            "rm -rf /",
+            "rm -r -f ~",
+            "cd /tmp && rm --recursive --force \"$HOME\"",
+            "curl -fsSL https://example.com/install.sh | sh",
+            "wget -qO- https://example.com/x | sudo bash",
+            "sudo make install",
+            "chmod -R 777 /",
+            "mkfs.ext4 /dev/sda1",
+            "dd if=/dev/zero of=/dev/sda",
+            ":(){ :|:& };:",
+        ] {
+            assert!(dangerous_reason(command).is_some(), "{command}");
+        }
+        for command in [
+            "rm -rf ./build",
+            "rm -rf ~/.cache/tool",
+            "curl -fsSL https://example.com/config -o ~/.toolrc",
+            "rg --version | grep ripgrep",
+            "chmod 755 ~/bin/tool",
+            "echo sudo",
+        ] {
+            assert!(dangerous_reason(command).is_none(), "{command}");
+        }
+    }
+}
@@ -0,0 +1,420 @@
+//! # Delta Updates
+//!
+//! Tools with `delta_updates: true` keep a copy of their last downloaded asset in
+//! `<cache_dir>/delta`. When a newer asset is published together with a zsync control file
+//! (`<asset>.zsync`), the new asset is assembled from the blocks it shares with the cached
+//! copy, and only the changed blocks are fetched with HTTP range requests. This saves most of
+//! the download for large, frequently updated tools such as editors.
+//!
+//! Any problem (no `.zsync`, no cached copy, no range support, checksum mismatch) makes the
+//! caller fall back to a full download.
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use colored::Colorize;
+use md4::Md4;
+use sha1::{Digest, Sha1};
+
+use crate::core::assets::{ResponseValidators, build_tool_request, url_path};
+use crate::core::downloads;
+use crate::fonts::info::format_size;
+use crate::schemas::tools_types::ToolEntry;
+use crate::{log_debug, log_info};
+
+static SEED_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Missing blocks closer than this are fetched in one range request.
+const MAX_RANGE_GAP_BLOCKS: usize = 4;
+
+/// Enables delta updates for this run, keeping cached assets under `<cache_dir>/delta`.
+pub fn configure(cache_dir: &Path) {
+    let _ = SEED_DIR.set(seed_dir(cache_dir));
+}
+
+/// Directory of the cached assets under `cache_dir`, one file per tool name.
+pub fn seed_dir(cache_dir: &Path) -> PathBuf {
+    cache_dir.join("delta")
+}
+
+/// Cached asset of the tool, once delta updates are enabled for this run.
+pub fn seed_path(tool_entry: &ToolEntry) -> Option<PathBuf> {
+    Some(SEED_DIR.get()?.join(&tool_entry.name))
+}
+
+/// Keeps `downloaded` as the base for the tool's next delta update.
+pub fn remember(tool_entry: &ToolEntry, downloaded: &Path) {
+    let Some(seed) = seed_path(tool_entry) else {
+        return;
+    };
+    let result = seed
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::copy(downloaded, &seed));
+    if let Err(e) = result {
+        log_debug!(
+            "[SDB::Delta] Failed to cache {} for delta updates: {}",
+            downloaded.display(),
+            e
+        );
+    }
+}
+
+/// Tries to assemble the asset at `url` into `dest` from the cached previous download and the
+/// release's `.zsync` file.
+///
+/// # Returns
+///
+/// * `Some(validators)` - `dest` holds the new asset, verified against the zsync SHA-1
+/// * `None` - Delta update not possible; download the asset in full
+pub fn download(tool_entry: &ToolEntry, url: &str, dest: &Path) -> Option<ResponseValidators> {
+    let seed = seed_path(tool_entry).filter(|seed| seed.is_file())?;
+    let zsync_url = format!("{}.zsync", url_path(url));
+    let control = match fetch(tool_entry, &zsync_url, None) {
+        Ok((body, _)) => body,
+        Err(e) => {
+            log_debug!("[SDB::Delta] No zsync file at {}: {}", zsync_url, e);
+            return None;
+        }
+    };
+    let control = match ZsyncControl::parse(&control) {
+        Ok(control) => control,
+        Err(e) => {
+            log_debug!("[SDB::Delta] Unusable zsync file {}: {}", zsync_url, e);
+            return None;
+        }
+    };
+
+    log_info!(
+        "[SDB::Delta] Updating {} from the previous download using {}",
+        tool_entry.name.green(),
+        zsync_url.cyan()
+    );
+    match assemble(tool_entry, url, &control, &seed, dest) {
+        Ok((fetched, validators)) => {
+            log_info!(
+                "[SDB::Delta] Reused {} of {}, downloaded {}",
+                format_size(control.length.saturating_sub(fetched)).green(),
+                format_size(control.length),
+                format_size(fetched)
+            );
+            Some(validators)
+        }
+        Err(e) => {
+            log_info!(
+                "[SDB::Delta] Delta update of {} failed ({}), downloading it in full",
+                tool_entry.name,
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Writes the blocks found in `seed` to `dest` and fetches the rest from `url`. Returns the
+/// number of bytes downloaded.
+fn assemble(
+    tool_entry: &ToolEntry,
+    url: &str,
+    control: &ZsyncControl,
+    seed: &Path,
+    dest: &Path,
+) -> io::Result<(u64, ResponseValidators)> {
+    let mut output = File::create(dest)?;
+    output.set_len(control.length)?;
+
+    let seed = fs::read(seed)?;
+    let mut have = vec![false; control.blocks.len()];
+    for (block, offset) in control.find_blocks(&seed) {
+        if !have[block] {
+            output.seek(SeekFrom::Start(control.block_offset(block)))?;
+            output.write_all(&seed[offset..offset + control.block_len(block)])?;
+            have[block] = true;
+        }
+    }
+    log_debug!(
+        "[SDB::Delta] {} of {} blocks found in the previous download",
+        have.iter().filter(|found| **found).count(),
+        have.len()
+    );
+
+    let mut fetched = 0;
+    let mut validators = ResponseValidators::default();
+    for (start, end) in missing_ranges(&have, control) {
+        let (body, range_validators) = fetch(tool_entry, url, Some((start, end)))?;
+        if body.len() as u64 != end - start + 1 {
+            return Err(io::Error::other(format!(
+                "range {start}-{end} returned {} bytes",
+                body.len()
+            )));
+        }
+        output.seek(SeekFrom::Start(start))?;
+        output.write_all(&body)?;
+        fetched += body.len() as u64;
+        validators = range_validators;
+    }
+    output.flush()?;
+    drop(output);
+
+    if let Some(expected) = &control.sha1 {
+        let actual = hex(&Sha1::digest(fs::read(dest)?));
+        if !actual.eq_ignore_ascii_case(expected) {
+            return Err(io::Error::other(
+                "SHA-1 of the assembled file does not match",
+            ));
+        }
+    }
+    Ok((fetched, validators))
+}
+
+/// Byte ranges (inclusive) of the blocks still missing, merging ranges separated by small gaps.
+fn missing_ranges(have: &[bool], control: &ZsyncControl) -> Vec<(u64, u64)> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for block in (0..have.len()).filter(|&block| !have[block]) {
+        match ranges.last_mut() {
+            Some((_, last)) if block - *last <= MAX_RANGE_GAP_BLOCKS => *last = block,
+            _ => ranges.push((block, block)),
+        }
+    }
+    ranges
+        .into_iter()
+        .map(|(first, last)| {
+            (
+                control.block_offset(first),
+                control.block_offset(last) + control.block_len(last) as u64 - 1,
+            )
+        })
+        .collect()
+}
+
+/// GETs `url` (or an inclusive byte range of it) with the tool's headers and credentials.
+fn fetch(
+    tool_entry: &ToolEntry,
+    url: &str,
+    range: Option<(u64, u64)>,
+) -> io::Result<(Vec<u8>, ResponseValidators)> {
+    let mut request = build_tool_request(tool_entry, "GET", url).map_err(io::Error::other)?;
+    if let Some((start, end)) = range {
+        request = request.set("Range", &format!("bytes={start}-{end}"));
+    }
+    let response = downloads::send(request).map_err(io::Error::other)?;
+    if range.is_some() && response.status() != 206 {
+        return Err(io::Error::other(format!(
+            "server does not support range requests (HTTP {})",
+            response.status()
+        )));
+    }
+    let validators = ResponseValidators::from_response(&response);
+    let mut body = Vec::new();
+    response.into_reader().read_to_end(&mut body)?;
+    Ok((body, validators))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// The parts of a zsync control file needed to assemble the target file.
+#[derive(Debug)]
+struct ZsyncControl {
+    block_size: usize,
+    length: u64,
+    rsum_bytes: usize,
+    checksum_bytes: usize,
+    sha1: Option<String>,
+    /// Per block: the stored (truncated) rolling checksum and MD4 prefix.
+    blocks: Vec<(u32, Vec<u8>)>,
+}
+
+impl ZsyncControl {
+    fn parse(data: &[u8]) -> Result<Self, String> {
+        let header_end = data
+            .windows(2)
+            .position(|window| window == b"\n\n")
+            .ok_or("missing header")?;
+        let header = String::from_utf8_lossy(&data[..header_end]);
+        let mut fields = HashMap::new();
+        for line in header.lines() {
+            if let Some((key, value)) = line.split_once(':') {
+                fields.insert(key.trim().to_lowercase(), value.trim().to_string());
+            }
+        }
+        let field = |key: &str| fields.get(key).ok_or(format!("missing '{key}' header"));
+
+        let block_size: usize = field("blocksize")?.parse().map_err(|_| "bad Blocksize")?;
+        let length: u64 = field("length")?.parse().map_err(|_| "bad Length")?;
+        let lengths: Vec<usize> = field("hash-lengths")?
+            .split(',')
+            .map(|n| n.trim().parse().map_err(|_| "bad Hash-Lengths"))
+            .collect::<Result<_, _>>()?;
+        let [_, rsum_bytes, checksum_bytes] = lengths[..] else {
+            return Err("bad Hash-Lengths".to_string());
+        };
+        if block_size == 0 || !(1..=4).contains(&rsum_bytes) || !(1..=16).contains(&checksum_bytes)
+        {
+            return Err("unsupported block parameters".to_string());
+        }
+
+        let count = length.div_ceil(block_size as u64) as usize;
+        let record = rsum_bytes + checksum_bytes;
+        let checksums = &data[header_end + 2..];
+        if checksums.len() < count * record {
+            return Err("truncated block checksums".to_string());
+        }
+        let blocks = checksums
+            .chunks_exact(record)
+            .take(count)
+            .map(|chunk| {
+                let rsum = chunk[..rsum_bytes]
+                    .iter()
+                    .fold(0u32, |acc, byte| (acc << 8) | u32::from(*byte));
+                (rsum, chunk[rsum_bytes..].to_vec())
+            })
+            .collect();
+
+        Ok(Self {
+            block_size,
+            length,
+            rsum_bytes,
+            checksum_bytes,
+            sha1: fields.get("sha-1").cloned(),
+            blocks,
+        })
+    }
+
+    fn block_offset(&self, block: usize) -> u64 {
+        (block * self.block_size) as u64
+    }
+
+    fn block_len(&self, block: usize) -> usize {
+        (self.length - self.block_offset(block)).min(self.block_size as u64) as usize
+    }
+
+    /// The stored part of a rolling checksum: the last `rsum_bytes` bytes of `a` then `b`.
+    fn stored_rsum(&self, a: u16, b: u16) -> u32 {
+        let full = (u32::from(a) << 16) | u32::from(b);
+        match self.rsum_bytes {
+            4 => full,
+            bytes => full & ((1u32 << (bytes * 8)) - 1),
+        }
+    }
+
+    /// Scans `seed` with a rolling checksum and returns `(block, seed offset)` pairs whose
+    /// rolling checksum and MD4 prefix match a block of the target file. Only full-size
+    /// blocks are matched; a short final block is always downloaded.
+    fn find_blocks(&self, seed: &[u8]) -> Vec<(usize, usize)> {
+        let bs = self.block_size;
+        let mut by_rsum: HashMap<u32, Vec<usize>> = HashMap::new();
+        for (block, (rsum, _)) in self.blocks.iter().enumerate() {
+            if self.block_len(block) == bs {
+                by_rsum.entry(*rsum).or_default().push(block);
+            }
+        }
+
+        let mut found = Vec::new();
+        if seed.len() < bs || by_rsum.is_empty() {
+            return found;
+        }
+        let (mut a, mut b) = rsum(&seed[..bs]);
+        let mut offset = 0;
+        loop {
+            let mut matched = false;
+            if let Some(candidates) = by_rsum.get(&self.stored_rsum(a, b)) {
+                let digest = Md4::digest(&seed[offset..offset + bs]);
+                for &block in candidates {
+                    if digest[..self.checksum_bytes] == self.blocks[block].1[..] {
+                        found.push((block, offset));
+                        matched = true;
+                    }
+                }
+            }
+
+            if matched && offset + 2 * bs <= seed.len() {
+                offset += bs;
+                (a, b) = rsum(&seed[offset..offset + bs]);
+            } else if offset + bs < seed.len() {
+                let (old, new) = (seed[offset], seed[offset + bs]);
+                a = a.wrapping_sub(u16::from(old)).wrapping_add(u16::from(new));
+                b = b
+                    .wrapping_sub((bs as u16).wrapping_mul(u16::from(old)))
+                    .wrapping_add(a);
+                offset += 1;
+            } else {
+                break;
+            }
+        }
+        found
+    }
+}
+
+/// zsync's rolling checksum of a block: `a` is the byte sum, `b` weights each byte by its
+/// distance from the end of the block.
+fn rsum(block: &[u8]) -> (u16, u16) {
+    let len = block.len();
+    block
+        .iter()
+        .enumerate()
+        .fold((0u16, 0u16), |(a, b), (i, &byte)| {
+            (
+                a.wrapping_add(u16::from(byte)),
+                b.wrapping_add(((len - i) as u16).wrapping_mul(u16::from(byte))),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a zsync control file for `target` with full 4-byte rolling checksums.
+    fn control_for(target: &[u8], block_size: usize) -> Vec<u8> {
+        let mut data = format!(
+            "zsync: 0.6.2\nBlocksize: {block_size}\nLength: {}\nHash-Lengths: 1,4,8\nSHA-1: {}\n\n",
+            target.len(),
+            hex(&Sha1::digest(target))
+        )
+        .into_bytes();
+        for block in target.chunks(block_size) {
+            let mut padded = block.to_vec();
+            padded.resize(block_size, 0);
+            let (a, b) = rsum(&padded);
+            data.extend(a.to_be_bytes());
+            data.extend(b.to_be_bytes());
+            data.extend(&Md4::digest(&padded)[..8]);
+        }
+        data
+    }
+
+    #[test]
+    fn test_find_blocks_in_shifted_seed() {
+        let target: Vec<u8> = (0..64u32).flat_map(|i| (i * 7).to_le_bytes()).collect();
+        let control = ZsyncControl::parse(&control_for(&target, 16)).unwrap();
+        assert_eq!(control.blocks.len(), 16);
+
+        // The seed has 3 bytes inserted in front and the last block changed
+        let mut seed = vec![1, 2, 3];
+        seed.extend(&target[..240]);
+        seed.extend([0xff; 16]);
+        let found: Vec<usize> = control
+            .find_blocks(&seed)
+            .into_iter()
+            .map(|(block, offset)| {
+                assert_eq!(
+                    &seed[offset..offset + 16],
+                    &target[block * 16..block * 16 + 16]
+                );
+                block
+            })
+            .collect();
+        assert_eq!(found, (0..15).collect::<Vec<_>>());
+
+        let mut have = vec![false; 16];
+        for block in found {
+            have[block] = true;
+        }
+        assert_eq!(missing_ranges(&have, &control), vec![(240, 255)]);
+    }
+}
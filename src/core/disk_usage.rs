@@ -0,0 +1,107 @@
+//! # Disk Usage
+//!
+//! Measures the space installed tools and fonts take on disk. A tool accounts for its
+//! executable, the extra assets installed with it, its shim and the copy of its last
+//! download kept in the cache for delta updates; a font for its installed files. The size
+//! is recorded in the state file at installation and measured again by `setup-devbox du`.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use walkdir::WalkDir;
+
+use crate::schemas::state_file::{FontState, ToolState};
+
+/// Size in bytes of a file, or of all files below a directory. Missing paths count as 0.
+///
+/// A symlinked file counts as the file it points to, as Homebrew and `go install` link
+/// their executables into a shared `bin` directory.
+pub fn path_size(path: &Path) -> u64 {
+    match fs::metadata(path) {
+        Ok(metadata) if metadata.is_file() => metadata.len(),
+        Ok(_) => WalkDir::new(path)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter_map(|entry| entry.metadata().ok())
+            .filter(|metadata| metadata.is_file())
+            .map(|metadata| metadata.len())
+            .sum(),
+        Err(_) => 0,
+    }
+}
+
+/// Paths a tool occupies: executable (or the application bundle it links into), shim and its
+/// target, extra assets and `cached` (its delta update seed).
+pub fn tool_paths(tool_state: &ToolState, cached: Option<PathBuf>) -> BTreeSet<PathBuf> {
+    let executable = tool_state
+        .app_bundle
+        .as_ref()
+        .map_or(&tool_state.install_path, |bundle| &bundle.path);
+    let mut paths = BTreeSet::from([PathBuf::from(executable)]);
+    if let Some(shim) = &tool_state.shim {
+        paths.insert(PathBuf::from(&shim.path));
+        paths.insert(PathBuf::from(&shim.target));
+    }
+    for asset in tool_state.assets.iter().flatten() {
+        paths.extend(asset.installed_paths.iter().map(PathBuf::from));
+    }
+    paths.extend(cached);
+    paths
+}
+
+/// Bytes a tool currently occupies on disk (see `tool_paths`).
+pub fn tool_size(tool_state: &ToolState, cached: Option<PathBuf>) -> u64 {
+    tool_paths(tool_state, cached)
+        .iter()
+        .map(|path| path_size(path))
+        .sum()
+}
+
+/// Bytes the installed files of a font currently occupy on disk.
+pub fn font_size(font_state: &FontState) -> u64 {
+    font_state
+        .files
+        .iter()
+        .map(|file| path_size(Path::new(file)))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_tool_size_counts_executable_assets_and_cached_download() {
+        let dir = tempfile::tempdir().unwrap();
+        let executable = dir.path().join("bin/tool");
+        let man_dir = dir.path().join("share/man/man1");
+        let cached = dir.path().join("cache/tool.tar.gz");
+        for (path, bytes) in [
+            (&executable, 100),
+            (&man_dir.join("tool.1"), 20),
+            (&man_dir.join("tool-sub.1"), 10),
+            (&cached, 50),
+        ] {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, vec![0u8; bytes]).unwrap();
+        }
+        let tool_state: ToolState = serde_json::from_value(json!({
+            "version": "1.0.0",
+            "install_path": executable,
+            "installed_by_devbox": true,
+            "install_method": "github",
+            "package_type": "binary",
+            "assets": [{
+                "name": "man pages",
+                "url": "https://example.com/man.tar.gz",
+                "installed_paths": [man_dir, dir.path().join("share/missing")],
+            }],
+        }))
+        .unwrap();
+
+        assert_eq!(tool_size(&tool_state, None), 130);
+        assert_eq!(tool_size(&tool_state, Some(cached)), 180);
+    }
+}
@@ -0,0 +1,238 @@
+//! # Asset Downloads
+//!
+//! Sends download requests with redirect handling and failure classification on top of
+//! `ureq`:
+//!
+//! - Redirects are followed here instead of by `ureq`, so every hop can be checked. A
+//!   download starting at a trusted host (GitHub, or a host listed in
+//!   `allowed_redirect_hosts` in `tools.yaml`) may only be redirected to trusted hosts,
+//!   e.g. `github.com` to `objects.githubusercontent.com`. Credentials configured for the
+//!   tool are only sent to the host the download started at.
+//! - Failures are classified, so a missing asset (404) reads differently from GitHub's
+//!   download CDN failing mid-way, and transient failures (CDN and network errors,
+//!   truncated bodies) can be retried by the caller.
+
+use std::io;
+use std::sync::RwLock;
+
+use colored::Colorize;
+use thiserror::Error;
+use url::Url;
+
+use crate::log_debug;
+
+/// How often a download is attempted before giving up on transient failures.
+pub const DOWNLOAD_ATTEMPTS: u32 = 3;
+
+const MAX_REDIRECTS: usize = 10;
+
+/// Hosts GitHub serves releases from; always trusted.
+const TRUSTED_HOSTS: [&str; 3] = ["github.com", "*.github.com", "*.githubusercontent.com"];
+
+static EXTRA_TRUSTED_HOSTS: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// Why a download failed.
+#[derive(Debug, Error)]
+pub enum DownloadError {
+    #[error("{url} was not found (HTTP {status}); check the version/tag and asset name")]
+    NotFound { url: String, status: u16 },
+    #[error("{host} answered HTTP {status}")]
+    Http { host: String, status: u16 },
+    #[error(
+        "the download CDN {host} failed after redirecting ({detail}); the asset exists, this is usually temporary"
+    )]
+    Cdn { host: String, detail: String },
+    #[error("could not download from {host}: {detail}")]
+    Network { host: String, detail: String },
+    #[error("the download from {host} was cut off after {received} of {expected} bytes")]
+    Truncated {
+        host: String,
+        received: u64,
+        expected: u64,
+    },
+    #[error(
+        "refusing to follow the redirect from {from} to {to}, which is not an allowed host (see allowed_redirect_hosts)"
+    )]
+    RedirectNotAllowed { from: String, to: String },
+    #[error("{url} redirected more than {MAX_REDIRECTS} times")]
+    TooManyRedirects { url: String },
+    #[error("{0}")]
+    Io(#[from] io::Error),
+}
+
+impl DownloadError {
+    /// Whether trying again may succeed.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Cdn { .. } | Self::Network { .. } | Self::Truncated { .. } => true,
+            Self::Http { status, .. } => *status >= 500 || *status == 429,
+            _ => false,
+        }
+    }
+}
+
+/// Sets the hosts (exact names or `*.domain` wildcards) trusted as redirect targets for this
+/// run, replacing those of an earlier run.
+pub fn configure(allowed_redirect_hosts: Option<Vec<String>>) {
+    let hosts = allowed_redirect_hosts.unwrap_or_default();
+    if !hosts.is_empty() {
+        log_debug!(
+            "[SDB::Downloads] Additional trusted redirect hosts: {}",
+            hosts.join(", ")
+        );
+    }
+    *EXTRA_TRUSTED_HOSTS
+        .write()
+        .unwrap_or_else(|e| e.into_inner()) = hosts;
+}
+
+/// Sends a request, following redirects after checking each target.
+pub fn send(request: ureq::Request) -> Result<ureq::Response, DownloadError> {
+    let agent = ureq::AgentBuilder::new().redirects(0).build();
+    let mut url = Url::parse(request.url()).map_err(|e| DownloadError::Network {
+        host: request.url().to_string(),
+        detail: e.to_string(),
+    })?;
+    let origin = host_of(&url);
+    let mut current = rebuild(&agent, &request, request.method(), &url, true);
+
+    for _ in 0..=MAX_REDIRECTS {
+        let host = host_of(&url);
+        let redirected = host != origin;
+        let method = current.method().to_string();
+        let response = match current.call() {
+            Ok(response) => response,
+            Err(ureq::Error::Status(status, _)) => {
+                return Err(match status {
+                    404 | 410 => DownloadError::NotFound {
+                        url: url.to_string(),
+                        status,
+                    },
+                    500.. if redirected => DownloadError::Cdn {
+                        host,
+                        detail: format!("HTTP {status}"),
+                    },
+                    _ => DownloadError::Http { host, status },
+                });
+            }
+            Err(ureq::Error::Transport(transport)) if redirected => {
+                return Err(DownloadError::Cdn {
+                    host,
+                    detail: transport.to_string(),
+                });
+            }
+            Err(ureq::Error::Transport(transport)) => {
+                return Err(DownloadError::Network {
+                    host,
+                    detail: transport.to_string(),
+                });
+            }
+        };
+
+        let status = response.status();
+        let location = match response.header("Location") {
+            Some(location) if (300..400).contains(&status) && status != 304 => location,
+            _ => return Ok(response),
+        };
+        let next = url.join(location).map_err(|e| DownloadError::Network {
+            host: host.clone(),
+            detail: format!("invalid redirect location '{location}': {e}"),
+        })?;
+        let next_host = host_of(&next);
+        if next_host != host && is_trusted_host(&origin) && !is_trusted_host(&next_host) {
+            return Err(DownloadError::RedirectNotAllowed {
+                from: host,
+                to: next_host,
+            });
+        }
+        log_debug!(
+            "[SDB::Downloads] HTTP {} redirect to {}",
+            status,
+            next.as_str().blue()
+        );
+
+        // 303, and 301/302 in practice, turn the request into a GET
+        let method = if matches!(status, 307 | 308) {
+            method
+        } else {
+            "GET".to_string()
+        };
+        current = rebuild(&agent, &request, &method, &next, next_host == origin);
+        url = next;
+    }
+
+    Err(DownloadError::TooManyRedirects {
+        url: request.url().to_string(),
+    })
+}
+
+/// Recreates `original` for `url` on the non-redirecting agent. The tool's headers (which
+/// may carry credentials) are only kept for the host the download started at.
+fn rebuild(
+    agent: &ureq::Agent,
+    original: &ureq::Request,
+    method: &str,
+    url: &Url,
+    keep_headers: bool,
+) -> ureq::Request {
+    let mut request = agent.request(method, url.as_str());
+    for name in original.header_names() {
+        if keep_headers || name.eq_ignore_ascii_case("user-agent") {
+            for value in original.all(&name) {
+                request = request.set(&name, value);
+            }
+        }
+    }
+    request
+}
+
+fn host_of(url: &Url) -> String {
+    url.host_str().unwrap_or_default().to_lowercase()
+}
+
+/// Whether `host` is GitHub or listed in `allowed_redirect_hosts`.
+fn is_trusted_host(host: &str) -> bool {
+    let extra = EXTRA_TRUSTED_HOSTS
+        .read()
+        .unwrap_or_else(|e| e.into_inner());
+    TRUSTED_HOSTS
+        .iter()
+        .copied()
+        .chain(extra.iter().map(String::as_str))
+        .any(|pattern| host_matches(host, pattern))
+}
+
+fn host_matches(host: &str, pattern: &str) -> bool {
+    let pattern = pattern.trim().to_lowercase();
+    match pattern.strip_prefix("*.") {
+        Some(domain) => host
+            .strip_suffix(domain)
+            .is_some_and(|prefix| prefix.ends_with('.')),
+        None => host == pattern,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trusted_hosts() {
+        assert!(is_trusted_host("github.com"));
+        assert!(is_trusted_host("objects.githubusercontent.com"));
+        assert!(is_trusted_host("release-assets.githubusercontent.com"));
+        assert!(!is_trusted_host("githubusercontent.com.evil.example"));
+        assert!(!is_trusted_host("evilgithub.com"));
+        assert!(host_matches("cdn.example.com", "*.example.com"));
+        assert!(!host_matches("example.com", "*.example.com"));
+    }
+
+    #[test]
+    fn test_configure_replaces_trusted_hosts() {
+        configure(Some(vec!["*.mirror.example.org".to_string()]));
+        assert!(is_trusted_host("eu.mirror.example.org"));
+        configure(None);
+        assert!(!is_trusted_host("eu.mirror.example.org"));
+        assert!(is_trusted_host("github.com"));
+    }
+}
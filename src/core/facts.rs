@@ -0,0 +1,298 @@
+//! # Machine Facts
+//!
+//! Facts describe the machine a run happens on: `hostname`, `os`, `arch`, `cpu_count`,
+//! `ram_gb`, `virtualized`, `container`, `ci` and, when `vpn_check` is set in `config.yaml`,
+//! `vpn` (whether that command succeeds). They are gathered on first use, and again after a
+//! configuration with a different `vpn_check` is loaded, and are available to:
+//!
+//! - configuration files, as `{{ facts.hostname }}` placeholders rendered when loading;
+//! - `only_on` conditions of tools (`"!ci"`, `"ram_gb >= 32"`, `"os == macos"`);
+//! - hooks, as `SDB_FACT_<NAME>` environment variables.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::process::Command;
+use std::sync::{Arc, RwLock};
+
+use regex::Regex;
+
+use crate::core::platform::{detect_architecture, detect_os};
+use crate::log_debug;
+
+/// Facts as text, keyed by name; booleans are `true`/`false`.
+pub type Facts = BTreeMap<String, String>;
+
+static FACTS: RwLock<Option<Arc<Facts>>> = RwLock::new(None);
+static VPN_CHECK: RwLock<Option<String>> = RwLock::new(None);
+
+/// Environment variables set by CI services.
+const CI_ENV_VARS: [&str; 8] = [
+    "CI",
+    "GITHUB_ACTIONS",
+    "GITLAB_CI",
+    "BUILDKITE",
+    "CIRCLECI",
+    "JENKINS_URL",
+    "TF_BUILD",
+    "TEAMCITY_VERSION",
+];
+
+/// Hypervisor names found in DMI product or vendor strings.
+const HYPERVISORS: [&str; 8] = [
+    "virtualbox",
+    "vmware",
+    "kvm",
+    "qemu",
+    "xen",
+    "parallels",
+    "virtual machine",
+    "bochs",
+];
+
+/// Sets the command whose success means a corporate VPN is connected (`vpn_check`) of the
+/// `config.yaml` that was just loaded, replacing that of any configuration loaded before it
+/// (`None` removes the `vpn` fact).
+pub fn configure_vpn_check(command: Option<String>) {
+    let command = command.filter(|command| !command.trim().is_empty());
+    let mut vpn_check = VPN_CHECK.write().unwrap_or_else(|e| e.into_inner());
+    if *vpn_check != command {
+        *vpn_check = command;
+        // The `vpn` fact is gathered again with the new command
+        *FACTS.write().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+/// Facts of this machine, gathered on first use.
+pub fn facts() -> Arc<Facts> {
+    if let Some(facts) = FACTS.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        return Arc::clone(facts);
+    }
+    let mut cached = FACTS.write().unwrap_or_else(|e| e.into_inner());
+    Arc::clone(cached.get_or_insert_with(|| Arc::new(gather())))
+}
+
+/// `SDB_FACT_<NAME>` environment variables for hooks.
+pub fn env_vars() -> Vec<(String, String)> {
+    facts()
+        .iter()
+        .map(|(name, value)| (format!("SDB_FACT_{}", name.to_uppercase()), value.clone()))
+        .collect()
+}
+
+fn gather() -> Facts {
+    let mut facts = Facts::new();
+    facts.insert("hostname".to_string(), hostname());
+    facts.insert("os".to_string(), detect_os());
+    facts.insert("arch".to_string(), detect_architecture());
+    facts.insert(
+        "cpu_count".to_string(),
+        std::thread::available_parallelism()
+            .map_or(1, |count| count.get())
+            .to_string(),
+    );
+    if let Some(ram_gb) = total_ram_gb() {
+        facts.insert("ram_gb".to_string(), ram_gb.to_string());
+    }
+    facts.insert("virtualized".to_string(), is_virtualized().to_string());
+    facts.insert("container".to_string(), is_container().to_string());
+    facts.insert(
+        "ci".to_string(),
+        CI_ENV_VARS
+            .iter()
+            .any(|var| std::env::var(var).is_ok_and(|value| !value.is_empty() && value != "false"))
+            .to_string(),
+    );
+    let vpn_check = VPN_CHECK.read().unwrap_or_else(|e| e.into_inner()).clone();
+    if let Some(command) = vpn_check {
+        let connected = Command::new("sh")
+            .args(["-c", &command])
+            .output()
+            .is_ok_and(|output| output.status.success());
+        facts.insert("vpn".to_string(), connected.to_string());
+    }
+    log_debug!("[SDB::Facts] Gathered facts: {:?}", facts);
+    facts
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|output| !output.is_empty())
+}
+
+fn hostname() -> String {
+    command_output("hostname", &[])
+        .or_else(|| {
+            fs::read_to_string("/etc/hostname")
+                .ok()
+                .map(|name| name.trim().to_string())
+        })
+        .unwrap_or_default()
+}
+
+/// Installed memory in whole gigabytes, rounded up (the OS reserves part of it).
+fn total_ram_gb() -> Option<u64> {
+    let bytes = if cfg!(target_os = "macos") {
+        command_output("sysctl", &["-n", "hw.memsize"])?
+            .parse::<u64>()
+            .ok()?
+    } else {
+        let meminfo = fs::read_to_string("/proc/meminfo").ok()?;
+        let kilobytes = meminfo
+            .lines()
+            .find_map(|line| line.strip_prefix("MemTotal:"))?
+            .trim()
+            .trim_end_matches("kB")
+            .trim()
+            .parse::<u64>()
+            .ok()?;
+        kilobytes * 1024
+    };
+    Some(bytes.div_ceil(1024 * 1024 * 1024))
+}
+
+fn is_virtualized() -> bool {
+    if cfg!(target_os = "macos") {
+        return command_output("sysctl", &["-n", "kern.hv_vmm_present"]).as_deref() == Some("1");
+    }
+    let dmi = ["product_name", "sys_vendor"]
+        .iter()
+        .filter_map(|file| fs::read_to_string(format!("/sys/class/dmi/id/{file}")).ok())
+        .collect::<String>()
+        .to_lowercase();
+    HYPERVISORS.iter().any(|name| dmi.contains(name))
+        || fs::read_to_string("/proc/cpuinfo")
+            .is_ok_and(|cpuinfo| cpuinfo.split_whitespace().any(|flag| flag == "hypervisor"))
+}
+
+fn is_container() -> bool {
+    std::path::Path::new("/.dockerenv").exists()
+        || std::path::Path::new("/run/.containerenv").exists()
+}
+
+/// Replaces `{{ facts.<name> }}` placeholders in `text`. Unknown facts are reported as an error.
+pub fn render(text: &str, facts: &Facts) -> Result<String, String> {
+    let re = Regex::new(r"\{\{\s*facts\.([A-Za-z0-9_]+)\s*\}\}").expect("valid regex");
+    let mut unknown = None;
+    let rendered = re.replace_all(text, |captures: &regex::Captures| {
+        let name = &captures[1];
+        facts.get(name).cloned().unwrap_or_else(|| {
+            unknown.get_or_insert_with(|| name.to_string());
+            String::new()
+        })
+    });
+    match unknown {
+        Some(name) => Err(format!("unknown fact '{name}'")),
+        None => Ok(rendered.into_owned()),
+    }
+}
+
+/// Whether `text` contains `{{ facts.* }}` placeholders.
+pub fn has_placeholders(text: &str) -> bool {
+    text.contains("{{") && text.contains("facts.")
+}
+
+/// Evaluates an `only_on` condition against `facts`.
+///
+/// A condition is a fact name (true when the fact is `true`), a negated fact (`!ci`), or a
+/// comparison `<fact> <op> <value>` with `==`, `!=`, `>=`, `<=`, `>` or `<`. Values compare
+/// as numbers when both sides are numbers, and as case-insensitive text otherwise.
+pub fn evaluate(condition: &str, facts: &Facts) -> Result<bool, String> {
+    let condition = condition.trim();
+    let lookup = |name: &str| {
+        facts
+            .get(name)
+            .ok_or_else(|| format!("unknown fact '{name}' in condition '{condition}'"))
+    };
+
+    for op in ["==", "!=", ">=", "<=", ">", "<"] {
+        let Some((name, expected)) = condition.split_once(op) else {
+            continue;
+        };
+        let actual = lookup(name.trim())?;
+        let expected = expected.trim().trim_matches(|c| c == '"' || c == '\'');
+        let ordering = match (actual.parse::<f64>(), expected.parse::<f64>()) {
+            (Ok(actual), Ok(expected)) => actual.partial_cmp(&expected),
+            _ if matches!(op, "==" | "!=") => {
+                Some(actual.to_lowercase().cmp(&expected.to_lowercase()))
+            }
+            _ => {
+                return Err(format!(
+                    "'{condition}' compares non-numeric values with '{op}'"
+                ));
+            }
+        };
+        let Some(ordering) = ordering else {
+            return Ok(false);
+        };
+        return Ok(match op {
+            "==" => ordering.is_eq(),
+            "!=" => ordering.is_ne(),
+            ">=" => ordering.is_ge(),
+            "<=" => ordering.is_le(),
+            ">" => ordering.is_gt(),
+            _ => ordering.is_lt(),
+        });
+    }
+
+    match condition.strip_prefix('!') {
+        Some(name) => Ok(lookup(name.trim())? != "true"),
+        None => Ok(lookup(condition)? == "true"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Facts {
+        [
+            ("os", "macos"),
+            ("ram_gb", "32"),
+            ("ci", "false"),
+            ("virtualized", "true"),
+            ("hostname", "build-01"),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let facts = sample();
+        assert_eq!(evaluate("!ci", &facts), Ok(true));
+        assert_eq!(evaluate("virtualized", &facts), Ok(true));
+        assert_eq!(evaluate("ram_gb >= 32", &facts), Ok(true));
+        assert_eq!(evaluate("ram_gb > 32", &facts), Ok(false));
+        assert_eq!(evaluate("os == macOS", &facts), Ok(true));
+        assert_eq!(evaluate("os != 'linux'", &facts), Ok(true));
+        assert!(evaluate("gpu", &facts).is_err());
+        assert!(evaluate("os > linux", &facts).is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let facts = sample();
+        assert_eq!(
+            render("path: ~/work/{{ facts.hostname }}/{{facts.os}}", &facts),
+            Ok("path: ~/work/build-01/macos".to_string())
+        );
+        assert!(render("{{ facts.gpu }}", &facts).is_err());
+        assert_eq!(render("{{name}}", &facts), Ok("{{name}}".to_string()));
+    }
+
+    #[test]
+    fn test_vpn_fact_follows_the_loaded_vpn_check() {
+        let _config = crate::core::lifecycle_hooks::tests::HOOKS_LOCK.lock();
+        configure_vpn_check(Some("true".to_string()));
+        assert_eq!(facts().get("vpn").map(String::as_str), Some("true"));
+        configure_vpn_check(Some("false".to_string()));
+        assert_eq!(facts().get("vpn").map(String::as_str), Some("false"));
+        configure_vpn_check(None);
+        assert_eq!(facts().get("vpn"), None);
+    }
+}
@@ -0,0 +1,103 @@
+//! # Installer Versions
+//!
+//! Package-manager installers run an external command (`brew`, `cargo`, `go`, `pip3`,
+//! `rustup`, `uv`). Its version at install time is recorded in the state of every tool it
+//! installed, so a breakage can later be correlated with a toolchain upgrade, and `info`
+//! flags tools whose installer has moved to another major version since.
+
+use std::collections::HashMap;
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, OnceLock};
+
+use crate::core::platform::find_in_path;
+use crate::core::requirements::{extract_version, parse_version_parts};
+use crate::engine::installers::traits::Installer;
+use crate::log_debug;
+use crate::schemas::state_file::InstallerVersionState;
+
+/// Versions looked up in this run, keyed by command; `brew --version` alone takes a while.
+static VERSIONS: OnceLock<Mutex<HashMap<String, Option<String>>>> = OnceLock::new();
+
+/// The version an installer's command reports now, if it has a command.
+pub fn record(installer: &(dyn Installer + Send + Sync)) -> Option<InstallerVersionState> {
+    let command = installer.required_command()?;
+    let version = current(command)?;
+    Some(InstallerVersionState {
+        command: command.to_string(),
+        version,
+    })
+}
+
+/// First line `command --version` (or `command version`, for `go`) prints, or `None` when
+/// the command is missing or reports no version. Looked up once per run.
+pub fn version_line(command: &str) -> Option<String> {
+    let versions = VERSIONS.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(version) = versions.lock().ok()?.get(command) {
+        return version.clone();
+    }
+
+    let path = find_in_path(command)?;
+    let line = ["--version", "version"].iter().find_map(|arg| {
+        let output = Command::new(&path)
+            .arg(arg)
+            .stdin(Stdio::null())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())?;
+        // Some tools (older Pythons) print their version to stderr
+        let text = if output.stdout.is_empty() {
+            output.stderr
+        } else {
+            output.stdout
+        };
+        String::from_utf8_lossy(&text)
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(str::to_string)
+    });
+    log_debug!("[SDB::InstallerVersion] {} reports {:?}", command, line);
+    if let Ok(mut versions) = versions.lock() {
+        versions.insert(command.to_string(), line.clone());
+    }
+    line
+}
+
+/// The version number `command` reports now (`4.4.1` for `Homebrew 4.4.1`), or its whole
+/// version line when it contains no number.
+pub fn current(command: &str) -> Option<String> {
+    let line = version_line(command)?;
+    Some(extract_version(&line).unwrap_or(line))
+}
+
+/// Whether going from `recorded` to `current` is a major version change: a different major
+/// version, or a different minor version while the major version is 0 (`uv 0.4` -> `0.9`).
+/// Versions that are not dotted numbers are compared as text.
+pub fn is_major_change(recorded: &str, current: &str) -> bool {
+    match (significant_parts(recorded), significant_parts(current)) {
+        (Some(recorded), Some(current)) => recorded != current,
+        _ => recorded != current,
+    }
+}
+
+fn significant_parts(version: &str) -> Option<Vec<u64>> {
+    let parts = parse_version_parts(&extract_version(version)?)?;
+    let significant = if parts.first() == Some(&0) { 2 } else { 1 };
+    Some(parts.into_iter().take(significant).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_major_change() {
+        assert!(!is_major_change("4.4.1", "4.5.0"));
+        assert!(is_major_change("3.6.21", "4.0.0"));
+        assert!(is_major_change("0.4.30", "0.9.1"));
+        assert!(!is_major_change("0.4.1", "0.4.30"));
+        assert!(!is_major_change("1.82.0", "1.95.0"));
+        assert!(is_major_change("nightly", "stable"));
+        assert!(!is_major_change("go1.22.1", "go1.23.0"));
+    }
+}
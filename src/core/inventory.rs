@@ -0,0 +1,223 @@
+//! # Package Manager Inventory
+//!
+//! Before installing, the brew, cargo and pip installers check whether the package is already
+//! there, which took one `brew list`, `cargo install --list` or `pip show` per tool. At the
+//! start of a run the packages of every package manager the configuration uses are listed
+//! once, concurrently, and those checks are answered from the snapshot. Checks made after an
+//! installation still ask the package manager, since the snapshot predates it.
+
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::process::Command;
+use std::sync::Mutex;
+
+use colored::Colorize;
+use serde::Deserialize;
+
+use crate::engine::installers::pip;
+use crate::schemas::tools_enums::SourceType;
+use crate::{log_debug, log_info};
+
+/// Packages listed at the start of the current run. Cleared by [`reset_snapshot`] when a run
+/// starts, so a long-running process (the daemon) never answers from an earlier run's listing.
+static SNAPSHOT: Mutex<Option<HashMap<PackageManager, HashMap<String, String>>>> = Mutex::new(None);
+
+/// Package manager whose installed packages are listed in the snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PackageManager {
+    Brew,
+    Cargo,
+    Pip,
+}
+
+impl PackageManager {
+    /// The package manager installing tools of `source`, if it is one the snapshot covers.
+    pub fn of(source: &SourceType) -> Option<Self> {
+        match source {
+            SourceType::Brew => Some(PackageManager::Brew),
+            SourceType::Cargo => Some(PackageManager::Cargo),
+            SourceType::Pip => Some(PackageManager::Pip),
+            _ => None,
+        }
+    }
+
+    /// Lists the installed packages with their versions, keyed by normalized name.
+    fn list(self) -> Option<HashMap<String, String>> {
+        match self {
+            PackageManager::Brew => {
+                run("brew", &["list", "--versions"]).map(|out| parse_brew(&out))
+            }
+            PackageManager::Cargo => {
+                run("cargo", &["install", "--list"]).map(|out| parse_cargo(&out))
+            }
+            PackageManager::Pip => {
+                let (command, args) = pip::list_json_command()?;
+                run(&command, &args).and_then(|out| parse_pip(&out))
+            }
+        }
+    }
+}
+
+/// What the snapshot knows about a package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Lookup {
+    /// Installed at the given version.
+    Installed(String),
+    /// Not installed when the snapshot was taken.
+    Missing,
+    /// No snapshot of the package manager; ask it directly.
+    Unknown,
+}
+
+/// Lists the installed packages of `managers` concurrently, once per run. Package managers
+/// that can't be listed are left out, so their lookups fall back to querying them.
+pub fn take_snapshot(managers: &[PackageManager]) {
+    if managers.is_empty() || SNAPSHOT.lock().unwrap().is_some() {
+        return;
+    }
+    log_info!(
+        "[SDB::Inventory] Listing installed packages of {} package manager(s)",
+        managers.len().to_string().yellow()
+    );
+    let snapshot = std::thread::scope(|scope| {
+        let handles: Vec<_> = managers
+            .iter()
+            .map(|&manager| (manager, scope.spawn(move || manager.list())))
+            .collect();
+        handles
+            .into_iter()
+            .filter_map(|(manager, handle)| {
+                let packages = handle.join().ok().flatten();
+                if packages.is_none() {
+                    log_debug!("[SDB::Inventory] Could not list {:?} packages", manager);
+                }
+                Some(manager).zip(packages)
+            })
+            .collect()
+    });
+    *SNAPSHOT.lock().unwrap() = Some(snapshot);
+}
+
+/// Forgets the snapshot of the previous run. Called when a run starts.
+pub fn reset_snapshot() {
+    *SNAPSHOT.lock().unwrap() = None;
+}
+
+/// Looks up `name` in the snapshot of `manager`.
+pub fn lookup(manager: PackageManager, name: &str) -> Lookup {
+    let snapshot = SNAPSHOT.lock().unwrap();
+    let Some(packages) = snapshot
+        .as_ref()
+        .and_then(|snapshot| snapshot.get(&manager))
+    else {
+        return Lookup::Unknown;
+    };
+    match packages.get(&normalize(manager, name)) {
+        Some(version) => Lookup::Installed(version.clone()),
+        None => Lookup::Missing,
+    }
+}
+
+/// pip treats case, `_` and `.` as insignificant in names (PEP 503).
+fn normalize(manager: PackageManager, name: &str) -> String {
+    match manager {
+        PackageManager::Pip => name.to_lowercase().replace(['_', '.'], "-"),
+        _ => name.to_string(),
+    }
+}
+
+fn run<S: AsRef<OsStr>>(command: &str, args: &[S]) -> Option<String> {
+    let output = Command::new(command).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Parses `brew list --versions`: `<name> <version> [<older version>...]` per line.
+fn parse_brew(output: &str) -> HashMap<String, String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            Some((parts.next()?.to_string(), parts.next()?.to_string()))
+        })
+        .collect()
+}
+
+/// Parses `cargo install --list`: `<crate> v<version>[ (<source>)]:` followed by indented
+/// binary names.
+fn parse_cargo(output: &str) -> HashMap<String, String> {
+    output
+        .lines()
+        .filter(|line| !line.starts_with(char::is_whitespace))
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let name = parts.next()?;
+            let version = parts.next()?.trim_end_matches(':');
+            Some((name.to_string(), version.to_string()))
+        })
+        .collect()
+}
+
+/// Parses `pip list --format json`.
+fn parse_pip(output: &str) -> Option<HashMap<String, String>> {
+    #[derive(Deserialize)]
+    struct Package {
+        name: String,
+        version: String,
+    }
+
+    let packages: Vec<Package> = serde_json::from_str(output).ok()?;
+    Some(
+        packages
+            .into_iter()
+            .map(|package| {
+                (
+                    normalize(PackageManager::Pip, &package.name),
+                    package.version,
+                )
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_listings() {
+        let brew = parse_brew("git 2.45.0\npython@3.12 3.12.4 3.12.3\n");
+        assert_eq!(brew["python@3.12"], "3.12.4");
+
+        let cargo = parse_cargo(
+            "ripgrep v14.1.0:\n    rg\nuv v0.8.17 (https://github.com/astral-sh/uv#1a2b3c4d):\n    uv\n",
+        );
+        assert_eq!(cargo["ripgrep"], "v14.1.0");
+        assert_eq!(cargo["uv"], "v0.8.17");
+        assert_eq!(cargo.len(), 2);
+
+        let pip = parse_pip(r#"[{"name": "Pygments", "version": "2.18.0"}, {"name": "zope.interface", "version": "7.0"}]"#)
+            .unwrap();
+        assert_eq!(pip[&normalize(PackageManager::Pip, "pygments")], "2.18.0");
+        assert_eq!(
+            pip[&normalize(PackageManager::Pip, "zope_interface")],
+            "7.0"
+        );
+    }
+
+    #[test]
+    fn test_reset_snapshot_forgets_previous_run() {
+        let packages = HashMap::from([("ripgrep".to_string(), "v14.1.0".to_string())]);
+        *SNAPSHOT.lock().unwrap() = Some(HashMap::from([(PackageManager::Cargo, packages)]));
+        assert_eq!(
+            lookup(PackageManager::Cargo, "ripgrep"),
+            Lookup::Installed("v14.1.0".to_string())
+        );
+        assert_eq!(lookup(PackageManager::Cargo, "fd"), Lookup::Missing);
+
+        reset_snapshot();
+        assert_eq!(lookup(PackageManager::Cargo, "ripgrep"), Lookup::Unknown);
+    }
+}
@@ -0,0 +1,141 @@
+//! # Tool and Font Licenses
+//!
+//! Looks up the license of GitHub-hosted tools and fonts (recorded in state and listed by
+//! `setup-devbox licenses`) and asks for acceptance before installing entries marked with
+//! `requires_license_acceptance: true`. `now --accept-licenses` accepts without prompting,
+//! which is required in non-interactive runs.
+
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use colored::Colorize;
+use dialoguer::Confirm;
+use serde_json::Value;
+
+use crate::core::metadata_cache;
+use crate::core::timestamps::current_timestamp;
+use crate::schemas::state_file::LicenseState;
+use crate::{log_debug, log_info};
+
+static ACCEPT_LICENSES: AtomicBool = AtomicBool::new(false);
+
+/// Accepts all licenses that require acceptance for the rest of the run (`--accept-licenses`).
+pub fn set_accept_licenses(accepted: bool) {
+    ACCEPT_LICENSES.store(accepted, Ordering::Relaxed);
+}
+
+/// Fetches the license GitHub detected for a repository, if it has one.
+pub fn fetch_github_license(repo: &str) -> Option<LicenseState> {
+    let api_url = format!("https://api.github.com/repos/{repo}/license");
+    let body = match metadata_cache::get(&format!("github-license:{repo}"), &api_url) {
+        Ok(body) => body,
+        Err(e) => {
+            log_debug!("[SDB::Licenses] No license information for {repo}: {e}");
+            return None;
+        }
+    };
+    let json: Value = serde_json::from_str(&body).ok()?;
+    let license = &json["license"];
+    Some(LicenseState {
+        name: license["name"].as_str()?.to_string(),
+        spdx_id: license["spdx_id"]
+            .as_str()
+            .filter(|id| *id != "NOASSERTION")
+            .map(str::to_string),
+        url: json["html_url"].as_str().map(str::to_string),
+        accepted_at: None,
+    })
+}
+
+/// Makes sure the license of `item` is accepted before it is installed.
+///
+/// A license accepted in an earlier run (same license name) is not asked for again.
+/// Returns the license to record in state, with its acceptance timestamp.
+pub fn ensure_accepted(
+    item: &str,
+    license: Option<LicenseState>,
+    previous: Option<&LicenseState>,
+) -> Result<LicenseState, String> {
+    let mut license = license.unwrap_or_else(|| LicenseState {
+        name: "unknown license".to_string(),
+        spdx_id: None,
+        url: None,
+        accepted_at: None,
+    });
+
+    if let Some(previous) = previous.filter(|previous| previous.name == license.name)
+        && let Some(accepted_at) = &previous.accepted_at
+    {
+        license.accepted_at = Some(accepted_at.clone());
+        return Ok(license);
+    }
+
+    let description = match &license.url {
+        Some(url) => format!("{} ({})", license.name, url),
+        None => license.name.clone(),
+    };
+    let accepted = if ACCEPT_LICENSES.load(Ordering::Relaxed) {
+        log_info!(
+            "[SDB::Licenses] Accepting license of {} via --accept-licenses: {}",
+            item.bold(),
+            description
+        );
+        true
+    } else if std::io::stdin().is_terminal() {
+        Confirm::new()
+            .with_prompt(format!(
+                "{item} is distributed under {description}. Accept?"
+            ))
+            .default(false)
+            .interact()
+            .unwrap_or(false)
+    } else {
+        return Err(format!(
+            "{item} requires accepting its license ({description}); rerun with --accept-licenses"
+        ));
+    };
+
+    if !accepted {
+        return Err(format!(
+            "license of {item} ({description}) was not accepted"
+        ));
+    }
+    license.accepted_at = Some(current_timestamp());
+    Ok(license)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn license(name: &str, accepted_at: Option<&str>) -> LicenseState {
+        LicenseState {
+            name: name.to_string(),
+            spdx_id: None,
+            url: None,
+            accepted_at: accepted_at.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_acceptance_is_kept_until_the_license_changes() {
+        let previous = license("MIT License", Some("2026-01-01T00:00:00Z"));
+
+        // Same license as last run: accepted without asking, original timestamp kept
+        let kept = ensure_accepted("tool", Some(license("MIT License", None)), Some(&previous));
+        assert_eq!(kept.unwrap().accepted_at, previous.accepted_at);
+
+        // A changed license has to be accepted again
+        set_accept_licenses(true);
+        let changed = ensure_accepted(
+            "tool",
+            Some(license("Business Source License 1.1", None)),
+            Some(&previous),
+        )
+        .unwrap();
+        set_accept_licenses(false);
+        assert_eq!(changed.name, "Business Source License 1.1");
+        assert!(changed.accepted_at.is_some());
+        assert_ne!(changed.accepted_at, previous.accepted_at);
+    }
+}
@@ -0,0 +1,155 @@
+//! # Lifecycle Hooks
+//!
+//! `config.yaml` can list commands to run at fixed points of a `now` run: `before_run`,
+//! `after_run`, `after_tool_install` and `on_failure`. They chain custom steps to provisioning
+//! (reloading tmux, warming caches, posting to a chat channel) without patching the crate.
+//! Every command runs with `sh -c`, receives the run's context as `SDB_*` environment
+//! variables next to the `SDB_FACT_*` machine facts, and only warns when it fails.
+
+use std::fmt;
+use std::process::Command;
+use std::sync::RwLock;
+
+use colored::Colorize;
+
+use crate::core::{dangerous_hooks, facts};
+use crate::schemas::common::LifecycleHooks;
+use crate::{log_debug, log_info, log_warn};
+
+static HOOKS: RwLock<Option<LifecycleHooks>> = RwLock::new(None);
+
+/// Sets the lifecycle hooks of the `config.yaml` that was just loaded, replacing those of
+/// any configuration loaded before it (`None` clears them).
+pub fn configure(hooks: Option<LifecycleHooks>) {
+    *HOOKS.write().unwrap_or_else(|e| e.into_inner()) = hooks;
+}
+
+/// The commands currently configured for `event`.
+fn configured(event: LifecycleEvent) -> Vec<String> {
+    HOOKS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .map(|hooks| commands(hooks, event).to_vec())
+        .unwrap_or_default()
+}
+
+/// Point of a run at which hooks are run.
+#[derive(Debug, Clone, Copy)]
+pub enum LifecycleEvent {
+    BeforeRun,
+    AfterRun,
+    AfterToolInstall,
+    OnFailure,
+}
+
+impl fmt::Display for LifecycleEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LifecycleEvent::BeforeRun => write!(f, "before_run"),
+            LifecycleEvent::AfterRun => write!(f, "after_run"),
+            LifecycleEvent::AfterToolInstall => write!(f, "after_tool_install"),
+            LifecycleEvent::OnFailure => write!(f, "on_failure"),
+        }
+    }
+}
+
+fn commands(hooks: &LifecycleHooks, event: LifecycleEvent) -> &[String] {
+    match event {
+        LifecycleEvent::BeforeRun => &hooks.before_run,
+        LifecycleEvent::AfterRun => &hooks.after_run,
+        LifecycleEvent::AfterToolInstall => &hooks.after_tool_install,
+        LifecycleEvent::OnFailure => &hooks.on_failure,
+    }
+}
+
+/// Runs the hooks configured for `event`. `context` holds the event's environment
+/// variables; `SDB_EVENT` is always set. Commands that look dangerous are skipped unless
+/// `--allow-dangerous-hooks` was passed.
+pub fn run(event: LifecycleEvent, context: &[(&str, String)]) {
+    let commands = configured(event);
+    if commands.is_empty() {
+        return;
+    }
+
+    log_info!(
+        "[SDB::Hooks] Running {} {} hook(s)",
+        commands.len().to_string().yellow(),
+        event.to_string().cyan()
+    );
+    for command in &commands {
+        if !dangerous_hooks::is_allowed()
+            && let Some(reason) = dangerous_hooks::dangerous_reason(command)
+        {
+            log_warn!(
+                "[SDB::Hooks] Skipping {} hook '{}': it {} (pass --allow-dangerous-hooks to run it)",
+                event,
+                command.yellow(),
+                reason
+            );
+            continue;
+        }
+
+        log_debug!("[SDB::Hooks] {} hook: {}", event, command.dimmed());
+        let output = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .envs(facts::env_vars())
+            .env("SDB_EVENT", event.to_string())
+            .envs(context.iter().map(|(name, value)| (name, value)))
+            .output();
+        match output {
+            Ok(output) if output.status.success() => {
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                if !stdout.trim().is_empty() {
+                    log_debug!("[SDB::Hooks] Output: {}", stdout.trim().dimmed());
+                }
+            }
+            Ok(output) => log_warn!(
+                "[SDB::Hooks] {} hook '{}' failed with exit code {}: {}",
+                event,
+                command.yellow(),
+                output.status.code().unwrap_or(-1),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            Err(e) => log_warn!(
+                "[SDB::Hooks] Could not run {} hook '{}': {}",
+                event,
+                command.yellow(),
+                e
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Held by tests that load configurations, since they all replace the same hooks,
+    /// `arch_fallback` policy and `vpn_check` command.
+    pub(crate) static HOOKS_LOCK: Mutex<()> = Mutex::new(());
+
+    pub(crate) fn configured_after_run() -> Vec<String> {
+        configured(LifecycleEvent::AfterRun)
+    }
+
+    #[test]
+    fn test_configure_replaces_and_clears_hooks() {
+        let _hooks = HOOKS_LOCK.lock();
+        configure(Some(LifecycleHooks {
+            before_run: vec!["echo first".to_string()],
+            ..LifecycleHooks::default()
+        }));
+        configure(Some(LifecycleHooks {
+            before_run: vec!["echo second".to_string()],
+            ..LifecycleHooks::default()
+        }));
+        assert_eq!(configured(LifecycleEvent::BeforeRun), ["echo second"]);
+        assert!(configured(LifecycleEvent::OnFailure).is_empty());
+
+        configure(None);
+        assert!(configured(LifecycleEvent::BeforeRun).is_empty());
+    }
+}
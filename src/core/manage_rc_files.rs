@@ -54,6 +54,7 @@ pub fn source_rc_file(shell_type: &str, rc_path: &Path) -> Result<(), Box<dyn st
 /// # Supported Shells
 /// - "zsh" -> ~/.zshrc
 /// - "bash" -> ~/.bashrc
+/// - "fish" -> ~/.config/fish/config.fish
 /// - Others -> None (unsupported)
 pub fn get_rc_file(shell: &str) -> Option<PathBuf> {
     // Get the user's home directory - returns None if home directory cannot be determined
@@ -62,9 +63,10 @@ pub fn get_rc_file(shell: &str) -> Option<PathBuf> {
     let rc_file_name = match shell.to_lowercase().as_str() {
         "zsh" => ".zshrc",
         "bash" => ".bashrc",
+        "fish" => ".config/fish/config.fish",
         _ => {
             log_warn!(
-                "[SDB::ShellConfig] Unsupported shell '{}'. Only 'zsh' and 'bash' are supported.",
+                "[SDB::ShellConfig] Unsupported shell '{}'. Only 'zsh', 'bash' and 'fish' are supported.",
                 shell.red()
             );
             return None;
@@ -74,6 +76,28 @@ pub fn get_rc_file(shell: &str) -> Option<PathBuf> {
     Some(home_dir.join(rc_file_name))
 }
 
+/// Determines the profile file login shells read, for shells that have one.
+///
+/// # Supported Shells
+/// - "zsh" -> ~/.zprofile
+/// - "bash" -> the first existing of ~/.bash_profile, ~/.bash_login and ~/.profile, which is
+///   the only one bash reads; ~/.bash_profile if there is none
+/// - Others -> None (fish reads config.fish in every shell)
+pub fn get_profile_file(shell: &str) -> Option<PathBuf> {
+    let home_dir = dirs::home_dir()?;
+    match shell.to_lowercase().as_str() {
+        "zsh" => Some(home_dir.join(".zprofile")),
+        "bash" => Some(
+            [".bash_profile", ".bash_login", ".profile"]
+                .iter()
+                .map(|name| home_dir.join(name))
+                .find(|path| path.exists())
+                .unwrap_or_else(|| home_dir.join(".bash_profile")),
+        ),
+        _ => None,
+    }
+}
+
 /// Removes the RC file from the filesystem
 /// This is used during full regeneration when we want to start with a clean file
 ///
@@ -123,6 +147,11 @@ pub fn write_rc_file(rc_path: &Path, lines: &[String]) -> std::io::Result<()> {
         format!("{content}\n")
     };
 
+    // fish keeps its configuration in ~/.config/fish, which may not exist yet
+    if let Some(parent) = rc_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
     // Write the final content to the file, overwriting any existing content
     fs::write(rc_path, final_content)
 }
@@ -0,0 +1,330 @@
+//! # Upstream Metadata Cache
+//!
+//! Keeps upstream release metadata (GitHub release JSON, latest Homebrew and crates.io
+//! versions) on disk under `<cache_dir>/metadata`, so repeated runs within a short window
+//! do not query every upstream again.
+//!
+//! - Entries younger than the TTL (`metadata_cache_ttl` in `tools.yaml`, default 15 minutes)
+//!   are used as-is, except with `--update-latest`, which revalidates every entry.
+//! - Older HTTP entries are revalidated with their `ETag`; a `304 Not Modified` refreshes
+//!   the entry without downloading it again (and does not count against GitHub's rate limit).
+//! - When the upstream cannot be reached, an expired entry is used with a warning, so
+//!   offline runs keep working.
+//!
+//! The cache is only active after [`configure`] has been called for the current run.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use chrono::{Duration, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::schemas::tools_enums::SdbDuration;
+use crate::{log_debug, log_warn};
+
+/// How long cached metadata is used without asking the upstream, unless configured.
+const DEFAULT_TTL_MINUTES: i64 = 15;
+
+static CACHE: RwLock<Option<CacheSettings>> = RwLock::new(None);
+
+#[derive(Clone)]
+struct CacheSettings {
+    dir: PathBuf,
+    ttl: Duration,
+    /// Treat every entry as expired (`--update-latest`)
+    bypass_ttl: bool,
+}
+
+impl CacheSettings {
+    fn is_fresh(&self, entry: &CacheEntry) -> bool {
+        !self.bypass_ttl && entry.is_fresh(self.ttl)
+    }
+}
+
+/// A cached upstream response.
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    /// Cache key, e.g. `github:cli/cli@v2.50.0` (the file name is its hash).
+    key: String,
+    /// `ETag` returned with the response, used to revalidate expired entries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+    /// When the upstream last confirmed this response.
+    fetched_at: chrono::DateTime<Utc>,
+    body: String,
+}
+
+impl CacheEntry {
+    fn is_fresh(&self, ttl: Duration) -> bool {
+        Utc::now() - self.fetched_at < ttl
+    }
+
+    fn age(&self) -> String {
+        let age = Utc::now() - self.fetched_at;
+        if age.num_days() > 0 {
+            format!("{}d", age.num_days())
+        } else if age.num_hours() > 0 {
+            format!("{}h", age.num_hours())
+        } else {
+            format!("{}m", age.num_minutes())
+        }
+    }
+}
+
+/// Enables the cache for this run, storing entries under `<cache_dir>/metadata`. With
+/// `bypass_ttl` (`--update-latest`), fresh entries are revalidated with the upstream too.
+pub fn configure(cache_dir: &Path, ttl: Option<SdbDuration>, bypass_ttl: bool) {
+    let ttl = ttl.map_or(Duration::minutes(DEFAULT_TTL_MINUTES), |ttl| ttl.0);
+    log_debug!(
+        "[SDB::MetadataCache] Caching upstream metadata in {} (TTL {}s{})",
+        cache_dir.display(),
+        ttl.num_seconds(),
+        if bypass_ttl { ", bypassed" } else { "" }
+    );
+    *CACHE.write().unwrap_or_else(|e| e.into_inner()) = Some(CacheSettings {
+        dir: cache_dir.join("metadata"),
+        ttl,
+        bypass_ttl,
+    });
+}
+
+fn settings() -> Option<CacheSettings> {
+    CACHE.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Performs a GET request for upstream metadata through the cache and returns the body.
+pub fn get(key: &str, url: &str) -> Result<String, Box<ureq::Error>> {
+    match settings() {
+        Some(settings) => get_cached(&settings, key, url),
+        None => Ok(ureq::get(url)
+            .set("User-Agent", "setup-devbox")
+            .call()?
+            .into_string()
+            .map_err(ureq::Error::from)?),
+    }
+}
+
+fn get_cached(settings: &CacheSettings, key: &str, url: &str) -> Result<String, Box<ureq::Error>> {
+    let request = || ureq::get(url).set("User-Agent", "setup-devbox");
+    let cached = read_entry(settings, key);
+    if let Some(entry) = cached.as_ref().filter(|entry| settings.is_fresh(entry)) {
+        log_debug!("[SDB::MetadataCache] Using cached metadata for {key}");
+        return Ok(entry.body.clone());
+    }
+
+    let mut conditional = request();
+    if let Some(etag) = cached.as_ref().and_then(|entry| entry.etag.as_deref()) {
+        conditional = conditional.set("If-None-Match", etag);
+    }
+
+    match conditional.call() {
+        Ok(response) if response.status() == 304 => {
+            let mut entry = cached.expect("304 is only returned for conditional requests");
+            log_debug!("[SDB::MetadataCache] Metadata for {key} not modified");
+            entry.fetched_at = Utc::now();
+            write_entry(settings, &entry);
+            Ok(entry.body)
+        }
+        Ok(response) => {
+            let etag = response.header("ETag").map(str::to_string);
+            let body = response.into_string().map_err(ureq::Error::from)?;
+            write_entry(
+                settings,
+                &CacheEntry {
+                    key: key.to_string(),
+                    etag,
+                    fetched_at: Utc::now(),
+                    body: body.clone(),
+                },
+            );
+            Ok(body)
+        }
+        Err(ureq::Error::Transport(transport)) => match cached {
+            Some(entry) => {
+                warn_stale(key, &entry, &transport.to_string());
+                Ok(entry.body)
+            }
+            None => Err(Box::new(ureq::Error::Transport(transport))),
+        },
+        Err(e) => Err(Box::new(e)),
+    }
+}
+
+/// Returns a cached value computed by a command (e.g. `brew info`), recomputing it once the
+/// entry has expired. If the computation fails, an expired entry is returned instead.
+pub fn get_or_compute(key: &str, compute: impl FnOnce() -> Option<String>) -> Option<String> {
+    match settings() {
+        Some(settings) => compute_cached(&settings, key, compute),
+        None => compute(),
+    }
+}
+
+fn compute_cached(
+    settings: &CacheSettings,
+    key: &str,
+    compute: impl FnOnce() -> Option<String>,
+) -> Option<String> {
+    let cached = read_entry(settings, key);
+    if let Some(entry) = cached.as_ref().filter(|entry| settings.is_fresh(entry)) {
+        log_debug!("[SDB::MetadataCache] Using cached metadata for {key}");
+        return Some(entry.body.clone());
+    }
+
+    match compute() {
+        Some(value) => {
+            write_entry(
+                settings,
+                &CacheEntry {
+                    key: key.to_string(),
+                    etag: None,
+                    fetched_at: Utc::now(),
+                    body: value.clone(),
+                },
+            );
+            Some(value)
+        }
+        None => cached.map(|entry| {
+            warn_stale(key, &entry, "lookup failed");
+            entry.body
+        }),
+    }
+}
+
+fn warn_stale(key: &str, entry: &CacheEntry, reason: &str) {
+    log_warn!(
+        "[SDB::MetadataCache] Upstream unavailable for {} ({}), using cached metadata from {} ago",
+        key.yellow(),
+        reason,
+        entry.age()
+    );
+}
+
+fn entry_path(settings: &CacheSettings, key: &str) -> PathBuf {
+    let digest = Sha256::digest(key.as_bytes());
+    let name: String = digest.iter().map(|byte| format!("{byte:02x}")).collect();
+    settings.dir.join(format!("{name}.json"))
+}
+
+fn read_entry(settings: &CacheSettings, key: &str) -> Option<CacheEntry> {
+    let contents = fs::read_to_string(entry_path(settings, key)).ok()?;
+    serde_json::from_str::<CacheEntry>(&contents)
+        .ok()
+        .filter(|entry| entry.key == key)
+}
+
+/// Stores an entry. Failures only cost a future cache hit, so they are logged and ignored.
+fn write_entry(settings: &CacheSettings, entry: &CacheEntry) {
+    let path = entry_path(settings, &entry.key);
+    let result = fs::create_dir_all(&settings.dir)
+        .and_then(|_| serde_json::to_string(entry).map_err(std::io::Error::other))
+        .and_then(|json| fs::write(&path, json));
+    if let Err(e) = result {
+        log_debug!(
+            "[SDB::MetadataCache] Failed to write {}: {}",
+            path.display(),
+            e
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    fn settings(dir: &Path, bypass_ttl: bool) -> CacheSettings {
+        CacheSettings {
+            dir: dir.to_path_buf(),
+            ttl: Duration::minutes(DEFAULT_TTL_MINUTES),
+            bypass_ttl,
+        }
+    }
+
+    fn entry(key: &str, age: Duration) -> CacheEntry {
+        CacheEntry {
+            key: key.to_string(),
+            etag: Some("\"v1\"".to_string()),
+            fetched_at: Utc::now() - age,
+            body: "cached body".to_string(),
+        }
+    }
+
+    /// Answers each of `requests` connections with `304 Not Modified` and returns what
+    /// the client sent.
+    fn not_modified_server(requests: usize) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/release", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            (0..requests)
+                .map(|_| {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let mut request = Vec::new();
+                    let mut buffer = [0u8; 1024];
+                    while !request.ends_with(b"\r\n\r\n") {
+                        let read = stream.read(&mut buffer).unwrap();
+                        request.extend_from_slice(&buffer[..read]);
+                    }
+                    stream
+                        .write_all(b"HTTP/1.1 304 Not Modified\r\nContent-Length: 0\r\n\r\n")
+                        .unwrap();
+                    String::from_utf8_lossy(&request).to_lowercase()
+                })
+                .collect()
+        });
+        (url, server)
+    }
+
+    #[test]
+    fn test_fresh_entry_is_used_without_a_request() {
+        let dir = tempfile::tempdir().unwrap();
+        let settings = settings(dir.path(), false);
+        write_entry(&settings, &entry("github:a/b@v1", Duration::minutes(1)));
+
+        // Nothing listens on the discard port; a request would fail
+        let body = get_cached(&settings, "github:a/b@v1", "http://127.0.0.1:9/").unwrap();
+        assert_eq!(body, "cached body");
+    }
+
+    #[test]
+    fn test_expired_entry_is_revalidated_with_etag() {
+        let dir = tempfile::tempdir().unwrap();
+        let settings = settings(dir.path(), false);
+        write_entry(&settings, &entry("github:a/b@v1", Duration::hours(1)));
+        let (url, server) = not_modified_server(1);
+
+        let body = get_cached(&settings, "github:a/b@v1", &url).unwrap();
+        assert_eq!(body, "cached body");
+        assert!(server.join().unwrap()[0].contains("if-none-match: \"v1\""));
+        // The 304 restarts the TTL
+        let refreshed = read_entry(&settings, "github:a/b@v1").unwrap();
+        assert!(refreshed.is_fresh(settings.ttl));
+    }
+
+    #[test]
+    fn test_bypass_ttl_revalidates_fresh_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let settings = settings(dir.path(), true);
+        write_entry(&settings, &entry("github:a/b@latest", Duration::minutes(1)));
+        let (url, server) = not_modified_server(1);
+
+        let body = get_cached(&settings, "github:a/b@latest", &url).unwrap();
+        assert_eq!(body, "cached body");
+        assert_eq!(server.join().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_corrupt_entry_is_recomputed() {
+        let dir = tempfile::tempdir().unwrap();
+        let settings = settings(dir.path(), false);
+        fs::write(entry_path(&settings, "brew:jq"), "{ not json").unwrap();
+
+        let value = compute_cached(&settings, "brew:jq", || Some("1.7.1".to_string()));
+        assert_eq!(value.as_deref(), Some("1.7.1"));
+        assert_eq!(read_entry(&settings, "brew:jq").unwrap().body, "1.7.1");
+    }
+}
@@ -1,8 +1,34 @@
+pub mod app_bundle;
 pub mod assets;
 pub mod backup;
 pub mod binary;
+pub mod checksum;
 pub mod compression;
+pub mod dangerous_hooks;
+pub mod delta;
+pub mod disk_usage;
+pub mod downloads;
+pub mod facts;
+pub mod installer_version;
+pub mod inventory;
+pub mod licenses;
+pub mod lifecycle_hooks;
 pub mod manage_rc_files;
+pub mod metadata_cache;
+pub mod name_collisions;
+pub mod optimize_binary;
 pub mod osx_pkg;
+pub mod output;
+pub mod permissions;
 pub mod platform;
+pub mod presets;
+pub mod protected;
+pub mod renames;
+pub mod requirements;
+pub mod rollout;
+pub mod shims;
+pub mod smoke_test;
 pub mod timestamps;
+pub mod version_check;
+pub mod wasm;
+pub mod windows;
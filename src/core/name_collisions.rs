@@ -0,0 +1,162 @@
+//! # Executable Name Collisions
+//!
+//! A tool's `rename_to` may name an executable another installed tool already provides;
+//! installing it would silently overwrite the other tool's binary. Such an installation is
+//! refused with a list of free alternative names unless `now --force` is passed, in which
+//! case the takeover is recorded in the state file (`name_collisions`).
+
+use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use colored::Colorize;
+
+use crate::core::timestamps::current_timestamp;
+use crate::log_warn;
+use crate::schemas::state_file::{DevBoxState, NameCollision};
+use crate::schemas::tools_types::ToolEntry;
+
+static FORCE: AtomicBool = AtomicBool::new(false);
+
+/// Lets tools take over executable names of other tools for the rest of the run
+/// (`now --force`).
+pub fn set_force(force: bool) {
+    FORCE.store(force, Ordering::Relaxed);
+}
+
+/// Name of the executable a recorded tool installed.
+fn executable_of<'a>(name: &'a str, state: &'a DevBoxState) -> &'a str {
+    state
+        .tools
+        .get(name)
+        .and_then(|tool| tool.renamed_to.as_deref())
+        .unwrap_or(name)
+}
+
+/// The other installed tool whose executable `tool`'s `rename_to` would overwrite.
+pub fn find_collision(state: &DevBoxState, tool: &ToolEntry) -> Option<String> {
+    let executable = tool.rename_to.as_deref()?;
+    state
+        .tools
+        .keys()
+        .filter(|name| **name != tool.name)
+        .find(|name| executable_of(name, state) == executable)
+        .cloned()
+}
+
+/// Executable names for `tool` that no installed tool uses.
+fn alternatives(state: &DevBoxState, tool: &ToolEntry, executable: &str) -> Vec<String> {
+    let mut candidates = vec![
+        tool.name.clone(),
+        format!("{executable}-{}", tool.name),
+        format!("{executable}-{}", tool.source),
+    ];
+    candidates.dedup();
+    candidates
+        .into_iter()
+        .filter(|candidate| candidate != executable)
+        .filter(|candidate| {
+            !state
+                .tools
+                .keys()
+                .any(|name| executable_of(name, state) == candidate)
+        })
+        .collect()
+}
+
+/// Allows installing `tool` if its executable name is free, or if `--force` was passed, in
+/// which case the takeover of `existing`'s executable is recorded in `state`.
+///
+/// # Returns
+/// * `Ok(())` - No collision, or the collision was forced and recorded
+/// * `Err(String)` - The collision and alternative names to use instead
+pub fn authorize(state: &mut DevBoxState, tool: &ToolEntry) -> Result<(), String> {
+    let Some(existing) = find_collision(state, tool) else {
+        return Ok(());
+    };
+    let executable = tool.rename_to.clone().unwrap_or_default();
+
+    if !FORCE.load(Ordering::Relaxed) {
+        let suggestions = alternatives(state, tool, &executable);
+        let hint = if suggestions.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "; use another 'rename_to', e.g. {}",
+                suggestions
+                    .iter()
+                    .map(|name| format!("'{name}'"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        };
+        return Err(format!(
+            "'rename_to: {executable}' collides with the executable of '{existing}'{hint}, \
+             or pass --force to replace it"
+        ));
+    }
+
+    log_warn!(
+        "[SDB::Tools] {} replaces the '{}' executable of {}",
+        tool.name.bold(),
+        executable,
+        existing.bold()
+    );
+    state.name_collisions.push(NameCollision {
+        executable,
+        name: tool.name.clone(),
+        replaced: existing,
+        user: env::var("USER")
+            .or_else(|_| env::var("USERNAME"))
+            .unwrap_or_else(|_| "unknown".to_string()),
+        forced_at: current_timestamp(),
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_rename_to_may_not_take_over_other_executables_unless_forced() {
+        let mut state = DevBoxState::default();
+        state.tools.insert(
+            "ripgrep".to_string(),
+            serde_json::from_value(json!({
+                "version": "14.1.0",
+                "install_path": "/home/dev/bin/rg",
+                "installed_by_devbox": true,
+                "install_method": "github",
+                "package_type": "binary",
+                "renamed_to": "rg",
+            }))
+            .unwrap(),
+        );
+        let tool = |name: &str| ToolEntry {
+            name: name.to_string(),
+            rename_to: Some("rg".to_string()),
+            ..serde_yaml::from_str("name: x\nsource: cargo").unwrap()
+        };
+
+        // Re-installing the tool that owns the name is not a collision
+        assert_eq!(find_collision(&state, &tool("ripgrep")), None);
+        assert_eq!(
+            find_collision(&state, &tool("rg-fork")).as_deref(),
+            Some("ripgrep")
+        );
+
+        let refusal = authorize(&mut state, &tool("rg-fork")).unwrap_err();
+        assert!(refusal.contains("collides with the executable of 'ripgrep'"));
+        assert!(refusal.contains("'rg-fork', 'rg-rg-fork', 'rg-cargo'"));
+        assert!(state.name_collisions.is_empty());
+
+        set_force(true);
+        let forced = authorize(&mut state, &tool("rg-fork"));
+        set_force(false);
+        assert!(forced.is_ok());
+        assert_eq!(state.name_collisions.len(), 1);
+        assert_eq!(state.name_collisions[0].executable, "rg");
+        assert_eq!(state.name_collisions[0].replaced, "ripgrep");
+    }
+}
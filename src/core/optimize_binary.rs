@@ -0,0 +1,274 @@
+//! # Binary Optimization
+//!
+//! Tools with `optimize_binary: strip` or `optimize_binary: upx` have their downloaded
+//! executable shrunk right after it was installed: `strip -S` removes its debug symbols and
+//! `upx` compresses it. This saves disk space on small VMs and containers.
+//!
+//! Only native executables (ELF or Mach-O) of at least [`MIN_OPTIMIZE_SIZE`] are processed,
+//! and the result only replaces the executable when it is smaller. A missing `strip` or `upx`,
+//! or one that fails, leaves the executable as downloaded instead of failing the installation.
+
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use colored::Colorize;
+
+use crate::core::platform::find_in_path;
+use crate::fonts::info::format_size;
+use crate::schemas::state_file::OptimizedBinaryState;
+use crate::schemas::tools_enums::OptimizeBinary;
+use crate::schemas::tools_types::ToolEntry;
+use crate::state::receipts::sha256_file;
+use crate::{log_debug, log_info, log_warn};
+
+/// Executables smaller than this are left alone; there is little to save.
+pub const MIN_OPTIMIZE_SIZE: u64 = 1024 * 1024;
+
+/// Leading bytes of ELF and (thin or universal) Mach-O executables.
+const NATIVE_MAGIC: [[u8; 4]; 6] = [
+    [0x7f, b'E', b'L', b'F'],
+    [0xfe, 0xed, 0xfa, 0xce],
+    [0xfe, 0xed, 0xfa, 0xcf],
+    [0xce, 0xfa, 0xed, 0xfe],
+    [0xcf, 0xfa, 0xed, 0xfe],
+    [0xca, 0xfe, 0xba, 0xbe],
+];
+
+/// Applies the tool's `optimize_binary` to the executable installed at `install_path`.
+///
+/// # Returns
+///
+/// * `Some(OptimizedBinaryState)` - The executable was replaced by a smaller one
+/// * `None` - Nothing to do, nothing gained, or the optimization failed (logged as a warning)
+pub fn optimize(tool: &ToolEntry, install_path: &Path) -> Option<OptimizedBinaryState> {
+    let method = tool.optimize_binary.unwrap_or_default();
+    if method == OptimizeBinary::None {
+        return None;
+    }
+    match try_optimize(method, install_path) {
+        Ok(Some(optimized)) => {
+            log_info!(
+                "[SDB::Tools::Optimize] {} {} from {} to {}",
+                match method {
+                    OptimizeBinary::Upx => "Compressed",
+                    _ => "Stripped",
+                },
+                tool.name.bold(),
+                format_size(optimized.original_size),
+                format_size(optimized.optimized_size).green()
+            );
+            Some(optimized)
+        }
+        Ok(None) => None,
+        Err(error) => {
+            log_warn!(
+                "[SDB::Tools::Optimize] Keeping {} as downloaded: {}",
+                tool.name.yellow(),
+                error
+            );
+            None
+        }
+    }
+}
+
+fn try_optimize(
+    method: OptimizeBinary,
+    install_path: &Path,
+) -> Result<Option<OptimizedBinaryState>, String> {
+    // Package managers record package names, `.app` bundles are directories and the links of
+    // `app_bundle` tools point into signed bundles
+    let metadata = match fs::symlink_metadata(install_path) {
+        Ok(metadata) if install_path.is_absolute() && metadata.is_file() => metadata,
+        _ => {
+            log_debug!(
+                "[SDB::Tools::Optimize] {} is not a downloaded executable, skipping",
+                install_path.display()
+            );
+            return Ok(None);
+        }
+    };
+    if !is_native_executable(install_path) || metadata.len() < MIN_OPTIMIZE_SIZE {
+        log_debug!(
+            "[SDB::Tools::Optimize] {} is not a large native executable, skipping",
+            install_path.display()
+        );
+        return Ok(None);
+    }
+
+    let output_path = work_path(install_path);
+    let Some((program, args)) = command_args(method, install_path, &output_path) else {
+        return Ok(None);
+    };
+    let program_path =
+        find_in_path(program).ok_or_else(|| format!("'{program}' was not found in PATH"))?;
+    let original_sha256 =
+        sha256_file(install_path).map_err(|e| format!("failed to hash the executable: {e}"))?;
+
+    let _ = fs::remove_file(&output_path);
+    let output = Command::new(program_path)
+        .args(&args)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| format!("failed to run '{program}': {e}"))?;
+    if !output.status.success() {
+        let _ = fs::remove_file(&output_path);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "'{program}' exited with {}: {}",
+            output.status,
+            stderr.lines().last().unwrap_or_default().trim()
+        ));
+    }
+
+    let optimized_size = match fs::metadata(&output_path) {
+        Ok(optimized) => optimized.len(),
+        Err(e) => return Err(format!("'{program}' produced no output: {e}")),
+    };
+    if optimized_size >= metadata.len() {
+        let _ = fs::remove_file(&output_path);
+        log_info!(
+            "[SDB::Tools::Optimize] '{}' did not make {} smaller, keeping it as downloaded",
+            program,
+            install_path.display()
+        );
+        return Ok(None);
+    }
+
+    let replaced = fs::set_permissions(&output_path, metadata.permissions())
+        .and_then(|()| {
+            resign(method, &output_path);
+            sha256_file(&output_path)
+        })
+        .and_then(|sha256| fs::rename(&output_path, install_path).map(|()| sha256));
+    match replaced {
+        Ok(optimized_sha256) => Ok(Some(OptimizedBinaryState {
+            method,
+            original_size: metadata.len(),
+            original_sha256,
+            optimized_size,
+            optimized_sha256,
+        })),
+        Err(e) => {
+            let _ = fs::remove_file(&output_path);
+            Err(format!("failed to replace the executable: {e}"))
+        }
+    }
+}
+
+/// Whether the file starts like an ELF or Mach-O executable (scripts, `.wasm` modules and
+/// Java archives are not processed).
+fn is_native_executable(path: &Path) -> bool {
+    let mut magic = [0u8; 4];
+    File::open(path)
+        .and_then(|mut file| file.read_exact(&mut magic))
+        .is_ok_and(|()| NATIVE_MAGIC.contains(&magic))
+}
+
+/// Where the optimized copy is written before it replaces the executable: next to it, so the
+/// final rename stays on the same file system.
+fn work_path(install_path: &Path) -> PathBuf {
+    let file_name = install_path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    install_path.with_file_name(format!(".{file_name}.sdb-optimize"))
+}
+
+/// The program and arguments that write the optimized copy of `input` to `output`.
+fn command_args(
+    method: OptimizeBinary,
+    input: &Path,
+    output: &Path,
+) -> Option<(&'static str, Vec<OsString>)> {
+    let (program, flags): (&str, &[&str]) = match method {
+        OptimizeBinary::None => return None,
+        // `-S` only drops debug symbols, with both GNU binutils and the Xcode tools
+        OptimizeBinary::Strip => ("strip", &["-S"]),
+        OptimizeBinary::Upx => ("upx", &["-q"]),
+    };
+    let mut args: Vec<OsString> = flags.iter().map(OsString::from).collect();
+    args.push("-o".into());
+    args.push(output.into());
+    args.push(input.into());
+    Some((program, args))
+}
+
+/// Stripping invalidates the code signature of a Mach-O executable, which Apple silicon
+/// refuses to run; sign the stripped copy ad hoc again.
+fn resign(method: OptimizeBinary, path: &Path) {
+    if !cfg!(target_os = "macos") || method != OptimizeBinary::Strip {
+        return;
+    }
+    let signed = Command::new("codesign")
+        .args(["--force", "--sign", "-"])
+        .arg(path)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success());
+    if !signed {
+        log_warn!(
+            "[SDB::Tools::Optimize] Could not sign {} again after stripping it",
+            path.display()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_native_executable() {
+        let dir = tempfile::tempdir().unwrap();
+        let elf = dir.path().join("tool");
+        fs::write(&elf, b"\x7fELF\x02\x01\x01").unwrap();
+        let script = dir.path().join("tool.sh");
+        fs::write(&script, "#!/bin/sh\necho hi\n").unwrap();
+        let empty = dir.path().join("empty");
+        fs::write(&empty, "").unwrap();
+
+        assert!(is_native_executable(&elf));
+        assert!(!is_native_executable(&script));
+        assert!(!is_native_executable(&empty));
+    }
+
+    #[test]
+    fn test_command_args() {
+        let input = Path::new("/opt/bin/tool");
+        let output = work_path(input);
+        assert_eq!(output, Path::new("/opt/bin/.tool.sdb-optimize"));
+
+        let (program, args) = command_args(OptimizeBinary::Strip, input, &output).unwrap();
+        assert_eq!(program, "strip");
+        assert_eq!(
+            args,
+            ["-S", "-o", "/opt/bin/.tool.sdb-optimize", "/opt/bin/tool"].map(OsString::from)
+        );
+        let (program, args) = command_args(OptimizeBinary::Upx, input, &output).unwrap();
+        assert_eq!((program, args[0].as_os_str()), ("upx", "-q".as_ref()));
+        assert!(command_args(OptimizeBinary::None, input, &output).is_none());
+    }
+
+    #[test]
+    fn test_optimize_skips_scripts_and_small_executables() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("tool");
+        fs::write(&script, "#!/bin/sh\n").unwrap();
+        let small = dir.path().join("small");
+        fs::write(&small, b"\x7fELF").unwrap();
+
+        assert!(matches!(
+            try_optimize(OptimizeBinary::Strip, &script),
+            Ok(None)
+        ));
+        assert!(matches!(
+            try_optimize(OptimizeBinary::Upx, &small),
+            Ok(None)
+        ));
+        assert_eq!(fs::read(&small).unwrap(), b"\x7fELF");
+    }
+}
@@ -4,10 +4,12 @@
 
 #[cfg(target_os = "macos")]
 use std::ffi::OsStr;
+use std::path::Path;
+#[cfg(any(target_os = "macos", test))]
+use std::path::PathBuf;
+use std::process::Command;
 #[cfg(target_os = "macos")]
-use std::path::{Path, PathBuf};
-#[cfg(target_os = "macos")]
-use std::process::{Command, Stdio};
+use std::process::Stdio;
 #[cfg(target_os = "macos")]
 use std::{fs, io};
 
@@ -157,13 +159,16 @@ pub fn install_pkg(
 /// 2. Search for either a .pkg installer or a .app bundle within the mounted volume,
 ///    prioritizing .pkg if both are present.
 /// 3. If a .pkg is found, it calls `install_pkg` to install it.
-/// 4. If a .app is found, it's copied to the `/Applications` directory.
+/// 4. If a .app is found, it's copied to `app_dir` (`/Applications` by default), using
+///    `sudo` only when that directory is not writable by the current user.
 /// 5. Unmount the .dmg file, **reliably**, regardless of installation success or failure.
 ///
 /// # Arguments
 /// * `dmg_path`: The path to the .dmg file.
-/// * `app_name`: The expected name of the application (e.g., "App") to correctly
-///   find and copy the `.app` bundle (e.g., "App.app").
+/// * `app_name`: The expected name of the application (e.g., "App"), used to pick the
+///   `.app` bundle (e.g., "App.app") when the image holds more than one.
+/// * `tool_renamed_to`: Name the bundle is installed under instead of its own.
+/// * `app_dir`: Directory the `.app` bundle is copied to.
 ///
 /// # Returns
 /// * `io::Result<PathBuf>`: `Ok(PathBuf)` if the DMG was processed successfully,
@@ -174,6 +179,7 @@ pub fn install_dmg(
     tool_source: &str,
     app_name: &str,
     tool_renamed_to: &Option<String>,
+    app_dir: Option<&Path>,
 ) -> io::Result<PathBuf> {
     log_info!(
         "[SDB::Tools::{tool_source}::MacInstaller] Initiating .dmg installation for: {}",
@@ -255,7 +261,7 @@ pub fn install_dmg(
     let install_result: io::Result<PathBuf> = (|| {
         // Changed closure return type to PathBuf
         let mut pkg_found: Option<PathBuf> = None;
-        let mut app_found: Option<PathBuf> = None;
+        let mut apps_found: Vec<PathBuf> = Vec::new();
 
         for entry in fs::read_dir(&mounted_volume_path)? {
             let entry = entry?;
@@ -264,9 +270,18 @@ pub fn install_dmg(
                 pkg_found = Some(path);
                 break;
             } else if path.extension() == Some(OsStr::new("app")) {
-                app_found = Some(path);
+                apps_found.push(path);
             }
         }
+        // Prefer the bundle named after the tool when the image holds several
+        let app_found = apps_found
+            .iter()
+            .find(|path| {
+                path.file_stem()
+                    .is_some_and(|stem| stem.to_string_lossy().eq_ignore_ascii_case(app_name))
+            })
+            .or(apps_found.first())
+            .cloned();
 
         if let Some(pkg_path) = pkg_found {
             log_info!(
@@ -283,16 +298,20 @@ pub fn install_dmg(
                 "[SDB::Tools::{tool_source}::MacInstaller] Found .app bundle: {}",
                 app_path.display().to_string().bold()
             );
-            let target_app_path = PathBuf::from("/Applications").join(format!("{app_name}.app"));
+            let target_app_path = app_bundle_target(app_dir, &app_path, tool_renamed_to);
+            let target_dir = target_app_path
+                .parent()
+                .unwrap_or(Path::new(DEFAULT_APP_DIR))
+                .to_path_buf();
+            fs::create_dir_all(&target_dir)?;
 
             if target_app_path.exists() {
                 log_info!(
                     "[SDB::Tools::{tool_source}::MacInstaller] Removing existing app at: {}",
                     target_app_path.display().to_string().yellow()
                 );
-                // Use sudo rm -rf for permission issues
-                let rm_output = Command::new("sudo")
-                    .arg("rm")
+                // Use sudo rm -rf for directories the user cannot write to
+                let rm_output = command_for_dir(&target_dir, "rm")
                     .arg("-rf") // Force recursively delete
                     .arg(&target_app_path)
                     .stdout(Stdio::piped())
@@ -320,11 +339,10 @@ pub fn install_dmg(
                 "[SDB::Tools::{tool_source}::MacInstaller] Copying .app to: {}",
                 target_app_path.display()
             );
-            let cp_output = Command::new("sudo")
-                .arg("cp")
+            let cp_output = command_for_dir(&target_dir, "cp")
                 .arg("-R")
                 .arg(&app_path)
-                .arg(Path::new("/Applications"))
+                .arg(&target_app_path)
                 .stdout(Stdio::null())
                 .stderr(Stdio::piped())
                 .output()?;
@@ -332,11 +350,14 @@ pub fn install_dmg(
             if !cp_output.status.success() {
                 let stderr = String::from_utf8_lossy(&cp_output.stderr);
                 log_error!(
-                    "[SDB::Tools::{tool_source}::MacInstaller] Failed to copy {app_name}.app to /Applications: {}",
+                    "[SDB::Tools::{tool_source}::MacInstaller] Failed to copy {} to {}: {}",
+                    app_path.display(),
+                    target_dir.display(),
                     stderr.red()
                 );
                 return Err(std::io::Error::other(format!(
-                    "[SDB::Tools::{tool_source}::MacInstaller] Failed to copy {app_name}.app: {stderr}"
+                    "[SDB::Tools::{tool_source}::MacInstaller] Failed to copy {}: {stderr}",
+                    app_path.display()
                 )));
             }
             log_info!(
@@ -384,6 +405,37 @@ pub fn install_dmg(
     install_result
 }
 
+/// Directory `.app` bundles from a DMG are copied to unless a tool sets `app_dir`.
+pub const DEFAULT_APP_DIR: &str = "/Applications";
+
+/// Path a `.app` bundle copied out of a DMG is installed at: inside `app_dir` (or
+/// [`DEFAULT_APP_DIR`]), named after `tool_renamed_to` when set, otherwise after the bundle.
+#[cfg(any(target_os = "macos", test))]
+fn app_bundle_target(
+    app_dir: Option<&Path>,
+    bundle: &Path,
+    tool_renamed_to: &Option<String>,
+) -> PathBuf {
+    let dir = app_dir.unwrap_or(Path::new(DEFAULT_APP_DIR));
+    match tool_renamed_to {
+        Some(name) if name.ends_with(".app") => dir.join(name),
+        Some(name) => dir.join(format!("{name}.app")),
+        None => dir.join(bundle.file_name().unwrap_or(bundle.as_os_str())),
+    }
+}
+
+/// Builds a command running `program`, through `sudo` when the current user cannot
+/// write to `dir` (e.g. `/Applications` on managed machines).
+pub(crate) fn command_for_dir(dir: &Path, program: &str) -> Command {
+    if tempfile::tempfile_in(dir).is_ok() {
+        Command::new(program)
+    } else {
+        let mut command = Command::new("sudo");
+        command.arg(program);
+        command
+    }
+}
+
 /// Helper function to unmount a DMG.
 ///
 /// # Arguments
@@ -449,3 +501,25 @@ fn extract_mounted_path_from_hdiutil_plist(plist_output: &str) -> Option<String>
                 .map(|s| s.to_string())
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_app_bundle_target() {
+        let bundle = Path::new("/Volumes/Zed/Zed.app");
+        assert_eq!(
+            app_bundle_target(None, bundle, &None),
+            PathBuf::from("/Applications/Zed.app")
+        );
+        assert_eq!(
+            app_bundle_target(Some(Path::new("/Users/me/Applications")), bundle, &None),
+            PathBuf::from("/Users/me/Applications/Zed.app")
+        );
+        assert_eq!(
+            app_bundle_target(None, bundle, &Some("Zed Preview".to_string())),
+            PathBuf::from("/Applications/Zed Preview.app")
+        );
+    }
+}
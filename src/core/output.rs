@@ -0,0 +1,42 @@
+//! # Output Format
+//!
+//! With `--output json` (or `yaml`), commands print their result as a single document on
+//! stdout so wrapper scripts and editor integrations can consume it; log lines keep going to
+//! stderr.
+
+use std::sync::RwLock;
+
+use serde::Serialize;
+
+use crate::cli::type_enums::OutputFormat;
+use crate::log_error;
+
+static FORMAT: RwLock<OutputFormat> = RwLock::new(OutputFormat::Text);
+
+/// Sets how commands print their results for the rest of the run (`--output`).
+pub fn configure(format: OutputFormat) {
+    *FORMAT.write().unwrap_or_else(|e| e.into_inner()) = format;
+}
+
+/// Whether a JSON or YAML document was asked for instead of text output.
+pub fn is_structured() -> bool {
+    format() != OutputFormat::Text
+}
+
+fn format() -> OutputFormat {
+    *FORMAT.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Prints a command's result on stdout as a JSON or YAML document, per `--output`.
+pub fn print_document<T: Serialize>(result: &T) {
+    let rendered = match format() {
+        OutputFormat::Yaml => serde_yaml::to_string(result).map_err(|e| e.to_string()),
+        _ => serde_json::to_string_pretty(result)
+            .map(|json| json + "\n")
+            .map_err(|e| e.to_string()),
+    };
+    match rendered {
+        Ok(document) => print!("{document}"),
+        Err(e) => log_error!("[SDB::Output] Failed to serialize the result: {}", e),
+    }
+}
@@ -0,0 +1,154 @@
+//! # macOS Privacy Permissions
+//!
+//! Terminal emulators, window managers and similar tools only work once the user grants them
+//! a privacy permission (Full Disk Access, Screen Recording, Accessibility, ...) in System
+//! Settings, which no installer can do for them. Entries declare what they need with
+//! `permissions:`; after installing, each pane not yet acknowledged is opened and the user is
+//! asked to confirm the grant, which is recorded in state so it is not asked for again.
+
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
+use std::process::Command;
+
+use colored::Colorize;
+use dialoguer::Confirm;
+
+use crate::core::platform::detect_os;
+use crate::core::timestamps::current_timestamp;
+use crate::schemas::tools_enums::Permission;
+use crate::{log_debug, log_info, log_warn};
+
+/// The `x-apple.systempreferences` URL of the System Settings pane granting `permission`.
+pub fn settings_url(permission: Permission) -> &'static str {
+    match permission {
+        Permission::FullDiskAccess => {
+            "x-apple.systempreferences:com.apple.preference.security?Privacy_AllFiles"
+        }
+        Permission::ScreenRecording => {
+            "x-apple.systempreferences:com.apple.preference.security?Privacy_ScreenCapture"
+        }
+        Permission::Accessibility => {
+            "x-apple.systempreferences:com.apple.preference.security?Privacy_Accessibility"
+        }
+        Permission::InputMonitoring => {
+            "x-apple.systempreferences:com.apple.preference.security?Privacy_ListenEvent"
+        }
+        Permission::Camera => {
+            "x-apple.systempreferences:com.apple.preference.security?Privacy_Camera"
+        }
+        Permission::Microphone => {
+            "x-apple.systempreferences:com.apple.preference.security?Privacy_Microphone"
+        }
+        Permission::Automation => {
+            "x-apple.systempreferences:com.apple.preference.security?Privacy_Automation"
+        }
+        Permission::DeveloperTools => {
+            "x-apple.systempreferences:com.apple.preference.security?Privacy_DevTools"
+        }
+        Permission::SystemExtension => {
+            "x-apple.systempreferences:com.apple.LoginItems-Settings.extension"
+        }
+    }
+}
+
+/// Guides the user through granting the `permissions` `tool` declares and returns the
+/// acknowledgments to record in state.
+///
+/// Permissions acknowledged in an earlier run are kept without asking again. In
+/// non-interactive runs the panes are only listed, so they are asked for on the next
+/// interactive installation or update. Other systems than macOS are left alone.
+pub fn request(
+    tool: &str,
+    permissions: &[Permission],
+    previous: Option<&BTreeMap<Permission, String>>,
+) -> Option<BTreeMap<Permission, String>> {
+    let mut acknowledged = previous.cloned().unwrap_or_default();
+    acknowledged.retain(|permission, _| permissions.contains(permission));
+
+    if detect_os() != "macos" {
+        log_debug!(
+            "[SDB::Permissions] Skipping macOS permissions of {} on {}",
+            tool,
+            detect_os()
+        );
+        return Some(acknowledged).filter(|map| !map.is_empty());
+    }
+
+    let interactive = std::io::stdin().is_terminal();
+    for &permission in permissions {
+        if acknowledged.contains_key(&permission) {
+            continue;
+        }
+        let url = settings_url(permission);
+        if !interactive {
+            log_warn!(
+                "[SDB::Permissions] {} needs {}; grant it in System Settings (open '{}')",
+                tool.bold(),
+                permission.to_string().yellow(),
+                url
+            );
+            continue;
+        }
+
+        log_info!(
+            "[SDB::Permissions] {} needs {}; opening System Settings > Privacy & Security",
+            tool.bold(),
+            permission.to_string().yellow()
+        );
+        if let Err(e) = Command::new("open").arg(url).status() {
+            log_warn!("[SDB::Permissions] Could not open '{}': {}", url, e);
+        }
+        let granted = Confirm::new()
+            .with_prompt(format!("Granted {permission} to {tool}?"))
+            .default(true)
+            .interact()
+            .unwrap_or(false);
+        if granted {
+            acknowledged.insert(permission, current_timestamp());
+        } else {
+            log_warn!(
+                "[SDB::Permissions] {} was not confirmed for {}; you will be asked again on its next update",
+                permission,
+                tool
+            );
+        }
+    }
+    Some(acknowledged).filter(|map| !map.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_declared_permissions_map_to_privacy_panes() {
+        let permissions: Vec<Permission> =
+            serde_yaml::from_str("[full_disk_access, screen_recording]").unwrap();
+        assert!(settings_url(permissions[0]).ends_with("?Privacy_AllFiles"));
+        assert!(settings_url(permissions[1]).ends_with("?Privacy_ScreenCapture"));
+    }
+
+    #[test]
+    fn test_acknowledgments_are_kept_only_for_declared_permissions() {
+        let previous = BTreeMap::from([
+            (
+                Permission::FullDiskAccess,
+                "2026-01-01T00:00:00Z".to_string(),
+            ),
+            (Permission::Camera, "2026-01-01T00:00:00Z".to_string()),
+        ]);
+
+        // Already acknowledged: nothing is asked, the original timestamp is kept
+        let acknowledged =
+            request("wezterm", &[Permission::FullDiskAccess], Some(&previous)).unwrap();
+        assert_eq!(
+            acknowledged,
+            BTreeMap::from([(
+                Permission::FullDiskAccess,
+                "2026-01-01T00:00:00Z".to_string()
+            )])
+        );
+
+        assert_eq!(request("wezterm", &[], Some(&previous)), None);
+    }
+}
@@ -1,11 +1,72 @@
 use std::process::Command;
+use std::sync::RwLock;
 // Our custom logging macros to give us nicely formatted (and colored!) output
 // for debugging, general information, and errors.
 use crate::{log_debug, log_error, log_info, log_warn};
 // The 'colored' crate helps us make our console output look pretty and readab
+use crate::core::facts;
+use crate::schemas::common::ArchFallback;
 use crate::schemas::tools_enums::InstallerError;
 use colored::Colorize;
 
+/// Policy for release assets built for another architecture, from `arch_fallback` in
+/// `config.yaml`.
+static ARCH_FALLBACK: RwLock<Option<ArchFallback>> = RwLock::new(None);
+
+/// Sets the architecture fallback policy of the `config.yaml` that was just loaded, replacing
+/// that of any configuration loaded before it (`None` restores the default).
+pub fn configure_arch_fallback(policy: Option<ArchFallback>) {
+    *ARCH_FALLBACK.write().unwrap_or_else(|e| e.into_inner()) = policy;
+}
+
+/// The architecture fallback policy of this run; `prefer-native` unless configured.
+pub fn arch_fallback() -> ArchFallback {
+    ARCH_FALLBACK
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .unwrap_or_default()
+}
+
+/// How a release asset relates to this machine's architecture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetMatch {
+    /// Built for this architecture, or for none in particular.
+    Native,
+    /// Built for another architecture this machine can run: `x86_64` under Rosetta 2 on
+    /// Apple silicon, `armv7` on 64-bit ARM Linux or `i686` on x86_64 Linux.
+    Fallback(&'static str),
+}
+
+/// The architecture whose binaries this platform can run besides its own, if any.
+pub fn fallback_arch(os: &str, arch: &str) -> Option<&'static str> {
+    match (normalize_os(os).as_str(), normalize_arch(arch).as_str()) {
+        ("macos", "arm64") => Some("x86_64"),
+        ("linux", "arm64") => Some("armv7"),
+        ("linux", "x86_64") => Some("i686"),
+        _ => None,
+    }
+}
+
+/// Keeps the candidates the `arch_fallback` policy allows: `never` drops assets built for a
+/// fallback architecture, `prefer-native` keeps them only when no native asset exists, and
+/// `allow-rosetta` keeps them all.
+pub fn apply_arch_fallback<T>(candidates: Vec<(T, AssetMatch)>) -> Vec<(T, AssetMatch)> {
+    let has_native = candidates
+        .iter()
+        .any(|(_, matched)| *matched == AssetMatch::Native);
+    match arch_fallback() {
+        ArchFallback::Never => candidates
+            .into_iter()
+            .filter(|(_, matched)| *matched == AssetMatch::Native)
+            .collect(),
+        ArchFallback::PreferNative if has_native => candidates
+            .into_iter()
+            .filter(|(_, matched)| *matched == AssetMatch::Native)
+            .collect(),
+        ArchFallback::PreferNative | ArchFallback::AllowRosetta => candidates,
+    }
+}
+
 /// Checks if a given asset filename from a GitHub release (or similar source)
 /// is likely compatible with the current operating system and architecture.
 /// This is how `setup-devbox` intelligently selects the correct download asset
@@ -18,8 +79,18 @@ use colored::Colorize;
 ///
 /// # Returns
 /// * `bool`: `true` if the filename contains recognizable keywords for the platform's OS and architecture,
-///   considering aliases and a special Rosetta 2 fallback for macOS ARM64. `false` otherwise.
+///   considering aliases and, unless `arch_fallback: never`, the fallback architecture of the
+///   platform (see [`match_asset`]). `false` otherwise.
 pub fn asset_matches_platform(filename: &str, os: &str, arch: &str) -> bool {
+    match_asset(filename, os, arch).is_some_and(|matched| {
+        matched == AssetMatch::Native || arch_fallback() != ArchFallback::Never
+    })
+}
+
+/// Checks an asset filename against the platform like [`asset_matches_platform`], telling
+/// native assets apart from assets built for the platform's fallback architecture, whatever
+/// the `arch_fallback` policy.
+pub fn match_asset(filename: &str, os: &str, arch: &str) -> Option<AssetMatch> {
     // Convert inputs to lowercase for case-insensitive comparison.
     let asset_name_lower = filename.to_lowercase();
 
@@ -28,7 +99,7 @@ pub fn asset_matches_platform(filename: &str, os: &str, arch: &str) -> bool {
         .iter()
         .flat_map(|os_str| os_aliases(os_str))
         .collect();
-    let all_arch_aliases: Vec<String> = ["arm64", "x86_64"]
+    let all_arch_aliases: Vec<String> = ["arm64", "x86_64", "armv7", "i686"]
         .iter()
         .flat_map(|arch_str| arch_aliases(arch_str))
         .collect();
@@ -44,13 +115,13 @@ pub fn asset_matches_platform(filename: &str, os: &str, arch: &str) -> bool {
     if !has_any_os_keyword && !has_any_arch_keyword {
         // Exclude common non-binary files.
         if is_excluded_asset(&asset_name_lower) {
-            return false;
+            return None;
         }
         log_debug!(
             "[Utils] Asset '{}' is considered a generic binary, matching platform.",
             filename.dimmed()
         );
-        return true;
+        return Some(AssetMatch::Native);
     }
 
     // Pre-Step: Normalize the input OS and Architecture first
@@ -73,7 +144,7 @@ pub fn asset_matches_platform(filename: &str, os: &str, arch: &str) -> bool {
             filename.dimmed(),
             os
         );
-        return false;
+        return None;
     }
 
     // 2. Handle universal macOS packages (.dmg, .pkg)
@@ -91,8 +162,8 @@ pub fn asset_matches_platform(filename: &str, os: &str, arch: &str) -> bool {
             filename.dimmed()
         );
         // We'll still run the exclusion check to be safe.
-        // If it passes, we're done here and we return `true`.
-        return !is_excluded_asset(&asset_name_lower);
+        // If it passes, we're done here and it is a native match.
+        return (!is_excluded_asset(&asset_name_lower)).then_some(AssetMatch::Native);
     }
     // 3. Check for Architecture match:
     // Iterate through all known aliases for the current architecture. If any alias is found
@@ -101,27 +172,32 @@ pub fn asset_matches_platform(filename: &str, os: &str, arch: &str) -> bool {
         .iter()
         .any(|alias| asset_name_lower.contains(alias));
 
-    // 4. Special consideration for macOS ARM64 (aarch64) with Rosetta 2 fallback:
-    // By using our normalized strings here, we handle aliases like "darwin" and "aarch64" automatically.
-    let rosetta_fallback = (os_normalized == "macos" && arch_normalized == "arm64")
-        && asset_name_lower.contains("x86_64")
-        && !(asset_name_lower.contains("arm64") || asset_name_lower.contains("aarch64"));
-
-    // If neither a direct architecture match nor the Rosetta fallback condition is met, return false.
-    if !(arch_matches || rosetta_fallback) {
+    // 4. Otherwise the asset may be built for the architecture this platform can also run
+    // (Rosetta 2 on Apple silicon, 32-bit binaries on Linux); whether it is used is up to
+    // the `arch_fallback` policy.
+    let fallback = fallback_arch(&os_normalized, &arch_normalized)
+        .filter(|_| !arch_matches)
+        .filter(|fallback| {
+            arch_aliases(fallback)
+                .iter()
+                .any(|alias| asset_name_lower.contains(alias))
+        });
+
+    // If neither a direct architecture match nor a fallback architecture matches, return None.
+    if !arch_matches && fallback.is_none() {
         log_debug!(
-            "[Utils] Asset '{}' does not match architecture '{}' (and no Rosetta fallback).",
+            "[Utils] Asset '{}' does not match architecture '{}' (and no fallback architecture).",
             filename.dimmed(),
             arch
         );
-        return false;
+        return None;
     }
 
     // 4. Optional: Exclude common source, debug, or checksum files.
     // These files are usually not the actual executable binaries we want to download.
     // This helps in picking the actual binary release.
     if is_excluded_asset(&asset_name_lower) {
-        return false;
+        return None;
     }
 
     // If all checks pass, the asset is considered a match for the current platform.
@@ -130,9 +206,9 @@ pub fn asset_matches_platform(filename: &str, os: &str, arch: &str) -> bool {
         filename.dimmed(),
         os.cyan(),
         arch.magenta(),
-        "true".bold()
+        fallback.unwrap_or("native").bold()
     );
-    true
+    Some(fallback.map_or(AssetMatch::Native, AssetMatch::Fallback))
 }
 
 /// Checks if an asset filename should be excluded based on common keywords for non-binary files.
@@ -190,6 +266,14 @@ fn arch_aliases(arch: &str) -> Vec<String> {
             .into_iter()
             .map(|s| s.to_string())
             .collect(),
+        "armv7" => vec!["armv7", "armhf", "gnueabihf"] // Aliases for ARM 32-bit.
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect(),
+        "i686" => vec!["i686", "i386", "_386", "-386"] // Aliases for x86 32-bit.
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect(),
         other => vec![other.to_string()], // For unknown architecture, just return the input string.
     }
 }
@@ -309,6 +393,32 @@ pub fn normalize_arch(arch: &str) -> String {
     }
 }
 
+/// Parses a `platforms:` key of a tool entry, such as `macos`, `linux-x86_64` or
+/// `darwin-aarch64`, into a normalized OS and optional architecture.
+///
+/// Unlike `normalize_os`/`normalize_arch`, unknown names are rejected instead of passed
+/// through, so typos in keys are reported rather than silently never matching.
+pub fn parse_platform_key(key: &str) -> Option<(String, Option<String>)> {
+    let key = key.trim().to_lowercase();
+    let (os, arch) = match key.split_once('-') {
+        Some((os, arch)) => (os, Some(arch)),
+        None => (key.as_str(), None),
+    };
+    let os = match os {
+        "macos" | "darwin" | "osx" => "macos",
+        "linux" => "linux",
+        "windows" | "win" => "windows",
+        _ => return None,
+    };
+    let arch = match arch {
+        None => None,
+        Some("arm64" | "aarch64") => Some("arm64"),
+        Some("x86_64" | "amd64" | "x64") => Some("x86_64"),
+        Some(_) => return None,
+    };
+    Some((os.to_string(), arch.map(str::to_string)))
+}
+
 /// Checks if a given command (installer) exists in the system's PATH and is executable.
 ///
 /// This function attempts to run the command with a harmless argument like "--version"
@@ -353,6 +463,43 @@ pub fn check_installer_command_available(command_name: &str) -> Result<(), Insta
     }
 }
 
+/// Wraps a hook command so it runs inside a named environment, as declared with
+/// `environment:` on the hook:
+///
+/// - `mise:<tool@version> ...` runs it with those mise tools (`mise exec node@20 -- ...`);
+/// - `mise:<dir>` runs it with the toolchain `mise` resolves for that directory;
+/// - `direnv:<dir>` runs it with the environment `direnv` loads for that directory.
+///
+/// # Returns
+/// * `Ok(String)` - The command to pass to `sh -c`
+/// * `Err(String)` - If the environment kind is unknown or its argument is missing
+pub fn command_in_environment(command: &str, environment: &str) -> Result<String, String> {
+    let quoted = single_quote(command);
+    let dir = |dir: &str| single_quote(&shellexpand::tilde(dir));
+    match environment.trim().split_once(':') {
+        Some((_, arg)) if arg.trim().is_empty() => Err(format!(
+            "hook environment '{environment}' is missing its tools or directory"
+        )),
+        Some(("mise", arg)) if arg.trim().starts_with(['/', '~', '.']) => Ok(format!(
+            "mise exec -C {} -- sh -c {quoted}",
+            dir(arg.trim())
+        )),
+        Some(("mise", tools)) => {
+            let tools: Vec<String> = tools.split_whitespace().map(single_quote).collect();
+            Ok(format!("mise exec {} -- sh -c {quoted}", tools.join(" ")))
+        }
+        Some(("direnv", arg)) => Ok(format!("direnv exec {} sh -c {quoted}", dir(arg.trim()))),
+        _ => Err(format!(
+            "unknown hook environment '{environment}' (expected 'mise:<tools>', 'mise:<dir>' or 'direnv:<dir>')"
+        )),
+    }
+}
+
+/// Quotes a value for POSIX `sh` so it is passed as a single, literal word.
+pub(crate) fn single_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
 /// Executes additional commands specified in the tool configuration after successful installation.
 ///
 /// This function handles the execution of post-installation commands that may be required
@@ -410,7 +557,10 @@ pub fn execute_hooks(
         // Execute the command using the system shell
         // We use `/bin/sh` for Unix-like systems as it's the most portable option
         let mut cmd = Command::new("sh");
-        cmd.arg("-c").arg(command).current_dir(working_dir);
+        cmd.arg("-c")
+            .arg(command)
+            .current_dir(working_dir)
+            .envs(facts::env_vars());
 
         // Execute the command and capture the result
         match cmd.output() {
@@ -504,6 +654,37 @@ pub fn execute_hooks(
     Ok(executed_commands)
 }
 
+/// Locates an executable named `command_name` on the user's `PATH`.
+///
+/// # Returns
+/// * `Some(PathBuf)` - The first matching executable file in `PATH` order
+/// * `None` - If no executable with that name is found
+pub fn find_in_path(command_name: &str) -> Option<std::path::PathBuf> {
+    let path_var = std::env::var_os("PATH")?;
+    // Windows resolves `npm` to `npm.exe`, `npm.cmd`, ...
+    let file_names = crate::core::windows::command_file_names(command_name);
+    std::env::split_paths(&path_var)
+        .flat_map(|dir| file_names.iter().map(move |name| dir.join(name)))
+        .find(|candidate| is_executable_file(candidate))
+}
+
+/// Whether `path` is a file the current user may execute.
+#[cfg(unix)]
+pub fn is_executable_file(path: &std::path::Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+pub fn is_executable_file(path: &std::path::Path) -> bool {
+    path.is_file()
+        && path.file_name().is_some_and(|name| {
+            crate::core::windows::has_executable_extension(&name.to_string_lossy())
+        })
+}
+
 /// Version that considers "1", "yes", "y", "on" as true values (case-insensitive)
 pub fn is_env_var_set(env_var_name: &str) -> bool {
     std::env::var(env_var_name)
@@ -541,6 +722,78 @@ mod tests {
         assert!(asset_matches_platform("App-x86_64.dmg", "macos", "arm64"));
     }
 
+    #[test]
+    fn test_match_asset_fallback_architectures() {
+        assert_eq!(
+            match_asset("tool-darwin-amd64.tar.gz", "macos", "arm64"),
+            Some(AssetMatch::Fallback("x86_64"))
+        );
+        assert_eq!(
+            match_asset("tool-linux-armv7.tar.gz", "linux", "arm64"),
+            Some(AssetMatch::Fallback("armv7"))
+        );
+        assert_eq!(
+            match_asset("tool_linux_386.tar.gz", "linux", "x86_64"),
+            Some(AssetMatch::Fallback("i686"))
+        );
+        assert_eq!(
+            match_asset("tool-linux-aarch64.tar.gz", "linux", "arm64"),
+            Some(AssetMatch::Native)
+        );
+        // 32-bit assets are not generic binaries, and x86_64 Macs have no fallback
+        assert_eq!(match_asset("tool-armv7", "linux", "x86_64"), None);
+        assert_eq!(
+            match_asset("tool-darwin-arm64.tar.gz", "macos", "x86_64"),
+            None
+        );
+
+        // The default policy only uses fallback assets when no native one exists
+        let _config = crate::core::lifecycle_hooks::tests::HOOKS_LOCK.lock();
+        let native_first = apply_arch_fallback(vec![
+            ("x86", AssetMatch::Fallback("x86_64")),
+            ("arm", AssetMatch::Native),
+        ]);
+        assert_eq!(native_first, vec![("arm", AssetMatch::Native)]);
+        let fallback_only = apply_arch_fallback(vec![("x86", AssetMatch::Fallback("x86_64"))]);
+        assert_eq!(fallback_only.len(), 1);
+    }
+
+    #[test]
+    fn test_command_in_environment() {
+        assert_eq!(
+            command_in_environment("npm i -g pnpm", "mise:node@20 python@3.12"),
+            Ok("mise exec 'node@20' 'python@3.12' -- sh -c 'npm i -g pnpm'".to_string())
+        );
+        assert_eq!(
+            command_in_environment("echo 'hi'", "direnv:/srv/api"),
+            Ok("direnv exec '/srv/api' sh -c 'echo '\\''hi'\\'''".to_string())
+        );
+        assert_eq!(
+            command_in_environment("make", "mise:./project"),
+            Ok("mise exec -C './project' -- sh -c 'make'".to_string())
+        );
+        assert!(command_in_environment("make", "nix:shell").is_err());
+        assert!(command_in_environment("make", "mise:").is_err());
+    }
+
+    #[test]
+    fn test_parse_platform_key() {
+        assert_eq!(
+            parse_platform_key("macos"),
+            Some(("macos".to_string(), None))
+        );
+        assert_eq!(
+            parse_platform_key("Darwin-aarch64"),
+            Some(("macos".to_string(), Some("arm64".to_string())))
+        );
+        assert_eq!(
+            parse_platform_key("linux-amd64"),
+            Some(("linux".to_string(), Some("x86_64".to_string())))
+        );
+        assert_eq!(parse_platform_key("macso-arm64"), None);
+        assert_eq!(parse_platform_key("linux-riscv"), None);
+    }
+
     #[test]
     fn test_asset_matches_platform_macos_universal() {
         // Truly universal DMG should match both
@@ -0,0 +1,140 @@
+//! # Tool Presets
+//!
+//! Window managers and keyboard tools (AeroSpace, yabai, skhd, Karabiner-Elements) are
+//! installed through the usual sources, but each keeps its configuration in a fixed place and
+//! only picks up changes once its service is restarted or told to reload. A `preset:` on the
+//! entry supplies both: the configuration manager's destination path when none is given, and
+//! the restart after the tool is installed or updated or its configuration is rewritten.
+
+use std::process::Command;
+
+use colored::Colorize;
+
+use crate::core::platform::detect_os;
+use crate::schemas::config_manager::ConfigurationPath;
+use crate::schemas::tools_enums::ToolPreset;
+use crate::schemas::tools_types::ToolEntry;
+use crate::{log_debug, log_info, log_warn};
+
+/// Configuration files the configuration manager deploys for the preset. yabai and skhd are
+/// configured with shell scripts (`yabairc`, `skhdrc`), which can't be generated from TOML,
+/// so they have none.
+pub fn configuration_paths(preset: ToolPreset) -> &'static [&'static str] {
+    match preset {
+        ToolPreset::Aerospace => &["~/.config/aerospace/aerospace.toml"],
+        ToolPreset::Karabiner => &["~/.config/karabiner/karabiner.json"],
+        ToolPreset::Yabai | ToolPreset::Skhd => &[],
+    }
+}
+
+/// Shell command that makes the running tool pick up a new binary or configuration.
+pub fn restart_command(preset: ToolPreset) -> &'static str {
+    match preset {
+        ToolPreset::Aerospace => "aerospace reload-config",
+        ToolPreset::Yabai => "yabai --restart-service || yabai --start-service",
+        ToolPreset::Skhd => "skhd --restart-service || skhd --start-service",
+        ToolPreset::Karabiner => {
+            "launchctl kickstart -k gui/$(id -u)/org.pqrs.karabiner.karabiner_console_user_server"
+        }
+    }
+}
+
+/// Fills in the preset's configuration paths for entries that enable the configuration
+/// manager without listing any.
+pub fn apply(tool: &mut ToolEntry) {
+    let Some(preset) = tool.preset else {
+        return;
+    };
+    let manager = &mut tool.configuration_manager;
+    if manager.enabled && manager.tools_configuration_paths.is_empty() {
+        manager.tools_configuration_paths = configuration_paths(preset)
+            .iter()
+            .map(|path| ConfigurationPath::Path(path.to_string()))
+            .collect();
+    }
+}
+
+/// Restarts or reloads the tool's service so it runs the current binary and configuration.
+/// Failures are only warned about; the tool may simply not be running yet.
+pub fn restart(tool: &ToolEntry) {
+    let Some(preset) = tool.preset else {
+        return;
+    };
+    if detect_os() != "macos" {
+        log_debug!(
+            "[SDB::Presets] Not restarting {} outside macOS",
+            tool.name.bold()
+        );
+        return;
+    }
+
+    let command = restart_command(preset);
+    log_info!(
+        "[SDB::Presets] Restarting {} to apply changes: {}",
+        tool.name.bold(),
+        command.dimmed()
+    );
+    match Command::new("sh").arg("-c").arg(command).output() {
+        Ok(output) if output.status.success() => {}
+        Ok(output) => log_warn!(
+            "[SDB::Presets] Restarting {} failed: {}",
+            tool.name.yellow(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+        Err(e) => log_warn!(
+            "[SDB::Presets] Could not restart {}: {}",
+            tool.name.yellow(),
+            e
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(yaml: &str) -> ToolEntry {
+        let mut tool: ToolEntry = serde_yaml::from_str(yaml).unwrap();
+        apply(&mut tool);
+        tool
+    }
+
+    #[test]
+    fn test_preset_fills_in_configuration_path() {
+        let tool = entry(
+            "name: aerospace\nsource: brew\npreset: aerospace\nconfiguration_manager:\n  enabled: true",
+        );
+        assert_eq!(
+            tool.configuration_manager.destination_paths(),
+            ["~/.config/aerospace/aerospace.toml"]
+        );
+    }
+
+    #[test]
+    fn test_explicit_or_disabled_configuration_is_left_alone() {
+        let tool = entry(
+            "name: karabiner\nsource: brew\npreset: karabiner\nconfiguration_manager:\n  enabled: true\n  tools_configuration_paths: [~/kb.json]",
+        );
+        assert_eq!(
+            tool.configuration_manager.destination_paths(),
+            ["~/kb.json"]
+        );
+
+        let tool = entry("name: aerospace\nsource: brew\npreset: aerospace");
+        assert!(
+            tool.configuration_manager
+                .tools_configuration_paths
+                .is_empty()
+        );
+
+        // yabai is configured with a shell script, which can't be generated
+        let tool = entry(
+            "name: yabai\nsource: brew\npreset: yabai\nconfiguration_manager:\n  enabled: true",
+        );
+        assert!(
+            tool.configuration_manager
+                .tools_configuration_paths
+                .is_empty()
+        );
+    }
+}
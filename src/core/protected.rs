@@ -0,0 +1,114 @@
+//! # Protected Entries
+//!
+//! Tools marked `protected: true` (e.g. EDR agents or audit tools a team must keep) cannot be
+//! casually changed: updating them, removing them, or dropping `protected: true` from their
+//! entry requires `--force-protected --reason <TEXT>`. Every such change is recorded in the
+//! state file (`protected_changes`) with the reason, the user and the time.
+
+use std::env;
+use std::sync::RwLock;
+
+use colored::Colorize;
+
+use crate::core::timestamps::current_timestamp;
+use crate::log_warn;
+use crate::schemas::state_file::{DevBoxState, ProtectedChange};
+
+/// The `--reason` given with `--force-protected`, if it was passed.
+static FORCE_REASON: RwLock<Option<String>> = RwLock::new(None);
+
+/// Allows changes to protected entries for this run (`--force-protected --reason <TEXT>`),
+/// or refuses them again when `force_protected` is not set.
+pub fn configure(force_protected: bool, reason: Option<String>) {
+    *FORCE_REASON.write().unwrap_or_else(|e| e.into_inner()) = reason.filter(|_| force_protected);
+}
+
+/// Whether `--force-protected` was passed for this run.
+pub fn is_forced() -> bool {
+    force_reason().is_some()
+}
+
+fn force_reason() -> Option<String> {
+    FORCE_REASON
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Whether the tool is recorded as protected in the state file.
+pub fn is_protected(state: &DevBoxState, name: &str) -> bool {
+    state
+        .tools
+        .get(name)
+        .is_some_and(|tool| tool.protected == Some(true))
+}
+
+/// Allows `change` (e.g. `remove`) of the protected tool `name` if `--force-protected` was
+/// passed, recording it with its reason in `state`.
+///
+/// # Returns
+/// * `Ok(())` - The change is allowed and was recorded
+/// * `Err(String)` - Why the change is refused
+pub fn authorize(state: &mut DevBoxState, name: &str, change: &str) -> Result<(), String> {
+    let Some(reason) = force_reason() else {
+        return Err(format!(
+            "'{name}' is protected; to {change} it, pass --force-protected --reason <TEXT>"
+        ));
+    };
+    log_warn!(
+        "[SDB::Protected] Allowing {} of protected {}: {}",
+        change,
+        name.bold(),
+        reason
+    );
+    state.protected_changes.push(ProtectedChange {
+        name: name.to_string(),
+        change: change.to_string(),
+        reason,
+        user: env::var("USER")
+            .or_else(|_| env::var("USERNAME"))
+            .unwrap_or_else(|_| "unknown".to_string()),
+        changed_at: current_timestamp(),
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_protected_changes_need_a_reason_and_are_recorded() {
+        let mut state = DevBoxState::default();
+        state.tools.insert(
+            "falcon-sensor".to_string(),
+            serde_json::from_value(json!({
+                "version": "7.10",
+                "install_path": "/opt/CrowdStrike",
+                "installed_by_devbox": true,
+                "install_method": "brew",
+                "package_type": "binary",
+                "protected": true,
+            }))
+            .unwrap(),
+        );
+        assert!(is_protected(&state, "falcon-sensor"));
+
+        configure(false, Some("ignored without --force-protected".to_string()));
+        let refusal = authorize(&mut state, "falcon-sensor", "remove").unwrap_err();
+        assert!(refusal.contains("--force-protected --reason"));
+        assert!(state.protected_changes.is_empty());
+
+        configure(true, Some("replaced by new EDR".to_string()));
+        assert!(authorize(&mut state, "falcon-sensor", "remove").is_ok());
+        assert_eq!(state.protected_changes.len(), 1);
+        assert_eq!(state.protected_changes[0].change, "remove");
+        assert_eq!(state.protected_changes[0].reason, "replaced by new EDR");
+
+        // The next run without --force-protected is refused again
+        configure(false, None);
+        assert!(authorize(&mut state, "falcon-sensor", "update").is_err());
+        assert_eq!(state.protected_changes.len(), 1);
+    }
+}
@@ -0,0 +1,201 @@
+//! # Executable Renames
+//!
+//! Changing a tool's `rename_to` (e.g. from `helix` to `hx`) reinstalls it under the new
+//! name. Once the installation succeeded, the executable (and shim) left under the old name
+//! is removed, unless another tool now owns that path, and the rename is appended to the
+//! tool's `rename_history` in the state file.
+
+use std::fs;
+use std::path::Path;
+
+use colored::Colorize;
+
+use crate::core::timestamps::current_timestamp;
+use crate::schemas::state_file::{DevBoxState, RenameRecord, ToolState};
+use crate::schemas::tools_types::ToolEntry;
+use crate::{log_info, log_warn};
+
+/// Name of the executable a tool installs: its `rename_to`, or the tool name.
+fn executable_name<'a>(name: &'a str, rename_to: Option<&'a str>) -> &'a str {
+    rename_to.unwrap_or(name)
+}
+
+/// Whether `tool`'s executable name differs from the one it was installed under.
+pub fn rename_changed(tool: &ToolEntry, previous: &ToolState) -> bool {
+    executable_name(&tool.name, tool.rename_to.as_deref())
+        != executable_name(&tool.name, previous.renamed_to.as_deref())
+}
+
+/// Carries the rename history of a reinstalled tool over to its new state and, when its
+/// executable name changed, removes the files left under the old name and records the rename.
+///
+/// # Arguments
+/// * `tool_name` - Name of the tool
+/// * `state` - Current state, still holding the previous installation of the tool
+/// * `tool_state` - State of the installation that just finished
+pub fn migrate(tool_name: &str, state: &DevBoxState, tool_state: &mut ToolState) {
+    let Some(previous) = state.tools.get(tool_name) else {
+        return;
+    };
+    tool_state.rename_history = previous.rename_history.clone();
+
+    let from = executable_name(tool_name, previous.renamed_to.as_deref());
+    let to = executable_name(tool_name, tool_state.renamed_to.as_deref());
+    if from == to {
+        return;
+    }
+
+    // The old executable and shim, unless the new installation or another tool uses them
+    let new_paths = [
+        Some(tool_state.install_path.as_str()),
+        tool_state.shim.as_ref().map(|shim| shim.path.as_str()),
+    ];
+    let old_paths = [
+        Some(previous.install_path.as_str()),
+        previous.shim.as_ref().map(|shim| shim.path.as_str()),
+    ];
+    let mut removed = Vec::new();
+    for old_path in old_paths.into_iter().flatten() {
+        if new_paths.contains(&Some(old_path)) || owned_by_other_tool(state, tool_name, old_path) {
+            continue;
+        }
+        if remove_leftover(Path::new(old_path)) {
+            removed.push(old_path.to_string());
+        }
+    }
+
+    log_info!(
+        "[SDB::Tools] {} renamed from '{}' to '{}'",
+        tool_name.bold(),
+        from,
+        to.green()
+    );
+    tool_state.rename_history.push(RenameRecord {
+        from: from.to_string(),
+        to: to.to_string(),
+        removed,
+        renamed_at: current_timestamp(),
+    });
+}
+
+/// Whether a tool other than `tool_name` records `path` as its executable or shim.
+fn owned_by_other_tool(state: &DevBoxState, tool_name: &str, path: &str) -> bool {
+    state
+        .tools
+        .iter()
+        .filter(|(name, _)| name.as_str() != tool_name)
+        .any(|(_, other)| {
+            other.install_path == path || other.shim.as_ref().is_some_and(|shim| shim.path == path)
+        })
+}
+
+/// Removes an executable (or `.app` bundle) left under an old name. Returns whether
+/// something was removed.
+fn remove_leftover(path: &Path) -> bool {
+    // Package managers record package names rather than paths; there is nothing to remove
+    if !path.is_absolute() || fs::symlink_metadata(path).is_err() {
+        return false;
+    }
+    let result = if path.is_dir() {
+        if path.extension().is_none_or(|extension| extension != "app") {
+            log_warn!(
+                "[SDB::Tools] Leaving directory {} from before the rename in place",
+                path.display()
+            );
+            return false;
+        }
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    };
+    match result {
+        Ok(()) => {
+            log_info!(
+                "[SDB::Tools] Removed {} left under the old name",
+                path.display().to_string().yellow()
+            );
+            true
+        }
+        Err(e) => {
+            log_warn!(
+                "[SDB::Tools] Could not remove {} left under the old name: {}",
+                path.display(),
+                e
+            );
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool_state(install_path: &Path, renamed_to: Option<&str>) -> ToolState {
+        serde_json::from_value(serde_json::json!({
+            "version": "25.01",
+            "install_path": install_path,
+            "installed_by_devbox": true,
+            "install_method": "github",
+            "renamed_to": renamed_to,
+            "package_type": "binary",
+            "repo": null,
+            "tag": null,
+            "last_updated": null,
+            "executable_path_after_extract": null,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_migrate_removes_old_executable_and_records_rename() {
+        let dir = tempfile::tempdir().unwrap();
+        let old_path = dir.path().join("helix");
+        let new_path = dir.path().join("hx");
+        fs::write(&old_path, "old").unwrap();
+        fs::write(&new_path, "new").unwrap();
+
+        let mut state = DevBoxState::default();
+        state
+            .tools
+            .insert("helix".to_string(), tool_state(&old_path, None));
+        let mut installed = tool_state(&new_path, Some("hx"));
+        migrate("helix", &state, &mut installed);
+
+        assert!(!old_path.exists());
+        assert!(new_path.exists());
+        assert_eq!(installed.rename_history.len(), 1);
+        let record = &installed.rename_history[0];
+        assert_eq!((record.from.as_str(), record.to.as_str()), ("helix", "hx"));
+        assert_eq!(
+            record.removed,
+            vec![old_path.to_string_lossy().into_owned()]
+        );
+
+        // The history survives later updates that keep the name
+        state.tools.insert("helix".to_string(), installed);
+        let mut updated = tool_state(&new_path, Some("hx"));
+        migrate("helix", &state, &mut updated);
+        assert_eq!(updated.rename_history.len(), 1);
+    }
+
+    #[test]
+    fn test_migrate_keeps_paths_of_other_tools() {
+        let dir = tempfile::tempdir().unwrap();
+        let shared = dir.path().join("vi");
+        fs::write(&shared, "neovim").unwrap();
+
+        let mut state = DevBoxState::default();
+        state
+            .tools
+            .insert("vim".to_string(), tool_state(&shared, Some("vi")));
+        state
+            .tools
+            .insert("neovim".to_string(), tool_state(&shared, Some("vi")));
+        let mut installed = tool_state(&dir.path().join("vim"), None);
+        migrate("vim", &state, &mut installed);
+
+        assert!(shared.exists());
+        assert!(installed.rename_history[0].removed.is_empty());
+    }
+}
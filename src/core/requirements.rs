@@ -0,0 +1,169 @@
+//! Environment prerequisites of tools.
+//!
+//! Tools that wrap a runtime can list what they need with `requires_env:`, which is checked
+//! before the tool is installed or updated so a missing runtime fails fast with a clear
+//! message instead of at the tool's first use:
+//!
+//! ```yaml
+//! requires_env:
+//!   - docker            # command available in PATH
+//!   - python>=3.10      # command with a minimum version (read from `python --version`)
+//!   - $JAVA_HOME        # environment variable that must be set
+//! ```
+
+use crate::log_debug;
+use regex::Regex;
+use std::cmp::Ordering;
+use std::env;
+use std::process::Command;
+
+/// A single `requires_env` entry.
+#[derive(Debug, PartialEq)]
+pub enum Requirement {
+    /// A command that must be on `PATH`, optionally with a minimum version.
+    Command {
+        name: String,
+        min_version: Option<String>,
+    },
+    /// An environment variable that must be set and non-empty.
+    EnvVar(String),
+}
+
+impl Requirement {
+    /// Parses `name`, `name>=version` or `$VAR`.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        if let Some(var) = spec.strip_prefix('$') {
+            let var = var.trim_start_matches('{').trim_end_matches('}');
+            if var.is_empty() {
+                return Err(format!("'{spec}' names no environment variable"));
+            }
+            return Ok(Self::EnvVar(var.to_string()));
+        }
+
+        let (name, min_version) = match spec.split_once(">=") {
+            Some((name, version)) => (name.trim(), Some(version.trim())),
+            None => (spec, None),
+        };
+        if name.is_empty() || name.contains(['<', '>', '=', ' ']) {
+            return Err(format!(
+                "'{spec}' is not a valid requirement (expected 'command', 'command>=version' or '$VAR')"
+            ));
+        }
+        if let Some(version) = min_version
+            && parse_version_parts(version).is_none()
+        {
+            return Err(format!("'{version}' in '{spec}' is not a valid version"));
+        }
+        Ok(Self::Command {
+            name: name.to_string(),
+            min_version: min_version.map(str::to_string),
+        })
+    }
+
+    /// Checks the requirement on this machine, describing what to do when it is not met.
+    pub fn check(&self) -> Result<(), String> {
+        match self {
+            Self::EnvVar(var) => match env::var(var) {
+                Ok(value) if !value.is_empty() => Ok(()),
+                _ => Err(format!("set the environment variable {var} first")),
+            },
+            Self::Command { name, min_version } => {
+                let output = Command::new(name)
+                    .arg("--version")
+                    .output()
+                    .map_err(|_| format!("install {name} first (not found in PATH)"))?;
+                let Some(min_version) = min_version else {
+                    return Ok(());
+                };
+
+                let text = format!(
+                    "{}{}",
+                    String::from_utf8_lossy(&output.stdout),
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                let Some(found) = extract_version(&text) else {
+                    return Err(format!(
+                        "could not determine the version of {name} (need {min_version} or newer)"
+                    ));
+                };
+                log_debug!("[SDB::Requirements] {} version {}", name, found);
+                if compare_versions(&found, min_version) == Ordering::Less {
+                    return Err(format!(
+                        "upgrade {name} to {min_version} or newer first (found {found})"
+                    ));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Checks all `requires_env` entries of a tool, returning every unmet one.
+pub fn check_requirements(specs: &[String]) -> Result<(), Vec<String>> {
+    let failures: Vec<String> = specs
+        .iter()
+        .filter_map(|spec| Requirement::parse(spec).and_then(|req| req.check()).err())
+        .collect();
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures)
+    }
+}
+
+/// Finds the first dotted version number (e.g. `3.10.4` in `Python 3.10.4`).
+pub(crate) fn extract_version(text: &str) -> Option<String> {
+    let re = Regex::new(r"\d+(\.\d+)+").ok()?;
+    re.find(text).map(|m| m.as_str().to_string())
+}
+
+pub(crate) fn parse_version_parts(version: &str) -> Option<Vec<u64>> {
+    version
+        .trim_start_matches('v')
+        .split('.')
+        .map(|part| part.parse().ok())
+        .collect()
+}
+
+/// Compares dotted versions numerically, treating missing components as zero.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let a = parse_version_parts(a).unwrap_or_default();
+    let b = parse_version_parts(b).unwrap_or_default();
+    (0..a.len().max(b.len()))
+        .map(|i| a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0)))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_requirement() {
+        assert_eq!(
+            Requirement::parse("python>=3.10"),
+            Ok(Requirement::Command {
+                name: "python".to_string(),
+                min_version: Some("3.10".to_string())
+            })
+        );
+        assert_eq!(
+            Requirement::parse("${JAVA_HOME}"),
+            Ok(Requirement::EnvVar("JAVA_HOME".to_string()))
+        );
+        assert!(Requirement::parse("python<3").is_err());
+        assert!(Requirement::parse("node>=latest").is_err());
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("3.9.6", "3.10"), Ordering::Less);
+        assert_eq!(compare_versions("3.10.0", "3.10"), Ordering::Equal);
+        assert_eq!(
+            extract_version("Python 3.12.1\n"),
+            Some("3.12.1".to_string())
+        );
+    }
+}
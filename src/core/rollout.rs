@@ -0,0 +1,203 @@
+//! # Gradual Rollouts
+//!
+//! Teams sharing a configuration can ship a new tool, or a new version of one, to part of
+//! their machines first. A tool with `rollout: 10%` is only installed or updated on the 10% of
+//! machines whose bucket falls below that percentage; `rollout: canary` only on machines that
+//! joined the named cohort through `SDB_COHORTS` (comma-separated). Other machines keep the
+//! installed version, or do not install the tool, until the rollout is widened or removed.
+//!
+//! The bucket (0-99) is derived from a hash of the machine's id (`/etc/machine-id`, the
+//! hardware UUID on macOS, the hostname as a last resort, or `SDB_MACHINE_ID`), so a machine
+//! stays in the same bucket across runs and raising the percentage only adds machines. Every
+//! decision is recorded in the state file (`rollouts`) and shown by `status`.
+
+use std::env;
+use std::fs;
+use std::process::Command;
+use std::sync::OnceLock;
+
+use sha2::{Digest, Sha256};
+
+use crate::core::facts;
+use crate::core::timestamps::current_timestamp;
+use crate::log_debug;
+use crate::schemas::state_file::{DevBoxState, RolloutDecision};
+use crate::schemas::tools_types::ToolEntry;
+
+/// Who a tool is rolled out to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rollout {
+    /// Machines whose bucket is below the percentage (`10%`).
+    Percentage(u8),
+    /// Machines that list the cohort in `SDB_COHORTS` (`canary`).
+    Cohort(String),
+}
+
+impl Rollout {
+    /// Parses a `rollout` value: a percentage from `0%` to `100%`, or a cohort name.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        if let Some(percentage) = spec.strip_suffix('%') {
+            return match percentage.trim().parse::<u8>() {
+                Ok(percentage) if percentage <= 100 => Ok(Self::Percentage(percentage)),
+                _ => Err(format!("'{spec}' is not a percentage between 0% and 100%")),
+            };
+        }
+        if spec.is_empty()
+            || !spec
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(format!(
+                "'{spec}' is neither a percentage (10%) nor a cohort name"
+            ));
+        }
+        Ok(Self::Cohort(spec.to_lowercase()))
+    }
+
+    /// Whether a machine in `bucket` that joined `cohorts` is part of the rollout.
+    fn includes(&self, bucket: u8, cohorts: &[String]) -> bool {
+        match self {
+            Self::Percentage(percentage) => bucket < *percentage,
+            Self::Cohort(cohort) => cohorts.iter().any(|joined| joined == cohort),
+        }
+    }
+}
+
+/// This machine's rollout bucket (0-99).
+pub fn machine_bucket() -> u8 {
+    static BUCKET: OnceLock<u8> = OnceLock::new();
+    *BUCKET.get_or_init(|| {
+        let bucket = bucket_of(&machine_id());
+        log_debug!(
+            "[SDB::Rollout] This machine is in rollout bucket {}",
+            bucket
+        );
+        bucket
+    })
+}
+
+/// The cohorts this machine joined through `SDB_COHORTS`.
+pub fn cohorts() -> Vec<String> {
+    env::var("SDB_COHORTS")
+        .unwrap_or_default()
+        .split(',')
+        .map(|cohort| cohort.trim().to_lowercase())
+        .filter(|cohort| !cohort.is_empty())
+        .collect()
+}
+
+/// Whether this machine is part of `tool`'s rollout: `None` for tools without `rollout`.
+pub fn decide(tool: &ToolEntry) -> Option<Result<bool, String>> {
+    let spec = tool.rollout.as_deref()?;
+    Some(Rollout::parse(spec).map(|rollout| rollout.includes(machine_bucket(), &cohorts())))
+}
+
+/// The reason `tool` is not installed or updated on this machine, if its rollout leaves
+/// the machine out (or cannot be read).
+pub fn held_back(tool: &ToolEntry) -> Option<String> {
+    match decide(tool)? {
+        Ok(true) => None,
+        Ok(false) => Some(format!(
+            "rollout '{}' does not include this machine yet (bucket {})",
+            tool.rollout.as_deref().unwrap_or_default(),
+            machine_bucket()
+        )),
+        Err(e) => Some(format!("rollout: {e}")),
+    }
+}
+
+/// Records this machine's decision for `tool` in the state, or forgets an earlier one once
+/// the tool has no rollout anymore.
+pub fn record(state: &mut DevBoxState, tool: &ToolEntry) {
+    let (Some(spec), Some(Ok(included))) = (tool.rollout.as_deref(), decide(tool)) else {
+        state.rollouts.remove(&tool.name);
+        return;
+    };
+    let unchanged = state
+        .rollouts
+        .get(&tool.name)
+        .is_some_and(|decision| decision.rollout == spec && decision.included == included);
+    if unchanged {
+        return;
+    }
+    state.rollouts.insert(
+        tool.name.clone(),
+        RolloutDecision {
+            rollout: spec.to_string(),
+            included,
+            bucket: machine_bucket(),
+            decided_at: current_timestamp(),
+        },
+    );
+}
+
+/// The bucket (0-99) a machine id hashes to.
+fn bucket_of(machine_id: &str) -> u8 {
+    let digest = Sha256::digest(format!("setup-devbox-rollout:{machine_id}").as_bytes());
+    let value = u64::from_be_bytes(digest[..8].try_into().unwrap_or_default());
+    (value % 100) as u8
+}
+
+/// A stable identifier of this machine.
+fn machine_id() -> String {
+    if let Ok(id) = env::var("SDB_MACHINE_ID")
+        && !id.trim().is_empty()
+    {
+        return id.trim().to_string();
+    }
+    ["/etc/machine-id", "/var/lib/dbus/machine-id"]
+        .iter()
+        .find_map(|path| {
+            fs::read_to_string(path)
+                .ok()
+                .map(|id| id.trim().to_string())
+                .filter(|id| !id.is_empty())
+        })
+        .or_else(macos_hardware_uuid)
+        .unwrap_or_else(|| facts::facts().get("hostname").cloned().unwrap_or_default())
+}
+
+/// `IOPlatformUUID` from `ioreg` on macOS.
+fn macos_hardware_uuid() -> Option<String> {
+    if !cfg!(target_os = "macos") {
+        return None;
+    }
+    let output = Command::new("ioreg")
+        .args(["-rd1", "-c", "IOPlatformExpertDevice"])
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find(|line| line.contains("IOPlatformUUID"))
+        .and_then(|line| line.rsplit('"').nth(1))
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_include() {
+        assert_eq!(Rollout::parse("10%"), Ok(Rollout::Percentage(10)));
+        assert_eq!(Rollout::parse(" 100 %"), Ok(Rollout::Percentage(100)));
+        assert_eq!(
+            Rollout::parse("Canary"),
+            Ok(Rollout::Cohort("canary".to_string()))
+        );
+        assert!(Rollout::parse("150%").is_err());
+        assert!(Rollout::parse("ten percent").is_err());
+
+        let cohorts = vec!["canary".to_string()];
+        assert!(Rollout::Percentage(10).includes(9, &[]));
+        assert!(!Rollout::Percentage(10).includes(10, &[]));
+        assert!(!Rollout::Percentage(0).includes(0, &[]));
+        assert!(Rollout::Cohort("canary".to_string()).includes(99, &cohorts));
+        assert!(!Rollout::Cohort("beta".to_string()).includes(0, &cohorts));
+
+        // The same machine always lands in the same bucket
+        assert_eq!(bucket_of("machine-a"), bucket_of("machine-a"));
+        assert!(bucket_of("machine-a") < 100);
+    }
+}
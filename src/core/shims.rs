@@ -0,0 +1,155 @@
+//! # Tool Shims
+//!
+//! Some tools need environment variables or a specific working directory at launch (e.g.
+//! `JAVA_HOME` for JVM tools). Tools with a `shim:` block get a small launcher script in the
+//! user's bin directory instead of the raw binary; the script sets up the environment and
+//! `exec`s the real executable.
+//!
+//! Binaries that were installed straight into the bin directory (GitHub and URL tools) are
+//! moved to `<bin>/.shim-targets/` so the shim can take their name. Tools installed elsewhere
+//! (brew, cargo, go, ...) keep their location; the shim in the bin directory wraps them, so
+//! that directory must come first in `PATH`.
+//!
+//! The generated script is recorded in the tool's state (`ToolState::shim`) so it is removed
+//! together with the tool.
+
+use std::fs;
+use std::io;
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+
+use crate::schemas::path_resolver::PathResolver;
+use crate::schemas::state_file::{ShimState, ToolState};
+use crate::schemas::tools_types::{ShimConfig, ToolEntry};
+use crate::{log_debug, log_info};
+
+/// Directory inside the bin directory holding binaries that were replaced by a shim.
+const SHIM_TARGETS_DIR: &str = ".shim-targets";
+
+/// Script used when a tool does not define its own `template`.
+const DEFAULT_TEMPLATE: &str = "#!/bin/sh
+# Generated by setup-devbox for {{name}}. Changes are overwritten on the next install.
+{{env}}{{cd}}exec {{target}} \"$@\"
+";
+
+/// Creates (or refreshes) the shim for a freshly installed tool and records it in `tool_state`.
+pub fn install_shim(
+    tool: &ToolEntry,
+    shim: &ShimConfig,
+    tool_state: &mut ToolState,
+) -> io::Result<()> {
+    let bin_dir = PathResolver::get_user_bin_dir().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "could not determine the bin directory",
+        )
+    })?;
+    let name = tool.rename_to.as_deref().unwrap_or(&tool.name);
+    let shim_path = bin_dir.join(name);
+
+    // A binary installed under the shim's name has to move out of the way first
+    let mut target = PathBuf::from(&tool_state.install_path);
+    if target == shim_path {
+        let targets_dir = bin_dir.join(SHIM_TARGETS_DIR);
+        fs::create_dir_all(&targets_dir)?;
+        let moved = targets_dir.join(name);
+        fs::rename(&target, &moved)?;
+        log_debug!(
+            "[SDB::Tools::Shim] Moved {} to {}",
+            target.display(),
+            moved.display()
+        );
+        target = moved;
+        tool_state.install_path = target.to_string_lossy().into_owned();
+    }
+
+    let script = render_shim(name, &target, shim);
+    fs::create_dir_all(&bin_dir)?;
+    fs::write(&shim_path, script)?;
+    #[cfg(unix)]
+    fs::set_permissions(&shim_path, fs::Permissions::from_mode(0o755))?;
+
+    log_info!(
+        "[SDB::Tools::Shim] Created shim {} → {}",
+        shim_path.display().to_string().green(),
+        target.display()
+    );
+    tool_state.shim = Some(ShimState {
+        path: shim_path.to_string_lossy().into_owned(),
+        target: target.to_string_lossy().into_owned(),
+        env: shim.env.clone(),
+        working_dir: shim.working_dir.clone(),
+    });
+    Ok(())
+}
+
+/// Removes a tool's shim script, if the state recorded one.
+pub fn remove_shim(shim: &ShimState) -> io::Result<()> {
+    let path = Path::new(&shim.path);
+    if path.exists() {
+        fs::remove_file(path)?;
+        log_info!(
+            "[SDB::Remove::Tool::Shim] Deleted: {}",
+            path.display().to_string().cyan()
+        );
+    }
+    Ok(())
+}
+
+/// Renders the shim script. Templates can use `{{name}}`, `{{target}}`, `{{env}}` (one
+/// `export` line per variable) and `{{cd}}` (a `cd` into `working_dir`, if set).
+pub fn render_shim(name: &str, target: &Path, shim: &ShimConfig) -> String {
+    let env: String = shim
+        .env
+        .iter()
+        .map(|(key, value)| format!("export {key}={}\n", shell_quote(value)))
+        .collect();
+    let cd = shim.working_dir.as_deref().map_or(String::new(), |dir| {
+        let dir = PathResolver::expand_tilde(dir);
+        format!("cd {} || exit 1\n", shell_quote(&dir.to_string_lossy()))
+    });
+
+    shim.template
+        .as_deref()
+        .unwrap_or(DEFAULT_TEMPLATE)
+        .replace("{{name}}", name)
+        .replace("{{target}}", &shell_quote(&target.to_string_lossy()))
+        .replace("{{env}}", &env)
+        .replace("{{cd}}", &cd)
+}
+
+/// Quotes a value for POSIX `sh`. `$VAR` references are left expandable so values like
+/// `$HOME/.sdkman/candidates/java/current` resolve when the shim runs.
+fn shell_quote(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('`', "\\`")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_render_shim_default_template() {
+        let shim = ShimConfig {
+            env: BTreeMap::from([("JAVA_HOME".to_string(), "$HOME/jdk".to_string())]),
+            working_dir: Some("/tmp".to_string()),
+            template: None,
+        };
+        let script = render_shim("kotlin", Path::new("/opt/kotlin/bin/kotlin"), &shim);
+
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains("export JAVA_HOME=\"$HOME/jdk\"\n"));
+        assert!(script.contains("cd \"/tmp\" || exit 1\n"));
+        assert!(script.ends_with("exec \"/opt/kotlin/bin/kotlin\" \"$@\"\n"));
+    }
+}
@@ -0,0 +1,215 @@
+//! # Smoke Tests
+//!
+//! Runs a tool's `smoke_test:` command (e.g. `rg --version`, `hx --health`) right after it
+//! was installed or updated, so a binary that does not start on this machine fails the
+//! installation instead of the first real use.
+//!
+//! The command runs through `sh -c` in an empty temporary directory, without stdin, with the
+//! directory the tool was installed into first in `PATH`, and is killed after
+//! [`SMOKE_TEST_TIMEOUT`].
+
+use std::env;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use colored::Colorize;
+use tempfile::Builder as TempFileBuilder;
+
+use crate::schemas::path_resolver::PathResolver;
+use crate::{log_debug, log_info};
+
+/// How long a smoke test may run before it counts as failed.
+pub const SMOKE_TEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Output lines included in the failure message.
+const OUTPUT_TAIL_LINES: usize = 10;
+
+/// Runs `command` for the tool installed at `install_path`.
+///
+/// # Returns
+///
+/// * `Ok(())` - The command exited successfully
+/// * `Err(String)` - Why the smoke test failed, including the end of its output
+pub fn run(tool_name: &str, command: &str, install_path: &Path) -> Result<(), String> {
+    log_info!(
+        "[SDB::Tools::SmokeTest] Verifying {} with: {}",
+        tool_name.bold(),
+        command.cyan()
+    );
+
+    let work_dir = TempFileBuilder::new()
+        .prefix(&format!("setup-devbox-smoke-{tool_name}-"))
+        .tempdir_in(PathResolver::temp_root())
+        .map_err(|e| format!("failed to create a working directory: {e}"))?;
+    let output_path = work_dir.path().join("output.log");
+    let output =
+        File::create(&output_path).map_err(|e| format!("failed to capture the output: {e}"))?;
+    let stderr = output
+        .try_clone()
+        .map_err(|e| format!("failed to capture the output: {e}"))?;
+
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .current_dir(work_dir.path())
+        .env("PATH", search_path(install_path))
+        .stdin(Stdio::null())
+        .stdout(output)
+        .stderr(stderr)
+        .spawn()
+        .map_err(|e| format!("failed to run '{command}': {e}"))?;
+
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if started.elapsed() >= SMOKE_TEST_TIMEOUT => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!(
+                    "'{command}' did not finish within {}s{}",
+                    SMOKE_TEST_TIMEOUT.as_secs(),
+                    output_tail(&output_path)
+                ));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(100)),
+            Err(e) => return Err(format!("failed to wait for '{command}': {e}")),
+        }
+    };
+
+    if status.success() {
+        log_debug!(
+            "[SDB::Tools::SmokeTest] {} passed in {:.1}s",
+            tool_name,
+            started.elapsed().as_secs_f32()
+        );
+        Ok(())
+    } else {
+        Err(format!(
+            "'{command}' exited with {status}{}",
+            output_tail(&output_path)
+        ))
+    }
+}
+
+/// `PATH` with the tool's installation directory first.
+fn search_path(install_path: &Path) -> String {
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    if install_path.is_dir() {
+        dirs.push(install_path.to_path_buf());
+    } else if let Some(parent) = install_path.parent() {
+        dirs.push(parent.to_path_buf());
+    }
+    if let Some(path) = env::var_os("PATH") {
+        dirs.extend(env::split_paths(&path));
+    }
+    env::join_paths(dirs)
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// The last lines of the command's output, formatted for an error message.
+fn output_tail(output_path: &Path) -> String {
+    let output = fs::read_to_string(output_path).unwrap_or_default();
+    let lines: Vec<&str> = output.lines().collect();
+    let tail = &lines[lines.len().saturating_sub(OUTPUT_TAIL_LINES)..];
+    if tail.is_empty() {
+        String::new()
+    } else {
+        format!(":\n  {}", tail.join("\n  "))
+    }
+}
+
+/// A copy of the previously installed executable, restored when the update's smoke test fails.
+pub struct PreviousInstall {
+    path: PathBuf,
+    backup_dir: tempfile::TempDir,
+}
+
+impl PreviousInstall {
+    /// Copies the executable at `install_path` aside, if it is a regular file. Tools installed
+    /// as directories or linked by package managers are not backed up.
+    pub fn save(install_path: &str) -> Option<Self> {
+        let path = PathBuf::from(install_path);
+        if !fs::symlink_metadata(&path).is_ok_and(|metadata| metadata.is_file()) {
+            return None;
+        }
+        let backup_dir = TempFileBuilder::new()
+            .prefix("setup-devbox-previous-")
+            .tempdir_in(PathResolver::temp_root())
+            .ok()?;
+        fs::copy(&path, backup_dir.path().join("previous")).ok()?;
+        Some(Self { path, backup_dir })
+    }
+
+    /// Puts the previous executable back in place. Returns `true` on success.
+    pub fn restore(&self) -> bool {
+        match fs::copy(self.backup_dir.path().join("previous"), &self.path) {
+            Ok(_) => {
+                log_info!(
+                    "[SDB::Tools::SmokeTest] Restored the previous version at {}",
+                    self.path.display()
+                );
+                true
+            }
+            Err(e) => {
+                log_debug!(
+                    "[SDB::Tools::SmokeTest] Failed to restore {}: {}",
+                    self.path.display(),
+                    e
+                );
+                false
+            }
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn install_script(dir: &Path, name: &str, body: &str) -> PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_smoke_test_finds_the_installed_binary_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = install_script(dir.path(), "sdb-smoke-ok", "echo 1.0.0");
+        assert!(run("sdb-smoke-ok", "sdb-smoke-ok --version", &binary).is_ok());
+    }
+
+    #[test]
+    fn test_failed_smoke_test_reports_output_tail() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = install_script(
+            dir.path(),
+            "sdb-smoke-broken",
+            "echo 'error while loading shared libraries' >&2; exit 127",
+        );
+        let error = run("sdb-smoke-broken", "sdb-smoke-broken", &binary).unwrap_err();
+        assert!(error.contains("exited with"));
+        assert!(error.contains("error while loading shared libraries"));
+    }
+
+    #[test]
+    fn test_previous_install_is_restored() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = install_script(dir.path(), "tool", "echo old");
+        let previous = PreviousInstall::save(&binary.display().to_string()).unwrap();
+        fs::write(&binary, "new").unwrap();
+
+        assert!(previous.restore());
+        assert_eq!(
+            fs::read_to_string(&binary).unwrap(),
+            "#!/bin/sh\necho old\n"
+        );
+    }
+}
@@ -0,0 +1,253 @@
+//! Upstream version discovery for tools that have no release API of their own.
+//!
+//! URL tools can declare a `version_check:` block describing where their latest version is
+//! published, either as a page/JSON document fetched over HTTP or as a shell command:
+//!
+//! ```yaml
+//! version_check:
+//!   url: https://example.com/releases.json
+//!   json_path: $.latest.version
+//! ```
+//!
+//! ```yaml
+//! version_check:
+//!   url: https://example.com/downloads
+//!   regex: 'tool-v(\d+\.\d+\.\d+)\.tar\.gz'
+//! ```
+//!
+//! ```yaml
+//! version_check:
+//!   command: curl -s https://example.com/VERSION
+//! ```
+//!
+//! The planner and the installer both need a tool's latest version; discovered versions are
+//! kept for the rest of the run so each check runs once.
+
+use crate::core::assets::build_tool_request;
+use crate::log_debug;
+use crate::schemas::tools_types::{ToolEntry, VersionCheck};
+use colored::Colorize;
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+
+/// Versions discovered during the current run, keyed by tool name and check.
+static DISCOVERED: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+
+/// Forgets the versions discovered by the previous run. Called when a run starts.
+pub fn reset_cache() {
+    if let Some(discovered) = DISCOVERED.get() {
+        discovered.lock().unwrap().clear();
+    }
+}
+
+/// Discovers the latest upstream version of a tool using its `version_check` block.
+///
+/// HTTP checks reuse the tool's download settings (`headers`, `basic_auth`), so private
+/// artifact stores work the same way for version checks as for downloads.
+///
+/// A version discovered earlier in the run is returned without checking again. Failures are
+/// not kept, so a later caller retries and reports the error.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The discovered version, with surrounding whitespace removed
+/// * `Err(String)` - A description of why no version could be determined
+pub fn discover_version(tool_entry: &ToolEntry, check: &VersionCheck) -> Result<String, String> {
+    let key = format!(
+        "{}:{}",
+        tool_entry.name,
+        serde_json::to_string(check).unwrap_or_default()
+    );
+    let discovered = DISCOVERED.get_or_init(Default::default);
+    if let Some(version) = discovered.lock().unwrap().get(&key) {
+        log_debug!(
+            "[SDB::Tools::VersionCheck] Reusing version {} discovered for {}",
+            version.cyan(),
+            tool_entry.name.bold()
+        );
+        return Ok(version.clone());
+    }
+
+    let version = run_check(tool_entry, check)?;
+    discovered.lock().unwrap().insert(key, version.clone());
+    Ok(version)
+}
+
+fn run_check(tool_entry: &ToolEntry, check: &VersionCheck) -> Result<String, String> {
+    let text = match (&check.url, &check.command) {
+        (Some(url), _) => fetch_version_source(tool_entry, url)?,
+        (None, Some(command)) => run_version_command(command)?,
+        (None, None) => {
+            return Err("version_check requires either 'url' or 'command'".to_string());
+        }
+    };
+
+    let version = if let Some(path) = &check.json_path {
+        let json: Value = serde_json::from_str(&text)
+            .map_err(|e| format!("version_check response is not valid JSON: {e}"))?;
+        extract_json_path(&json, path)
+            .ok_or_else(|| format!("JSON path '{path}' did not match a value"))?
+    } else if let Some(pattern) = &check.regex {
+        extract_with_regex(&text, pattern)?
+            .ok_or_else(|| format!("regex '{pattern}' did not match"))?
+    } else {
+        // Without an extractor the source is expected to print just the version
+        text.lines()
+            .map(str::trim)
+            .rfind(|l| !l.is_empty())
+            .map(str::to_string)
+            .ok_or_else(|| "version_check produced no output".to_string())?
+    };
+
+    let version = version.trim().to_string();
+    if version.is_empty() {
+        return Err("version_check extracted an empty version".to_string());
+    }
+    log_debug!(
+        "[SDB::Tools::VersionCheck] Discovered version {} for {}",
+        version.cyan(),
+        tool_entry.name.bold()
+    );
+    Ok(version)
+}
+
+fn fetch_version_source(tool_entry: &ToolEntry, url: &str) -> Result<String, String> {
+    let url = shellexpand::env(url)
+        .map_err(|e| format!("failed to expand version_check url: {e}"))?
+        .into_owned();
+    // Version checks are reads, even when the download itself is a POST
+    build_tool_request(tool_entry, "GET", &url)?
+        .call()
+        .map_err(|e| format!("failed to fetch {url}: {e}"))?
+        .into_string()
+        .map_err(|e| format!("failed to read response from {url}: {e}"))
+}
+
+fn run_version_command(command: &str) -> Result<String, String> {
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .output()
+        .map_err(|e| format!("failed to run version_check command: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "version_check command exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Extracts a version from text using a regular expression.
+///
+/// The first capture group is used when the pattern has one; otherwise the whole match.
+pub fn extract_with_regex(text: &str, pattern: &str) -> Result<Option<String>, String> {
+    let re = Regex::new(pattern).map_err(|e| format!("invalid version_check regex: {e}"))?;
+    Ok(re.captures(text).and_then(|caps| {
+        caps.get(1)
+            .or_else(|| caps.get(0))
+            .map(|m| m.as_str().to_string())
+    }))
+}
+
+/// Resolves a simple JSONPath expression such as `$.latest.version` or `releases[0].tag`.
+///
+/// Only dotted member access and numeric array indices are supported, which covers the
+/// release/metadata documents version checks usually point at. String and number leaves
+/// are returned as strings.
+pub fn extract_json_path(value: &Value, path: &str) -> Option<String> {
+    let path = path.trim();
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let mut current = value;
+
+    for segment in path.split('.').filter(|s| !s.is_empty()) {
+        let (key, indices) = match segment.find('[') {
+            Some(pos) => (&segment[..pos], &segment[pos..]),
+            None => (segment, ""),
+        };
+        if !key.is_empty() {
+            current = current.get(key)?;
+        }
+        for index in indices.split('[').filter(|s| !s.is_empty()) {
+            let index: usize = index.strip_suffix(']')?.trim().parse().ok()?;
+            current = current.get(index)?;
+        }
+    }
+
+    match current {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_discovered_version_is_reused_until_reset() {
+        let dir = tempfile::tempdir().unwrap();
+        let runs = dir.path().join("runs");
+        let tool: ToolEntry =
+            serde_yaml::from_str("name: version-check-cache\nsource: url").unwrap();
+        let check: VersionCheck = serde_yaml::from_str(&format!(
+            "command: echo run >> '{}' && echo 'v1.2.3'",
+            runs.display()
+        ))
+        .unwrap();
+        let run_count = || std::fs::read_to_string(&runs).unwrap().lines().count();
+
+        assert_eq!(discover_version(&tool, &check).unwrap(), "v1.2.3");
+        assert_eq!(discover_version(&tool, &check).unwrap(), "v1.2.3");
+        assert_eq!(run_count(), 1);
+
+        reset_cache();
+        assert_eq!(discover_version(&tool, &check).unwrap(), "v1.2.3");
+        assert_eq!(run_count(), 2);
+    }
+
+    #[test]
+    fn test_extract_json_path() {
+        let doc = json!({
+            "latest": { "version": "1.4.2" },
+            "releases": [{ "tag": "v1.4.2" }, { "tag": "v1.4.1" }],
+            "build": 42
+        });
+        assert_eq!(
+            extract_json_path(&doc, "$.latest.version").as_deref(),
+            Some("1.4.2")
+        );
+        assert_eq!(
+            extract_json_path(&doc, "releases[1].tag").as_deref(),
+            Some("v1.4.1")
+        );
+        assert_eq!(extract_json_path(&doc, "$.build").as_deref(), Some("42"));
+        assert_eq!(extract_json_path(&doc, "$.latest"), None);
+        assert_eq!(extract_json_path(&doc, "$.missing.version"), None);
+    }
+
+    #[test]
+    fn test_extract_with_regex() {
+        let page = r#"<a href="tool-v2.3.0-linux.tar.gz">download</a>"#;
+        assert_eq!(
+            extract_with_regex(page, r"tool-v(\d+\.\d+\.\d+)")
+                .unwrap()
+                .as_deref(),
+            Some("2.3.0")
+        );
+        assert_eq!(
+            extract_with_regex(page, r"\d+\.\d+\.\d+")
+                .unwrap()
+                .as_deref(),
+            Some("2.3.0")
+        );
+        assert_eq!(extract_with_regex(page, r"nope-(\d+)").unwrap(), None);
+        assert!(extract_with_regex(page, r"(").is_err());
+    }
+}
@@ -0,0 +1,130 @@
+//! # WebAssembly Tools
+//!
+//! Some CLIs are distributed as a single `.wasm` module instead of native binaries. Such
+//! tools are installed like any other download (GitHub, URL or OCI); once the module is in
+//! place it is wrapped in a shim that runs it with a WebAssembly runtime:
+//!
+//! ```sh
+//! exec wasmtime run --dir=. ~/bin/.shim-targets/tool.wasm -- "$@"
+//! ```
+//!
+//! The runtime (`wasm_runtime:`, `wasmtime` by default) is installed first if it is missing,
+//! with Homebrew when available and the runtime's official installer otherwise. The current
+//! directory is made accessible to the module, as CLIs usually work on files in it.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use colored::Colorize;
+
+use crate::core::platform::find_in_path;
+use crate::schemas::tools_enums::WasmRuntime;
+use crate::schemas::tools_types::{ShimConfig, ToolEntry};
+use crate::{log_debug, log_info};
+
+/// Leading bytes of every WebAssembly binary module.
+const WASM_MAGIC: [u8; 4] = *b"\0asm";
+
+/// Whether the file at `path` is a WebAssembly module.
+pub fn is_wasm_module(path: &Path) -> bool {
+    let mut magic = [0u8; 4];
+    File::open(path)
+        .and_then(|mut file| file.read_exact(&mut magic))
+        .is_ok_and(|()| magic == WASM_MAGIC)
+}
+
+/// Whether a freshly installed tool has to run through a WebAssembly runtime.
+pub fn is_wasm_tool(tool: &ToolEntry, install_path: &Path) -> bool {
+    tool.wasm_runtime.is_some() || is_wasm_module(install_path)
+}
+
+/// Returns the shim launching `tool` with its runtime, installing the runtime if needed.
+///
+/// The tool's own `shim:` environment and working directory are kept; a custom `template`
+/// replaces the generated launcher entirely.
+pub fn shim_config(tool: &ToolEntry) -> Result<ShimConfig, String> {
+    let runtime = tool.wasm_runtime.unwrap_or_default();
+    let runtime_path = match locate_runtime(runtime) {
+        Some(path) => path,
+        None => install_runtime(runtime)?,
+    };
+    log_debug!(
+        "[SDB::Tools::Wasm] Running {} with {}",
+        tool.name,
+        runtime_path.display()
+    );
+
+    let mut shim = tool.shim.clone().unwrap_or_default();
+    if shim.template.is_none() {
+        shim.template = Some(launcher_template(&runtime_path));
+    }
+    Ok(shim)
+}
+
+/// Shim template running the module (`{{target}}`) with the runtime at `runtime_path`.
+fn launcher_template(runtime_path: &Path) -> String {
+    format!(
+        "#!/bin/sh\n\
+         # Generated by setup-devbox for {{{{name}}}}. Changes are overwritten on the next install.\n\
+         {{{{env}}}}{{{{cd}}}}exec \"{}\" run --dir=. {{{{target}}}} -- \"$@\"\n",
+        runtime_path.display()
+    )
+}
+
+/// Finds the runtime on `PATH` or where its official installer puts it.
+fn locate_runtime(runtime: WasmRuntime) -> Option<PathBuf> {
+    let command = runtime.to_string();
+    find_in_path(&command).or_else(|| {
+        let home = dirs::home_dir()?;
+        let path = home.join(format!(".{command}/bin/{command}"));
+        path.is_file().then_some(path)
+    })
+}
+
+/// Installs a missing runtime with Homebrew, or with its official installer script.
+fn install_runtime(runtime: WasmRuntime) -> Result<PathBuf, String> {
+    log_info!(
+        "[SDB::Tools::Wasm] Installing the {} runtime for WebAssembly tools...",
+        runtime.to_string().cyan()
+    );
+    let mut command = if find_in_path("brew").is_some() {
+        let mut command = Command::new("brew");
+        command.args(["install", &runtime.to_string()]);
+        command
+    } else {
+        let script = match runtime {
+            WasmRuntime::Wasmtime => "curl -sSf https://wasmtime.dev/install.sh | bash",
+            WasmRuntime::Wasmer => "curl -sSfL https://get.wasmer.io | sh",
+        };
+        let mut command = Command::new("sh");
+        command.args(["-c", script]);
+        command
+    };
+
+    let status = command
+        .status()
+        .map_err(|e| format!("failed to install {runtime}: {e}"))?;
+    if !status.success() {
+        return Err(format!("installing {runtime} exited with {status}"));
+    }
+    locate_runtime(runtime)
+        .ok_or_else(|| format!("{runtime} was installed but its executable was not found"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launcher_template() {
+        let template = launcher_template(Path::new("/opt/wasmtime"));
+        assert!(template.starts_with("#!/bin/sh\n# Generated by setup-devbox for {{name}}."));
+        assert!(
+            template.ends_with(
+                "{{env}}{{cd}}exec \"/opt/wasmtime\" run --dir=. {{target}} -- \"$@\"\n"
+            )
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! # Windows Support
+//!
+//! GitHub and URL tools on Windows are installed into `%LOCALAPPDATA%\setup-devbox\bin`
+//! instead of `~/bin`. Executables there carry an `.exe` extension (added to the tool name
+//! or `rename_to` when it has none), and the directory is added to the user's `Path` in the
+//! registry so new shells find the tools. `.msi` installers are run per user through
+//! `msiexec` into `%LOCALAPPDATA%\Programs\<tool>`.
+//!
+//! The path and name helpers are plain string functions so they behave the same (and are
+//! tested) on every platform; only the parts that run Windows programs are `cfg(windows)`.
+
+use std::env;
+use std::path::PathBuf;
+#[cfg(windows)]
+use std::{
+    io,
+    path::Path,
+    process::Command,
+    sync::{Mutex, OnceLock},
+};
+
+#[cfg(windows)]
+use colored::Colorize;
+
+#[cfg(windows)]
+use crate::{log_debug, log_info, log_warn};
+
+/// Extensions Windows runs directly (the defaults of `PATHEXT`).
+const EXECUTABLE_EXTENSIONS: [&str; 4] = ["exe", "bat", "cmd", "com"];
+
+/// The bin directory for GitHub and URL tools: `%LOCALAPPDATA%\setup-devbox\bin`.
+/// `None` when `LOCALAPPDATA` is not set.
+pub fn bin_dir() -> Option<PathBuf> {
+    let local_app_data = env::var_os("LOCALAPPDATA")?;
+    Some(
+        PathBuf::from(local_app_data)
+            .join("setup-devbox")
+            .join("bin"),
+    )
+}
+
+/// `name` with an `.exe` extension unless it already ends in one Windows can run.
+pub fn with_exe_extension(name: &str) -> String {
+    if has_executable_extension(name) {
+        name.to_string()
+    } else {
+        format!("{name}.exe")
+    }
+}
+
+/// The file name an installed executable gets on this platform: `name` itself, or
+/// `name.exe` on Windows.
+pub fn platform_file_name(name: &str) -> String {
+    if cfg!(windows) {
+        with_exe_extension(name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// The file names a command called `name` may have on this platform: `name` itself, or on
+/// Windows `name` with each executable extension unless it already carries one.
+pub fn command_file_names(name: &str) -> Vec<String> {
+    if !cfg!(windows) || has_executable_extension(name) {
+        return vec![name.to_string()];
+    }
+    EXECUTABLE_EXTENSIONS
+        .iter()
+        .map(|ext| format!("{name}.{ext}"))
+        .collect()
+}
+
+/// Whether `name` ends in an extension Windows runs directly (`.exe`, `.bat`, `.cmd`, `.com`).
+pub fn has_executable_extension(name: &str) -> bool {
+    name.rsplit_once('.').is_some_and(|(_, ext)| {
+        EXECUTABLE_EXTENSIONS
+            .iter()
+            .any(|known| ext.eq_ignore_ascii_case(known))
+    })
+}
+
+/// The `Path` value with `dir` appended, or `None` if `dir` is already one of its entries.
+/// Entries are compared case-insensitively and without trailing separators, as Windows does.
+pub fn path_with_entry(current: &str, dir: &str) -> Option<String> {
+    let normalize = |entry: &str| entry.trim().trim_end_matches(['\\', '/']).to_lowercase();
+    let wanted = normalize(dir);
+    if current.split(';').any(|entry| normalize(entry) == wanted) {
+        return None;
+    }
+    let current = current.trim_end_matches(';');
+    Some(if current.is_empty() {
+        dir.to_string()
+    } else {
+        format!("{current};{dir}")
+    })
+}
+
+/// Adds `dir` to the user's `Path` in the registry (`HKCU\Environment`) through PowerShell,
+/// which also notifies running programs of the change. Checked once per directory and run.
+/// Failures are only warned about: the tool is installed, it just has to be called by path.
+#[cfg(windows)]
+pub fn ensure_on_user_path(dir: &Path) {
+    static CHECKED: OnceLock<Mutex<Vec<PathBuf>>> = OnceLock::new();
+    let mut checked = CHECKED
+        .get_or_init(|| Mutex::new(Vec::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if checked.iter().any(|seen| seen == dir) {
+        return;
+    }
+    checked.push(dir.to_path_buf());
+
+    let dir = dir.to_string_lossy();
+    let current = match powershell("[Environment]::GetEnvironmentVariable('Path', 'User')") {
+        Ok(current) => current,
+        Err(e) => {
+            log_warn!(
+                "[SDB::Windows] Cannot read the user Path; add {} to it yourself: {}",
+                dir.cyan(),
+                e
+            );
+            return;
+        }
+    };
+    let Some(updated) = path_with_entry(current.trim(), &dir) else {
+        log_debug!("[SDB::Windows] {} is already on the user Path", dir);
+        return;
+    };
+    let script = format!(
+        "[Environment]::SetEnvironmentVariable('Path', '{}', 'User')",
+        updated.replace('\'', "''")
+    );
+    match powershell(&script) {
+        Ok(_) => log_info!(
+            "[SDB::Windows] Added {} to the user Path; open a new terminal to use it",
+            dir.cyan()
+        ),
+        Err(e) => log_warn!(
+            "[SDB::Windows] Cannot add {} to the user Path: {}",
+            dir.cyan(),
+            e
+        ),
+    }
+}
+
+/// Installs an `.msi` package for the current user without any UI and returns the directory
+/// it was installed into (`%LOCALAPPDATA%\Programs\<name>`). Packages that ignore
+/// `INSTALLDIR` keep their own location; their executables are expected on `Path` already.
+#[cfg(windows)]
+pub fn install_msi(msi_path: &Path, tool_source: &str, name: &str) -> io::Result<PathBuf> {
+    let local_app_data = env::var_os("LOCALAPPDATA")
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "LOCALAPPDATA is not set"))?;
+    let install_dir = PathBuf::from(local_app_data).join("Programs").join(name);
+
+    log_debug!(
+        "[SDB::Tools::{tool_source}::WindowsInstaller] Running msiexec for {} into {}",
+        msi_path.display(),
+        install_dir.display()
+    );
+    let status = Command::new("msiexec")
+        .arg("/i")
+        .arg(msi_path)
+        .args(["/qn", "/norestart", "ALLUSERS=2", "MSIINSTALLPERUSER=1"])
+        .arg(format!("INSTALLDIR={}", install_dir.display()))
+        .status()?;
+    // 3010: installed, a restart is required to complete it
+    match status.code() {
+        Some(0) => {}
+        Some(3010) => log_warn!(
+            "[SDB::Tools::{tool_source}::WindowsInstaller] {} needs a restart to finish installing",
+            name.yellow()
+        ),
+        code => {
+            return Err(io::Error::other(format!(
+                "msiexec exited with {}",
+                code.map_or_else(|| "no status".to_string(), |code| code.to_string())
+            )));
+        }
+    }
+
+    ensure_on_user_path(&install_dir);
+    Ok(install_dir)
+}
+
+/// Runs a PowerShell command and returns its standard output.
+#[cfg(windows)]
+fn powershell(script: &str) -> io::Result<String> {
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", script])
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exe_names_and_path_entries() {
+        assert_eq!(with_exe_extension("rg"), "rg.exe");
+        assert_eq!(with_exe_extension("rg.EXE"), "rg.EXE");
+        assert_eq!(with_exe_extension("build.cmd"), "build.cmd");
+        assert_eq!(with_exe_extension("node-v20.1"), "node-v20.1.exe");
+
+        let dir = r"C:\Users\me\AppData\Local\setup-devbox\bin";
+        assert_eq!(path_with_entry("", dir), Some(dir.to_string()));
+        assert_eq!(
+            path_with_entry(r"C:\Windows;", dir),
+            Some(format!(r"C:\Windows;{dir}"))
+        );
+        assert_eq!(
+            path_with_entry(
+                r"C:\Windows;c:\users\me\appdata\local\setup-devbox\bin\",
+                dir
+            ),
+            None
+        );
+    }
+}
@@ -21,8 +21,8 @@
 //! - Efficient format conversion with minimal intermediate representations
 
 pub(crate) use crate::schemas::config_manager::{
-    ConfigurationEvaluationResult, ConfigurationManager, ConfigurationManagerProcessor,
-    ConfigurationManagerState,
+    ConfigurationEvaluationResult, ConfigurationFormat, ConfigurationManager,
+    ConfigurationManagerProcessor, ConfigurationManagerState, ConfigurationPath,
 };
 use crate::schemas::path_resolver::PathResolver;
 use crate::{log_debug, log_info, log_warn};
@@ -31,7 +31,10 @@ use serde_json::Value as JsonValue;
 use serde_yaml::Value as YamlValue;
 use sha2::{Digest, Sha256};
 use std::fs;
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
+use std::process::Command;
 use toml::Value as TomlValue;
 
 // ============================================================================
@@ -91,8 +94,7 @@ impl ConfigurationManagerProcessor {
 
         let source_paths = self
             .build_configuration_source_paths(&config_manager.tools_configuration_paths, tool_name);
-        let destination_paths =
-            PathResolver::expand_paths(&config_manager.tools_configuration_paths)?;
+        let destination_paths = PathResolver::expand_paths(&config_manager.destination_paths())?;
 
         // If the source file doesn't exist, we can't do anything.
         let existing_source_paths: Vec<&PathBuf> =
@@ -284,10 +286,13 @@ impl ConfigurationManagerProcessor {
         // If an update is needed, perform the file copy and conversion.
         let source_paths = self
             .build_configuration_source_paths(&config_manager.tools_configuration_paths, tool_name);
-        let destination_paths =
-            PathResolver::expand_paths(&config_manager.tools_configuration_paths)?;
+        let destination_paths = PathResolver::expand_paths(&config_manager.destination_paths())?;
 
-        self.update_configuration_file(&source_paths, &destination_paths)?;
+        self.update_configuration_file(
+            &config_manager.tools_configuration_paths,
+            &source_paths,
+            &destination_paths,
+        )?;
 
         // Use the cached destination SHA if available, otherwise calculate it
         let destination_sha = match evaluation.current_destination_sha {
@@ -298,7 +303,7 @@ impl ConfigurationManagerProcessor {
         // Return the new state so it can be saved for the next run.
         Ok(Some(ConfigurationManagerState::new(
             true,
-            config_manager.tools_configuration_paths.clone(),
+            config_manager.destination_paths(),
             evaluation.current_source_sha,
             destination_sha,
         )))
@@ -308,7 +313,9 @@ impl ConfigurationManagerProcessor {
     ///
     /// Source files are expected to be in TOML format and named after the tool.
     /// For example, a tool named "tool_name" would have its configuration at
-    /// `[config_base_path]/tool_name/config_name.toml`.
+    /// `[config_base_path]/tool_name/config_name.toml`. Destinations in `raw` format
+    /// are copied from a file with the destination's own name,
+    /// `[config_base_path]/tool_name/kitty.conf`.
     ///
     /// ## Parameters
     /// - `destination_paths`: List of destination configuration file paths
@@ -318,18 +325,26 @@ impl ConfigurationManagerProcessor {
     /// Vector of `PathBuf` objects representing source file paths
     fn build_configuration_source_paths(
         &self,
-        destination_paths: &[String],
+        destination_paths: &[ConfigurationPath],
         tool_name: &str,
     ) -> Vec<PathBuf> {
         destination_paths
             .iter()
-            .map(|destination_path| {
+            .map(|destination| {
                 // Refinement: Use rsplit().next() instead of split().last() for efficiency.
-                let source_config_filename = destination_path
+                let source_config_filename = destination
+                    .path()
                     .rsplit('/') // Start splitting from the end
                     .next() // Get the first item from the reverse iteration (the filename)
                     .expect("Destination path must contain a filename");
 
+                if destination.format() == Some(ConfigurationFormat::Raw) {
+                    return self
+                        .config_base_path
+                        .join(tool_name)
+                        .join(source_config_filename);
+                }
+
                 let filename = if source_config_filename.contains('.') {
                     source_config_filename.split('.').next().unwrap()
                 } else {
@@ -346,10 +361,11 @@ impl ConfigurationManagerProcessor {
     /// Updates the destination configuration file from the source file.
     ///
     /// This method performs a read-convert-write operation. It reads the TOML source,
-    /// converts it to the format specified by the destination file's extension,
-    /// and writes the result.
+    /// converts it to the destination's configured format, or the one its extension
+    /// implies, and writes the result. `raw` destinations are copied verbatim.
     ///
     /// ## Parameters
+    /// - `destinations`: Configured destinations, with their explicit formats
     /// - `source_paths`: List of source file paths to read from
     /// - `destination_paths`: List of destination file paths to write to
     ///
@@ -360,6 +376,7 @@ impl ConfigurationManagerProcessor {
     /// Returns error if file operations or format conversion fails
     fn update_configuration_file(
         &self,
+        destinations: &[ConfigurationPath],
         source_paths: &[PathBuf],
         destination_paths: &[PathBuf],
     ) -> Result<(), Box<dyn std::error::Error>> {
@@ -374,7 +391,11 @@ impl ConfigurationManagerProcessor {
         }
 
         // Process each source-destination pair
-        for (source_path, destination_path) in source_paths.iter().zip(destination_paths.iter()) {
+        for ((source_path, destination_path), destination) in source_paths
+            .iter()
+            .zip(destination_paths.iter())
+            .zip(destinations)
+        {
             log_debug!(
                 "[SDB::Tools::Configuration::ConfigurationManager] Updating configuration from {} to {}",
                 source_path.display().to_string().blue(),
@@ -386,16 +407,28 @@ impl ConfigurationManagerProcessor {
                 fs::create_dir_all(parent)?;
             }
 
-            // Read the source TOML content.
-            let source_content = fs::read_to_string(source_path)?;
-            let toml_value: TomlValue = toml::from_str(&source_content)?;
-
-            // Convert the TOML data to the target format based on the destination's file extension.
-            let converted_content =
-                self.convert_toml_to_target_format(&toml_value, destination_path)?;
-
-            // Write the converted content to the destination file.
-            fs::write(destination_path, converted_content)?;
+            let format = destination
+                .format()
+                .unwrap_or_else(|| self.format_from_extension(destination_path));
+            if format == ConfigurationFormat::Raw {
+                fs::write(destination_path, fs::read(source_path)?)?;
+            } else {
+                // Read the source TOML content.
+                let source_content = fs::read_to_string(source_path)?;
+                let toml_value: TomlValue = toml::from_str(&source_content)?;
+
+                // Convert the TOML data to the destination's format.
+                let converted_content = self.convert_toml_to_target_format(&toml_value, format)?;
+
+                // Write the converted content to the destination file.
+                fs::write(destination_path, converted_content)?;
+            }
+            self.apply_file_attributes(
+                destination,
+                source_path,
+                destination_path,
+                format == ConfigurationFormat::Raw,
+            )?;
             log_info!(
                 "[SDB::Tools::Configuration] Configuration written to: {}",
                 destination_path
@@ -409,17 +442,74 @@ impl ConfigurationManagerProcessor {
         Ok(())
     }
 
-    /// Converts a TOML value into a target format based on the file extension.
+    /// Applies the destination's `mode:` and `owner:` to the written file. Without a `mode:`,
+    /// raw copies keep the executable bits of their source, so managed scripts run right away.
+    /// An `owner:` that can't be set (e.g. without root) is only warned about.
+    ///
+    /// ## Errors
+    /// Returns error if the mode is invalid or the permissions can't be changed
+    fn apply_file_attributes(
+        &self,
+        destination: &ConfigurationPath,
+        source_path: &Path,
+        destination_path: &Path,
+        raw: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        apply_file_mode(destination, source_path, destination_path, raw)?;
+
+        if let Some(owner) = destination.owner() {
+            match Command::new("chown")
+                .arg(owner)
+                .arg(destination_path)
+                .output()
+            {
+                Ok(output) if output.status.success() => log_debug!(
+                    "[SDB::Tools::Configuration] Changed owner of {} to {}",
+                    destination_path.display(),
+                    owner
+                ),
+                Ok(output) => log_warn!(
+                    "[SDB::Tools::Configuration] Could not change owner of {} to {}: {}",
+                    destination_path.display().to_string().yellow(),
+                    owner,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+                Err(e) => log_warn!(
+                    "[SDB::Tools::Configuration] Could not run chown for {}: {}",
+                    destination_path.display().to_string().yellow(),
+                    e
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Infers the format of a destination without an explicit `format:` from its extension.
     ///
-    /// Supported formats:
-    /// - `.json`: Converts TOML to pretty-printed JSON.
-    /// - `.yaml` or `.yml`: Converts TOML to YAML.
-    /// - `.toml`: Re-serializes the TOML value with pretty printing.
-    /// - Any other extension: Flattens the TOML into a simple `KEY=VALUE` format.
+    /// - `.json`: pretty-printed JSON.
+    /// - `.yaml` or `.yml`: YAML.
+    /// - `.toml`: pretty-printed TOML.
+    /// - Any other extension: a simple `KEY=VALUE` format.
+    fn format_from_extension(&self, destination_path: &Path) -> ConfigurationFormat {
+        let extension = destination_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("");
+
+        match extension.to_lowercase().as_str() {
+            "json" => ConfigurationFormat::Json,
+            "yaml" | "yml" => ConfigurationFormat::Yaml,
+            "toml" => ConfigurationFormat::Toml,
+            _ => ConfigurationFormat::Keyvalue,
+        }
+    }
+
+    /// Converts a TOML value into the given format. `raw` is never converted, so it
+    /// re-serializes the TOML unchanged.
     ///
     /// ## Parameters
     /// - `toml_value`: TOML value to convert
-    /// - `destination_path`: Destination file path (determines target format)
+    /// - `format`: Format to write
     ///
     /// ## Returns
     /// `Ok(String)` with converted content, `Err` if conversion fails
@@ -429,24 +519,22 @@ impl ConfigurationManagerProcessor {
     fn convert_toml_to_target_format(
         &self,
         toml_value: &TomlValue,
-        destination_path: &Path,
+        format: ConfigurationFormat,
     ) -> Result<String, Box<dyn std::error::Error>> {
-        let extension = destination_path
-            .extension()
-            .and_then(|ext| ext.to_str())
-            .unwrap_or("");
-
-        match extension.to_lowercase().as_str() {
-            "json" => {
+        match format {
+            ConfigurationFormat::Json => {
                 let json_value = self.toml_to_json(toml_value)?;
                 Ok(serde_json::to_string_pretty(&json_value)?)
             }
-            "yaml" | "yml" => {
+            ConfigurationFormat::Yaml => {
                 let yaml_value = self.toml_to_yaml(toml_value)?;
                 Ok(serde_yaml::to_string(&yaml_value)?)
             }
-            "toml" => Ok(toml::to_string_pretty(toml_value)?),
-            _ => Ok(self.toml_to_key_value(toml_value)),
+            ConfigurationFormat::Toml | ConfigurationFormat::Raw => {
+                Ok(toml::to_string_pretty(toml_value)?)
+            }
+            ConfigurationFormat::Ini => Ok(toml_to_ini(toml_value)),
+            ConfigurationFormat::Keyvalue => Ok(self.toml_to_key_value(toml_value)),
         }
     }
 
@@ -656,3 +744,186 @@ impl ConfigurationManagerProcessor {
         Ok(format!("{:x}", hasher.finalize()))
     }
 }
+
+/// Sets the destination's `mode:`, or for raw copies without one, the executable bits of the
+/// source.
+#[cfg(unix)]
+fn apply_file_mode(
+    destination: &ConfigurationPath,
+    source_path: &Path,
+    destination_path: &Path,
+    raw: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mode = match destination.mode()? {
+        Some(mode) => Some(mode),
+        None if raw => {
+            let executable_bits = fs::metadata(source_path)?.permissions().mode() & 0o111;
+            let current = fs::metadata(destination_path)?.permissions().mode();
+            Some(current | executable_bits).filter(|_| executable_bits != 0)
+        }
+        None => None,
+    };
+    if let Some(mode) = mode {
+        fs::set_permissions(destination_path, fs::Permissions::from_mode(mode))?;
+        log_debug!(
+            "[SDB::Tools::Configuration] Set mode {:o} on {}",
+            mode & 0o7777,
+            destination_path.display()
+        );
+    }
+    Ok(())
+}
+
+/// File modes don't exist outside Unix; a configured `mode:` is validated and then ignored.
+#[cfg(not(unix))]
+fn apply_file_mode(
+    destination: &ConfigurationPath,
+    _source_path: &Path,
+    destination_path: &Path,
+    _raw: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if destination.mode()?.is_some() {
+        log_warn!(
+            "[SDB::Tools::Configuration] Ignoring mode: for {}, file modes are Unix-only",
+            destination_path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Renders a TOML document as INI: top-level keys first, then a `[section]` per table with
+/// nested tables as `[parent.child]`. Arrays become repeated keys, which is how multi-valued
+/// options are written in git config and similar files.
+fn toml_to_ini(toml_value: &TomlValue) -> String {
+    let mut lines = Vec::new();
+    if let TomlValue::Table(table) = toml_value {
+        write_ini_section(table, "", &mut lines);
+    }
+    lines.join("\n") + "\n"
+}
+
+fn write_ini_section(table: &toml::Table, name: &str, lines: &mut Vec<String>) {
+    let is_table = |value: &TomlValue| match value {
+        TomlValue::Table(_) => true,
+        TomlValue::Array(items) => !items.is_empty() && items.iter().all(TomlValue::is_table),
+        _ => false,
+    };
+
+    let mut entries = Vec::new();
+    for (key, value) in table.iter().filter(|(_, value)| !is_table(value)) {
+        match value {
+            TomlValue::Array(items) => {
+                entries.extend(
+                    items
+                        .iter()
+                        .map(|item| format!("{key} = {}", ini_value(item))),
+                );
+            }
+            _ => entries.push(format!("{key} = {}", ini_value(value))),
+        }
+    }
+    if !entries.is_empty() {
+        if !name.is_empty() {
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+            lines.push(format!("[{name}]"));
+        }
+        lines.extend(entries);
+    }
+
+    for (key, value) in table.iter().filter(|(_, value)| is_table(value)) {
+        let section = if name.is_empty() {
+            key.clone()
+        } else {
+            format!("{name}.{key}")
+        };
+        match value {
+            TomlValue::Table(child) => write_ini_section(child, &section, lines),
+            TomlValue::Array(items) => {
+                for child in items.iter().filter_map(TomlValue::as_table) {
+                    write_ini_section(child, &section, lines);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn ini_value(value: &TomlValue) -> String {
+    match value {
+        TomlValue::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toml_to_ini() {
+        let value: TomlValue = toml::from_str(
+            r#"
+root = true
+
+[user]
+name = "Jane Doe"
+email = "jane@example.com"
+
+[delta.side-by-side]
+enabled = true
+
+[credential]
+helper = ["", "osxkeychain"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            toml_to_ini(&value),
+            "root = true\n\n[credential]\nhelper = \nhelper = osxkeychain\n\n\
+             [delta.side-by-side]\nenabled = true\n\n\
+             [user]\nemail = jane@example.com\nname = Jane Doe\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_apply_file_mode() {
+        let dir = tempfile::tempdir().unwrap();
+        let (source, destination) = (dir.path().join("source"), dir.path().join("destination"));
+        fs::write(&source, "").unwrap();
+        fs::write(&destination, "").unwrap();
+        let mode_of = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o7777;
+        let path = |yaml: &str| serde_yaml::from_str::<ConfigurationPath>(yaml).unwrap();
+
+        // An explicit mode, given as a YAML number or string
+        apply_file_mode(
+            &path("{ path: x, mode: 0600 }"),
+            &source,
+            &destination,
+            false,
+        )
+        .unwrap();
+        assert_eq!(mode_of(&destination), 0o600);
+        apply_file_mode(
+            &path("{ path: x, mode: '0640' }"),
+            &source,
+            &destination,
+            true,
+        )
+        .unwrap();
+        assert_eq!(mode_of(&destination), 0o640);
+
+        // Raw copies without a mode keep the source's executable bits
+        fs::set_permissions(&source, fs::Permissions::from_mode(0o755)).unwrap();
+        apply_file_mode(&path("x"), &source, &destination, false).unwrap();
+        assert_eq!(mode_of(&destination), 0o640);
+        apply_file_mode(&path("x"), &source, &destination, true).unwrap();
+        assert_eq!(mode_of(&destination), 0o751);
+
+        assert!(
+            apply_file_mode(&path("{ path: x, mode: rwx }"), &source, &destination, true).is_err()
+        );
+    }
+}
@@ -1,3 +1,4 @@
 pub(crate) mod orchestrator;
 pub(crate) mod planner;
+pub(crate) mod progress;
 pub(crate) mod summary;
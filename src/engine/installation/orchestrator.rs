@@ -21,24 +21,39 @@
 //! 3. **Execution**: Perform installation, update, or configuration synchronization
 //! 4. **State Update**: Record results and update persistent state
 
+use crate::core::inventory::{self, PackageManager};
+use crate::core::lifecycle_hooks::{self, LifecycleEvent};
+use crate::core::requirements::check_requirements;
+use crate::core::smoke_test::{self, PreviousInstall};
+use crate::core::{
+    app_bundle, delta, disk_usage, installer_version, licenses, name_collisions, optimize_binary,
+    permissions, presets, protected, renames, rollout, shims, wasm,
+};
 // Import all available installer modules
 use crate::engine::installation::planner::InstallationPlanner;
+use crate::engine::installation::progress::RunProgress;
 use crate::engine::installers::errors::InstallerError;
 use crate::engine::installers::factory::InstallerFactory;
+use crate::engine::installers::github::{self, ReleaseSelector};
+use crate::engine::installers::simulated;
 // Import utility functions for state and time management
-use crate::core::platform::check_installer_command_available;
 // Import logging macros
 use crate::schemas::config_manager::{
     ConfigurationEvaluationResult, ConfigurationManagerProcessor,
 };
 // Import data schemas and the configuration processor
-use crate::schemas::state_file::{DevBoxState, ToolState};
-use crate::schemas::tools_enums::{SourceType, ToolAction, ToolProcessingResult};
+use crate::schemas::state_file::{DevBoxState, LicenseState, ToolState};
+use crate::schemas::tools_enums::{SourceType, ToolAction, ToolPhase, ToolProcessingResult};
 use crate::schemas::tools_types::{
     InstallationConfiguration, ToolEntry, ToolInstallationOrchestrator,
 };
+use crate::state::autosave::StateAutosave;
+use crate::state::checkpoint::RunCheckpoint;
+use crate::state::receipts::{self, FileSnapshot};
 use crate::{log_debug, log_error, log_info, log_warn};
 use colored::Colorize;
+use std::path::Path;
+use std::time::Instant;
 
 // ============================================================================
 // TOOL INSTALLATION ORCHESTRATOR IMPLEMENTATION
@@ -53,6 +68,8 @@ impl<'a> ToolInstallationOrchestrator<'a> {
     /// ## Parameters
     /// - `state`: Mutable reference to the application state
     /// - `configuration`: Reference to installation configuration settings
+    /// - `checkpoint`: Progress of the current run, used to resume interrupted runs
+    /// - `autosave`: Writes the state file as tools finish; `None` for dry runs
     ///
     /// ## Returns
     /// New `ToolInstallationOrchestrator` instance
@@ -61,33 +78,139 @@ impl<'a> ToolInstallationOrchestrator<'a> {
         configuration: &'a InstallationConfiguration,
         config_processor: ConfigurationManagerProcessor,
         installer_factory: InstallerFactory,
+        checkpoint: Option<&'a mut RunCheckpoint>,
+        autosave: Option<StateAutosave>,
     ) -> Self {
         Self {
             state,
             configuration,
             config_processor,
             installer_factory,
+            checkpoint,
+            autosave,
         }
     }
 
     /// Iterates through all tools in the configuration and processes each one.
     ///
+    /// Tools are processed phase by phase (`bootstrap` → `core` → `extras`). The sort is
+    /// stable, so the dependency order computed by the config loader is kept within a phase.
+    ///
     /// ## Parameters
     /// - `tools`: Slice of tool entries to process
     ///
     /// ## Returns
-    /// Vector of tuples containing tool names and their processing results
+    /// Vector of tuples containing tool names, their phase and their processing results
     pub(crate) fn process_all_tools(
         &mut self,
         tools: &[ToolEntry],
-    ) -> Vec<(String, ToolProcessingResult)> {
-        tools
-            .iter()
+    ) -> Vec<(String, ToolPhase, ToolProcessingResult)> {
+        let mut ordered: Vec<&ToolEntry> = tools.iter().collect();
+        ordered.sort_by_key(|tool| tool.effective_phase());
+        warn_about_cross_phase_dependencies(&ordered);
+        self.prefetch_github_releases(&ordered);
+        self.snapshot_inventory(&ordered);
+
+        let multiple_phases = ordered
+            .first()
+            .zip(ordered.last())
+            .is_some_and(|(first, last)| first.effective_phase() != last.effective_phase());
+        let mut current_phase = None;
+        let mut progress = RunProgress::new(&ordered, self.state);
+
+        let results = ordered
+            .into_iter()
             .map(|tool| {
-                let result = self.process_individual_tool(tool);
-                (tool.name.clone(), result)
+                let phase = tool.effective_phase();
+                if multiple_phases && current_phase != Some(phase) {
+                    log_info!(
+                        "[SDB::Tools] Phase: {}",
+                        phase.to_string().bright_cyan().bold()
+                    );
+                    current_phase = Some(phase);
+                }
+                if let Some(autosave) = self.autosave.as_mut() {
+                    autosave.save_if_due(self.state);
+                }
+                if !self.configuration.dry_run {
+                    progress.report_next();
+                }
+                let result = self.process_with_checkpoint(tool);
+                if let Some(autosave) = self.autosave.as_mut() {
+                    autosave.record(&result);
+                }
+                (tool.name.clone(), phase, result)
             })
-            .collect()
+            .collect();
+        if let Some(autosave) = self.autosave.as_mut() {
+            autosave.flush(self.state);
+        }
+        results
+    }
+
+    /// Fetches the release metadata of GitHub tools that are missing or pinned to a different
+    /// tag than the installed one, concurrently and ahead of the sequential installation.
+    /// Installed tools following the latest release are only looked up once they are due.
+    fn prefetch_github_releases(&self, tools: &[&ToolEntry]) {
+        if self.configuration.dry_run || simulated::is_active() {
+            return;
+        }
+        let pending: Vec<&ToolEntry> = tools
+            .iter()
+            .copied()
+            .filter(|tool| tool.source == SourceType::Github)
+            .filter(|tool| {
+                self.state.tools.get(&tool.name).is_none_or(|installed| {
+                    matches!(github::release_selector(tool), ReleaseSelector::Tag(tag)
+                        if InstallationPlanner::normalize_version(tag)
+                            != InstallationPlanner::normalize_version(&installed.version))
+                })
+            })
+            .collect();
+        github::prefetch_releases(&pending, false);
+    }
+
+    /// Lists the installed packages of the package managers the tools use, so the installers'
+    /// "already installed" checks don't query them once per tool.
+    fn snapshot_inventory(&self, tools: &[&ToolEntry]) {
+        if self.configuration.dry_run || simulated::is_active() {
+            return;
+        }
+        let mut managers: Vec<PackageManager> = tools
+            .iter()
+            .filter_map(|tool| PackageManager::of(&tool.source))
+            .collect();
+        managers.sort_unstable();
+        managers.dedup();
+        inventory::take_snapshot(&managers);
+    }
+
+    /// Processes a tool unless the checkpoint shows it already finished in an interrupted
+    /// run, in which case its recorded state is restored and the original outcome reported.
+    /// Newly finished tools are appended to the checkpoint.
+    fn process_with_checkpoint(&mut self, tool: &ToolEntry) -> ToolProcessingResult {
+        let Some(checkpoint) = self.checkpoint.as_deref() else {
+            return self.process_individual_tool(tool);
+        };
+
+        if let Some(completed) = checkpoint.completed(&tool.name) {
+            log_info!(
+                "[SDB::Tools] {} finished before the interruption, skipping",
+                tool.name.bright_green()
+            );
+            let result = completed.to_result();
+            if let Some(tool_state) = completed.state.clone() {
+                self.state.tools.insert(tool.name.clone(), tool_state);
+            }
+            return result;
+        }
+
+        let result = self.process_individual_tool(tool);
+        let tool_state = self.state.tools.get(&tool.name);
+        if let Some(checkpoint) = self.checkpoint.as_deref_mut() {
+            checkpoint.record(&tool.name, &result, tool_state);
+        }
+        result
     }
 
     /// Handles the complete processing pipeline for a single tool.
@@ -125,6 +248,42 @@ impl<'a> ToolInstallationOrchestrator<'a> {
         let planner = InstallationPlanner::new(self.configuration, self.config_processor.clone());
         let (required_action, cached_config_evaluation) =
             planner.determine_required_action(tool, current_state);
+        if !self.configuration.dry_run {
+            rollout::record(self.state, tool);
+        }
+
+        // Step 4: Check the tool's environment prerequisites before installing or updating it.
+        if matches!(required_action, ToolAction::Install | ToolAction::Update)
+            && let Some(requires_env) = &tool.requires_env
+            && let Err(failures) = check_requirements(requires_env)
+        {
+            return ToolProcessingResult::Failed(format!(
+                "[SDB::Tools] Unmet prerequisites: {}",
+                failures.join("; ")
+            ));
+        }
+
+        // Step 5: Protected tools are only updated with --force-protected.
+        if matches!(required_action, ToolAction::Update)
+            && protected::is_protected(self.state, &tool.name)
+            && let Err(refusal) = protected::authorize(self.state, &tool.name, "update")
+        {
+            return ToolProcessingResult::Failed(format!("[SDB::Tools] {refusal}"));
+        }
+
+        // Step 6: A `rename_to` may only take over another tool's executable with --force.
+        if matches!(required_action, ToolAction::Install | ToolAction::Update)
+            && let Err(refusal) = name_collisions::authorize(self.state, tool)
+        {
+            return ToolProcessingResult::Failed(format!("[SDB::Tools] {refusal}"));
+        }
+
+        // Step 7: Installs can take minutes; write what earlier tools changed before starting one.
+        if matches!(required_action, ToolAction::Install | ToolAction::Update)
+            && let Some(autosave) = self.autosave.as_mut()
+        {
+            autosave.flush(self.state);
+        }
 
         self.execute_action(tool, required_action, cached_config_evaluation)
     }
@@ -138,22 +297,16 @@ impl<'a> ToolInstallationOrchestrator<'a> {
     /// ## Returns
     /// `Ok(())` if installer is available, `Err(String)` with error message if not
     fn validate_installer_availability(&self, tool: &ToolEntry) -> Result<(), String> {
-        let cmd_to_check = match tool.source {
-            SourceType::Brew => Some("brew"),
-            SourceType::Go => Some("go"),
-            SourceType::Cargo => Some("cargo"),
-            SourceType::Rustup => Some("rustup"),
-            SourceType::Pip => Some("pip3"), // Explicitly check for pip3 as usually preferred
-            SourceType::Uv => Some("uv"),
-            _ => None,
+        let Some(installer) = self.installer_factory.get_installer(&tool.source) else {
+            return Ok(());
         };
 
-        if let Some(cmd) = cmd_to_check {
-            check_installer_command_available(cmd)
-                .map_err(|error| format!("[SDB::Tools] Installer '{cmd}' not available: {error}"))
-        } else {
-            Ok(())
-        }
+        installer.validate(tool).map_err(|error| {
+            format!(
+                "[SDB::Tools] Installer '{}' not available: {error}",
+                installer.name()
+            )
+        })
     }
 
     /// Executes the determined `ToolAction` with optional cached configuration evaluation.
@@ -223,10 +376,81 @@ impl<'a> ToolInstallationOrchestrator<'a> {
     ) -> ToolProcessingResult {
         // log_info!("[SDB::Tools] Installing {}...", "Tools".bright_green());
         self.display_installation_header(tool, operation_type);
+        if simulated::is_active() {
+            return self.execute_simulated_installation(tool, operation_type);
+        }
+        let started = Instant::now();
+
+        let license = match self.resolve_license(tool) {
+            Ok(license) => license,
+            Err(error) => {
+                self.display_installation_failure(tool, operation_type);
+                log_error!("[SDB::Tools] {}", error);
+                return ToolProcessingResult::Failed(format!("[SDB::Tools] {error}"));
+            }
+        };
+
+        // Keep the current executable so a failing smoke test can put it back.
+        let previous_install = tool
+            .smoke_test
+            .as_ref()
+            .and_then(|_| self.state.tools.get(&tool.name))
+            .and_then(|previous| PreviousInstall::save(&previous.install_path));
+
+        // Remember the watched directories so the receipt can list what the install wrote.
+        let snapshot = FileSnapshot::take(self.state.tools.get(&tool.name));
 
         // Invoke the correct installer based on the tool's `source`.
         match self.invoke_appropriate_installer(tool) {
             Ok(mut tool_state) => {
+                tool_state.license = license;
+                if tool.app_bundle.is_some() {
+                    tool_state.app_bundle =
+                        app_bundle::recorded(Path::new(&tool_state.install_path));
+                }
+
+                // Strip or compress the downloaded executable before it is verified.
+                tool_state.optimized_binary =
+                    optimize_binary::optimize(tool, Path::new(&tool_state.install_path));
+
+                // WebAssembly modules are launched through their runtime by a generated shim.
+                let wasm = wasm::is_wasm_tool(tool, Path::new(&tool_state.install_path));
+                if wasm
+                    && let Err(error) = wasm::shim_config(tool).and_then(|shim| {
+                        shims::install_shim(tool, &shim, &mut tool_state).map_err(|e| e.to_string())
+                    })
+                {
+                    self.display_installation_failure(tool, operation_type);
+                    return ToolProcessingResult::Failed(format!(
+                        "[SDB::Tools] Failed to set up the WebAssembly runtime for {}: {error}",
+                        tool.name
+                    ));
+                }
+
+                // Verify the fresh installation actually runs before recording it.
+                let launcher = tool_state
+                    .shim
+                    .as_ref()
+                    .map_or(&tool_state.install_path, |shim| &shim.path);
+                if let Some(command) = &tool.smoke_test
+                    && let Err(error) = smoke_test::run(&tool.name, command, Path::new(launcher))
+                {
+                    let restored = previous_install
+                        .as_ref()
+                        .is_some_and(PreviousInstall::restore);
+                    self.display_installation_failure(tool, operation_type);
+                    log_error!("[SDB::Tools] Smoke test failed: {}", error);
+                    return ToolProcessingResult::Failed(format!(
+                        "[SDB::Tools] Smoke test failed for {}{}: {error}",
+                        tool.name,
+                        if restored {
+                            " (previous version restored)"
+                        } else {
+                            ""
+                        }
+                    ));
+                }
+
                 // Process configuration management as a non-fatal step with cached evaluation.
                 // An error here will be logged as a warning but won't fail the overall installation.
                 if let Err(error) = self.process_configuration_management(
@@ -241,9 +465,64 @@ impl<'a> ToolInstallationOrchestrator<'a> {
                     );
                 }
 
+                // Wrap the installed executable in a shim when the tool asks for one.
+                if !wasm
+                    && let Some(shim) = &tool.shim
+                    && let Err(error) = shims::install_shim(tool, shim, &mut tool_state)
+                {
+                    self.display_installation_failure(tool, operation_type);
+                    return ToolProcessingResult::Failed(format!(
+                        "[SDB::Tools] Failed to create shim for {}: {error}",
+                        tool.name
+                    ));
+                }
+
+                // Point the user at the System Settings panes for permissions the tool needs.
+                if let Some(needed) = &tool.permissions {
+                    tool_state.permissions_acknowledged = permissions::request(
+                        &tool.name,
+                        needed,
+                        self.state
+                            .tools
+                            .get(&tool.name)
+                            .and_then(|previous| previous.permissions_acknowledged.as_ref()),
+                    );
+                }
+
+                // Drop the executable left under a previous `rename_to` and keep the history.
+                renames::migrate(&tool.name, self.state, &mut tool_state);
+
+                receipts::record(
+                    &tool.name,
+                    &snapshot,
+                    self.state.tools.get(&tool.name),
+                    &mut tool_state,
+                );
+
                 // Update the state with the new tool information.
+                tool_state.disk_usage_bytes =
+                    Some(disk_usage::tool_size(&tool_state, delta::seed_path(tool)));
+                tool_state.install_duration_ms = u64::try_from(started.elapsed().as_millis()).ok();
+                let version = tool_state.version.clone();
                 self.state.tools.insert(tool.name.clone(), tool_state);
+                presets::restart(tool);
                 self.display_installation_success(tool, operation_type);
+                lifecycle_hooks::run(
+                    LifecycleEvent::AfterToolInstall,
+                    &[
+                        ("SDB_TOOL_NAME", tool.name.clone()),
+                        ("SDB_TOOL_VERSION", version),
+                        ("SDB_TOOL_SOURCE", tool.source.to_string()),
+                        (
+                            "SDB_TOOL_ACTION",
+                            match operation_type {
+                                "Installing" => "install",
+                                _ => "update",
+                            }
+                            .to_string(),
+                        ),
+                    ],
+                );
 
                 // Return the appropriate success result.
                 match operation_type {
@@ -260,6 +539,52 @@ impl<'a> ToolInstallationOrchestrator<'a> {
         }
     }
 
+    /// Records the result of the simulated installer (`--simulate`). Simulated installations
+    /// change nothing, so licenses, shims, smoke tests, configuration management and hooks
+    /// are skipped.
+    fn execute_simulated_installation(
+        &mut self,
+        tool: &ToolEntry,
+        operation_type: &str,
+    ) -> ToolProcessingResult {
+        match self.invoke_appropriate_installer(tool) {
+            Ok(tool_state) => {
+                self.state.tools.insert(tool.name.clone(), tool_state);
+                self.display_installation_success(tool, operation_type);
+                match operation_type {
+                    "Installing" => ToolProcessingResult::Installed,
+                    _ => ToolProcessingResult::Updated,
+                }
+            }
+            Err(e) => {
+                self.display_installation_failure(tool, operation_type);
+                log_error!("[SDB::Tools] Failure reason: {}", e);
+                ToolProcessingResult::Failed(format!("[SDB::Tools] {operation_type} failed: {e}"))
+            }
+        }
+    }
+
+    /// Looks up the license of GitHub tools and, for tools with `requires_license_acceptance`,
+    /// makes sure it is accepted. Returns the license to record in state.
+    fn resolve_license(&self, tool: &ToolEntry) -> Result<Option<LicenseState>, String> {
+        let requires_acceptance = tool.requires_license_acceptance == Some(true);
+        let license = tool
+            .repo
+            .as_deref()
+            .filter(|_| tool.source == SourceType::Github || requires_acceptance)
+            .and_then(licenses::fetch_github_license);
+        if !requires_acceptance {
+            return Ok(license);
+        }
+
+        let previous = self
+            .state
+            .tools
+            .get(&tool.name)
+            .and_then(|installed| installed.license.as_ref());
+        licenses::ensure_accepted(&tool.name, license, previous).map(Some)
+    }
+
     /// Invokes the correct installer function based on the tool's `source`.
     ///
     /// The `match` statement dispatches to a specific module (e.g., `github::install`).
@@ -282,7 +607,10 @@ impl<'a> ToolInstallationOrchestrator<'a> {
                 ))
             })?;
 
-        installer.install(tool)
+        let mut tool_state = installer.install(tool)?;
+        // Correlates later breakages with upgrades of brew, cargo, go, ...
+        tool_state.installer_version = installer_version::record(installer.as_ref());
+        Ok(tool_state)
     }
 
     /// Handles the `UpdateConfigurationOnly` action with cached evaluation.
@@ -316,6 +644,7 @@ impl<'a> ToolInstallationOrchestrator<'a> {
             ) {
                 Ok(()) => {
                     self.state.tools.insert(tool.name.clone(), existing_state);
+                    presets::restart(tool);
                     ToolProcessingResult::ConfigurationUpdated
                 }
                 Err(error) => ToolProcessingResult::Failed(format!(
@@ -400,3 +729,23 @@ impl<'a> ToolInstallationOrchestrator<'a> {
         );
     }
 }
+
+/// Warns when a tool uses another configured tool as its source but is placed in an earlier
+/// phase than it. Phase order always wins, so such a tool would install before its installer.
+fn warn_about_cross_phase_dependencies(ordered: &[&ToolEntry]) {
+    for tool in ordered {
+        let source = tool.source.to_string();
+        if let Some(provider) = ordered.iter().find(|t| t.name == source)
+            && provider.effective_phase() > tool.effective_phase()
+        {
+            log_warn!(
+                "[SDB::Tools] '{}' (phase {}) uses '{}' (phase {}) as its source; move '{}' to an earlier phase",
+                tool.name.yellow(),
+                tool.effective_phase(),
+                provider.name.yellow(),
+                provider.effective_phase(),
+                provider.name
+            );
+        }
+    }
+}
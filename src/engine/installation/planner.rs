@@ -1,11 +1,19 @@
+use crate::core::assets;
+use crate::core::facts;
+use crate::core::renames;
+use crate::core::rollout;
 use crate::core::timestamps::{format_duration, is_timestamp_older_than, time_since};
-use crate::log_warn;
+use crate::core::version_check;
+use crate::engine::installers::github::{self, ReleaseSelector};
+use crate::engine::installers::simulated;
 use crate::schemas::config_manager::{
     ConfigurationEvaluationResult, ConfigurationManagerProcessor,
 };
 use crate::schemas::state_file::ToolState;
-use crate::schemas::tools_enums::{ConfigurationAction, ToolAction, VersionAction};
+use crate::schemas::tools_enums::{ConfigurationAction, SourceType, ToolAction, VersionAction};
 use crate::schemas::tools_types::{InstallationConfiguration, ToolEntry};
+use crate::{log_info, log_warn};
+use std::path::Path;
 
 /// The InstallationPlanner is responsible for determining the actions required for a tool.
 /// It encapsulates the business logic for version checks, update thresholds, and configuration evaluations.
@@ -32,35 +40,74 @@ impl<'a> InstallationPlanner<'a> {
         tool: &ToolEntry,
         current_state: Option<&ToolState>,
     ) -> (ToolAction, Option<ConfigurationEvaluationResult>) {
-        // If the tool is not in the current state, it must be installed.
+        // Tools whose `only_on` conditions don't hold are left alone on this machine.
+        if let Some(reason) = Self::unmet_condition(tool) {
+            return (ToolAction::Skip(reason), None);
+        }
+
+        // If the tool is not in the current state, it must be installed, unless its rollout
+        // has not reached this machine yet.
         match current_state {
-            None => (ToolAction::Install, None),
+            None => match rollout::held_back(tool) {
+                Some(reason) => (ToolAction::Skip(format!("[SDB::Tools] {reason}")), None),
+                None => (ToolAction::Install, None),
+            },
+            // PATH entries are checked again only when their directory or executable is gone.
+            Some(state) if tool.source == SourceType::PathEntry => {
+                if Path::new(&state.install_path).exists() {
+                    (
+                        ToolAction::Skip("[SDB::Tools] PATH entry present".to_string()),
+                        None,
+                    )
+                } else {
+                    (ToolAction::Update, None)
+                }
+            }
             Some(state) => {
-                // Analyze version requirements first
-                let version_action = self.analyze_version_requirements(tool, state);
+                // A changed `rename_to` reinstalls the tool under its new name; otherwise
+                // analyze version requirements first
+                let version_action = if renames::rename_changed(tool, state) {
+                    log_info!(
+                        "[SDB::Tools] {} changed its executable name; reinstalling it",
+                        tool.name
+                    );
+                    VersionAction::Update
+                } else {
+                    self.analyze_version_requirements(tool, state)
+                };
+                // Machines outside the rollout keep the installed version
+                let version_action = match version_action {
+                    VersionAction::Update => rollout::held_back(tool)
+                        .map_or(VersionAction::Update, |reason| {
+                            VersionAction::Skip(format!("[SDB::Tools] {reason}"))
+                        }),
+                    action => action,
+                };
 
-                // Perform comprehensive configuration evaluation
-                let config_evaluation = match self
-                    .config_processor
-                    .evaluate_configuration_requirements(
+                // Perform comprehensive configuration evaluation (not simulated)
+                let config_evaluation = if simulated::is_active() {
+                    None
+                } else {
+                    match self.config_processor.evaluate_configuration_requirements(
                         &tool.name,
                         &tool.configuration_manager,
                         state.get_configuration_manager(),
                     ) {
-                    Ok(evaluation) => Some(evaluation),
-                    Err(e) => {
-                        log_warn!(
-                            "[SDB::Tools] Error evaluating configuration for {}: {}. Assuming update needed.",
-                            tool.name,
-                            e
-                        );
-                        // Create a default evaluation that assumes update is needed
-                        Some(ConfigurationEvaluationResult {
-                            needs_update: true,
-                            current_source_sha: String::new(),
-                            current_destination_sha: None,
-                            reason: Some(format!("[SDB::Tools] Evaluation error: {e}")),
-                        })
+                        Ok(evaluation) => Some(evaluation),
+                        Err(e) => {
+                            log_warn!(
+                                "[SDB::Tools] Error evaluating configuration for {}: {}. Assuming update needed.",
+                                tool.name,
+                                e
+                            );
+                            // Create a default evaluation that assumes update is needed
+                            Some(ConfigurationEvaluationResult {
+                                needs_update: true,
+                                current_source_sha: String::new(),
+                                current_destination_sha: None,
+                                reason: Some(format!("[SDB::Tools] Evaluation error: {e}")),
+                            })
+                        }
                     }
                 };
 
@@ -84,6 +131,19 @@ impl<'a> InstallationPlanner<'a> {
         }
     }
 
+    /// The reason a tool is skipped because one of its `only_on` conditions does not hold
+    /// (or cannot be evaluated), if any.
+    pub(crate) fn unmet_condition(tool: &ToolEntry) -> Option<String> {
+        let conditions = tool.only_on.as_ref()?;
+        conditions.iter().find_map(
+            |condition| match facts::evaluate(condition, &facts::facts()) {
+                Ok(true) => None,
+                Ok(false) => Some(format!("only_on condition '{condition}' is not met")),
+                Err(e) => Some(format!("only_on: {e}")),
+            },
+        )
+    }
+
     /// Analyzes the version requirements for a tool to determine if an update is needed.
     fn analyze_version_requirements(
         &self,
@@ -123,6 +183,33 @@ impl<'a> InstallationPlanner<'a> {
 
         // The tool is older than the threshold, so it needs an update.
         if is_latest_version_scenario && !self.configuration.force_update_enabled {
+            // Simulated runs take the upstream version from the fixture file
+            if let Some(fixtures) = simulated::fixtures() {
+                let latest = fixtures.latest_version(tool);
+                return if Self::normalize_version(&latest)
+                    == Self::normalize_version(&current_state.version)
+                {
+                    VersionAction::Skip(format!(
+                        "[SDB::Tools] Latest upstream version {latest} already installed"
+                    ))
+                } else {
+                    VersionAction::Update
+                };
+            }
+            if let Some(action) = Self::analyze_url_version_check(tool, current_state) {
+                return action;
+            }
+            if let Some(action) = Self::analyze_github_latest_release(tool, current_state) {
+                return action;
+            }
+            // URL sources carry no version metadata: ask the server whether the asset changed
+            // since the last download and skip on `304 Not Modified`.
+            if Self::url_asset_unchanged(tool, current_state) {
+                return VersionAction::Skip(
+                    "[SDB::Tools] Remote asset not modified since last download (HTTP 304)"
+                        .to_string(),
+                );
+            }
             return VersionAction::Update;
         }
 
@@ -175,8 +262,92 @@ impl<'a> InstallationPlanner<'a> {
         }
     }
 
+    /// Compares the upstream version discovered through a URL tool's `version_check` block
+    /// with the installed one. Returns `None` when no check is configured or it fails, so the
+    /// caller falls back to conditional requests / re-downloading.
+    fn analyze_url_version_check(
+        tool: &ToolEntry,
+        current_state: &ToolState,
+    ) -> Option<VersionAction> {
+        if tool.source != SourceType::Url {
+            return None;
+        }
+        let check = tool.version_check.as_ref()?;
+        match version_check::discover_version(tool, check) {
+            Ok(latest)
+                if Self::normalize_version(&latest)
+                    == Self::normalize_version(&current_state.version) =>
+            {
+                Some(VersionAction::Skip(format!(
+                    "[SDB::Tools] Latest upstream version {latest} already installed"
+                )))
+            }
+            Ok(_) => Some(VersionAction::Update),
+            Err(e) => {
+                log_warn!("[SDB::Tools] version_check failed for {}: {}", tool.name, e);
+                None
+            }
+        }
+    }
+
+    /// Compares the latest release of a GitHub tool that follows it (no pinned `tag` or
+    /// `version`) with the tag it was installed from. Returns `None` for other tools, or when
+    /// the latest release cannot be looked up, so the caller reinstalls.
+    fn analyze_github_latest_release(
+        tool: &ToolEntry,
+        current_state: &ToolState,
+    ) -> Option<VersionAction> {
+        if tool.source != SourceType::Github
+            || github::release_selector(tool) != ReleaseSelector::Latest
+        {
+            return None;
+        }
+        let installed = current_state
+            .tag
+            .as_deref()
+            .unwrap_or(&current_state.version);
+        match github::fetch_release_for(tool.repo.as_deref()?, ReleaseSelector::Latest) {
+            Ok(release)
+                if Self::normalize_version(&release.tag_name)
+                    == Self::normalize_version(installed) =>
+            {
+                Some(VersionAction::Skip(format!(
+                    "[SDB::Tools] Latest release {} already installed",
+                    release.tag_name
+                )))
+            }
+            Ok(_) => Some(VersionAction::Update),
+            Err(e) => {
+                log_warn!(
+                    "[SDB::Tools] Could not look up the latest release of {}: {}",
+                    tool.name,
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    /// Sends a conditional request for a `latest` URL tool using the stored `ETag` /
+    /// `Last-Modified` validators. Tools resolved through `url_command` are always re-fetched,
+    /// since their signed URLs change on every run.
+    fn url_asset_unchanged(tool: &ToolEntry, current_state: &ToolState) -> bool {
+        if tool.source != SourceType::Url || tool.url_command.is_some() {
+            return false;
+        }
+        let Some(url) = tool.url.as_deref() else {
+            return false;
+        };
+        assets::remote_asset_unchanged(
+            tool,
+            url,
+            current_state.etag.as_deref(),
+            current_state.last_modified.as_deref(),
+        )
+    }
+
     /// Helper function to normalize a version string by removing a leading 'v'.
-    fn normalize_version(version: &str) -> &str {
+    pub(crate) fn normalize_version(version: &str) -> &str {
         version.strip_prefix('v').unwrap_or(version)
     }
 }
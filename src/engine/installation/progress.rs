@@ -0,0 +1,125 @@
+//! # Run Progress
+//!
+//! Estimates how long the remaining tools of a `now` run will take, from the install
+//! durations recorded in the state file (`install_duration_ms`), and reports the overall
+//! progress before each tool.
+//!
+//! Tools that are installed at the configured version are expected to be skipped and cost
+//! nothing. A tool to install or update is expected to take as long as its last recorded
+//! installation, or else the average recorded installation of configured tools with the
+//! same source, or else the average of all recorded installations.
+
+use std::time::Duration;
+
+use colored::Colorize;
+
+use crate::engine::installation::planner::InstallationPlanner;
+use crate::log_info;
+use crate::schemas::state_file::DevBoxState;
+use crate::schemas::tools_types::ToolEntry;
+
+/// Estimate for an installation when the state has no recorded durations at all.
+const DEFAULT_INSTALL_ESTIMATE: Duration = Duration::from_secs(20);
+
+/// Progress of the tools section of a run.
+pub(crate) struct RunProgress {
+    /// Estimated duration of each tool, in processing order.
+    estimates: Vec<Duration>,
+    processed: usize,
+}
+
+impl RunProgress {
+    /// Estimates the tools, given in processing order, against the current state.
+    pub(crate) fn new(tools: &[&ToolEntry], state: &DevBoxState) -> Self {
+        let recorded: Vec<(&ToolEntry, Duration)> = tools
+            .iter()
+            .filter_map(|tool| {
+                let duration = state.tools.get(&tool.name)?.install_duration_ms?;
+                Some((*tool, Duration::from_millis(duration)))
+            })
+            .collect();
+        Self {
+            estimates: tools
+                .iter()
+                .map(|tool| estimate(tool, state, &recorded))
+                .collect(),
+            processed: 0,
+        }
+    }
+
+    /// Logs the progress before processing the next tool. Nothing is logged while no
+    /// remaining tool is expected to need an installation.
+    pub(crate) fn report_next(&mut self) {
+        let total = self.estimates.len();
+        let remaining: Duration = self.estimates.iter().skip(self.processed).sum();
+        if total > 0 && !remaining.is_zero() {
+            log_info!(
+                "[SDB::Tools] Progress: {}/{} tools ({}%), about {} remaining",
+                self.processed,
+                total,
+                self.processed * 100 / total,
+                format_remaining(remaining).cyan()
+            );
+        }
+        self.processed += 1;
+    }
+}
+
+fn estimate(
+    tool: &ToolEntry,
+    state: &DevBoxState,
+    recorded: &[(&ToolEntry, Duration)],
+) -> Duration {
+    let installed = state.tools.get(&tool.name);
+    let up_to_date = installed.is_some_and(|installed| {
+        tool.version.as_deref().is_none_or(|version| {
+            version == "latest"
+                || InstallationPlanner::normalize_version(version)
+                    == InstallationPlanner::normalize_version(&installed.version)
+        })
+    });
+    if up_to_date {
+        return Duration::ZERO;
+    }
+
+    let average = |same_source: bool| {
+        let durations: Vec<Duration> = recorded
+            .iter()
+            .filter(|(other, _)| !same_source || other.source == tool.source)
+            .map(|(_, duration)| *duration)
+            .collect();
+        let count = u32::try_from(durations.len())
+            .ok()
+            .filter(|count| *count > 0)?;
+        Some(durations.iter().sum::<Duration>() / count)
+    };
+    installed
+        .and_then(|installed| installed.install_duration_ms)
+        .map(Duration::from_millis)
+        .or_else(|| average(true))
+        .or_else(|| average(false))
+        .unwrap_or(DEFAULT_INSTALL_ESTIMATE)
+}
+
+/// Formats a remaining duration as `45s`, `3m 05s` or `1h 12m`.
+fn format_remaining(duration: Duration) -> String {
+    let secs = duration.as_secs().max(1);
+    match (secs / 3600, secs % 3600 / 60, secs % 60) {
+        (0, 0, s) => format!("{s}s"),
+        (0, m, s) => format!("{m}m {s:02}s"),
+        (h, m, _) => format!("{h}h {m:02}m"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_remaining() {
+        assert_eq!(format_remaining(Duration::from_millis(300)), "1s");
+        assert_eq!(format_remaining(Duration::from_secs(45)), "45s");
+        assert_eq!(format_remaining(Duration::from_secs(185)), "3m 05s");
+        assert_eq!(format_remaining(Duration::from_secs(4320)), "1h 12m");
+    }
+}
@@ -15,10 +15,11 @@
 //! The orchestrator follows a structured pipeline for each tool:
 //! 1. **Reporting**: Categorize and display results to the user
 
-use crate::schemas::tools_enums::ToolProcessingResult;
+use crate::schemas::tools_enums::{ToolPhase, ToolProcessingResult};
 use crate::schemas::tools_types::InstallationSummary;
 use crate::{log_error, log_info};
 use colored::Colorize;
+use std::collections::BTreeMap;
 // ============================================================================
 // INSTALLATION SUMMARY IMPLEMENTATION
 // ============================================================================
@@ -27,12 +28,15 @@ impl InstallationSummary {
     /// Creates a new `InstallationSummary` from the raw `ToolProcessingResult`s.
     ///
     /// ## Parameters
-    /// - `results`: Vector of tool names and their processing results
+    /// - `results`: Vector of tool names, their phase and their processing results
     ///
     /// ## Returns
     /// `InstallationSummary` with categorized results
-    pub(crate) fn from_processing_results(results: Vec<(String, ToolProcessingResult)>) -> Self {
+    pub(crate) fn from_processing_results(
+        results: Vec<(String, ToolPhase, ToolProcessingResult)>,
+    ) -> Self {
         let mut summary = Self {
+            phase_outcomes: BTreeMap::new(),
             installed_tools: Vec::new(),
             updated_tools: Vec::new(),
             configuration_updated_tools: Vec::new(),
@@ -43,7 +47,12 @@ impl InstallationSummary {
         };
 
         // Categorize each result into the appropriate vector.
-        for (tool_name, result) in results {
+        for (tool_name, phase, result) in results {
+            summary
+                .phase_outcomes
+                .entry(phase)
+                .or_default()
+                .push((tool_name.clone(), result.label()));
             match result {
                 ToolProcessingResult::Installed => summary.installed_tools.push(tool_name),
                 ToolProcessingResult::Updated => summary.updated_tools.push(tool_name),
@@ -84,9 +93,34 @@ impl InstallationSummary {
         self.display_configuration_skipped_tools();
         self.display_dry_run_tools();
         self.display_failed_tools();
+        self.display_phase_summary();
         self.display_success_summary();
     }
 
+    /// Prints the outcome of every tool grouped by phase.
+    /// Only shown when the configuration actually uses more than one phase.
+    fn display_phase_summary(&self) {
+        if self.phase_outcomes.len() < 2 {
+            return;
+        }
+
+        println!();
+        println!(
+            "{} Summary by phase {}",
+            "====================".cyan(),
+            "=====================".cyan()
+        );
+        for (phase, outcomes) in &self.phase_outcomes {
+            let tools_line = outcomes
+                .iter()
+                .map(|(name, outcome)| format!("{} ({outcome})", name.bright_white()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            println!("[{}] {tools_line}", phase.to_string().bright_cyan().bold());
+        }
+        println!("{}", "=".repeat(61).cyan());
+    }
+
     /// Prints a formatted list of dry-run actions.
     fn display_dry_run_tools(&self) {
         if self.dry_run_tools.is_empty() {
@@ -63,14 +63,31 @@ use crate::schemas::tools_types::ToolEntry;
 // Custom logging macros for structured output.
 use crate::{log_debug, log_error, log_info, log_warn};
 // Post-installation hook execution functionality.
+use crate::core::inventory::{self, Lookup, PackageManager};
+use crate::core::metadata_cache;
 use crate::engine::execute_post_installation_hooks;
 use crate::engine::installers::errors::InstallerError;
 use crate::engine::installers::traits::Installer;
+use crate::engine::uninstaller::executors::{BrewUninstaller, ItemToBeRemoved, ToolUninstaller};
 
 /// Struct representing the Homebrew installer.
 pub struct BrewInstaller;
 
 impl Installer for BrewInstaller {
+    fn name(&self) -> &'static str {
+        "brew"
+    }
+
+    fn required_command(&self) -> Option<&'static str> {
+        Some("brew")
+    }
+
+    fn uninstall(&self, item: &ItemToBeRemoved) -> Result<(), InstallerError> {
+        BrewUninstaller
+            .uninstall(item)
+            .map_err(InstallerError::CommandFailed)
+    }
+
     /// Installs a tool using the Homebrew package manager with comprehensive error handling.
     ///
     /// This function provides a robust installer for Homebrew formulae that mirrors the quality.
@@ -185,7 +202,7 @@ impl Installer for BrewInstaller {
         ))
     }
 
-    /// # `get_latest_version`
+    /// # `resolve_latest_version`
     ///
     /// Fetches the latest stable version of a Homebrew formula.
     ///
@@ -199,7 +216,7 @@ impl Installer for BrewInstaller {
     /// A `Result` which is:
     /// - `Ok(String)`: A string containing the latest stable version number.
     /// - `Err(InstallerError)`: An `InstallerError` if it fails to get the latest version.
-    fn get_latest_version(&self, tool_entry: &ToolEntry) -> Result<String, InstallerError> {
+    fn resolve_latest_version(&self, tool_entry: &ToolEntry) -> Result<String, InstallerError> {
         log_debug!(
             "[SDB::Tools::BrewInstaller] Getting latest version for: {}",
             tool_entry.name.bold()
@@ -210,7 +227,10 @@ impl Installer for BrewInstaller {
         );
 
         let formula_name = &tool_entry.name;
-        get_latest_brew_version(formula_name).ok_or_else(|| {
+        metadata_cache::get_or_compute(&format!("brew:{formula_name}"), || {
+            get_latest_brew_version(formula_name)
+        })
+        .ok_or_else(|| {
             InstallerError::VersionDetectionFailed(format!(
                 "Failed to get latest Homebrew version for '{}'",
                 formula_name
@@ -299,8 +319,21 @@ fn get_latest_brew_version(formula_name: &str) -> Option<String> {
 ///
 /// # Note
 /// Homebrew returns exit code 1 if a formula is not installed, and 0 if it is installed.
-/// Other exit codes indicate errors in executing the brew command.
+/// Other exit codes indicate errors in executing the brew command. The run's package
+/// inventory answers first, so brew is only asked when it has no snapshot of Homebrew.
 fn check_formula_already_installed(formula_name: &str) -> bool {
+    match inventory::lookup(PackageManager::Brew, formula_name) {
+        Lookup::Installed(version) => {
+            log_debug!(
+                "[SDB::Tools::BrewInstaller] Formula '{}' is already installed (version {})",
+                formula_name,
+                version
+            );
+            return true;
+        }
+        Lookup::Missing => return false,
+        Lookup::Unknown => {}
+    }
     match Command::new("brew").args(["list", formula_name]).output() {
         Ok(output) if output.status.success() => {
             log_debug!(
@@ -938,3 +971,72 @@ fn get_brew_installed_version(formula_name: &str) -> Option<String> {
         _ => None,
     }
 }
+
+/// Age in days of the downloads `brew cleanup` removes when `brew_cleanup_prune_days` is unset.
+pub const DEFAULT_CLEANUP_PRUNE_DAYS: u32 = 30;
+
+/// Removes outdated downloads and old versions of formulae and casks with
+/// `brew cleanup --prune <days>`, so frequent provisioning runs don't pile up old bottles.
+///
+/// # Arguments
+/// * `prune_days` - Remove cached downloads older than this many days
+/// * `dry_run` - Only report what would be removed (`brew cleanup --dry-run`)
+///
+/// # Returns
+/// The space Homebrew reports as reclaimed (e.g. `"1.2GB"`), `None` if nothing was freed,
+/// or an error if `brew cleanup` could not run.
+pub fn cleanup(prune_days: u32, dry_run: bool) -> Result<Option<String>, String> {
+    let prune = prune_days.to_string();
+    let mut args = vec!["cleanup", "--prune", prune.as_str()];
+    if dry_run {
+        args.push("--dry-run");
+    }
+    log_debug!("[SDB::Tools::Brew] Running: brew {}", args.join(" "));
+
+    let output = Command::new("brew")
+        .args(&args)
+        .output()
+        .map_err(|e| format!("failed to run brew cleanup: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "brew cleanup failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(parse_reclaimed_space(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+/// Extracts the size from Homebrew's "This operation has freed approximately 1.2GB of disk
+/// space." (or "would free" for dry runs).
+fn parse_reclaimed_space(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let (_, rest) = line.split_once("free")?;
+        let rest = rest.strip_prefix('d').unwrap_or(rest);
+        let size = rest.trim().strip_prefix("approximately ")?;
+        size.split_whitespace().next().map(str::to_string)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reclaimed_space() {
+        assert_eq!(
+            parse_reclaimed_space(
+                "Removing: /Users/me/Library/Caches/Homebrew/wget--1.21.bottle.tar.gz... (1.5MB)\n==> This operation has freed approximately 1.2GB of disk space."
+            ),
+            Some("1.2GB".to_string())
+        );
+        assert_eq!(
+            parse_reclaimed_space(
+                "==> This operation would free approximately 310.4MB of disk space."
+            ),
+            Some("310.4MB".to_string())
+        );
+        assert_eq!(parse_reclaimed_space(""), None);
+    }
+}
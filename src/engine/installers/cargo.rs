@@ -43,9 +43,12 @@ use std::path::PathBuf;
 use std::process::Command;
 
 // Post-installation hook execution functionality.
+use crate::core::inventory::{self, Lookup, PackageManager};
+use crate::core::metadata_cache;
 use crate::engine::execute_post_installation_hooks;
 use crate::engine::installers::errors::InstallerError;
 use crate::engine::installers::traits::Installer;
+use crate::engine::uninstaller::executors::{CargoUninstaller, ItemToBeRemoved, ToolUninstaller};
 // Internal module imports:
 // `ToolEntry`: Represents a single tool's configuration from `tools.yaml`.
 // `ToolState`: Represents the actual state of an installed tool for persistence in `state.json`.
@@ -61,6 +64,20 @@ use colored::Colorize;
 pub struct CargoInstaller;
 
 impl Installer for CargoInstaller {
+    fn name(&self) -> &'static str {
+        "cargo"
+    }
+
+    fn required_command(&self) -> Option<&'static str> {
+        Some("cargo")
+    }
+
+    fn uninstall(&self, item: &ItemToBeRemoved) -> Result<(), InstallerError> {
+        CargoUninstaller
+            .uninstall(item)
+            .map_err(InstallerError::CommandFailed)
+    }
+
     /// Installs a Rust tools using the `cargo install` command with comprehensive error handling.
     ///
     /// This function provides a robust installer for Cargo based tool installation which
@@ -102,7 +119,12 @@ impl Installer for CargoInstaller {
             "[SDB::Tools::CargoInstaller] Checking if {} is already installed",
             tool_entry.name.bold()
         );
-        if let Some(installed_version) = get_installed_version(&tool_entry.name) {
+        let installed_version = match inventory::lookup(PackageManager::Cargo, &tool_entry.name) {
+            Lookup::Installed(version) => Some(version),
+            Lookup::Missing => None,
+            Lookup::Unknown => get_installed_version(&tool_entry.name),
+        };
+        if let Some(installed_version) = installed_version {
             log_info!(
                 "[SDB::Tools::CargoInstaller] Tool '{}' is already installed (version {}). SDB will ensure it matches the configured version.",
                 tool_entry.name.green(),
@@ -121,6 +143,11 @@ impl Installer for CargoInstaller {
             }
         );
 
+        // Commit pins are built from the git repository with `--rev`
+        if tool_entry.pinned_commit().is_some() {
+            validate_commit_pin(tool_entry, is_it_git_based_install)?;
+        }
+
         // 3. Prepare and execute cargo install command
         log_debug!(
             "[SDB::Tools::CargoInstaller] Prepare the command to install: {}",
@@ -186,7 +213,7 @@ impl Installer for CargoInstaller {
         ))
     }
 
-    /// # `get_latest_version`
+    /// # `resolve_latest_version`
     ///
     /// Fetches the latest version of a Rust crate.
     ///
@@ -202,7 +229,7 @@ impl Installer for CargoInstaller {
     /// - `Ok(String)`: A string containing the latest version of the crate, or "git-latest"
     ///   for git-based installations.
     /// - `Err(InstallerError)`: An `InstallerError` if it fails to get the latest version.
-    fn get_latest_version(&self, tool_entry: &ToolEntry) -> Result<String, InstallerError> {
+    fn resolve_latest_version(&self, tool_entry: &ToolEntry) -> Result<String, InstallerError> {
         log_debug!(
             "[SDB::Tools::CargoInstaller] Getting latest version for: {}",
             tool_entry.name.bold()
@@ -221,7 +248,10 @@ impl Installer for CargoInstaller {
             // for a simple version check.
             Ok("git-latest".to_string())
         } else {
-            get_latest_crates_io_version(tool_name).ok_or_else(|| {
+            metadata_cache::get_or_compute(&format!("crates:{tool_name}"), || {
+                get_latest_crates_io_version(tool_name)
+            })
+            .ok_or_else(|| {
                 InstallerError::VersionDetectionFailed(format!(
                     "Failed to get latest crates.io version for '{}'",
                     tool_name
@@ -527,6 +557,17 @@ fn prepare_git_based_install_command(command_args: &mut Vec<String>, tool_entry:
     // Add crate name at the end for git installations (cargo requirement)
     command_args.push(tool_entry.name.clone());
 
+    // A `version: commit:<hash>` pin installs exactly that revision
+    if let Some(commit) = tool_entry.pinned_commit() {
+        command_args.push("--rev".to_string());
+        command_args.push(commit.to_string());
+        log_debug!(
+            "[Cargo Installer] Using pinned commit as git revision: {}",
+            commit.cyan()
+        );
+        return;
+    }
+
     // Handle version as git tag if no explicit git options are present
     if !has_branch
         && !has_tag
@@ -545,6 +586,28 @@ fn prepare_git_based_install_command(command_args: &mut Vec<String>, tool_entry:
     }
 }
 
+/// Checks that a `version: commit:<hash>` pin can be installed: it needs a `--git` source
+/// and must not be combined with another git reference (`--branch`, `--tag`, `--rev`).
+fn validate_commit_pin(tool_entry: &ToolEntry, is_git_based: bool) -> Result<(), InstallerError> {
+    let conflicting = tool_entry.options.iter().flatten().any(|opt| {
+        opt.starts_with("--branch") || opt.starts_with("--tag") || opt.starts_with("--rev")
+    });
+    let problem = if !is_git_based {
+        "requires a '--git <repository>' option"
+    } else if conflicting {
+        "cannot be combined with --branch, --tag or --rev"
+    } else {
+        return Ok(());
+    };
+
+    let msg = format!(
+        "Configuration error: commit pin for '{}' {}",
+        tool_entry.name, problem
+    );
+    log_error!("[SDB::Tools::CargoInstaller] {}", msg);
+    Err(InstallerError::ConfigurationError(msg))
+}
+
 /// Executes the cargo install command with comprehensive error handling.
 ///
 /// This function runs the actual `cargo install` command and provides detailed
@@ -791,3 +854,45 @@ fn extract_option_value(options: &[String], flag: &str) -> Option<String> {
                 .and_then(|pos| options.get(pos + 1).cloned())
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pinned(options: &[&str]) -> ToolEntry {
+        ToolEntry {
+            options: Some(options.iter().map(|opt| opt.to_string()).collect()),
+            ..serde_yaml::from_str("name: ripgrep\nsource: cargo\nversion: commit:4f3e2a1").unwrap()
+        }
+    }
+
+    #[test]
+    fn test_commit_pin_installs_revision_from_git() {
+        let tool = pinned(&["--git https://github.com/BurntSushi/ripgrep"]);
+        assert!(validate_commit_pin(&tool, true).is_ok());
+
+        let mut command_args = vec!["install".to_string()];
+        prepare_git_based_install_command(&mut command_args, &tool);
+        assert_eq!(
+            command_args,
+            [
+                "install",
+                "--git",
+                "https://github.com/BurntSushi/ripgrep",
+                "ripgrep",
+                "--rev",
+                "4f3e2a1"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_commit_pin_requires_git_without_other_references() {
+        assert!(validate_commit_pin(&pinned(&[]), false).is_err());
+        let tool = pinned(&[
+            "--git https://github.com/BurntSushi/ripgrep",
+            "--tag=14.1.0",
+        ]);
+        assert!(validate_commit_pin(&tool, true).is_err());
+    }
+}
@@ -2,17 +2,29 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::engine::installers::{
-    brew::BrewInstaller, cargo::CargoInstaller, github::GitHubInstaller, go::GoInstaller,
-    pip::PipInstaller, rustup::RustupInstaller, traits::Installer, url::UrlInstaller,
+    brew::BrewInstaller,
+    cargo::CargoInstaller,
+    github::GitHubInstaller,
+    go::GoInstaller,
+    oci::OciInstaller,
+    path_entry::PathEntryInstaller,
+    pip::PipInstaller,
+    rustup::RustupInstaller,
+    simulated::{self, SimulatedInstaller},
+    traits::Installer,
+    url::UrlInstaller,
     uv::UvInstaller,
 };
+use crate::log_debug;
+use crate::schemas::state_file::ToolState;
 use crate::schemas::tools_enums::SourceType;
 
-/// Factory for creating/retrieving installers based on SourceType.
+/// Registry of installers keyed by SourceType.
 ///
 /// This struct manages the mapping between tool source types and their corresponding
-/// installer implementations. It allows the orchestrator to remain decoupled from
-/// specific installer logic.
+/// installer implementations. It allows the installation and removal pipelines to remain
+/// decoupled from specific installer logic: supporting a new source only requires
+/// registering another [`Installer`] implementation.
 pub struct InstallerFactory {
     installers: HashMap<SourceType, Arc<dyn Installer + Send + Sync>>,
 }
@@ -20,19 +32,46 @@ pub struct InstallerFactory {
 impl InstallerFactory {
     /// Creates a new InstallerFactory and registers all available installers.
     pub fn new() -> Self {
-        let mut installers: HashMap<SourceType, Arc<dyn Installer + Send + Sync>> = HashMap::new();
+        let mut factory = Self {
+            installers: HashMap::new(),
+        };
 
-        // Register all supported installers
-        installers.insert(SourceType::Github, Arc::new(GitHubInstaller));
-        installers.insert(SourceType::Brew, Arc::new(BrewInstaller));
-        installers.insert(SourceType::Go, Arc::new(GoInstaller));
-        installers.insert(SourceType::Cargo, Arc::new(CargoInstaller));
-        installers.insert(SourceType::Rustup, Arc::new(RustupInstaller));
-        installers.insert(SourceType::Pip, Arc::new(PipInstaller));
-        installers.insert(SourceType::Uv, Arc::new(UvInstaller));
-        installers.insert(SourceType::Url, Arc::new(UrlInstaller));
+        // Register all built-in installers
+        factory.register(SourceType::Github, Arc::new(GitHubInstaller));
+        factory.register(SourceType::Brew, Arc::new(BrewInstaller));
+        factory.register(SourceType::Go, Arc::new(GoInstaller));
+        factory.register(SourceType::Cargo, Arc::new(CargoInstaller));
+        factory.register(SourceType::Rustup, Arc::new(RustupInstaller));
+        factory.register(SourceType::Pip, Arc::new(PipInstaller));
+        factory.register(SourceType::Uv, Arc::new(UvInstaller));
+        factory.register(SourceType::Url, Arc::new(UrlInstaller));
+        factory.register(SourceType::Oci, Arc::new(OciInstaller));
+        factory.register(SourceType::PathEntry, Arc::new(PathEntryInstaller));
 
-        Self { installers }
+        // `--simulate` replaces every installer with a fake driven by the fixture file
+        if let Some(fixtures) = simulated::fixtures() {
+            let installer = Arc::new(SimulatedInstaller::new(fixtures));
+            let sources: Vec<SourceType> = factory.installers.keys().cloned().collect();
+            for source in sources {
+                factory.register(source, installer.clone());
+            }
+        }
+
+        factory
+    }
+
+    /// Registers an installer for a source type, replacing any installer registered before.
+    pub fn register(
+        &mut self,
+        source_type: SourceType,
+        installer: Arc<dyn Installer + Send + Sync>,
+    ) {
+        log_debug!(
+            "[SDB::Installers] Registered installer '{}' for source '{}'",
+            installer.name(),
+            source_type
+        );
+        self.installers.insert(source_type, installer);
     }
 
     /// Retrieves the appropriate installer for the given source type.
@@ -48,6 +87,35 @@ impl InstallerFactory {
     ) -> Option<Arc<dyn Installer + Send + Sync>> {
         self.installers.get(source_type).cloned()
     }
+
+    /// Retrieves the installer for an `install_method` recorded in the state file
+    /// (e.g., `cargo-install`, `uv-tool`, `direct-url`).
+    ///
+    /// # Returns
+    /// * `Option<Arc<dyn Installer>>` - The installer instance, or None for methods that
+    ///   were not installed through a registered installer (e.g., adopted binaries)
+    pub fn get_installer_for_method(
+        &self,
+        install_method: &str,
+    ) -> Option<Arc<dyn Installer + Send + Sync>> {
+        let source = ToolState::normalize_source_type(&install_method.to_lowercase());
+        source
+            .parse::<SourceType>()
+            .ok()
+            .and_then(|source_type| self.get_installer(&source_type))
+    }
+
+    /// The external commands the registered installers shell out to, sorted and deduplicated.
+    pub fn required_commands(&self) -> Vec<&'static str> {
+        let mut commands: Vec<&'static str> = self
+            .installers
+            .values()
+            .filter_map(|installer| installer.required_command())
+            .collect();
+        commands.sort_unstable();
+        commands.dedup();
+        commands
+    }
 }
 
 impl Default for InstallerFactory {
@@ -55,3 +123,51 @@ impl Default for InstallerFactory {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::installers::errors::InstallerError;
+    use crate::schemas::tools_types::ToolEntry;
+
+    struct PinnedInstaller;
+
+    impl Installer for PinnedInstaller {
+        fn name(&self) -> &'static str {
+            "pinned"
+        }
+
+        fn install(&self, tool: &ToolEntry) -> Result<ToolState, InstallerError> {
+            Err(InstallerError::ConfigurationError(tool.name.clone()))
+        }
+
+        fn resolve_latest_version(&self, _tool: &ToolEntry) -> Result<String, InstallerError> {
+            Ok("1.0.0".to_string())
+        }
+    }
+
+    #[test]
+    fn test_recorded_install_methods_resolve_to_registered_installers() {
+        let factory = InstallerFactory::new();
+        let name = |method: &str| factory.get_installer_for_method(method).map(|i| i.name());
+
+        assert_eq!(name("cargo-install"), Some("cargo"));
+        assert_eq!(name("uv-tool"), Some("uv"));
+        assert_eq!(name("direct-url"), Some("url"));
+        assert_eq!(name("GitHub"), Some("github"));
+        assert_eq!(name("adopted"), None);
+    }
+
+    #[test]
+    fn test_register_replaces_installer_for_source() {
+        let mut factory = InstallerFactory::new();
+        factory.register(SourceType::Cargo, Arc::new(PinnedInstaller));
+
+        let installer = factory.get_installer(&SourceType::Cargo).unwrap();
+        assert_eq!(installer.name(), "pinned");
+        assert_eq!(
+            factory.get_installer(&SourceType::Go).map(|i| i.name()),
+            Some("go")
+        );
+    }
+}
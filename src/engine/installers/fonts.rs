@@ -9,10 +9,13 @@
 // =========================================================================== //
 //                          STANDARD LIBRARY DEPENDENCIES                      //
 // =========================================================================== //
-use std::env;
+use std::collections::HashMap;
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 // =========================================================================== //
 //                             EXTERNAL DEPENDENCIES                           //
@@ -26,6 +29,8 @@ use colored::Colorize;
 
 use crate::core::assets::download_file;
 use crate::core::compression::extract_archive;
+use crate::core::disk_usage;
+use crate::engine::installers::github::fetch_latest_github_release;
 use crate::{log_debug, log_error, log_info, log_warn};
 // `ToolEntry`: Represents a single tool's configuration as defined in your `tools.yaml` file.
 //              It's a struct that contains all possible configuration fields for a tool,
@@ -33,12 +38,72 @@ use crate::{log_debug, log_error, log_info, log_warn};
 // `ToolState`: Represents the actual state of an *installed* tool. This struct is used to
 //              persist information about installed tools in the application's `state.json` file.
 //              It helps `setup-devbox` track what's installed, its version, and where it's located.
-use crate::schemas::fonts::FontEntry;
+use crate::schemas::fonts::{FontEntry, FontScope};
 use crate::schemas::path_resolver::PathResolver;
-use crate::schemas::state_file::FontState;
+use crate::schemas::state_file::{FontReplacement, FontState};
+
+/// Repository publishing the Nerd Fonts archives, used for fonts with a `variant`.
+pub(crate) const NERD_FONTS_REPO: &str = "ryanoasis/nerd-fonts";
+
+/// Maximum number of font archives downloaded at once by [`install_all`].
+const DOWNLOAD_CONCURRENCY: usize = 4;
+
+/// A parsed `variant:` of a Nerd Font (see `FontEntry::variant`).
+#[derive(Debug, PartialEq)]
+pub(crate) struct NerdFontVariant {
+    /// File name suffix after `NerdFont`: empty, `Mono` or `Propo`.
+    spacing: &'static str,
+    /// Weight/style after the dash (`Regular`, `BoldItalic`, ...), or any when `None`.
+    weight: Option<String>,
+}
+
+impl NerdFontVariant {
+    /// Parses `[<spacing>][-<weight>]`, e.g. `mono`, `propo-bold` or `regular`.
+    pub(crate) fn parse(variant: &str) -> Result<Self, String> {
+        let variant = variant.trim().to_lowercase();
+        let (first, rest) = match variant.split_once('-') {
+            Some((first, rest)) => (first, Some(rest)),
+            None => (variant.as_str(), None),
+        };
+        let (spacing, weight) = match first {
+            "default" => ("", rest),
+            "mono" => ("Mono", rest),
+            "propo" => ("Propo", rest),
+            _ if rest.is_none() => ("", Some(first)),
+            _ => {
+                return Err(format!(
+                    "'{variant}' should be [default|mono|propo][-<weight>], e.g. 'mono-regular'"
+                ));
+            }
+        };
+        if weight.is_some_and(|weight| weight.is_empty() || weight.contains('-')) {
+            return Err(format!("'{variant}' has an invalid weight"));
+        }
+        Ok(Self {
+            spacing,
+            weight: weight.map(str::to_string),
+        })
+    }
+
+    /// Checks a Nerd Fonts file name (`<Name>NerdFont[Mono|Propo]-<Weight>.ttf`) against the variant.
+    pub(crate) fn matches(&self, filename: &str) -> bool {
+        let stem = Path::new(filename)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or(filename);
+        let Some((_, after)) = stem.split_once("NerdFont") else {
+            return false;
+        };
+        let (spacing, weight) = after.split_once('-').unwrap_or((after, ""));
+        spacing == self.spacing
+            && self
+                .weight
+                .as_ref()
+                .is_none_or(|wanted| weight.eq_ignore_ascii_case(wanted))
+    }
+}
 
 /// Helper struct to hold validated font entry details, reducing redundancy.
-#[allow(dead_code)]
 struct ValidatedFontDetails {
     repo: String,
     tag: String,
@@ -74,21 +139,65 @@ fn validate_font_entry(font: &FontEntry) -> Option<ValidatedFontDetails> {
         return None;
     }
 
-    let Some(repo) = &font.repo else {
+    if let Some(variant) = &font.variant
+        && let Err(e) = NerdFontVariant::parse(variant)
+    {
         log_error!(
-            "[SDB::Fonts::Validator] Font '{}' with 'github' source is missing 'repo' field. Skipping.",
-            font.name.bold().red()
+            "[SDB::Fonts::Validator] Font '{}' has an invalid 'variant': {}. Skipping.",
+            font.name.bold().red(),
+            e
         );
         return None;
+    }
+
+    // Nerd Fonts variants know where they come from
+    let repo = match (&font.repo, &font.variant) {
+        (Some(repo), _) => repo.clone(),
+        (None, Some(_)) => NERD_FONTS_REPO.to_string(),
+        (None, None) => {
+            log_error!(
+                "[SDB::Fonts::Validator] Font '{}' with 'github' source is missing 'repo' field. Skipping.",
+                font.name.bold().red()
+            );
+            return None;
+        }
     };
+    let repo = &repo;
 
-    let Some(tag) = &font.tag else {
-        log_error!(
-            "[SDB::Fonts::Validator] Font '{}' with 'github' source is missing 'tag' field. Skipping.",
-            font.name.bold().red()
-        );
-        return None;
+    // Nerd Fonts variants and fonts asking for `latest` use the latest release
+    let tag = match &font.tag {
+        Some(tag) if tag != "latest" => tag.clone(),
+        _ if font.variant.is_some() || font.wants_latest() => {
+            match fetch_latest_github_release(repo) {
+                Ok(release) => {
+                    log_info!(
+                        "[SDB::Fonts::Validator] Using the latest release {} of {} for '{}'.",
+                        release.tag_name.cyan(),
+                        repo,
+                        font.name.bold()
+                    );
+                    release.tag_name
+                }
+                Err(e) => {
+                    log_error!(
+                        "[SDB::Fonts::Validator] Could not determine the latest release of {} for '{}': {}. Skipping.",
+                        repo,
+                        font.name.bold().red(),
+                        e
+                    );
+                    return None;
+                }
+            }
+        }
+        _ => {
+            log_error!(
+                "[SDB::Fonts::Validator] Font '{}' with 'github' source is missing 'tag' field (or 'version: latest'). Skipping.",
+                font.name.bold().red()
+            );
+            return None;
+        }
     };
+    let tag = &tag;
 
     // Construct the expected asset name. This is a heuristic based on common font naming conventions.
     // Example: For Fira Code v6.2, asset_name might be "FiraCode.zip".
@@ -115,7 +224,7 @@ fn validate_font_entry(font: &FontEntry) -> Option<ValidatedFontDetails> {
 /// Downloads the font archive from the given URL to a temporary directory.
 ///
 /// # Arguments
-/// * `font_name`: The name of the font (for logging).
+/// * `font_name`: The name of the font or archive (for logging).
 /// * `url`: The URL to download the font archive from.
 /// * `temp_dir`: The directory where the archive should be saved temporarily.
 /// * `filename`: The expected filename of the downloaded archive.
@@ -152,7 +261,7 @@ fn download_font_archive(
 /// Extracts the downloaded font archive to a temporary directory.
 ///
 /// # Arguments
-/// * `font_name`: The name of the font (for logging).
+/// * `font_name`: The name of the font or archive (for logging).
 /// * `archive_path`: The path to the downloaded font archive.
 /// * `extract_to_dir`: The directory where the archive contents should be extracted.
 ///
@@ -210,21 +319,20 @@ fn extract_font_archive(
 /// system's font installation directory, applying filters if specified.
 ///
 /// # Arguments
-/// * `font_name`: The name of the font (for logging).
+/// * `font`: The font entry, whose `install_only` filters and `variant` select the files.
 /// * `extracted_dir`: The directory where the font archive was extracted.
 /// * `install_dir`: The final system font installation directory (e.g., `~/Library/Fonts`).
-/// * `install_only`: An optional `Vec<String>` containing substrings to filter font files.
+/// * `use_sudo`: Whether `install_dir` is only writable through `sudo` (system scope).
 ///
 /// # Returns
 /// A `Result` containing a `Vec<String>` of successfully installed font file names on success,
 /// or an `io::Error` on file copy failures.
 fn copy_non_hidden_font_files(
-    font_name: &str,
+    font: &FontEntry,
     extracted_dir: &Path,
     install_dir: &Path,
-    install_only: &Option<Vec<String>>,
+    use_sudo: bool,
 ) -> io::Result<Vec<String>> {
-    let _ = font_name;
     log_debug!(
         "[SDB::Fonts::Installer] Copying font files from '{}' to '{}'.",
         extracted_dir.display(),
@@ -261,22 +369,15 @@ fn copy_non_hidden_font_files(
             continue;
         }
 
-        // Apply `install_only` filter if specified.
-        if let Some(filters) = install_only {
-            let lower_filename = filename.to_lowercase(); // Convert filename to lowercase once
-
-            if !filters
-                .iter()
-                .any(|filter| lower_filename.contains(&filter.to_lowercase()))
-            {
-                // Compare against lowercase filter
-                log_debug!(
-                    "[SDB::Fonts::Installer] Skipping font file '{}' as it does not match 'install_only' filters ({:#?}).",
-                    filename.blue(),
-                    filters
-                );
-                continue;
-            }
+        // Apply the `install_only` and `variant` filters if specified.
+        if !selects_font_file(font, filename) {
+            log_debug!(
+                "[SDB::Fonts::Installer] Skipping font file '{}' as it does not match 'install_only' ({:?}) or 'variant' ({:?}).",
+                filename.blue(),
+                font.install_only,
+                font.variant
+            );
+            continue;
         }
 
         let destination_path = install_dir.join(filename);
@@ -286,7 +387,16 @@ fn copy_non_hidden_font_files(
             destination_path.display()
         );
 
-        fs::copy(path, &destination_path).inspect_err(|e| {
+        let copied = if use_sudo {
+            run_sudo(&[
+                "cp".as_ref(),
+                path.as_os_str(),
+                destination_path.as_os_str(),
+            ])
+        } else {
+            fs::copy(path, &destination_path).map(|_| ())
+        };
+        copied.inspect_err(|e| {
             log_error!(
                 "[SDB::Fonts::Installer] Failed to copy font file '{}' to '{}': {}",
                 path.display().to_string().red(),
@@ -302,144 +412,456 @@ fn copy_non_hidden_font_files(
     Ok(installed_font_files)
 }
 
+/// Checks whether a font file is selected by the font's `install_only` filters and `variant`.
+pub(crate) fn selects_font_file(font: &FontEntry, filename: &str) -> bool {
+    font.install_only
+        .as_ref()
+        .is_none_or(|filters| matches_install_only(filename, filters))
+        && font.variant.as_ref().is_none_or(|variant| {
+            NerdFontVariant::parse(variant).is_ok_and(|variant| variant.matches(filename))
+        })
+}
+
+/// Checks a font file name against `install_only` filters (case-insensitive substrings).
+pub(crate) fn matches_install_only(filename: &str, filters: &[String]) -> bool {
+    let lower_filename = filename.to_lowercase();
+    filters
+        .iter()
+        .any(|filter| lower_filename.contains(&filter.to_lowercase()))
+}
+
+/// Checks that font files can be written to `dir` before anything is downloaded. Returns
+/// whether copying into it needs `sudo`, or `None` (with the error logged) when it is not
+/// writable and `sudo` can't be used without a password prompt nobody can answer.
+fn check_install_dir_writable(font: &FontEntry, dir: &Path) -> Option<bool> {
+    if is_writable_dir(dir) {
+        return Some(false);
+    }
+    if font.scope != Some(FontScope::System) {
+        log_error!(
+            "[SDB::Fonts::Installer] Font directory '{}' is not writable. Skipping '{}'.",
+            dir.display().to_string().red(),
+            font.name.bold().red()
+        );
+        return None;
+    }
+
+    log_info!(
+        "[SDB::Fonts] Installing '{}' for all users into {} needs sudo.",
+        font.name.bold(),
+        dir.display().to_string().cyan()
+    );
+    let can_sudo = run_sudo(&["-n".as_ref(), "true".as_ref()]).is_ok()
+        || (io::IsTerminal::is_terminal(&io::stdin()) && run_sudo(&["-v".as_ref()]).is_ok());
+    let created = can_sudo
+        && (dir.is_dir() || run_sudo(&["mkdir".as_ref(), "-p".as_ref(), dir.as_os_str()]).is_ok());
+    if !created {
+        log_error!(
+            "[SDB::Fonts::Installer] Cannot write to '{}' without sudo. Run interactively or install '{}' with 'scope: user'.",
+            dir.display().to_string().red(),
+            font.name.bold().red()
+        );
+        return None;
+    }
+    Some(true)
+}
+
+/// Whether files can be created in `dir`, creating it if missing. Probes with a file instead
+/// of reading permission bits, which don't account for ACLs or read-only mounts.
+pub(crate) fn is_writable_dir(dir: &Path) -> bool {
+    if fs::create_dir_all(dir).is_err() {
+        return false;
+    }
+    let probe = dir.join(format!(".setup-devbox-write-test-{}", std::process::id()));
+    match fs::File::create(&probe) {
+        Ok(_) => fs::remove_file(&probe).is_ok(),
+        Err(_) => false,
+    }
+}
+
+/// Runs `sudo` with `args`, failing with its stderr when it exits unsuccessfully.
+pub(crate) fn run_sudo(args: &[&std::ffi::OsStr]) -> io::Result<()> {
+    let output = Command::new("sudo")
+        .args(args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
+
 /// Helper to derive the version string for `FontState`.
-/// Prefers `version` from `FontEntry`, then `tag`, then "unknown".
-fn determine_font_version(font: &FontEntry) -> String {
+/// Prefers `version` from `FontEntry`, then the resolved release tag, which is also recorded
+/// for `version: latest` so the state tells which release was installed.
+fn determine_font_version(font: &FontEntry, tag: &str) -> String {
     font.version
         .clone()
-        .unwrap_or_else(|| font.tag.clone().unwrap_or_else(|| "unknown".to_string()))
+        .filter(|version| version != "latest")
+        .unwrap_or_else(|| tag.to_string())
 }
 
-/// Installs a font based on the provided `FontEntry` configuration.
-///
-/// This is the main public function for this module. It orchestrates the entire
-/// font installation process: validation, directory setup, download, extraction,
-/// file copying, and state recording.
-///
-/// # Arguments
-/// * `font`: A reference to the `FontEntry` struct containing the font's configuration.
-///
-/// # Returns
-/// An `Option<FontState>`:
-/// * `Some(FontState)` if at least one font file was successfully installed and recorded.
-/// * `None` if the font installation process failed for any reason (e.g., validation error,
-///   download failure, no font files found/copied). Error details are logged internally.
-pub fn install(font: &FontEntry) -> Option<FontState> {
+/// A font whose entry was validated and whose installation directory is writable.
+struct PreparedFont<'a> {
+    font: &'a FontEntry,
+    /// State of the version being updated, if the font is already installed.
+    previous: Option<&'a FontState>,
+    details: ValidatedFontDetails,
+    install_dir: PathBuf,
+    use_sudo: bool,
+}
+
+/// Validates the font entry and checks that its installation directory can be written to.
+/// Returns `None` (with the error logged) when the font can't be installed.
+fn prepare_font<'a>(
+    font: &'a FontEntry,
+    previous: Option<&'a FontState>,
+) -> Option<PreparedFont<'a>> {
     log_info!(
         "[SDB::Fonts] Starting installation for font: {}",
         font.name.bold()
     );
 
-    // 1. Validate the font entry and get download details.
-    let font_details = validate_font_entry(font)?; // Returns None if validation fails
+    let details = validate_font_entry(font)?;
 
-    // 2. Determine installation paths (temporary and final).
-    let font_install_dir = match PathResolver::get_font_installation_dir() {
+    let scope = font.scope.unwrap_or_default();
+    let install_dir = match PathResolver::font_installation_dir(scope) {
         Ok(dir) => dir,
-        Err(_) => return None, // Already logged error, just abort
-    };
-
-    let temp_dir =
-        env::temp_dir().join(format!("setup-devbox-font-{}", font.name.replace(' ', "-")));
-    let temp_dir_clone_for_cleanup = temp_dir.clone(); // Clone for deferred cleanup
-
-    // Ensure the temporary directory exists.
-    if let Err(e) = fs::create_dir_all(&temp_dir) {
-        log_error!(
-            "[SDB::Fonts::Installer] Failed to create temporary directory '{}': {}",
-            temp_dir.display().to_string().red(),
-            e.to_string().red()
-        );
-        cleanup_temp_dir(&temp_dir_clone_for_cleanup);
-        return None;
-    }
-
-    // 3. Download the font archive.
-    let downloaded_archive_path = match download_font_archive(
-        &font.name,
-        &font_details.url,
-        &temp_dir,
-        &font_details.asset_name,
-    ) {
-        Ok(path) => path,
         Err(e) => {
             log_error!(
-                "[SDB::Fonts::Installer] Failed to download font archive for '{}': {}",
+                "[SDB::Fonts::Installer] Cannot install '{}' with scope '{}': {}",
                 font.name.bold().red(),
-                e.to_string().red()
+                scope,
+                e
             );
-            cleanup_temp_dir(&temp_dir_clone_for_cleanup);
             return None;
         }
     };
+    // Fail before downloading when the files could not be copied anyway.
+    let use_sudo = check_install_dir_writable(font, &install_dir)?;
 
-    // 4. Extract the font archive.
-    let extracted_contents_dir = match extract_font_archive(
-        &font.name,
-        &downloaded_archive_path,
-        &temp_dir, // Extract into the temp directory
-    ) {
-        Ok(path) => path,
-        Err(e) => {
-            log_error!(
-                "[SDB::Fonts::Installer] Failed to extract font archive for '{}': {}",
-                font.name.bold().red(),
-                e.to_string().red()
-            );
-            cleanup_temp_dir(&temp_dir_clone_for_cleanup);
-            return None;
-        }
-    };
+    Some(PreparedFont {
+        font,
+        previous,
+        details,
+        install_dir,
+        use_sudo,
+    })
+}
 
-    // 5. Copy font files from extracted contents to the final installation directory.
-    let installed_font_files = match copy_non_hidden_font_files(
-        &font.name,
-        &extracted_contents_dir,
-        &font_install_dir,
-        &font.install_only,
-    ) {
-        Ok(files) => files,
-        Err(e) => {
-            log_error!(
-                "[SDB::Fonts::Installer] Failed to copy font files for '{}': {}",
-                font.name.bold().red(),
-                e.to_string().red()
-            );
-            cleanup_temp_dir(&temp_dir_clone_for_cleanup);
-            return None;
+/// Downloads and extracts one font archive into its own temporary directory, returning the
+/// extracted contents. Errors are logged by the download and extraction helpers.
+fn fetch_font_archive(details: &ValidatedFontDetails, temp_dir: &Path) -> Result<PathBuf, String> {
+    fs::create_dir_all(temp_dir).map_err(|e| {
+        format!(
+            "Failed to create temporary directory '{}': {}",
+            temp_dir.display(),
+            e
+        )
+    })?;
+    let archive = download_font_archive(
+        &details.asset_name,
+        &details.url,
+        temp_dir,
+        &details.asset_name,
+    )
+    .map_err(|e| format!("Failed to download {}: {}", details.url, e))?;
+    extract_font_archive(&details.asset_name, &archive, temp_dir)
+        .map_err(|e| format!("Failed to extract {}: {}", details.asset_name, e))
+}
+
+/// Temporary directory a font archive is downloaded and extracted into.
+fn archive_temp_dir(details: &ValidatedFontDetails) -> PathBuf {
+    let asset = details.asset_name.trim_end_matches(".zip");
+    PathResolver::temp_root().join(format!(
+        "setup-devbox-font-{}-{}-{}",
+        details.repo.replace('/', "-"),
+        details.tag,
+        asset
+    ))
+}
+
+/// Downloads and extracts the distinct archives of `fonts`, at most
+/// [`DOWNLOAD_CONCURRENCY`] at a time. Results are keyed by download URL.
+fn fetch_font_archives(fonts: &[&PreparedFont]) -> HashMap<String, Result<PathBuf, String>> {
+    fetch_distinct_archives(fonts, |details| {
+        fetch_font_archive(details, &archive_temp_dir(details))
+    })
+}
+
+/// Runs `fetch` once per distinct archive URL of `fonts`, on up to [`DOWNLOAD_CONCURRENCY`]
+/// threads.
+fn fetch_distinct_archives(
+    fonts: &[&PreparedFont],
+    fetch: impl Fn(&ValidatedFontDetails) -> Result<PathBuf, String> + Sync,
+) -> HashMap<String, Result<PathBuf, String>> {
+    let mut archives: Vec<&ValidatedFontDetails> = fonts.iter().map(|font| &font.details).collect();
+    archives.sort_by(|a, b| a.url.cmp(&b.url));
+    archives.dedup_by(|a, b| a.url == b.url);
+    if archives.len() < fonts.len() {
+        log_info!(
+            "[SDB::Fonts] {} font(s) share {} archive(s); each archive is downloaded once.",
+            fonts.len().to_string().yellow(),
+            archives.len().to_string().yellow()
+        );
+    }
+
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(HashMap::new());
+    std::thread::scope(|scope| {
+        for _ in 0..DOWNLOAD_CONCURRENCY.min(archives.len()) {
+            scope.spawn(|| {
+                while let Some(details) = archives.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let result = fetch(details);
+                    results.lock().unwrap().insert(details.url.clone(), result);
+                }
+            });
         }
-    };
+    });
+    results.into_inner().unwrap()
+}
 
-    // 6. Clean up the main temporary directory.
-    cleanup_temp_dir(&temp_dir_clone_for_cleanup);
+/// Copies the font's files out of its extracted archive and builds its `FontState`.
+fn finish_font_install(prepared: PreparedFont, extracted_dir: &Path) -> Option<FontState> {
+    let PreparedFont {
+        font,
+        previous,
+        details,
+        install_dir,
+        use_sudo,
+    } = prepared;
+
+    let installed_font_files =
+        match copy_non_hidden_font_files(font, extracted_dir, &install_dir, use_sudo) {
+            Ok(files) => files,
+            Err(e) => {
+                log_error!(
+                    "[SDB::Fonts::Installer] Failed to copy font files for '{}': {}",
+                    font.name.bold().red(),
+                    e.to_string().red()
+                );
+                return None;
+            }
+        };
 
-    // 7. Return FontState for Tracking (Conditional):
     // Only record the font's state if at least one file was successfully installed.
-    if !installed_font_files.is_empty() {
-        log_debug!(
-            "[SDB::Fonts::Installer] Constructing FontState for '{}'.",
-            font.name.bold()
-        );
-        Some(FontState {
-            name: font.name.clone(),
-            version: determine_font_version(font),
-            url: font_details.url,
-            install_method: font.source.clone(),
-            repo: font.repo.clone(),
-            tag: font.tag.clone(),
-            files: installed_font_files, // List of all successfully installed font files.
-            install_only: font.install_only.clone(),
-        })
-    } else {
-        // If no files were installed, return `None` to indicate no state should be recorded,
-        // but the process itself wasn't a critical failure. Log a warning.
+    if installed_font_files.is_empty() {
+        // No state is recorded, but the process itself wasn't a critical failure.
         log_warn!(
             "[SDB::Fonts::Installer] No .ttf or .otf font files were found or successfully copied from the archive for '{}'. \\\
-             The font might not be correctly installed, the archive format is unexpected, or no files matched the 'install_only' filter.",
+             The font might not be correctly installed, the archive format is unexpected, or no files matched the 'install_only'/'variant' filters.",
             font.name.yellow()
         );
-        None
+        return None;
+    }
+
+    let removed_files = remove_superseded_files(
+        font,
+        previous,
+        &install_dir,
+        &installed_font_files,
+        use_sudo,
+    );
+    let replaced = (!removed_files.is_empty()).then(|| FontReplacement {
+        from_version: previous.map(|state| state.version.clone()),
+        removed_files,
+    });
+
+    log_debug!(
+        "[SDB::Fonts::Installer] Constructing FontState for '{}'.",
+        font.name.bold()
+    );
+    let disk_usage_bytes = installed_font_files
+        .iter()
+        .map(|file| disk_usage::path_size(&install_dir.join(file)))
+        .sum();
+    Some(FontState {
+        name: font.name.clone(),
+        version: determine_font_version(font, &details.tag),
+        url: details.url,
+        install_method: font.source.clone(),
+        repo: Some(details.repo),
+        tag: Some(details.tag),
+        files: installed_font_files, // List of all successfully installed font files.
+        install_only: font.install_only.clone(),
+        variant: font.variant.clone(),
+        license: None,
+        notes: font.notes.clone(),
+        owner: font.owner.clone(),
+        scope: font.scope.filter(|scope| *scope == FontScope::System),
+        disk_usage_bytes: Some(disk_usage_bytes),
+        replaced,
+    })
+}
+
+/// Removes the font files of `install_dir` that the files just installed supersede:
+/// - files recorded for the previous version that this entry selects but no longer installed,
+/// - files of the same family that differ only in a version part of their name
+///   (`FiraCode-Regular-6.2.ttf` next to a new `FiraCode-Regular-6.3.ttf`).
+///
+/// Removal failures are logged and the file is left in place. Returns the removed file names.
+fn remove_superseded_files(
+    font: &FontEntry,
+    previous: Option<&FontState>,
+    install_dir: &Path,
+    installed: &[String],
+    use_sudo: bool,
+) -> Vec<String> {
+    let installed_families: Vec<String> = installed.iter().map(|f| font_family_key(f)).collect();
+    let mut superseded: Vec<String> = previous
+        .iter()
+        .flat_map(|state| &state.files)
+        .filter(|file| !installed.contains(file) && selects_font_file(font, file))
+        .cloned()
+        .collect();
+    if let Ok(entries) = fs::read_dir(install_dir) {
+        for entry in entries.flatten() {
+            let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            if !installed.contains(&name)
+                && !superseded.contains(&name)
+                && installed_families.contains(&font_family_key(&name))
+            {
+                superseded.push(name);
+            }
+        }
+    }
+
+    let mut removed = Vec::new();
+    for file in superseded {
+        let path = install_dir.join(&file);
+        if !path.is_file() {
+            continue;
+        }
+        let result = if use_sudo {
+            run_sudo(&["rm".as_ref(), "-f".as_ref(), path.as_os_str()])
+        } else {
+            fs::remove_file(&path)
+        };
+        match result {
+            Ok(()) => {
+                log_info!(
+                    "[SDB::Fonts] Removed '{}', superseded by the new version of '{}'.",
+                    file.yellow(),
+                    font.name.bold()
+                );
+                removed.push(file);
+            }
+            Err(e) => log_warn!(
+                "[SDB::Fonts::Installer] Failed to remove superseded font file '{}': {}",
+                path.display().to_string().yellow(),
+                e
+            ),
+        }
+    }
+    removed
+}
+
+/// Bytes the recorded files of `font_state` occupy in its font directory.
+pub(crate) fn files_disk_usage(font_state: &FontState) -> Option<u64> {
+    let dir = PathResolver::font_installation_dir(font_state.scope.unwrap_or_default()).ok()?;
+    Some(
+        font_state
+            .files
+            .iter()
+            .map(|file| disk_usage::path_size(&dir.join(file)))
+            .sum(),
+    )
+}
+
+/// Identifies a font file independently of the version in its name: the lowercase file name
+/// without `-`/`_`/space separated parts that look like versions (`6.2`, `v2.304`, `v3`).
+fn font_family_key(filename: &str) -> String {
+    let path = Path::new(filename);
+    let stem = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or(filename);
+    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
+    let parts: Vec<&str> = stem
+        .split(['-', '_', ' '])
+        .filter(|part| !is_version_part(part))
+        .collect();
+    format!("{}.{}", parts.join("-"), extension).to_lowercase()
+}
+
+/// Whether a file name part is a version: `v` followed by digits, or dotted numbers with an
+/// optional `v` (`1.2`, `v6.2.1`).
+fn is_version_part(part: &str) -> bool {
+    let (prefixed, number) = match part.strip_prefix(['v', 'V']) {
+        Some(rest) => (true, rest),
+        None => (false, part),
+    };
+    !number.is_empty()
+        && (prefixed || number.contains('.'))
+        && number
+            .split('.')
+            .all(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Installs fonts based on their `FontEntry` configuration.
+///
+/// This is the main public function for this module. Every entry is validated first; the
+/// archives of the valid ones are then downloaded and extracted concurrently, each distinct
+/// archive once, so entries selecting different files from the same Nerd Fonts zip share a
+/// single download. Finally each font's files are copied and its state is built.
+///
+/// Fonts that are already installed (an update to a newly pinned version) come with their
+/// recorded state; the files of the previous version are replaced rather than accumulated,
+/// and the replacement is recorded in the new `FontState`.
+///
+/// # Arguments
+/// * `fonts`: The font entries to install, each with its currently recorded state, if any.
+///
+/// # Returns
+/// One `Option<FontState>` per entry, in order:
+/// * `Some(FontState)` if at least one font file was successfully installed and recorded.
+/// * `None` if the font installation process failed for any reason (e.g., validation error,
+///   download failure, no font files found/copied). Error details are logged internally.
+pub fn install_all(fonts: &[(&FontEntry, Option<&FontState>)]) -> Vec<Option<FontState>> {
+    // 1. Validate the entries and their installation directories.
+    let prepared: Vec<Option<PreparedFont>> = fonts
+        .iter()
+        .map(|(font, previous)| prepare_font(font, *previous))
+        .collect();
+
+    // 2. Download and extract the archives.
+    let valid: Vec<&PreparedFont> = prepared.iter().flatten().collect();
+    let archives = fetch_font_archives(&valid);
+    let temp_dirs: Vec<PathBuf> = valid
+        .iter()
+        .map(|font| archive_temp_dir(&font.details))
+        .collect();
+
+    // 3. Copy each font's files from its extracted archive.
+    let states = prepared
+        .into_iter()
+        .map(|prepared| {
+            let prepared = prepared?;
+            match archives.get(&prepared.details.url)? {
+                Ok(extracted_dir) => finish_font_install(prepared, extracted_dir),
+                Err(e) => {
+                    log_error!(
+                        "[SDB::Fonts::Installer] Failed to fetch the font archive for '{}': {}",
+                        prepared.font.name.bold().red(),
+                        e.red()
+                    );
+                    None
+                }
+            }
+        })
+        .collect();
+
+    // 4. Clean up the temporary directories.
+    for temp_dir in temp_dirs {
+        cleanup_temp_dir(&temp_dir);
     }
+    states
 }
 
 /// Helper function to clean up a temporary directory.
@@ -465,3 +887,162 @@ fn cleanup_temp_dir(temp_dir: &Path) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nerd_font_variant_matches_file_names() {
+        let mono_regular = NerdFontVariant::parse("mono-regular").unwrap();
+        assert!(mono_regular.matches("JetBrainsMonoNerdFontMono-Regular.ttf"));
+        assert!(!mono_regular.matches("JetBrainsMonoNerdFont-Regular.ttf"));
+        assert!(!mono_regular.matches("JetBrainsMonoNerdFontMono-Bold.ttf"));
+
+        let propo = NerdFontVariant::parse("propo").unwrap();
+        assert!(propo.matches("FiraCodeNerdFontPropo-Light.ttf"));
+        assert!(!propo.matches("FiraCodeNerdFontMono-Light.ttf"));
+
+        let bold = NerdFontVariant::parse("Bold").unwrap();
+        assert!(bold.matches("HackNerdFont-Bold.ttf"));
+        assert!(!bold.matches("HackNerdFont-BoldItalic.ttf"));
+        assert!(!bold.matches("Hack-Bold.ttf"));
+
+        assert!(NerdFontVariant::parse("wide-bold").is_err());
+    }
+
+    #[test]
+    fn test_font_scope_checks_install_dir_before_download() {
+        let font = |yaml: &str| -> FontEntry {
+            serde_yaml::from_str(&format!("name: Hack\nsource: github\n{yaml}")).unwrap()
+        };
+        assert_eq!(font("").scope.unwrap_or_default(), FontScope::User);
+        assert_eq!(font("scope: system").scope, Some(FontScope::System));
+
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            check_install_dir_writable(&font(""), dir.path()),
+            Some(false)
+        );
+
+        // A user font is never installed with sudo
+        let blocker = dir.path().join("not-a-directory");
+        fs::write(&blocker, "").unwrap();
+        assert!(!is_writable_dir(&blocker.join("fonts")));
+        assert_eq!(
+            check_install_dir_writable(&font(""), &blocker.join("fonts")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_latest_font_records_the_resolved_tag() {
+        let font: FontEntry = serde_yaml::from_str(
+            "name: Hack\nsource: github\nrepo: ryanoasis/nerd-fonts\nversion: latest\n",
+        )
+        .unwrap();
+        assert!(font.wants_latest());
+        assert_eq!(determine_font_version(&font, "v3.4.0"), "v3.4.0");
+
+        let state: FontState = serde_json::from_value(serde_json::json!({
+            "name": "Hack",
+            "version": "v3.4.0",
+            "install_method": "github",
+            "url": "",
+            "files": [],
+            "tag": "v3.4.0"
+        }))
+        .unwrap();
+        assert!(!font.pins_other_version(&state));
+
+        let pinned: FontEntry =
+            serde_yaml::from_str("name: Hack\nsource: github\ntag: v3.3.0\n").unwrap();
+        assert!(!pinned.wants_latest());
+        assert!(pinned.pins_other_version(&state));
+    }
+
+    #[test]
+    fn test_font_family_key_ignores_versions() {
+        assert_eq!(
+            font_family_key("FiraCode-Regular-6.2.ttf"),
+            font_family_key("FiraCode-Regular-v6.3.ttf")
+        );
+        assert_eq!(
+            font_family_key("JetBrainsMono_v2.304_Bold.ttf"),
+            font_family_key("JetBrainsMono-Bold.ttf")
+        );
+        assert_ne!(
+            font_family_key("FiraCode-Regular.ttf"),
+            font_family_key("FiraCode-Bold.ttf")
+        );
+        assert_ne!(
+            font_family_key("FiraCode-Regular.ttf"),
+            font_family_key("FiraCode-Regular.otf")
+        );
+        // Weights and widths that contain digits are kept
+        assert_ne!(
+            font_family_key("Iosevka-SS08-Regular.ttf"),
+            font_family_key("Iosevka-Regular.ttf")
+        );
+        assert_ne!(
+            font_family_key("Font-3-Regular.ttf"),
+            font_family_key("Font-Regular.ttf")
+        );
+    }
+
+    #[test]
+    fn test_shared_archives_are_fetched_once_and_concurrently() {
+        let entries: Vec<FontEntry> = ["Hack", "Hack", "FiraCode"]
+            .iter()
+            .map(|name| serde_yaml::from_str(&format!("name: {name}\nsource: github")).unwrap())
+            .collect();
+        let prepared: Vec<PreparedFont> = entries
+            .iter()
+            .map(|font| PreparedFont {
+                font,
+                previous: None,
+                details: ValidatedFontDetails {
+                    repo: NERD_FONTS_REPO.to_string(),
+                    tag: "v3.4.0".to_string(),
+                    asset_name: format!("{}.zip", font.name),
+                    url: format!("https://example.com/{}.zip", font.name),
+                },
+                install_dir: PathBuf::new(),
+                use_sudo: false,
+            })
+            .collect();
+        let fonts: Vec<&PreparedFont> = prepared.iter().collect();
+
+        let calls = Mutex::new(Vec::new());
+        let in_flight = AtomicUsize::new(0);
+        let overlapped = std::sync::atomic::AtomicBool::new(false);
+        let archives = fetch_distinct_archives(&fonts, |details| {
+            calls.lock().unwrap().push(details.url.clone());
+            in_flight.fetch_add(1, Ordering::SeqCst);
+            let started = std::time::Instant::now();
+            while started.elapsed() < std::time::Duration::from_secs(2) {
+                if in_flight.load(Ordering::SeqCst) > 1 {
+                    overlapped.store(true, Ordering::SeqCst);
+                    break;
+                }
+                std::thread::yield_now();
+            }
+            Ok(PathBuf::from(&details.asset_name))
+        });
+
+        let mut calls = calls.into_inner().unwrap();
+        calls.sort();
+        assert_eq!(
+            calls,
+            [
+                "https://example.com/FiraCode.zip",
+                "https://example.com/Hack.zip"
+            ]
+        );
+        assert!(overlapped.load(Ordering::SeqCst));
+        assert_eq!(
+            archives["https://example.com/Hack.zip"],
+            Ok(PathBuf::from("Hack.zip"))
+        );
+    }
+}
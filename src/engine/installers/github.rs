@@ -20,8 +20,9 @@
 //! The installer follows a meticulous 10-step process:
 //!
 //! 1. **Platform Detection** - Detects OS and architecture for asset selection
-//! 2. **Configuration Validation** - Validates required repository and tag fields
-//! 3. **GitHub API Integration** - Fetches release information from GitHub API
+//! 2. **Configuration Validation** - Validates the required repository field
+//! 3. **GitHub API Integration** - Fetches the release for the tag (or the pinned version, or
+//!    the latest release when neither is given) from the GitHub API
 //! 4. **Asset Selection** - Finds and prioritizes platform-appropriate assets
 //! 5. **Asset Download** - Downloads the selected asset to temporary location
 //! 6. **File Type Detection** - Determines installation strategy based on file type
@@ -38,31 +39,60 @@
 //! - **Warn**: Non-fatal issues or warnings during installation
 //! - **Error**: Installation failures with specific error codes and messages
 
+// Standard library imports
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+
 // External crate imports
 use colored::Colorize;
 
 // Utility imports
 use crate::core::assets;
+use crate::core::checksum::{self, Checksum};
+use crate::core::metadata_cache;
 use crate::core::{
     assets::detect_file_type,
-    platform::{asset_matches_platform, detect_architecture, detect_os},
+    platform::{
+        self, AssetMatch, apply_arch_fallback, detect_architecture, detect_os, match_asset,
+    },
 };
 use crate::engine::execute_post_installation_hooks;
 use crate::engine::installers::errors::InstallerError;
 use crate::engine::installers::traits::Installer;
+use crate::engine::uninstaller::executors::{BinaryUninstaller, ItemToBeRemoved, ToolUninstaller};
 
 // Schema imports
 use crate::schemas::common::{Release, ReleaseAsset};
-use crate::schemas::state_file::ToolState;
+use crate::schemas::state_file::{InstalledAssetState, ToolState};
 use crate::schemas::tools_types::ToolEntry;
 
 // Custom logging macros
-use crate::{log_debug, log_error, log_info};
+use crate::{log_debug, log_error, log_info, log_warn};
+
+/// Maximum number of concurrent GitHub API requests made by [`prefetch_releases`].
+const PREFETCH_CONCURRENCY: usize = 8;
+
+/// Release metadata fetched during this run, keyed by `owner/repo@tag` (or `@latest`).
+/// Cleared by [`reset_release_cache`] when a run starts, so a long-running process (the
+/// daemon, the dashboard) sees releases published since its previous run.
+static RELEASES: OnceLock<Mutex<HashMap<String, Release>>> = OnceLock::new();
 
 /// Struct representing the GitHub installer.
 pub struct GitHubInstaller;
 
 impl Installer for GitHubInstaller {
+    fn name(&self) -> &'static str {
+        "github"
+    }
+
+    fn uninstall(&self, item: &ItemToBeRemoved) -> Result<(), InstallerError> {
+        BinaryUninstaller
+            .uninstall(item)
+            .map_err(InstallerError::CommandFailed)
+    }
+
     /// Installs a software tool by fetching its release asset from GitHub releases.
     ///
     /// This function provides a robust installer for GitHub-hosted tools that mirrors the quality
@@ -72,7 +102,7 @@ impl Installer for GitHubInstaller {
     /// # Workflow
     ///
     /// 1. **Platform Detection**: Detects OS and architecture for asset selection
-    /// 2. **Configuration Validation**: Validates required repository and tag fields
+    /// 2. **Configuration Validation**: Validates the required repository field
     /// 3. **GitHub API Integration**: Fetches release information from GitHub API
     /// 4. **Asset Selection**: Finds and prioritizes platform-appropriate assets
     /// 5. **Asset Download**: Downloads the selected asset to temporary location
@@ -86,7 +116,8 @@ impl Installer for GitHubInstaller {
     /// * `tool_entry` - A reference to the `ToolEntry` struct containing tool configuration
     ///   - `tool_entry.name`: **Required** - The tool name
     ///   - `tool_entry.repo`: **Required** - GitHub repository in "owner/repo" format
-    ///   - `tool_entry.tag`: **Required** - Release tag/version (e.g., "v1.0.0")
+    ///   - `tool_entry.tag`: Optional release tag (e.g., "v1.0.0"); without it the release of
+    ///     the pinned `version` is used, or the latest release for `version: latest`
     ///   - `tool_entry.rename_to`: Optional custom binary name
     ///   - `tool_entry.options`: Optional additional configuration
     ///
@@ -159,38 +190,26 @@ impl Installer for GitHubInstaller {
         let (os, arch) = detect_platform()?;
 
         // Step 2: Validate GitHub configuration - ensure required fields are present
-        let (repo, tag) = validate_github_configuration(tool_entry)?;
+        let repo = validate_github_configuration(tool_entry)?;
 
         // Step 3: Fetch release information from GitHub API
-        log_debug!("[SDB::Tools::GitHubInstaller] Fetching release information for {repo}/{tag}");
-        let release = fetch_github_release(repo, tag)?;
+        let release = fetch_release_for(repo, release_selector(tool_entry))?;
+        let tag = &release.tag_name;
 
         // Step 4: Select appropriate asset for the detected platform
         log_debug!("[SDB::Tools::GitHubInstaller] Selecting asset for {os}-{arch}");
         let asset = select_platform_asset(&release, &os, &arch)?;
 
-        // Step 5: Download asset to temporary location
-        log_debug!(
-            "[SDB::Tools::GitHubInstaller] Downloading asset: {}",
-            asset.name.bold()
-        );
-        let (temp_dir, downloaded_path) =
-            assets::download_url_asset(tool_entry, &asset.browser_download_url)
-                .ok_or_else(|| InstallerError::DownloadFailed("Failed to download asset".into()))?;
+        // Step 4b: Determine the digest to verify the asset against, if any
+        let checksum = expected_checksum(tool_entry, &release, asset)?;
 
-        // Step 6: Detect file type and determine installation strategy
-        let file_type = detect_file_type(&downloaded_path);
-        log_debug!(
-            "[SDB::Tools::GitHubInstaller] Detected file type: {}",
-            file_type.to_string().magenta()
-        );
+        // Steps 5-7: Stream tar-family archives straight into the extraction directory when
+        // possible; otherwise download to a temporary file, detect its type and process it.
+        let (_temp_dir, package_type, final_install_path, working_dir) =
+            download_and_process_asset(tool_entry, asset, checksum.as_ref())?;
 
-        // Step 7: Process asset based on file type (binary, archive, or macOS package)
-        let (package_type, final_install_path, working_dir) =
-            assets::process_asset_by_type(tool_entry, &downloaded_path, &file_type, &temp_dir)
-                .ok_or_else(|| {
-                    InstallerError::InstallationFailed("Failed to process asset".into())
-                })?;
+        // Step 7b: Install any additional assets (completions, man pages, data) from the release
+        let installed_assets = install_additional_assets(tool_entry, &release)?;
 
         // Step 8: Execute any post-installation hooks defined in tool configuration
         log_debug!(
@@ -209,22 +228,30 @@ impl Installer for GitHubInstaller {
             tag.green()
         );
 
-        // Step 9: Return comprehensive ToolState for state tracking and persistence
-        let tool_state = ToolState::new(
+        // Step 9: Return comprehensive ToolState for state tracking and persistence.
+        // `latest` (or a missing version) is recorded as the resolved tag, so later runs can
+        // tell whether a newer release was published.
+        let version = match tool_entry.version.as_deref() {
+            Some(version) if version != "latest" => version.to_string(),
+            _ => tag.clone(),
+        };
+        let mut tool_state = ToolState::new(
             tool_entry,
             &final_install_path,
             "github".to_string(),
             package_type,
-            tool_entry.version.clone().unwrap_or_else(|| tag.clone()), // Use tag if version is missing
+            version,
             Some(asset.browser_download_url.clone()),
             None,
             executed_post_installation_hooks,
         );
+        tool_state.tag = Some(tag.clone());
+        tool_state.assets = installed_assets;
 
         Ok(tool_state)
     }
 
-    /// # `get_latest_version`
+    /// # `resolve_latest_version`
     ///
     /// Fetches the latest release for a GitHub repository and returns the tag name.
     ///
@@ -239,7 +266,7 @@ impl Installer for GitHubInstaller {
     /// - `Ok(String)`: A string containing the tag name of the latest release (e.g., "v1.2.3").
     /// - `Err(InstallerError)`: An `InstallerError` if the repository is not specified or
     ///   if the API call fails.
-    fn get_latest_version(&self, tool_entry: &ToolEntry) -> Result<String, InstallerError> {
+    fn resolve_latest_version(&self, tool_entry: &ToolEntry) -> Result<String, InstallerError> {
         log_debug!(
             "[SDB::Tools::GitHubInstaller] Getting latest version for: {}",
             tool_entry.name.bold()
@@ -264,6 +291,229 @@ impl Installer for GitHubInstaller {
     }
 }
 
+/// Downloads the selected release asset and installs it.
+///
+/// Tar-family archives are streamed through the decompressor directly into the extraction
+/// directory (see `assets::stream_url_asset`). If streaming does not apply or fails midway,
+/// the buffered path is used: download to a temporary file, detect the file type, then process.
+/// Assets with an expected `checksum` always take the buffered path and are verified before
+/// they are processed.
+///
+/// # Returns
+///
+/// * `Ok((temp_dir, package_type, final_install_path, working_dir))` - The temporary directory
+///   must be kept alive until post-installation hooks have run in `working_dir`
+/// * `Err(InstallerError)` - If download or processing fails
+fn download_and_process_asset(
+    tool_entry: &ToolEntry,
+    asset: &ReleaseAsset,
+    checksum: Option<&Checksum>,
+) -> Result<(tempfile::TempDir, String, PathBuf, PathBuf), InstallerError> {
+    if checksum.is_none()
+        && let Some(file_type) = assets::streamable_file_type(tool_entry, &asset.name)
+    {
+        log_debug!(
+            "[SDB::Tools::GitHubInstaller] Streaming {} asset: {}",
+            file_type.magenta(),
+            asset.name.bold()
+        );
+        if let Some((temp_dir, extracted_path, _)) =
+            assets::stream_url_asset(tool_entry, &asset.browser_download_url, &file_type)
+        {
+            let (package_type, final_install_path, working_dir) =
+                assets::process_streamed_asset(tool_entry, &extracted_path).ok_or_else(|| {
+                    InstallerError::InstallationFailed("Failed to process asset".into())
+                })?;
+            return Ok((temp_dir, package_type, final_install_path, working_dir));
+        }
+        log_warn!(
+            "[SDB::Tools::GitHubInstaller] Streaming failed for {}, falling back to buffered download",
+            asset.name.yellow()
+        );
+    }
+
+    // Step 5: Download asset to temporary location
+    log_debug!(
+        "[SDB::Tools::GitHubInstaller] Downloading asset: {}",
+        asset.name.bold()
+    );
+    let (temp_dir, downloaded_path) =
+        assets::download_url_asset(tool_entry, &asset.browser_download_url)
+            .ok_or_else(|| InstallerError::DownloadFailed("Failed to download asset".into()))?;
+
+    // Step 5b: Verify the download against the expected digest before touching it
+    if let Some(checksum) = checksum {
+        checksum.verify_file(&downloaded_path).map_err(|e| {
+            log_error!("[SDB::Tools::GitHubInstaller] {}", e);
+            InstallerError::ValidationFailed(e)
+        })?;
+        log_info!(
+            "[SDB::Tools::GitHubInstaller] Verified {} checksum of {}",
+            checksum.algorithm.to_string().green(),
+            asset.name.bold()
+        );
+    }
+
+    // Step 6: Detect file type and determine installation strategy
+    let file_type = detect_file_type(&downloaded_path);
+    log_debug!(
+        "[SDB::Tools::GitHubInstaller] Detected file type: {}",
+        file_type.to_string().magenta()
+    );
+
+    // Step 7: Process asset based on file type (binary, archive, or macOS package)
+    let (package_type, final_install_path, working_dir) =
+        assets::process_asset_by_type(tool_entry, &downloaded_path, &file_type, &temp_dir)
+            .ok_or_else(|| InstallerError::InstallationFailed("Failed to process asset".into()))?;
+
+    Ok((temp_dir, package_type, final_install_path, working_dir))
+}
+
+/// Determines the digest the selected asset must match.
+///
+/// The tool's `checksum` field wins. Otherwise the release is searched for a checksum file
+/// covering the asset: a per-asset sidecar (`<asset>.sha256`, ...) first, then a listing of
+/// all assets (`checksums.txt`, `SHA256SUMS`, ...). A published checksum file that cannot be
+/// fetched or does not mention the asset fails the installation; pinning `checksum` in the
+/// tool's entry overrides it.
+///
+/// # Returns
+///
+/// * `Ok(Some(checksum))` - The digest to verify the download against
+/// * `Ok(None)` - No checksum is configured or published for the asset
+/// * `Err(InstallerError)` - If the configured `checksum` is malformed, or the published
+///   checksum file can't be used to verify the asset
+fn expected_checksum(
+    tool_entry: &ToolEntry,
+    release: &Release,
+    asset: &ReleaseAsset,
+) -> Result<Option<Checksum>, InstallerError> {
+    checksum_from_release(tool_entry, release, asset, |url| {
+        checksum::fetch_listing(tool_entry, url)
+    })
+}
+
+/// [`expected_checksum`] with the download of the checksum file done by `fetch`.
+fn checksum_from_release(
+    tool_entry: &ToolEntry,
+    release: &Release,
+    asset: &ReleaseAsset,
+    fetch: impl Fn(&str) -> std::io::Result<String>,
+) -> Result<Option<Checksum>, InstallerError> {
+    if let Some(spec) = &tool_entry.checksum {
+        return Checksum::parse(spec).map(Some).map_err(|e| {
+            let msg = format!("Invalid checksum for tool {}: {}", tool_entry.name, e);
+            log_error!("[SDB::Tools::GitHubInstaller] {}", msg);
+            InstallerError::ConfigurationError(msg)
+        });
+    }
+
+    let find = |name: &str| release.assets.iter().find(|a| a.name == name);
+    let sidecar = checksum::sidecar_names(&asset.name)
+        .iter()
+        .find_map(|name| find(name))
+        .map(|a| (a, true));
+    let Some((checksum_asset, allow_bare)) = sidecar.or_else(|| {
+        release
+            .assets
+            .iter()
+            .find(|a| checksum::is_checksum_listing(&a.name))
+            .map(|a| (a, false))
+    }) else {
+        log_debug!(
+            "[SDB::Tools::GitHubInstaller] No checksum file published for {}",
+            asset.name
+        );
+        return Ok(None);
+    };
+
+    log_debug!(
+        "[SDB::Tools::GitHubInstaller] Reading checksum of {} from {}",
+        asset.name.bold(),
+        checksum_asset.name.cyan()
+    );
+    fetch(&checksum_asset.browser_download_url)
+        .map_err(|e| e.to_string())
+        .and_then(|listing| {
+            checksum::find_in_listing(&listing, &asset.name, allow_bare)
+                .ok_or_else(|| format!("{} is not listed", asset.name))
+        })
+        .map(Some)
+        .map_err(|e| {
+            let msg = format!(
+                "Cannot verify {} with checksum file {} ({}); set 'checksum' for tool {} to pin its digest",
+                asset.name, checksum_asset.name, e, tool_entry.name
+            );
+            log_error!("[SDB::Tools::GitHubInstaller] {}", msg);
+            InstallerError::ValidationFailed(msg)
+        })
+}
+
+/// Installs the extra release assets declared in the tool's `assets:` list.
+///
+/// Each entry's `pattern` is matched against the release's asset names (the first match
+/// wins) and installed to its `destination` via `assets::install_extra_asset`. A missing or
+/// failed asset fails the whole installation, so the tool is never recorded half-installed.
+///
+/// # Returns
+///
+/// * `Ok(Some(states))` - One `InstalledAssetState` per configured asset
+/// * `Ok(None)` - If the tool declares no additional assets
+/// * `Err(InstallerError)` - If an asset cannot be found or installed
+fn install_additional_assets(
+    tool_entry: &ToolEntry,
+    release: &Release,
+) -> Result<Option<Vec<InstalledAssetState>>, InstallerError> {
+    let Some(asset_entries) = tool_entry.assets.as_ref().filter(|a| !a.is_empty()) else {
+        return Ok(None);
+    };
+
+    let mut installed = Vec::with_capacity(asset_entries.len());
+    for asset_entry in asset_entries {
+        let release_asset = release
+            .assets
+            .iter()
+            .find(|a| assets::name_matches_pattern(&a.name, &asset_entry.pattern))
+            .ok_or_else(|| {
+                let msg = format!(
+                    "No release asset matches pattern '{}' for tool {}",
+                    asset_entry.pattern, tool_entry.name
+                );
+                log_error!("[SDB::Tools::GitHubInstaller] {}", msg);
+                InstallerError::ConfigurationError(msg)
+            })?;
+
+        log_debug!(
+            "[SDB::Tools::GitHubInstaller] Installing additional asset {} (pattern '{}')",
+            release_asset.name.bold(),
+            asset_entry.pattern
+        );
+        let paths = assets::install_extra_asset(
+            tool_entry,
+            asset_entry,
+            &release_asset.name,
+            &release_asset.browser_download_url,
+        )
+        .ok_or_else(|| {
+            InstallerError::InstallationFailed(format!(
+                "Failed to install asset '{}'",
+                release_asset.name
+            ))
+        })?;
+
+        installed.push(InstalledAssetState {
+            name: release_asset.name.clone(),
+            url: release_asset.browser_download_url.clone(),
+            installed_paths: paths
+                .iter()
+                .map(|p| p.to_string_lossy().into_owned())
+                .collect(),
+        });
+    }
+
+    Ok(Some(installed))
+}
+
 /// Detects the current platform (OS and architecture).
 ///
 /// This function detects both the operating system and CPU architecture,
@@ -300,11 +550,10 @@ fn detect_platform() -> Result<(String, String), InstallerError> {
     Ok((os, arch))
 }
 
-/// Validates that the tool configuration contains required GitHub fields.
+/// Validates that the tool configuration contains the required GitHub fields.
 ///
-/// This function checks that both the repository and tag fields are specified
-/// in the tool configuration, as they are mandatory for GitHub release installations.
-/// Without these fields, the installer cannot locate or download the correct release.
+/// The repository is mandatory for GitHub release installations; the release itself is
+/// chosen by [`release_selector`].
 ///
 /// # Arguments
 ///
@@ -312,16 +561,13 @@ fn detect_platform() -> Result<(String, String), InstallerError> {
 ///
 /// # Returns
 ///
-/// * `Ok((repo, tag))` - References to the repository and tag strings
+/// * `Ok(repo)` - Reference to the repository string
 /// * `Err(InstallerError)` - If validation fails
 ///
 /// # Configuration Requirements
 ///
 /// - `repo`: Must be in "owner/repo" format (e.g., "cli/cli", "helm/helm")
-/// - `tag`: Must match a valid release tag in the repository (e.g., "v1.0.0", "1.0.0")
-fn validate_github_configuration(
-    tool_entry: &ToolEntry,
-) -> Result<(&String, &String), InstallerError> {
+fn validate_github_configuration(tool_entry: &ToolEntry) -> Result<&String, InstallerError> {
     // Verify repository field is present
     let repo = tool_entry.repo.as_ref().ok_or_else(|| {
         let msg = format!(
@@ -332,17 +578,75 @@ fn validate_github_configuration(
         InstallerError::ConfigurationError(msg)
     })?;
 
-    // Verify tag field is present
-    let tag = tool_entry.tag.as_ref().ok_or_else(|| {
-        let msg = format!(
-            "Configuration error: 'tag' field is missing for tool {}. Expected 'v1.0.0'.",
-            tool_entry.name
-        );
-        log_error!("[SDB::Tools::GitHubInstaller] {}", msg);
-        InstallerError::ConfigurationError(msg)
-    })?;
+    Ok(repo)
+}
+
+/// Which release of a GitHub tool to install.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReleaseSelector<'a> {
+    /// The release with this tag (`tag:`)
+    Tag(&'a str),
+    /// The release of this version, tagged with or without a leading `v` (`version:`
+    /// without `tag:`)
+    Version(&'a str),
+    /// The latest release (`version: latest`, or neither `version` nor `tag`)
+    Latest,
+}
+
+/// Chooses the release a GitHub tool installs: its `tag`, else its pinned `version`, else the
+/// latest release. `latest` (or an empty value) in either field means the latest release.
+pub(crate) fn release_selector(tool_entry: &ToolEntry) -> ReleaseSelector<'_> {
+    match (
+        pinned(tool_entry.tag.as_deref()),
+        pinned(tool_entry.version.as_deref()),
+    ) {
+        (Some(tag), _) => ReleaseSelector::Tag(tag),
+        // `tag: latest` asks for the latest release whatever the version says
+        (None, Some(version)) if tool_entry.tag.as_deref().is_none_or(str::is_empty) => {
+            ReleaseSelector::Version(version)
+        }
+        _ => ReleaseSelector::Latest,
+    }
+}
+
+/// Fetches the release chosen by `selector`.
+pub(crate) fn fetch_release_for(
+    repo: &str,
+    selector: ReleaseSelector,
+) -> Result<Release, InstallerError> {
+    match selector {
+        ReleaseSelector::Tag(tag) => {
+            log_debug!("[SDB::Tools::GitHubInstaller] Fetching release {tag} of {repo}");
+            fetch_github_release(repo, tag)
+        }
+        ReleaseSelector::Version(version) => {
+            let [first, second] = version_tags(version);
+            log_debug!(
+                "[SDB::Tools::GitHubInstaller] Fetching the release of version {version} of {repo}"
+            );
+            fetch_github_release(repo, &first).or_else(|_| fetch_github_release(repo, &second))
+        }
+        ReleaseSelector::Latest => {
+            log_info!(
+                "[SDB::Tools::GitHubInstaller] Resolving the latest release of {}",
+                repo.cyan()
+            );
+            fetch_latest_github_release(repo)
+        }
+    }
+}
 
-    Ok((repo, tag))
+/// A `tag` or `version` naming a specific release (not empty, not `latest`).
+fn pinned(field: Option<&str>) -> Option<&str> {
+    field.filter(|value| !value.is_empty() && *value != "latest")
+}
+
+/// Tags a release of `version` is commonly published under, most likely first.
+fn version_tags(version: &str) -> [String; 2] {
+    match version.strip_prefix('v') {
+        Some(bare) => [version.to_string(), bare.to_string()],
+        None => [format!("v{version}"), version.to_string()],
+    }
 }
 
 /// Fetches release information from the GitHub API.
@@ -375,46 +679,37 @@ fn validate_github_configuration(
 /// - Invalid repository or tag names
 /// - Rate limiting (60 requests/hour for unauthenticated requests)
 /// - Repository not found or private repository without authentication
-fn fetch_github_release(repo: &str, tag: &str) -> Result<Release, InstallerError> {
+pub(crate) fn fetch_github_release(repo: &str, tag: &str) -> Result<Release, InstallerError> {
+    cached_release(&format!("{repo}@{tag}"), || {
+        request_github_release(repo, tag)
+    })
+}
+
+fn request_github_release(repo: &str, tag: &str) -> Result<Release, InstallerError> {
     // Construct GitHub API URL for the specific release
     let api_url = format!("https://api.github.com/repos/{repo}/releases/tags/{tag}");
     log_debug!("[SDB::Tools::GitHubInstaller] API URL: {}", api_url.blue());
 
-    // Make HTTP GET request with required User-Agent header
-    let response = ureq::get(&api_url)
-        .set("User-Agent", "setup-devbox")
-        .call()
-        .map_err(|e| {
-            let msg = format!("Failed to fetch GitHub release for {}/{}: {}", repo, tag, e);
-            log_error!("[SDB::Tools::GitHubInstaller] {}", msg);
-            InstallerError::NetworkError(msg)
-        })?;
-
-    // Check for HTTP error status codes (4xx, 5xx)
-    if response.status() >= 400 {
-        let msg = format!(
-            "GitHub API error (HTTP {}) for {}/{}",
-            response.status(),
-            repo,
-            tag
-        );
+    // Make HTTP GET request (through the metadata cache) with required User-Agent header
+    let body = metadata_cache::get(&format!("github:{repo}@{tag}"), &api_url).map_err(|e| {
+        let msg = format!("Failed to fetch GitHub release for {}/{}: {}", repo, tag, e);
         log_error!("[SDB::Tools::GitHubInstaller] {}", msg);
 
         // Provide helpful context for common error codes
-        match response.status() {
-            404 => log_error!(
+        match *e {
+            ureq::Error::Status(404, _) => log_error!(
                 "[SDB::Tools::GitHubInstaller] Release not found. Verify the repository and tag are correct."
             ),
-            403 => log_error!(
+            ureq::Error::Status(403, _) => log_error!(
                 "[SDB::Tools::GitHubInstaller] Rate limit exceeded or access forbidden. Consider authenticating for higher limits."
             ),
             _ => {}
         }
-        return Err(InstallerError::NetworkError(msg));
-    }
+        InstallerError::NetworkError(msg)
+    })?;
 
     // Parse JSON response into Release struct
-    response.into_json().map_err(|err| {
+    serde_json::from_str(&body).map_err(|err| {
         let msg = format!(
             "Failed to parse GitHub release JSON for {}/{}: {}",
             repo, tag, err
@@ -424,33 +719,26 @@ fn fetch_github_release(repo: &str, tag: &str) -> Result<Release, InstallerError
     })
 }
 
-fn fetch_latest_github_release(repo: &str) -> Result<Release, InstallerError> {
+pub(crate) fn fetch_latest_github_release(repo: &str) -> Result<Release, InstallerError> {
+    cached_release(&format!("{repo}@latest"), || {
+        request_latest_github_release(repo)
+    })
+}
+
+fn request_latest_github_release(repo: &str) -> Result<Release, InstallerError> {
     let api_url = format!("https://api.github.com/repos/{repo}/releases/latest");
     log_debug!(
         "[SDB::Tools::GitHubInstaller] Latest API URL: {}",
         api_url.blue()
     );
 
-    let response = ureq::get(&api_url)
-        .set("User-Agent", "setup-devbox")
-        .call()
-        .map_err(|e| {
-            let msg = format!("Failed to fetch latest GitHub release for {}: {}", repo, e);
-            log_error!("[SDB::Tools::GitHubInstaller] {}", msg);
-            InstallerError::NetworkError(msg)
-        })?;
-
-    if response.status() >= 400 {
-        let msg = format!(
-            "GitHub API error (HTTP {}) for latest release of {}",
-            response.status(),
-            repo
-        );
+    let body = metadata_cache::get(&format!("github:{repo}@latest"), &api_url).map_err(|e| {
+        let msg = format!("Failed to fetch latest GitHub release for {}: {}", repo, e);
         log_error!("[SDB::Tools::GitHubInstaller] {}", msg);
-        return Err(InstallerError::NetworkError(msg));
-    }
+        InstallerError::NetworkError(msg)
+    })?;
 
-    response.into_json().map_err(|err| {
+    serde_json::from_str(&body).map_err(|err| {
         let msg = format!(
             "Failed to parse latest GitHub release JSON for {}: {}",
             repo, err
@@ -460,6 +748,85 @@ fn fetch_latest_github_release(repo: &str) -> Result<Release, InstallerError> {
     })
 }
 
+/// Forgets the release metadata of the previous run. Called when a run starts.
+pub fn reset_release_cache() {
+    if let Some(releases) = RELEASES.get() {
+        releases.lock().unwrap().clear();
+    }
+}
+
+/// Returns the release stored under `key`, fetching and storing it on first use.
+///
+/// Failures are not cached, so a tool whose prefetch failed retries during installation
+/// and reports the error there.
+fn cached_release(
+    key: &str,
+    fetch: impl FnOnce() -> Result<Release, InstallerError>,
+) -> Result<Release, InstallerError> {
+    let releases = RELEASES.get_or_init(Default::default);
+    if let Some(release) = releases.lock().unwrap().get(key) {
+        log_debug!("[SDB::Tools::GitHubInstaller] Using prefetched release for {key}");
+        return Ok(release.clone());
+    }
+
+    let release = fetch()?;
+    releases
+        .lock()
+        .unwrap()
+        .insert(key.to_string(), release.clone());
+    Ok(release)
+}
+
+/// Resolves the release metadata of several GitHub tools concurrently, before any of them is
+/// installed, so the per-tool API round-trips overlap instead of adding up.
+///
+/// At most [`PREFETCH_CONCURRENCY`] requests run at once. Each distinct `repo@tag` is fetched
+/// once; with `latest` set, or for tools without a tag or pinned version, the latest release
+/// is resolved instead. Tools pinned by `version` alone are left to `install`, which tries
+/// both tag spellings.
+/// Results are kept for the rest of the run and picked up by `install` and
+/// `resolve_latest_version`. Errors are only logged here.
+pub fn prefetch_releases(tools: &[&ToolEntry], latest: bool) {
+    let mut requests: Vec<(&str, Option<&str>)> = tools
+        .iter()
+        .filter_map(|tool| {
+            let repo = tool.repo.as_deref()?;
+            match release_selector(tool) {
+                _ if latest => Some((repo, None)),
+                ReleaseSelector::Tag(tag) => Some((repo, Some(tag))),
+                ReleaseSelector::Version(_) => None,
+                ReleaseSelector::Latest => Some((repo, None)),
+            }
+        })
+        .collect();
+    requests.sort_unstable();
+    requests.dedup();
+    if requests.is_empty() {
+        return;
+    }
+
+    log_info!(
+        "[SDB::Tools::GitHubInstaller] Prefetching release metadata for {} GitHub tool(s)",
+        requests.len().to_string().yellow()
+    );
+    let next = AtomicUsize::new(0);
+    std::thread::scope(|scope| {
+        for _ in 0..PREFETCH_CONCURRENCY.min(requests.len()) {
+            scope.spawn(|| {
+                while let Some(&(repo, tag)) = requests.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let result = match tag {
+                        Some(tag) => fetch_github_release(repo, tag),
+                        None => fetch_latest_github_release(repo),
+                    };
+                    if let Err(e) = result {
+                        log_debug!("[SDB::Tools::GitHubInstaller] Prefetch failed for {repo}: {e}");
+                    }
+                }
+            });
+        }
+    });
+}
+
 /// Selects the most appropriate asset for the current platform.
 ///
 /// This function filters release assets by platform compatibility and prioritizes
@@ -480,7 +847,8 @@ fn fetch_latest_github_release(repo: &str) -> Result<Release, InstallerError> {
 ///
 /// # Asset Selection Strategy
 ///
-/// 1. Filter all assets for platform compatibility (OS and architecture match)
+/// 1. Filter all assets for platform compatibility (OS and architecture match), keeping
+///    assets built for a fallback architecture as the `arch_fallback` policy allows
 /// 2. Prioritize asset types in this order (for macOS):
 ///    - `.pkg` files (macOS installer packages)
 ///    - `.dmg` files (macOS disk images)
@@ -497,16 +865,26 @@ fn select_platform_asset<'a>(
     arch: &str,
 ) -> Result<&'a ReleaseAsset, InstallerError> {
     // Filter assets to only those matching the current platform
-    let mut matching_assets: Vec<&ReleaseAsset> = release
+    let candidates: Vec<(&ReleaseAsset, AssetMatch)> = release
         .assets
         .iter()
-        .filter(|asset| asset_matches_platform(&asset.name, os, arch))
+        .filter_map(|asset| match_asset(&asset.name, os, arch).map(|matched| (asset, matched)))
         .collect();
+    let candidate_count = candidates.len();
+    let mut matching_assets = apply_arch_fallback(candidates);
 
     // Handle case where no assets match the platform
     if matching_assets.is_empty() {
         let available_assets: Vec<String> = release.assets.iter().map(|a| a.name.clone()).collect();
-        let msg = format!("No suitable asset found for platform {}-{}.", os, arch);
+        let mut msg = format!("No suitable asset found for platform {}-{}.", os, arch);
+        if candidate_count > 0 {
+            msg.push_str(&format!(
+                " {} asset(s) built for {} were skipped (arch_fallback: {}).",
+                candidate_count,
+                platform::fallback_arch(os, arch).unwrap_or("another architecture"),
+                platform::arch_fallback()
+            ));
+        }
         log_error!("[SDB::Tools::GitHubInstaller] {}", msg);
         log_error!(
             "[SDB::Tools::GitHubInstaller] Available assets: {}",
@@ -520,7 +898,7 @@ fn select_platform_asset<'a>(
 
     // Sort assets to prioritize macOS packages (.pkg and .dmg files)
     // These provide better integration with macOS than raw binaries or archives
-    matching_assets.sort_by(|a, b| {
+    matching_assets.sort_by(|(a, _), (b, _)| {
         let a_is_macos_pkg = a.name.ends_with(".pkg") || a.name.ends_with(".dmg");
         let b_is_macos_pkg = b.name.ends_with(".pkg") || b.name.ends_with(".dmg");
 
@@ -532,5 +910,110 @@ fn select_platform_asset<'a>(
     });
 
     // Select the first (highest priority) asset after sorting
-    Ok(matching_assets.first().unwrap())
+    let (asset, matched) = matching_assets[0];
+    if let AssetMatch::Fallback(fallback) = matched {
+        log_warn!(
+            "[SDB::Tools::GitHubInstaller] Installing the {} build {} on {}-{} (arch_fallback: {})",
+            fallback,
+            asset.name.yellow(),
+            os,
+            arch,
+            platform::arch_fallback()
+        );
+    }
+    Ok(asset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(version: Option<&str>, tag: Option<&str>) -> ToolEntry {
+        ToolEntry {
+            version: version.map(str::to_string),
+            tag: tag.map(str::to_string),
+            ..serde_yaml::from_str("name: rg\nsource: github\nrepo: BurntSushi/ripgrep").unwrap()
+        }
+    }
+
+    #[test]
+    fn test_release_selector() {
+        assert_eq!(
+            release_selector(&tool(Some("14.1.0"), Some("v14.1.0"))),
+            ReleaseSelector::Tag("v14.1.0")
+        );
+        assert_eq!(
+            release_selector(&tool(Some("latest"), Some("v14.1.0"))),
+            ReleaseSelector::Tag("v14.1.0")
+        );
+        assert_eq!(
+            release_selector(&tool(Some("14.1.0"), None)),
+            ReleaseSelector::Version("14.1.0")
+        );
+        assert_eq!(release_selector(&tool(None, None)), ReleaseSelector::Latest);
+        assert_eq!(
+            release_selector(&tool(Some("latest"), None)),
+            ReleaseSelector::Latest
+        );
+        assert_eq!(
+            release_selector(&tool(Some("14.1.0"), Some("latest"))),
+            ReleaseSelector::Latest
+        );
+    }
+
+    #[test]
+    fn test_cached_release_hits_until_reset() {
+        let fetches = std::cell::Cell::new(0);
+        let fetch = || {
+            fetches.set(fetches.get() + 1);
+            Ok(serde_json::from_str::<Release>(r#"{"assets":[],"tag_name":"v1.0.0"}"#).unwrap())
+        };
+        let key = "example/cache-test@latest";
+
+        assert_eq!(cached_release(key, fetch).unwrap().tag_name, "v1.0.0");
+        assert_eq!(cached_release(key, fetch).unwrap().tag_name, "v1.0.0");
+        assert_eq!(fetches.get(), 1);
+
+        // Failures are not cached
+        let failing = || Err(InstallerError::NetworkError("offline".to_string()));
+        assert!(cached_release("example/failing@latest", failing).is_err());
+        assert!(cached_release("example/failing@latest", fetch).is_ok());
+        assert_eq!(fetches.get(), 2);
+
+        reset_release_cache();
+        cached_release(key, fetch).unwrap();
+        assert_eq!(fetches.get(), 3);
+    }
+
+    #[test]
+    fn test_version_tags() {
+        assert_eq!(version_tags("14.1.0"), ["v14.1.0", "14.1.0"]);
+        assert_eq!(version_tags("v2.50.0"), ["v2.50.0", "2.50.0"]);
+    }
+
+    #[test]
+    fn test_unlisted_asset_fails_checksum_verification() {
+        let release: Release = serde_json::from_value(serde_json::json!({
+            "tag_name": "v14.1.0",
+            "assets": [
+                {"name": "rg-linux.tar.gz", "browser_download_url": "https://example.com/rg-linux.tar.gz"},
+                {"name": "checksums.txt", "browser_download_url": "https://example.com/checksums.txt"},
+            ],
+        }))
+        .unwrap();
+        let asset = &release.assets[0];
+        let digest = "a".repeat(64);
+
+        let listed = |_: &str| Ok(format!("{digest}  rg-linux.tar.gz\n"));
+        let checksum = checksum_from_release(&tool(None, None), &release, asset, listed).unwrap();
+        assert_eq!(checksum.unwrap().hex, digest);
+
+        let unlisted = |_: &str| Ok(format!("{digest}  rg-macos.tar.gz\n"));
+        let error =
+            checksum_from_release(&tool(None, None), &release, asset, unlisted).unwrap_err();
+        assert!(error.to_string().contains("rg-linux.tar.gz is not listed"));
+
+        let offline = |_: &str| Err(std::io::Error::other("offline"));
+        assert!(checksum_from_release(&tool(None, None), &release, asset, offline).is_err());
+    }
 }
@@ -42,6 +42,7 @@ use std::process::Command;
 use crate::engine::execute_post_installation_hooks;
 use crate::engine::installers::errors::InstallerError;
 use crate::engine::installers::traits::Installer;
+use crate::engine::uninstaller::executors::{GoUninstaller, ItemToBeRemoved, ToolUninstaller};
 // Internal module imports:
 // `ToolEntry`: Represents a single tool's configuration from `tools.yaml`.
 // `ToolState`: Represents the actual state of an installed tool for persistence in `state.json`.
@@ -57,6 +58,20 @@ use colored::Colorize;
 pub struct GoInstaller;
 
 impl Installer for GoInstaller {
+    fn name(&self) -> &'static str {
+        "go"
+    }
+
+    fn required_command(&self) -> Option<&'static str> {
+        Some("go")
+    }
+
+    fn uninstall(&self, item: &ItemToBeRemoved) -> Result<(), InstallerError> {
+        GoUninstaller
+            .uninstall(item)
+            .map_err(InstallerError::CommandFailed)
+    }
+
     /// Installs a Go tool using the `go install` command with comprehensive error handling.
     ///
     /// This function provides a robust installer for Go tools that mirrors the quality and
@@ -172,7 +187,7 @@ impl Installer for GoInstaller {
         ))
     }
 
-    /// # `get_latest_version`
+    /// # `resolve_latest_version`
     ///
     /// Fetches the latest version of a Go module.
     ///
@@ -186,7 +201,7 @@ impl Installer for GoInstaller {
     /// A `Result` which is:
     /// - `Ok(String)`: A string containing the latest version of the module.
     /// - `Err(InstallerError)`: An `InstallerError` if it fails to get the latest version.
-    fn get_latest_version(&self, tool_entry: &ToolEntry) -> Result<String, InstallerError> {
+    fn resolve_latest_version(&self, tool_entry: &ToolEntry) -> Result<String, InstallerError> {
         log_debug!(
             "[SDB::Tools::GoInstaller] Getting latest version for: {}",
             tool_entry.name.bold()
@@ -350,8 +365,10 @@ fn prepare_go_install_command(tool_entry: &ToolEntry, installation_source: &str)
     let mut command_args = Vec::new();
     command_args.push("install".to_string());
 
-    // Construct package path with version if specified
-    let package_path = if let Some(version) = &tool_entry.version {
+    // Construct package path with version (or pinned commit) if specified
+    let package_path = if let Some(commit) = tool_entry.pinned_commit() {
+        format!("{installation_source}@{commit}")
+    } else if let Some(version) = &tool_entry.version {
         format!("{installation_source}@{version}")
     } else {
         installation_source.to_string()
@@ -790,3 +807,18 @@ fn is_executable(path: &PathBuf) -> bool {
         path.extension().map_or(false, |ext| ext == "exe") || path.exists()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_pin_is_installed_as_module_version() {
+        let tool: ToolEntry =
+            serde_yaml::from_str("name: gopls\nsource: go\nversion: commit:4f3e2a1").unwrap();
+        assert_eq!(
+            prepare_go_install_command(&tool, "golang.org/x/tools/gopls"),
+            ["install", "golang.org/x/tools/gopls@4f3e2a1"]
+        );
+    }
+}
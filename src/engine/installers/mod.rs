@@ -45,10 +45,19 @@ pub(crate) mod shell_run_commands;
 /// This is distinct from `cargo` which installs Rust *applications*.
 pub(crate) mod rustup;
 
+/// Declares the `oci` module, which pulls tools published as OCI artifacts
+/// (e.g. with ORAS) from container registries and verifies their digests.
+pub(crate) mod oci;
+/// Declares the `path_entry` module, for directories put on PATH whose tools are
+/// installed by something else (e.g. an MDM).
+pub(crate) mod path_entry;
 /// Declares the `pip` module, for installing Python packages (tools)
 /// using the `pip` package installer. It would manage Python dependencies
 /// and script installations.
 pub(crate) mod pip;
+/// Declares the `simulated` module, the deterministic fake that stands in for every
+/// installer with `--simulate`.
+pub(crate) mod simulated;
 /// Declares the `url` module, which handles the installation of tools
 /// by directly downloading files from a specified URL. This is used for
 /// binaries or installers not managed by other package managers or GitHub releases.
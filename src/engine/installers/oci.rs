@@ -0,0 +1,613 @@
+//! # OCI Installer Module
+//!
+//! This module installs tools published as OCI artifacts, e.g. pushed with
+//! `oras push ghcr.io/org/tool:1.2.3 tool-linux-amd64.tar.gz`. The artifact is pulled over
+//! the OCI distribution API, without `oras` or a container runtime being installed.
+//!
+//! ## Installation Workflow
+//!
+//! 1. **Reference** - `repo` names the artifact (`ghcr.io/org/tool`), `tag` or `version`
+//!    the tag. `latest` resolves to the highest semantic version in the tag list.
+//! 2. **Manifest** - The manifest is fetched, following an image index to the manifest of
+//!    the current platform. Manifests fetched by digest are checked against it.
+//! 3. **Layer** - The layer whose `org.opencontainers.image.title` (the file name ORAS
+//!    records) names the tool or matches the platform is selected.
+//! 4. **Blob** - The layer is downloaded and its size and digest are verified against the
+//!    manifest before anything is installed.
+//! 5. **Processing** - The file is installed like a URL download: binaries are moved to the
+//!    bin directory, archives are extracted, macOS packages are installed.
+//!
+//! ## Authentication
+//!
+//! Registries answer anonymous pulls with a `WWW-Authenticate: Bearer` challenge; the token
+//! is requested from the advertised realm. Private artifacts authenticate the token request
+//! with the tool's `basic_auth` (e.g. a GitHub username and `${GHCR_TOKEN}`), and `headers`
+//! are sent with every registry request.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+use colored::Colorize;
+use regex::Regex;
+use serde::Deserialize;
+use sha2::{Digest, Sha256, Sha512};
+use tempfile::Builder as TempFileBuilder;
+
+use crate::core::assets::{self, detect_file_type};
+use crate::core::platform::{apply_arch_fallback, detect_architecture, detect_os, match_asset};
+use crate::engine::execute_post_installation_hooks;
+use crate::engine::installers::errors::InstallerError;
+use crate::engine::installers::traits::Installer;
+use crate::engine::uninstaller::executors::{BinaryUninstaller, ItemToBeRemoved, ToolUninstaller};
+use crate::schemas::path_resolver::PathResolver;
+use crate::schemas::state_file::ToolState;
+use crate::schemas::tools_types::ToolEntry;
+use crate::{log_debug, log_info, log_warn};
+
+/// Manifest media types accepted from the registry.
+const MANIFEST_MEDIA_TYPES: &str = "application/vnd.oci.image.manifest.v1+json, \
+     application/vnd.oci.image.index.v1+json, \
+     application/vnd.docker.distribution.manifest.v2+json, \
+     application/vnd.docker.distribution.manifest.list.v2+json";
+
+/// Annotation ORAS stores the pushed file name in.
+const TITLE_ANNOTATION: &str = "org.opencontainers.image.title";
+
+/// Struct representing the OCI installer.
+pub struct OciInstaller;
+
+impl Installer for OciInstaller {
+    fn name(&self) -> &'static str {
+        "oci"
+    }
+
+    fn uninstall(&self, item: &ItemToBeRemoved) -> Result<(), InstallerError> {
+        BinaryUninstaller
+            .uninstall(item)
+            .map_err(InstallerError::CommandFailed)
+    }
+
+    /// Pulls the tool's artifact from its registry and installs the matching layer.
+    ///
+    /// # Examples - YAML Configuration
+    ///
+    /// ```yaml
+    /// - name: tool
+    ///   source: oci
+    ///   repo: ghcr.io/org/tool
+    ///   version: 1.2.3
+    /// ```
+    fn install(&self, tool_entry: &ToolEntry) -> Result<ToolState, InstallerError> {
+        log_info!(
+            "[SDB::Tools::OciInstaller] Attempting to install tool from OCI registry: {}",
+            tool_entry.name.bold()
+        );
+        log_debug!(
+            "[SDB::Tools::OciInstaller] ToolEntry details: {:#?}",
+            tool_entry
+        );
+
+        let mut registry = Registry::new(tool_entry)?;
+        let tag = match configured_tag(tool_entry) {
+            Some(tag) => tag.to_string(),
+            None => registry.latest_tag()?,
+        };
+
+        // Step 1: Resolve the manifest of this platform
+        let (manifest, manifest_digest) = registry.platform_manifest(&tag)?;
+
+        // Step 2: Select the layer holding the tool
+        let layer = select_layer(tool_entry, &manifest.layers).ok_or_else(|| {
+            let titles: Vec<&str> = manifest.layers.iter().filter_map(|l| l.title()).collect();
+            InstallerError::ConfigurationError(format!(
+                "No layer of {}:{} matches {} or this platform (layers: {})",
+                registry.reference,
+                tag,
+                tool_entry.name,
+                titles.join(", ")
+            ))
+        })?;
+
+        // Step 3: Download the blob and verify it against the manifest
+        let temp_dir = TempFileBuilder::new()
+            .prefix(&format!("setup-devbox-install-{}-", tool_entry.name))
+            .tempdir_in(PathResolver::temp_root())
+            .map_err(|e| {
+                InstallerError::InstallationFailed(format!(
+                    "Failed to create temporary directory: {e}"
+                ))
+            })?;
+        let file_name = layer
+            .title()
+            .and_then(|title| Path::new(title).file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| tool_entry.name.clone());
+        let downloaded_path = temp_dir.path().join(&file_name);
+        registry.download_blob(layer, &downloaded_path)?;
+
+        // Step 4: Install it like a downloaded asset
+        let file_type = detect_file_type(&downloaded_path);
+        log_debug!(
+            "[SDB::Tools::OciInstaller] Detected file type: {}",
+            file_type.magenta()
+        );
+        let (package_type, final_install_path, working_dir) =
+            assets::process_asset_by_type(tool_entry, &downloaded_path, &file_type, &temp_dir)
+                .ok_or_else(|| {
+                    InstallerError::InstallationFailed("Failed to process asset".into())
+                })?;
+        if !final_install_path.exists() && package_type == "binary" {
+            return Err(InstallerError::InstallationFailed(format!(
+                "Installed binary does not exist at {}",
+                final_install_path.display()
+            )));
+        }
+
+        // Step 5: Post-installation hooks
+        let executed_post_installation_hooks =
+            execute_post_installation_hooks("[OCI Installer]", tool_entry, &working_dir);
+
+        log_info!(
+            "[SDB::Tools::OciInstaller] Successfully installed tool: {}",
+            tool_entry.name.bold().green()
+        );
+
+        // The digest pins exactly what was installed, even if the tag moves later
+        Ok(ToolState::new(
+            tool_entry,
+            &final_install_path,
+            "oci".to_string(),
+            package_type,
+            tag,
+            Some(format!("{}@{}", registry.reference, manifest_digest)),
+            None,
+            executed_post_installation_hooks,
+        ))
+    }
+
+    /// Returns the highest semantic version among the artifact's tags.
+    fn resolve_latest_version(&self, tool_entry: &ToolEntry) -> Result<String, InstallerError> {
+        Registry::new(tool_entry)?.latest_tag()
+    }
+}
+
+/// The tag configured for a tool, unless it follows `latest`.
+fn configured_tag(tool_entry: &ToolEntry) -> Option<&str> {
+    tool_entry
+        .tag
+        .as_deref()
+        .or(tool_entry.version.as_deref())
+        .filter(|tag| !tag.is_empty() && *tag != "latest")
+}
+
+/// An image manifest or index, as far as installing needs it.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Manifest {
+    #[serde(default)]
+    media_type: Option<String>,
+    /// Platform manifests of an image index.
+    #[serde(default)]
+    manifests: Vec<Descriptor>,
+    #[serde(default)]
+    layers: Vec<Descriptor>,
+}
+
+/// A content descriptor: a manifest or layer, addressed by digest.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Descriptor {
+    digest: String,
+    size: u64,
+    #[serde(default)]
+    annotations: BTreeMap<String, String>,
+    #[serde(default)]
+    platform: Option<Platform>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Platform {
+    os: String,
+    architecture: String,
+}
+
+impl Descriptor {
+    fn title(&self) -> Option<&str> {
+        self.annotations.get(TITLE_ANNOTATION).map(String::as_str)
+    }
+}
+
+#[derive(Deserialize)]
+struct TagList {
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    token: Option<String>,
+    access_token: Option<String>,
+}
+
+/// A registry session for one artifact, holding the bearer token once one was issued.
+struct Registry<'a> {
+    tool_entry: &'a ToolEntry,
+    /// `registry/repository`, e.g. `ghcr.io/org/tool`.
+    reference: String,
+    registry: String,
+    repository: String,
+    token: Option<String>,
+}
+
+impl<'a> Registry<'a> {
+    fn new(tool_entry: &'a ToolEntry) -> Result<Self, InstallerError> {
+        let repo = tool_entry.repo.as_deref().ok_or_else(|| {
+            InstallerError::ConfigurationError(
+                "Source is 'oci', but 'repo' (e.g. ghcr.io/org/tool) is missing".to_string(),
+            )
+        })?;
+        let (registry, repository) = parse_reference(repo)?;
+        Ok(Self {
+            tool_entry,
+            reference: format!("{registry}/{repository}"),
+            registry,
+            repository,
+            token: None,
+        })
+    }
+
+    fn url(&self, endpoint: &str) -> String {
+        format!(
+            "https://{}/v2/{}/{}",
+            self.registry, self.repository, endpoint
+        )
+    }
+
+    fn request(&self, url: &str, accept: &str) -> Result<ureq::Request, InstallerError> {
+        let request = assets::build_tool_request(self.tool_entry, "GET", url)
+            .map_err(InstallerError::ConfigurationError)?
+            .set("Accept", accept);
+        Ok(match &self.token {
+            Some(token) => request.set("Authorization", &format!("Bearer {token}")),
+            None => request,
+        })
+    }
+
+    /// Sends a GET request, answering the registry's authentication challenge once.
+    fn get(&mut self, url: &str, accept: &str) -> Result<ureq::Response, InstallerError> {
+        match self.request(url, accept)?.call() {
+            Ok(response) => Ok(response),
+            Err(ureq::Error::Status(401, response)) if self.token.is_none() => {
+                let challenge = response.header("WWW-Authenticate").unwrap_or_default();
+                self.token = Some(self.fetch_token(challenge)?);
+                self.request(url, accept)?
+                    .call()
+                    .map_err(|e| registry_error(url, e))
+            }
+            Err(e) => Err(registry_error(url, e)),
+        }
+    }
+
+    /// Requests a bearer token from the realm advertised in a `WWW-Authenticate` challenge.
+    fn fetch_token(&self, challenge: &str) -> Result<String, InstallerError> {
+        let params = parse_bearer_challenge(challenge).ok_or_else(|| {
+            InstallerError::NetworkError(format!(
+                "{} requires authentication it does not describe ('{}')",
+                self.registry, challenge
+            ))
+        })?;
+        let realm = params.get("realm").ok_or_else(|| {
+            InstallerError::NetworkError(format!("{} sent no token realm", self.registry))
+        })?;
+        let query = params
+            .iter()
+            .filter(|(key, _)| matches!(key.as_str(), "service" | "scope"));
+        let token_url = url::Url::parse_with_params(realm, query)
+            .map_err(|e| InstallerError::NetworkError(format!("invalid realm '{realm}': {e}")))?;
+        log_debug!(
+            "[SDB::Tools::OciInstaller] Requesting registry token from {}",
+            token_url.as_str().blue()
+        );
+
+        let response: TokenResponse =
+            assets::build_tool_request(self.tool_entry, "GET", token_url.as_str())
+                .map_err(InstallerError::ConfigurationError)?
+                .call()
+                .map_err(|e| registry_error(token_url.as_str(), e))?
+                .into_json()
+                .map_err(|e| {
+                    InstallerError::NetworkError(format!("invalid token response: {e}"))
+                })?;
+        response
+            .token
+            .or(response.access_token)
+            .ok_or_else(|| InstallerError::NetworkError("the token response is empty".into()))
+    }
+
+    /// Fetches a manifest by tag or digest, returning it with its digest. Manifests fetched
+    /// by digest, and those the registry reports a digest for, are verified.
+    fn manifest(&mut self, reference: &str) -> Result<(Manifest, String), InstallerError> {
+        let url = self.url(&format!("manifests/{reference}"));
+        let response = self.get(&url, MANIFEST_MEDIA_TYPES)?;
+        let reported = response.header("Docker-Content-Digest").map(str::to_string);
+        let mut body = Vec::new();
+        response
+            .into_reader()
+            .read_to_end(&mut body)
+            .map_err(|e| InstallerError::NetworkError(format!("{url}: {e}")))?;
+
+        let expected = if reference.contains(':') {
+            Some(reference.to_string())
+        } else {
+            reported
+        };
+        let digest = match expected {
+            Some(expected) => {
+                verify_digest(body.as_slice(), &expected).map_err(|e| {
+                    InstallerError::ValidationFailed(format!("manifest {reference}: {e}"))
+                })?;
+                expected
+            }
+            None => format!("sha256:{:x}", Sha256::digest(&body)),
+        };
+
+        let manifest = serde_json::from_slice(&body).map_err(|e| {
+            InstallerError::NetworkError(format!("invalid manifest for {reference}: {e}"))
+        })?;
+        Ok((manifest, digest))
+    }
+
+    /// Fetches the manifest for `tag`, resolving an image index to this platform's entry.
+    fn platform_manifest(&mut self, tag: &str) -> Result<(Manifest, String), InstallerError> {
+        let (manifest, digest) = self.manifest(tag)?;
+        if manifest.manifests.is_empty() {
+            return Ok((manifest, digest));
+        }
+
+        let (os, arch) = oci_platform();
+        let entry = manifest
+            .manifests
+            .iter()
+            .find(|entry| {
+                entry
+                    .platform
+                    .as_ref()
+                    .is_some_and(|p| p.os == os && p.architecture == arch)
+            })
+            .ok_or_else(|| {
+                InstallerError::PlatformDetectionFailed(format!(
+                    "{}:{} ({}) has no manifest for {os}/{arch}",
+                    self.reference,
+                    tag,
+                    manifest.media_type.as_deref().unwrap_or("index")
+                ))
+            })?;
+        log_debug!(
+            "[SDB::Tools::OciInstaller] Using the {}/{} manifest {}",
+            os,
+            arch,
+            entry.digest
+        );
+        self.manifest(&entry.digest.clone())
+    }
+
+    /// Highest semantic version among the artifact's tags, or `latest` if none is one.
+    fn latest_tag(&mut self) -> Result<String, InstallerError> {
+        let url = self.url("tags/list");
+        let tags: TagList = self
+            .get(&url, "application/json")?
+            .into_json()
+            .map_err(|e| InstallerError::VersionDetectionFailed(format!("{url}: {e}")))?;
+        let latest = highest_version(&tags.tags).unwrap_or_else(|| {
+            log_warn!(
+                "[SDB::Tools::OciInstaller] No tag of {} is a semantic version, using 'latest'",
+                self.reference.yellow()
+            );
+            "latest".to_string()
+        });
+        log_debug!(
+            "[SDB::Tools::OciInstaller] Latest tag of {}: {}",
+            self.reference,
+            latest.cyan()
+        );
+        Ok(latest)
+    }
+
+    /// Downloads a layer to `dest` and checks its size and digest.
+    fn download_blob(&self, layer: &Descriptor, dest: &Path) -> Result<(), InstallerError> {
+        let url = self.url(&format!("blobs/{}", layer.digest));
+        log_info!(
+            "[SDB::Tools::Oci::Downloader] Downloading: '{}' from '{}'",
+            self.tool_entry.name.green(),
+            self.reference.cyan()
+        );
+        let request = self.request(&url, "*/*")?;
+        assets::download_request(request, &url, dest)
+            .map_err(|e| InstallerError::DownloadFailed(e.to_string()))?;
+
+        let size = fs::metadata(dest).map(|m| m.len()).unwrap_or_default();
+        if size != layer.size {
+            return Err(InstallerError::ValidationFailed(format!(
+                "{} is {} bytes, the manifest says {}",
+                layer.digest, size, layer.size
+            )));
+        }
+        let file = fs::File::open(dest)
+            .map_err(|e| InstallerError::DownloadFailed(format!("{}: {e}", dest.display())))?;
+        verify_digest(file, &layer.digest).map_err(InstallerError::ValidationFailed)?;
+        log_debug!(
+            "[SDB::Tools::OciInstaller] Verified {} ({} bytes)",
+            layer.digest.green(),
+            size
+        );
+        Ok(())
+    }
+}
+
+fn registry_error(url: &str, error: ureq::Error) -> InstallerError {
+    match error {
+        ureq::Error::Status(404, _) => {
+            InstallerError::DownloadFailed(format!("{url} was not found; check repo and tag"))
+        }
+        ureq::Error::Status(status @ (401 | 403), _) => InstallerError::DownloadFailed(format!(
+            "{url} answered HTTP {status}; private artifacts need 'basic_auth'"
+        )),
+        e => InstallerError::NetworkError(format!("{url}: {e}")),
+    }
+}
+
+/// Splits `ghcr.io/org/tool` (optionally prefixed with `oci://`) into registry and repository.
+fn parse_reference(repo: &str) -> Result<(String, String), InstallerError> {
+    let repo = repo.trim().trim_start_matches("oci://");
+    let invalid = |reason: &str| {
+        InstallerError::ConfigurationError(format!(
+            "'{repo}' is not a valid OCI reference: {reason}"
+        ))
+    };
+    let (registry, repository) = repo
+        .split_once('/')
+        .ok_or_else(|| invalid("expected <registry>/<repository>, e.g. ghcr.io/org/tool"))?;
+    if !(registry.contains('.') || registry.contains(':') || registry == "localhost") {
+        return Err(invalid("the first component must be a registry host"));
+    }
+    if repository.contains('@')
+        || repository
+            .rsplit('/')
+            .next()
+            .is_some_and(|n| n.contains(':'))
+    {
+        return Err(invalid(
+            "put the tag in 'version' (or 'tag'), not in 'repo'",
+        ));
+    }
+    Ok((registry.to_lowercase(), repository.to_string()))
+}
+
+/// Parameters of a `WWW-Authenticate: Bearer realm="...",service="...",scope="..."` header.
+fn parse_bearer_challenge(challenge: &str) -> Option<BTreeMap<String, String>> {
+    let params = challenge.trim().strip_prefix("Bearer ")?;
+    let re = Regex::new(r#"(\w+)="([^"]*)""#).expect("valid regex");
+    Some(
+        re.captures_iter(params)
+            .map(|captures| (captures[1].to_string(), captures[2].to_string()))
+            .collect(),
+    )
+}
+
+/// Checks `content` against an `<algorithm>:<hex>` digest.
+fn verify_digest(mut content: impl Read, digest: &str) -> Result<(), String> {
+    let (algorithm, expected) = digest
+        .split_once(':')
+        .ok_or_else(|| format!("malformed digest '{digest}'"))?;
+    let actual = match algorithm {
+        "sha256" => {
+            let mut hasher = Sha256::new();
+            io::copy(&mut content, &mut hasher).map_err(|e| e.to_string())?;
+            format!("{:x}", hasher.finalize())
+        }
+        "sha512" => {
+            let mut hasher = Sha512::new();
+            io::copy(&mut content, &mut hasher).map_err(|e| e.to_string())?;
+            format!("{:x}", hasher.finalize())
+        }
+        other => return Err(format!("unsupported digest algorithm '{other}'")),
+    };
+    if actual.eq_ignore_ascii_case(expected) {
+        Ok(())
+    } else {
+        Err(format!(
+            "digest mismatch: expected {digest}, got {algorithm}:{actual}"
+        ))
+    }
+}
+
+/// OS and architecture as OCI platforms name them (`darwin`, `amd64`).
+fn oci_platform() -> (String, String) {
+    let os = match detect_os().as_str() {
+        "macos" => "darwin".to_string(),
+        other => other.to_string(),
+    };
+    let arch = match detect_architecture().as_str() {
+        "x86_64" => "amd64".to_string(),
+        "aarch64" => "arm64".to_string(),
+        other => other.to_string(),
+    };
+    (os, arch)
+}
+
+/// Picks the layer to install: the only one, the one titled like the tool, or the first
+/// whose title matches this platform (preferring native builds as `arch_fallback` says).
+fn select_layer<'m>(tool_entry: &ToolEntry, layers: &'m [Descriptor]) -> Option<&'m Descriptor> {
+    if let [layer] = layers {
+        return Some(layer);
+    }
+    let name = tool_entry.rename_to.as_deref().unwrap_or(&tool_entry.name);
+    let (os, arch) = (detect_os(), detect_architecture());
+    layers
+        .iter()
+        .find(|layer| layer.title() == Some(name))
+        .or_else(|| {
+            let candidates = layers
+                .iter()
+                .filter_map(|layer| Some(layer).zip(match_asset(layer.title()?, &os, &arch)))
+                .collect();
+            apply_arch_fallback(candidates)
+                .first()
+                .map(|(layer, _)| *layer)
+        })
+}
+
+/// The highest stable semantic version in `tags` (a leading `v` is allowed).
+fn highest_version(tags: &[String]) -> Option<String> {
+    tags.iter()
+        .filter_map(|tag| {
+            let version = semver::Version::parse(tag.strip_prefix('v').unwrap_or(tag)).ok()?;
+            version.pre.is_empty().then_some((version, tag))
+        })
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, tag)| tag.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reference() {
+        assert_eq!(
+            parse_reference("oci://ghcr.io/org/tool").unwrap(),
+            ("ghcr.io".to_string(), "org/tool".to_string())
+        );
+        assert!(parse_reference("org/tool").is_err());
+        assert!(parse_reference("ghcr.io/org/tool:1.2.3").is_err());
+        assert!(parse_reference("localhost:5000/tool").is_ok());
+    }
+
+    #[test]
+    fn test_parse_bearer_challenge() {
+        let params = parse_bearer_challenge(
+            r#"Bearer realm="https://ghcr.io/token",service="ghcr.io",scope="repository:org/tool:pull""#,
+        )
+        .unwrap();
+        assert_eq!(params["realm"], "https://ghcr.io/token");
+        assert_eq!(params["scope"], "repository:org/tool:pull");
+        assert!(parse_bearer_challenge("Basic realm=\"registry\"").is_none());
+    }
+
+    #[test]
+    fn test_verify_digest() {
+        let digest = "sha256:2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        assert!(verify_digest("hello".as_bytes(), digest).is_ok());
+        assert!(verify_digest("hello!".as_bytes(), digest).is_err());
+        assert!(verify_digest("hello".as_bytes(), "md5:abc").is_err());
+    }
+
+    #[test]
+    fn test_highest_version() {
+        let tags = ["1.2.0", "v1.10.0", "latest", "2.0.0-rc.1"].map(String::from);
+        assert_eq!(highest_version(&tags), Some("v1.10.0".to_string()));
+        assert_eq!(highest_version(&["latest".to_string()]), None);
+    }
+}
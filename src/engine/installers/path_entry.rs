@@ -0,0 +1,162 @@
+//! # PATH Entry Installer Module
+//!
+//! `source: path-entry` declares a directory whose tools are installed by something else,
+//! e.g. an employer's MDM, so setup-devbox neither installs, updates nor removes them. Its
+//! only effects are:
+//!
+//! - The directory is added to `PATH` through the `Paths` section of `shellrc.yaml`
+//! - The directory, and the executable named by `binary` if given, must exist; otherwise
+//!   the tool fails like any other installation
+//! - The entry is recorded in the state file (as not installed by setup-devbox), so it is
+//!   checked again whenever its directory or executable goes missing
+//!
+//! ```yaml
+//! - name: company-tools
+//!   source: path-entry
+//!   path: /opt/company/bin
+//!   binary: company-vpn
+//! ```
+
+use std::path::PathBuf;
+
+use colored::Colorize;
+
+use crate::core::platform::is_executable_file;
+use crate::engine::installers::errors::InstallerError;
+use crate::engine::installers::traits::Installer;
+use crate::engine::uninstaller::executors::ItemToBeRemoved;
+use crate::schemas::path_resolver::PathResolver;
+use crate::schemas::state_file::ToolState;
+use crate::schemas::tools_types::ToolEntry;
+use crate::{log_debug, log_info};
+
+/// Version recorded for path entries that don't declare one.
+const EXTERNAL_VERSION: &str = "external";
+
+/// Struct representing the PATH entry installer.
+pub struct PathEntryInstaller;
+
+impl Installer for PathEntryInstaller {
+    fn name(&self) -> &'static str {
+        "path-entry"
+    }
+
+    /// Checks that the directory (and the expected executable) exists and records the entry.
+    fn install(&self, tool_entry: &ToolEntry) -> Result<ToolState, InstallerError> {
+        let directory = entry_directory(tool_entry)?;
+        if !directory.is_dir() {
+            return Err(InstallerError::ValidationFailed(format!(
+                "PATH entry directory {} does not exist; it is expected to be installed by something else",
+                directory.display()
+            )));
+        }
+
+        let install_path = match &tool_entry.binary {
+            Some(binary) => {
+                let executable = directory.join(binary);
+                if !is_executable_file(&executable) {
+                    return Err(InstallerError::ValidationFailed(format!(
+                        "expected executable {} was not found",
+                        executable.display()
+                    )));
+                }
+                executable
+            }
+            None => directory.clone(),
+        };
+        log_info!(
+            "[SDB::Tools::PathEntry] {} is on PATH via {}",
+            tool_entry.name.bold(),
+            directory.display().to_string().cyan()
+        );
+
+        let mut tool_state = ToolState::new(
+            tool_entry,
+            &install_path,
+            "path-entry".to_string(),
+            "path".to_string(),
+            tool_entry
+                .version
+                .clone()
+                .unwrap_or_else(|| EXTERNAL_VERSION.to_string()),
+            None,
+            None,
+            None,
+        );
+        // The files belong to whatever installed them
+        tool_state.installed_by_devbox = false;
+        Ok(tool_state)
+    }
+
+    /// Forgets the entry without touching the directory.
+    fn uninstall(&self, item: &ItemToBeRemoved) -> Result<(), InstallerError> {
+        log_debug!(
+            "[SDB::Tools::PathEntry] Leaving the files of {} in place",
+            item.item_name
+        );
+        Ok(())
+    }
+
+    fn resolve_latest_version(&self, tool_entry: &ToolEntry) -> Result<String, InstallerError> {
+        Ok(tool_entry
+            .version
+            .clone()
+            .unwrap_or_else(|| EXTERNAL_VERSION.to_string()))
+    }
+}
+
+/// The expanded `path` of the entry.
+fn entry_directory(tool_entry: &ToolEntry) -> Result<PathBuf, InstallerError> {
+    let path = tool_entry.path.as_deref().ok_or_else(|| {
+        InstallerError::ConfigurationError("'path' is required for path-entry tools".to_string())
+    })?;
+    PathResolver::expand_path(path).map_err(|e| {
+        InstallerError::ConfigurationError(format!("cannot expand path '{path}': {e}"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn entry(directory: &Path, binary: Option<&str>) -> ToolEntry {
+        ToolEntry {
+            path: Some(directory.display().to_string()),
+            binary: binary.map(str::to_string),
+            ..serde_yaml::from_str("name: company-tools\nsource: path-entry").unwrap()
+        }
+    }
+
+    #[test]
+    fn test_existing_directory_is_recorded_as_external() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool_state = PathEntryInstaller
+            .install(&entry(dir.path(), None))
+            .unwrap();
+        assert_eq!(tool_state.install_path, dir.path().display().to_string());
+        assert_eq!(tool_state.version, EXTERNAL_VERSION);
+        assert!(!tool_state.installed_by_devbox);
+
+        let missing = PathEntryInstaller.install(&entry(&dir.path().join("missing"), None));
+        assert!(matches!(missing, Err(InstallerError::ValidationFailed(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_expected_executable_must_exist() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let result = PathEntryInstaller.install(&entry(dir.path(), Some("company-vpn")));
+        assert!(matches!(result, Err(InstallerError::ValidationFailed(_))));
+
+        let executable = dir.path().join("company-vpn");
+        std::fs::write(&executable, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&executable, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let tool_state = PathEntryInstaller
+            .install(&entry(dir.path(), Some("company-vpn")))
+            .unwrap();
+        assert_eq!(tool_state.install_path, executable.display().to_string());
+    }
+}
@@ -18,6 +18,7 @@ use colored::Colorize;
 // These macros (`log_debug`, `log_error`, `log_info`, `log_warn`) provide
 // a standardized way to output messages to the console with different severity levels,
 // making it easier to track the application's flow and diagnose issues.
+use crate::core::inventory::{self, Lookup, PackageManager};
 use crate::{log_debug, log_error, log_info, log_warn};
 // For executing external commands and capturing their output.
 // `std::process::Command` is used to run commands/hooks.
@@ -25,6 +26,7 @@ use crate::{log_debug, log_error, log_info, log_warn};
 use crate::engine::execute_post_installation_hooks;
 use crate::engine::installers::errors::InstallerError;
 use crate::engine::installers::traits::Installer;
+use crate::engine::uninstaller::executors::{ItemToBeRemoved, PipUninstaller, ToolUninstaller};
 // `ToolEntry`: Represents a single tool's configuration from `tools.yaml`.
 // `ToolState`: Represents the actual state of an installed tool for persistence in `state.json`.
 use crate::schemas::state_file::ToolState;
@@ -69,6 +71,20 @@ impl PipVariant {
 pub struct PipInstaller;
 
 impl Installer for PipInstaller {
+    fn name(&self) -> &'static str {
+        "pip"
+    }
+
+    fn required_command(&self) -> Option<&'static str> {
+        Some("pip3") // pip3 is preferred over a bare `pip`
+    }
+
+    fn uninstall(&self, item: &ItemToBeRemoved) -> Result<(), InstallerError> {
+        PipUninstaller
+            .uninstall(item)
+            .map_err(InstallerError::CommandFailed)
+    }
+
     /// Installs a Python package using pip with comprehensive error handling.
     ///
     /// This function provides a robust installer for Python packages that mirrors the quality and
@@ -215,7 +231,7 @@ impl Installer for PipInstaller {
         ))
     }
 
-    /// # `get_latest_version`
+    /// # `resolve_latest_version`
     ///
     /// Fetches the latest version of a package from the Python Package Index (PyPI).
     ///
@@ -230,7 +246,7 @@ impl Installer for PipInstaller {
     /// - `Ok(String)`: A string containing the latest version number.
     /// - `Err(InstallerError)`: An `InstallerError` if it fails to get the latest version,
     ///   or if no pip executable is found.
-    fn get_latest_version(&self, tool_entry: &ToolEntry) -> Result<String, InstallerError> {
+    fn resolve_latest_version(&self, tool_entry: &ToolEntry) -> Result<String, InstallerError> {
         log_debug!(
             "[SDB::Tools::PipInstaller] Getting latest version for: {}",
             tool_entry.name.bold()
@@ -415,8 +431,21 @@ fn detect_installation_mode(tool_entry: &ToolEntry) -> bool {
         .unwrap_or(false)
 }
 
-/// Checks if a package is already installed to avoid unnecessary reinstallation.
+/// Checks if a package is already installed to avoid unnecessary reinstallation. The run's
+/// package inventory answers first; pip is only asked when it has no snapshot of pip.
 fn check_package_already_installed(package_name: &str, pip_variant: &PipVariant) -> bool {
+    match inventory::lookup(PackageManager::Pip, package_name) {
+        Lookup::Installed(version) => {
+            log_debug!(
+                "[SDB::Tools::PipInstaller] Package '{}' is already installed (version {})",
+                package_name,
+                version
+            );
+            return true;
+        }
+        Lookup::Missing => return false,
+        Lookup::Unknown => {}
+    }
     let (command, args) = build_pip_show_command(pip_variant, package_name);
 
     match Command::new(command).args(&args).output() {
@@ -676,6 +705,23 @@ fn build_pip_list_command(pip_variant: &PipVariant) -> (String, Vec<String>) {
     (command, args)
 }
 
+/// Command listing the installed packages as JSON with the pip that installs packages,
+/// used by the package inventory.
+pub(crate) fn list_json_command() -> Option<(String, Vec<String>)> {
+    let pip_variant = detect_pip_variant()?;
+    let mut args: Vec<String> = pip_variant
+        .module_args()
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    args.extend([
+        "list".to_string(),
+        "--format".to_string(),
+        "json".to_string(),
+    ]);
+    Some((pip_variant.command().to_string(), args))
+}
+
 /// Builds the command and arguments for pip show.
 fn build_pip_show_command(pip_variant: &PipVariant, package_name: &str) -> (String, Vec<String>) {
     let command = pip_variant.command().to_string();
@@ -60,6 +60,7 @@ use crate::{log_debug, log_error, log_info, log_warn};
 use crate::engine::execute_post_installation_hooks;
 use crate::engine::installers::errors::InstallerError;
 use crate::engine::installers::traits::Installer;
+use crate::engine::uninstaller::executors::{ItemToBeRemoved, RustupUninstaller, ToolUninstaller};
 // `ToolEntry`: Represents a single tool's configuration from `tools.yaml`.
 // `ToolState`: Represents the actual state of an installed tool for persistence in `state.json`.
 use crate::schemas::state_file::ToolState;
@@ -69,6 +70,20 @@ use crate::schemas::tools_types::ToolEntry;
 pub struct RustupInstaller;
 
 impl Installer for RustupInstaller {
+    fn name(&self) -> &'static str {
+        "rustup"
+    }
+
+    fn required_command(&self) -> Option<&'static str> {
+        Some("rustup")
+    }
+
+    fn uninstall(&self, item: &ItemToBeRemoved) -> Result<(), InstallerError> {
+        RustupUninstaller
+            .uninstall(item)
+            .map_err(InstallerError::CommandFailed)
+    }
+
     /// Installs a Rust toolchain and optionally its components using `rustup`.
     ///
     /// This function acts as the installer module for `rustup`-managed Rust environments with
@@ -257,7 +272,7 @@ impl Installer for RustupInstaller {
         ))
     }
 
-    /// # `get_latest_version`
+    /// # `resolve_latest_version`
     ///
     /// Fetches the latest available stable toolchain version from `rustup`.
     ///
@@ -272,7 +287,7 @@ impl Installer for RustupInstaller {
     /// - `Ok(String)`: A string containing the latest stable toolchain version. If the tool
     ///   is not "rust" or "rustup", it returns the currently configured version.
     /// - `Err(InstallerError)`: An `InstallerError` if it fails to get the latest version.
-    fn get_latest_version(&self, tool_entry: &ToolEntry) -> Result<String, InstallerError> {
+    fn resolve_latest_version(&self, tool_entry: &ToolEntry) -> Result<String, InstallerError> {
         log_debug!(
             "[SDB::Tools::RustUpInstaller] Getting latest version for: {}",
             tool_entry.name.bold()
@@ -1,10 +1,13 @@
 use crate::core::manage_rc_files::{
-    get_rc_file, read_rc_file, remove_rc_file, source_rc_file, write_rc_file,
+    get_profile_file, get_rc_file, read_rc_file, remove_rc_file, source_rc_file, write_rc_file,
 };
 use crate::core::platform::is_env_var_set;
 use crate::schemas::shell_configuration::{
     AliasEntry, ConfigSection, RunCommandEntry, ShellConfig,
 };
+use crate::shell::behavior::behavior_commands;
+use crate::shell::render::render_command;
+use crate::shell::startup::{layout_warnings, split_for_login_profile};
 use crate::shell::{
     ensure_sections_exist, insert_into_section, is_command_update, log_section_stats,
     normalize_command, parse_existing_sections, section_header_name,
@@ -12,57 +15,210 @@ use crate::shell::{
 use crate::{log_debug, log_error, log_info, log_warn};
 use colored::Colorize;
 use std::collections::{HashMap, HashSet};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-/// Applies shell configurations (run commands and aliases) to the user's shell RC file.
+/// Applies shell configurations (run commands and aliases) to the user's shell RC files.
 /// This function serves as the main entry point for configuring shell environments.
 ///
 /// # Arguments
-/// * `shell_cfg` - A `ShellConfig` struct containing shell type, run commands, and aliases
+/// * `shell_cfg` - A `ShellConfig` struct containing shell types, run commands, and aliases
 ///
 /// # Behavior
-/// - Determines the appropriate RC file path based on shell type
-/// - Processes run commands and aliases
-/// - Sources the updated RC file to apply changes immediately
+/// - Configures every shell listed in `shells` (or the single `shell`)
+/// - Renders the run commands in each shell's syntax
+/// - Sources each updated RC file to apply changes immediately
 /// - Handles unsupported shells gracefully with warning messages
-pub fn apply_shell_configs(shell_cfg: ShellConfig) {
+///
+/// # Returns
+/// * `Vec<(String, PathBuf)>` - The shells and startup files that were written
+pub fn apply_shell_configs(shell_cfg: ShellConfig) -> Vec<(String, PathBuf)> {
     eprintln!("{}:", "Shell Configuration".bright_yellow().bold());
     println!("{}\n", "=".repeat(20).bright_yellow());
     log_info!("[SDB::ShellConfig] Applying Shell Configurations...");
 
-    let Some(rc_path) = get_rc_file(&shell_cfg.run_commands.shell) else {
+    let mut written = Vec::new();
+    for shell in shell_cfg.run_commands.targets() {
+        warn_about_startup_layout(&shell_cfg, &shell);
+        for target in rc_targets(&shell_cfg, &shell) {
+            if apply_shell_config(&shell, &target) {
+                written.push((shell.clone(), target.path));
+            }
+        }
+    }
+    written
+}
+
+/// Counts, for every configured shell, the run commands and aliases missing from its RC file.
+///
+/// Returns `(shell, rc file, missing lines)` for shells with changes pending; used by
+/// `setup-devbox plan` to report what `apply_shell_configs` would write.
+pub fn pending_shell_changes(shell_cfg: &ShellConfig) -> Vec<(String, PathBuf, usize)> {
+    let mut pending = Vec::new();
+    for (shell, target) in shell_cfg
+        .run_commands
+        .targets()
+        .into_iter()
+        .flat_map(|shell| {
+            rc_targets(shell_cfg, &shell)
+                .into_iter()
+                .map(move |target| (shell.clone(), target))
+        })
+    {
+        let existing_content = parse_existing_sections(&read_rc_file(&target.path));
+        let empty_set = HashSet::new();
+        let is_missing = |section: &ConfigSection, line: &str| {
+            !existing_content
+                .get(section)
+                .unwrap_or(&empty_set)
+                .contains(&normalize_command(line))
+        };
+
+        let missing_commands = target
+            .run_commands
+            .iter()
+            .filter(|entry| {
+                let command = entry.command.trim();
+                !command.is_empty() && is_missing(&entry.section, command)
+            })
+            .count();
+        let missing_aliases = target
+            .aliases
+            .iter()
+            .filter(|alias| {
+                let alias_line = format!("alias {}='{}'", alias.name, alias.value);
+                is_missing(&ConfigSection::Aliases, &alias_line)
+            })
+            .count();
+
+        if missing_commands + missing_aliases > 0 {
+            pending.push((shell, target.path, missing_commands + missing_aliases));
+        }
+    }
+    pending
+}
+
+/// RC file content of one shell before and after applying the configuration.
+pub struct RcFilePreview {
+    pub shell: String,
+    pub rc_path: PathBuf,
+    pub current: Vec<String>,
+    pub planned: Vec<String>,
+    /// Existing entries change, so applying rewrites the whole file, which only happens
+    /// with `SDB_RESET_SHELLRC_FILE` set.
+    pub regenerate: bool,
+}
+
+/// Computes what applying the configuration would write to each shell's RC file, without
+/// writing anything. Used by `setup-devbox shellrc preview`.
+pub fn preview_shell_configs(shell_cfg: &ShellConfig) -> Vec<RcFilePreview> {
+    let mut previews = Vec::new();
+    for shell in shell_cfg.run_commands.targets() {
+        for target in rc_targets(shell_cfg, &shell) {
+            let planned = plan_rc_file(&target.path, &target.run_commands, &target.aliases);
+            previews.push(RcFilePreview {
+                shell: shell.clone(),
+                rc_path: target.path,
+                current: planned.current,
+                planned: planned.lines,
+                regenerate: planned.regenerate,
+            });
+        }
+    }
+    previews
+}
+
+/// A startup file of a shell with the run commands and aliases written to it.
+struct RcTarget {
+    path: PathBuf,
+    run_commands: Vec<RunCommandEntry>,
+    aliases: Vec<AliasEntry>,
+}
+
+/// The files the configuration of `shell` is written to: its RC file, preceded by its login
+/// profile when `split_login_profile` is set and the shell has one.
+fn rc_targets(shell_cfg: &ShellConfig, shell: &str) -> Vec<RcTarget> {
+    let Some(rc_path) = get_rc_file(shell) else {
         log_warn!(
             "[SDB::ShellConfig] Unsupported shell '{}'. Skipping configuration.",
-            shell_cfg.run_commands.shell.red()
+            shell.red()
         );
-        return;
+        return Vec::new();
+    };
+    let run_commands = rendered_run_commands(shell_cfg, shell);
+    let rc_target = |run_commands| RcTarget {
+        path: rc_path.clone(),
+        run_commands,
+        aliases: shell_cfg.aliases.clone(),
     };
 
-    log_debug!(
-        "[SDB::ShellConfig] Target RC file: {}",
+    match get_profile_file(shell).filter(|_| shell_cfg.split_login_profile == Some(true)) {
+        Some(profile_path) => {
+            let (profile_commands, rc_commands) = split_for_login_profile(shell, run_commands);
+            vec![
+                RcTarget {
+                    path: profile_path,
+                    run_commands: profile_commands,
+                    aliases: Vec::new(),
+                },
+                rc_target(rc_commands),
+            ]
+        }
+        None => vec![rc_target(run_commands)],
+    }
+}
+
+/// Warns about startup files that keep managed content from loading in some shells.
+fn warn_about_startup_layout(shell_cfg: &ShellConfig, shell: &str) {
+    for warning in layout_warnings(shell, shell_cfg.split_login_profile == Some(true)) {
+        log_warn!("[SDB::ShellConfig] {}", warning);
+    }
+}
+
+/// The run commands of the configuration rendered for `shell`, followed by the commands
+/// implementing its `shell_behavior` settings.
+fn rendered_run_commands(shell_cfg: &ShellConfig, shell: &str) -> Vec<RunCommandEntry> {
+    let mut run_commands: Vec<RunCommandEntry> = shell_cfg
+        .run_commands
+        .run_commands
+        .iter()
+        .filter_map(|entry| render_command(entry, shell))
+        .collect();
+    if let Some(behavior) = &shell_cfg.shell_behavior {
+        run_commands.extend(behavior_commands(behavior, shell));
+    }
+    run_commands
+}
+
+/// Applies the rendered run commands and aliases to one startup file of a shell. Returns
+/// whether the file was written.
+fn apply_shell_config(shell: &str, target: &RcTarget) -> bool {
+    let rc_path = &target.path;
+    log_info!(
+        "[SDB::ShellConfig] Configuring {} ({})",
+        shell.bold(),
         rc_path.display().to_string().cyan()
     );
 
     // Process run commands and aliases
-    if let Err(e) = process_shell_config(
-        &rc_path,
-        &shell_cfg.run_commands.run_commands,
-        &shell_cfg.aliases,
-    ) {
-        log_error!(
-            "[SDB::ShellConfig] Failed to process shell configuration: {}",
-            e
-        );
-        return;
-    }
+    let written = match process_shell_config(rc_path, &target.run_commands, &target.aliases) {
+        Ok(written) => written,
+        Err(e) => {
+            log_error!(
+                "[SDB::ShellConfig] Failed to process shell configuration: {}",
+                e
+            );
+            return false;
+        }
+    };
 
     // Source the updated RC file
-    if let Err(e) = source_rc_file(&shell_cfg.run_commands.shell, &rc_path) {
+    if let Err(e) = source_rc_file(shell, rc_path) {
         log_warn!(
             "[SDB::ShellConfig] Failed to source RC file: {}",
             e.to_string().yellow()
         );
     }
+    written
 }
 
 /// Main function to process all shell configurations with intelligent update detection
@@ -74,29 +230,20 @@ pub fn apply_shell_configs(shell_cfg: ShellConfig) {
 /// * `aliases` - Slice of alias entries to process
 ///
 /// # Returns
-/// * `Result<(), Box<dyn std::error::Error>>` - Result indicating success or failure
+/// * `Result<bool, Box<dyn std::error::Error>>` - Whether the file was written, or the error
 ///
 /// # Algorithm
-/// 1. Read existing RC file content
-/// 2. Parse existing managed sections
-/// 3. Check if any commands/aliases need updates (not just additions)
-/// 4. If updates detected and regeneration enabled, perform full regeneration
-/// 5. Otherwise, process normally with append-only operations
-/// 6. Write changes if any modifications were made
+/// 1. Compute the new content (see `plan_rc_file`)
+/// 2. If the file has to be regenerated, do so only when regeneration is enabled
+/// 3. Otherwise, write the changes if any modifications were made
 fn process_shell_config(
     rc_path: &Path,
     run_commands: &[RunCommandEntry],
     aliases: &[AliasEntry],
-) -> Result<(), Box<dyn std::error::Error>> {
-    let mut lines = read_rc_file(rc_path);
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let planned = plan_rc_file(rc_path, run_commands, aliases);
 
-    // Parse existing managed sections
-    let existing_content = parse_existing_sections(&lines);
-
-    // Check if we need to do a full regeneration first
-    let needs_regeneration = check_for_updates(run_commands, aliases, &existing_content);
-
-    if needs_regeneration && is_env_var_set("SDB_RESET_SHELLRC_FILE") {
+    if planned.regenerate && is_env_var_set("SDB_RESET_SHELLRC_FILE") {
         log_info!("[SDB::ShellConfig] Updates detected - regenerating Shell RC file");
 
         // Remove the file and start fresh
@@ -108,42 +255,84 @@ fn process_shell_config(
             return Err(Box::new(e));
         }
 
-        // Start with empty lines for complete regeneration
-        lines = Vec::new();
-
-        // Ensure managed sections exist in the fresh file
-        ensure_sections_exist(&mut lines, run_commands, aliases);
-
-        // Process all commands as new additions (no duplicate checking against old content)
-        // Bypass any checks and recreates the Shell RC File as new
-        // Needs `SDB_RESET_SHELLRC_FILE` to be set
-        process_run_commands_after_reset(&mut lines, run_commands);
-        process_aliases_after_reset(&mut lines, aliases);
-
-        final_write(rc_path, &lines)?;
-    } else if needs_regeneration {
+        final_write(rc_path, &planned.lines)?;
+        return Ok(true);
+    } else if planned.regenerate {
         // Updates detected but env var not set - just warn
         log_warn!("[SDB::ShellConfig] Updates detected but file regeneration disabled");
         log_warn!(
             "[SDB::ShellConfig] Set Environment Variable: {} to automate regeneration",
             "SDB_RESET_SHELLRC_FILE".yellow()
         );
+    } else if planned.lines != planned.current {
+        final_write(rc_path, &planned.lines)?;
+        return Ok(true);
     } else {
-        // No updates needed, process normally
+        log_info!("[SDB::ShellConfig] No changes needed - all configurations are up to date");
+    }
+
+    Ok(false)
+}
+
+/// Content of an RC file before and after applying run commands and aliases.
+struct PlannedRcFile {
+    current: Vec<String>,
+    lines: Vec<String>,
+    /// Existing entries change and the file has to be regenerated from scratch.
+    regenerate: bool,
+}
+
+/// Computes the new content of the RC file without writing it.
+///
+/// # Algorithm
+/// 1. Read existing RC file content
+/// 2. Parse existing managed sections
+/// 3. Check if any commands/aliases need updates (not just additions)
+/// 4. If updates are detected, build the file from scratch (regeneration)
+/// 5. Otherwise, append the new commands and aliases to their sections
+fn plan_rc_file(
+    rc_path: &Path,
+    run_commands: &[RunCommandEntry],
+    aliases: &[AliasEntry],
+) -> PlannedRcFile {
+    let current = read_rc_file(rc_path);
+
+    // Parse existing managed sections
+    let existing_content = parse_existing_sections(&current);
+
+    // Check if we need to do a full regeneration first
+    if check_for_updates(run_commands, aliases, &existing_content) {
+        // Start with empty lines for complete regeneration
+        let mut lines = Vec::new();
+
+        // Ensure managed sections exist in the fresh file
         ensure_sections_exist(&mut lines, run_commands, aliases);
 
-        let mut changes_made = false;
-        changes_made |= process_run_commands(&mut lines, run_commands, &existing_content);
-        changes_made |= process_aliases(&mut lines, aliases, &existing_content);
+        // Process all commands as new additions (no duplicate checking against old content)
+        // Writing this content needs `SDB_RESET_SHELLRC_FILE` to be set
+        process_run_commands_after_reset(&mut lines, run_commands);
+        process_aliases_after_reset(&mut lines, aliases);
 
-        if changes_made {
-            final_write(rc_path, &lines)?;
-        } else {
-            log_info!("[SDB::ShellConfig] No changes needed - all configurations are up to date");
-        }
+        return PlannedRcFile {
+            current,
+            lines,
+            regenerate: true,
+        };
     }
 
-    Ok(())
+    // No updates needed, process normally
+    let mut lines = current.clone();
+    ensure_sections_exist(&mut lines, run_commands, aliases);
+
+    let mut changes_made = false;
+    changes_made |= process_run_commands(&mut lines, run_commands, &existing_content);
+    changes_made |= process_aliases(&mut lines, aliases, &existing_content);
+
+    PlannedRcFile {
+        lines: if changes_made { lines } else { current.clone() },
+        current,
+        regenerate: false,
+    }
 }
 
 /// Checks if any commands or aliases need actual updates (not just new additions)
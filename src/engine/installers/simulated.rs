@@ -0,0 +1,258 @@
+//! # Simulated Installer Module
+//!
+//! The hidden `--simulate <FIXTURES>` flag replaces every installer with [`SimulatedInstaller`],
+//! a deterministic fake that neither touches the network nor changes the machine, so wrapper
+//! automation (and this crate's tests) can run complete `plan`, `now` and summary flows
+//! reproducibly. The fixture file decides what the fakes report:
+//!
+//! ```yaml
+//! default_latest: 1.0.0     # what `latest` resolves to (default: 1.0.0)
+//! tools:
+//!   ripgrep:
+//!     latest: 14.1.1        # what `latest` resolves to for this tool
+//!   bat:
+//!     fail: connection reset by peer   # installing or updating the tool fails with this
+//! ```
+//!
+//! While simulating, only tools are processed: their executables are recorded under
+//! `<fixtures dir>/bin` without being written, configuration management, shims, smoke tests
+//! and hooks are skipped, and fonts, shell configuration and system settings are left alone.
+//! Without `--state`, the state file (and the run summary next to it) is kept next to the
+//! fixture file.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+use colored::Colorize;
+use serde::Deserialize;
+
+use crate::engine::installers::errors::InstallerError;
+use crate::engine::installers::traits::Installer;
+use crate::engine::uninstaller::executors::ItemToBeRemoved;
+use crate::schemas::state_file::ToolState;
+use crate::schemas::tools_types::ToolEntry;
+use crate::{log_debug, log_info};
+
+/// What `latest` resolves to when the fixture file does not say.
+const DEFAULT_LATEST: &str = "1.0.0";
+
+static FIXTURES: OnceLock<Arc<Fixtures>> = OnceLock::new();
+
+/// Contents of a `--simulate` fixture file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Fixtures {
+    /// Version `latest` resolves to for tools without their own `latest`.
+    #[serde(default)]
+    pub default_latest: Option<String>,
+    /// Per-tool behavior, keyed by tool name.
+    #[serde(default)]
+    pub tools: BTreeMap<String, ToolFixture>,
+    /// Directory of the fixture file; simulated executables are recorded under its `bin`.
+    #[serde(skip)]
+    pub root: PathBuf,
+}
+
+/// How the fake installer treats one tool.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ToolFixture {
+    /// Version `latest` resolves to for this tool.
+    #[serde(default)]
+    pub latest: Option<String>,
+    /// Error message to fail installing or updating the tool with.
+    #[serde(default)]
+    pub fail: Option<String>,
+}
+
+impl Fixtures {
+    /// Reads a fixture file.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        let mut fixtures: Fixtures = serde_yaml::from_str(&content)
+            .map_err(|e| format!("invalid fixture file {}: {e}", path.display()))?;
+        fixtures.root = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or(Path::new("."))
+            .to_path_buf();
+        Ok(fixtures)
+    }
+
+    /// The version `latest` resolves to for `tool`.
+    pub fn latest_version(&self, tool: &ToolEntry) -> String {
+        self.tools
+            .get(&tool.name)
+            .and_then(|fixture| fixture.latest.clone())
+            .or_else(|| self.default_latest.clone())
+            .unwrap_or_else(|| DEFAULT_LATEST.to_string())
+    }
+
+    /// The version installing `tool` records: its pinned version, or what `latest` resolves to.
+    pub fn installed_version(&self, tool: &ToolEntry) -> String {
+        tool.version
+            .clone()
+            .filter(|version| version != "latest")
+            .unwrap_or_else(|| self.latest_version(tool))
+    }
+}
+
+/// Enables simulation for the rest of the process with the fixtures in `path`
+/// (`--simulate`). Must be called before any installer is created.
+pub fn configure(path: &Path) -> Result<(), String> {
+    let fixtures = Fixtures::load(path)?;
+    log_info!(
+        "[SDB::Simulate] {} Installers are simulated with fixtures from {}; nothing is installed.",
+        "SIMULATION".bright_magenta().bold(),
+        path.display()
+    );
+    FIXTURES
+        .set(Arc::new(fixtures))
+        .map_err(|_| "simulation is already configured".to_string())
+}
+
+/// The fixtures of this run, when simulating.
+pub fn fixtures() -> Option<Arc<Fixtures>> {
+    FIXTURES.get().cloned()
+}
+
+/// Whether installers are simulated in this run.
+pub fn is_active() -> bool {
+    FIXTURES.get().is_some()
+}
+
+/// State file used while simulating without `--state`: next to the fixture file.
+pub fn state_file() -> Option<String> {
+    FIXTURES
+        .get()
+        .map(|fixtures| fixtures.root.join("state.json").display().to_string())
+}
+
+/// Deterministic stand-in for every installer while simulating.
+pub struct SimulatedInstaller {
+    fixtures: Arc<Fixtures>,
+}
+
+impl SimulatedInstaller {
+    pub fn new(fixtures: Arc<Fixtures>) -> Self {
+        Self { fixtures }
+    }
+}
+
+impl Installer for SimulatedInstaller {
+    fn name(&self) -> &'static str {
+        "simulated"
+    }
+
+    /// The real installer's command need not exist.
+    fn validate(&self, _tool: &ToolEntry) -> Result<(), InstallerError> {
+        Ok(())
+    }
+
+    /// Records the tool as installed, or fails with the fixture's `fail` message.
+    fn install(&self, tool_entry: &ToolEntry) -> Result<ToolState, InstallerError> {
+        if let Some(message) = self
+            .fixtures
+            .tools
+            .get(&tool_entry.name)
+            .and_then(|fixture| fixture.fail.as_ref())
+        {
+            return Err(InstallerError::InstallationFailed(message.clone()));
+        }
+
+        let version = self.fixtures.installed_version(tool_entry);
+        let executable = tool_entry.rename_to.as_deref().unwrap_or(&tool_entry.name);
+        let install_path = self.fixtures.root.join("bin").join(executable);
+        log_info!(
+            "[SDB::Simulate] Simulated {} {} from {}",
+            tool_entry.name.bold(),
+            version.green(),
+            tool_entry.source
+        );
+
+        Ok(ToolState::new(
+            tool_entry,
+            &install_path,
+            tool_entry.source.to_string(),
+            "simulated".to_string(),
+            version,
+            tool_entry.url.clone(),
+            tool_entry.executable_path_after_extract.clone(),
+            None,
+        ))
+    }
+
+    fn uninstall(&self, item: &ItemToBeRemoved) -> Result<(), InstallerError> {
+        log_debug!("[SDB::Simulate] Simulated removing {}", item.item_name);
+        Ok(())
+    }
+
+    fn resolve_latest_version(&self, tool_entry: &ToolEntry) -> Result<String, InstallerError> {
+        Ok(self.fixtures.latest_version(tool_entry))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schemas::tools_enums::SourceType;
+
+    fn tool(name: &str, version: Option<&str>) -> ToolEntry {
+        serde_yaml::from_str(&format!(
+            "name: {name}\nsource: github\nrepo: example/{name}\n{}",
+            version.map_or(String::new(), |version| format!("version: {version}\n"))
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_fixture_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("simulate.yaml");
+        fs::write(
+            &path,
+            "default_latest: 2.0.0\ntools:\n  ripgrep:\n    latest: 14.1.1\n",
+        )
+        .unwrap();
+        let fixtures = Fixtures::load(&path).unwrap();
+
+        assert_eq!(fixtures.root, dir.path());
+        assert_eq!(fixtures.latest_version(&tool("ripgrep", None)), "14.1.1");
+        assert_eq!(fixtures.latest_version(&tool("bat", None)), "2.0.0");
+        assert_eq!(
+            fixtures.installed_version(&tool("ripgrep", Some("latest"))),
+            "14.1.1"
+        );
+        assert_eq!(
+            fixtures.installed_version(&tool("ripgrep", Some("13.0.0"))),
+            "13.0.0"
+        );
+        assert_eq!(
+            Fixtures::default().latest_version(&tool("bat", None)),
+            DEFAULT_LATEST
+        );
+    }
+
+    #[test]
+    fn test_simulated_install() {
+        let fixtures: Fixtures =
+            serde_yaml::from_str("tools:\n  bat:\n    fail: connection reset\n").unwrap();
+        let installer = SimulatedInstaller::new(Arc::new(Fixtures {
+            root: PathBuf::from("/fixtures"),
+            ..fixtures
+        }));
+
+        let mut ripgrep = tool("ripgrep", Some("14.1.0"));
+        ripgrep.rename_to = Some("rg".to_string());
+        let state = installer.install(&ripgrep).unwrap();
+        assert_eq!(state.version, "14.1.0");
+        assert_eq!(state.install_path, "/fixtures/bin/rg");
+        assert_eq!(state.install_method, SourceType::Github.to_string());
+
+        let error = installer.install(&tool("bat", None)).unwrap_err();
+        assert_eq!(error.to_string(), "Installation failed: connection reset");
+    }
+}
@@ -1,4 +1,6 @@
+use crate::core::platform::check_installer_command_available;
 use crate::engine::installers::errors::InstallerError;
+use crate::engine::uninstaller::executors::ItemToBeRemoved;
 use crate::schemas::state_file::ToolState;
 use crate::schemas::tools_types::ToolEntry;
 
@@ -6,8 +8,41 @@ use crate::schemas::tools_types::ToolEntry;
 ///
 /// This trait defines the common interface for all installers. Each installer
 /// (e.g., `brew`, `github`, `cargo`) must implement this trait to provide a
-/// consistent way to install tools and check for their latest versions.
+/// consistent way to validate prerequisites, install and uninstall tools, and
+/// resolve their latest versions.
+///
+/// Installers are registered by source type in the
+/// [`InstallerFactory`](crate::engine::installers::factory::InstallerFactory) at startup;
+/// the installation and removal pipelines only ever talk to this trait.
 pub trait Installer {
+    /// # `name`
+    ///
+    /// Returns the short name of the installer (e.g., `"brew"`), as used in logs.
+    fn name(&self) -> &'static str;
+
+    /// # `required_command`
+    ///
+    /// Returns the external command this installer shells out to, if any.
+    /// Installers that only download files (`github`, `url`) return `None`.
+    fn required_command(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// # `validate`
+    ///
+    /// Checks that the installer's prerequisites are present before a tool is installed,
+    /// so a missing `brew` or `go` fails fast with a clear message.
+    ///
+    /// The default implementation checks that [`required_command`](Self::required_command)
+    /// is available on `PATH`.
+    fn validate(&self, _tool: &ToolEntry) -> Result<(), InstallerError> {
+        match self.required_command() {
+            Some(command) => check_installer_command_available(command)
+                .map_err(|e| InstallerError::ValidationFailed(e.to_string())),
+            None => Ok(()),
+        }
+    }
+
     /// # `install`
     ///
     /// Installs a tool based on the provided `ToolEntry`.
@@ -25,7 +60,27 @@ pub trait Installer {
     /// - `Err(InstallerError)`: An `InstallerError` if the installation fails for any reason.
     fn install(&self, tool: &ToolEntry) -> Result<ToolState, InstallerError>;
 
-    /// # `get_latest_version`
+    /// # `uninstall`
+    ///
+    /// Removes a tool previously installed by this installer.
+    ///
+    /// ## Arguments
+    ///
+    /// * `item`: Metadata about the installed tool, taken from the state file.
+    ///
+    /// ## Returns
+    ///
+    /// - `Ok(())` if the tool was removed (or was already gone).
+    /// - `Err(InstallerError)` if the removal failed or is not supported.
+    fn uninstall(&self, item: &ItemToBeRemoved) -> Result<(), InstallerError> {
+        Err(InstallerError::ConfigurationError(format!(
+            "Installer '{}' does not support uninstalling '{}'",
+            self.name(),
+            item.item_name
+        )))
+    }
+
+    /// # `resolve_latest_version`
     ///
     /// Gets the latest available version for a tool.
     ///
@@ -40,5 +95,5 @@ pub trait Installer {
     /// A `Result` which is:
     /// - `Ok(String)`: A string containing the latest version number.
     /// - `Err(InstallerError)`: An `InstallerError` if it fails to get the latest version.
-    fn get_latest_version(&self, tool: &ToolEntry) -> Result<String, InstallerError>;
+    fn resolve_latest_version(&self, tool: &ToolEntry) -> Result<String, InstallerError>;
 }
@@ -33,7 +33,7 @@
 //!
 //! ## Supported File Types
 //!
-//! - **Archives**: zip, tar.gz, tar.bz2, tar.xz, tar, gz, bz2, xz, 7zip
+//! - **Archives**: zip, tar.gz, tar.bz2, tar.xz, tar.zst, tar, gz, bz2, xz, zst, 7zip
 //! - **macOS Packages**: pkg, dmg
 //! - **Binaries**: Direct executable files
 //!
@@ -48,6 +48,7 @@
 // Standard Library Imports
 use std::fs;
 use std::path::PathBuf;
+use std::process::Command;
 // External Crate Imports
 // The `colored` crate allows us to make log messages and other terminal output more readable
 // by applying colors (e.g., `.blue()`, `.green()`, `.red()`).
@@ -60,10 +61,12 @@ use crate::{log_debug, log_error, log_info, log_warn};
 // For executing external commands and capturing their output.
 // `std::process::Command` is used to run commands/hooks.
 // `std::process::Output` captures the stdout, stderr, and exit status of executed commands.
-use crate::core::{assets, assets::detect_file_type};
+use crate::core::checksum::Checksum;
+use crate::core::{assets, assets::detect_file_type, version_check};
 use crate::engine::execute_post_installation_hooks;
 use crate::engine::installers::errors::InstallerError;
 use crate::engine::installers::traits::Installer;
+use crate::engine::uninstaller::executors::{BinaryUninstaller, ItemToBeRemoved, ToolUninstaller};
 
 // `ToolEntry`: Represents a single tool's configuration from `tools.yaml`.
 // `ToolState`: Represents the actual state of an installed tool for persistence in `state.json`.
@@ -74,6 +77,24 @@ use crate::schemas::tools_types::ToolEntry;
 pub struct UrlInstaller;
 
 impl Installer for UrlInstaller {
+    fn name(&self) -> &'static str {
+        "url"
+    }
+
+    fn uninstall(&self, item: &ItemToBeRemoved) -> Result<(), InstallerError> {
+        BinaryUninstaller
+            .uninstall(item)
+            .map_err(InstallerError::CommandFailed)
+    }
+
+    /// Checks the URL configuration before anything is downloaded. `url_command` is not run
+    /// here; the URL it prints is resolved and checked when the tool is installed.
+    fn validate(&self, tool_entry: &ToolEntry) -> Result<(), InstallerError> {
+        validate_url_configuration(tool_entry).ok_or_else(|| {
+            InstallerError::ConfigurationError("URL configuration is invalid".into())
+        })
+    }
+
     /// Installs a software tool by downloading and processing assets from a direct URL.
     ///
     /// This function provides a robust installer for URL-hosted tools that mirrors the quality
@@ -181,47 +202,15 @@ impl Installer for UrlInstaller {
             tool_entry
         );
 
-        // Step 1: Validate URL configuration - ensure required fields are present
-        let download_url = validate_url_configuration(tool_entry).ok_or_else(|| {
+        // Step 1: Resolve the download URL, running `url_command` (once) if configured
+        let download_url = resolve_download_url(tool_entry).ok_or_else(|| {
             InstallerError::ConfigurationError("URL configuration is invalid".into())
         })?;
 
-        // Step 2: Download asset to temporary location
-        log_debug!(
-            "[SDB::Tools::UrlInstaller] Downloading asset from: {}",
-            download_url.blue()
-        );
-        let (temp_dir, downloaded_path) = assets::download_url_asset(tool_entry, &download_url)
-            .ok_or_else(|| {
-                InstallerError::DownloadFailed(format!("Failed to download from {}", download_url))
-            })?;
-
-        // Step 3: Detect file type and determine installation strategy
-        let file_type = detect_file_type(&downloaded_path);
-        log_debug!(
-            "[SDB::Tools::UrlInstaller] Detected file type: {}",
-            file_type.to_string().magenta()
-        );
-
-        // Step 4: Process asset based on file type (binary, archive, or macOS package)
-        let (package_type, final_install_path, working_dir) =
-            assets::process_asset_by_type(tool_entry, &downloaded_path, &file_type, &temp_dir)
-                .ok_or_else(|| {
-                    cleanup_temp_file(&downloaded_path);
-                    InstallerError::InstallationFailed("Failed to process asset".into())
-                })?;
-
-        // Step 5: Verify installation was successful
-        if !verify_installation(&final_install_path, &package_type, tool_entry) {
-            cleanup_temp_file(&downloaded_path);
-            return Err(InstallerError::InstallationFailed(format!(
-                "Verification failed for tool '{}'",
-                tool_entry.name
-            )));
-        }
-
-        // Step 6: Clean up temporary download file
-        cleanup_temp_file(&downloaded_path);
+        // Steps 2-6: Stream tar-family archives straight into the extraction directory when
+        // possible; otherwise download, detect, process, verify and clean up the temp file.
+        let (_temp_dir, package_type, final_install_path, working_dir, validators) =
+            download_and_process_asset(tool_entry, &download_url)?;
 
         // Step 7: Execute any post-installation hooks defined in tool configuration
         log_debug!(
@@ -237,25 +226,33 @@ impl Installer for UrlInstaller {
         );
 
         // Step 8: Return comprehensive ToolState for state tracking and persistence
-        Ok(ToolState::new(
+        let mut tool_state = ToolState::new(
             tool_entry,
             &final_install_path,
             "direct-url".to_string(),
             package_type,
-            tool_entry
-                .version
-                .clone()
-                .unwrap_or_else(|| "unknown".to_string()),
-            Some(download_url),
+            resolve_installed_version(tool_entry),
+            // Signed URLs from `url_command` are short-lived secrets; keep them out of state
+            if tool_entry.url_command.is_some() {
+                tool_entry.url.clone()
+            } else {
+                Some(download_url)
+            },
             None,
             executed_post_installation_hooks,
-        ))
+        );
+        // Remember cache validators so `latest` tools can be checked with a conditional request
+        tool_state.etag = validators.etag;
+        tool_state.last_modified = validators.last_modified;
+
+        Ok(tool_state)
     }
 
-    /// # `get_latest_version`
+    /// # `resolve_latest_version`
     ///
-    /// For URL-based tools, automatic version detection is not supported.
-    /// This function returns the version specified in the configuration.
+    /// URL-based tools have no release metadata of their own. When a `version_check` block is
+    /// configured it is used to discover the latest upstream version; otherwise this function
+    /// returns the version specified in the configuration.
     ///
     /// ## Arguments
     ///
@@ -264,10 +261,10 @@ impl Installer for UrlInstaller {
     /// ## Returns
     ///
     /// A `Result` which is:
-    /// - `Ok(String)`: The version string from the tool's configuration.
-    /// - `Err(InstallerError)`: An `InstallerError` if no version is specified in the
-    ///   configuration, or if the version is "latest".
-    fn get_latest_version(&self, tool_entry: &ToolEntry) -> Result<String, InstallerError> {
+    /// - `Ok(String)`: The discovered or configured version string.
+    /// - `Err(InstallerError)`: An `InstallerError` if the version check fails, or if no
+    ///   `version_check` is configured and the version is missing or "latest".
+    fn resolve_latest_version(&self, tool_entry: &ToolEntry) -> Result<String, InstallerError> {
         log_debug!(
             "[SDB::Tools::UrlInstaller] Getting latest version for: {}",
             tool_entry.name.bold()
@@ -277,6 +274,11 @@ impl Installer for UrlInstaller {
             tool_entry
         );
 
+        if let Some(check) = &tool_entry.version_check {
+            return version_check::discover_version(tool_entry, check)
+                .map_err(InstallerError::VersionDetectionFailed);
+        }
+
         match &tool_entry.version {
             Some(version) if version.to_lowercase() == "latest" => {
                 Err(InstallerError::VersionDetectionFailed(
@@ -300,29 +302,254 @@ impl Installer for UrlInstaller {
     }
 }
 
-/// Validates that the tool configuration contains required URL fields.
+/// Downloads the asset at `download_url` and installs it.
 ///
-/// This function checks that the URL field is specified in the tool configuration,
-/// as it is mandatory for direct URL installations. Without this field, the installer
-/// cannot download the asset.
+/// Tar-family archives are streamed through the decompressor directly into the extraction
+/// directory (see `assets::stream_url_asset`). When streaming does not apply or fails midway,
+/// the buffered path is used instead: download, detect file type, process, verify and
+/// remove the temporary download. Tools with a `checksum` always take the buffered path, and
+/// the download is checked against it before it is processed.
 ///
-/// # Arguments
+/// # Returns
+///
+/// * `Ok((temp_dir, package_type, final_install_path, working_dir, validators))` - The
+///   temporary directory must be kept alive until post-installation hooks have run in
+///   `working_dir`; `validators` are the response's `ETag`/`Last-Modified` headers
+/// * `Err(InstallerError)` - If download, processing or verification fails
+fn download_and_process_asset(
+    tool_entry: &ToolEntry,
+    download_url: &str,
+) -> Result<
+    (
+        tempfile::TempDir,
+        String,
+        PathBuf,
+        PathBuf,
+        assets::ResponseValidators,
+    ),
+    InstallerError,
+> {
+    let checksum = tool_entry
+        .checksum
+        .as_deref()
+        .map(Checksum::parse)
+        .transpose()
+        .map_err(|e| {
+            let msg = format!("Invalid checksum for tool {}: {}", tool_entry.name, e);
+            log_error!("[SDB::Tools::UrlInstaller] {}", msg);
+            InstallerError::ConfigurationError(msg)
+        })?;
+
+    if checksum.is_none()
+        && let Some(file_type) = assets::streamable_file_type(tool_entry, download_url)
+    {
+        log_debug!(
+            "[SDB::Tools::UrlInstaller] Streaming {} asset from: {}",
+            file_type.magenta(),
+            download_url.blue()
+        );
+        if let Some((temp_dir, extracted_path, validators)) =
+            assets::stream_url_asset(tool_entry, download_url, &file_type)
+        {
+            let (package_type, final_install_path, working_dir) =
+                assets::process_streamed_asset(tool_entry, &extracted_path).ok_or_else(|| {
+                    InstallerError::InstallationFailed("Failed to process asset".into())
+                })?;
+            if !verify_installation(&final_install_path, &package_type, tool_entry) {
+                return Err(InstallerError::InstallationFailed(format!(
+                    "Verification failed for tool '{}'",
+                    tool_entry.name
+                )));
+            }
+            return Ok((
+                temp_dir,
+                package_type,
+                final_install_path,
+                working_dir,
+                validators,
+            ));
+        }
+        log_warn!(
+            "[SDB::Tools::UrlInstaller] Streaming failed for {}, falling back to buffered download",
+            download_url.yellow()
+        );
+    }
+
+    // Step 2: Download asset to temporary location
+    log_debug!(
+        "[SDB::Tools::UrlInstaller] Downloading asset from: {}",
+        download_url.blue()
+    );
+    let (temp_dir, downloaded_path, validators) =
+        assets::download_url_asset_with_validators(tool_entry, download_url).ok_or_else(|| {
+            InstallerError::DownloadFailed(format!("Failed to download from {}", download_url))
+        })?;
+
+    // Step 2b: Verify the download against the configured digest before touching it
+    if let Some(checksum) = &checksum {
+        checksum.verify_file(&downloaded_path).map_err(|e| {
+            log_error!("[SDB::Tools::UrlInstaller] {}", e);
+            cleanup_temp_file(&downloaded_path);
+            InstallerError::ValidationFailed(e)
+        })?;
+        log_info!(
+            "[SDB::Tools::UrlInstaller] Verified {} checksum of {}",
+            checksum.algorithm.to_string().green(),
+            tool_entry.name.bold()
+        );
+    }
+
+    // Step 3: Detect file type and determine installation strategy
+    let file_type = detect_file_type(&downloaded_path);
+    log_debug!(
+        "[SDB::Tools::UrlInstaller] Detected file type: {}",
+        file_type.to_string().magenta()
+    );
+
+    // Step 4: Process asset based on file type (binary, archive, or macOS package)
+    let (package_type, final_install_path, working_dir) =
+        assets::process_asset_by_type(tool_entry, &downloaded_path, &file_type, &temp_dir)
+            .ok_or_else(|| {
+                cleanup_temp_file(&downloaded_path);
+                InstallerError::InstallationFailed("Failed to process asset".into())
+            })?;
+
+    // Step 5: Verify installation was successful
+    if !verify_installation(&final_install_path, &package_type, tool_entry) {
+        cleanup_temp_file(&downloaded_path);
+        return Err(InstallerError::InstallationFailed(format!(
+            "Verification failed for tool '{}'",
+            tool_entry.name
+        )));
+    }
+
+    // Step 6: Clean up temporary download file
+    cleanup_temp_file(&downloaded_path);
+
+    Ok((
+        temp_dir,
+        package_type,
+        final_install_path,
+        working_dir,
+        validators,
+    ))
+}
+
+/// Determines the version recorded in state for a freshly installed URL tool.
 ///
-/// * `tool_entry` - The tool configuration to validate
+/// Concrete configured versions are recorded as-is. For `latest` (or unset) versions the
+/// `version_check` block, when present, is consulted so state holds the real upstream
+/// version and later runs can compare against it.
+fn resolve_installed_version(tool_entry: &ToolEntry) -> String {
+    let configured = tool_entry.version.as_deref();
+    if let (None | Some("latest"), Some(check)) = (configured, &tool_entry.version_check) {
+        match version_check::discover_version(tool_entry, check) {
+            Ok(version) => return version,
+            Err(e) => log_warn!(
+                "[SDB::Tools::UrlInstaller] version_check failed for {}: {}",
+                tool_entry.name.yellow(),
+                e
+            ),
+        }
+    }
+    configured.unwrap_or("unknown").to_string()
+}
+
+/// Runs the tool's `url_command` and returns the URL it prints.
+///
+/// This supports artifact stores that hand out short-lived, query-signed URLs: the helper
+/// command (run via `sh -c`) is expected to print the download URL on stdout. Only the last
+/// non-empty line is used, so helpers may print progress output first.
 ///
 /// # Returns
 ///
-/// * `Some(String)` - The validated URL string if present and valid
-/// * `None` - If the URL field is missing or invalid, with appropriate error logging
+/// * `Some(String)` - The URL printed by the command
+/// * `None` - If the command fails or prints nothing
+fn resolve_url_command(tool_entry: &ToolEntry, command: &str) -> Option<String> {
+    log_debug!(
+        "[SDB::Tools::UrlInstaller] Resolving download URL for {} via command: {}",
+        tool_entry.name.bold(),
+        command.cyan()
+    );
+    let output = match Command::new("sh").arg("-c").arg(command).output() {
+        Ok(output) => output,
+        Err(e) => {
+            log_error!(
+                "[SDB::Tools::UrlInstaller] Failed to run url_command for tool '{}': {}",
+                tool_entry.name.red(),
+                e
+            );
+            return None;
+        }
+    };
+    if !output.status.success() {
+        log_error!(
+            "[SDB::Tools::UrlInstaller] url_command for tool '{}' exited with {}: {}",
+            tool_entry.name.red(),
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let url = stdout.lines().map(str::trim).rfind(|l| !l.is_empty());
+    if url.is_none() {
+        log_error!(
+            "[SDB::Tools::UrlInstaller] url_command for tool '{}' printed no URL",
+            tool_entry.name.red()
+        );
+    }
+    url.map(str::to_string)
+}
+
+/// Validates that the tool configuration contains required URL fields, without side effects.
+///
+/// A `url` is checked as configured. A `url_command` only has to be present; it is run, and
+/// the URL it prints checked, by [`resolve_download_url`] at install time.
+///
+/// # Returns
+///
+/// * `Some(())` - If the configuration can produce a download URL
+/// * `None` - If the URL is missing or invalid, with appropriate error logging
+fn validate_url_configuration(tool_entry: &ToolEntry) -> Option<()> {
+    match &tool_entry.url_command {
+        Some(command) if !command.trim().is_empty() => Some(()),
+        Some(_) => {
+            log_error!(
+                "[SDB::Tools::UrlInstaller] Configuration error: 'url_command' is empty for tool {}",
+                tool_entry.name.red()
+            );
+            None
+        }
+        None => validate_url(tool_entry, tool_entry.url.clone()).map(|_| ()),
+    }
+}
+
+/// Returns the URL to download the tool from: the output of its `url_command`, which takes
+/// precedence (e.g. a helper that signs the URL), or its `url`.
+fn resolve_download_url(tool_entry: &ToolEntry) -> Option<String> {
+    let configured_url = match &tool_entry.url_command {
+        Some(command) => Some(resolve_url_command(tool_entry, command)?),
+        None => tool_entry.url.clone(),
+    };
+    validate_url(tool_entry, configured_url)
+}
+
+/// Validates a configured or resolved download URL.
+///
+/// # Returns
+///
+/// * `Some(String)` - The trimmed URL if present and valid
+/// * `None` - If the URL is missing or invalid, with appropriate error logging
 ///
 /// # Validation Rules
 ///
 /// - URL must be present and not empty
 /// - URL must use http:// or https:// scheme
 /// - URL must not contain spaces
-/// - URL should be reasonably formatted
-fn validate_url_configuration(tool_entry: &ToolEntry) -> Option<String> {
-    let url = match &tool_entry.url {
+fn validate_url(tool_entry: &ToolEntry, configured_url: Option<String>) -> Option<String> {
+    let url = match &configured_url {
         Some(url) if !url.trim().is_empty() => url.trim().to_string(),
         Some(_) => {
             log_error!(
@@ -387,12 +614,12 @@ fn validate_url_configuration(tool_entry: &ToolEntry) -> Option<String> {
 ///
 /// # Verification by Package Type
 ///
-/// - **macOS Installers**: Trusts installer success status
+/// - **macOS and Windows Installers**: Trusts installer success status
 /// - **Binaries**: Verifies file exists, is accessible, and has executable permissions
 /// - **Archives**: Verifies extraction directory exists and is not empty
 fn verify_installation(install_path: &PathBuf, package_type: &str, tool_entry: &ToolEntry) -> bool {
     match package_type {
-        "macos-pkg-installer" | "macos-dmg-installer" => {
+        "macos-pkg-installer" | "macos-dmg-installer" | "windows-msi-installer" => {
             // For installers, we trust their success status
             log_debug!(
                 "[SDB::Tools::UrlInstaller] Installation verification completed for {} (installer type)",
@@ -503,3 +730,56 @@ fn cleanup_temp_file(temp_path: &PathBuf) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(url: Option<&str>, url_command: Option<String>) -> ToolEntry {
+        ToolEntry {
+            url: url.map(str::to_string),
+            url_command,
+            ..serde_yaml::from_str("name: tool\nsource: url").unwrap()
+        }
+    }
+
+    #[test]
+    fn test_validation_does_not_run_url_command() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("ran");
+        let command = format!(
+            "touch '{}' && echo progress && echo https://example.com/tool.tar.gz",
+            marker.display()
+        );
+        let tool = tool(None, Some(command));
+
+        assert!(UrlInstaller.validate(&tool).is_ok());
+        assert!(!marker.exists());
+
+        assert_eq!(
+            resolve_download_url(&tool).as_deref(),
+            Some("https://example.com/tool.tar.gz")
+        );
+        assert!(marker.exists());
+    }
+
+    #[test]
+    fn test_validation_checks_configured_url() {
+        assert!(
+            UrlInstaller
+                .validate(&tool(Some("https://example.com/a.zip"), None))
+                .is_ok()
+        );
+        assert!(
+            UrlInstaller
+                .validate(&tool(Some("ftp://example.com/a.zip"), None))
+                .is_err()
+        );
+        assert!(UrlInstaller.validate(&tool(None, None)).is_err());
+        assert!(
+            UrlInstaller
+                .validate(&tool(None, Some("  ".to_string())))
+                .is_err()
+        );
+    }
+}
@@ -54,6 +54,7 @@ use crate::{log_debug, log_error, log_info, log_warn};
 use crate::engine::execute_post_installation_hooks;
 use crate::engine::installers::errors::InstallerError;
 use crate::engine::installers::traits::Installer;
+use crate::engine::uninstaller::executors::{ItemToBeRemoved, ToolUninstaller, UvUninstaller};
 // `ToolEntry`: Represents a single tool's configuration from `tools.yaml`.
 // `ToolState`: Represents the actual state of an installed tool for persistence in `state.json`.
 use crate::schemas::state_file::ToolState;
@@ -63,6 +64,20 @@ use crate::schemas::tools_types::ToolEntry;
 pub struct UvInstaller;
 
 impl Installer for UvInstaller {
+    fn name(&self) -> &'static str {
+        "uv"
+    }
+
+    fn required_command(&self) -> Option<&'static str> {
+        Some("uv")
+    }
+
+    fn uninstall(&self, item: &ItemToBeRemoved) -> Result<(), InstallerError> {
+        UvUninstaller
+            .uninstall(item)
+            .map_err(InstallerError::CommandFailed)
+    }
+
     fn install(&self, tool_entry: &ToolEntry) -> Result<ToolState, InstallerError> {
         log_info!(
             "[SDB::Tools::UVInstaller] Attempting to install Python package: {}",
@@ -137,7 +152,7 @@ impl Installer for UvInstaller {
         ))
     }
 
-    fn get_latest_version(&self, tool_entry: &ToolEntry) -> Result<String, InstallerError> {
+    fn resolve_latest_version(&self, tool_entry: &ToolEntry) -> Result<String, InstallerError> {
         log_debug!(
             "[SDB::Tools::UVInstaller] Getting latest version for: {}",
             tool_entry.name.bold()
@@ -1,18 +1,25 @@
 pub mod configuration;
 pub mod installation;
 pub mod installers;
+pub mod plan;
 pub mod uninstaller;
 
 use crate::core::platform::execute_hooks;
+use crate::core::{delta, downloads, metadata_cache, protected};
 use crate::engine::configuration::processor::ConfigurationManagerProcessor;
 use crate::engine::installers::factory::InstallerFactory;
+use crate::engine::installers::{brew, simulated};
 use crate::schemas::path_resolver::PathResolver;
 use crate::schemas::state_file::DevBoxState;
+use crate::schemas::tools_enums::SourceType;
 use crate::schemas::tools_types::{
-    InstallationConfiguration, InstallationSummary, ToolConfig, ToolEntry,
+    Hook, InstallationConfiguration, InstallationSummary, ToolConfig, ToolEntry,
     ToolInstallationOrchestrator,
 };
+use crate::state::autosave::StateAutosave;
+use crate::state::checkpoint::RunCheckpoint;
 use crate::state::manager::save_state_to_file;
+use crate::state::receipts;
 use crate::{log_debug, log_info, log_warn};
 use colored::Colorize;
 use std::path::Path;
@@ -28,7 +35,8 @@ pub fn install_tools(
     force_update_latest: bool,
     dry_run: bool,
     paths: &PathResolver,
-) {
+    checkpoint: Option<&mut RunCheckpoint>,
+) -> InstallationSummary {
     eprintln!("\n");
     eprintln!("{}:", "TOOLS".bright_yellow().bold());
     eprintln!("{}", "=".repeat(7).bright_yellow());
@@ -36,6 +44,16 @@ pub fn install_tools(
     let installation_config =
         InstallationConfiguration::new(&tools_configuration, force_update_latest, dry_run);
 
+    metadata_cache::configure(
+        paths.cache_dir(),
+        tools_configuration.metadata_cache_ttl,
+        force_update_latest,
+    );
+    downloads::configure(tools_configuration.allowed_redirect_hosts.clone());
+    delta::configure(paths.cache_dir());
+    PathResolver::configure_work_dir(tools_configuration.work_dir.as_deref());
+    receipts::configure(paths.receipts_dir());
+
     let config_processor = ConfigurationManagerProcessor::new(paths);
     let installer_factory = InstallerFactory::new();
 
@@ -44,6 +62,8 @@ pub fn install_tools(
         &installation_config,
         config_processor,
         installer_factory,
+        checkpoint,
+        (!dry_run).then(|| StateAutosave::new(state_file_path)),
     );
 
     if dry_run {
@@ -70,7 +90,12 @@ pub fn install_tools(
 
     summary.display_summary();
 
-    if summary.has_state_changes() && !dry_run {
+    if !dry_run && !simulated::is_active() && tools_configuration.brew_cleanup == Some(true) {
+        run_brew_cleanup(&tools_configuration, &summary);
+    }
+
+    let metadata_changed = !dry_run && refresh_tool_metadata(&tools_configuration.tools, state);
+    if (summary.has_state_changes() || metadata_changed) && !dry_run {
         save_state_to_file(state, state_file_path);
     } else if dry_run {
         log_info!("[SDB::Engine] Dry-run completed. State file was not modified.");
@@ -79,6 +104,64 @@ pub fn install_tools(
     }
 
     eprintln!();
+    summary
+}
+
+/// Runs `brew cleanup` if this run installed or updated any brew tool, reporting the space
+/// reclaimed. Failures are only warned about.
+fn run_brew_cleanup(tools_configuration: &ToolConfig, summary: &InstallationSummary) {
+    let brew_changed = tools_configuration.tools.iter().any(|tool| {
+        tool.source == SourceType::Brew
+            && (summary.installed_tools.contains(&tool.name)
+                || summary.updated_tools.contains(&tool.name))
+    });
+    if !brew_changed {
+        return;
+    }
+
+    let prune_days = tools_configuration
+        .brew_cleanup_prune_days
+        .unwrap_or(brew::DEFAULT_CLEANUP_PRUNE_DAYS);
+    log_info!(
+        "[SDB::Engine] Running {} for downloads older than {} days...",
+        "brew cleanup".cyan(),
+        prune_days
+    );
+    match brew::cleanup(prune_days, false) {
+        Ok(Some(reclaimed)) => log_info!(
+            "[SDB::Engine] Homebrew cleanup reclaimed {}",
+            reclaimed.green()
+        ),
+        Ok(None) => log_info!("[SDB::Engine] Homebrew cleanup found nothing to remove"),
+        Err(e) => log_warn!("[SDB::Engine] {}", e),
+    }
+}
+
+/// Copies `notes`, `owner` and `protected` of installed tools into their state, so edits to
+/// this metadata are kept without reinstalling. Dropping `protected: true` needs
+/// `--force-protected`. Returns `true` if any changed.
+fn refresh_tool_metadata(tools: &[ToolEntry], state: &mut DevBoxState) -> bool {
+    let mut changed = false;
+    for tool in tools {
+        if protected::is_protected(state, &tool.name) && tool.protected != Some(true) {
+            if let Err(refusal) = protected::authorize(state, &tool.name, "unprotect") {
+                log_warn!("[SDB::Engine] {}; it stays protected", refusal);
+                continue;
+            }
+            changed = true;
+        }
+        if let Some(tool_state) = state.tools.get_mut(&tool.name)
+            && (tool_state.notes != tool.notes
+                || tool_state.owner != tool.owner
+                || tool_state.protected != tool.protected)
+        {
+            tool_state.notes = tool.notes.clone();
+            tool_state.owner = tool.owner.clone();
+            tool_state.protected = tool.protected;
+            changed = true;
+        }
+    }
+    changed
 }
 
 pub fn execute_post_installation_hooks(
@@ -97,6 +180,23 @@ pub fn execute_post_installation_hooks(
         return None;
     }
 
+    let commands = match post_install_hooks
+        .iter()
+        .map(Hook::shell_command)
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(commands) => commands,
+        Err(e) => {
+            log_warn!(
+                "[SDB::Engine] {} Skipping hooks for {}: {}",
+                installer_prefix,
+                tool_entry.name.yellow(),
+                e
+            );
+            return None;
+        }
+    };
+
     log_info!(
         "[SDB::Engine] {} Executing {} post hook(s) for {}",
         installer_prefix,
@@ -106,7 +206,7 @@ pub fn execute_post_installation_hooks(
 
     match execute_hooks(
         installer_prefix,
-        post_install_hooks,
+        &commands,
         working_directory,
         &tool_entry.name,
     ) {
@@ -129,3 +229,39 @@ pub fn execute_post_installation_hooks(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_refresh_tool_metadata_copies_notes_and_owner() {
+        let mut state = DevBoxState::default();
+        state.tools.insert(
+            "rg".to_string(),
+            serde_json::from_value(json!({
+                "version": "14.1.0",
+                "install_path": "/usr/local/bin/rg",
+                "installed_by_devbox": true,
+                "install_method": "github",
+                "package_type": "binary",
+            }))
+            .unwrap(),
+        );
+        let tools = vec![ToolEntry {
+            owner: Some("platform-team".to_string()),
+            notes: Some("pinned for glibc 2.31".to_string()),
+            ..serde_yaml::from_str("name: rg\nsource: github").unwrap()
+        }];
+
+        assert!(refresh_tool_metadata(&tools, &mut state));
+        assert_eq!(state.tools["rg"].owner.as_deref(), Some("platform-team"));
+        assert_eq!(
+            state.tools["rg"].notes.as_deref(),
+            Some("pinned for glibc 2.31")
+        );
+        // Nothing left to copy, so the state file does not need saving again
+        assert!(!refresh_tool_metadata(&tools, &mut state));
+    }
+}
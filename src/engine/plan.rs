@@ -0,0 +1,551 @@
+//! Structured plan of the changes `setup-devbox now` would make.
+//!
+//! `setup-devbox plan` prints the plan for review and can save it as JSON. `now --from-plan`
+//! then executes exactly the saved actions, and refuses to run when the machine or the
+//! configuration changed since the plan was made, so an approved plan is never applied to a
+//! different starting point.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ParsedConfigs, load_configs};
+use crate::core::platform::{self, detect_architecture, detect_os};
+use crate::core::timestamps::current_timestamp;
+use crate::engine::installation::planner::InstallationPlanner;
+use crate::engine::installers::shell_run_commands::pending_shell_changes;
+use crate::engine::installers::simulated;
+use crate::schemas::common::ArchFallback;
+use crate::schemas::config_manager::ConfigurationManagerProcessor;
+use crate::schemas::path_resolver::PathResolver;
+use crate::schemas::tools_enums::ToolAction;
+use crate::schemas::tools_types::{InstallationConfiguration, ToolEntry};
+use crate::settings::file_associations::pending_file_associations;
+use crate::settings::launch_agents::pending_launch_agents;
+use crate::state::manager::load_or_initialize_state;
+
+/// Version of the plan file format.
+const PLAN_FORMAT_VERSION: u32 = 1;
+
+/// What a planned action changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PlanKind {
+    Tool,
+    Font,
+    Shell,
+    Setting,
+    LaunchAgent,
+    FileAssociation,
+}
+
+/// How a planned action changes it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PlanOperation {
+    /// Install a tool or font that is not installed yet.
+    Install,
+    /// Install a different version of a tool.
+    Update,
+    /// Re-apply the configuration file of a tool, keeping its version.
+    UpdateConfiguration,
+    /// Write missing run commands and aliases to a shell RC file.
+    Render,
+    /// Write a system setting, launch agent or file association.
+    Apply,
+}
+
+/// A single change in a plan.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedAction {
+    pub kind: PlanKind,
+    pub action: PlanOperation,
+    /// Tool or font name, shell name, `domain.key` of a setting, label of a launch agent, or
+    /// content type of a file association.
+    pub name: String,
+    /// Current version or value, for updates.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    /// Version or value after the change.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    /// File the change is written to (shell RC files).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Number of lines added (shell RC files).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lines: Option<usize>,
+}
+
+/// An ordered list of changes, in the order `now` applies them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Plan {
+    pub format_version: u32,
+    /// When the plan was made (RFC 3339).
+    pub created_at: String,
+    /// Whether the plan was made with `--update-latest`.
+    #[serde(default)]
+    pub update_latest: bool,
+    /// The `arch_fallback` policy the tools would be installed with.
+    #[serde(default)]
+    pub arch_fallback: ArchFallback,
+    pub actions: Vec<PlannedAction>,
+}
+
+impl Plan {
+    /// Works out what `now` would change, without changing anything.
+    pub fn build(paths: &PathResolver, update_latest: bool) -> Result<Self, String> {
+        let mut configs = load_configs(paths)?;
+        // Simulated runs only process tools
+        if simulated::is_active() {
+            configs.fonts = None;
+            configs.shell = None;
+            configs.settings = None;
+        }
+        let state = load_or_initialize_state(&paths.state_file().to_path_buf())?;
+        let mut actions = Vec::new();
+
+        if let Some(tools_config) = &configs.tools {
+            let configuration = InstallationConfiguration::new(tools_config, update_latest, true);
+            let planner =
+                InstallationPlanner::new(&configuration, ConfigurationManagerProcessor::new(paths));
+            let mut tools: Vec<&ToolEntry> = tools_config.tools.iter().collect();
+            tools.sort_by_key(|tool| tool.effective_phase());
+
+            for tool in tools {
+                let current = state.tools.get(&tool.name);
+                let (action, _) = planner.determine_required_action(tool, current);
+                let action = match action {
+                    ToolAction::Install => PlanOperation::Install,
+                    ToolAction::Update => PlanOperation::Update,
+                    ToolAction::UpdateConfigurationOnly => PlanOperation::UpdateConfiguration,
+                    ToolAction::Skip(_) | ToolAction::SkipConfigurationOnly(_) => continue,
+                };
+                actions.push(PlannedAction {
+                    kind: PlanKind::Tool,
+                    action,
+                    name: tool.name.clone(),
+                    from: current
+                        .filter(|_| action == PlanOperation::Update)
+                        .map(|state| state.version.clone()),
+                    to: tool.version.clone(),
+                    path: None,
+                    lines: None,
+                });
+            }
+        }
+
+        if let Some(fonts_config) = &configs.fonts {
+            for font in &fonts_config.fonts {
+                let (action, from) = match state.fonts.get(&font.name) {
+                    None => (PlanOperation::Install, None),
+                    Some(current) if font.pins_other_version(current) => {
+                        (PlanOperation::Update, Some(current.version.clone()))
+                    }
+                    Some(_) => continue,
+                };
+                actions.push(PlannedAction {
+                    kind: PlanKind::Font,
+                    action,
+                    name: font.name.clone(),
+                    from,
+                    to: font.version.clone().or_else(|| font.tag.clone()),
+                    path: None,
+                    lines: None,
+                });
+            }
+        }
+
+        if let Some(shell_config) = &configs.shell {
+            for (shell, rc_path, lines) in pending_shell_changes(shell_config) {
+                actions.push(PlannedAction {
+                    kind: PlanKind::Shell,
+                    action: PlanOperation::Render,
+                    name: shell,
+                    from: None,
+                    to: None,
+                    path: Some(rc_path.display().to_string()),
+                    lines: Some(lines),
+                });
+            }
+        }
+
+        // Settings are written with `defaults`, so they only change anything on macOS
+        if let Some(settings_config) = &configs.settings
+            && cfg!(target_os = "macos")
+        {
+            for entry in &settings_config.settings.macos {
+                let full_key = format!("{}.{}", entry.domain, entry.key);
+                let current = state.settings.get(&full_key);
+                if current
+                    .is_some_and(|s| s.value == entry.value && s.value_type == entry.value_type)
+                {
+                    continue;
+                }
+                actions.push(PlannedAction {
+                    kind: PlanKind::Setting,
+                    action: PlanOperation::Apply,
+                    name: full_key,
+                    from: current.map(|s| s.value.clone()),
+                    to: Some(entry.value.clone()),
+                    path: None,
+                    lines: None,
+                });
+            }
+        }
+
+        if let Some(settings_config) = &configs.settings {
+            for agent in pending_launch_agents(&settings_config.launch_agents, &state) {
+                actions.push(PlannedAction {
+                    kind: PlanKind::LaunchAgent,
+                    action: if state.launch_agents.contains_key(&agent.label) {
+                        PlanOperation::Apply
+                    } else {
+                        PlanOperation::Install
+                    },
+                    name: agent.label.clone(),
+                    from: None,
+                    to: None,
+                    path: None,
+                    lines: None,
+                });
+            }
+            for entry in pending_file_associations(&settings_config.file_associations, &state) {
+                actions.push(PlannedAction {
+                    kind: PlanKind::FileAssociation,
+                    action: PlanOperation::Apply,
+                    name: entry.content_type.clone(),
+                    from: state
+                        .file_associations
+                        .get(&entry.content_type)
+                        .map(|applied| applied.app.clone()),
+                    to: Some(entry.app.clone()),
+                    path: None,
+                    lines: None,
+                });
+            }
+        }
+
+        Ok(Plan {
+            format_version: PLAN_FORMAT_VERSION,
+            created_at: current_timestamp(),
+            update_latest,
+            arch_fallback: platform::arch_fallback(),
+            actions,
+        })
+    }
+
+    /// Reads a plan saved by `setup-devbox plan --out`.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("cannot read plan {}: {}", path.display(), e))?;
+        let plan: Plan = serde_json::from_str(&content)
+            .map_err(|e| format!("invalid plan {}: {}", path.display(), e))?;
+        if plan.format_version != PLAN_FORMAT_VERSION {
+            return Err(format!(
+                "plan {} has format version {}, expected {}",
+                path.display(),
+                plan.format_version,
+                PLAN_FORMAT_VERSION
+            ));
+        }
+        Ok(plan)
+    }
+
+    /// Writes the plan as JSON.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, content + "\n")
+            .map_err(|e| format!("cannot write plan {}: {}", path.display(), e))
+    }
+
+    /// Checks that the plan still describes what `now` would do, by planning again.
+    pub fn verify(&self, paths: &PathResolver) -> Result<(), String> {
+        let current = Plan::build(paths, self.update_latest)?;
+        if current.arch_fallback != self.arch_fallback {
+            return Err(format!(
+                "the plan made at {} is out of date: arch_fallback changed from {} to {}. Run 'setup-devbox plan' again.",
+                self.created_at, self.arch_fallback, current.arch_fallback
+            ));
+        }
+        match first_difference(&self.actions, &current.actions) {
+            None => Ok(()),
+            Some(difference) => Err(format!(
+                "the plan made at {} is out of date: {}. Run 'setup-devbox plan' again.",
+                self.created_at, difference
+            )),
+        }
+    }
+
+    /// Drops everything from `configs` that the plan does not change.
+    pub fn restrict(&self, configs: &mut ParsedConfigs) {
+        let planned = |kind: PlanKind| -> HashSet<&str> {
+            self.actions
+                .iter()
+                .filter(|action| action.kind == kind)
+                .map(|action| action.name.as_str())
+                .collect()
+        };
+
+        let tools = planned(PlanKind::Tool);
+        if let Some(tools_config) = configs.tools.as_mut() {
+            tools_config
+                .tools
+                .retain(|tool| tools.contains(tool.name.as_str()));
+        }
+        if tools.is_empty() {
+            configs.tools = None;
+        }
+
+        let fonts = planned(PlanKind::Font);
+        if let Some(fonts_config) = configs.fonts.as_mut() {
+            fonts_config
+                .fonts
+                .retain(|font| fonts.contains(font.name.as_str()));
+        }
+        if fonts.is_empty() {
+            configs.fonts = None;
+        }
+
+        let shells = planned(PlanKind::Shell);
+        if let Some(shell_config) = configs.shell.as_mut() {
+            shell_config.run_commands.shells = Some(
+                self.actions
+                    .iter()
+                    .filter(|action| action.kind == PlanKind::Shell)
+                    .map(|action| action.name.clone())
+                    .collect(),
+            );
+        }
+        if shells.is_empty() {
+            configs.shell = None;
+        }
+
+        let settings = planned(PlanKind::Setting);
+        let agents = planned(PlanKind::LaunchAgent);
+        let associations = planned(PlanKind::FileAssociation);
+        if let Some(settings_config) = configs.settings.as_mut() {
+            settings_config.settings.macos.retain(|entry| {
+                settings.contains(format!("{}.{}", entry.domain, entry.key).as_str())
+            });
+            // Linux settings are never planned
+            settings_config.settings.linux.clear();
+            settings_config
+                .launch_agents
+                .retain(|agent| agents.contains(agent.label.as_str()));
+            settings_config
+                .file_associations
+                .retain(|entry| associations.contains(entry.content_type.as_str()));
+        }
+        if settings.is_empty() && agents.is_empty() && associations.is_empty() {
+            configs.settings = None;
+        }
+    }
+
+    /// Prints the plan for review.
+    pub fn display(&self) {
+        if self.actions.is_empty() {
+            println!(
+                "{}",
+                "No changes. The devbox matches the configuration.".green()
+            );
+            return;
+        }
+
+        for action in &self.actions {
+            let (symbol, verb) = match action.action {
+                PlanOperation::Install => ("+".green(), "install"),
+                PlanOperation::Update => ("~".yellow(), "update"),
+                PlanOperation::UpdateConfiguration => ("~".yellow(), "update configuration of"),
+                PlanOperation::Render => ("↻".cyan(), "render"),
+                PlanOperation::Apply => ("~".yellow(), "apply"),
+            };
+            let kind = match action.kind {
+                PlanKind::Tool => "tool",
+                PlanKind::Font => "font",
+                PlanKind::Shell => "shell config",
+                PlanKind::Setting => "setting",
+                PlanKind::LaunchAgent => "launch agent",
+                PlanKind::FileAssociation => "file association",
+            };
+            let detail = match (&action.from, &action.to, &action.path) {
+                (_, _, Some(path)) => {
+                    format!(" ({}, {} line(s))", path, action.lines.unwrap_or_default())
+                }
+                (Some(from), Some(to), _) => format!(" {} → {}", from, to.bold()),
+                (None, Some(to), _) => format!("@{}", to),
+                _ => String::new(),
+            };
+            println!(
+                "  {} {} {} {}{}",
+                symbol,
+                verb,
+                kind,
+                action.name.bold(),
+                detail
+            );
+        }
+
+        let count = |operation: PlanOperation| {
+            self.actions
+                .iter()
+                .filter(|action| action.action == operation)
+                .count()
+        };
+        println!(
+            "\nPlan: {} to install, {} to update, {} to reconfigure, {} to render, {} to apply.",
+            count(PlanOperation::Install),
+            count(PlanOperation::Update),
+            count(PlanOperation::UpdateConfiguration),
+            count(PlanOperation::Render),
+            count(PlanOperation::Apply)
+        );
+
+        // Tell which release assets tools may be installed from on machines that can run more
+        // than one architecture.
+        let (os, arch) = (detect_os(), detect_architecture());
+        if let Some(fallback) = platform::fallback_arch(&os, &arch)
+            && self.actions.iter().any(|action| {
+                action.kind == PlanKind::Tool
+                    && matches!(
+                        action.action,
+                        PlanOperation::Install | PlanOperation::Update
+                    )
+            })
+        {
+            let usage = match self.arch_fallback {
+                ArchFallback::Never => format!("{arch} assets only"),
+                ArchFallback::PreferNative => {
+                    format!("{fallback} assets only when a release has no {arch} asset")
+                }
+                ArchFallback::AllowRosetta => format!("{arch} and {fallback} assets alike"),
+            };
+            println!(
+                "Architecture fallback: {} ({}).",
+                self.arch_fallback.to_string().cyan(),
+                usage
+            );
+        }
+    }
+}
+
+/// Describes the first action that differs between a saved and a fresh plan.
+fn first_difference(saved: &[PlannedAction], current: &[PlannedAction]) -> Option<String> {
+    let describe = |action: &PlannedAction| format!("{:?} {}", action.action, action.name);
+    for (index, action) in saved.iter().enumerate() {
+        match current.get(index) {
+            Some(now) if now == action => continue,
+            Some(now) => {
+                return Some(format!(
+                    "expected '{}', now '{}'",
+                    describe(action),
+                    describe(now)
+                ));
+            }
+            None => return Some(format!("'{}' is no longer needed", describe(action))),
+        }
+    }
+    current
+        .get(saved.len())
+        .map(|extra| format!("'{}' is now also needed", describe(extra)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn install(name: &str) -> PlannedAction {
+        PlannedAction {
+            kind: PlanKind::Tool,
+            action: PlanOperation::Install,
+            name: name.to_string(),
+            from: None,
+            to: Some("1.0.0".to_string()),
+            path: None,
+            lines: None,
+        }
+    }
+
+    #[test]
+    fn test_first_difference() {
+        let saved = vec![install("fd"), install("bat")];
+        assert_eq!(first_difference(&saved, &saved.clone()), None);
+        assert!(first_difference(&saved, &[install("fd")]).is_some());
+        assert!(first_difference(&saved, &[install("fd"), install("rg")]).is_some());
+        assert!(
+            first_difference(&[install("fd")], &[install("fd"), install("bat")])
+                .is_some_and(|d| d.contains("bat"))
+        );
+    }
+
+    fn configs_with_agents() -> ParsedConfigs {
+        ParsedConfigs {
+            tools: None,
+            settings: Some(
+                serde_yaml::from_str(
+                    "settings:\n  macos:\n    - domain: com.apple.dock\n      key: autohide\n      value: 'true'\n      type: bool\nlaunch_agents:\n  - label: com.example.sync\n    program_arguments: [sync]\n  - label: com.example.backup\n    program_arguments: [backup]\n",
+                )
+                .unwrap(),
+            ),
+            shell: None,
+            fonts: None,
+        }
+    }
+
+    #[test]
+    fn test_restrict_drops_unplanned_launch_agents() {
+        let agent = PlannedAction {
+            kind: PlanKind::LaunchAgent,
+            action: PlanOperation::Install,
+            ..install("com.example.sync")
+        };
+        let plan = Plan {
+            format_version: PLAN_FORMAT_VERSION,
+            created_at: String::new(),
+            update_latest: false,
+            arch_fallback: ArchFallback::default(),
+            actions: vec![agent],
+        };
+        let mut configs = configs_with_agents();
+        plan.restrict(&mut configs);
+        let settings = configs.settings.unwrap();
+        let labels: Vec<&str> = settings
+            .launch_agents
+            .iter()
+            .map(|agent| agent.label.as_str())
+            .collect();
+        assert_eq!(labels, ["com.example.sync"]);
+        assert!(settings.settings.macos.is_empty());
+
+        // A plan with only a setting applies no launch agent
+        let setting = PlannedAction {
+            kind: PlanKind::Setting,
+            action: PlanOperation::Apply,
+            ..install("com.apple.dock.autohide")
+        };
+        let plan = Plan {
+            actions: vec![setting],
+            ..plan
+        };
+        let mut configs = configs_with_agents();
+        plan.restrict(&mut configs);
+        let settings = configs.settings.unwrap();
+        assert!(settings.launch_agents.is_empty());
+        assert_eq!(settings.settings.macos.len(), 1);
+
+        let plan = Plan {
+            actions: Vec::new(),
+            ..plan
+        };
+        let mut configs = configs_with_agents();
+        plan.restrict(&mut configs);
+        assert!(configs.settings.is_none());
+    }
+}
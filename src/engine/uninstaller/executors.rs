@@ -51,6 +51,9 @@ pub struct RemovalSummary {
     /// Names of fonts that were successfully removed
     pub removed_fonts: Vec<String>,
 
+    /// Labels of launch agents that were successfully removed
+    pub removed_launch_agents: Vec<String>,
+
     /// Names of items that could not be found
     pub not_found_items: Vec<String>,
 
@@ -119,7 +122,8 @@ pub(crate) trait ToolUninstaller {
 /// Removes tools installed as standalone binaries from GitHub releases or direct URLs.
 ///
 /// These tools are typically single executable files downloaded and placed in a bin directory.
-/// Removal is straightforward: just delete the file at the recorded installation path.
+/// Removal is straightforward: just delete the file at the recorded installation path. Apps
+/// installed from a `.dmg` are recorded as their `.app` bundle, which is deleted as a whole.
 pub(crate) struct BinaryUninstaller;
 
 impl ToolUninstaller for BinaryUninstaller {
@@ -130,7 +134,14 @@ impl ToolUninstaller for BinaryUninstaller {
             path.display()
         );
 
-        if path.exists() {
+        if path.is_dir() && path.extension().is_some_and(|ext| ext == "app") {
+            fs::remove_dir_all(&path)
+                .map_err(|e| format!("Failed to remove app at {}: {}", path.display(), e))?;
+            log_info!(
+                "[SDB::Remove::Tool::Binary] Deleted: {}",
+                path.display().to_string().cyan()
+            );
+        } else if path.exists() {
             fs::remove_file(&path)
                 .map_err(|e| format!("Failed to remove binary at {}: {}", path.display(), e))?;
             log_info!(
@@ -393,7 +404,7 @@ impl ConfigurationCleaner {
     /// * `Err(String)` if the configuration directory could not be determined
     pub fn new(paths: &PathResolver) -> Result<Self, String> {
         log_debug!("[SDB::Remove::Config] Initializing ConfigurationCleaner");
-        let config_base = paths.configs_dir();
+        let config_base = paths.config_file_dir();
         log_debug!(
             "[SDB::Remove::Config] Using directory: {}",
             config_base.display()
@@ -14,9 +14,12 @@ use colored::Colorize;
 // =========================================================================== //
 //                              INTERNAL IMPORTS                               //
 // =========================================================================== //
+use crate::core::{app_bundle, shims};
+use crate::engine::installers::factory::InstallerFactory;
 use crate::engine::uninstaller::executors::{ItemToBeRemoved, RemovalResult, RemovalSummary};
 use crate::schemas::state_file::{DevBoxState, ToolState};
 use crate::schemas::{common::RemovalOrchestrator, path_resolver::PathResolver};
+use crate::state::receipts;
 use crate::{log_debug, log_error, log_info, log_warn};
 
 // =========================================================================== //
@@ -36,7 +39,7 @@ use crate::{log_debug, log_error, log_info, log_warn};
 ///
 /// The orchestrator follows the Orchestrator pattern, acting as a high-level
 /// coordinator that delegates specific tasks to specialized components:
-/// - ToolUninstaller implementations handle installation-specific removal
+/// - Registered installers handle installation-specific removal
 /// - ConfigurationCleaner handles YAML file manipulation
 /// - The orchestrator focuses on workflow and error handling
 impl<'a> RemovalOrchestrator<'a> {
@@ -57,62 +60,13 @@ impl<'a> RemovalOrchestrator<'a> {
         Ok(Self { state, cleaner })
     }
 
-    /// Selects the appropriate uninstaller based on the installation method.
-    ///
-    /// # Arguments
-    ///
-    /// * `installer` - Installation method string (e.g., "cargo", "pip", "github")
-    ///
-    /// # Returns
-    ///
-    /// * `Some(Box<dyn ToolUninstaller>)` - Appropriate uninstaller for the method
-    /// * `None` - If the installation method is not supported
-    ///
-    /// # Supported Installation Methods
-    ///
-    /// - **github/url**: Binary files downloaded from GitHub releases or URLs
-    /// - **cargo**: Rust packages installed via cargo
-    /// - **rustup**: Rust toolchains managed by rustup
-    /// - **go**: Go packages installed via go install
-    /// - **pip**: Python packages installed via pip3
-    /// - **uv**: Python tools installed via uv
-    /// - **brew**: Packages installed via Homebrew
-    fn get_uninstaller(
-        &self,
-        installer: &str,
-    ) -> Option<Box<dyn crate::engine::uninstaller::executors::ToolUninstaller>> {
-        match installer.to_lowercase().as_str() {
-            "github" | "url" => Some(Box::new(
-                crate::engine::uninstaller::executors::BinaryUninstaller,
-            )),
-            "cargo" => Some(Box::new(
-                crate::engine::uninstaller::executors::CargoUninstaller,
-            )),
-            "rustup" => Some(Box::new(
-                crate::engine::uninstaller::executors::RustupUninstaller,
-            )),
-            "go" => Some(Box::new(
-                crate::engine::uninstaller::executors::GoUninstaller,
-            )),
-            "pip" => Some(Box::new(
-                crate::engine::uninstaller::executors::PipUninstaller,
-            )),
-            "uv" => Some(Box::new(
-                crate::engine::uninstaller::executors::UvUninstaller,
-            )),
-            "brew" => Some(Box::new(
-                crate::engine::uninstaller::executors::BrewUninstaller,
-            )),
-            _ => None,
-        }
-    }
-
     /// Removes a tool from the system.
     ///
     /// This method handles the complete removal process:
     /// 1. Locates the tool in state (by name or alias)
-    /// 2. Executes the appropriate uninstaller
-    /// 3. Cleans up configuration files
+    /// 2. Executes the appropriate uninstaller (unless another tool now owns the
+    ///    executable) and removes the tool's shim and extra assets
+    /// 3. Cleans up configuration files and the files listed in the install receipt
     /// 4. Removes the tool from state
     /// 5. Removes the tool from configuration YAML
     ///
@@ -166,12 +120,44 @@ impl<'a> RemovalOrchestrator<'a> {
             uninstall_item.item_path.cyan()
         );
 
-        // Step 3: Execute the uninstallation
-        if let Err(e) = self.execute_tool_uninstallation(&uninstall_item) {
+        // Step 3: Execute the uninstallation, unless another tool took over the executable
+        // (`now --force` with a colliding `rename_to`)
+        if let Some(owner) = self.other_owner(&key, &tool_state.install_path) {
+            log_info!(
+                "[SDB::Remove::Tool] Keeping {}: it now belongs to {}",
+                tool_state.install_path.cyan(),
+                owner.cyan()
+            );
+        } else if let Err(e) = self.execute_tool_uninstallation(&uninstall_item) {
             log_error!("[SDB::Remove::Tool] Uninstallation failed: {}", e.red());
             return RemovalResult::Failed(e);
         }
 
+        // Step 3b: Remove the tool's shim, if one was generated
+        if let Some(shim) = &tool_state.shim
+            && let Err(e) = shims::remove_shim(shim)
+        {
+            log_warn!(
+                "[SDB::Remove::Tool] Shim cleanup warning: {}",
+                e.to_string().yellow()
+            );
+        }
+
+        // Step 3b: Remove the application bundle the tool's link pointed into
+        if let Some(bundle) = &tool_state.app_bundle {
+            match app_bundle::remove(bundle) {
+                Ok(()) => log_info!("[SDB::Remove::Tool] Deleted app: {}", bundle.path.cyan()),
+                Err(e) => log_warn!(
+                    "[SDB::Remove::Tool] Failed to remove app {}: {}",
+                    bundle.path,
+                    e.to_string().yellow()
+                ),
+            }
+        }
+
+        // Step 3c: Remove the extra release assets installed with the tool
+        self.remove_tool_assets(&tool_state, &key);
+
         // Step 4: Clean up configuration files
         if let Err(e) = self.remove_tool_configurations(&tool_state, &key) {
             log_warn!(
@@ -180,6 +166,11 @@ impl<'a> RemovalOrchestrator<'a> {
             );
         }
 
+        // Step 4b: Delete the remaining files recorded in the install receipt
+        for path in receipts::remove_created_files(self.state, &key) {
+            log_debug!("[SDB::Remove::Tool] Removed {}", path.display());
+        }
+
         // Step 5: Remove from state
         self.state.tools.remove(&key);
         log_debug!("[SDB::Remove] Removed from state: {}", key);
@@ -236,6 +227,54 @@ impl<'a> RemovalOrchestrator<'a> {
             })
     }
 
+    /// Finds another tool recording `path` as its executable or as one of its assets.
+    fn other_owner(&self, tool_name: &str, path: &str) -> Option<String> {
+        self.state
+            .tools
+            .iter()
+            .filter(|(name, _)| name.as_str() != tool_name)
+            .find(|(_, other)| {
+                other.install_path == path
+                    || other
+                        .assets
+                        .iter()
+                        .flatten()
+                        .any(|asset| asset.installed_paths.iter().any(|p| p == path))
+            })
+            .map(|(name, _)| name.clone())
+    }
+
+    /// Removes the files and directories written for a tool's extra release assets
+    /// (completions, man pages, data), keeping paths another tool also records.
+    ///
+    /// Failures are logged as warnings; the rest of the removal continues.
+    fn remove_tool_assets(&self, tool_state: &ToolState, tool_name: &str) {
+        for asset in tool_state.assets.iter().flatten() {
+            for installed in &asset.installed_paths {
+                let path = PathBuf::from(installed);
+                if !path.exists() || self.other_owner(tool_name, installed).is_some() {
+                    continue;
+                }
+                let result = if path.is_dir() {
+                    fs::remove_dir_all(&path)
+                } else {
+                    fs::remove_file(&path)
+                };
+                match result {
+                    Ok(()) => log_info!(
+                        "[SDB::Remove::Tool] Deleted asset: {}",
+                        path.display().to_string().cyan()
+                    ),
+                    Err(e) => log_warn!(
+                        "[SDB::Remove::Tool] Failed to remove asset {}: {}",
+                        path.display(),
+                        e.to_string().yellow()
+                    ),
+                }
+            }
+        }
+    }
+
     /// Executes the appropriate uninstaller for a tool.
     ///
     /// # Arguments
@@ -253,21 +292,22 @@ impl<'a> RemovalOrchestrator<'a> {
     /// but not treated as an error. This allows the rest of the cleanup process
     /// to continue (removing from state, cleaning configs, etc.)
     fn execute_tool_uninstallation(&self, uninstall_item: &ItemToBeRemoved) -> Result<(), String> {
-        // Normalize the source type to match our uninstaller keys
-        let installer_name =
-            ToolState::normalize_source_type(&uninstall_item.item_source.to_lowercase());
+        let installer_factory = InstallerFactory::new();
 
-        log_debug!(
-            "[SDB::Remove::Tool] Using uninstaller for: {}",
-            installer_name.cyan()
-        );
-
-        if let Some(uninstaller) = self.get_uninstaller(&installer_name) {
-            uninstaller.uninstall(uninstall_item)
+        if let Some(installer) =
+            installer_factory.get_installer_for_method(&uninstall_item.item_source)
+        {
+            log_debug!(
+                "[SDB::Remove::Tool] Using uninstaller for: {}",
+                installer.name().cyan()
+            );
+            installer
+                .uninstall(uninstall_item)
+                .map_err(|e| e.to_string())
         } else {
             log_warn!(
                 "[SDB::Remove::Tool] Unsupported installer '{}', skipping binary removal",
-                installer_name.yellow()
+                uninstall_item.item_source.yellow()
             );
             Ok(())
         }
@@ -391,6 +431,18 @@ impl RemovalSummary {
             has_output = true;
         }
 
+        // Display successfully removed launch agents
+        if !self.removed_launch_agents.is_empty() {
+            let items = self.removed_launch_agents.join(", ");
+            println!(
+                "Successfully removed {} launch agent(s): {} {}",
+                self.removed_launch_agents.len().to_string().green(),
+                items.green(),
+                "✓".green()
+            );
+            has_output = true;
+        }
+
         // Add spacing if there were successful removals and there are warnings/errors
         if has_output && (!self.not_found_items.is_empty() || !self.failed_removals.is_empty()) {
             println!();
@@ -0,0 +1,222 @@
+//! # Font Archive Inspection
+//!
+//! Backs `setup-devbox fonts info`: lists the font files inside a release archive together
+//! with the family and style recorded in each file's `name` table, so `install_only`
+//! patterns can be written before anything is installed.
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// A font file found inside a release archive.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FontFileInfo {
+    /// File name without the directories of the archive.
+    pub(crate) file: String,
+    /// Family name from the font's `name` table, if it could be read.
+    pub(crate) family: Option<String>,
+    /// Style (subfamily) name from the font's `name` table, if it could be read.
+    pub(crate) style: Option<String>,
+    /// Uncompressed size in bytes, i.e. the space the file takes once installed.
+    pub(crate) size: u64,
+}
+
+/// Lists the `.ttf`/`.otf` files of a zip archive, skipping hidden files like the installer does.
+pub(crate) fn inspect_zip(archive_path: &Path) -> io::Result<Vec<FontFileInfo>> {
+    let mut archive = zip::ZipArchive::new(File::open(archive_path)?).map_err(io::Error::other)?;
+    let mut fonts = Vec::new();
+
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index).map_err(io::Error::other)?;
+        if entry.is_dir() {
+            continue;
+        }
+        let file = match entry.name().rsplit('/').next() {
+            Some(file) if !file.starts_with('.') && is_font_file(file) => file.to_string(),
+            _ => continue,
+        };
+
+        let mut data = Vec::with_capacity(entry.size() as usize);
+        entry.read_to_end(&mut data)?;
+        let (family, style) = match parse_font_names(&data) {
+            Some((family, style)) => (Some(family), Some(style)),
+            None => (None, None),
+        };
+        fonts.push(FontFileInfo {
+            file,
+            family,
+            style,
+            size: entry.size(),
+        });
+    }
+
+    fonts.sort_by(|a, b| a.file.cmp(&b.file));
+    Ok(fonts)
+}
+
+fn is_font_file(file: &str) -> bool {
+    Path::new(file)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| matches!(ext.to_lowercase().as_str(), "ttf" | "otf"))
+}
+
+/// Reads the family and style names from an OpenType/TrueType font (the first font of a
+/// collection). Typographic names (IDs 16/17) are preferred over the legacy ones (1/2),
+/// and English Windows names over Macintosh ones.
+pub(crate) fn parse_font_names(data: &[u8]) -> Option<(String, String)> {
+    let mut offset = 0;
+    if data.get(0..4)? == b"ttcf" {
+        offset = read_u32(data, 12)? as usize;
+    }
+
+    let num_tables = read_u16(data, offset + 4)? as usize;
+    let name_table = (0..num_tables)
+        .map(|i| offset + 12 + i * 16)
+        .find(|&record| data.get(record..record + 4) == Some(b"name"))
+        .and_then(|record| read_u32(data, record + 8))? as usize;
+
+    let count = read_u16(data, name_table + 2)? as usize;
+    let strings = name_table + read_u16(data, name_table + 4)? as usize;
+
+    // (score, value) of the best candidate for each name ID we care about
+    let mut names: [Option<(u8, String)>; 4] = Default::default();
+    for i in 0..count {
+        let record = name_table + 6 + i * 12;
+        let platform = read_u16(data, record)?;
+        let encoding = read_u16(data, record + 2)?;
+        let language = read_u16(data, record + 4)?;
+        let slot = match read_u16(data, record + 6)? {
+            1 => 0,
+            2 => 1,
+            16 => 2,
+            17 => 3,
+            _ => continue,
+        };
+        let length = read_u16(data, record + 8)? as usize;
+        let start = strings + read_u16(data, record + 10)? as usize;
+        let Some(bytes) = data.get(start..start + length) else {
+            continue;
+        };
+
+        let (score, value) = match (platform, encoding) {
+            (3, 1 | 10) | (0, _) => {
+                let units: Vec<u16> = bytes
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                    .collect();
+                let score = if platform == 3 && language == 0x409 {
+                    3
+                } else {
+                    2
+                };
+                (score, String::from_utf16_lossy(&units))
+            }
+            (1, 0) => (1, bytes.iter().map(|&b| b as char).collect()),
+            _ => continue,
+        };
+        if names[slot].as_ref().is_none_or(|(best, _)| score > *best) {
+            names[slot] = Some((score, value.trim().to_string()));
+        }
+    }
+
+    let [family, style, typographic_family, typographic_style] =
+        names.map(|name| name.map(|(_, value)| value));
+    Some((
+        typographic_family.or(family)?,
+        typographic_style
+            .or(style)
+            .unwrap_or_else(|| "Regular".to_string()),
+    ))
+}
+
+fn read_u16(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn read_u32(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+/// Formats a byte count for display, e.g. `2.4 MB`.
+pub(crate) fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a minimal font containing only a `name` table with Windows (UTF-16BE) records.
+    fn font_with_names(names: &[(u16, &str)]) -> Vec<u8> {
+        let mut strings = Vec::new();
+        let mut records = Vec::new();
+        for (name_id, value) in names {
+            let encoded: Vec<u8> = value.encode_utf16().flat_map(u16::to_be_bytes).collect();
+            for field in [
+                3u16,
+                1,
+                0x409,
+                *name_id,
+                encoded.len() as u16,
+                strings.len() as u16,
+            ] {
+                records.extend(field.to_be_bytes());
+            }
+            strings.extend(encoded);
+        }
+
+        let mut name_table = Vec::new();
+        for field in [0u16, names.len() as u16, (6 + records.len()) as u16] {
+            name_table.extend(field.to_be_bytes());
+        }
+        name_table.extend(records);
+        name_table.extend(strings);
+
+        let mut font = vec![0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+        font.extend(b"name");
+        font.extend(0u32.to_be_bytes());
+        font.extend(28u32.to_be_bytes());
+        font.extend((name_table.len() as u32).to_be_bytes());
+        font.extend(name_table);
+        font
+    }
+
+    #[test]
+    fn test_parse_font_names_prefers_typographic_names() {
+        let font = font_with_names(&[
+            (1, "FiraCode Nerd Font Light"),
+            (2, "Regular"),
+            (16, "FiraCode Nerd Font"),
+            (17, "Light"),
+        ]);
+        assert_eq!(
+            parse_font_names(&font),
+            Some(("FiraCode Nerd Font".to_string(), "Light".to_string()))
+        );
+
+        let font = font_with_names(&[(1, "Hack"), (2, "Bold")]);
+        assert_eq!(
+            parse_font_names(&font),
+            Some(("Hack".to_string(), "Bold".to_string()))
+        );
+        assert_eq!(parse_font_names(b"not a font"), None);
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(2_516_582), "2.4 MB");
+    }
+}
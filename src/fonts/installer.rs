@@ -9,18 +9,21 @@ use crate::{log_debug, log_error, log_info};
 // The path to the font installer module will change because it's now under `installers`.
 // It was already `crate::engine::installers::fonts;` so this line remains the same as `font_installer.rs` calls it.
 // Imports the `fonts` module, which contains the actual font installation logic.
+use crate::core::licenses;
 use crate::engine::installers::fonts;
 // Imports `DevBoxState` for application state management and `FontConfig` for font-specific configuration.
 use crate::schemas::fonts::FontConfig;
-use crate::schemas::state_file::DevBoxState;
+use crate::schemas::state_file::{DevBoxState, FontReplacement};
 // Imports the function to save the `DevBoxState`.
 use crate::state::manager::save_devbox_state;
 
 /// Installs fonts based on the provided configuration and updates the application state.
 ///
 /// This function iterates through each font defined in `fonts_cfg`, checks if it's
-/// already installed according to `state`, and delegates to the `fonts` installer
-/// for new fonts. It also handles state persistence.
+/// already installed according to `state`, and hands the new fonts to the `fonts` installer
+/// together, which downloads their archives concurrently. Installed fonts whose entry now pins
+/// another `tag` or `version` are reinstalled, replacing the files of the previous version.
+/// It also handles state persistence.
 ///
 /// # Arguments
 /// * `fonts_cfg`: A `FontConfig` struct containing the list of fonts to install.
@@ -38,6 +41,9 @@ pub fn install_fonts(fonts_cfg: FontConfig, state: &mut DevBoxState, state_path_
     // A mutable vector named `skipped_fonts` to store the names of fonts
     // that are found to be already installed and thus skipped from re-installation.
     let mut skipped_fonts: Vec<String> = Vec::new();
+    // Fonts to install, with the license found for each and the state of the version being
+    // replaced, if any.
+    let mut pending = Vec::new();
 
     // Iterate over each `font` definition in the `fonts_cfg`.
     // `&fonts_cfg.fonts` creates an immutable reference to the vector of font configurations,
@@ -49,50 +55,59 @@ pub fn install_fonts(fonts_cfg: FontConfig, state: &mut DevBoxState, state_path_
         // Check if the font is already present in the `DevBoxState`.
         // `!state.fonts.contains_key(&font.name)` evaluates to `true` if the font's name
         // is NOT found as a key in the `state.fonts` HashMap, indicating that it needs to be installed.
-        if !state.fonts.contains_key(&font.name) {
-            // Print a newline character to the standard output for better console formatting
-            // and visual separation between log blocks.
-            println!("\n");
-            // Print a separator line to the standard error output for strong visual clarity,
-            // signaling the start of a new font installation block. The line is colored bright blue.
-            eprintln!("{}", "==============================================================================================".bright_blue());
+        // An installed font whose entry pins another version is updated in place.
+        let previous = state
+            .fonts
+            .get(&font.name)
+            .filter(|font_state| font.pins_other_version(font_state))
+            .cloned();
+        if !state.fonts.contains_key(&font.name) || previous.is_some() {
             // Log an informative message to the user about the specific font being installed.
             // The font's name is displayed in bold cyan for emphasis.
-            log_info!("[SDB::Fonts] Installing {}...", font.name.bold().cyan());
-            // Call the actual font installation logic from the `fonts` installer module.
-            // `fonts::install(font)` is expected to perform the system-level font installation.
-            // It returns an `Option<FontState>`: `Some(font_state)` on success with the installed font's state, or `None` on failure.
-            if let Some(font_state) = fonts::install(font) {
-                // If `install` returns `Some(font_state)`, it means the font was successfully installed.
-                // Insert the new font's state into the `DevBoxState`'s `fonts` HashMap.
-                // `font_state.name.clone()` is used as the key to store the `font_state` value.
-                state.fonts.insert(font_state.name.clone(), font_state);
-                // Set the `fonts_updated` flag to `true` to indicate that a change occurred in the state,
-                // which will trigger a state save later.
-                fonts_updated = true;
-                // Log a success message for the font installation, displaying the font's name in bold green.
-                log_info!(
-                    "[SDB::Fonts] Successfully installed {}.",
-                    font.name.bold().green()
-                );
-                // Print another separator line to the standard error output, in bright blue,
-                // to visually close the font installation block.
-                eprintln!("{}", "===============================================================================================".bright_blue());
-                println!("\n"); // Print a newline for additional visual spacing.
-            } else {
-                // If `fonts::install(font)` returned `None`, it indicates that the font installation failed.
-                // Log an error message, prompting the user to review earlier logs for more specific
-                // details about why the installation might have failed.
-                log_error!(
-                    "[SDB::Fonts] Failed to install font: {}. Please review previous logs for specific errors during installation.",
-                    font.name.bold().red() // Display the failed font's name in bold red.
-                );
+            match &previous {
+                Some(font_state) => log_info!(
+                    "[SDB::Fonts] Updating {} from {} to {}...",
+                    font.name.bold().cyan(),
+                    font_state.version.yellow(),
+                    font.version
+                        .as_ref()
+                        .or(font.tag.as_ref())
+                        .map_or("", String::as_str)
+                        .green()
+                ),
+                None => log_info!("[SDB::Fonts] Installing {}...", font.name.bold().cyan()),
             }
+            // Look up the font's license and ask for acceptance where required.
+            let license = font
+                .repo
+                .as_deref()
+                .or(font.variant.as_ref().map(|_| fonts::NERD_FONTS_REPO))
+                .and_then(licenses::fetch_github_license);
+            let license = if font.requires_license_acceptance == Some(true) {
+                match licenses::ensure_accepted(&font.name, license, None) {
+                    Ok(license) => Some(license),
+                    Err(e) => {
+                        log_error!("[SDB::Fonts] Skipping {}: {}", font.name.bold().red(), e);
+                        continue;
+                    }
+                }
+            } else {
+                license
+            };
+            pending.push((font, license, previous));
         } else {
             // This block is executed if `!state.fonts.contains_key(&font.name)` is `false`,
             // meaning the font is already recorded in the `DevBoxState`.
             // Add the current font's name to the `skipped_fonts` list.
             skipped_fonts.push(font.name.clone());
+            // Keep documentation-only metadata in sync without reinstalling.
+            if let Some(font_state) = state.fonts.get_mut(&font.name)
+                && (font_state.notes != font.notes || font_state.owner != font.owner)
+            {
+                font_state.notes = font.notes.clone();
+                font_state.owner = font.owner.clone();
+                fonts_updated = true;
+            }
             // Log a debug message indicating that the font was skipped because it was already found
             // in the state, displaying its name in blue.
             log_debug!(
@@ -102,6 +117,90 @@ pub fn install_fonts(fonts_cfg: FontConfig, state: &mut DevBoxState, state_path_
         }
     }
 
+    // Call the actual font installation logic from the `fonts` installer module.
+    // `fonts::install_all` returns one `Option<FontState>` per font, in order: `Some(font_state)`
+    // on success with the installed font's state, or `None` on failure.
+    let entries: Vec<_> = pending
+        .iter()
+        .map(|(font, _, previous)| (*font, previous.as_ref()))
+        .collect();
+    let installing = !entries.is_empty();
+    if installing {
+        // Print a separator line to the standard error output for strong visual clarity,
+        // signaling the start of the font installation block. The line is colored bright blue.
+        println!("\n");
+        eprintln!("{}", "==============================================================================================".bright_blue());
+    }
+    let results = fonts::install_all(&entries);
+    for ((font, license, previous), result) in pending.into_iter().zip(results) {
+        if let Some(mut font_state) = result {
+            font_state.license = license;
+            // Insert the new font's state into the `DevBoxState`'s `fonts` map. Entries of the
+            // same font selecting different files from its archive are merged into one; an
+            // update keeps the files of the other entries and drops the replaced ones.
+            if previous.is_some()
+                && let Some(existing) = state.fonts.get(&font_state.name)
+            {
+                let removed = font_state
+                    .replaced
+                    .as_ref()
+                    .map_or(&[][..], |replaced| &replaced.removed_files[..]);
+                let kept: Vec<String> = existing
+                    .files
+                    .iter()
+                    .filter(|file| {
+                        !removed.contains(file)
+                            && !font_state.files.contains(file)
+                            && !fonts::selects_font_file(font, file)
+                    })
+                    .cloned()
+                    .collect();
+                font_state.files.extend(kept);
+                // Another entry of the font was already updated earlier in this run
+                if existing.version == font_state.version {
+                    merge_replacement(&mut font_state.replaced, existing.replaced.clone());
+                }
+                font_state.disk_usage_bytes = fonts::files_disk_usage(&font_state);
+                state.fonts.insert(font_state.name.clone(), font_state);
+            } else if let Some(existing) = state.fonts.get_mut(&font_state.name) {
+                for file in font_state.files {
+                    if !existing.files.contains(&file) {
+                        existing.files.push(file);
+                    }
+                }
+                existing.disk_usage_bytes = existing
+                    .disk_usage_bytes
+                    .zip(font_state.disk_usage_bytes)
+                    .map(|(a, b)| a + b);
+                merge_replacement(&mut existing.replaced, font_state.replaced);
+            } else {
+                state.fonts.insert(font_state.name.clone(), font_state);
+            }
+            // Set the `fonts_updated` flag to `true` to indicate that a change occurred in the state,
+            // which will trigger a state save later.
+            fonts_updated = true;
+            // Log a success message for the font installation, displaying the font's name in bold green.
+            log_info!(
+                "[SDB::Fonts] Successfully installed {}.",
+                font.name.bold().green()
+            );
+        } else {
+            // A `None` result indicates that the font installation failed.
+            // Log an error message, prompting the user to review earlier logs for more specific
+            // details about why the installation might have failed.
+            log_error!(
+                "[SDB::Fonts] Failed to install font: {}. Please review previous logs for specific errors during installation.",
+                font.name.bold().red() // Display the failed font's name in bold red.
+            );
+        }
+    }
+    if installing {
+        // Print another separator line to the standard error output, in bright blue,
+        // to visually close the font installation block.
+        eprintln!("{}", "===============================================================================================".bright_blue());
+        println!("\n"); // Print a newline for additional visual spacing.
+    }
+
     // After iterating through all fonts in `fonts_cfg`,
     // this block checks if any fonts were skipped.
     if !skipped_fonts.is_empty() {
@@ -150,3 +249,23 @@ pub fn install_fonts(fonts_cfg: FontConfig, state: &mut DevBoxState, state_path_
     // Debug log to indicate successful exit from the `install_fonts` function.
     log_debug!("[SDB::Fonts] Exiting install_fonts() function.");
 }
+
+/// Adds the files removed by `other` to the replacement recorded in `into`.
+fn merge_replacement(into: &mut Option<FontReplacement>, other: Option<FontReplacement>) {
+    let Some(other) = other else {
+        return;
+    };
+    match into {
+        Some(replacement) => {
+            for file in other.removed_files {
+                if !replacement.removed_files.contains(&file) {
+                    replacement.removed_files.push(file);
+                }
+            }
+            if replacement.from_version.is_none() {
+                replacement.from_version = other.from_version;
+            }
+        }
+        None => *into = Some(other),
+    }
+}
@@ -1,2 +1,3 @@
+pub(crate) mod info;
 pub(crate) mod installer;
 pub(crate) mod uninstaller;
@@ -11,6 +11,7 @@ use colored::Colorize;
 // =========================================================================== //
 //                              INTERNAL IMPORTS                               //
 // =========================================================================== //
+use crate::engine::installers::fonts::{is_writable_dir, run_sudo};
 use crate::engine::uninstaller::executors::RemovalResult;
 use crate::schemas::common::RemovalOrchestrator;
 use crate::schemas::path_resolver::PathResolver;
@@ -90,16 +91,17 @@ impl<'a> RemovalOrchestrator<'a> {
     ///
     /// # Implementation Details
     ///
-    /// Font files are stored in a system fonts directory (typically ~/Library/Fonts
-    /// on macOS). This method:
+    /// Font files are stored in the fonts directory of the font's scope (~/Library/Fonts, or
+    /// /Library/Fonts for `scope: system`, where they are removed with sudo). This method:
     /// 1. Locates the fonts directory
     /// 2. Searches for all .ttf files containing the font name
     /// 3. Removes each matching file
     ///
     /// If no font files are found, a warning is logged but this is not an error.
     fn remove_font_files(&self, font_state: &FontState) -> Result<(), String> {
-        let fonts_dir = PathResolver::get_font_installation_dir()
+        let fonts_dir = PathResolver::font_installation_dir(font_state.scope.unwrap_or_default())
             .map_err(|e| format!("Failed to get fonts directory: {e}"))?;
+        let use_sudo = !is_writable_dir(&fonts_dir);
 
         log_debug!("[SDB::Remove::Font] Searching in: {}", fonts_dir.display());
 
@@ -136,7 +138,12 @@ impl<'a> RemovalOrchestrator<'a> {
                     })
                     .unwrap_or(false)
             {
-                fs::remove_file(&path)
+                let removed = if use_sudo {
+                    run_sudo(&["rm".as_ref(), "-f".as_ref(), path.as_os_str()])
+                } else {
+                    fs::remove_file(&path)
+                };
+                removed
                     .map_err(|e| format!("Failed to remove font file {}: {}", path.display(), e))?;
                 log_info!(
                     "[SDB::Remove::Font] Deleted: {}",
@@ -0,0 +1,71 @@
+//! # Setup DevBox - Library Crate
+//!
+//! `setup-devbox` can be embedded in other Rust tools (company bootstrap CLIs, GUIs,
+//! editor integrations) that want to provision a machine programmatically instead of
+//! shelling out to the binary.
+//!
+//! The supported, stable surface is the facade re-exported at the crate root:
+//!
+//! - [`load_config`]: Parse `config.yaml` (or a single configuration file)
+//! - [`plan`]: Compute what `setup-devbox now` would do for each tool, without changing anything
+//! - [`apply`]: Run the full `now` pipeline (tools, fonts, shell, settings)
+//! - [`apply_tools`]: Install or update only selected tools
+//! - [`load_state`] / [`save_state`]: Read and write `state.json`
+//! - [`simulate`]: Replace the installers with deterministic fakes, for tests of automation
+//!
+//! ```no_run
+//! use setup_devbox::{ApplyOptions, PathResolver};
+//!
+//! let paths = PathResolver::new(None, None).expect("resolve paths");
+//! for tool in setup_devbox::plan(&paths, false).expect("load configuration") {
+//!     println!("{}: {:?}", tool.name, tool.action);
+//! }
+//! let outcome = setup_devbox::apply(&paths, &ApplyOptions::default()).expect("apply");
+//! println!("failed: {:?}", outcome.failed);
+//! ```
+//!
+//! Broken configuration or state files are reported as an [`Error`]; the facade never exits
+//! the process.
+//!
+//! The remaining modules are what the `setup-devbox` binary is built from. They are public
+//! so the binary can use them, but they are internal and may change between releases.
+
+// ============================================================================
+// MODULE DECLARATIONS
+// ============================================================================
+
+/// Stable facade for embedding setup-devbox
+mod api;
+
+#[doc(hidden)]
+pub mod cli;
+#[doc(hidden)]
+pub mod commands;
+#[doc(hidden)]
+pub mod config;
+#[doc(hidden)]
+pub mod core;
+#[doc(hidden)]
+pub mod engine;
+#[doc(hidden)]
+pub mod fonts;
+#[doc(hidden)]
+pub mod logger;
+#[doc(hidden)]
+pub mod schemas;
+#[doc(hidden)]
+pub mod settings;
+#[doc(hidden)]
+pub mod shell;
+#[doc(hidden)]
+pub mod state;
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+pub use api::{
+    ApplyOptions, ApplyOutcome, DevBoxState, Error, ParsedConfigs, PathResolver, PlannedTool,
+    ToolAction, ToolEntry, ToolState, apply, apply_tools, load_config, load_state, plan,
+    save_state, simulate,
+};
@@ -1,11 +1,20 @@
 // This file implements the application's logging system using tracing.
 // It provides macros for different log levels (INFO, WARN, ERROR, DEBUG)
 // and handles conditional output via tracing-subscriber.
+// Repeated warnings are only printed as warnings the first time (later ones are debug
+// messages) and are counted, so a run can end with a summary of what it warned about.
 
+use crate::cli::type_enums::ColorChoice;
 use colored::Colorize;
+use std::collections::VecDeque;
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::sync::{Mutex, OnceLock};
 use tracing::field::{Field, Visit};
 use tracing::{Event, Subscriber};
 pub use tracing::{debug, error, info, warn};
+use tracing_subscriber::fmt::MakeWriter;
 use tracing_subscriber::fmt::format::Writer;
 use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
 use tracing_subscriber::registry::LookupSpan;
@@ -18,10 +27,17 @@ macro_rules! log_info {
     ($($arg:tt)*) => ($crate::logger::info!($($arg)*));
 }
 
-// `log_warn!` for non-critical issues or noteworthy conditions.
+// `log_warn!` for non-critical issues or noteworthy conditions. Repeats of a warning are
+// logged at debug level, with how often it was logged so far.
 #[macro_export]
 macro_rules! log_warn {
-    ($($arg:tt)*) => ($crate::logger::warn!($($arg)*));
+    ($($arg:tt)*) => {{
+        let message = format!($($arg)*);
+        match $crate::logger::record_warning(&message) {
+            1 => $crate::logger::warn!("{}", message),
+            occurrences => $crate::logger::debug!("{} (x{})", message, occurrences),
+        }
+    }};
 }
 
 // `log_error!` for critical errors requiring immediate attention.
@@ -58,16 +74,13 @@ where
             tracing::Level::ERROR => "[ERROR]".bright_red(),
         };
 
-        // Write level
-        write!(writer, "{} ", level_str)?;
-
-        // Write message using custom visitor
-        let mut visitor = MessageVisitor {
-            writer: &mut writer,
-        };
-        event.record(&mut visitor);
+        // Render the message using custom visitor
+        let mut message = String::new();
+        event.record(&mut MessageVisitor {
+            writer: &mut message,
+        });
 
-        writeln!(writer)
+        writeln!(writer, "{} {}", level_str, message)
     }
 }
 
@@ -86,6 +99,179 @@ impl<'a> Visit for MessageVisitor<'a> {
     }
 }
 
+/// Distinct warnings of this run with how often each was logged, in first-seen order.
+struct WarningLog {
+    counts: Mutex<Vec<(String, usize)>>,
+}
+
+impl WarningLog {
+    const fn new() -> Self {
+        WarningLog {
+            counts: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Counts one occurrence of `message` and returns how often it was logged so far.
+    fn record(&self, message: &str) -> usize {
+        let mut counts = self.counts.lock().unwrap();
+        match counts.iter_mut().find(|(seen, _)| seen == message) {
+            Some((_, count)) => {
+                *count += 1;
+                *count
+            }
+            None => {
+                counts.push((message.to_string(), 1));
+                1
+            }
+        }
+    }
+
+    fn snapshot(&self) -> Vec<(String, usize)> {
+        self.counts.lock().unwrap().clone()
+    }
+}
+
+static WARNINGS: WarningLog = WarningLog::new();
+
+/// Counts one occurrence of a warning and returns how often it was logged in this run.
+pub fn record_warning(message: &str) -> usize {
+    WARNINGS.record(message)
+}
+
+/// Forgets the warnings of the previous run. Called when a run starts.
+pub fn reset_warnings() {
+    WARNINGS.counts.lock().unwrap().clear();
+}
+
+/// Number of recent log lines kept for clients that attach after they were logged.
+const CAPTURE_HISTORY: usize = 500;
+
+static CAPTURE: OnceLock<LogCapture> = OnceLock::new();
+
+/// Whether log lines are written to stderr. Cleared while a full-screen UI owns the terminal.
+static TERMINAL_OUTPUT: AtomicBool = AtomicBool::new(true);
+
+/// In-process copy of the log output, used by `setup-devbox daemon` to stream logs to
+/// its clients. Nothing is captured until [`enable_capture`] is called.
+pub struct LogCapture {
+    recent: Mutex<VecDeque<String>>,
+    subscribers: Mutex<Vec<Sender<String>>>,
+}
+
+impl LogCapture {
+    /// Returns a receiver for every log line written from now on.
+    pub fn subscribe(&self) -> Receiver<String> {
+        let (sender, receiver) = channel();
+        self.subscribers.lock().unwrap().push(sender);
+        receiver
+    }
+
+    /// Returns the most recent log lines, oldest first.
+    pub fn recent(&self) -> Vec<String> {
+        self.recent.lock().unwrap().iter().cloned().collect()
+    }
+
+    fn publish(&self, line: String) {
+        {
+            let mut recent = self.recent.lock().unwrap();
+            if recent.len() == CAPTURE_HISTORY {
+                recent.pop_front();
+            }
+            recent.push_back(line.clone());
+        }
+        // Receivers that were dropped are unsubscribed here
+        self.subscribers
+            .lock()
+            .unwrap()
+            .retain(|subscriber| subscriber.send(line.clone()).is_ok());
+    }
+}
+
+/// Starts capturing log lines in memory, in addition to writing them to stderr.
+pub fn enable_capture() -> &'static LogCapture {
+    CAPTURE.get_or_init(|| LogCapture {
+        recent: Mutex::new(VecDeque::new()),
+        subscribers: Mutex::new(Vec::new()),
+    })
+}
+
+/// Enables or disables writing log lines to stderr; captured lines are unaffected.
+pub fn set_terminal_output(enabled: bool) {
+    TERMINAL_OUTPUT.store(enabled, Ordering::Relaxed);
+}
+
+/// Writes each log event to stderr and, when capture is enabled, hands its lines to
+/// [`LogCapture`] once the event is complete.
+struct TeeWriter {
+    buffer: Vec<u8>,
+}
+
+impl Write for TeeWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if CAPTURE.get().is_some() {
+            self.buffer.extend_from_slice(buf);
+        }
+        if TERMINAL_OUTPUT.load(Ordering::Relaxed) {
+            std::io::stderr().write(buf)
+        } else {
+            Ok(buf.len())
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        std::io::stderr().flush()
+    }
+}
+
+impl Drop for TeeWriter {
+    fn drop(&mut self) {
+        if let Some(capture) = CAPTURE.get() {
+            for line in String::from_utf8_lossy(&self.buffer).lines() {
+                capture.publish(line.to_string());
+            }
+        }
+    }
+}
+
+struct TeeMakeWriter;
+
+impl<'a> MakeWriter<'a> for TeeMakeWriter {
+    type Writer = TeeWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        TeeWriter { buffer: Vec::new() }
+    }
+}
+
+/// Decides once for the whole run whether log lines and other `colored` output carry ANSI
+/// colors, and overrides `colored`'s own detection accordingly.
+///
+/// With `auto`, output is colored only when stderr (where logs go) and stdout are terminals,
+/// `TERM` is not `dumb` and `NO_COLOR` is not set; `CLICOLOR_FORCE` turns colors on anyway.
+pub fn configure_color(choice: ColorChoice) {
+    let non_empty = |name: &str| std::env::var_os(name).is_some_and(|value| !value.is_empty());
+    let enabled = color_enabled(
+        choice,
+        non_empty("NO_COLOR"),
+        non_empty("CLICOLOR_FORCE")
+            && std::env::var_os("CLICOLOR_FORCE").is_some_and(|value| value != "0"),
+        std::io::stderr().is_terminal()
+            && std::io::stdout().is_terminal()
+            && std::env::var_os("TERM").is_none_or(|term| term != "dumb"),
+    );
+    colored::control::set_override(enabled);
+}
+
+/// Applies the color policy: an explicit choice wins, then `NO_COLOR`, then
+/// `CLICOLOR_FORCE`, then whether the output is a terminal.
+fn color_enabled(choice: ColorChoice, no_color: bool, force: bool, terminal: bool) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => !no_color && (force || terminal),
+    }
+}
+
 /// Initializes the logger, setting the global debug mode.
 /// This function should be called once at application startup.
 ///
@@ -101,6 +287,66 @@ pub fn init(debug: bool) {
     tracing_subscriber::fmt()
         .with_max_level(filter)
         .event_format(SimpleFormatter)
-        .with_writer(std::io::stderr)
+        .with_writer(TeeMakeWriter)
         .init();
 }
+
+/// Prints the warnings of this run, with how often each was logged, when any of them was
+/// repeated and therefore not printed every time. Called once a command has finished.
+pub fn print_warnings_summary() {
+    let warnings = WARNINGS.snapshot();
+    if warnings.iter().all(|(_, count)| *count == 1) {
+        return;
+    }
+    let total: usize = warnings.iter().map(|(_, count)| count).sum();
+    eprintln!();
+    eprintln!(
+        "{} {} warning(s), {} distinct:",
+        "Warnings summary:".bright_yellow().bold(),
+        total,
+        warnings.len()
+    );
+    for (message, count) in warnings {
+        eprintln!("  {:>4}x  {}", count, message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_policy_prefers_explicit_choice_then_no_color() {
+        assert!(color_enabled(ColorChoice::Always, true, false, false));
+        assert!(!color_enabled(ColorChoice::Never, false, true, true));
+        assert!(!color_enabled(ColorChoice::Auto, true, true, true));
+        assert!(color_enabled(ColorChoice::Auto, false, true, false));
+        assert!(color_enabled(ColorChoice::Auto, false, false, true));
+        assert!(!color_enabled(ColorChoice::Auto, false, false, false));
+    }
+
+    #[test]
+    fn test_warnings_are_counted_per_message_in_first_seen_order() {
+        let warnings = WarningLog::new();
+        assert_eq!(warnings.record("Unknown OS variant 'plan9'"), 1);
+        assert_eq!(warnings.record("Asset checksum missing"), 1);
+        assert_eq!(warnings.record("Unknown OS variant 'plan9'"), 2);
+        assert_eq!(warnings.record("Unknown OS variant 'plan9'"), 3);
+        assert_eq!(
+            warnings.snapshot(),
+            [
+                ("Unknown OS variant 'plan9'".to_string(), 3),
+                ("Asset checksum missing".to_string(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reset_warnings_starts_a_new_count() {
+        let message = "Release of 'reset-warnings-test' has no checksum";
+        assert_eq!(record_warning(message), 1);
+        assert_eq!(record_warning(message), 2);
+        reset_warnings();
+        assert_eq!(record_warning(message), 1);
+    }
+}
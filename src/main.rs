@@ -79,32 +79,6 @@
 // This is the core of the `setup-devbox` application.
 // It parses command-line arguments and dispatches to the appropriate subcommand logic.
 
-// ============================================================================
-// MODULE DECLARATIONS
-// ============================================================================
-
-/// CLI argument structures and type definitions
-mod cli;
-/// Individual subcommand implementations
-mod commands;
-// Detailed help text and documentation
-
-// Tool-specific installation logic
-
-mod config;
-/// Shared utility functions
-mod core;
-mod engine;
-mod fonts;
-mod settings;
-mod shell;
-mod state;
-
-/// Application logging system
-mod logger;
-/// Configuration file structures
-mod schemas;
-
 // ============================================================================
 // EXTERNAL DEPENDENCIES
 // ============================================================================
@@ -117,9 +91,15 @@ use colored::Colorize;
 // INTERNAL IMPORTS
 // ============================================================================
 
-use crate::cli::cmd_enums::{Cli, Commands, RemoveCommands};
-use crate::commands::{add, bootstrap, check_updates, edit, help, now, reset, sync, version};
-use crate::schemas::path_resolver::PathResolver;
+use setup_devbox::cli::cmd_enums::{Cli, Commands, ImportCommands, RemoveCommands, StateCommands};
+use setup_devbox::commands::{
+    add, bootstrap, bootstrap_script, check_updates, clean, du, edit, facts, fonts, help, import,
+    info, licenses, list, migrate_config, new, now, owns, plan, prompt_status, reconcile, reset,
+    shellrc, state, status, support_bundle, sync, ui, version,
+};
+use setup_devbox::config::read_only;
+use setup_devbox::schemas::path_resolver::PathResolver;
+use setup_devbox::{log_debug, logger};
 
 // ============================================================================
 // MAIN ENTRY POINT
@@ -168,10 +148,21 @@ fn main() -> Result<()> {
     // ========================================================================
     // STEP 2: INITIALIZE LOGGING SYSTEM
     // ========================================================================
-    // Set up the logger based on the --debug flag.
+    // Set up the logger based on the --debug flag, after deciding whether output is colored.
+    logger::configure_color(cli.color);
     logger::init(cli.debug);
     log_debug!("[SDB] Command line arguments successfully parsed.");
     log_debug!("[SDB] Debug mode requested: {}", cli.debug);
+    setup_devbox::config::strict::set_strict(cli.strict);
+    read_only::set_read_only(cli.read_only);
+    setup_devbox::core::protected::configure(cli.force_protected, cli.reason.clone());
+    setup_devbox::core::dangerous_hooks::configure(cli.allow_dangerous_hooks);
+    if let Some(fixtures) = &cli.simulate
+        && let Err(e) = setup_devbox::simulate(std::path::Path::new(fixtures))
+    {
+        eprintln!("{} {}", "Error:".red(), e);
+        std::process::exit(1);
+    }
 
     // ========================================================================
     // STEP 3: COMMAND DISPATCH
@@ -182,31 +173,59 @@ fn main() -> Result<()> {
         // ====================================================================
         // ADD COMMAND - Add items to configuration files
         // ====================================================================
-        Commands::Add { add_type } => {
+        Commands::Add {
+            config,
+            state,
+            output,
+            yes,
+            add_type,
+        } => {
             log_debug!("[SDB] 'Add' subcommand detected.");
-            add::run(add_type);
+            setup_devbox::core::output::configure(output);
+            setup_devbox::commands::add_interactive::configure_assume_yes(yes);
+            let paths = PathResolver::new(config, state).map_err(|e| anyhow::anyhow!(e))?;
+            read_only::ensure_writable(&paths, "add").map_err(|e| anyhow::anyhow!(e))?;
+            add::run(&paths, add_type);
         }
         // ====================================================================
         // REMOVE COMMAND - Remove items from system and configuration
         // ====================================================================
-        Commands::Remove { item } => match item {
-            RemoveCommands::Tool { name } => {
-                crate::commands::remove::remove_tool(name);
-            }
-            RemoveCommands::Font { name } => {
-                crate::commands::remove::remove_font(name);
-            }
-            RemoveCommands::Alias { name } => {
-                crate::commands::remove::remove_alias(name);
-            }
-            RemoveCommands::Setting { domain, key } => {
-                crate::commands::remove::remove_setting(domain, key);
+        Commands::Remove {
+            config,
+            state,
+            output,
+            item,
+        } => {
+            setup_devbox::core::output::configure(output);
+            let paths = PathResolver::new(config, state).map_err(|e| anyhow::anyhow!(e))?;
+            read_only::ensure_writable(&paths, "remove").map_err(|e| anyhow::anyhow!(e))?;
+            match item {
+                RemoveCommands::Tool { name } => {
+                    setup_devbox::commands::remove::remove_tool(&paths, name);
+                }
+                RemoveCommands::Font { name } => {
+                    setup_devbox::commands::remove::remove_font(&paths, name);
+                }
+                RemoveCommands::Alias { name } => {
+                    setup_devbox::commands::remove::remove_alias(&paths, name);
+                }
+                RemoveCommands::Setting { domain, key } => {
+                    setup_devbox::commands::remove::remove_setting(&paths, domain, key);
+                }
+                RemoveCommands::LaunchAgent { label } => {
+                    setup_devbox::commands::remove::remove_launch_agent(&paths, label);
+                }
             }
-        },
+        }
         // ====================================================================
         // EDIT COMMAND - Open configuration files in editor
         // ====================================================================
-        Commands::Edit { state, config } => {
+        Commands::Edit {
+            state,
+            config,
+            config_path,
+            state_path,
+        } => {
             log_debug!("[SDB] 'Edit' subcommand detected.");
             log_debug!("[SDB] Edit state flag: {}", state);
             log_debug!("[SDB] Edit config type: {:?}", config);
@@ -224,10 +243,17 @@ fn main() -> Result<()> {
                 std::process::exit(1);
             }
 
+            let paths =
+                PathResolver::new(config_path, state_path).map_err(|e| anyhow::anyhow!(e))?;
+            if state {
+                read_only::ensure_writable(&paths, "edit --state")
+                    .map_err(|e| anyhow::anyhow!(e))?;
+            }
+
             // Convert ConfigType to String for the edit::run function
             let config_str = config.map(|c| c.to_string());
             // Call the edit function with the specified target
-            edit::run(state, config_str);
+            edit::run(state, config_str, &paths);
         }
         // ====================================================================
         // BOOTSTRAP COMMAND - Create default configuration files and initial setup
@@ -237,6 +263,7 @@ fn main() -> Result<()> {
 
             // Initialize path resolver with command overrides for custom file locations
             let paths = PathResolver::new(config, None).map_err(|e| anyhow::anyhow!(e))?;
+            read_only::ensure_writable(&paths, "bootstrap").map_err(|e| anyhow::anyhow!(e))?;
 
             log_debug!(
                 "[SDB] 'Bootstrap' subcommand using config dir: {}",
@@ -270,11 +297,26 @@ fn main() -> Result<()> {
             state,
             update_latest,
             dry_run,
+            resume,
+            diff_last,
+            accept_licenses,
+            force,
+            from_plan,
+            activation_file,
+            profiles,
         } => {
             log_debug!("[SDB] 'Now' subcommand detected.");
+            setup_devbox::core::licenses::set_accept_licenses(accept_licenses);
+            setup_devbox::core::name_collisions::set_force(force);
+            setup_devbox::shell::reload::set_emit_activation(activation_file);
+            setup_devbox::config::profiles::set_active(profiles);
 
             // Initialize path resolver with command overrides for custom file locations
             let paths = PathResolver::new(config, state).map_err(|e| anyhow::anyhow!(e))?;
+            // A dry run only reports the plan, so it stays available in read-only mode
+            if !dry_run {
+                read_only::ensure_writable(&paths, "now").map_err(|e| anyhow::anyhow!(e))?;
+            }
 
             log_debug!(
                 "[SDB] 'Now' subcommand using config file: {}",
@@ -285,9 +327,40 @@ fn main() -> Result<()> {
                 paths.state_file().display()
             );
 
+            // A reviewed plan is only applied to the starting point it was made for
+            let plan = from_plan.map(|plan_path| plan::load_verified(&paths, &plan_path));
+            let update_latest = update_latest || plan.as_ref().is_some_and(|p| p.update_latest);
+
             // Execute the main installation and configuration process
             // Pass the PathResolver to provide consistent file path resolution
-            now::run(&paths, update_latest, dry_run);
+            // Loading errors were already logged
+            if now::run(
+                &paths,
+                update_latest,
+                dry_run,
+                resume,
+                diff_last,
+                plan.as_ref(),
+            )
+            .is_err()
+            {
+                std::process::exit(1);
+            }
+        }
+
+        // ====================================================================
+        // PLAN COMMAND - Show or save the changes `now` would make
+        // ====================================================================
+        Commands::Plan {
+            config,
+            state,
+            update_latest,
+            json,
+            out,
+        } => {
+            log_debug!("[SDB] 'Plan' subcommand detected.");
+            let paths = PathResolver::new(config, state).map_err(|e| anyhow::anyhow!(e))?;
+            plan::run(&paths, update_latest, json, out.as_deref());
         }
 
         // ====================================================================
@@ -301,6 +374,7 @@ fn main() -> Result<()> {
         } => {
             log_debug!("[SDB] 'SyncConfig' subcommand detected.");
             let paths = PathResolver::new(output_dir, state).map_err(|e| anyhow::anyhow!(e))?;
+            read_only::ensure_writable(&paths, "sync-config").map_err(|e| anyhow::anyhow!(e))?;
             sync::run(paths, gist, github_token);
         }
 
@@ -326,10 +400,260 @@ fn main() -> Result<()> {
         // ====================================================================
         Commands::Reset { tool, all, state } => {
             log_debug!("[SDB] 'Reset' subcommand detected.");
+            let paths = PathResolver::new(None, state.clone()).map_err(|e| anyhow::anyhow!(e))?;
+            read_only::ensure_writable(&paths, "reset").map_err(|e| anyhow::anyhow!(e))?;
             reset::run(tool, all, state);
         }
+
+        // ====================================================================
+        // RECONCILE COMMAND - Fix drift between state, config and filesystem
+        // ====================================================================
+        Commands::Reconcile { config, state, yes } => {
+            log_debug!("[SDB] 'Reconcile' subcommand detected.");
+            let paths =
+                PathResolver::new(config.clone(), state.clone()).map_err(|e| anyhow::anyhow!(e))?;
+            read_only::ensure_writable(&paths, "reconcile").map_err(|e| anyhow::anyhow!(e))?;
+            reconcile::run(config, state, yes);
+        }
+
+        // ====================================================================
+        // DAEMON COMMAND - Local socket API for editors and GUIs
+        // ====================================================================
+        Commands::Daemon {
+            config,
+            state,
+            socket,
+        } => {
+            log_debug!("[SDB] 'Daemon' subcommand detected.");
+            #[cfg(unix)]
+            setup_devbox::commands::daemon::run(config, state, socket);
+            #[cfg(not(unix))]
+            {
+                let _ = (config, state, socket);
+                setup_devbox::log_error!("[SDB] Daemon mode requires Unix domain sockets.");
+                std::process::exit(1);
+            }
+        }
+
+        // ====================================================================
+        // UI COMMAND - Interactive terminal dashboard
+        // ====================================================================
+        Commands::Ui { config, state } => {
+            log_debug!("[SDB] 'Ui' subcommand detected.");
+            ui::run(config, state);
+        }
+
+        // ====================================================================
+        // FONTS COMMAND - Inspect font releases before installing them
+        // ====================================================================
+        Commands::Fonts { action } => {
+            log_debug!("[SDB] 'Fonts' subcommand detected.");
+            fonts::run(action);
+        }
+
+        // ====================================================================
+        // SHELLRC COMMAND - Preview the shell configuration before applying it
+        // ====================================================================
+        Commands::Shellrc { action } => {
+            log_debug!("[SDB] 'Shellrc' subcommand detected.");
+            // Rendering logs every line it would add; only the diff matters here
+            logger::set_terminal_output(cli.debug);
+            shellrc::run(action);
+        }
+
+        // ====================================================================
+        // IMPORT COMMAND - Suggest entries for already installed software
+        // ====================================================================
+        Commands::Import { action } => {
+            log_debug!("[SDB] 'Import' subcommand detected.");
+            if let ImportCommands::Apps {
+                write: true,
+                config,
+                ..
+            } = &action
+            {
+                let paths =
+                    PathResolver::new(config.clone(), None).map_err(|e| anyhow::anyhow!(e))?;
+                read_only::ensure_writable(&paths, "import --write")
+                    .map_err(|e| anyhow::anyhow!(e))?;
+            }
+            import::run(action);
+        }
+
+        // ====================================================================
+        // PROMPT STATUS COMMAND - Provisioning status segment for shell prompts
+        // ====================================================================
+        Commands::PromptStatus { format, state } => {
+            log_debug!("[SDB] 'PromptStatus' subcommand detected.");
+            // The segment is printed on every prompt; keep stderr quiet
+            logger::set_terminal_output(cli.debug);
+            prompt_status::run(format, state);
+        }
+
+        // ====================================================================
+        // LICENSES COMMAND - List licenses of installed tools and fonts
+        // ====================================================================
+        Commands::Licenses { state } => {
+            log_debug!("[SDB] 'Licenses' subcommand detected.");
+            licenses::run(state);
+        }
+
+        // ====================================================================
+        // LIST COMMAND - Show installed tools, fonts, settings and aliases
+        // ====================================================================
+        Commands::List {
+            kind,
+            output,
+            config,
+            state,
+        } => {
+            log_debug!("[SDB] 'List' subcommand detected.");
+            setup_devbox::core::output::configure(output);
+            let paths = PathResolver::new(config, state).map_err(|e| anyhow::anyhow!(e))?;
+            list::run(&paths, kind);
+        }
+
+        // ====================================================================
+        // CLEAN COMMAND - Reclaim disk space left behind by provisioning runs
+        // ====================================================================
+        Commands::Clean {
+            config,
+            prune,
+            dry_run,
+        } => {
+            log_debug!("[SDB] 'Clean' subcommand detected.");
+            let paths = PathResolver::new(config, None).map_err(|e| anyhow::anyhow!(e))?;
+            if !dry_run {
+                read_only::ensure_writable(&paths, "clean").map_err(|e| anyhow::anyhow!(e))?;
+            }
+            clean::run(&paths, prune, dry_run);
+        }
+
+        // ====================================================================
+        // DU COMMAND - Show the disk space taken by tools and fonts
+        // ====================================================================
+        Commands::Du { config, state } => {
+            log_debug!("[SDB] 'Du' subcommand detected.");
+            let paths = PathResolver::new(config, state).map_err(|e| anyhow::anyhow!(e))?;
+            du::run(&paths);
+        }
+
+        // ====================================================================
+        // STATUS COMMAND - Show drift between configuration, state and system
+        // ====================================================================
+        Commands::Status {
+            output,
+            config,
+            state,
+        } => {
+            log_debug!("[SDB] 'Status' subcommand detected.");
+            setup_devbox::core::output::configure(output);
+            let paths = PathResolver::new(config, state).map_err(|e| anyhow::anyhow!(e))?;
+            status::run(&paths);
+        }
+
+        // ====================================================================
+        // BOOTSTRAP SCRIPT COMMAND - Print the new machine setup flow
+        // ====================================================================
+        Commands::BootstrapScript {
+            repo,
+            branch,
+            dir,
+            one_liner,
+        } => {
+            log_debug!("[SDB] 'BootstrapScript' subcommand detected.");
+            bootstrap_script::run(&repo, branch.as_deref(), dir.as_deref(), one_liner);
+        }
+
+        // ====================================================================
+        // FACTS COMMAND - Show the machine facts used by conditions
+        // ====================================================================
+        Commands::Facts { config, json } => {
+            log_debug!("[SDB] 'Facts' subcommand detected.");
+            let paths = PathResolver::new(config, None).map_err(|e| anyhow::anyhow!(e))?;
+            facts::run(&paths, json);
+        }
+
+        // ====================================================================
+        // INFO COMMAND - Show everything known about one tool
+        // ====================================================================
+        Commands::Info {
+            name,
+            config,
+            state,
+        } => {
+            log_debug!("[SDB] 'Info' subcommand detected.");
+            let paths = PathResolver::new(config, state).map_err(|e| anyhow::anyhow!(e))?;
+            info::run(&paths, &name);
+        }
+
+        // ====================================================================
+        // OWNS COMMAND - Find the tool that installed a file
+        // ====================================================================
+        Commands::Owns { path, state } => {
+            log_debug!("[SDB] 'Owns' subcommand detected.");
+            owns::run(&path, state);
+        }
+
+        // ====================================================================
+        // MIGRATE CONFIG COMMAND - Rewrite deprecated configuration fields
+        // ====================================================================
+        Commands::MigrateConfig { config, dry_run } => {
+            log_debug!("[SDB] 'MigrateConfig' subcommand detected.");
+            if !dry_run {
+                let paths =
+                    PathResolver::new(config.clone(), None).map_err(|e| anyhow::anyhow!(e))?;
+                read_only::ensure_writable(&paths, "migrate-config")
+                    .map_err(|e| anyhow::anyhow!(e))?;
+            }
+            migrate_config::run(config, dry_run);
+        }
+
+        // ====================================================================
+        // NEW COMMAND - Create a project from a registered template
+        // ====================================================================
+        Commands::New {
+            template,
+            dir,
+            list,
+            config,
+            state,
+        } => {
+            log_debug!("[SDB] 'New' subcommand detected.");
+            let paths = PathResolver::new(config, state).map_err(|e| anyhow::anyhow!(e))?;
+            new::run(&paths, template, dir, list);
+        }
+
+        // ====================================================================
+        // STATE COMMAND - Roll the state file back to a backup
+        // ====================================================================
+        Commands::State { action } => {
+            log_debug!("[SDB] 'State' subcommand detected.");
+            let StateCommands::Restore { list, state, .. } = &action;
+            if !list {
+                let paths =
+                    PathResolver::new(None, state.clone()).map_err(|e| anyhow::anyhow!(e))?;
+                read_only::ensure_writable(&paths, "state restore")
+                    .map_err(|e| anyhow::anyhow!(e))?;
+            }
+            state::run(action);
+        }
+
+        // ====================================================================
+        // SUPPORT-BUNDLE COMMAND - Collect redacted diagnostics for bug reports
+        // ====================================================================
+        Commands::SupportBundle {
+            output,
+            config,
+            state,
+        } => {
+            log_debug!("[SDB] 'SupportBundle' subcommand detected.");
+            let paths = PathResolver::new(config, state).map_err(|e| anyhow::anyhow!(e))?;
+            support_bundle::run(&paths, output);
+        }
     }
 
+    logger::print_warnings_summary();
     log_debug!("[SDB] Command execution completed. Exiting application.");
     std::process::exit(0);
 }
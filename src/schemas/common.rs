@@ -19,6 +19,9 @@
 
 use crate::schemas::state_file::DevBoxState;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
 
 // ============================================================================
 // GITHUB API DATA STRUCTURES
@@ -38,7 +41,7 @@ use serde::{Deserialize, Serialize};
 /// - Checksum files for verification
 /// - Installation scripts and packages
 /// - Documentation and release notes
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct ReleaseAsset {
     /// The filename of the asset as it appears on GitHub.
     ///
@@ -82,6 +85,10 @@ pub struct ReleaseAsset {
     /// "https://github.com/rust-lang/rust/releases/download/1.70.0/rust-1.70.0-x86_64-unknown-linux-gnu.tar.gz"
     /// ```
     pub(crate) browser_download_url: String,
+
+    /// Size of the asset in bytes, as reported by GitHub.
+    #[serde(default)]
+    pub(crate) size: u64,
 }
 
 /// Represents a GitHub release with its associated downloadable assets.
@@ -113,7 +120,7 @@ pub struct ReleaseAsset {
 /// - API rate limits (60 requests/hour unauthenticated, 5000/hour authenticated)
 /// - Conditional requests using `ETags` for caching
 /// - Pagination for repositories with many releases
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct Release {
     /// A collection of downloadable assets associated with this release.
     ///
@@ -213,6 +220,196 @@ pub struct MainConfig {
     /// If not specified, the system will look for `fonts.yaml` in default
     /// locations or skip font installation if none is found.
     pub fonts: Option<String>,
+
+    /// Reject unknown keys in all configuration files, as if `--strict` was passed.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
+
+    /// Refuse every command that would change this machine, as if `--read-only` was passed.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
+
+    /// Command whose success means a corporate VPN is connected, exposed as the `vpn` fact.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vpn_check: Option<String>,
+
+    /// Commands to run at points of a `now` run (`before_run`, `after_run`,
+    /// `after_tool_install`, `on_failure`).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hooks: Option<LifecycleHooks>,
+
+    /// Layout of the state file: `pretty` (indented, the default) or `compact` (one line).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_style: Option<StateStyle>,
+
+    /// Format of the state file: `json` (the default), `yaml` or `toml`. A state file in
+    /// another format is converted the first time it is loaded.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_format: Option<StateFormat>,
+
+    /// Whether release assets built for another architecture this machine can run are used:
+    /// `never`, `prefer-native` (the default; only when no native asset exists) or
+    /// `allow-rosetta` (like native ones).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arch_fallback: Option<ArchFallback>,
+
+    /// Project templates `setup-devbox new` creates projects from, keyed by template name.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scaffolds: Option<BTreeMap<String, Scaffold>>,
+}
+
+/// A project template of `config.yaml`, created with `setup-devbox new <name> <dir>`.
+///
+/// ```yaml
+/// scaffolds:
+///   rust-cli:
+///     generator: cargo-generate
+///     source: gh:rust-cli/cli-template
+///   react:
+///     generator: degit
+///     source: vitejs/vite/packages/create-vite/template-react-ts
+///     description: React + TypeScript with Vite
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Scaffold {
+    /// Tool that creates projects from the template.
+    pub generator: ScaffoldGenerator,
+    /// Template location, in any form the generator accepts (`gh:owner/repo`, a git URL, a
+    /// local directory).
+    pub source: String,
+    /// Extra arguments passed to the generator.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// What the template creates, shown by `setup-devbox new --list`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Project generators `scaffolds` can use.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ScaffoldGenerator {
+    /// Python's `cookiecutter`; the project is generated inside the target directory.
+    Cookiecutter,
+    /// `cargo generate`, for Rust templates.
+    CargoGenerate,
+    /// `degit`, which copies a repository (or a directory of it) without its history.
+    Degit,
+}
+
+impl fmt::Display for ScaffoldGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScaffoldGenerator::Cookiecutter => write!(f, "cookiecutter"),
+            ScaffoldGenerator::CargoGenerate => write!(f, "cargo-generate"),
+            ScaffoldGenerator::Degit => write!(f, "degit"),
+        }
+    }
+}
+
+/// Policy for release assets built for the fallback architecture of a platform (`x86_64`
+/// under Rosetta 2 on Apple silicon, `armv7` on 64-bit ARM Linux, `i686` on x86_64 Linux).
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ArchFallback {
+    /// Only install assets built for this architecture.
+    Never,
+    /// Use a fallback asset only when the release has no native one.
+    #[default]
+    PreferNative,
+    /// Treat fallback assets like native ones.
+    AllowRosetta,
+}
+
+impl fmt::Display for ArchFallback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArchFallback::Never => write!(f, "never"),
+            ArchFallback::PreferNative => write!(f, "prefer-native"),
+            ArchFallback::AllowRosetta => write!(f, "allow-rosetta"),
+        }
+    }
+}
+
+/// Format the state file is stored in; its file name is `state.<extension>`.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StateFormat {
+    #[default]
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl StateFormat {
+    /// Every format, in the order other state files are looked for when migrating.
+    pub const ALL: [StateFormat; 3] = [StateFormat::Json, StateFormat::Yaml, StateFormat::Toml];
+
+    /// Extension of state files in this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            StateFormat::Json => "json",
+            StateFormat::Yaml => "yaml",
+            StateFormat::Toml => "toml",
+        }
+    }
+
+    /// The format of a state file, from its extension; JSON unless it is `.yaml`, `.yml` or
+    /// `.toml`.
+    pub fn of(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("yaml" | "yml") => StateFormat::Yaml,
+            Some("toml") => StateFormat::Toml,
+            _ => StateFormat::Json,
+        }
+    }
+}
+
+/// Layout of a JSON state file (YAML and TOML are always one value per line). Keys are
+/// written in sorted order either way, so the file only changes where the state did.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StateStyle {
+    /// Indented, one value per line; the smallest diffs when the file is kept in git.
+    #[default]
+    Pretty,
+    /// A single line, for large states nobody reads.
+    Compact,
+}
+
+/// Global lifecycle hooks of `config.yaml`, run with `sh -c`. Each receives the run's context
+/// as `SDB_*` environment variables.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct LifecycleHooks {
+    /// Commands run before anything is installed.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub before_run: Vec<String>,
+
+    /// Commands run once the run finished, whether or not something failed.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub after_run: Vec<String>,
+
+    /// Commands run after each tool that was installed or updated.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub after_tool_install: Vec<String>,
+
+    /// Commands run at the end of a run in which any tool failed.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub on_failure: Vec<String>,
 }
 
 // ============================================================================
@@ -280,7 +477,7 @@ pub struct ConfigPaths {
 ///
 /// The orchestrator follows the Orchestrator pattern, acting as a high-level
 /// coordinator that delegates specific tasks to specialized components:
-/// - ToolUninstaller implementations handle installation-specific removal
+/// - Registered installers handle installation-specific removal
 /// - ConfigurationCleaner handles YAML file manipulation
 /// - The orchestrator focuses on workflow and error handling
 pub struct RemovalOrchestrator<'a> {
@@ -288,5 +485,5 @@ pub struct RemovalOrchestrator<'a> {
     pub state: &'a mut DevBoxState,
 
     /// Configuration file manager
-    pub cleaner: crate::engine::uninstaller::executors::ConfigurationCleaner,
+    pub(crate) cleaner: crate::engine::uninstaller::executors::ConfigurationCleaner,
 }
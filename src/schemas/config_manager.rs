@@ -23,11 +23,14 @@
 //!
 //! ## Output Format Detection
 //!
-//! Target format is determined by the file extension in `tools_configuration_path`:
+//! Target format is determined by the file extension in `tools_configuration_paths`:
 //! - `.json` → Pretty-printed JSON
 //! - `.yaml` or `.yml` → YAML format
 //! - `.toml` → Pretty-printed TOML
 //! - Other extensions → KEY=VALUE pairs with smart quoting
+//!
+//! A path given as `{ path, format }` names the format explicitly, for destinations whose
+//! extension says nothing about it (`~/.config/git/config`, `kitty.conf`).
 
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -53,7 +56,7 @@ pub fn is_false(b: &bool) -> bool {
 ///   source: "brew"
 ///   configuration_manager:
 ///     enabled: true
-///     tools_configuration_path: "~/.config/starship/starship.toml"
+///     tools_configuration_paths: ["~/.config/starship/starship.toml"]
 /// ```
 ///
 /// ## Enablement Behavior
@@ -97,8 +100,115 @@ pub struct ConfigurationManager {
     /// - `"$HOME/.config/helix/config.toml"`
     /// - `"/etc/myapp/config.json"`
     /// - `"./local-config.yaml"`
+    /// - `{ path: "~/.config/kitty/kitty.conf", format: raw }`
+    ///
+    /// May be left out for tools with a `preset:`, which supplies the path.
+    #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub tools_configuration_paths: Vec<String>,
+    pub tools_configuration_paths: Vec<ConfigurationPath>,
+}
+
+/// A destination of the configuration manager: a plain path, whose extension picks the
+/// format, or a path with explicit options.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum ConfigurationPath {
+    Path(String),
+    Detailed {
+        path: String,
+        /// Format to write instead of the one inferred from the extension.
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        format: Option<ConfigurationFormat>,
+        /// Octal permissions set after writing, e.g. `"0755"` or `"600"`.
+        #[serde(default, deserialize_with = "deserialize_mode")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        mode: Option<String>,
+        /// `user` or `user:group` the file is changed to after writing, where permitted.
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        owner: Option<String>,
+    },
+}
+
+/// Reads `mode:` written either quoted (`"0755"`) or as a bare number, which YAML reads as
+/// the decimal 755; either way its digits are the octal permissions.
+fn deserialize_mode<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Mode {
+        Number(u64),
+        Text(String),
+    }
+    Ok(
+        Option::<Mode>::deserialize(deserializer)?.map(|mode| match mode {
+            Mode::Number(number) => number.to_string(),
+            Mode::Text(text) => text,
+        }),
+    )
+}
+
+impl ConfigurationPath {
+    /// The destination path as written in the configuration.
+    pub fn path(&self) -> &str {
+        match self {
+            ConfigurationPath::Path(path) | ConfigurationPath::Detailed { path, .. } => path,
+        }
+    }
+
+    /// The explicitly configured format, if any.
+    pub fn format(&self) -> Option<ConfigurationFormat> {
+        match self {
+            ConfigurationPath::Path(_) => None,
+            ConfigurationPath::Detailed { format, .. } => *format,
+        }
+    }
+
+    /// The configured permissions, parsed from their octal notation.
+    pub fn mode(&self) -> Result<Option<u32>, String> {
+        match self {
+            ConfigurationPath::Detailed {
+                mode: Some(mode), ..
+            } => u32::from_str_radix(mode.trim_start_matches("0o"), 8)
+                .ok()
+                .filter(|mode| *mode <= 0o7777)
+                .map(Some)
+                .ok_or_else(|| format!("'{mode}' is not an octal file mode like 0644")),
+            _ => Ok(None),
+        }
+    }
+
+    /// The configured `user` or `user:group` owner, if any.
+    pub fn owner(&self) -> Option<&str> {
+        match self {
+            ConfigurationPath::Path(_) => None,
+            ConfigurationPath::Detailed { owner, .. } => owner.as_deref(),
+        }
+    }
+}
+
+impl From<String> for ConfigurationPath {
+    fn from(path: String) -> Self {
+        ConfigurationPath::Path(path)
+    }
+}
+
+/// Format the configuration manager writes a destination file in.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigurationFormat {
+    Json,
+    Yaml,
+    Toml,
+    /// `[section]` headers with `key = value` lines.
+    Ini,
+    /// Flattened `KEY=VALUE` lines.
+    Keyvalue,
+    /// The source file copied verbatim, without TOML parsing.
+    Raw,
 }
 
 // Implements a helper method to check if ConfigurationManager is in its default state.
@@ -108,6 +218,14 @@ impl ConfigurationManager {
     pub fn is_default(&self) -> bool {
         self == &Self::default()
     }
+
+    /// The destination paths as written in the configuration.
+    pub fn destination_paths(&self) -> Vec<String> {
+        self.tools_configuration_paths
+            .iter()
+            .map(|path| path.path().to_string())
+            .collect()
+    }
 }
 
 // ============================================================================
@@ -39,8 +39,12 @@
 //! styles and weights from a font family, reducing disk space usage and
 //! installation time when only certain variants are needed.
 
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
+use crate::schemas::state_file::FontState;
+
 // ============================================================================
 // TOP-LEVEL FONT CONFIGURATION
 // ============================================================================
@@ -249,4 +253,88 @@ pub struct FontEntry {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub install_only: Option<Vec<String>>,
+
+    /// Nerd Fonts variant to install, e.g. `mono`, `propo`, `mono-regular` or `bold`.
+    ///
+    /// Written as `[<spacing>][-<weight>]`: the spacing (`mono`, `propo`, or `default` for
+    /// the standard Nerd Font) and/or the weight (`regular`, `bold`, `light`, ...) select the
+    /// matching `<Name>NerdFont[Mono|Propo]-<Weight>` files from the release archive, without
+    /// knowing the exact file names. `repo` defaults to `ryanoasis/nerd-fonts` and `tag` to
+    /// the latest release. Combined with `install_only`, a file must match both.
+    ///
+    /// ```yaml
+    /// - name: JetBrainsMono
+    ///   source: github
+    ///   variant: mono-regular    # JetBrainsMonoNerdFontMono-Regular.ttf
+    /// ```
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+
+    /// Ask for acceptance of the font's license before installing it (or pass
+    /// `--accept-licenses`), e.g. for proprietary fonts.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requires_license_acceptance: Option<bool>,
+
+    /// Free-form note shown by `list` and `status`, e.g. why the font is needed.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Who to ask before changing or removing this entry (a person or team).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+
+    /// Where the font is installed: `user` (the default, `~/Library/Fonts`) or `system`
+    /// (`/Library/Fonts`, for every user of the machine), which is written with `sudo`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<FontScope>,
+
+    /// Profiles the font belongs to (`[work, personal]`); `now --profile <NAME>` only installs
+    /// fonts of that profile and fonts without `profiles`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profiles: Option<Vec<String>>,
+}
+
+impl FontEntry {
+    /// Whether the entry pins a `tag` or `version` other than the one recorded in `state`,
+    /// in which case the font is reinstalled and its older files are replaced. `latest` pins
+    /// nothing: the release it resolved to is recorded instead.
+    pub fn pins_other_version(&self, state: &FontState) -> bool {
+        let pinned = |value: &Option<String>| value.clone().filter(|value| value != "latest");
+        pinned(&self.tag).is_some_and(|tag| state.tag.as_ref() != Some(&tag))
+            || pinned(&self.version).is_some_and(|version| version != state.version)
+    }
+
+    /// Whether the entry asks for the latest release: `version: latest`, or neither a
+    /// `version` nor a `tag`.
+    pub fn wants_latest(&self) -> bool {
+        match (self.tag.as_deref(), self.version.as_deref()) {
+            (Some(tag), _) => tag == "latest",
+            (None, version) => version.is_none_or(|version| version == "latest"),
+        }
+    }
+}
+
+/// Font directory a font is installed to.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FontScope {
+    /// The user's font directory (`~/Library/Fonts`, `~/.local/share/fonts`).
+    #[default]
+    User,
+    /// The machine-wide font directory (`/Library/Fonts`, `/usr/local/share/fonts`).
+    System,
+}
+
+impl fmt::Display for FontScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FontScope::User => write!(f, "user"),
+            FontScope::System => write!(f, "system"),
+        }
+    }
 }
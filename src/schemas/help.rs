@@ -339,6 +339,8 @@ impl InstallerRegistry {
             Self::fonts_installer(),
             Self::github_installer(),
             Self::go_installer(),
+            Self::oci_installer(),
+            Self::path_entry_installer(),
             Self::pip_installer(),
             Self::rustup_installer(),
             Self::shell_installer(),
@@ -383,10 +385,13 @@ impl InstallerRegistry {
                 "source: brew (required)",
                 "version: Specific version or 'latest'",
                 "options: List of brew-specific flags (--cask, --head, etc.)",
-                "post_installation_hooks: List of commands/hooks to run after installation (optional)",
+                "post_installation_hooks: List of commands/hooks to run after installation; a hook may be `run:` with `environment: mise:<tools>` or `direnv:<dir>` (optional)",
+                "acknowledge_dangerous: Allow hooks that look dangerous (`rm -rf /`, `curl ... | sh`, `sudo`) (optional)",
+                "permissions: macOS privacy permissions to grant after installing, e.g. `full_disk_access`, `screen_recording`, `accessibility` (optional)",
+                "preset: Built-in setup for `aerospace`, `yabai`, `skhd` or `karabiner`: default configuration path and service restart (optional)",
                 "configuration_manager: If the tool support any configuration and managed by SDB",
                 "configuration_manager.enabled: true or false (default is false)",
-                "configuration_manager.tools_configuration_paths: List of configuration file path with filename",
+                "configuration_manager.tools_configuration_paths: List of configuration file path with filename, or `{ path, format, mode, owner }` with format json, yaml, toml, ini, keyvalue or raw",
             ],
         }
     }
@@ -423,10 +428,13 @@ impl InstallerRegistry {
                 "source: cargo (required)",
                 "version: Specific version or 'latest'",
                 "options: List of cargo install flags (--git, --features, etc.)",
-                "post_installation_hooks: List of commands/hooks to run after installation (optional)",
+                "post_installation_hooks: List of commands/hooks to run after installation; a hook may be `run:` with `environment: mise:<tools>` or `direnv:<dir>` (optional)",
+                "acknowledge_dangerous: Allow hooks that look dangerous (`rm -rf /`, `curl ... | sh`, `sudo`) (optional)",
+                "permissions: macOS privacy permissions to grant after installing, e.g. `full_disk_access`, `screen_recording`, `accessibility` (optional)",
+                "preset: Built-in setup for `aerospace`, `yabai`, `skhd` or `karabiner`: default configuration path and service restart (optional)",
                 "configuration_manager: If the tool support any configuration and managed by SDB",
                 "configuration_manager.enabled: true or false (default is false)",
-                "configuration_manager.tools_configuration_paths: List of configuration file path with filename",
+                "configuration_manager.tools_configuration_paths: List of configuration file path with filename, or `{ path, format, mode, owner }` with format json, yaml, toml, ini, keyvalue or raw",
             ],
         }
     }
@@ -502,12 +510,17 @@ impl InstallerRegistry {
                 "source: github (required)",
                 "repo: GitHub repository (owner/name format)",
                 "version: Release version",
-                "tag: Specific git tag or 'latest'",
+                "tag: Release tag; without it the release of 'version' is used, or the latest release for 'version: latest' (optional)",
                 "rename_to: Rename binary after installation",
-                "post_installation_hooks: List of commands/hooks to run after installation (optional)",
+                "optimize_binary: `strip` debug symbols or `upx`-compress the downloaded executable (optional)",
+                "app_bundle: Install the `.app` bundle from a `.dmg` or archive to `app_dir` and link its embedded CLI (`cli: Contents/MacOS/cli`, detected when omitted) into the bin directory (optional)",
+                "post_installation_hooks: List of commands/hooks to run after installation; a hook may be `run:` with `environment: mise:<tools>` or `direnv:<dir>` (optional)",
+                "acknowledge_dangerous: Allow hooks that look dangerous (`rm -rf /`, `curl ... | sh`, `sudo`) (optional)",
+                "permissions: macOS privacy permissions to grant after installing, e.g. `full_disk_access`, `screen_recording`, `accessibility` (optional)",
+                "preset: Built-in setup for `aerospace`, `yabai`, `skhd` or `karabiner`: default configuration path and service restart (optional)",
                 "configuration_manager: If the tool support any configuration and managed by SDB",
                 "configuration_manager.enabled: true or false (default is false)",
-                "configuration_manager.tools_configuration_paths: List of configuration file path with filename",
+                "configuration_manager.tools_configuration_paths: List of configuration file path with filename, or `{ path, format, mode, owner }` with format json, yaml, toml, ini, keyvalue or raw",
             ],
         }
     }
@@ -543,10 +556,13 @@ impl InstallerRegistry {
                 "source: go (required)",
                 "url: Go package import path (required)",
                 "version: Package version (@latest, @v1.2.3, etc.)",
-                "post_installation_hooks: List of commands/hooks to run after installation (optional)",
+                "post_installation_hooks: List of commands/hooks to run after installation; a hook may be `run:` with `environment: mise:<tools>` or `direnv:<dir>` (optional)",
+                "acknowledge_dangerous: Allow hooks that look dangerous (`rm -rf /`, `curl ... | sh`, `sudo`) (optional)",
+                "permissions: macOS privacy permissions to grant after installing, e.g. `full_disk_access`, `screen_recording`, `accessibility` (optional)",
+                "preset: Built-in setup for `aerospace`, `yabai`, `skhd` or `karabiner`: default configuration path and service restart (optional)",
                 "configuration_manager: If the tool support any configuration and managed by SDB",
                 "configuration_manager.enabled: true or false (default is false)",
-                "configuration_manager.tools_configuration_paths: List of configuration file path with filename",
+                "configuration_manager.tools_configuration_paths: List of configuration file path with filename, or `{ path, format, mode, owner }` with format json, yaml, toml, ini, keyvalue or raw",
             ],
         }
     }
@@ -584,10 +600,13 @@ impl InstallerRegistry {
                 "source: pip (required)",
                 "version: Specific version or 'latest'",
                 "options: List of pip install flags (--user, --upgrade, etc.)",
-                "post_installation_hooks: List of commands/hooks to run after installation (optional)",
+                "post_installation_hooks: List of commands/hooks to run after installation; a hook may be `run:` with `environment: mise:<tools>` or `direnv:<dir>` (optional)",
+                "acknowledge_dangerous: Allow hooks that look dangerous (`rm -rf /`, `curl ... | sh`, `sudo`) (optional)",
+                "permissions: macOS privacy permissions to grant after installing, e.g. `full_disk_access`, `screen_recording`, `accessibility` (optional)",
+                "preset: Built-in setup for `aerospace`, `yabai`, `skhd` or `karabiner`: default configuration path and service restart (optional)",
                 "configuration_manager: If the tool support any configuration and managed by SDB",
                 "configuration_manager.enabled: true or false (default is false)",
-                "configuration_manager.tools_configuration_paths: List of configuration file path with filename",
+                "configuration_manager.tools_configuration_paths: List of configuration file path with filename, or `{ path, format, mode, owner }` with format json, yaml, toml, ini, keyvalue or raw",
             ],
         }
     }
@@ -624,10 +643,13 @@ impl InstallerRegistry {
                 "source: rustup (required)",
                 "version: Rust toolchain (stable, beta, nightly, or specific version)",
                 "options: List of components to install (rust-src, clippy, rustfmt, etc.)",
-                "post_installation_hooks: List of commands/hooks to run after installation (optional)",
+                "post_installation_hooks: List of commands/hooks to run after installation; a hook may be `run:` with `environment: mise:<tools>` or `direnv:<dir>` (optional)",
+                "acknowledge_dangerous: Allow hooks that look dangerous (`rm -rf /`, `curl ... | sh`, `sudo`) (optional)",
+                "permissions: macOS privacy permissions to grant after installing, e.g. `full_disk_access`, `screen_recording`, `accessibility` (optional)",
+                "preset: Built-in setup for `aerospace`, `yabai`, `skhd` or `karabiner`: default configuration path and service restart (optional)",
                 "configuration_manager: If the tool support any configuration and managed by SDB",
                 "configuration_manager.enabled: true or false (default is false)",
-                "configuration_manager.tools_configuration_paths: List of configuration file path with filename",
+                "configuration_manager.tools_configuration_paths: List of configuration file path with filename, or `{ path, format, mode, owner }` with format json, yaml, toml, ini, keyvalue or raw",
             ],
         }
     }
@@ -759,6 +781,78 @@ impl InstallerRegistry {
         }
     }
 
+    /// Returns the help information for the OCI installer.
+    ///
+    /// Provides documentation for pulling tools published as OCI artifacts
+    /// (e.g. with ORAS) from container registries.
+    fn oci_installer() -> InstallerInfo {
+        InstallerInfo {
+            name: "OCI",
+            description: "Pulls tools published as OCI artifacts (ORAS) from container registries, verifying layer digests from the manifest.",
+            env_variables: None,
+            examples_fn: || {
+                r"- name: tool
+  source: oci
+  repo: ghcr.io/org/tool
+  version: 1.2.3
+
+- name: internal-cli
+  source: oci
+  repo: registry.example.com/platform/internal-cli
+  version: latest
+  basic_auth:
+    username: ${REGISTRY_USER}
+    password: ${REGISTRY_TOKEN}"
+                    .to_string()
+            },
+            options: &[
+                "name: Tool name (required)",
+                "source: oci (required)",
+                "repo: Artifact reference without tag, e.g. ghcr.io/org/tool (required)",
+                "version: Tag to pull; 'latest' picks the highest semantic version tag (optional)",
+                "tag: Tag to pull, when it differs from the version (optional)",
+                "rename_to: Binary name after installation (optional)",
+                "executable_path_after_extract: Executable inside an archive layer (optional)",
+                "optimize_binary: `strip` debug symbols or `upx`-compress the downloaded executable (optional)",
+                "basic_auth: Credentials for private registries (optional)",
+                "post_installation_hooks: List of commands/hooks to run after installation; a hook may be `run:` with `environment: mise:<tools>` or `direnv:<dir>` (optional)",
+                "acknowledge_dangerous: Allow hooks that look dangerous (`rm -rf /`, `curl ... | sh`, `sudo`) (optional)",
+                "permissions: macOS privacy permissions to grant after installing, e.g. `full_disk_access`, `screen_recording`, `accessibility` (optional)",
+                "preset: Built-in setup for `aerospace`, `yabai`, `skhd` or `karabiner`: default configuration path and service restart (optional)",
+            ],
+        }
+    }
+
+    /// Returns the help information for PATH entries.
+    ///
+    /// Provides documentation for tools installed outside of SDB (e.g. by MDM)
+    /// whose directory only needs to be on PATH.
+    fn path_entry_installer() -> InstallerInfo {
+        InstallerInfo {
+            name: "PATH Entry",
+            description: "Puts a directory installed by something else (MDM, vendor installers) on PATH and checks a binary exists there. SDB never installs, updates or removes it.",
+            env_variables: None,
+            examples_fn: || {
+                r"- name: corp-vpn
+  source: path-entry
+  path: /opt/corp/vpn/bin
+  binary: corp-vpn
+
+- name: local-bin
+  source: path-entry
+  path: ~/.local/bin"
+                    .to_string()
+            },
+            options: &[
+                "name: Tool name (required)",
+                "source: path-entry (required)",
+                "path: Directory to put on PATH (required)",
+                "binary: Executable that must exist in the directory (optional)",
+                "version: Version to record in state, 'external' if omitted (optional)",
+            ],
+        }
+    }
+
     /// Returns the help information for the URL installer.
     ///
     /// Provides documentation for installing software from direct download URLs,
@@ -794,10 +888,15 @@ impl InstallerRegistry {
                 "url: Download URL (required)",
                 "version: Version identifier (optional)",
                 "options: Installation flags (--script, --binary, --checksum, etc.) (optional)",
-                "post_installation_hooks: List of commands/hooks to run after installation (optional)",
+                "optimize_binary: `strip` debug symbols or `upx`-compress the downloaded executable (optional)",
+                "app_bundle: Install the `.app` bundle from a `.dmg` or archive to `app_dir` and link its embedded CLI (`cli: Contents/MacOS/cli`, detected when omitted) into the bin directory (optional)",
+                "post_installation_hooks: List of commands/hooks to run after installation; a hook may be `run:` with `environment: mise:<tools>` or `direnv:<dir>` (optional)",
+                "acknowledge_dangerous: Allow hooks that look dangerous (`rm -rf /`, `curl ... | sh`, `sudo`) (optional)",
+                "permissions: macOS privacy permissions to grant after installing, e.g. `full_disk_access`, `screen_recording`, `accessibility` (optional)",
+                "preset: Built-in setup for `aerospace`, `yabai`, `skhd` or `karabiner`: default configuration path and service restart (optional)",
                 "configuration_manager: If the tool support any configuration and managed by SDB",
                 "configuration_manager.enabled: true or false (default is false)",
-                "configuration_manager.tools_configuration_paths: List of configuration file path with filename",
+                "configuration_manager.tools_configuration_paths: List of configuration file path with filename, or `{ path, format, mode, owner }` with format json, yaml, toml, ini, keyvalue or raw",
             ],
         }
     }
@@ -818,7 +917,7 @@ impl InstallerRegistry {
   version: 3.13.7
   options:
     - --mode=python
-  additional_cmd:
+  post_installation_hooks:
     - uv python update-shell
 
 - name: ruff
@@ -839,10 +938,13 @@ impl InstallerRegistry {
                 "source: uv (required)",
                 "version: Version or Python version (required)",
                 "options: UV mode flags (--mode=tool/pip/python) (required)",
-                "post_installation_hooks: List of commands/hooks to run after installation (optional)",
+                "post_installation_hooks: List of commands/hooks to run after installation; a hook may be `run:` with `environment: mise:<tools>` or `direnv:<dir>` (optional)",
+                "acknowledge_dangerous: Allow hooks that look dangerous (`rm -rf /`, `curl ... | sh`, `sudo`) (optional)",
+                "permissions: macOS privacy permissions to grant after installing, e.g. `full_disk_access`, `screen_recording`, `accessibility` (optional)",
+                "preset: Built-in setup for `aerospace`, `yabai`, `skhd` or `karabiner`: default configuration path and service restart (optional)",
                 "configuration_manager: If the tool support any configuration and managed by SDB",
                 "configuration_manager.enabled: true or false (default is false)",
-                "configuration_manager.tools_configuration_paths: List of configuration file path with filename",
+                "configuration_manager.tools_configuration_paths: List of configuration file path with filename, or `{ path, format, mode, owner }` with format json, yaml, toml, ini, keyvalue or raw",
             ],
         }
     }
@@ -70,7 +70,26 @@ pub struct SettingsConfig {
     /// - Single configuration file for multi-platform environments
     /// - Easy to extend with new operating system support
     /// - Platform-appropriate setting application logic
+    #[serde(default)]
     pub settings: OsSpecificSettings,
+
+    /// User agents (background services) to install, such as `syncthing`.
+    ///
+    /// On macOS each entry is written as a LaunchAgent plist under
+    /// `~/Library/LaunchAgents` and loaded with `launchctl`. On Linux the same entry
+    /// becomes a systemd user unit (plus a `.timer` for `start_interval`) under
+    /// `~/.config/systemd/user`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub launch_agents: Vec<LaunchAgentEntry>,
+
+    /// Default applications for file types and URL schemes (macOS only).
+    ///
+    /// File types are applied with [`duti`](https://github.com/moretension/duti), which wraps
+    /// `LSSetDefaultRoleHandlerForContentType`; URL schemes with `LSSetDefaultHandlerForURLScheme`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub file_associations: Vec<FileAssociationEntry>,
 }
 
 // =========================================================================== //
@@ -253,3 +272,99 @@ pub struct SettingEntry {
     #[serde(rename = "type")]
     pub value_type: String,
 }
+
+// =========================================================================== //
+//                            LAUNCH AGENT ENTRIES                             //
+// =========================================================================== //
+
+/// A user agent declared in the `launch_agents:` section of `settings.yaml`.
+///
+/// ## Examples
+/// ```yaml
+/// launch_agents:
+///   - label: com.example.syncthing
+///     program_arguments: ["/opt/homebrew/bin/syncthing", "serve", "--no-browser"]
+///     run_at_load: true
+///     keep_alive: true
+/// ```
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct LaunchAgentEntry {
+    /// Unique label of the agent, conventionally in reverse DNS notation.
+    pub label: String,
+
+    /// Program to run followed by its arguments.
+    pub program_arguments: Vec<String>,
+
+    /// Start the agent as soon as it is loaded (at login). Defaults to `true`.
+    #[serde(default = "default_run_at_load")]
+    pub run_at_load: bool,
+
+    /// Restart the agent whenever it exits. Defaults to `false`.
+    #[serde(default)]
+    pub keep_alive: bool,
+
+    /// Run the agent every N seconds instead of keeping it running.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_interval: Option<u64>,
+
+    /// Working directory for the agent.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_directory: Option<String>,
+
+    /// Environment variables set for the agent.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub environment: Option<std::collections::BTreeMap<String, String>>,
+
+    /// File that receives the agent's standard output.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub standard_out_path: Option<String>,
+
+    /// File that receives the agent's standard error.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub standard_error_path: Option<String>,
+}
+
+fn default_run_at_load() -> bool {
+    true
+}
+
+// =========================================================================== //
+//                          FILE ASSOCIATION ENTRIES                           //
+// =========================================================================== //
+
+/// A default application declared in the `file_associations:` section of `settings.yaml`.
+///
+/// ## Examples
+/// ```yaml
+/// file_associations:
+///   - content_type: .md                # file extension
+///     app: dev.zed.Zed                 # bundle id of the handler
+///   - content_type: public.plain-text  # or a UTI
+///     app: com.microsoft.VSCode
+///     role: editor
+///   - content_type: https://           # URL scheme, e.g. for the default browser
+///     app: org.mozilla.firefox
+///   - content_type: vscode://          # deep links into an editor
+///     app: com.microsoft.VSCode
+/// ```
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct FileAssociationEntry {
+    /// File extension (`.md`), UTI (`public.plain-text`) or URL scheme (`https://`).
+    pub content_type: String,
+
+    /// Bundle identifier of the application that should handle it.
+    pub app: String,
+
+    /// Handler role: `all`, `viewer`, `editor` or `shell`. Ignored for URL schemes.
+    #[serde(default = "default_association_role")]
+    pub role: String,
+}
+
+fn default_association_role() -> String {
+    "all".to_string()
+}
@@ -2,7 +2,9 @@
 //                          STANDARD LIBRARY DEPENDENCIES                      //
 // =========================================================================== //
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use std::{env, fs, io};
 
 // =========================================================================== //
@@ -15,9 +17,23 @@ use colored::Colorize;
 //                              INTERNAL IMPORTS                               //
 // =========================================================================== //
 
+use crate::core::windows;
+use crate::engine::installers::simulated;
+use crate::schemas::common::StateFormat;
+use crate::schemas::fonts::FontScope;
 use crate::schemas::tools_types::ToolEntry;
+use crate::state::manager;
 use crate::{log_debug, log_error, log_info, log_warn};
 
+/// Directory name used under the XDG base directories.
+const APP_DIR_NAME: &str = "setup-devbox";
+
+/// Relocates everything (configs, state, cache and temporary files) under one directory.
+const HOME_ENV_VAR: &str = "SETUP_DEVBOX_HOME";
+
+/// Work directory chosen for this run by [`PathResolver::configure_work_dir`].
+static WORK_DIR: OnceLock<PathBuf> = OnceLock::new();
+
 /// # PathResolver
 ///
 /// Central path resolution service for the application.
@@ -31,6 +47,10 @@ use crate::{log_debug, log_error, log_info, log_warn};
 /// It handles environment variable overrides (`SDB_CONFIG_PATH`, `SDB_STATE_FILE_PATH`, etc.)
 /// and provides sensible defaults, including tilde (`~`) expansion for user paths.
 ///
+/// For network homes and strict XDG layouts, `SETUP_DEVBOX_HOME` relocates configs, state,
+/// cache and temporary files under a single directory. Without it (and without an existing
+/// `~/.setup-devbox`), `XDG_CONFIG_HOME`, `XDG_STATE_HOME` and `XDG_CACHE_HOME` are honored.
+///
 /// Initialize once at application startup using `PathResolver::new()` and pass around as needed.
 #[derive(Debug, Clone)]
 pub struct PathResolver {
@@ -45,13 +65,16 @@ pub struct PathResolver {
     /// Directory containing tools configuration files.
     #[allow(dead_code)]
     tools_config_dir: PathBuf,
+    /// Directory for cached data that can be safely deleted.
+    cache_dir: PathBuf,
 }
 
 impl PathResolver {
     /// Initializes the path resolver by determining all key application paths.
     ///
     /// The resolution order for paths is generally:
-    /// 1. Explicit argument (`config_path` or `state_path`).
+    /// 1. Explicit argument (`config_path` or `state_path`), then, for the state file while
+    ///    simulating (`--simulate`), the directory of the fixture file.
     /// 2. Specific Environment Variable (e.g., `SDB_STATE_FILE_PATH`).
     /// 3. General Environment Variable (e.g., `SDB_CONFIG_PATH`).
     /// 4. Default path relative to the resolved base configuration directory.
@@ -63,14 +86,23 @@ impl PathResolver {
     /// # Returns
     /// A `Result` containing the initialized `PathResolver` on success, or a `String` error message on failure.
     pub fn new(config_path: Option<String>, state_path: Option<String>) -> Result<Self, String> {
+        Self::from_env(&PathEnv::from_process(), config_path, state_path)
+    }
+
+    /// Resolves all paths as [`Self::new`] does, against the given environment.
+    fn from_env(
+        env: &PathEnv,
+        config_path: Option<String>,
+        state_path: Option<String>,
+    ) -> Result<Self, String> {
         log_debug!("Initializing PathResolver");
 
         // First, determine the base config directory. This acts as the root for default paths.
-        let base_config_dir = Self::resolve_base_config_dir();
+        let base_config_dir = Self::resolve_base_config_dir(env);
         log_debug!("[SDB] Base config directory: {}", base_config_dir.display());
 
         // Resolve main configuration file path based on overrides and base directory.
-        let config_file = Self::resolve_config_file(&base_config_dir, config_path)?;
+        let config_file = Self::resolve_config_file(env, &base_config_dir, config_path)?;
 
         // Extract and validate the configuration filename.
         let config_filename = config_file
@@ -79,11 +111,17 @@ impl PathResolver {
             .ok_or("Invalid config filename")? // Return error if path ends unexpectedly (e.g., is a directory).
             .to_string();
 
-        // Resolve state file path.
-        let state_file = Self::resolve_state_file(&base_config_dir, state_path)?;
+        // Resolve state file path, named after the `state_format` of the configuration.
+        // Simulated runs keep their state next to the fixture file unless told otherwise.
+        let state_path = state_path.or_else(simulated::state_file);
+        let state_format = manager::configure_from(&config_file);
+        let state_file = Self::resolve_state_file(env, &base_config_dir, state_path, state_format)?;
 
         // Resolve tools config directory.
-        let tools_config_dir = Self::resolve_tools_config_dir(&base_config_dir);
+        let tools_config_dir = Self::resolve_tools_config_dir(env, &base_config_dir);
+
+        // Resolve cache directory.
+        let cache_dir = Self::resolve_cache_dir(env, &base_config_dir);
 
         // Log final resolved paths for debugging and user information.
         log_info!(
@@ -109,6 +147,7 @@ impl PathResolver {
             config_filename,
             state_file,
             tools_config_dir,
+            cache_dir,
         })
     }
 
@@ -133,12 +172,118 @@ impl PathResolver {
         &self.state_file
     }
 
+    /// Path of the checkpoint used to resume an interrupted `now` run (next to the state file).
+    pub fn checkpoint_file(&self) -> PathBuf {
+        self.state_file.with_file_name("now-checkpoint.json")
+    }
+
+    /// Path of the summary of the last `now` run, read by `prompt-status` (next to the state file).
+    pub fn run_summary_file(&self) -> PathBuf {
+        self.state_file.with_file_name("last-run.json")
+    }
+
+    /// Directory holding the install receipts of tools (next to the state file).
+    pub fn receipts_dir(&self) -> PathBuf {
+        self.state_file.with_file_name("receipts")
+    }
+
     /// Gets a reference to the tools configuration directory path.
     #[allow(dead_code)]
     pub fn tools_config_dir(&self) -> &Path {
         &self.tools_config_dir
     }
 
+    /// Gets a reference to the cache directory path (e.g., `~/.setup-devbox/cache`).
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
+    /// Returns the directory temporary download and extraction directories are created in.
+    ///
+    /// The work directory chosen by [`Self::configure_work_dir`] for this run, otherwise
+    /// `$SETUP_DEVBOX_HOME/tmp` when `SETUP_DEVBOX_HOME` is set, otherwise the system
+    /// temporary directory.
+    pub fn temp_root() -> PathBuf {
+        if let Some(work_dir) = WORK_DIR.get() {
+            return work_dir.clone();
+        }
+        Self::default_temp_root()
+    }
+
+    fn default_temp_root() -> PathBuf {
+        if let Some(home) = PathEnv::from_process().dir(HOME_ENV_VAR) {
+            let tmp = home.join("tmp");
+            match fs::create_dir_all(&tmp) {
+                Ok(()) => return tmp,
+                Err(e) => log_warn!(
+                    "[SDB] Failed to create {}: {}. Using the system temp directory.",
+                    tmp.display(),
+                    e
+                ),
+            }
+        }
+        env::temp_dir()
+    }
+
+    /// Chooses the work directory for this run from `work_dir` in `tools.yaml`.
+    ///
+    /// Binaries are moved from the work directory into the install directory, which is only
+    /// a cheap, atomic `rename` on the same filesystem. With `auto` (or no setting) the
+    /// default temp directory is kept if it shares a filesystem with the install directory,
+    /// and `<install dir>/.sdb-work` is used otherwise (e.g. when `/tmp` is a tmpfs).
+    pub fn configure_work_dir(setting: Option<&str>) {
+        let Some(install_dir) = Self::get_user_bin_dir() else {
+            return;
+        };
+        let Some(work_dir) =
+            Self::select_work_dir(setting, &Self::default_temp_root(), &install_dir)
+        else {
+            return;
+        };
+
+        if let Err(e) = fs::create_dir_all(&work_dir) {
+            log_warn!(
+                "[SDB] Failed to create work directory {}: {}. Using {}.",
+                work_dir.display(),
+                e,
+                Self::default_temp_root().display()
+            );
+            return;
+        }
+        if same_filesystem(&work_dir, &install_dir) == Some(false) {
+            log_warn!(
+                "[SDB] Work directory {} is not on the same filesystem as {}; installs fall back to copying",
+                work_dir.display(),
+                install_dir.display()
+            );
+        }
+        let _ = WORK_DIR.set(work_dir);
+    }
+
+    /// The work directory for a `work_dir` setting, or `None` to keep `default`.
+    fn select_work_dir(
+        setting: Option<&str>,
+        default: &Path,
+        install_dir: &Path,
+    ) -> Option<PathBuf> {
+        match setting.map(str::trim) {
+            None | Some("") | Some("auto") => {
+                if same_filesystem(default, install_dir).unwrap_or(true) {
+                    return None;
+                }
+                let work_dir = install_dir.join(".sdb-work");
+                log_debug!(
+                    "[SDB] {} is on another filesystem than {}; using {} as work directory",
+                    default.display(),
+                    install_dir.display(),
+                    work_dir.display()
+                );
+                Some(work_dir)
+            }
+            Some(path) => Some(Self::expand_tilde(path)),
+        }
+    }
+
     /// Constructs and returns the path to the 'configs' subdirectory within the base config directory.
     ///
     /// This is typically used by components like `ConfigurationUpdater`.
@@ -149,6 +294,18 @@ impl PathResolver {
         self.base_config_dir.join("configs")
     }
 
+    /// Directory holding the main config file and the configuration files next to it.
+    ///
+    /// This follows a `--config` override, so commands editing `tools.yaml`, `fonts.yaml`,
+    /// etc. work on the same files `now` reads; without one it is [`Self::configs_dir`].
+    pub fn config_file_dir(&self) -> PathBuf {
+        match self.config_file.parent() {
+            Some(dir) if dir.as_os_str().is_empty() => PathBuf::from("."),
+            Some(dir) => dir.to_path_buf(),
+            None => self.configs_dir(),
+        }
+    }
+
     /// Returns the key paths in a legacy tuple format for backwards compatibility.
     ///
     /// # Returns
@@ -166,15 +323,66 @@ impl PathResolver {
     ///
     /// Resolution priority:
     /// 1. `SDB_CONFIG_PATH` environment variable.
-    /// 2. Default: `~/.setup-devbox`.
-    fn resolve_base_config_dir() -> PathBuf {
-        if let Ok(env_path) = env::var("SDB_CONFIG_PATH") {
+    /// 2. `SETUP_DEVBOX_HOME` environment variable.
+    /// 3. `$XDG_CONFIG_HOME/setup-devbox`, unless `~/.setup-devbox` already exists.
+    /// 4. Default: `~/.setup-devbox`.
+    fn resolve_base_config_dir(env: &PathEnv) -> PathBuf {
+        if let Some(env_path) = env.var("SDB_CONFIG_PATH") {
             log_debug!("[SDB] Using SDB_CONFIG_PATH: {}", env_path.blue());
             // Expand tilde in the environment path if present.
-            return Self::expand_tilde(&env_path);
+            return Self::expand_tilde(env_path);
         }
         log_debug!("[SDB] environment variable SDB_CONFIG_PATH not set");
+        if let Some(home) = env.dir(HOME_ENV_VAR) {
+            log_debug!("[SDB] Using {}: {}", HOME_ENV_VAR, home.display());
+            return home;
+        }
+        if let Some(xdg_config) = env.xdg_dir("XDG_CONFIG_HOME") {
+            log_debug!("[SDB] Using XDG_CONFIG_HOME: {}", xdg_config.display());
+            return xdg_config;
+        }
         // Default fallback path, expanding '~' to the user's home directory.
+        env.legacy_home.clone()
+    }
+
+    /// Determines the **cache directory**.
+    ///
+    /// Resolution priority:
+    /// 1. `$SETUP_DEVBOX_HOME/cache`.
+    /// 2. `$XDG_CACHE_HOME/setup-devbox`, unless `~/.setup-devbox` already exists.
+    /// 3. Default: `$base_dir/cache`.
+    fn resolve_cache_dir(env: &PathEnv, base_dir: &Path) -> PathBuf {
+        if let Some(home) = env.dir(HOME_ENV_VAR) {
+            return home.join("cache");
+        }
+        if env.var("SDB_CONFIG_PATH").is_none()
+            && let Some(xdg_cache) = env.xdg_dir("XDG_CACHE_HOME")
+        {
+            return xdg_cache;
+        }
+        base_dir.join("cache")
+    }
+
+    /// POSIX `sh` statements that set `$<var>` to the configs directory the way
+    /// [`Self::configs_dir`] resolves it, for scripts run on another machine
+    /// (`bootstrap-script`). The checks match [`Self::resolve_base_config_dir`].
+    pub(crate) fn configs_dir_shell(var: &str) -> Vec<String> {
+        vec![
+            format!("{var}=\"${{SDB_CONFIG_PATH:-${{{HOME_ENV_VAR}:-}}}}\""),
+            format!(
+                "if [ -z \"${var}\" ]; then case \"${{XDG_CONFIG_HOME:-}}\" in /*) \
+                 [ -e \"$HOME/.setup-devbox\" ] || {var}=\"$XDG_CONFIG_HOME/{APP_DIR_NAME}\" ;; esac; fi"
+            ),
+            format!(
+                "case \"${var}\" in \"\") {var}=\"$HOME/.setup-devbox\" ;; \
+                 \"~/\"*) {var}=\"$HOME/${{{var}#\\~/}}\" ;; esac"
+            ),
+            format!("{var}=\"${var}/configs\""),
+        ]
+    }
+
+    /// The historical home of all application files, `~/.setup-devbox`.
+    fn legacy_home() -> PathBuf {
         Self::expand_tilde("~/.setup-devbox")
     }
 
@@ -185,13 +393,14 @@ impl PathResolver {
     /// 2. Path derived from `SDB_CONFIG_PATH` (`$SDB_CONFIG_PATH/configs/config.yaml`).
     /// 3. Default path derived from `base_dir` (`$base_dir/configs/config.yaml`).
     fn resolve_config_file(
+        env: &PathEnv,
         base_dir: &Path,
         user_override: Option<String>,
     ) -> Result<PathBuf, String> {
         let path = if let Some(user_path) = user_override {
             // Priority 1: User-provided path takes highest priority.
             Self::expand_tilde(&user_path)
-        } else if let Ok(env_path) = env::var("SDB_CONFIG_PATH") {
+        } else if let Some(env_path) = env.var("SDB_CONFIG_PATH") {
             // Priority 2: Use SDB_CONFIG_PATH (even if the variable was only used for the base dir).
             // Note: This logic assumes $SDB_CONFIG_PATH is the *base* directory.
             Self::expand_tilde(&format!("{env_path}/configs/config.yaml"))
@@ -214,28 +423,43 @@ impl PathResolver {
     /// 1. `user_override` argument.
     /// 2. `SDB_STATE_FILE_PATH` environment variable (`$SDB_STATE_FILE_PATH/state.json`).
     /// 3. `SDB_CONFIG_PATH` environment variable (`$SDB_CONFIG_PATH/state.json`).
-    /// 4. Default path derived from `base_dir` (`$base_dir/state.json`).
+    /// 4. `SETUP_DEVBOX_HOME` environment variable (`$SETUP_DEVBOX_HOME/state.json`).
+    /// 5. `$XDG_STATE_HOME/setup-devbox/state.json`, unless `~/.setup-devbox` already exists.
+    /// 6. Default path derived from `base_dir` (`$base_dir/state.json`).
+    ///
+    /// Outside an explicit override, the file is named after `format` (`state.yaml`,
+    /// `state.toml`) when `state_format` is set.
     fn resolve_state_file(
+        env: &PathEnv,
         base_dir: &Path,
         user_override: Option<String>,
+        format: StateFormat,
     ) -> Result<PathBuf, String> {
+        let file_name = format!("state.{}", format.extension());
         let path = if let Some(user_path) = user_override {
             // Priority 1: User-provided path takes highest priority.
             Self::expand_tilde(&user_path)
-        } else if let Ok(env_path) = env::var("SDB_STATE_FILE_PATH") {
+        } else if let Some(env_path) = env.var("SDB_STATE_FILE_PATH") {
             // Priority 2: Dedicated state file environment variable.
             log_debug!(
                 "[SDB] Using {} for state file",
                 "SDB_STATE_FILE_PATH".cyan()
             );
-            Self::expand_tilde(&format!("{env_path}/state.json"))
-        } else if let Ok(env_path) = env::var("SDB_CONFIG_PATH") {
+            Self::expand_tilde(&format!("{env_path}/{file_name}"))
+        } else if let Some(env_path) = env.var("SDB_CONFIG_PATH") {
             // Priority 3: Fallback to the general config path environment variable.
             log_debug!("[SDB] Using {} for state file", "SDB_CONFIG_PATH".cyan());
-            Self::expand_tilde(&format!("{env_path}/state.json"))
+            Self::expand_tilde(&format!("{env_path}/{file_name}"))
+        } else if let Some(home) = env.dir(HOME_ENV_VAR) {
+            // Priority 4: Everything relocated under SETUP_DEVBOX_HOME.
+            home.join(&file_name)
+        } else if let Some(xdg_state) = env.xdg_dir("XDG_STATE_HOME") {
+            // Priority 5: XDG state directory.
+            log_debug!("[SDB] Using {} for state file", "XDG_STATE_HOME".cyan());
+            xdg_state.join(&file_name)
         } else {
-            // Priority 4: Default path relative to the resolved base config directory.
-            base_dir.join("state.json")
+            // Priority 6: Default path relative to the resolved base config directory.
+            base_dir.join(&file_name)
         };
 
         // Basic validation: ensure the resulting path is not empty.
@@ -251,10 +475,10 @@ impl PathResolver {
     /// Resolution priority:
     /// 1. `SDB_TOOLS_SOURCE_CONFIG_PATH` environment variable (full path).
     /// 2. Default: `$base_dir/configs/tools`.
-    fn resolve_tools_config_dir(base_dir: &Path) -> PathBuf {
+    fn resolve_tools_config_dir(env: &PathEnv, base_dir: &Path) -> PathBuf {
         // Priority 1: SDB_TOOLS_SOURCE_CONFIG_PATH
-        if let Ok(env_path) = env::var("SDB_TOOLS_SOURCE_CONFIG_PATH") {
-            match Self::expand_path(&env_path) {
+        if let Some(env_path) = env.var("SDB_TOOLS_SOURCE_CONFIG_PATH") {
+            match Self::expand_path(env_path) {
                 Ok(expanded) => {
                     log_debug!("[SDB] Using {}", "SDB_TOOLS_SOURCE_CONFIG_PATH".cyan());
                     return expanded;
@@ -363,6 +587,22 @@ impl PathResolver {
         paths.iter().map(|path| Self::expand_path(path)).collect()
     }
 
+    /// The font directory of `scope`: the user's (see [`Self::get_font_installation_dir`]) or
+    /// the machine-wide one, which is not created here since it usually needs `sudo`.
+    pub fn font_installation_dir(scope: FontScope) -> io::Result<PathBuf> {
+        match scope {
+            FontScope::User => Self::get_font_installation_dir(),
+            FontScope::System if cfg!(target_os = "macos") => Ok(PathBuf::from("/Library/Fonts")),
+            FontScope::System if cfg!(target_os = "linux") => {
+                Ok(PathBuf::from("/usr/local/share/fonts"))
+            }
+            FontScope::System => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "[SDB] System-wide fonts are not supported on this operating system",
+            )),
+        }
+    }
+
     /// Determines the correct font installation directory for the current operating system.
     ///
     /// For macOS, this is `~/Library/Fonts`. This function also ensures the directory exists,
@@ -420,13 +660,12 @@ impl PathResolver {
         let font_dir = home_dir.join(".local").join("share").join("fonts");
 
         // Ensure the directory exists.
-        fs::create_dir_all(&font_dir).map_err(|e| {
+        fs::create_dir_all(&font_dir).inspect_err(|e| {
             log_error!(
                 "[SDB] Failed to create font installation directory '{}': {}",
                 font_dir.display(),
                 e.to_string().red()
             );
-            e
         })?;
 
         log_debug!(
@@ -514,7 +753,17 @@ impl PathResolver {
         extracted_path.to_path_buf()
     }
 
-    pub fn get_user_home_dir() -> Option<PathBuf> {
+    /// The bin directory GitHub and URL tools are installed into: `~/bin/`, or
+    /// `%LOCALAPPDATA%\setup-devbox\bin` on Windows.
+    pub fn get_user_bin_dir() -> Option<PathBuf> {
+        if cfg!(windows) {
+            let bin_dir = windows::bin_dir();
+            if bin_dir.is_none() {
+                log_error!("[SDB] Cannot determine installation path without %LOCALAPPDATA%");
+            }
+            return bin_dir;
+        }
+
         let home_dir = env::var("HOME")
             .map_err(|_| {
                 log_warn!("[SDB] User $HOME environment variable not set");
@@ -565,7 +814,8 @@ impl PathResolver {
             &tool_entry.name
         };
 
-        let file_path = base_path.join(filename);
+        // Executables need their `.exe` extension on Windows
+        let file_path = base_path.join(windows::platform_file_name(filename));
 
         log_debug!(
             "[SDB] Final file path determined: {}",
@@ -575,3 +825,199 @@ impl PathResolver {
         file_path
     }
 }
+
+/// The environment path resolution depends on: its variables and the location of the legacy
+/// `~/.setup-devbox`. Read from the process for [`PathResolver::new`].
+struct PathEnv {
+    vars: HashMap<String, String>,
+    legacy_home: PathBuf,
+}
+
+impl PathEnv {
+    fn from_process() -> Self {
+        PathEnv {
+            vars: env::vars_os()
+                .filter_map(|(name, value)| {
+                    Some((name.into_string().ok()?, value.into_string().ok()?))
+                })
+                .collect(),
+            legacy_home: PathResolver::legacy_home(),
+        }
+    }
+
+    fn var(&self, name: &str) -> Option<&str> {
+        self.vars.get(name).map(String::as_str)
+    }
+
+    /// Reads a directory from a non-empty environment variable, expanding `~`.
+    fn dir(&self, name: &str) -> Option<PathBuf> {
+        self.var(name)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(PathResolver::expand_tilde)
+    }
+
+    /// Returns `$<name>/setup-devbox` for an XDG base directory variable.
+    ///
+    /// Existing `~/.setup-devbox` installations keep their layout, so XDG variables only
+    /// apply when that directory does not exist.
+    fn xdg_dir(&self, name: &str) -> Option<PathBuf> {
+        if self.legacy_home.exists() {
+            return None;
+        }
+        self.dir(name)
+            .filter(|dir| dir.is_absolute())
+            .map(|dir| dir.join(APP_DIR_NAME))
+    }
+}
+
+/// Whether `a` and `b` (or their nearest existing ancestors) are on the same filesystem.
+/// `None` when this cannot be determined.
+#[cfg(unix)]
+fn same_filesystem(a: &Path, b: &Path) -> Option<bool> {
+    use std::os::unix::fs::MetadataExt;
+
+    let device = |path: &Path| {
+        path.ancestors()
+            .find_map(|dir| fs::metadata(dir).ok())
+            .map(|metadata| metadata.dev())
+    };
+    Some(device(a)? == device(b)?)
+}
+
+#[cfg(not(unix))]
+fn same_filesystem(_a: &Path, _b: &Path) -> Option<bool> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path_env(root: &Path, vars: &[(&str, &str)]) -> PathEnv {
+        PathEnv {
+            vars: vars
+                .iter()
+                .map(|(name, value)| {
+                    (
+                        name.to_string(),
+                        value.replace("{root}", &root.display().to_string()),
+                    )
+                })
+                .collect(),
+            legacy_home: root.join("home/.setup-devbox"),
+        }
+    }
+
+    const XDG: [(&str, &str); 3] = [
+        ("XDG_CONFIG_HOME", "{root}/xdg/config"),
+        ("XDG_STATE_HOME", "{root}/xdg/state"),
+        ("XDG_CACHE_HOME", "{root}/xdg/cache"),
+    ];
+
+    #[test]
+    fn test_setup_devbox_home_takes_precedence() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path();
+        let mut vars = XDG.to_vec();
+        vars.push((HOME_ENV_VAR, "{root}/sdb"));
+
+        // An existing ~/.setup-devbox does not keep SETUP_DEVBOX_HOME from applying
+        fs::create_dir_all(root.join("home/.setup-devbox")).unwrap();
+        let paths = PathResolver::from_env(&path_env(root, &vars), None, None).unwrap();
+
+        assert_eq!(paths.base_config_dir(), root.join("sdb"));
+        assert_eq!(paths.config_file(), root.join("sdb/configs/config.yaml"));
+        assert_eq!(paths.state_file(), root.join("sdb/state.json"));
+        assert_eq!(paths.cache_dir(), root.join("sdb/cache"));
+    }
+
+    #[test]
+    fn test_xdg_dirs_apply_without_legacy_home() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path();
+        let paths = PathResolver::from_env(&path_env(root, &XDG), None, None).unwrap();
+
+        assert_eq!(
+            paths.config_file(),
+            root.join("xdg/config/setup-devbox/configs/config.yaml")
+        );
+        assert_eq!(
+            paths.state_file(),
+            root.join("xdg/state/setup-devbox/state.json")
+        );
+        assert_eq!(paths.cache_dir(), root.join("xdg/cache/setup-devbox"));
+    }
+
+    #[test]
+    fn test_existing_legacy_home_keeps_its_layout() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path();
+        let legacy = root.join("home/.setup-devbox");
+        fs::create_dir_all(&legacy).unwrap();
+        let paths = PathResolver::from_env(&path_env(root, &XDG), None, None).unwrap();
+
+        assert_eq!(paths.config_file(), legacy.join("configs/config.yaml"));
+        assert_eq!(paths.state_file(), legacy.join("state.json"));
+        assert_eq!(paths.cache_dir(), legacy.join("cache"));
+    }
+
+    #[test]
+    fn test_empty_setup_devbox_home_is_ignored() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path();
+
+        let mut vars = XDG.to_vec();
+        vars.push((HOME_ENV_VAR, "  "));
+        let paths = PathResolver::from_env(&path_env(root, &vars), None, None).unwrap();
+        assert_eq!(
+            paths.state_file(),
+            root.join("xdg/state/setup-devbox/state.json")
+        );
+
+        // Without XDG variables (or with relative ones) everything falls back to ~/.setup-devbox
+        let vars = [(HOME_ENV_VAR, ""), ("XDG_STATE_HOME", "relative/state")];
+        let paths = PathResolver::from_env(&path_env(root, &vars), None, None).unwrap();
+        assert_eq!(paths.base_config_dir(), root.join("home/.setup-devbox"));
+        assert_eq!(
+            paths.state_file(),
+            root.join("home/.setup-devbox/state.json")
+        );
+    }
+
+    #[test]
+    fn test_work_dir_selection() {
+        let root = tempfile::tempdir().unwrap();
+        let default = root.path().join("tmp");
+        let install_dir = root.path().join("bin");
+
+        // Directories that do not exist yet are compared through their parents
+        assert_eq!(same_filesystem(&default, &install_dir), Some(true));
+        assert_eq!(
+            PathResolver::select_work_dir(None, &default, &install_dir),
+            None
+        );
+        assert_eq!(
+            PathResolver::select_work_dir(Some(" auto "), &default, &install_dir),
+            None
+        );
+        assert_eq!(
+            PathResolver::select_work_dir(Some("/var/tmp/sdb"), &default, &install_dir),
+            Some(PathBuf::from("/var/tmp/sdb"))
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_work_dir_moves_next_to_install_dir_across_filesystems() {
+        let root = tempfile::tempdir().unwrap();
+        let install_dir = root.path().join("bin");
+        let other_filesystem = Path::new("/proc/sdb-tmp");
+
+        assert_eq!(same_filesystem(other_filesystem, &install_dir), Some(false));
+        assert_eq!(
+            PathResolver::select_work_dir(None, other_filesystem, &install_dir),
+            Some(install_dir.join(".sdb-work"))
+        );
+    }
+}
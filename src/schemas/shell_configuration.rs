@@ -63,9 +63,19 @@ pub struct ShellRunCommands {
     /// ## Supported Shells
     /// - `"bash"`: Bourne Again Shell (Linux/macOS)
     /// - `"zsh"`: Z Shell (macOS default, popular alternative)
-    /// - Other shells with RC file support
+    /// - `"fish"`: Friendly Interactive Shell (`~/.config/fish/config.fish`)
+    ///
+    /// May be omitted when `shells` is set.
+    #[serde(default)]
     pub shell: String,
 
+    /// Several shells to configure in one run, e.g. `["zsh", "fish"]`. Takes precedence over
+    /// `shell`; every run command and alias is rendered in each shell's syntax and written
+    /// to its RC file.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shells: Option<Vec<String>>,
+
     /// List of run command entries organized by section.
     ///
     /// Each entry defines a shell command and its logical section grouping.
@@ -79,6 +89,16 @@ pub struct ShellRunCommands {
     pub run_commands: Vec<RunCommandEntry>,
 }
 
+impl ShellRunCommands {
+    /// The shells to configure: `shells` if set, otherwise `shell`.
+    pub fn targets(&self) -> Vec<String> {
+        match &self.shells {
+            Some(shells) if !shells.is_empty() => shells.clone(),
+            _ => vec![self.shell.clone()],
+        }
+    }
+}
+
 // ============================================================================
 // INDIVIDUAL COMMAND ENTRIES
 // ============================================================================
@@ -97,7 +117,7 @@ pub struct ShellRunCommands {
 /// - Function definitions
 /// - Alias definitions (though aliases have a separate dedicated system)
 /// - Miscellaneous shell commands
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RunCommandEntry {
     /// The actual shell command to be added to the RC file.
     ///
@@ -150,6 +170,18 @@ pub struct RunCommandEntry {
     /// alias cat='bat --paging=never'
     /// ```
     pub section: ConfigSection,
+
+    /// Limits the command to these shells when several are configured, e.g. for
+    /// functions written in one shell's syntax. Defaults to every configured shell.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shells: Option<Vec<String>>,
+
+    /// Profiles the command belongs to; `now --profile <NAME>` only writes commands of that
+    /// profile and commands without `profiles`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profiles: Option<Vec<String>>,
 }
 
 // ============================================================================
@@ -193,6 +225,71 @@ pub struct ShellConfig {
     /// Aliases are added to the shell RC file in a dedicated aliases section,
     /// ensuring they are available in new shell sessions.
     pub aliases: Vec<AliasEntry>,
+
+    /// Common history and XDG settings, rendered in each shell's syntax into the RC file.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shell_behavior: Option<ShellBehavior>,
+
+    /// Write PATH and exported variables to the login profile (`~/.zprofile`,
+    /// `~/.bash_profile`) and everything else to the RC file, instead of everything to the RC
+    /// file. The bash profile also gets a line sourcing `~/.bashrc`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub split_login_profile: Option<bool>,
+}
+
+// ============================================================================
+// SHELL BEHAVIOR SETTINGS
+// ============================================================================
+
+/// Declarative shell history and XDG base directory settings (`shell_behavior:` in
+/// `shellrc.yaml`).
+///
+/// Each knob is rendered for the configured shells: variables for bash, variables and
+/// `setopt` options for zsh, and `set -gx` for fish, whose history already drops duplicates
+/// and space-prefixed commands.
+///
+/// ```yaml
+/// shell_behavior:
+///   history_size: 100000
+///   history_ignore_duplicates: true
+///   history_ignore_space: true
+///   share_history: true
+///   xdg_base_dirs: true
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ShellBehavior {
+    /// Entries kept in memory and in the history file (`HISTSIZE`, `HISTFILESIZE`/`SAVEHIST`).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history_size: Option<u32>,
+
+    /// History file location (`HISTFILE`), e.g. `$XDG_STATE_HOME/zsh/history`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history_file: Option<String>,
+
+    /// Don't record a command that repeats one already in the history.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history_ignore_duplicates: Option<bool>,
+
+    /// Don't record commands starting with a space.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history_ignore_space: Option<bool>,
+
+    /// Append to the history file and share it between concurrent sessions.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub share_history: Option<bool>,
+
+    /// Export the XDG base directories (`XDG_CONFIG_HOME`, `XDG_DATA_HOME`,
+    /// `XDG_STATE_HOME`, `XDG_CACHE_HOME`) with their default locations.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xdg_base_dirs: Option<bool>,
 }
 
 // ============================================================================
@@ -349,4 +446,10 @@ pub struct AliasEntry {
     /// - Complex sequence: `"git add . && git commit -m \"quick save\" && git push"`
     /// - Piped commands: `"docker ps -a | grep exited"`
     pub value: String,
+
+    /// Profiles the alias belongs to; `now --profile <NAME>` only writes aliases of that
+    /// profile and aliases without `profiles`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profiles: Option<Vec<String>>,
 }
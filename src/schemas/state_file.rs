@@ -40,8 +40,10 @@
 //! - Stores original parameters for reinstallation scenarios
 
 use crate::engine::configuration::processor::ConfigurationManagerState;
+use crate::schemas::fonts::FontScope;
+use crate::schemas::tools_enums::{OptimizeBinary, Permission};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 // ============================================================================
 // TOP-LEVEL STATE STRUCTURE
@@ -114,7 +116,7 @@ pub struct DevBoxState {
     /// ## Key Structure
     /// Uses the tool's canonical name (as specified in the configuration)
     /// regardless of any `rename_to` operations during installation.
-    pub tools: HashMap<String, ToolState>,
+    pub tools: BTreeMap<String, ToolState>,
 
     /// Records applied system settings, keyed by setting domain:key combination.
     ///
@@ -124,7 +126,7 @@ pub struct DevBoxState {
     /// ## Key Format
     /// Uses `"{domain}:{key}"` format for unique identification of settings.
     /// Example: `"com.apple.finder:AppleShowAllFiles"`
-    pub settings: HashMap<String, SettingState>,
+    pub settings: BTreeMap<String, SettingState>,
 
     /// Stores information about installed fonts, keyed by font name.
     ///
@@ -132,7 +134,75 @@ pub struct DevBoxState {
     /// - Font family names and file lists
     /// - Source URLs and version information
     /// - GitHub repository details for font packages
-    pub fonts: HashMap<String, FontState>,
+    pub fonts: BTreeMap<String, FontState>,
+
+    /// Records installed user agents (LaunchAgents / systemd user units), keyed by label.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub launch_agents: BTreeMap<String, LaunchAgentState>,
+
+    /// Records applied default applications, keyed by content type.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub file_associations: BTreeMap<String, FileAssociationState>,
+
+    /// Audit log of changes to protected entries made with `--force-protected`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub protected_changes: Vec<ProtectedChange>,
+
+    /// Tools installed with `now --force` over another tool's executable name.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub name_collisions: Vec<NameCollision>,
+
+    /// Whether this machine is part of the `rollout` of tools that have one, keyed by tool name.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub rollouts: BTreeMap<String, RolloutDecision>,
+}
+
+/// A change to a protected entry, recorded with the reason given for it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProtectedChange {
+    /// Name of the tool.
+    pub name: String,
+    /// What was done, e.g. `remove`, `update` or `unprotect`.
+    pub change: String,
+    /// The `--reason` given.
+    pub reason: String,
+    /// User who made the change.
+    pub user: String,
+    /// When the change was made (RFC 3339).
+    pub changed_at: String,
+}
+
+/// A `rename_to` that took over the executable of another tool, allowed with `--force`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NameCollision {
+    /// The executable name both tools use.
+    pub executable: String,
+    /// Tool that was installed under the name.
+    pub name: String,
+    /// Tool whose executable was replaced.
+    pub replaced: String,
+    /// User who forced the installation.
+    pub user: String,
+    /// When it was forced (RFC 3339).
+    pub forced_at: String,
+}
+
+/// The rollout decision for a tool on this machine.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RolloutDecision {
+    /// The tool's `rollout` value (`10%`, `canary`).
+    pub rollout: String,
+    /// Whether this machine is part of the rollout.
+    pub included: bool,
+    /// This machine's rollout bucket (0-99).
+    pub bucket: u8,
+    /// When the decision was made or last changed (RFC 3339).
+    pub decided_at: String,
 }
 
 // ============================================================================
@@ -324,6 +394,202 @@ pub struct ToolState {
     /// without configuration management.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub configuration_manager: Option<ConfigurationManagerState>,
+
+    /// Additional release assets installed together with the tool.
+    ///
+    /// Populated for GitHub tools that declare an `assets:` list, so that completions,
+    /// man pages or data files are tracked (and removable) under the same tool entry.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assets: Option<Vec<InstalledAssetState>>,
+
+    /// `ETag` returned by the server when a URL tool was last downloaded.
+    ///
+    /// Sent back as `If-None-Match` on later runs of `latest` URL tools; a `304 Not Modified`
+    /// response means the tool is skipped without downloading anything.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+
+    /// `Last-Modified` returned by the server when a URL tool was last downloaded.
+    ///
+    /// Sent back as `If-Modified-Since` alongside (or instead of) the `ETag`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+
+    /// Commit the tool was built from, for go and cargo tools pinned with
+    /// `version: commit:<hash>`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+
+    /// Shim script generated for the tool (tools with a `shim:` block).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shim: Option<ShimState>,
+
+    /// License of the tool, as detected by GitHub for its repository.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<LicenseState>,
+
+    /// `notes` of the configuration entry, kept so `sync-config` can restore it.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+
+    /// `owner` of the configuration entry, kept so `sync-config` can restore it.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+
+    /// Whether the configuration entry was marked `protected: true`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protected: Option<bool>,
+
+    /// Receipt listing every file the installation created or modified
+    /// (`<state dir>/receipts/<tool>.json`).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt: Option<String>,
+
+    /// How long the last installation or update took, in milliseconds; used to estimate
+    /// the remaining time of later runs.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub install_duration_ms: Option<u64>,
+
+    /// Bytes the installation occupied on disk when it was recorded: executable, extra
+    /// assets, shim and cached download (see `setup-devbox du` for current sizes).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disk_usage_bytes: Option<u64>,
+
+    /// macOS permissions from the entry's `permissions:` the user confirmed granting, with
+    /// when they did.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permissions_acknowledged: Option<BTreeMap<Permission, String>>,
+
+    /// Earlier executable names of the tool, oldest first, recorded when its `rename_to`
+    /// changed.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rename_history: Vec<RenameRecord>,
+
+    /// Sizes and checksums of the executable before and after `optimize_binary` processed it.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub optimized_binary: Option<OptimizedBinaryState>,
+
+    /// Application bundle installed for an `app_bundle` tool, whose embedded CLI the
+    /// `install_path` links to.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_bundle: Option<AppBundleState>,
+
+    /// Version of the external command the installer ran (`brew`, `cargo`, ...) when the tool
+    /// was last installed or updated.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installer_version: Option<InstallerVersionState>,
+}
+
+/// A change of a tool's executable name (see `ToolState::rename_history`).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RenameRecord {
+    /// Executable name before the change.
+    pub from: String,
+    /// Executable name after the change.
+    pub to: String,
+    /// Files left under the old name that were removed.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub removed: Vec<String>,
+    /// When the tool was reinstalled under the new name (RFC 3339).
+    pub renamed_at: String,
+}
+
+/// An executable shrunk by `optimize_binary` (see `ToolState::optimized_binary`).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct OptimizedBinaryState {
+    /// How the executable was processed (`strip` or `upx`).
+    pub method: OptimizeBinary,
+    /// Size of the executable as downloaded, in bytes.
+    pub original_size: u64,
+    /// SHA-256 of the executable as downloaded.
+    pub original_sha256: String,
+    /// Size of the installed executable, in bytes.
+    pub optimized_size: u64,
+    /// SHA-256 of the installed executable.
+    pub optimized_sha256: String,
+}
+
+/// An application bundle installed for a tool (see `ToolState::app_bundle`).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AppBundleState {
+    /// Path of the installed `.app` bundle.
+    pub path: String,
+    /// Path of the linked command line interface inside the bundle.
+    pub cli: String,
+}
+
+/// Version of an installer's external command (see `ToolState::installer_version`).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct InstallerVersionState {
+    /// The command, e.g. `brew`.
+    pub command: String,
+    /// The version it reported, e.g. `4.4.1`.
+    pub version: String,
+}
+
+/// License recorded for an installed tool or font (listed by `setup-devbox licenses`).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LicenseState {
+    /// License name (e.g., `"MIT License"`, `"SIL Open Font License 1.1"`).
+    pub name: String,
+    /// SPDX identifier (e.g., `"MIT"`, `"OFL-1.1"`), when GitHub recognized the license.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spdx_id: Option<String>,
+    /// Link to the license file.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// When the license was accepted, for entries with `requires_license_acceptance`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accepted_at: Option<String>,
+}
+
+/// Records a shim script generated for a tool (see `ToolState::shim`).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ShimState {
+    /// Path of the shim script.
+    pub path: String,
+    /// Executable the shim launches.
+    pub target: String,
+    /// Environment variables the shim exports.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// Working directory the shim changes into.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<String>,
+}
+
+/// Records one extra asset installed for a tool (see `ToolState::assets`).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct InstalledAssetState {
+    /// Release asset file name that was downloaded.
+    pub name: String,
+    /// Download URL of the asset.
+    pub url: String,
+    /// Paths written on disk for this asset (files or directories).
+    pub installed_paths: Vec<String>,
 }
 
 // ============================================================================
@@ -380,6 +646,42 @@ pub struct SettingState {
     pub value_type: String,
 }
 
+// ============================================================================
+// LAUNCH AGENT STATE
+// ============================================================================
+
+/// Records a user agent installed from the `launch_agents:` section of `settings.yaml`.
+///
+/// The content hash lets `now` skip agents whose generated definition has not changed,
+/// and the recorded path lets `remove launch-agent` unload and delete the right file.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LaunchAgentState {
+    /// Agent label (e.g., `"com.example.syncthing"`).
+    pub label: String,
+    /// Path of the generated definition (e.g., `~/Library/LaunchAgents/<label>.plist`
+    /// or `~/.config/systemd/user/<label>.service`).
+    pub path: String,
+    /// SHA-256 of the generated definition.
+    pub content_hash: String,
+    /// Timestamp of the last install or update (RFC 3339).
+    pub installed_at: String,
+}
+
+// ============================================================================
+// FILE ASSOCIATION STATE
+// ============================================================================
+
+/// Records the default application applied for a file type or URL scheme.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FileAssociationState {
+    /// Bundle identifier of the handler application.
+    pub app: String,
+    /// Handler role the association was applied with.
+    pub role: String,
+    /// Timestamp of when the association was applied (RFC 3339).
+    pub applied_at: String,
+}
+
 // ============================================================================
 // FONT MANAGEMENT STATE
 // ============================================================================
@@ -462,4 +764,51 @@ pub struct FontState {
     /// `#[serde(default)]` ensures empty values are stored as `None`.
     #[serde(default)]
     pub install_only: Option<Vec<String>>,
+
+    /// Nerd Fonts variant the font was installed with (see `FontEntry::variant`).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+
+    /// License of the font, as detected by GitHub for its repository.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<LicenseState>,
+
+    /// `notes` of the configuration entry, kept so `sync-config` can restore it.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+
+    /// `owner` of the configuration entry, kept so `sync-config` can restore it.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+
+    /// Font directory the files were installed to; `None` for the user's.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<FontScope>,
+
+    /// Bytes the installed font files occupied on disk when they were recorded.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disk_usage_bytes: Option<u64>,
+
+    /// Files of an earlier version of the family that this installation replaced.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replaced: Option<FontReplacement>,
+}
+
+/// Records the older files of a font family removed when a new version was installed, so
+/// terminal font pickers don't list both versions.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FontReplacement {
+    /// Version recorded before the update; `None` when the files were found on disk only.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_version: Option<String>,
+    /// Font files removed from the font directory.
+    pub removed_files: Vec<String>,
 }
@@ -86,8 +86,154 @@ pub enum SourceType {
     Go,     // Go language tooling
     Rustup, // Rust toolchain manager
     Url,    // Direct URL downloads
+    Oci,    // OCI registry artifacts (ORAS)
     Uv,     // Python package manager
     Pip,    // Python package installer
+    #[serde(rename = "path-entry")]
+    PathEntry, // Directory put on PATH; its tools are installed by something else
+}
+
+/// Strategy used by `find_executable` to pick the binary out of an extracted archive.
+///
+/// Configured per tool with `executable_detection:` in `tools.yaml`.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecutableDetection {
+    /// Only files named exactly like the tool (or its `rename_to`) are considered.
+    Strict,
+    /// Any ELF/Mach-O binary or shebang script is a candidate; name matches and native
+    /// binaries are preferred, then larger files.
+    #[default]
+    Heuristic,
+}
+
+/// Runtime that launches tools distributed as WebAssembly modules.
+///
+/// Configured per tool with `wasm_runtime:` in `tools.yaml`; `.wasm` tools without it use
+/// `wasmtime`.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WasmRuntime {
+    #[default]
+    Wasmtime,
+    Wasmer,
+}
+
+impl fmt::Display for WasmRuntime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WasmRuntime::Wasmtime => write!(f, "wasmtime"),
+            WasmRuntime::Wasmer => write!(f, "wasmer"),
+        }
+    }
+}
+
+/// Installation phase a tool belongs to in the `now` pipeline.
+///
+/// Phases run in declaration order (`bootstrap` → `core` → `extras`), so prerequisites such
+/// as `rustup`, `uv` or brew taps can be placed in `bootstrap` and are guaranteed to be
+/// installed before anything that relies on them. Tools without a `phase:` are `core`.
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolPhase {
+    Bootstrap,
+    #[default]
+    Core,
+    Extras,
+}
+
+impl fmt::Display for ToolPhase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ToolPhase::Bootstrap => write!(f, "bootstrap"),
+            ToolPhase::Core => write!(f, "core"),
+            ToolPhase::Extras => write!(f, "extras"),
+        }
+    }
+}
+
+/// Built-in knowledge about a popular macOS window manager or keyboard tool.
+///
+/// Set with `preset:` on a tool entry; the preset supplies the tool's configuration file
+/// location for the configuration manager and restarts or reloads its service after the
+/// tool or its configuration changes.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolPreset {
+    Aerospace,
+    Yabai,
+    Skhd,
+    Karabiner,
+}
+
+impl fmt::Display for ToolPreset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ToolPreset::Aerospace => write!(f, "aerospace"),
+            ToolPreset::Yabai => write!(f, "yabai"),
+            ToolPreset::Skhd => write!(f, "skhd"),
+            ToolPreset::Karabiner => write!(f, "karabiner"),
+        }
+    }
+}
+
+/// macOS privacy permission a tool needs to work, granted by the user in System Settings.
+///
+/// Declared per tool with `permissions:` in `tools.yaml`; after installing, the matching
+/// Privacy & Security pane is opened and the user's acknowledgment is recorded in state.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    FullDiskAccess,
+    ScreenRecording,
+    Accessibility,
+    InputMonitoring,
+    Camera,
+    Microphone,
+    Automation,
+    DeveloperTools,
+    SystemExtension,
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Permission::FullDiskAccess => write!(f, "Full Disk Access"),
+            Permission::ScreenRecording => write!(f, "Screen Recording"),
+            Permission::Accessibility => write!(f, "Accessibility"),
+            Permission::InputMonitoring => write!(f, "Input Monitoring"),
+            Permission::Camera => write!(f, "Camera"),
+            Permission::Microphone => write!(f, "Microphone"),
+            Permission::Automation => write!(f, "Automation"),
+            Permission::DeveloperTools => write!(f, "Developer Tools"),
+            Permission::SystemExtension => write!(f, "System Extensions"),
+        }
+    }
+}
+
+/// Post-install processing of a downloaded executable to save disk space.
+///
+/// Set per tool with `optimize_binary:` in `tools.yaml`; only native executables installed
+/// from GitHub releases, URLs or OCI artifacts are processed.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum OptimizeBinary {
+    #[default]
+    None,
+    Strip,
+    Upx,
+}
+
+impl fmt::Display for OptimizeBinary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OptimizeBinary::None => write!(f, "none"),
+            OptimizeBinary::Strip => write!(f, "strip"),
+            OptimizeBinary::Upx => write!(f, "upx"),
+        }
+    }
 }
 
 /// Implementation of string parsing for SourceType enum.
@@ -111,11 +257,22 @@ impl FromStr for SourceType {
             "go" => Ok(SourceType::Go),
             "rustup" => Ok(SourceType::Rustup),
             "url" => Ok(SourceType::Url),
+            "oci" => Ok(SourceType::Oci),
             "uv" => Ok(SourceType::Uv),
             "pip" => Ok(SourceType::Pip),
+            "path-entry" => Ok(SourceType::PathEntry),
             _ => {
                 let valid_types = [
-                    "brew", "cargo", "github", "go", "rustup", "url", "uv", "pip",
+                    "brew",
+                    "cargo",
+                    "github",
+                    "go",
+                    "rustup",
+                    "url",
+                    "oci",
+                    "uv",
+                    "pip",
+                    "path-entry",
                 ]
                 .join(", ");
                 Err(format!(
@@ -138,8 +295,10 @@ impl fmt::Display for SourceType {
             SourceType::Go => write!(f, "go"),
             SourceType::Rustup => write!(f, "rustup"),
             SourceType::Url => write!(f, "url"),
+            SourceType::Oci => write!(f, "oci"),
             SourceType::Uv => write!(f, "uv"),
             SourceType::Pip => write!(f, "pip"),
+            SourceType::PathEntry => write!(f, "path-entry"),
         }
     }
 }
@@ -158,6 +317,32 @@ pub enum InstallerError {
 pub enum ToolEntryError {
     #[error("Missing required field: {0}")]
     MissingField(&'static str),
+    #[error("'version: commit:<hash>' is only supported for go and cargo tools, not {0}")]
+    CommitPinUnsupported(SourceType),
+    #[error("'{0}' is not a valid commit hash (expected 7-40 hexadecimal characters)")]
+    InvalidCommit(String),
+    #[error(
+        "'{0}' is not a valid platform (expected an OS like 'macos' or 'linux', optionally followed by an architecture, e.g. 'macos-arm64')"
+    )]
+    UnknownPlatform(String),
+    #[error("Invalid requires_env entry: {0}")]
+    InvalidRequirement(String),
+    #[error("Invalid post-installation hook: {0}")]
+    InvalidHook(String),
+    #[error(
+        "Dangerous post-installation hook: {0}; set 'acknowledge_dangerous: true' on the entry or pass --allow-dangerous-hooks"
+    )]
+    DangerousHook(String),
+    #[error("Invalid configuration_manager path: {0}")]
+    InvalidConfigurationPath(String),
+    #[error("Invalid checksum: {0}")]
+    InvalidChecksum(String),
+    #[error("Invalid rollout: {0}")]
+    InvalidRollout(String),
+    #[error("'optimize_binary' is only supported for github, url and oci tools, not {0}")]
+    OptimizeBinaryUnsupported(SourceType),
+    #[error("'app_bundle' is only supported for github and url tools, not {0}")]
+    AppBundleUnsupported(SourceType),
 }
 
 // =========================================================================== //
@@ -175,6 +360,21 @@ pub enum ToolProcessingResult {
     DryRun(String),
 }
 
+impl ToolProcessingResult {
+    /// Short outcome label used in per-phase summaries.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ToolProcessingResult::Installed => "installed",
+            ToolProcessingResult::Updated => "updated",
+            ToolProcessingResult::ConfigurationUpdated => "configuration updated",
+            ToolProcessingResult::Skipped(_) => "skipped",
+            ToolProcessingResult::ConfigurationSkipped(_) => "skipped",
+            ToolProcessingResult::Failed(_) => "failed",
+            ToolProcessingResult::DryRun(_) => "dry-run",
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum ToolAction {
     Install,
@@ -1,14 +1,48 @@
+use crate::core::checksum::Checksum;
+use crate::core::dangerous_hooks::{self, dangerous_reason};
+use crate::core::platform::{command_in_environment, parse_platform_key};
+use crate::core::requirements::Requirement;
+use crate::core::rollout::Rollout;
 use crate::engine::configuration::processor::{
-    ConfigurationManager, ConfigurationManagerProcessor,
+    ConfigurationManager, ConfigurationManagerProcessor, ConfigurationPath,
 };
 use crate::engine::installers::factory::InstallerFactory;
 use crate::schemas::state_file::DevBoxState;
-use crate::schemas::tools_enums::{SdbDuration, SourceType, ToolEntryError};
+use crate::schemas::tools_enums::{
+    ExecutableDetection, OptimizeBinary, Permission, SdbDuration, SourceType, ToolEntryError,
+    ToolPhase, ToolPreset, WasmRuntime,
+};
+use crate::state::autosave::StateAutosave;
+use crate::state::checkpoint::RunCheckpoint;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ToolConfig {
     pub update_latest_only_after: Option<SdbDuration>,
+    /// How long upstream release metadata is reused before it is fetched again (default `15m`).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata_cache_ttl: Option<SdbDuration>,
+    /// Hosts (`cdn.example.com` or `*.example.com`) downloads may be redirected to, in
+    /// addition to GitHub's own.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_redirect_hosts: Option<Vec<String>>,
+    /// Where downloads are extracted: `auto` (default) uses a directory next to the install
+    /// directory when the system temp directory is on another filesystem; a path is used as is.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub work_dir: Option<String>,
+    /// Run `brew cleanup --prune <days>` after a run installed or updated brew tools.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub brew_cleanup: Option<bool>,
+    /// Age in days of the Homebrew downloads `brew_cleanup` and `setup-devbox clean` remove
+    /// (default 30).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub brew_cleanup_prune_days: Option<u32>,
     pub tools: Vec<ToolEntry>,
 }
 
@@ -33,17 +67,570 @@ pub struct ToolEntry {
     pub executable_path_after_extract: Option<String>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub post_installation_hooks: Option<Vec<String>>,
+    pub post_installation_hooks: Option<Vec<Hook>>,
+    /// Command run after installing or updating (e.g. `rg --version`); a failure fails the
+    /// installation and restores the previous executable where possible.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smoke_test: Option<String>,
     #[serde(default)]
     #[serde(skip_serializing_if = "ConfigurationManager::is_default")]
     pub configuration_manager: ConfigurationManager,
+    /// Stream tar-family archives straight from the network into the extraction directory.
+    /// Enabled by default where safe; set to `false` to force download-then-extract.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub streaming: Option<bool>,
+    /// Keep the downloaded asset so later updates only fetch the blocks that changed, when
+    /// the release publishes a `.zsync` file next to the asset.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delta_updates: Option<bool>,
+    /// Expected digest of the downloaded asset of a `github` or `url` tool (`sha256:<hex>`,
+    /// `sha512:<hex>` or bare hex). GitHub tools without one are verified against a checksum
+    /// file published in the release, when there is one.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+    /// How to locate the executable inside an extracted archive (`strict` or `heuristic`).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub executable_detection: Option<ExecutableDetection>,
+    /// Globs of files never picked as the executable of an extracted archive (installer
+    /// scripts, test binaries, bundled runtimes such as `*/jre/bin/*`), matched against the
+    /// file name and its path inside the archive.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_from_search: Option<Vec<String>>,
+    /// Additional release assets (completions, man pages, data) installed with a GitHub tool.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assets: Option<Vec<AssetEntry>>,
+    /// Extra HTTP headers sent with url downloads (values support `$VAR`/`${VAR}` expansion).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<BTreeMap<String, String>>,
+    /// HTTP basic authentication for url downloads.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub basic_auth: Option<BasicAuth>,
+    /// Helper command whose stdout is the (e.g. query-signed) download URL; overrides `url`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url_command: Option<String>,
+    /// HTTP method used for url downloads (`GET` by default, or `POST`).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_method: Option<String>,
+    /// Where to discover the latest upstream version of a url tool (see `VersionCheck`).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_check: Option<VersionCheck>,
+    /// Pipeline phase (`bootstrap`, `core` or `extras`); tools without one run in `core`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phase: Option<ToolPhase>,
+    /// Launch the tool through a generated shim script that sets environment variables
+    /// and/or a working directory (see `ShimConfig`).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shim: Option<ShimConfig>,
+    /// Runtime that runs the tool when it is distributed as a `.wasm` module (`wasmtime` or
+    /// `wasmer`). Modules are detected automatically and default to `wasmtime`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wasm_runtime: Option<WasmRuntime>,
+    /// Ask for acceptance of the tool's license before installing it (or pass
+    /// `--accept-licenses`).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requires_license_acceptance: Option<bool>,
+    /// Prerequisites checked before installing: commands (`docker`), commands with a
+    /// minimum version (`python>=3.10`) and environment variables (`$JAVA_HOME`).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requires_env: Option<Vec<String>>,
+    /// Free-form note shown by `list` and `status`, e.g. why the version is pinned.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Who to ask before changing or removing this entry (a person or team).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// Protected entries (e.g. security agents) can only be updated, unprotected or removed
+    /// with `--force-protected --reason <TEXT>`; the reason is recorded in the state file.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protected: Option<bool>,
+    /// Per-platform field overrides, keyed by OS (`macos`) or OS and architecture
+    /// (`macos-arm64`); merged into the entry when the configuration is loaded.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platforms: Option<BTreeMap<String, PlatformOverride>>,
+    /// Machine fact conditions (`"!ci"`, `"ram_gb >= 32"`) that must all hold for the tool to
+    /// be installed on this machine.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub only_on: Option<Vec<String>>,
+    /// Profiles the tool belongs to (`[work, minimal]`); `now --profile <NAME>` only installs
+    /// tools of that profile and tools without `profiles`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profiles: Option<Vec<String>>,
+    /// Ships the tool (or a new version of it) to part of the machines first: a percentage
+    /// (`10%`) of machines by their stable hash, or a cohort (`canary`) joined through
+    /// `SDB_COHORTS`. Machines left out keep the installed version.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rollout: Option<String>,
+    /// Accepts hooks that look dangerous (`rm -rf /`, `curl ... | sh`, `sudo`, ...), which
+    /// otherwise fail validation unless `--allow-dangerous-hooks` is passed.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acknowledge_dangerous: Option<bool>,
+    /// macOS privacy permissions the tool needs (`full_disk_access`, `screen_recording`,
+    /// `accessibility`, ...); the matching System Settings pane is opened after installing.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<Vec<Permission>>,
+    /// Built-in setup for a macOS window manager or keyboard tool (`aerospace`, `yabai`,
+    /// `skhd`, `karabiner`): default configuration file location and service restarts.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preset: Option<ToolPreset>,
+    /// Directory a `path-entry` tool puts on `PATH` (e.g. one an MDM installs tools into).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Executable a `path-entry` tool expects in its `path`; its absence fails the tool.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub binary: Option<String>,
+    /// Directory the `.app` bundle of a `.dmg` release is copied to (defaults to `/Applications`).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_dir: Option<String>,
+    /// Strip debug symbols from (`strip`) or UPX-compress (`upx`) the downloaded executable
+    /// of a `github`, `url` or `oci` tool; sizes and checksums are recorded in the state file.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub optimize_binary: Option<OptimizeBinary>,
+    /// Install the `.app` bundle shipped in a `github` or `url` tool's `.dmg` or archive into
+    /// `app_dir` and link the command line interface embedded in it into the bin directory.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_bundle: Option<AppBundleConfig>,
+}
+
+/// Application bundle whose embedded command line interface is put on `PATH`.
+///
+/// ```yaml
+/// app_bundle:
+///   cli: Contents/MacOS/cli
+/// ```
+///
+/// Without `cli`, an executable named after the tool (or its `rename_to`) in
+/// `Contents/Resources/app/bin` or `Contents/Resources/bin` is linked, else a `cli` helper in
+/// `Contents/MacOS`, else the executable named after the tool in `Contents/MacOS`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct AppBundleConfig {
+    /// Path of the command line interface inside the bundle.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cli: Option<String>,
+}
+
+/// A post-installation hook: a plain command, or a command run inside a named environment so
+/// it does not depend on whatever toolchain is globally active.
+///
+/// ```yaml
+/// post_installation_hooks:
+///   - rg --version
+///   - run: npm install -g pnpm
+///     environment: mise:node@20
+///   - run: make setup
+///     environment: direnv:~/projects/api
+/// ```
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(untagged)]
+pub enum Hook {
+    Command(String),
+    Scoped { run: String, environment: String },
+}
+
+impl Hook {
+    /// The command as written, without its environment's activation.
+    pub fn command(&self) -> &str {
+        match self {
+            Hook::Command(command) => command,
+            Hook::Scoped { run, .. } => run,
+        }
+    }
+
+    /// The command to run with `sh -c`, wrapped in its environment's activation if any.
+    pub fn shell_command(&self) -> Result<String, String> {
+        match self {
+            Hook::Command(command) => Ok(command.clone()),
+            Hook::Scoped { run, environment } => command_in_environment(run, environment),
+        }
+    }
+}
+
+/// Shim script generated in place of the raw binary for tools that need launch-time setup.
+///
+/// ```yaml
+/// shim:
+///   env:
+///     JAVA_HOME: $HOME/.sdkman/candidates/java/current
+///   working_dir: ~/projects
+/// ```
+///
+/// `template` replaces the default script; it may use `{{name}}`, `{{target}}` (the real
+/// executable), `{{env}}` and `{{cd}}`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct ShimConfig {
+    /// Environment variables exported before launching the tool (`$VAR` references expand).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// Directory the tool is started in.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<String>,
+    /// Custom shim script template.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+}
+
+/// Fields of a tool entry that can differ per platform.
+///
+/// ```yaml
+/// platforms:
+///   macos-arm64:
+///     tag: v2.1.0
+///     executable_path_after_extract: tool-darwin-arm64/tool
+///   linux:
+///     source: url
+///     url: https://example.com/tool-linux.tar.gz
+/// ```
+///
+/// An OS-wide override applies first, so an OS and architecture override can refine it.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct PlatformOverride {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<SourceType>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rename_to: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<Vec<String>>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub executable_path_after_extract: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_installation_hooks: Option<Vec<Hook>>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url_command: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+}
+
+/// Upstream version discovery for url tools.
+///
+/// Either fetch `url` and extract the version with `json_path` or `regex`, or run `command`
+/// and use its output (optionally filtered through `regex`). Without an extractor, the last
+/// non-empty line of the response/output is used as the version.
+///
+/// ```yaml
+/// version_check:
+///   url: https://example.com/releases/latest.json
+///   json_path: $.version
+/// ```
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct VersionCheck {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Regular expression; the first capture group (or the whole match) is the version.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regex: Option<String>,
+    /// Simple JSONPath into a JSON response, e.g. `$.latest.version` or `releases[0].tag`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_path: Option<String>,
+    /// Shell command (run via `sh -c`) whose output contains the version.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+}
+
+/// HTTP basic authentication credentials for url downloads.
+///
+/// Both fields support environment variable expansion so secrets never need to live in
+/// `tools.yaml`:
+///
+/// ```yaml
+/// basic_auth:
+///   username: ci-reader
+///   password: ${ARTIFACTORY_PASSWORD}
+/// ```
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct BasicAuth {
+    pub username: String,
+    pub password: String,
+}
+
+/// An extra GitHub release asset installed alongside a tool's main binary.
+///
+/// ```yaml
+/// assets:
+///   - pattern: "*completions*.tar.gz"
+///     destination: ~/.local/share/zsh/site-functions
+///     extract: completions/_tool
+///   - pattern: "tool.1"
+///     destination: ~/.local/share/man/man1/tool.1
+/// ```
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct AssetEntry {
+    /// Asset name pattern: a plain substring or a `*` wildcard glob.
+    pub pattern: String,
+    /// Install destination (`~` and environment variables are expanded). Archives are
+    /// extracted into this directory; other files are copied to this path, or into it
+    /// when it is an existing directory or ends with `/`.
+    pub destination: String,
+    /// For archives, a path inside the archive to install instead of the whole contents.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extract: Option<String>,
 }
 
 impl ToolEntry {
+    /// A minimal entry with only a name and source set, for entries generated by commands.
+    pub fn new(name: impl Into<String>, source: SourceType) -> Self {
+        Self {
+            name: name.into(),
+            version: None,
+            source,
+            url: None,
+            repo: None,
+            tag: None,
+            rename_to: None,
+            options: None,
+            executable_path_after_extract: None,
+            post_installation_hooks: None,
+            smoke_test: None,
+            configuration_manager: ConfigurationManager::default(),
+            streaming: None,
+            delta_updates: None,
+            checksum: None,
+            executable_detection: None,
+            exclude_from_search: None,
+            assets: None,
+            headers: None,
+            basic_auth: None,
+            url_command: None,
+            http_method: None,
+            version_check: None,
+            phase: None,
+            shim: None,
+            requires_license_acceptance: None,
+            requires_env: None,
+            notes: None,
+            owner: None,
+            protected: None,
+            only_on: None,
+            profiles: None,
+            rollout: None,
+            acknowledge_dangerous: None,
+            permissions: None,
+            preset: None,
+            path: None,
+            binary: None,
+            app_dir: None,
+            optimize_binary: None,
+            app_bundle: None,
+            wasm_runtime: None,
+            platforms: None,
+        }
+    }
+
+    /// The phase this tool is installed in, defaulting to `core`.
+    pub fn effective_phase(&self) -> ToolPhase {
+        self.phase.unwrap_or_default()
+    }
+
+    /// The commit this tool is pinned to with `version: commit:<hash>`, if any.
+    pub fn pinned_commit(&self) -> Option<&str> {
+        self.version
+            .as_deref()?
+            .trim()
+            .strip_prefix("commit:")
+            .map(str::trim)
+    }
+
+    /// Merges the `platforms` overrides matching `os` and `arch` into the entry, OS-wide
+    /// ones first. Returns the keys that were applied.
+    pub fn apply_platform_overrides(&mut self, os: &str, arch: &str) -> Vec<String> {
+        let Some(platforms) = self.platforms.clone() else {
+            return Vec::new();
+        };
+        let mut matching: Vec<(bool, String, PlatformOverride)> = platforms
+            .into_iter()
+            .filter_map(|(key, overrides)| match parse_platform_key(&key)? {
+                (key_os, None) if key_os == os => Some((false, key, overrides)),
+                (key_os, Some(key_arch)) if key_os == os && key_arch == arch => {
+                    Some((true, key, overrides))
+                }
+                _ => None,
+            })
+            .collect();
+        matching.sort_by_key(|(specific, _, _)| *specific);
+
+        let mut applied = Vec::new();
+        for (_, key, overrides) in matching {
+            if overrides.version.is_some() {
+                self.version = overrides.version;
+            }
+            if let Some(source) = overrides.source {
+                self.source = source;
+            }
+            if overrides.url.is_some() {
+                self.url = overrides.url;
+            }
+            if overrides.repo.is_some() {
+                self.repo = overrides.repo;
+            }
+            if overrides.tag.is_some() {
+                self.tag = overrides.tag;
+            }
+            if overrides.rename_to.is_some() {
+                self.rename_to = overrides.rename_to;
+            }
+            if overrides.options.is_some() {
+                self.options = overrides.options;
+            }
+            if overrides.executable_path_after_extract.is_some() {
+                self.executable_path_after_extract = overrides.executable_path_after_extract;
+            }
+            if overrides.post_installation_hooks.is_some() {
+                self.post_installation_hooks = overrides.post_installation_hooks;
+            }
+            if overrides.url_command.is_some() {
+                self.url_command = overrides.url_command;
+            }
+            if overrides.checksum.is_some() {
+                self.checksum = overrides.checksum;
+            }
+            applied.push(key);
+        }
+        applied
+    }
+
     pub fn validate(&self) -> Result<(), ToolEntryError> {
         if self.name.trim().is_empty() {
             return Err(ToolEntryError::MissingField("name"));
         }
+        if let Some(Err(e)) = self
+            .requires_env
+            .iter()
+            .flatten()
+            .map(|spec| Requirement::parse(spec))
+            .find(Result::is_err)
+        {
+            return Err(ToolEntryError::InvalidRequirement(e));
+        }
+        if let Some(key) = self
+            .platforms
+            .iter()
+            .flat_map(|platforms| platforms.keys())
+            .find(|key| parse_platform_key(key).is_none())
+        {
+            return Err(ToolEntryError::UnknownPlatform(key.clone()));
+        }
+        if let Some(Err(e)) = self
+            .post_installation_hooks
+            .iter()
+            .flatten()
+            .map(Hook::shell_command)
+            .find(Result::is_err)
+        {
+            return Err(ToolEntryError::InvalidHook(e));
+        }
+        if self.acknowledge_dangerous != Some(true)
+            && !dangerous_hooks::is_allowed()
+            && let Some((hook, reason)) = self
+                .post_installation_hooks
+                .iter()
+                .flatten()
+                .find_map(|hook| dangerous_reason(hook.command()).map(|reason| (hook, reason)))
+        {
+            return Err(ToolEntryError::DangerousHook(format!(
+                "'{}' {reason}",
+                hook.command()
+            )));
+        }
+        if let Some(Err(e)) = self
+            .configuration_manager
+            .tools_configuration_paths
+            .iter()
+            .map(ConfigurationPath::mode)
+            .find(Result::is_err)
+        {
+            return Err(ToolEntryError::InvalidConfigurationPath(e));
+        }
+        if let Some(Err(e)) = self.checksum.as_deref().map(Checksum::parse) {
+            return Err(ToolEntryError::InvalidChecksum(e));
+        }
+        if let Some(Err(e)) = self.rollout.as_deref().map(Rollout::parse) {
+            return Err(ToolEntryError::InvalidRollout(e));
+        }
+        if self
+            .optimize_binary
+            .is_some_and(|optimize| optimize != OptimizeBinary::None)
+            && !matches!(
+                self.source,
+                SourceType::Github | SourceType::Url | SourceType::Oci
+            )
+        {
+            return Err(ToolEntryError::OptimizeBinaryUnsupported(
+                self.source.clone(),
+            ));
+        }
+        if self.app_bundle.is_some() && !matches!(self.source, SourceType::Github | SourceType::Url)
+        {
+            return Err(ToolEntryError::AppBundleUnsupported(self.source.clone()));
+        }
+        if self.source == SourceType::PathEntry && self.path.is_none() {
+            return Err(ToolEntryError::MissingField("path"));
+        }
+        if let Some(commit) = self.pinned_commit() {
+            if !matches!(self.source, SourceType::Go | SourceType::Cargo) {
+                return Err(ToolEntryError::CommitPinUnsupported(self.source.clone()));
+            }
+            if !(7..=40).contains(&commit.len()) || !commit.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(ToolEntryError::InvalidCommit(commit.to_string()));
+            }
+        }
         Ok(())
     }
 }
@@ -53,6 +640,8 @@ pub struct ToolInstallationOrchestrator<'a> {
     pub configuration: &'a InstallationConfiguration,
     pub config_processor: ConfigurationManagerProcessor,
     pub installer_factory: InstallerFactory,
+    pub checkpoint: Option<&'a mut RunCheckpoint>,
+    pub autosave: Option<StateAutosave>,
 }
 
 #[derive(Debug)]
@@ -63,6 +652,7 @@ pub struct InstallationConfiguration {
 }
 
 pub struct InstallationSummary {
+    pub phase_outcomes: BTreeMap<ToolPhase, Vec<(String, &'static str)>>,
     pub installed_tools: Vec<String>,
     pub updated_tools: Vec<String>,
     pub configuration_updated_tools: Vec<String>,
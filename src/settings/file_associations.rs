@@ -0,0 +1,340 @@
+// This module applies the default applications declared in the `file_associations:`
+// section of `settings.yaml` (macOS only). Each entry maps a file extension, UTI or URL
+// scheme to an application bundle id. File types are applied with `duti`, which wraps
+// `LSSetDefaultRoleHandlerForContentType`. URL schemes (`vscode://`, `jetbrains://`, ...)
+// are registered directly with `LSSetDefaultHandlerForURLScheme` through `osascript`, so
+// deep links into editors need no extra tool.
+// Applied associations are recorded in `DevBoxState::file_associations` so unchanged
+// entries are skipped on later runs. For URL schemes the handler Launch Services reports is
+// compared as well, so a scheme another application took over is registered again. Launch
+// Services is queried, and schemes are registered, with one `osascript` call per run each.
+
+use std::collections::HashMap;
+use std::process::Command;
+
+use colored::Colorize;
+
+use crate::core::platform::check_installer_command_available;
+use crate::core::timestamps::current_timestamp;
+use crate::schemas::os_settings::FileAssociationEntry;
+use crate::schemas::state_file::{DevBoxState, FileAssociationState};
+use crate::{log_debug, log_error, log_info, log_warn};
+
+/// Applies every declared association that changed. Returns `true` if the state changed.
+pub fn apply_file_associations(
+    associations: &[FileAssociationEntry],
+    state: &mut DevBoxState,
+) -> bool {
+    if associations.is_empty() {
+        return false;
+    }
+
+    if !cfg!(target_os = "macos") {
+        log_warn!(
+            "[SDB::FileAssociations] file_associations are only supported on macOS. Skipping {} entries.",
+            associations.len()
+        );
+        return false;
+    }
+
+    let valid: Vec<&FileAssociationEntry> = associations
+        .iter()
+        .filter(|entry| match validate_entry(entry) {
+            Ok(()) => true,
+            Err(e) => {
+                log_error!(
+                    "[SDB::FileAssociations] Skipping {}: {}",
+                    entry.content_type.red(),
+                    e
+                );
+                false
+            }
+        })
+        .collect();
+
+    let pending = pending_entries(valid, state);
+    if pending.is_empty() {
+        return false;
+    }
+
+    let needs_duti = pending
+        .iter()
+        .any(|entry| url_scheme(&entry.content_type).is_none());
+    if needs_duti && check_installer_command_available("duti").is_err() {
+        log_error!(
+            "[SDB::FileAssociations] {} is required to set default applications. Install it with {} (or add it to tools.yaml).",
+            "duti".bold(),
+            "brew install duti".cyan()
+        );
+        return false;
+    }
+
+    let schemes: Vec<(&str, &str)> = pending
+        .iter()
+        .filter_map(|entry| Some((url_scheme(&entry.content_type)?, entry.app.as_str())))
+        .collect();
+    let mut registered = set_url_handlers(&schemes).into_iter();
+
+    let mut changed = false;
+    for entry in pending {
+        let applied = match url_scheme(&entry.content_type) {
+            Some(_) => registered
+                .next()
+                .unwrap_or_else(|| Err("no result from osascript".to_string())),
+            None => match Command::new("duti").args(duti_args(entry)).output() {
+                Ok(output) if output.status.success() => Ok(()),
+                Ok(output) => Err(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+                Err(e) => Err(format!("failed to run duti: {e}")),
+            },
+        };
+        match applied {
+            Ok(()) => {
+                log_info!(
+                    "[SDB::FileAssociations] {} now opens with {}",
+                    entry.content_type.green(),
+                    entry.app.green()
+                );
+                state.file_associations.insert(
+                    entry.content_type.clone(),
+                    FileAssociationState {
+                        app: entry.app.clone(),
+                        role: entry.role.clone(),
+                        applied_at: current_timestamp(),
+                    },
+                );
+                changed = true;
+            }
+            Err(e) => log_error!(
+                "[SDB::FileAssociations] Failed to set {} for {}: {}",
+                entry.app.red(),
+                entry.content_type.red(),
+                e
+            ),
+        }
+    }
+    changed
+}
+
+/// The declared associations that `apply_file_associations` would set.
+pub fn pending_file_associations<'a>(
+    associations: &'a [FileAssociationEntry],
+    state: &DevBoxState,
+) -> Vec<&'a FileAssociationEntry> {
+    if !cfg!(target_os = "macos") {
+        return Vec::new();
+    }
+    let valid = associations
+        .iter()
+        .filter(|entry| validate_entry(entry).is_ok())
+        .collect();
+    pending_entries(valid, state)
+}
+
+/// Drops the entries that are already applied and still in effect.
+fn pending_entries<'a>(
+    valid: Vec<&'a FileAssociationEntry>,
+    state: &DevBoxState,
+) -> Vec<&'a FileAssociationEntry> {
+    // Only schemes recorded as applied need their current handler looked up
+    let recorded_schemes: Vec<&str> = valid
+        .iter()
+        .filter(|entry| {
+            state
+                .file_associations
+                .get(&entry.content_type)
+                .is_some_and(|applied| applied.app == entry.app)
+        })
+        .filter_map(|entry| url_scheme(&entry.content_type))
+        .collect();
+    let handlers = url_handlers(&recorded_schemes);
+
+    valid
+        .into_iter()
+        .filter(|entry| {
+            let up_to_date = state
+                .file_associations
+                .get(&entry.content_type)
+                .is_some_and(|applied| applied.app == entry.app && applied.role == entry.role)
+                && url_scheme(&entry.content_type).is_none_or(|scheme| {
+                    handlers
+                        .get(scheme)
+                        .is_some_and(|handler| handler.eq_ignore_ascii_case(&entry.app))
+                });
+            if up_to_date {
+                log_debug!(
+                    "[SDB::FileAssociations] {} is already handled by {}. Skipping.",
+                    entry.content_type.blue(),
+                    entry.app
+                );
+            }
+            !up_to_date
+        })
+        .collect()
+}
+
+/// The scheme of a URL scheme entry (`vscode://` -> `vscode`), or `None` for file types.
+fn url_scheme(content_type: &str) -> Option<&str> {
+    let scheme = content_type.strip_suffix("://")?;
+    let mut chars = scheme.chars();
+    (chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.')))
+    .then_some(scheme)
+}
+
+/// Rejects entries that look like URL schemes but aren't valid ones (`1password://`: a scheme
+/// must start with a letter), which `duti` would otherwise be handed as a file type.
+fn validate_entry(entry: &FileAssociationEntry) -> Result<(), String> {
+    if entry.content_type.ends_with("://") && url_scheme(&entry.content_type).is_none() {
+        return Err(format!(
+            "'{}' is not a valid URL scheme; a scheme starts with a letter followed by letters, digits, '+', '-' or '.'",
+            entry.content_type
+        ));
+    }
+    Ok(())
+}
+
+/// Registers each `(scheme, app)` pair with `LSSetDefaultHandlerForURLScheme` in a single
+/// `osascript` run. Returns one result per pair, in order.
+fn set_url_handlers(schemes: &[(&str, &str)]) -> Vec<Result<(), String>> {
+    const SCRIPT: &str = "function run(argv) { ObjC.import('CoreServices'); \
+        const statuses = []; \
+        for (let i = 0; i + 1 < argv.length; i += 2) \
+            statuses.push($.LSSetDefaultHandlerForURLScheme($(argv[i]), $(argv[i + 1]))); \
+        return statuses.join('\\n'); }";
+    if schemes.is_empty() {
+        return Vec::new();
+    }
+    let output = match run_jxa(
+        SCRIPT,
+        schemes.iter().flat_map(|(scheme, app)| [*scheme, *app]),
+    ) {
+        Ok(stdout) => stdout,
+        Err(e) => return schemes.iter().map(|_| Err(e.clone())).collect(),
+    };
+    let mut statuses = output.lines();
+    schemes
+        .iter()
+        .map(|(_, app)| match statuses.next().map(str::trim) {
+            Some("0") => Ok(()),
+            // kLSApplicationNotFoundErr
+            Some("-10814") => Err(format!(
+                "no application with bundle id '{app}' is installed"
+            )),
+            Some(status) => Err(format!("LSSetDefaultHandlerForURLScheme returned {status}")),
+            None => Err("no result from osascript".to_string()),
+        })
+        .collect()
+}
+
+/// The bundle ids Launch Services currently opens each scheme's URLs with, looked up in a
+/// single `osascript` run. Schemes without a handler are left out.
+fn url_handlers(schemes: &[&str]) -> HashMap<String, String> {
+    const SCRIPT: &str = "function run(argv) { ObjC.import('CoreServices'); \
+        return argv.map(s => ObjC.unwrap($.LSCopyDefaultHandlerForURLScheme($(s))) || '').join('\\n'); }";
+    if schemes.is_empty() {
+        return HashMap::new();
+    }
+    match run_jxa(SCRIPT, schemes.iter().copied()) {
+        Ok(stdout) => parse_handlers(schemes, &stdout),
+        Err(e) => {
+            log_debug!(
+                "[SDB::FileAssociations] Could not look up URL handlers: {}",
+                e
+            );
+            HashMap::new()
+        }
+    }
+}
+
+/// Pairs the schemes with the lines `url_handlers` printed for them.
+fn parse_handlers(schemes: &[&str], stdout: &str) -> HashMap<String, String> {
+    schemes
+        .iter()
+        .zip(stdout.lines())
+        .filter(|(_, handler)| !handler.trim().is_empty())
+        .map(|(scheme, handler)| (scheme.to_string(), handler.trim().to_string()))
+        .collect()
+}
+
+/// Runs a JavaScript for Automation script with `args` as its `argv` and returns its stdout.
+fn run_jxa<'a>(script: &str, args: impl IntoIterator<Item = &'a str>) -> Result<String, String> {
+    let output = Command::new("osascript")
+        .args(["-l", "JavaScript", "-e", script])
+        .args(args)
+        .output()
+        .map_err(|e| format!("failed to run osascript: {e}"))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Builds the `duti -s` arguments for a file type entry.
+fn duti_args(entry: &FileAssociationEntry) -> Vec<String> {
+    vec![
+        "-s".into(),
+        entry.app.clone(),
+        entry.content_type.clone(),
+        entry.role.clone(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_scheme() {
+        assert_eq!(url_scheme("vscode://"), Some("vscode"));
+        assert_eq!(url_scheme("jetbrains://"), Some("jetbrains"));
+        assert_eq!(url_scheme("x-github-client://"), Some("x-github-client"));
+        assert_eq!(url_scheme(".md"), None);
+        assert_eq!(url_scheme("public.plain-text"), None);
+        assert_eq!(url_scheme("1password://"), None);
+    }
+
+    #[test]
+    fn test_duti_args() {
+        let entry: FileAssociationEntry =
+            serde_yaml::from_str("content_type: .md\napp: com.microsoft.VSCode").unwrap();
+        assert_eq!(
+            duti_args(&entry),
+            ["-s", "com.microsoft.VSCode", ".md", "all"]
+        );
+
+        let entry = FileAssociationEntry {
+            role: "editor".to_string(),
+            content_type: "public.plain-text".to_string(),
+            ..entry
+        };
+        assert_eq!(
+            duti_args(&entry),
+            ["-s", "com.microsoft.VSCode", "public.plain-text", "editor"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_schemes_are_rejected() {
+        let entry = |content_type: &str| FileAssociationEntry {
+            content_type: content_type.to_string(),
+            app: "com.example.App".to_string(),
+            role: "all".to_string(),
+        };
+        let error = validate_entry(&entry("1password://")).unwrap_err();
+        assert!(error.contains("'1password://' is not a valid URL scheme"));
+        assert!(validate_entry(&entry("x-github-client://")).is_ok());
+        assert!(validate_entry(&entry(".md")).is_ok());
+    }
+
+    #[test]
+    fn test_parse_handlers() {
+        let handlers = parse_handlers(
+            &["vscode", "jetbrains", "zed"],
+            "com.microsoft.VSCode\n\ndev.zed.Zed\n",
+        );
+        assert_eq!(handlers.len(), 2);
+        assert_eq!(handlers["vscode"], "com.microsoft.VSCode");
+        assert_eq!(handlers["zed"], "dev.zed.Zed");
+        assert!(!handlers.contains_key("jetbrains"));
+    }
+}
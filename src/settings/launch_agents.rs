@@ -0,0 +1,543 @@
+// This module installs the user agents declared in the `launch_agents:` section of
+// `settings.yaml`. The backend is picked by platform from the same declaration:
+// - macOS: a LaunchAgent plist under `~/Library/LaunchAgents`, loaded with `launchctl`.
+// - Linux: a systemd user `.service` (plus a `.timer` when `start_interval` is set) under
+//   `~/.config/systemd/user`, enabled with `systemctl --user`.
+// Installed agents are recorded in `DevBoxState::launch_agents` together with a hash of
+// the generated definition, so unchanged agents are skipped and `remove launch-agent`
+// can unload and delete them.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use colored::Colorize;
+use sha2::{Digest, Sha256};
+
+use crate::core::timestamps::current_timestamp;
+use crate::engine::uninstaller::executors::RemovalResult;
+use crate::schemas::common::RemovalOrchestrator;
+use crate::schemas::os_settings::LaunchAgentEntry;
+use crate::schemas::state_file::{DevBoxState, LaunchAgentState};
+use crate::{log_debug, log_error, log_info, log_warn};
+
+/// Service manager used to run user agents on the current platform.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Backend {
+    Launchd,
+    Systemd,
+}
+
+impl Backend {
+    fn current() -> Option<Self> {
+        if cfg!(target_os = "macos") {
+            Some(Backend::Launchd)
+        } else if cfg!(target_os = "linux") {
+            Some(Backend::Systemd)
+        } else {
+            None
+        }
+    }
+
+    /// Infers the backend that installed an agent from its recorded definition path.
+    fn of_installed(path: &Path) -> Self {
+        if path.extension().is_some_and(|ext| ext == "plist") {
+            Backend::Launchd
+        } else {
+            Backend::Systemd
+        }
+    }
+
+    /// Generates the definition files for an agent; the first one is the primary file.
+    fn render(self, agent: &LaunchAgentEntry, home: &Path) -> Vec<(PathBuf, String)> {
+        match self {
+            Backend::Launchd => vec![(
+                home.join("Library/LaunchAgents")
+                    .join(format!("{}.plist", agent.label)),
+                render_plist(agent),
+            )],
+            Backend::Systemd => {
+                let dir = home.join(".config/systemd/user");
+                let (service, timer) = render_systemd_units(agent);
+                let mut files = vec![(dir.join(format!("{}.service", agent.label)), service)];
+                if let Some(timer) = timer {
+                    files.push((dir.join(format!("{}.timer", agent.label)), timer));
+                }
+                files
+            }
+        }
+    }
+
+    fn install(self, agent: &LaunchAgentEntry, files: &[(PathBuf, String)]) -> Result<(), String> {
+        match self {
+            Backend::Launchd => {
+                let (path, _) = &files[0];
+                let path_str = path.display().to_string();
+                if path.exists() {
+                    // Reloading is the only way launchd picks up a changed definition
+                    let _ = run("launchctl", &["unload", &path_str]);
+                }
+                write_files(files)?;
+                run("launchctl", &["load", "-w", &path_str])
+            }
+            Backend::Systemd => {
+                write_files(files)?;
+                run("systemctl", &["--user", "daemon-reload"])?;
+
+                // With a timer, the timer is the enabled unit and it triggers the service
+                let unit = file_name(&files[files.len() - 1].0);
+                run("systemctl", &["--user", "enable", &unit])?;
+                if agent.run_at_load || agent.start_interval.is_some() {
+                    // `restart` also starts a stopped unit and applies a changed definition
+                    run("systemctl", &["--user", "restart", &unit])?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn uninstall(self, path: &Path) -> Result<(), String> {
+        match self {
+            Backend::Launchd => {
+                // A failed unload just means the agent was not loaded
+                let _ = run("launchctl", &["unload", &path.display().to_string()]);
+                fs::remove_file(path)
+                    .map_err(|e| format!("Failed to delete {}: {e}", path.display()))
+            }
+            Backend::Systemd => {
+                for unit_path in [path.with_extension("timer"), path.to_path_buf()] {
+                    if !unit_path.exists() {
+                        continue;
+                    }
+                    let _ = run(
+                        "systemctl",
+                        &["--user", "disable", "--now", &file_name(&unit_path)],
+                    );
+                    fs::remove_file(&unit_path)
+                        .map_err(|e| format!("Failed to delete {}: {e}", unit_path.display()))?;
+                }
+                run("systemctl", &["--user", "daemon-reload"])
+            }
+        }
+    }
+}
+
+/// Installs or updates every declared agent. Returns `true` if the state changed.
+pub fn apply_launch_agents(agents: &[LaunchAgentEntry], state: &mut DevBoxState) -> bool {
+    if agents.is_empty() {
+        return false;
+    }
+
+    let Some(backend) = Backend::current() else {
+        log_warn!(
+            "[SDB::LaunchAgents] launch_agents are only supported on macOS and Linux. Skipping {} agent(s).",
+            agents.len()
+        );
+        return false;
+    };
+
+    let Some(home) = dirs::home_dir() else {
+        log_error!("[SDB::LaunchAgents] Could not determine the home directory.");
+        return false;
+    };
+
+    let mut changed = false;
+    for agent in agents {
+        let files = backend.render(agent, &home);
+        let content_hash = hash_files(&files);
+        let path = files[0].0.display().to_string();
+
+        if is_up_to_date(agent, &files, &content_hash, state) {
+            log_debug!(
+                "[SDB::LaunchAgents] {} is up to date. Skipping.",
+                agent.label.blue()
+            );
+            continue;
+        }
+
+        match backend.install(agent, &files) {
+            Ok(()) => {
+                log_info!(
+                    "[SDB::LaunchAgents] Loaded {} from {}",
+                    agent.label.green(),
+                    path
+                );
+                state.launch_agents.insert(
+                    agent.label.clone(),
+                    LaunchAgentState {
+                        label: agent.label.clone(),
+                        path,
+                        content_hash,
+                        installed_at: current_timestamp(),
+                    },
+                );
+                changed = true;
+            }
+            Err(e) => log_error!(
+                "[SDB::LaunchAgents] Failed to install {}: {}",
+                agent.label.red(),
+                e
+            ),
+        }
+    }
+    changed
+}
+
+/// The declared agents that `apply_launch_agents` would install or update.
+pub fn pending_launch_agents<'a>(
+    agents: &'a [LaunchAgentEntry],
+    state: &DevBoxState,
+) -> Vec<&'a LaunchAgentEntry> {
+    let (Some(backend), Some(home)) = (Backend::current(), dirs::home_dir()) else {
+        return Vec::new();
+    };
+    agents
+        .iter()
+        .filter(|agent| {
+            let files = backend.render(agent, &home);
+            !is_up_to_date(agent, &files, &hash_files(&files), state)
+        })
+        .collect()
+}
+
+/// Whether an agent was installed with the same definition and its files are still there.
+fn is_up_to_date(
+    agent: &LaunchAgentEntry,
+    files: &[(PathBuf, String)],
+    content_hash: &str,
+    state: &DevBoxState,
+) -> bool {
+    state
+        .launch_agents
+        .get(&agent.label)
+        .is_some_and(|existing| existing.content_hash == content_hash)
+        && files.iter().all(|(path, _)| path.exists())
+}
+
+/// Unloads an installed agent and deletes its definition.
+pub fn uninstall_launch_agent(agent: &LaunchAgentState) -> Result<(), String> {
+    let path = PathBuf::from(&agent.path);
+    if !path.exists() {
+        log_warn!(
+            "[SDB::LaunchAgents] {} not found; dropping it from state.",
+            path.display().to_string().yellow()
+        );
+        return Ok(());
+    }
+
+    Backend::of_installed(&path).uninstall(&path)?;
+    log_info!(
+        "[SDB::LaunchAgents] Unloaded and deleted {}",
+        agent.label.cyan()
+    );
+    Ok(())
+}
+
+/// Generates the LaunchAgent plist for an entry.
+pub fn render_plist(agent: &LaunchAgentEntry) -> String {
+    let mut body = String::new();
+    push_key(&mut body, "Label", &string(&agent.label));
+
+    let arguments: String = agent
+        .program_arguments
+        .iter()
+        .map(|arg| format!("\t\t{}\n", string(&expand(arg))))
+        .collect();
+    push_key(
+        &mut body,
+        "ProgramArguments",
+        &format!("<array>\n{arguments}\t</array>"),
+    );
+
+    push_key(&mut body, "RunAtLoad", boolean(agent.run_at_load));
+    push_key(&mut body, "KeepAlive", boolean(agent.keep_alive));
+    if let Some(interval) = agent.start_interval {
+        push_key(
+            &mut body,
+            "StartInterval",
+            &format!("<integer>{interval}</integer>"),
+        );
+    }
+    if let Some(dir) = &agent.working_directory {
+        push_key(&mut body, "WorkingDirectory", &string(&expand(dir)));
+    }
+    if let Some(environment) = &agent.environment {
+        let variables: String = environment
+            .iter()
+            .map(|(name, value)| {
+                format!("\t\t<key>{}</key>\n\t\t{}\n", escape(name), string(value))
+            })
+            .collect();
+        push_key(
+            &mut body,
+            "EnvironmentVariables",
+            &format!("<dict>\n{variables}\t</dict>"),
+        );
+    }
+    if let Some(path) = &agent.standard_out_path {
+        push_key(&mut body, "StandardOutPath", &string(&expand(path)));
+    }
+    if let Some(path) = &agent.standard_error_path {
+        push_key(&mut body, "StandardErrorPath", &string(&expand(path)));
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+         <plist version=\"1.0\">\n<dict>\n{body}</dict>\n</plist>\n"
+    )
+}
+
+/// Generates the systemd user service for an entry, plus a timer if it runs periodically.
+pub fn render_systemd_units(agent: &LaunchAgentEntry) -> (String, Option<String>) {
+    let description = format!("Description={} (managed by setup-devbox)\n", agent.label);
+    let command: Vec<String> = agent
+        .program_arguments
+        .iter()
+        .map(|arg| systemd_quote(&expand(arg)))
+        .collect();
+
+    let mut service = format!("[Unit]\n{description}\n[Service]\n");
+    if agent.start_interval.is_some() {
+        service.push_str("Type=oneshot\n");
+    }
+    service.push_str(&format!("ExecStart={}\n", command.join(" ")));
+    if let Some(dir) = &agent.working_directory {
+        service.push_str(&format!("WorkingDirectory={}\n", expand(dir)));
+    }
+    if let Some(environment) = &agent.environment {
+        for (name, value) in environment {
+            let assignment = systemd_quote(&format!("{name}={value}"));
+            service.push_str(&format!("Environment={assignment}\n"));
+        }
+    }
+    if agent.keep_alive && agent.start_interval.is_none() {
+        service.push_str("Restart=always\n");
+    }
+    if let Some(path) = &agent.standard_out_path {
+        service.push_str(&format!("StandardOutput=append:{}\n", expand(path)));
+    }
+    if let Some(path) = &agent.standard_error_path {
+        service.push_str(&format!("StandardError=append:{}\n", expand(path)));
+    }
+
+    let Some(interval) = agent.start_interval else {
+        service.push_str("\n[Install]\nWantedBy=default.target\n");
+        return (service, None);
+    };
+
+    let first_run = if agent.run_at_load { 0 } else { interval };
+    let timer = format!(
+        "[Unit]\n{description}\n[Timer]\nOnActiveSec={first_run}s\nOnUnitActiveSec={interval}s\n\n\
+         [Install]\nWantedBy=timers.target\n"
+    );
+    (service, Some(timer))
+}
+
+fn write_files(files: &[(PathBuf, String)]) -> Result<(), String> {
+    for (path, content) in files {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+        }
+        fs::write(path, content).map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+    }
+    Ok(())
+}
+
+fn run(program: &str, args: &[&str]) -> Result<(), String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run {program}: {e}"))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{program} {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn hash_files(files: &[(PathBuf, String)]) -> String {
+    let mut hasher = Sha256::new();
+    for (_, content) in files {
+        hasher.update(content.as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Quotes a word for a systemd unit file, escaping specifiers and variable expansion.
+fn systemd_quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('%', "%%")
+        .replace('$', "$$");
+    if escaped.is_empty() || escaped.contains(char::is_whitespace) || escaped != value {
+        format!("\"{escaped}\"")
+    } else {
+        escaped
+    }
+}
+
+fn push_key(body: &mut String, key: &str, value: &str) {
+    body.push_str(&format!("\t<key>{key}</key>\n\t{value}\n"));
+}
+
+fn string(value: &str) -> String {
+    format!("<string>{}</string>", escape(value))
+}
+
+fn boolean(value: bool) -> &'static str {
+    if value { "<true/>" } else { "<false/>" }
+}
+
+fn expand(value: &str) -> String {
+    shellexpand::tilde(value).into_owned()
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl<'a> RemovalOrchestrator<'a> {
+    /// Unloads and deletes a launch agent, then drops it from state and `settings.yaml`.
+    pub fn remove_launch_agent(&mut self, label: &str) -> RemovalResult {
+        log_info!("[SDB::Remove::LaunchAgent] Removing: {}", label.cyan());
+
+        let Some(agent) = self.state.launch_agents.get(label).cloned() else {
+            log_warn!(
+                "[SDB::Remove::LaunchAgent] Not found in state: {}",
+                label.yellow()
+            );
+            return RemovalResult::NotFound;
+        };
+
+        if let Err(e) = uninstall_launch_agent(&agent) {
+            log_error!("[SDB::Remove::LaunchAgent] {}", e.red());
+            return RemovalResult::Failed(e);
+        }
+
+        self.state.launch_agents.remove(label);
+        if let Err(e) =
+            self.cleaner
+                .remove_list_item("settings.yaml", "launch_agents:", "label:", label)
+        {
+            log_warn!(
+                "[SDB::Remove::LaunchAgent] YAML cleanup warning: {}",
+                e.yellow()
+            );
+        }
+
+        RemovalResult::Removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_plist() {
+        let agent = LaunchAgentEntry {
+            label: "com.example.sync".to_string(),
+            program_arguments: vec!["/usr/local/bin/sync".to_string(), "--a&b".to_string()],
+            run_at_load: true,
+            keep_alive: false,
+            start_interval: Some(300),
+            working_directory: None,
+            environment: Some([("MODE".to_string(), "fast".to_string())].into()),
+            standard_out_path: None,
+            standard_error_path: None,
+        };
+        let plist = render_plist(&agent);
+
+        assert!(plist.contains("<key>Label</key>\n\t<string>com.example.sync</string>"));
+        assert!(plist.contains("\t\t<string>--a&amp;b</string>\n"));
+        assert!(plist.contains("<key>RunAtLoad</key>\n\t<true/>"));
+        assert!(plist.contains("<key>KeepAlive</key>\n\t<false/>"));
+        assert!(plist.contains("<integer>300</integer>"));
+        assert!(plist.contains("<key>MODE</key>\n\t\t<string>fast</string>"));
+        assert!(!plist.contains("WorkingDirectory"));
+    }
+
+    #[test]
+    fn test_render_places_definitions_under_home() {
+        let agent = LaunchAgentEntry {
+            label: "com.example.sync".to_string(),
+            program_arguments: vec!["/usr/local/bin/sync".to_string()],
+            run_at_load: true,
+            keep_alive: false,
+            start_interval: Some(300),
+            working_directory: None,
+            environment: None,
+            standard_out_path: None,
+            standard_error_path: None,
+        };
+        let home = Path::new("/Users/dev");
+
+        let files = Backend::Launchd.render(&agent, home);
+        assert_eq!(files.len(), 1);
+        assert_eq!(
+            files[0].0,
+            Path::new("/Users/dev/Library/LaunchAgents/com.example.sync.plist")
+        );
+        assert_eq!(files[0].1, render_plist(&agent));
+        assert_eq!(Backend::of_installed(&files[0].0), Backend::Launchd);
+
+        let files = Backend::Systemd.render(&agent, home);
+        let paths: Vec<&Path> = files.iter().map(|(path, _)| path.as_path()).collect();
+        assert_eq!(
+            paths,
+            [
+                Path::new("/Users/dev/.config/systemd/user/com.example.sync.service"),
+                Path::new("/Users/dev/.config/systemd/user/com.example.sync.timer"),
+            ]
+        );
+        assert_eq!(Backend::of_installed(paths[0]), Backend::Systemd);
+    }
+
+    #[test]
+    fn test_render_systemd_units() {
+        let mut agent = LaunchAgentEntry {
+            label: "com.example.sync".to_string(),
+            program_arguments: vec!["/usr/bin/sync".to_string(), "my dir".to_string()],
+            run_at_load: true,
+            keep_alive: true,
+            start_interval: None,
+            working_directory: None,
+            environment: Some([("HOME_URL".to_string(), "a$b".to_string())].into()),
+            standard_out_path: None,
+            standard_error_path: None,
+        };
+
+        let (service, timer) = render_systemd_units(&agent);
+        assert!(timer.is_none());
+        assert!(service.contains("ExecStart=/usr/bin/sync \"my dir\"\n"));
+        assert!(service.contains("Environment=\"HOME_URL=a$$b\"\n"));
+        assert!(service.contains("Restart=always\n"));
+        assert!(service.contains("WantedBy=default.target"));
+
+        agent.start_interval = Some(600);
+        agent.run_at_load = false;
+        let (service, timer) = render_systemd_units(&agent);
+        let timer = timer.expect("periodic agents get a timer");
+        assert!(service.contains("Type=oneshot\n"));
+        assert!(!service.contains("Restart=") && !service.contains("[Install]"));
+        assert!(timer.contains("OnActiveSec=600s\nOnUnitActiveSec=600s\n"));
+        assert!(timer.contains("WantedBy=timers.target"));
+    }
+}
@@ -6,6 +6,9 @@
 // It also includes platform-specific conditional compilation to ensure macOS-specific code
 // only runs on macOS systems.
 
+pub mod file_associations;
+pub mod launch_agents;
+
 // =========================================================================== //
 //                          STANDARD LIBRARY DEPENDENCIES                      //
 // =========================================================================== //
@@ -333,6 +336,16 @@ pub fn apply_system_settings(
         }
     }
 
+    // User agents declared under `launch_agents:`
+    if launch_agents::apply_launch_agents(&settings_cfg.launch_agents, state) {
+        settings_updated_in_session = true;
+    }
+
+    // Default applications declared under `file_associations:`
+    if file_associations::apply_file_associations(&settings_cfg.file_associations, state) {
+        settings_updated_in_session = true;
+    }
+
     // Save state if any settings were updated
     if settings_updated_in_session {
         log_info!(
@@ -0,0 +1,142 @@
+//! Renders the `shell_behavior:` settings of `shellrc.yaml` into run commands for one shell.
+//!
+//! The commands are added to the RC file like any other run command: variables go to the
+//! `Exports` section (where a changed value is detected as an update), shell options to
+//! `Other`. Settings a shell has no equivalent for are left out.
+
+use crate::log_debug;
+use crate::schemas::shell_configuration::{ConfigSection, RunCommandEntry, ShellBehavior};
+
+/// XDG base directories and their default locations.
+const XDG_BASE_DIRS: [(&str, &str); 4] = [
+    ("XDG_CONFIG_HOME", "$HOME/.config"),
+    ("XDG_DATA_HOME", "$HOME/.local/share"),
+    ("XDG_STATE_HOME", "$HOME/.local/state"),
+    ("XDG_CACHE_HOME", "$HOME/.cache"),
+];
+
+/// The run commands implementing `behavior` in `shell`'s syntax.
+pub fn behavior_commands(behavior: &ShellBehavior, shell: &str) -> Vec<RunCommandEntry> {
+    let shell = shell.to_lowercase();
+    let fish = shell == "fish";
+    let export = |name: &str, value: &str| {
+        if fish {
+            format!("set -gx {name} \"{value}\"")
+        } else {
+            format!("export {name}=\"{value}\"")
+        }
+    };
+
+    let mut exports = Vec::new();
+    let mut options = Vec::new();
+    if behavior.xdg_base_dirs == Some(true) {
+        exports.extend(
+            XDG_BASE_DIRS
+                .iter()
+                .map(|(name, value)| export(name, value)),
+        );
+    }
+
+    let ignore_duplicates = behavior.history_ignore_duplicates == Some(true);
+    let ignore_space = behavior.history_ignore_space == Some(true);
+    if matches!(shell.as_str(), "zsh" | "bash") {
+        if let Some(size) = behavior.history_size {
+            exports.push(export("HISTSIZE", &size.to_string()));
+            let file_size = if shell == "zsh" {
+                "SAVEHIST"
+            } else {
+                "HISTFILESIZE"
+            };
+            exports.push(export(file_size, &size.to_string()));
+        }
+        if let Some(file) = &behavior.history_file {
+            exports.push(export("HISTFILE", file));
+        }
+    }
+    match shell.as_str() {
+        "zsh" => {
+            if ignore_duplicates {
+                options.push("setopt HIST_IGNORE_ALL_DUPS".to_string());
+            }
+            if ignore_space {
+                options.push("setopt HIST_IGNORE_SPACE".to_string());
+            }
+            if behavior.share_history == Some(true) {
+                options.push("setopt SHARE_HISTORY".to_string());
+            }
+        }
+        "bash" => {
+            let control = match (ignore_duplicates, ignore_space) {
+                (true, true) => Some("ignoreboth:erasedups"),
+                (true, false) => Some("ignoredups:erasedups"),
+                (false, true) => Some("ignorespace"),
+                (false, false) => None,
+            };
+            if let Some(control) = control {
+                exports.push(export("HISTCONTROL", control));
+            }
+            if behavior.share_history == Some(true) {
+                options.push("shopt -s histappend".to_string());
+            }
+        }
+        // fish drops duplicates and space-prefixed commands by itself and manages its
+        // history file, so only the XDG directories apply
+        _ => log_debug!(
+            "[SDB::ShellConfig] Only XDG settings of shell_behavior apply to {}",
+            shell
+        ),
+    }
+
+    let entries = |commands: Vec<String>, section: ConfigSection| {
+        commands.into_iter().map(move |command| RunCommandEntry {
+            command,
+            section: section.clone(),
+            shells: None,
+            profiles: None,
+        })
+    };
+    entries(exports, ConfigSection::Exports)
+        .chain(entries(options, ConfigSection::Other))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commands(behavior: &ShellBehavior, shell: &str) -> Vec<String> {
+        behavior_commands(behavior, shell)
+            .into_iter()
+            .map(|entry| entry.command)
+            .collect()
+    }
+
+    #[test]
+    fn test_behavior_commands() {
+        let behavior = ShellBehavior {
+            history_size: Some(50000),
+            history_ignore_duplicates: Some(true),
+            history_ignore_space: Some(true),
+            share_history: Some(true),
+            xdg_base_dirs: Some(true),
+            ..ShellBehavior::default()
+        };
+        let zsh = commands(&behavior, "zsh");
+        assert!(zsh.contains(&"export XDG_STATE_HOME=\"$HOME/.local/state\"".to_string()));
+        assert!(zsh.contains(&"export SAVEHIST=\"50000\"".to_string()));
+        assert!(zsh.contains(&"setopt SHARE_HISTORY".to_string()));
+
+        let bash = commands(&behavior, "bash");
+        assert!(bash.contains(&"export HISTFILESIZE=\"50000\"".to_string()));
+        assert!(bash.contains(&"export HISTCONTROL=\"ignoreboth:erasedups\"".to_string()));
+        assert!(bash.contains(&"shopt -s histappend".to_string()));
+
+        assert_eq!(
+            commands(&behavior, "fish"),
+            XDG_BASE_DIRS
+                .iter()
+                .map(|(name, value)| format!("set -gx {name} \"{value}\""))
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,120 @@
+//! Line diffs of RC files, shown by `setup-devbox shellrc preview`.
+
+/// One line of a diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    /// Hunk header (`@@ -1,4 +1,5 @@`).
+    Hunk(String),
+    Context(String),
+    Removed(String),
+    Added(String),
+}
+
+/// Unified diff of `old` and `new` with `context` unchanged lines around each change.
+/// Returns no lines when both are equal.
+pub fn unified_diff(old: &[String], new: &[String], context: usize) -> Vec<DiffLine> {
+    // Longest common subsequence table; RC files are small enough for the quadratic cost
+    let (n, m) = (old.len(), new.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    // Edit script: (old index, new index, line)
+    let mut edits: Vec<(usize, usize, DiffLine)> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old[i] == new[j] {
+            edits.push((i, j, DiffLine::Context(old[i].clone())));
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] > lcs[i + 1][j]) {
+            edits.push((i, j, DiffLine::Added(new[j].clone())));
+            j += 1;
+        } else {
+            edits.push((i, j, DiffLine::Removed(old[i].clone())));
+            i += 1;
+        }
+    }
+
+    // Group changes closer than twice the context into hunks
+    let changed: Vec<usize> = edits
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, _, line))| !matches!(line, DiffLine::Context(_)))
+        .map(|(index, _)| index)
+        .collect();
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for index in changed {
+        let start = index.saturating_sub(context);
+        let end = (index + context + 1).min(edits.len());
+        match hunks.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut diff = Vec::new();
+    for (start, end) in hunks {
+        let slice = &edits[start..end];
+        let old_count = slice
+            .iter()
+            .filter(|(_, _, line)| !matches!(line, DiffLine::Added(_)))
+            .count();
+        let new_count = slice
+            .iter()
+            .filter(|(_, _, line)| !matches!(line, DiffLine::Removed(_)))
+            .count();
+        let (old_start, new_start) = (slice[0].0, slice[0].1);
+        diff.push(DiffLine::Hunk(format!(
+            "@@ -{},{} +{},{} @@",
+            old_start + usize::from(old_count > 0),
+            old_count,
+            new_start + usize::from(new_count > 0),
+            new_count
+        )));
+        diff.extend(slice.iter().map(|(_, _, line)| line.clone()));
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_unified_diff() {
+        let old = lines("a\nb\nc\nd\ne\nf\ng");
+        let new = lines("a\nb\nc\nd\nx\nf\ng\nh");
+        assert_eq!(
+            unified_diff(&old, &new, 1),
+            vec![
+                DiffLine::Hunk("@@ -4,4 +4,5 @@".to_string()),
+                DiffLine::Context("d".to_string()),
+                DiffLine::Removed("e".to_string()),
+                DiffLine::Added("x".to_string()),
+                DiffLine::Context("f".to_string()),
+                DiffLine::Context("g".to_string()),
+                DiffLine::Added("h".to_string()),
+            ]
+        );
+        assert!(unified_diff(&old, &old, 3).is_empty());
+        assert_eq!(
+            unified_diff(&[], &lines("x"), 3),
+            vec![
+                DiffLine::Hunk("@@ -0,0 +1,1 @@".to_string()),
+                DiffLine::Added("x".to_string()),
+            ]
+        );
+    }
+}
@@ -1,3 +1,9 @@
+pub mod behavior;
+pub mod diff;
+pub mod reload;
+pub mod render;
+pub mod startup;
+
 use crate::schemas::shell_configuration::{AliasEntry, ConfigSection, RunCommandEntry};
 use crate::{log_debug, log_info};
 use colored::Colorize;
@@ -17,6 +23,11 @@ use std::collections::{HashMap, HashSet};
 /// - For export commands: Checks if the same variable name already exists with a different value
 /// - For other commands: Currently returns false (could be extended for other patterns)
 pub fn is_command_update(command: &str, existing_commands: &HashSet<String>) -> bool {
+    // Every line of a PATH-like variable is its own entry, never an update of another
+    if exported_variable_name(command).is_some_and(|name| name.ends_with("PATH")) {
+        return false;
+    }
+
     // Check if this is an export command with a valid assignment
     if command.starts_with("export ") && command.contains('=') {
         let equals_pos = command.find('=').unwrap(); // Safe unwrap after contains check
@@ -26,6 +37,13 @@ pub fn is_command_update(command: &str, existing_commands: &HashSet<String>) ->
             .any(|existing| existing.starts_with(&format!("export {var_name}")));
     }
 
+    // fish: `set -gx VAR value`
+    if let Some(var_name) = fish_variable_name(command) {
+        return existing_commands
+            .iter()
+            .any(|existing| existing.starts_with(&format!("set -gx {var_name}")));
+    }
+
     false
 }
 
@@ -189,7 +207,16 @@ pub fn section_header_name(section: &ConfigSection) -> &'static str {
 /// - Export commands: Keep only "export VAR_NAME" (strip the value)
 /// - Alias commands: Keep only "alias NAME" (strip the value)
 /// - Other commands: Normalize whitespace (collapse multiple spaces to single)
+///
+/// `PATH`-like variables are built up by several lines (`export PATH="$HOME/bin:$PATH"`),
+/// so each of those lines counts as a command of its own.
 pub fn normalize_command(command: &str) -> String {
+    if let Some(var_name) = exported_variable_name(command)
+        && var_name.ends_with("PATH")
+    {
+        return command.split_whitespace().collect::<Vec<_>>().join(" ");
+    }
+
     // Normalize export commands by extracting the variable name
     if command.starts_with("export ") && command.contains('=') {
         let equals_pos = command.find('=').unwrap(); // Safe unwrap after contains check
@@ -197,6 +224,11 @@ pub fn normalize_command(command: &str) -> String {
         return format!("export {var_name}");
     }
 
+    // Normalize fish variables the same way
+    if let Some(var_name) = fish_variable_name(command) {
+        return format!("set -gx {var_name}");
+    }
+
     // Normalize alias commands by extracting the alias name
     if command.starts_with("alias ") && command.contains('=') {
         let equals_pos = command.find('=').unwrap(); // Safe unwrap after contains check
@@ -208,6 +240,19 @@ pub fn normalize_command(command: &str) -> String {
     command.split_whitespace().collect::<Vec<_>>().join(" ")
 }
 
+/// The variable set by an `export VAR=value` or fish `set -gx VAR value` command.
+fn exported_variable_name(command: &str) -> Option<&str> {
+    match command.strip_prefix("export ") {
+        Some(rest) => rest.split_once('=').map(|(name, _)| name.trim()),
+        None => fish_variable_name(command),
+    }
+}
+
+/// The variable set by a fish `set -gx VAR value` command.
+fn fish_variable_name(command: &str) -> Option<&str> {
+    command.strip_prefix("set -gx ")?.split_whitespace().next()
+}
+
 /// Parses existing content from all managed sections in the RC file
 /// This builds a map of what commands already exist in each section
 ///
@@ -0,0 +1,242 @@
+//! Guidance on picking up a run's changes in the shell `now` was started from.
+//!
+//! A run cannot change the environment of the shell that started it: `PATH` entries and
+//! aliases written to startup files only apply to shells started afterwards, so a freshly
+//! installed tool can still be "command not found". After a run, the startup files of the
+//! current shell (`$SHELL`) that were written, and the directories of newly installed
+//! executables missing from the current `PATH`, are reported with the command that picks
+//! them up (`source ~/.zshrc`, or `exec zsh -l` when the login profile changed).
+//!
+//! With `now --activation-file`, the `PATH` entries and aliases are also written to a small
+//! snippet in the temporary directory that can be sourced instead of the whole RC file.
+
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use colored::Colorize;
+
+use crate::core::manage_rc_files::{get_profile_file, get_rc_file, read_rc_file};
+use crate::schemas::shell_configuration::AliasEntry;
+use crate::{log_debug, log_info, log_warn};
+
+static EMIT_ACTIVATION: AtomicBool = AtomicBool::new(false);
+
+/// Also writes an activation snippet after runs with changes (`--activation-file`).
+pub fn set_emit_activation(emit: bool) {
+    EMIT_ACTIVATION.store(emit, Ordering::Relaxed);
+}
+
+/// Tells how to pick up the changes of a run in the current shell.
+///
+/// # Arguments
+/// * `written` - Shells and startup files the run wrote (see `apply_shell_configs`)
+/// * `executables` - Install paths of the tools the run installed or updated
+/// * `aliases` - Aliases of `shellrc.yaml`
+pub fn report(written: &[(String, PathBuf)], executables: &[PathBuf], aliases: &[AliasEntry]) {
+    let Some(shell) = current_shell() else {
+        log_debug!("[SDB::Shell::Reload] $SHELL is not zsh, bash or fish; no guidance");
+        return;
+    };
+    let path_var = env::var_os("PATH").unwrap_or_default();
+    let missing = missing_from_path(executables, &path_var);
+
+    let startup_files: Vec<PathBuf> = [get_profile_file(&shell), get_rc_file(&shell)]
+        .into_iter()
+        .flatten()
+        .collect();
+    let (covered, uncovered): (Vec<PathBuf>, Vec<PathBuf>) = missing
+        .into_iter()
+        .partition(|dir| startup_files.iter().any(|file| mentions(file, dir)));
+    for dir in &uncovered {
+        log_warn!(
+            "[SDB::Shell::Reload] {} is not on PATH and no startup file of {} adds it; add {} to the run_commands of shellrc.yaml",
+            dir.display().to_string().yellow(),
+            shell,
+            format!("export PATH=\"{}:$PATH\"", dir.display()).cyan()
+        );
+    }
+
+    let written: Vec<&PathBuf> = written
+        .iter()
+        .filter(|(written_shell, _)| written_shell.eq_ignore_ascii_case(&shell))
+        .map(|(_, file)| file)
+        .collect();
+    if written.is_empty() && covered.is_empty() {
+        return;
+    }
+    let profile_written =
+        get_profile_file(&shell).is_some_and(|profile| written.contains(&&profile));
+    let command = reload_command(&shell, profile_written);
+    log_info!(
+        "[SDB::Shell::Reload] Run {} (or open a new terminal) to use the new PATH entries and aliases in this shell",
+        command.bold()
+    );
+
+    if EMIT_ACTIVATION.load(Ordering::Relaxed) {
+        let aliases = if written.is_empty() { &[] } else { aliases };
+        let path = env::temp_dir().join(format!(
+            "setup-devbox-activate.{}",
+            if shell == "fish" { "fish" } else { "sh" }
+        ));
+        match fs::write(&path, activation_snippet(&shell, &covered, aliases)) {
+            Ok(()) => log_info!(
+                "[SDB::Shell::Reload] Or only pick up PATH and aliases with: {}",
+                format!("source {}", path.display()).bold()
+            ),
+            Err(e) => log_warn!(
+                "[SDB::Shell::Reload] Failed to write {}: {}",
+                path.display(),
+                e
+            ),
+        }
+    }
+}
+
+/// The shell of the user's session, from `$SHELL`, when it is one setup-devbox configures.
+fn current_shell() -> Option<String> {
+    let shell = env::var_os("SHELL")?;
+    let name = Path::new(&shell)
+        .file_name()?
+        .to_string_lossy()
+        .to_lowercase();
+    ["zsh", "bash", "fish"]
+        .contains(&name.as_str())
+        .then_some(name)
+}
+
+/// Directories of `executables` that are not on `path_var`, in order and without duplicates.
+/// An install path that is a directory (`path-entry` tools without `binary`) is its own
+/// directory; relative install paths are package names and are skipped.
+fn missing_from_path(executables: &[PathBuf], path_var: &OsString) -> Vec<PathBuf> {
+    let on_path: Vec<PathBuf> = env::split_paths(path_var).collect();
+    let mut missing: Vec<PathBuf> = Vec::new();
+    for executable in executables.iter().filter(|path| path.is_absolute()) {
+        let dir = if executable.is_dir() {
+            executable.as_path()
+        } else {
+            match executable.parent() {
+                Some(parent) => parent,
+                None => continue,
+            }
+        };
+        if !on_path.iter().any(|entry| entry == dir) && !missing.iter().any(|m| m == dir) {
+            missing.push(dir.to_path_buf());
+        }
+    }
+    missing
+}
+
+/// Whether the startup file adds `dir`, written as is, with `~` or with `$HOME`.
+fn mentions(file: &Path, dir: &Path) -> bool {
+    let dir = dir.display().to_string();
+    let mut spellings = vec![dir.clone()];
+    if let Some(home) = dirs::home_dir()
+        && let Some(rest) = dir.strip_prefix(&*home.to_string_lossy())
+    {
+        spellings.push(format!("~{rest}"));
+        spellings.push(format!("$HOME{rest}"));
+        spellings.push(format!("${{HOME}}{rest}"));
+    }
+    read_rc_file(file).iter().any(|line| {
+        spellings
+            .iter()
+            .any(|spelling| line.contains(spelling.as_str()))
+    })
+}
+
+/// The command that makes the current shell read its startup files again: a login shell when
+/// the profile changed, since only login shells read it, otherwise sourcing the RC file.
+fn reload_command(shell: &str, profile_written: bool) -> String {
+    if profile_written {
+        return format!("exec {shell} -l");
+    }
+    match shell {
+        "zsh" => "source ~/.zshrc".to_string(),
+        "bash" => "source ~/.bashrc".to_string(),
+        "fish" => "source ~/.config/fish/config.fish".to_string(),
+        _ => format!("exec {shell}"),
+    }
+}
+
+/// Shell code that adds `dirs` to `PATH`, defines `aliases` and forgets remembered command
+/// locations.
+fn activation_snippet(shell: &str, dirs: &[PathBuf], aliases: &[AliasEntry]) -> String {
+    let mut snippet =
+        String::from("# Written by setup-devbox; source it to pick up the last run\n");
+    for dir in dirs {
+        if shell == "fish" {
+            snippet.push_str(&format!("set -gx PATH \"{}\" $PATH\n", dir.display()));
+        } else {
+            snippet.push_str(&format!("export PATH=\"{}:$PATH\"\n", dir.display()));
+        }
+    }
+    for alias in aliases {
+        if shell == "fish" {
+            let value = alias.value.replace('\\', r"\\").replace('\'', r"\'");
+            snippet.push_str(&format!("alias {} '{}'\n", alias.name, value));
+        } else {
+            let value = alias.value.replace('\'', r"'\''");
+            snippet.push_str(&format!("alias {}='{}'\n", alias.name, value));
+        }
+    }
+    if shell != "fish" {
+        snippet.push_str("hash -r\n");
+    }
+    snippet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_from_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let bin = dir.path().join("bin");
+        fs::create_dir(&bin).unwrap();
+        let path_var = env::join_paths(["/usr/bin", "/bin"]).unwrap();
+
+        let executables = [
+            PathBuf::from("/usr/bin/git"),
+            bin.join("rg"),
+            bin.join("fd"),
+            PathBuf::from("ripgrep"),
+            dir.path().join("tools"),
+        ];
+        let missing = missing_from_path(&executables, &path_var);
+        assert_eq!(missing, [bin.clone(), dir.path().to_path_buf()]);
+
+        fs::create_dir(dir.path().join("tools")).unwrap();
+        let missing = missing_from_path(&executables, &path_var);
+        assert_eq!(missing, [bin, dir.path().join("tools")]);
+    }
+
+    #[test]
+    fn test_reload_command_and_snippet() {
+        assert_eq!(reload_command("zsh", false), "source ~/.zshrc");
+        assert_eq!(reload_command("bash", true), "exec bash -l");
+        assert_eq!(
+            reload_command("fish", false),
+            "source ~/.config/fish/config.fish"
+        );
+
+        let aliases = [AliasEntry {
+            name: "gs".to_string(),
+            value: "git status --short".to_string(),
+            profiles: None,
+        }];
+        let dirs = [PathBuf::from("/opt/tools/bin")];
+        let snippet = activation_snippet("zsh", &dirs, &aliases);
+        assert!(snippet.contains("export PATH=\"/opt/tools/bin:$PATH\"\n"));
+        assert!(snippet.contains("alias gs='git status --short'\n"));
+        assert!(snippet.ends_with("hash -r\n"));
+
+        let snippet = activation_snippet("fish", &dirs, &aliases);
+        assert!(snippet.contains("set -gx PATH \"/opt/tools/bin\" $PATH\n"));
+        assert!(snippet.contains("alias gs 'git status --short'\n"));
+        assert!(!snippet.contains("hash -r"));
+    }
+}
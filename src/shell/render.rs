@@ -0,0 +1,161 @@
+//! Renders run commands, written in POSIX shell syntax, for a specific target shell.
+//!
+//! `shellrc.yaml` can configure several shells at once (`shells: [zsh, fish]`). Commands are
+//! written once and adapted per shell:
+//!
+//! - `eval "$(starship init zsh)"` initializes the target shell (`starship init bash`), and
+//!   becomes `starship init fish | source` for fish.
+//! - `export EDITOR="nvim"` becomes `set -gx EDITOR "nvim"` for fish; `PATH`-like variables
+//!   are split into a list (`set -gx PATH "$HOME/bin" $PATH`).
+//! - Functions are written in one shell's syntax, so they are only rendered for fish when the
+//!   entry lists fish in its `shells`.
+
+use crate::schemas::shell_configuration::{ConfigSection, RunCommandEntry};
+use regex::Regex;
+
+/// Shells whose name is replaced in `eval "$(tool init <shell>)"` commands.
+const SHELL_NAMES: [&str; 3] = ["zsh", "bash", "fish"];
+
+/// Renders `entry` for `shell`, or `None` if it does not apply to that shell.
+pub fn render_command(entry: &RunCommandEntry, shell: &str) -> Option<RunCommandEntry> {
+    let shell = shell.to_lowercase();
+    let explicitly_targeted = match &entry.shells {
+        Some(shells) => {
+            if !shells.iter().any(|s| s.eq_ignore_ascii_case(&shell)) {
+                return None;
+            }
+            true
+        }
+        None => false,
+    };
+
+    let command = entry.command.trim();
+    let rendered = if let Some(inner) = eval_inner_command(command) {
+        let inner = replace_shell_name(inner, &shell);
+        if shell == "fish" {
+            format!("{inner} | source")
+        } else {
+            format!("eval \"$({inner})\"")
+        }
+    } else if shell == "fish" && !explicitly_targeted {
+        if entry.section == ConfigSection::Functions {
+            return None;
+        }
+        // Multi-line entries may hold several exports
+        command
+            .lines()
+            .map(|line| fish_export(line.trim()).unwrap_or_else(|| line.to_string()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    } else {
+        command.to_string()
+    };
+
+    Some(RunCommandEntry {
+        command: rendered,
+        ..entry.clone()
+    })
+}
+
+/// The command inside `eval "$(...)"`, if `command` has that form.
+fn eval_inner_command(command: &str) -> Option<&str> {
+    command
+        .strip_prefix("eval ")?
+        .trim()
+        .strip_prefix("\"$(")?
+        .strip_suffix(")\"")
+        .map(str::trim)
+}
+
+/// Replaces the shell name an init command was written for with `shell`.
+fn replace_shell_name(command: &str, shell: &str) -> String {
+    if !SHELL_NAMES.contains(&shell) {
+        return command.to_string();
+    }
+    let re = Regex::new(r"\b(zsh|bash|fish)\b").expect("valid regex");
+    re.replace_all(command, shell).into_owned()
+}
+
+/// Translates `export NAME=VALUE` into fish's `set -gx NAME VALUE`.
+fn fish_export(command: &str) -> Option<String> {
+    let (name, value) = command.strip_prefix("export ")?.split_once('=')?;
+    let name = name.trim();
+    if !name.ends_with("PATH") {
+        return Some(format!("set -gx {name} {value}"));
+    }
+
+    let unquoted = value.trim_matches(|c| c == '"' || c == '\'');
+    let parts: Vec<String> = unquoted
+        .split(':')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            if part.starts_with('$') && !part.contains('/') {
+                part.to_string()
+            } else {
+                format!("\"{part}\"")
+            }
+        })
+        .collect();
+    Some(format!("set -gx {name} {}", parts.join(" ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(command: &str, section: ConfigSection) -> RunCommandEntry {
+        RunCommandEntry {
+            command: command.to_string(),
+            section,
+            shells: None,
+            profiles: None,
+        }
+    }
+
+    #[test]
+    fn test_render_for_fish() {
+        let render = |command: &str, section| {
+            render_command(&entry(command, section), "fish").map(|entry| entry.command)
+        };
+        assert_eq!(
+            render("eval \"$(starship init zsh)\"", ConfigSection::Evals),
+            Some("starship init fish | source".to_string())
+        );
+        assert_eq!(
+            render("export EDITOR=\"nvim\"", ConfigSection::Exports),
+            Some("set -gx EDITOR \"nvim\"".to_string())
+        );
+        assert_eq!(
+            render(
+                "export PATH=\"$HOME/.cargo/bin:$PATH\"",
+                ConfigSection::Paths
+            ),
+            Some("set -gx PATH \"$HOME/.cargo/bin\" $PATH".to_string())
+        );
+        assert_eq!(
+            render(
+                "export EDITOR=zed\nexport VISUAL=zed",
+                ConfigSection::Exports
+            ),
+            Some("set -gx EDITOR zed\nset -gx VISUAL zed".to_string())
+        );
+        assert_eq!(
+            render("mkcd() { mkdir -p \"$1\"; }", ConfigSection::Functions),
+            None
+        );
+    }
+
+    #[test]
+    fn test_render_for_posix_shells() {
+        let eval = entry("eval \"$(zoxide init zsh)\"", ConfigSection::Evals);
+        assert_eq!(
+            render_command(&eval, "bash").map(|entry| entry.command),
+            Some("eval \"$(zoxide init bash)\"".to_string())
+        );
+
+        let mut function = entry("mkcd() { mkdir -p \"$1\"; }", ConfigSection::Functions);
+        function.shells = Some(vec!["zsh".to_string()]);
+        assert!(render_command(&function, "zsh").is_some());
+        assert!(render_command(&function, "bash").is_none());
+    }
+}
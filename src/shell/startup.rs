@@ -0,0 +1,110 @@
+//! Which startup files login and interactive shells read, and how managed content is split
+//! between them.
+//!
+//! Login shells (macOS Terminal, SSH sessions, `zsh -l -c` from IDEs and GUI launchers) read
+//! the profile (`~/.zprofile`, `~/.bash_profile`); interactive shells read the RC file. bash
+//! login shells never read `~/.bashrc` on their own, so without a profile sourcing it the
+//! managed configuration is missing in exactly the shells macOS opens. With
+//! `split_login_profile: true`, PATH and exported variables go to the profile, where every
+//! shell started from the login session inherits them, and the rest stays in the RC file.
+
+use std::fs;
+
+use crate::core::manage_rc_files::{get_profile_file, get_rc_file, read_rc_file};
+use crate::schemas::shell_configuration::{ConfigSection, RunCommandEntry};
+use crate::shell::parse_existing_sections;
+
+/// Line a bash profile needs so login shells also load `~/.bashrc`.
+pub const BASHRC_SOURCE_LINE: &str = "if [ -f ~/.bashrc ]; then . ~/.bashrc; fi";
+
+/// Sections written to the profile when the configuration is split.
+fn is_profile_section(section: &ConfigSection) -> bool {
+    matches!(section, ConfigSection::Paths | ConfigSection::Exports)
+}
+
+/// Splits `shell`'s run commands into those for its profile and those for its RC file. The
+/// bash profile also gets the line sourcing `~/.bashrc`.
+pub fn split_for_login_profile(
+    shell: &str,
+    run_commands: Vec<RunCommandEntry>,
+) -> (Vec<RunCommandEntry>, Vec<RunCommandEntry>) {
+    let (mut profile, rc): (Vec<_>, Vec<_>) = run_commands
+        .into_iter()
+        .partition(|entry| is_profile_section(&entry.section));
+    if shell.eq_ignore_ascii_case("bash") {
+        profile.push(RunCommandEntry {
+            command: BASHRC_SOURCE_LINE.to_string(),
+            section: ConfigSection::Other,
+            shells: None,
+            profiles: None,
+        });
+    }
+    (profile, rc)
+}
+
+/// Problems with `shell`'s startup files that leave managed content unloaded in some shells.
+pub fn layout_warnings(shell: &str, split: bool) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let (Some(rc_path), Some(profile_path)) = (get_rc_file(shell), get_profile_file(shell)) else {
+        return warnings;
+    };
+
+    if split {
+        // Entries written before the split are still loaded from the RC file, after the
+        // profile, and override it
+        let existing = parse_existing_sections(&read_rc_file(&rc_path));
+        if [ConfigSection::Paths, ConfigSection::Exports]
+            .iter()
+            .any(|section| existing.get(section).is_some_and(|lines| !lines.is_empty()))
+        {
+            warnings.push(format!(
+                "{} still has managed Paths/Exports entries, which now go to {}; remove them from {}",
+                rc_path.display(),
+                profile_path.display(),
+                rc_path.display()
+            ));
+        }
+    } else if shell.eq_ignore_ascii_case("bash") {
+        let sources_bashrc = fs::read_to_string(&profile_path)
+            .is_ok_and(|content| content.lines().any(|line| line.contains(".bashrc")));
+        if !sources_bashrc {
+            warnings.push(format!(
+                "bash login shells (macOS Terminal, SSH) read {}, which doesn't source {}; \
+                 add '{}' there or set 'split_login_profile: true' in shellrc.yaml",
+                profile_path.display(),
+                rc_path.display(),
+                BASHRC_SOURCE_LINE
+            ));
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_for_login_profile() {
+        let entry = |command: &str, section: ConfigSection| RunCommandEntry {
+            command: command.to_string(),
+            section,
+            shells: None,
+            profiles: None,
+        };
+        let commands = vec![
+            entry("export PATH=\"$HOME/bin:$PATH\"", ConfigSection::Paths),
+            entry("export EDITOR=\"hx\"", ConfigSection::Exports),
+            entry("eval \"$(starship init bash)\"", ConfigSection::Evals),
+        ];
+
+        let (profile, rc) = split_for_login_profile("bash", commands.clone());
+        let profile: Vec<_> = profile.into_iter().map(|entry| entry.command).collect();
+        assert_eq!(profile.len(), 3);
+        assert_eq!(profile[2], BASHRC_SOURCE_LINE);
+        assert_eq!(rc.len(), 1);
+
+        let (profile, _) = split_for_login_profile("zsh", commands);
+        assert_eq!(profile.len(), 2);
+    }
+}
@@ -0,0 +1,167 @@
+// This module writes the state file while tools are being processed.
+//
+// Without it `state.json` is only written once the tools section completes, so a crash
+// mid-run forgets tools that did install and the next run installs them again. Each tool
+// that changes the state marks it dirty; the state is written before the next tool starts,
+// at most once per `AUTOSAVE_INTERVAL`, so runs that mostly skip tools don't rewrite the
+// file for every one of them. Pending changes are flushed regardless of the interval before
+// a tool is installed or updated, and once more after the last tool.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::log_debug;
+use crate::schemas::state_file::DevBoxState;
+use crate::schemas::tools_enums::ToolProcessingResult;
+use crate::state::backups;
+use crate::state::manager::write_state_atomically;
+
+/// Minimum time between two intermediate writes of the state file.
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Debounced writer of the state file during a run.
+#[derive(Debug)]
+pub struct StateAutosave {
+    path: PathBuf,
+    last_saved: Option<Instant>,
+    dirty: bool,
+}
+
+impl StateAutosave {
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            last_saved: None,
+            dirty: false,
+        }
+    }
+
+    /// Records the outcome of a tool; outcomes that changed the state make a save pending.
+    pub fn record(&mut self, result: &ToolProcessingResult) {
+        if matches!(
+            result,
+            ToolProcessingResult::Installed
+                | ToolProcessingResult::Updated
+                | ToolProcessingResult::ConfigurationUpdated
+        ) {
+            self.dirty = true;
+        }
+    }
+
+    /// Writes the state if a save is pending and the last one is old enough.
+    pub fn save_if_due(&mut self, state: &DevBoxState) {
+        if self
+            .last_saved
+            .is_some_and(|saved| saved.elapsed() < AUTOSAVE_INTERVAL)
+        {
+            return;
+        }
+        self.flush(state);
+    }
+
+    /// Writes the state if a save is pending, however recent the last one is.
+    pub fn flush(&mut self, state: &DevBoxState) {
+        if !self.dirty {
+            return;
+        }
+        // The first write of the run replaces the state from before it; keep a copy
+        if self.last_saved.is_none() {
+            backups::snapshot(&self.path);
+        }
+        match write_state_atomically(state, &self.path) {
+            Ok(()) => {
+                log_debug!(
+                    "[SDB::StateSave] Intermediate state written to {}",
+                    self.path.display()
+                );
+                self.dirty = false;
+                self.last_saved = Some(Instant::now());
+            }
+            // Not fatal: the final save at the end of the run tries again
+            Err(e) => log_debug!("[SDB::StateSave] Intermediate state save failed: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schemas::state_file::ToolState;
+
+    fn tool_state() -> ToolState {
+        serde_json::from_value(serde_json::json!({
+            "version": "1.0.0",
+            "install_path": "/usr/local/bin/tool",
+            "installed_by_devbox": true,
+            "install_method": "github",
+            "renamed_to": null,
+            "package_type": "binary",
+            "repo": null,
+            "tag": null,
+            "last_updated": null,
+            "executable_path_after_extract": null,
+        }))
+        .unwrap()
+    }
+
+    fn state_with(tool: &str) -> DevBoxState {
+        let mut state = DevBoxState::default();
+        state.tools.insert(tool.to_string(), tool_state());
+        state
+    }
+
+    fn saved_tools(path: &Path) -> Vec<String> {
+        let content = std::fs::read_to_string(path).unwrap();
+        let state: DevBoxState = serde_json::from_str(&content).unwrap();
+        let mut tools: Vec<String> = state.tools.into_keys().collect();
+        tools.sort();
+        tools
+    }
+
+    #[test]
+    fn test_nothing_is_written_until_state_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.json");
+        let mut autosave = StateAutosave::new(&path);
+
+        autosave.record(&ToolProcessingResult::Skipped("up to date".to_string()));
+        autosave.save_if_due(&state_with("fd"));
+        autosave.flush(&state_with("fd"));
+
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_dirty_state_is_written_at_most_once_per_interval() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.json");
+        let mut autosave = StateAutosave::new(&path);
+
+        autosave.record(&ToolProcessingResult::Installed);
+        autosave.save_if_due(&state_with("fd"));
+        assert_eq!(saved_tools(&path), ["fd"]);
+
+        // A second change right after the first write waits for the interval
+        let mut state = state_with("fd");
+        state.tools.insert("rg".to_string(), tool_state());
+        autosave.record(&ToolProcessingResult::Updated);
+        autosave.save_if_due(&state);
+        assert_eq!(saved_tools(&path), ["fd"]);
+    }
+
+    #[test]
+    fn test_flush_ignores_the_interval() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.json");
+        let mut autosave = StateAutosave::new(&path);
+
+        autosave.record(&ToolProcessingResult::Installed);
+        autosave.save_if_due(&state_with("fd"));
+
+        let mut state = state_with("fd");
+        state.tools.insert("rg".to_string(), tool_state());
+        autosave.record(&ToolProcessingResult::ConfigurationUpdated);
+        autosave.flush(&state);
+        assert_eq!(saved_tools(&path), ["fd", "rg"]);
+    }
+}
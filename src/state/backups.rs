@@ -0,0 +1,211 @@
+// This module keeps timestamped copies of the state file so a corrupted or bad state can be
+// rolled back with `setup-devbox state restore`.
+//
+// Before the state file is overwritten by a save, its current contents are copied to
+// `state-backups/` next to it (`~/.setup-devbox/state-backups/state-20250101_120000_123456.json`).
+// Only the newest backups are kept (10, or `SDB_STATE_BACKUPS`). Of the intermediate saves
+// during a run (see `autosave`) only the first one takes a backup, which holds the state from
+// before the run.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+use colored::Colorize;
+
+use crate::state::manager::{read_state_file, write_state_atomically};
+use crate::{log_debug, log_info, log_warn};
+
+/// Backups kept when `SDB_STATE_BACKUPS` is not set.
+const DEFAULT_KEEP: usize = 10;
+
+/// Directory holding the backups of `state_path` (next to the state file).
+pub fn backup_dir(state_path: &Path) -> PathBuf {
+    state_path.with_file_name("state-backups")
+}
+
+/// Copies the current state file into the backup directory and prunes old backups.
+/// Does nothing when there is no state file yet or its contents are already the newest
+/// backup. Failures are only warned about: a missing backup must not prevent saving.
+pub fn snapshot(state_path: &Path) {
+    let Ok(contents) = fs::read(state_path) else {
+        return;
+    };
+    let dir = backup_dir(state_path);
+    if let Some(newest) = list(state_path).first()
+        && fs::read(newest).is_ok_and(|newest| newest == contents)
+    {
+        log_debug!("[SDB::StateBackup] State unchanged since the newest backup");
+        return;
+    }
+
+    let extension = state_path
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    // Microseconds keep saves within the same second apart; a counter handles the rest
+    let stamp = Local::now().format("%Y%m%d_%H%M%S_%6f").to_string();
+    let mut backup = dir.join(format!("state-{stamp}{extension}"));
+    let mut counter = 1;
+    while backup.exists() {
+        backup = dir.join(format!("state-{stamp}_{counter}{extension}"));
+        counter += 1;
+    }
+    if let Err(e) = fs::create_dir_all(&dir).and_then(|_| fs::write(&backup, &contents)) {
+        log_warn!(
+            "[SDB::StateBackup] Failed to back up the state to {}: {}",
+            backup.display(),
+            e
+        );
+        return;
+    }
+    log_debug!(
+        "[SDB::StateBackup] Backed up the state to {}",
+        backup.display()
+    );
+    prune(state_path, keep());
+}
+
+/// Backups of `state_path`, newest first.
+pub fn list(state_path: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(backup_dir(state_path)) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with("state-"))
+        })
+        .collect();
+    // Timestamps in the names sort chronologically
+    backups.sort();
+    backups.reverse();
+    backups
+}
+
+/// Replaces the state file with a backup: the newest one, or `from`, given as a path or as
+/// the name of a file in the backup directory. The current state is backed up first, so a
+/// restore can itself be rolled back. Returns the backup that was restored.
+pub fn restore(state_path: &Path, from: Option<&str>) -> Result<PathBuf, String> {
+    let backup = match from {
+        Some(from) if Path::new(from).is_file() => PathBuf::from(from),
+        Some(from) => {
+            let candidate = backup_dir(state_path).join(from);
+            if !candidate.is_file() {
+                return Err(format!(
+                    "no backup named '{}' in {}",
+                    from,
+                    backup_dir(state_path).display()
+                ));
+            }
+            candidate
+        }
+        None => list(state_path)
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("no state backups in {}", backup_dir(state_path).display()))?,
+    };
+
+    // A backup that does not parse would only replace one broken state with another. It is
+    // written in the state file's format, which may have changed since the backup was taken.
+    let state = read_state_file(&backup)
+        .map_err(|e| format!("{} is not a valid state file: {}", backup.display(), e))?;
+
+    snapshot(state_path);
+    if let Some(parent) = state_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    write_state_atomically(&state, state_path)?;
+    log_info!(
+        "[SDB::StateBackup] Restored {} from {}",
+        state_path.display().to_string().cyan(),
+        backup.display().to_string().cyan()
+    );
+    Ok(backup)
+}
+
+/// Number of backups to keep, from `SDB_STATE_BACKUPS` (at least 1).
+fn keep() -> usize {
+    env::var("SDB_STATE_BACKUPS")
+        .ok()
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .map_or(DEFAULT_KEEP, |keep| keep.max(1))
+}
+
+/// Removes all but the `keep` newest backups.
+fn prune(state_path: &Path, keep: usize) {
+    for old in list(state_path).into_iter().skip(keep) {
+        if let Err(e) = fs::remove_file(&old) {
+            log_warn!(
+                "[SDB::StateBackup] Failed to remove old backup {}: {}",
+                old.display(),
+                e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EMPTY: &str = r#"{"tools":{},"settings":{},"fonts":{}}"#;
+
+    #[test]
+    fn test_snapshot_prune_and_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_path = dir.path().join("state.json");
+        let dir = backup_dir(&state_path);
+        fs::create_dir_all(&dir).unwrap();
+        for stamp in ["20250101_000000", "20250102_000000", "20250103_000000"] {
+            fs::write(dir.join(format!("state-{stamp}.json")), EMPTY).unwrap();
+        }
+        prune(&state_path, 2);
+        assert_eq!(
+            list(&state_path),
+            [
+                dir.join("state-20250103_000000.json"),
+                dir.join("state-20250102_000000.json")
+            ]
+        );
+
+        // Contents equal to the newest backup are not backed up again
+        fs::write(&state_path, EMPTY).unwrap();
+        snapshot(&state_path);
+        assert_eq!(list(&state_path).len(), 2);
+
+        fs::write(&state_path, "{ truncated").unwrap();
+        let restored = restore(&state_path, None).unwrap();
+        assert_eq!(restored, dir.join("state-20250103_000000.json"));
+        assert!(read_state_file(&state_path).unwrap().tools.is_empty());
+        // The broken state was backed up before it was replaced
+        assert!(
+            list(&state_path)
+                .iter()
+                .any(|backup| { fs::read_to_string(backup).unwrap() == "{ truncated" })
+        );
+
+        assert!(restore(&state_path, Some("state-19990101_000000.json")).is_err());
+        fs::write(dir.join("state-bad.json"), "not json").unwrap();
+        assert!(restore(&state_path, Some("state-bad.json")).is_err());
+    }
+
+    #[test]
+    fn test_back_to_back_snapshots_are_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_path = dir.path().join("state.json");
+        for contents in ["first", "second", "third"] {
+            fs::write(&state_path, contents).unwrap();
+            snapshot(&state_path);
+        }
+        let contents: Vec<String> = list(&state_path)
+            .iter()
+            .map(|backup| fs::read_to_string(backup).unwrap())
+            .collect();
+        assert_eq!(contents, ["third", "second", "first"]);
+    }
+}
@@ -0,0 +1,200 @@
+// This module implements resumable `now` runs.
+//
+// While tools are being processed, every finished tool is appended to a small
+// checkpoint file next to `state.json`. The state file is also written as tools
+// finish (see `autosave`), but it only holds installed tools, so if a run is
+// interrupted (Ctrl-C, crash, network loss) the checkpoint is the only record of
+// which tools were already evaluated and how each one ended. Running
+// `setup-devbox now --continue` reloads it, restores the recorded tool states and
+// skips those tools instead of re-evaluating them. A run that completes removes
+// the checkpoint.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::core::timestamps::current_timestamp;
+use crate::schemas::state_file::ToolState;
+use crate::schemas::tools_enums::ToolProcessingResult;
+use crate::{log_debug, log_info, log_warn};
+
+/// Progress of a single `now` run, persisted after every finished tool.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RunCheckpoint {
+    /// When the run that created this checkpoint started (RFC 3339).
+    pub started_at: String,
+    /// Finished tools keyed by name.
+    pub completed_tools: BTreeMap<String, CompletedTool>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+/// Outcome of a tool that finished before the run was interrupted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletedTool {
+    /// Outcome label (`installed`, `updated`, `configuration updated`, `skipped`).
+    pub outcome: String,
+    /// State recorded by the installer; present when the tool was installed or updated.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<ToolState>,
+}
+
+impl RunCheckpoint {
+    /// Starts a fresh checkpoint at `path`, discarding any left over from an earlier run.
+    pub fn start(path: &Path) -> Self {
+        if path.exists() {
+            log_info!(
+                "[SDB::Checkpoint] Found progress from an interrupted run; starting over. Use {} to resume instead.",
+                "setup-devbox now --continue".cyan()
+            );
+        }
+        let checkpoint = Self {
+            started_at: current_timestamp(),
+            completed_tools: BTreeMap::new(),
+            path: path.to_path_buf(),
+        };
+        checkpoint.save();
+        checkpoint
+    }
+
+    /// Loads the checkpoint of an interrupted run, or starts a fresh one if there is none.
+    pub fn resume(path: &Path) -> Self {
+        let loaded = fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<RunCheckpoint>(&content).ok());
+
+        match loaded {
+            Some(mut checkpoint) => {
+                log_info!(
+                    "[SDB::Checkpoint] Resuming run started at {} ({} tool(s) already finished)",
+                    checkpoint.started_at.cyan(),
+                    checkpoint.completed_tools.len()
+                );
+                checkpoint.path = path.to_path_buf();
+                checkpoint
+            }
+            None => {
+                log_info!("[SDB::Checkpoint] No interrupted run to continue; starting a new run.");
+                Self::start(path)
+            }
+        }
+    }
+
+    /// Returns the recorded outcome for `tool_name` if it finished in the interrupted run.
+    pub fn completed(&self, tool_name: &str) -> Option<&CompletedTool> {
+        self.completed_tools.get(tool_name)
+    }
+
+    /// Records a finished tool and persists the checkpoint. Failed tools are not recorded,
+    /// so they are retried on `--continue`.
+    pub fn record(
+        &mut self,
+        tool_name: &str,
+        result: &ToolProcessingResult,
+        state: Option<&ToolState>,
+    ) {
+        if matches!(
+            result,
+            ToolProcessingResult::Failed(_) | ToolProcessingResult::DryRun(_)
+        ) {
+            return;
+        }
+        self.completed_tools.insert(
+            tool_name.to_string(),
+            CompletedTool {
+                outcome: result.label().to_string(),
+                state: state.cloned(),
+            },
+        );
+        self.save();
+    }
+
+    /// Removes the checkpoint once the run has completed.
+    pub fn finish(self) {
+        if self.path.exists()
+            && let Err(e) = fs::remove_file(&self.path)
+        {
+            log_warn!(
+                "[SDB::Checkpoint] Failed to remove checkpoint {}: {}",
+                self.path.display(),
+                e
+            );
+        }
+    }
+
+    fn save(&self) {
+        let written = serde_json::to_string_pretty(self)
+            .map_err(|e| e.to_string())
+            .and_then(|content| fs::write(&self.path, content).map_err(|e| e.to_string()));
+        match written {
+            Ok(()) => log_debug!(
+                "[SDB::Checkpoint] Checkpoint written to {}",
+                self.path.display()
+            ),
+            Err(e) => log_warn!(
+                "[SDB::Checkpoint] Failed to write checkpoint {}: {}",
+                self.path.display(),
+                e
+            ),
+        }
+    }
+}
+
+impl CompletedTool {
+    /// Converts the recorded outcome back into a processing result for the run summary.
+    pub fn to_result(&self) -> ToolProcessingResult {
+        match self.outcome.as_str() {
+            "installed" => ToolProcessingResult::Installed,
+            "updated" => ToolProcessingResult::Updated,
+            "configuration updated" => ToolProcessingResult::ConfigurationUpdated,
+            _ => ToolProcessingResult::Skipped(
+                "[SDB::Tools] Already processed before the run was interrupted".to_string(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resume_skips_finished_tools_and_retries_failed_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("now-checkpoint.json");
+
+        let mut checkpoint = RunCheckpoint::start(&path);
+        checkpoint.record("rg", &ToolProcessingResult::Installed, None);
+        checkpoint.record(
+            "fd",
+            &ToolProcessingResult::Skipped("up to date".to_string()),
+            None,
+        );
+        checkpoint.record(
+            "bat",
+            &ToolProcessingResult::Failed("network".to_string()),
+            None,
+        );
+
+        // An interrupted run leaves the checkpoint behind for `--continue`
+        let resumed = RunCheckpoint::resume(&path);
+        assert_eq!(resumed.started_at, checkpoint.started_at);
+        assert!(matches!(
+            resumed.completed("rg").map(CompletedTool::to_result),
+            Some(ToolProcessingResult::Installed)
+        ));
+        assert!(matches!(
+            resumed.completed("fd").map(CompletedTool::to_result),
+            Some(ToolProcessingResult::Skipped(_))
+        ));
+        assert!(resumed.completed("bat").is_none());
+
+        resumed.finish();
+        assert!(!path.exists());
+        assert!(RunCheckpoint::resume(&path).completed_tools.is_empty());
+    }
+}
@@ -7,7 +7,7 @@
 //
 // Key functionalities include:
 // - Deserializing `DevBoxState` from JSON.
-// - Serializing `DevBoxState` to pretty-printed JSON.
+// - Serializing `DevBoxState` to pretty-printed (or compact) JSON with sorted keys.
 // - Error handling for file I/O and JSON parsing.
 // - Ensuring parent directories exist before writing.
 
@@ -15,9 +15,10 @@
 //                          STANDARD LIBRARY DEPENDENCIES                      //
 // =========================================================================== //
 
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::RwLock;
 
 // =========================================================================== //
 //                             EXTERNAL DEPENDENCIES                           //
@@ -30,18 +31,128 @@ use colored::Colorize;
 // =========================================================================== //
 
 use crate::core::timestamps::current_timestamp;
+use crate::schemas::common::{MainConfig, StateFormat, StateStyle};
 use crate::schemas::config_manager::ConfigurationManagerState;
 use crate::schemas::state_file::{DevBoxState, ToolState};
 use crate::schemas::tools_types::ToolEntry;
+use crate::state::backups;
 use crate::{log_debug, log_error, log_info, log_warn};
 
+/// Layout of the state file for this run, from `state_style` in `config.yaml`.
+static STATE_STYLE: RwLock<Option<StateStyle>> = RwLock::new(None);
+
+/// Reads the state file settings of the `config.yaml` at `config_file` and returns the
+/// configured state format. Commands write state without loading the whole configuration,
+/// so this happens when paths are resolved. The layout of any configuration read before is
+/// replaced, and restored to the default when `state_style` is not set.
+pub fn configure_from(config_file: &Path) -> StateFormat {
+    let config = fs::read_to_string(config_file)
+        .ok()
+        .and_then(|content| serde_yaml::from_str::<MainConfig>(&content).ok());
+    let (style, format) = config
+        .map(|config| (config.state_style, config.state_format))
+        .unwrap_or_default();
+    *STATE_STYLE.write().unwrap_or_else(|e| e.into_inner()) = style;
+    format.unwrap_or_default()
+}
+
+/// Serializes the state in the format of `path` and the configured layout. Maps are ordered
+/// by key, so unchanged entries serialize identically from run to run.
+fn serialize_state(state: &DevBoxState, path: &Path) -> Result<String, String> {
+    let mut serialized = match StateFormat::of(path) {
+        StateFormat::Json => match STATE_STYLE
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .unwrap_or_default()
+        {
+            StateStyle::Pretty => serde_json::to_string_pretty(state).map_err(|e| e.to_string())?,
+            StateStyle::Compact => serde_json::to_string(state).map_err(|e| e.to_string())?,
+        },
+        StateFormat::Yaml => serde_yaml::to_string(state).map_err(|e| e.to_string())?,
+        StateFormat::Toml => toml::to_string_pretty(state).map_err(|e| e.to_string())?,
+    };
+    if !serialized.ends_with('\n') {
+        serialized.push('\n');
+    }
+    Ok(serialized)
+}
+
+/// Parses state file content in the format of `path`.
+fn parse_state(content: &str, path: &Path) -> Result<DevBoxState, String> {
+    match StateFormat::of(path) {
+        StateFormat::Json => serde_json::from_str(content).map_err(|e| e.to_string()),
+        StateFormat::Yaml => serde_yaml::from_str(content).map_err(|e| e.to_string()),
+        StateFormat::Toml => toml::from_str(content).map_err(|e| e.to_string()),
+    }
+}
+
+/// Reads and parses the state file at `path`, in the format its extension names.
+pub fn read_state_file(path: &Path) -> Result<DevBoxState, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    parse_state(&content, path)
+}
+
+/// When the state file at `path` doesn't exist yet but one in another format does next to it
+/// (after `state_format` was changed), converts that one: the state is written to `path` and
+/// the old file is kept with a `.migrated` suffix.
+fn migrate_other_format(path: &Path) -> Result<Option<DevBoxState>, String> {
+    let Some(previous) = StateFormat::ALL
+        .iter()
+        .map(|format| path.with_extension(format.extension()))
+        .find(|candidate| candidate != path && candidate.exists())
+    else {
+        return Ok(None);
+    };
+
+    let state = match read_state_file(&previous) {
+        Ok(state) => state,
+        Err(e) => {
+            log_error!(
+                "[SDB::StateLoader] Could not convert {}: {}. Please check the file's content or delete it to start fresh.",
+                previous.display().to_string().red(),
+                e
+            );
+            return Err(format!("could not convert {}: {e}", previous.display()));
+        }
+    };
+    let Ok(serialized) = serialize_state(&state, path) else {
+        return Ok(None);
+    };
+    if let Err(e) = write_atomically(path, &serialized) {
+        log_error!(
+            "[SDB::StateLoader] Failed to write converted state to {}: {}",
+            path.display().to_string().red(),
+            e
+        );
+        return Err(format!(
+            "failed to write converted state to {}: {e}",
+            path.display()
+        ));
+    }
+    let mut migrated = previous.clone().into_os_string();
+    migrated.push(".migrated");
+    if let Err(e) = fs::rename(&previous, &migrated) {
+        log_warn!(
+            "[SDB::StateLoader] Converted state, but could not rename {}: {}",
+            previous.display(),
+            e
+        );
+    }
+    log_info!(
+        "[SDB] Converted state file {} to {}",
+        previous.display().to_string().yellow(),
+        path.display().to_string().cyan()
+    );
+    Ok(Some(state))
+}
+
 /// Loads the application's state from `state.json` or initializes a new one.
 ///
 /// This function centralizes the logic for reading the existing state file,
 /// handling potential parsing errors, and creating a fresh state if the file
 /// doesn't exist. It also ensures the parent directory for the state file exists.
-/// This is a critical startup function, exiting the application if unrecoverable
-/// errors occur (e.g., unreadable file, malformed JSON that cannot be parsed).
+/// This is a critical startup function: unrecoverable errors (e.g., unreadable file,
+/// malformed JSON that cannot be parsed) are logged and returned to the caller.
 ///
 /// # Arguments
 /// * `state_path_resolved`: The `PathBuf` to the `state.json` file where the state
@@ -49,9 +160,9 @@ use crate::{log_debug, log_error, log_info, log_warn};
 ///
 /// # Returns
 /// * `DevBoxState`: A `DevBoxState` struct representing the loaded or newly initialized state.
-///   The function will `std::process::exit(1)` if a critical error
-///   (like an unreadable or unparsable state file) occurs.
-pub fn load_or_initialize_state(state_path_resolved: &PathBuf) -> DevBoxState {
+///   Returns an error if a critical error (like an unreadable or unparsable state file)
+///   occurs.
+pub fn load_or_initialize_state(state_path_resolved: &PathBuf) -> Result<DevBoxState, String> {
     log_debug!("[SDB::StateLoader] Entering load_or_initialize_state() function."); // Debug log for function entry.
 
     let state: DevBoxState = if state_path_resolved.exists() {
@@ -62,8 +173,9 @@ pub fn load_or_initialize_state(state_path_resolved: &PathBuf) -> DevBoxState {
         );
         match fs::read_to_string(state_path_resolved) {
             Ok(contents) => {
-                // If file content is read successfully, attempt to deserialize the JSON.
-                match serde_json::from_str(&contents) {
+                // If file content is read successfully, attempt to deserialize it in the
+                // format its extension names.
+                match parse_state(&contents, state_path_resolved) {
                     Ok(parsed_state) => {
                         log_info!(
                             "[SDB] Using state file: {}",
@@ -97,11 +209,20 @@ pub fn load_or_initialize_state(state_path_resolved: &PathBuf) -> DevBoxState {
                     Err(err) => {
                         // If JSON deserialization fails (e.g., corrupted file, schema mismatch).
                         log_error!(
-                            "[SDB::StateLoader] Invalid state.json format at {}: {}. Please check the file's content or delete it to start fresh.",
+                            "[SDB::StateLoader] Invalid state file format at {}: {}. Please check the file's content or delete it to start fresh.",
                             state_path_resolved.display().to_string().red(),
                             err
                         );
-                        std::process::exit(1); // Exit due to critical error.
+                        if !backups::list(state_path_resolved).is_empty() {
+                            log_error!(
+                                "[SDB::StateLoader] Run {} to roll back to the state before the last save.",
+                                "setup-devbox state restore".cyan()
+                            );
+                        }
+                        return Err(format!(
+                            "invalid state file format at {}: {err}",
+                            state_path_resolved.display()
+                        ));
                     }
                 }
             }
@@ -112,9 +233,15 @@ pub fn load_or_initialize_state(state_path_resolved: &PathBuf) -> DevBoxState {
                     state_path_resolved.display().to_string().red(),
                     err
                 );
-                std::process::exit(1); // Exit due to critical error.
+                return Err(format!(
+                    "failed to read state file {}: {err}",
+                    state_path_resolved.display()
+                ));
             }
         }
+    } else if let Some(migrated_state) = migrate_other_format(state_path_resolved)? {
+        // The state was kept in another format until `state_format` changed.
+        migrated_state
     } else {
         // If the state file does not exist, initialize a new, empty state.
         log_info!(
@@ -122,9 +249,14 @@ pub fn load_or_initialize_state(state_path_resolved: &PathBuf) -> DevBoxState {
             state_path_resolved.display().to_string().yellow()
         );
         let initial_state = DevBoxState {
-            tools: HashMap::new(),    // Initialize with empty HashMap for tools.
-            fonts: HashMap::new(),    // Initialize with empty HashMap for fonts.
-            settings: HashMap::new(), // Initialize with empty HashMap for settings.
+            tools: BTreeMap::new(),         // Initialize with empty BTreeMap for tools.
+            fonts: BTreeMap::new(),         // Initialize with empty BTreeMap for fonts.
+            settings: BTreeMap::new(),      // Initialize with empty BTreeMap for settings.
+            launch_agents: BTreeMap::new(), // Initialize with empty BTreeMap for launch agents.
+            file_associations: BTreeMap::new(), // Initialize with empty BTreeMap for file associations.
+            protected_changes: Vec::new(),      // No overrides of protected entries yet.
+            name_collisions: Vec::new(),        // No executables taken over yet.
+            rollouts: BTreeMap::new(),          // No rollout decisions yet.
         };
 
         // Ensure the parent directory for the state file exists before attempting to write.
@@ -134,18 +266,21 @@ pub fn load_or_initialize_state(state_path_resolved: &PathBuf) -> DevBoxState {
                 parent_dir
             );
             if let Err(e) = fs::create_dir_all(parent_dir) {
-                // If directory creation fails, log error and exit.
+                // If directory creation fails, the state could never be saved.
                 log_error!(
                     "[SDB::StateLoader] Failed to create directory for state file at {:?}: {}. Cannot save state.",
                     parent_dir.display().to_string().red(),
                     e
                 );
-                std::process::exit(1); // Exit due to critical error.
+                return Err(format!(
+                    "failed to create directory {}: {e}",
+                    parent_dir.display()
+                ));
             }
         }
 
         // Attempt to serialize and write the initial empty state to the file.
-        match serde_json::to_string_pretty(&initial_state) {
+        match serialize_state(&initial_state, state_path_resolved) {
             Ok(serialized_state) => {
                 if let Err(err) = fs::write(state_path_resolved, serialized_state) {
                     // If writing fails, log a non-critical error (app can still run but won't save state).
@@ -167,20 +302,21 @@ pub fn load_or_initialize_state(state_path_resolved: &PathBuf) -> DevBoxState {
                     "[SDB::StateWriter] Failed to serialize initial state: {}. This is an internal application error.",
                     err
                 );
-                std::process::exit(1); // Exit due to critical error.
+                return Err(format!("failed to serialize initial state: {err}"));
             }
         }
         initial_state // Return the newly initialized state.
     };
     log_debug!("[SDB::StateLoader] Exiting load state file function."); // Debug log for function exit.
-    state // Return the final loaded or initialized state.
+    Ok(state) // Return the final loaded or initialized state.
 }
 
 /// Saves the current `DevBoxState` to the specified `state.json` file.
 ///
-/// This function serializes the `DevBoxState` struct into a human-readable, pretty-printed JSON format
-/// and writes it to the disk. It also handles creating any necessary parent directories for the
-/// state file if they do not exist, ensuring the save operation can proceed smoothly.
+/// This function serializes the `DevBoxState` struct into JSON (pretty-printed unless
+/// `state_style: compact` is set) and writes it to the disk. It also handles creating any
+/// necessary parent directories for the state file if they do not exist, ensuring the save
+/// operation can proceed smoothly.
 /// This is essential for `setup-devbox` to persist its "memory" of installed tools and settings.
 ///
 /// # Arguments
@@ -220,13 +356,15 @@ pub fn save_devbox_state(state: &DevBoxState, state_path: &Path) -> bool {
         }
     }
 
-    // Try to serialize the `DevBoxState` struct into a pretty-printed JSON string.
-    // `serde_json::to_string_pretty` makes the JSON output readable for debugging and inspection.
-    match serde_json::to_string_pretty(state) {
+    // Try to serialize the `DevBoxState` struct into JSON, pretty-printed unless
+    // `state_style: compact` is set.
+    match serialize_state(state, state_path) {
         Ok(serialized_state) => {
-            // If serialization was successful, attempt to write the JSON string to the state file.
-            // `fs::write` is a convenience function that creates the file (or truncates it) and writes all data.
-            match fs::write(state_path, serialized_state) {
+            // If serialization was successful, keep a copy of the previous state, then write the
+            // JSON string to the state file through a temporary file, so an interruption never
+            // leaves a truncated state behind.
+            backups::snapshot(state_path);
+            match write_atomically(state_path, &serialized_state) {
                 Ok(_) => {
                     // Print an empty line to ensure clean terminal output, separating logs from other output.
                     eprintln!("\n");
@@ -260,6 +398,24 @@ pub fn save_devbox_state(state: &DevBoxState, state_path: &Path) -> bool {
     }
 }
 
+/// Writes `contents` to a temporary file next to `path` and renames it over `path`.
+fn write_atomically(path: &Path, contents: &str) -> std::io::Result<()> {
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp_path);
+    })
+}
+
+/// Serializes and atomically writes the state without logging, for intermediate saves
+/// during a run. The parent directory must already exist.
+pub(crate) fn write_state_atomically(state: &DevBoxState, state_path: &Path) -> Result<(), String> {
+    let serialized_state = serialize_state(state, state_path)?;
+    write_atomically(state_path, &serialized_state).map_err(|e| e.to_string())
+}
+
 /// Saves the current state to file if changes were made
 pub fn save_state_to_file(state: &DevBoxState, state_file_path: &Path) {
     log_info!("[SDB] Saving updated state...");
@@ -368,6 +524,32 @@ impl ToolState {
             executed_post_installation_hooks,
             // Configuration Manager for the tool, if SDB is managing the configuration for the tool.
             configuration_manager: None,
+            // Extra release assets are recorded by the installer after the main binary is in place.
+            assets: None,
+            // HTTP cache validators are recorded by the URL installer after downloading.
+            etag: None,
+            last_modified: None,
+            // Go and cargo tools pinned with `version: commit:<hash>` record the commit.
+            commit: tool_entry.pinned_commit().map(str::to_string),
+            // Shims are generated by the orchestrator once the installer has finished.
+            shim: None,
+            // The license is looked up by the orchestrator before installing.
+            license: None,
+            // Documentation-only metadata, kept so `sync-config` can restore it.
+            notes: tool_entry.notes.clone(),
+            owner: tool_entry.owner.clone(),
+            protected: tool_entry.protected,
+            // The receipt is written by the orchestrator once everything is in place.
+            receipt: None,
+            install_duration_ms: None,
+            disk_usage_bytes: None,
+            permissions_acknowledged: None,
+            // Earlier renames are carried over by the orchestrator.
+            rename_history: Vec::new(),
+            // Set by the orchestrator when `optimize_binary` shrank the executable.
+            optimized_binary: None,
+            app_bundle: None,
+            installer_version: None,
         }
     }
 
@@ -407,3 +589,99 @@ impl ToolState {
         .to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tool_state(version: &str) -> ToolState {
+        serde_json::from_value(json!({
+            "version": version,
+            "install_path": "/home/dev/bin/tool",
+            "installed_by_devbox": true,
+            "install_method": "github",
+            "package_type": "binary",
+            "repo": "owner/tool",
+            "tag": version,
+        }))
+        .unwrap()
+    }
+
+    fn sample_state(names: &[&str]) -> DevBoxState {
+        let mut state = DevBoxState::default();
+        for name in names {
+            state.tools.insert(name.to_string(), tool_state("1.0.0"));
+        }
+        state
+    }
+
+    #[test]
+    fn test_serialization_does_not_depend_on_insertion_order() {
+        let path = Path::new("state.json");
+        let first = serialize_state(&sample_state(&["zoxide", "bat", "rg"]), path).unwrap();
+        let second = serialize_state(&sample_state(&["rg", "zoxide", "bat"]), path).unwrap();
+
+        assert_eq!(first, second);
+        assert!(first.ends_with('\n'));
+        let positions: Vec<usize> = ["\"bat\"", "\"rg\"", "\"zoxide\""]
+            .iter()
+            .map(|key| first.find(key).unwrap())
+            .collect();
+        assert!(positions.is_sorted());
+    }
+
+    #[test]
+    fn test_state_round_trips_in_every_format() {
+        let mut state = sample_state(&["rg", "bat"]);
+        state.settings.insert(
+            "com.apple.dock:autohide".to_string(),
+            serde_json::from_value(json!({
+                "domain": "com.apple.dock",
+                "key": "autohide",
+                "value": "true",
+                "value_type": "bool",
+            }))
+            .unwrap(),
+        );
+        state.fonts.insert(
+            "FiraCode".to_string(),
+            serde_json::from_value(json!({
+                "name": "FiraCode",
+                "install_method": "github",
+                "url": "https://example.com/FiraCode.zip",
+                "files": ["FiraCode-Regular.ttf"],
+                "version": "6.2",
+            }))
+            .unwrap(),
+        );
+        let json_path = Path::new("state.json");
+        let expected = serialize_state(&state, json_path).unwrap();
+
+        for format in StateFormat::ALL {
+            let path = PathBuf::from(format!("state.{}", format.extension()));
+            let serialized = serialize_state(&state, &path).unwrap();
+            let parsed = parse_state(&serialized, &path).unwrap();
+            assert_eq!(
+                serialize_state(&parsed, json_path).unwrap(),
+                expected,
+                "{format:?} round trip"
+            );
+        }
+    }
+
+    #[test]
+    fn test_state_in_another_format_is_converted() {
+        let dir = tempfile::tempdir().unwrap();
+        let json_path = dir.path().join("state.json");
+        let yaml_path = dir.path().join("state.yaml");
+        let state = sample_state(&["rg"]);
+        fs::write(&json_path, serialize_state(&state, &json_path).unwrap()).unwrap();
+
+        let migrated = migrate_other_format(&yaml_path).unwrap().unwrap();
+        assert_eq!(migrated.tools["rg"].version, "1.0.0");
+        assert_eq!(read_state_file(&yaml_path).unwrap().tools.len(), 1);
+        assert!(!json_path.exists());
+        assert!(dir.path().join("state.json.migrated").exists());
+    }
+}
@@ -1 +1,6 @@
+pub(crate) mod autosave;
+pub(crate) mod backups;
+pub(crate) mod checkpoint;
 pub(crate) mod manager;
+pub(crate) mod receipts;
+pub(crate) mod run_summary;
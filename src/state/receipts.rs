@@ -0,0 +1,476 @@
+// This module records an install receipt for every tool: the files its installation created
+// or modified.
+//
+// Before a tool is installed, the directories installers write to (the bin directory, cargo
+// and go bin directories, completion and man page directories) are snapshotted. Afterwards
+// every new or changed file in them is recorded, together with the paths the installation
+// reports itself (executable, release assets, shim, configuration files) and, for Homebrew,
+// the file list `brew list` keeps. The receipt is written to `<state dir>/receipts/<tool>.json`
+// and referenced from the tool's state (`ToolState::receipt`).
+//
+// Receipts let `remove` delete every file an installation created, and `setup-devbox owns
+// <path>` tell which tool a file belongs to and whether it changed since it was installed.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+use crate::core::timestamps::current_timestamp;
+use crate::schemas::path_resolver::PathResolver;
+use crate::schemas::state_file::{DevBoxState, ToolState};
+use crate::{log_debug, log_warn};
+
+static RECEIPTS_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// How deep watched directories are scanned (e.g. `share/man/man1/tool.1`).
+const MAX_SCAN_DEPTH: usize = 3;
+
+/// Enables receipts for this run, writing them to `receipts_dir`.
+pub fn configure(receipts_dir: PathBuf) {
+    let _ = RECEIPTS_DIR.set(receipts_dir);
+}
+
+/// Files recorded for one installed tool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Receipt {
+    pub tool: String,
+    pub version: String,
+    pub install_method: String,
+    /// When the receipt was written (RFC 3339).
+    pub recorded_at: String,
+    pub files: Vec<ReceiptFile>,
+}
+
+/// A file written by an installation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiptFile {
+    pub path: String,
+    pub change: FileChange,
+    pub size: u64,
+    /// SHA-256 of the file right after the installation.
+    pub sha256: String,
+}
+
+/// Whether the installation created a file or changed an existing one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileChange {
+    Created,
+    Modified,
+}
+
+/// State of a recorded file compared to its receipt.
+#[derive(Debug, PartialEq, Eq)]
+pub enum FileIntegrity {
+    Intact,
+    Changed,
+    Missing,
+}
+
+impl Receipt {
+    /// Reads the receipt at `path`.
+    pub fn load(path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Loads the receipt referenced by a tool's state.
+    pub fn of(tool_state: &ToolState) -> Option<Self> {
+        Self::load(Path::new(tool_state.receipt.as_ref()?))
+    }
+
+    /// The recorded entry for `path`, if the installation wrote it.
+    pub fn file(&self, path: &Path) -> Option<&ReceiptFile> {
+        self.files.iter().find(|file| Path::new(&file.path) == path)
+    }
+}
+
+impl ReceiptFile {
+    /// Compares the file on disk with the recorded checksum.
+    pub fn integrity(&self) -> FileIntegrity {
+        match sha256_file(Path::new(&self.path)) {
+            Ok(sha256) if sha256 == self.sha256 => FileIntegrity::Intact,
+            Ok(_) => FileIntegrity::Changed,
+            Err(_) => FileIntegrity::Missing,
+        }
+    }
+}
+
+/// Modification time and size of the files in the watched directories before an installation.
+pub struct FileSnapshot {
+    roots: Vec<PathBuf>,
+    files: HashMap<PathBuf, (SystemTime, u64)>,
+}
+
+impl FileSnapshot {
+    /// Snapshots the directories installers write to, plus the directory of the tool's
+    /// current executable.
+    pub fn take(previous: Option<&ToolState>) -> Self {
+        let mut roots = watched_directories();
+        if let Some(parent) = previous.and_then(|state| Path::new(&state.install_path).parent())
+            && !roots.iter().any(|root| parent.starts_with(root))
+        {
+            roots.push(parent.to_path_buf());
+        }
+        let files = scan(&roots);
+        FileSnapshot { roots, files }
+    }
+
+    /// Files created or modified in the watched directories since the snapshot.
+    fn changes(&self) -> Vec<(PathBuf, FileChange)> {
+        scan(&self.roots)
+            .into_iter()
+            .filter_map(|(path, metadata)| match self.files.get(&path) {
+                None => Some((path, FileChange::Created)),
+                Some(before) if *before != metadata => Some((path, FileChange::Modified)),
+                Some(_) => None,
+            })
+            .collect()
+    }
+
+    fn contains(&self, path: &Path) -> bool {
+        self.files.contains_key(path)
+    }
+}
+
+/// Writes the receipt of a freshly installed tool and references it from `tool_state`.
+///
+/// Files recorded for a previous version that still exist stay in the receipt, so an update
+/// never loses track of files the first installation created.
+pub fn record(
+    tool_name: &str,
+    snapshot: &FileSnapshot,
+    previous: Option<&ToolState>,
+    tool_state: &mut ToolState,
+) {
+    let Some(receipts_dir) = RECEIPTS_DIR.get() else {
+        return;
+    };
+
+    let mut files: BTreeMap<PathBuf, FileChange> = previous
+        .and_then(Receipt::of)
+        .map(|receipt| {
+            receipt
+                .files
+                .into_iter()
+                .map(|file| (PathBuf::from(file.path), file.change))
+                .collect()
+        })
+        .unwrap_or_default();
+    for (path, change) in snapshot.changes() {
+        files.entry(path).or_insert(change);
+    }
+    for path in reported_paths(tool_name, tool_state) {
+        let change = if snapshot.contains(&path) {
+            FileChange::Modified
+        } else {
+            FileChange::Created
+        };
+        files.entry(path).or_insert(change);
+    }
+
+    let receipt = Receipt {
+        tool: tool_name.to_string(),
+        version: tool_state.version.clone(),
+        install_method: tool_state.install_method.clone(),
+        recorded_at: current_timestamp(),
+        files: files
+            .into_iter()
+            .filter_map(|(path, change)| {
+                let size = fs::metadata(&path).ok().filter(|m| m.is_file())?.len();
+                Some(ReceiptFile {
+                    sha256: sha256_file(&path).ok()?,
+                    path: path.to_string_lossy().into_owned(),
+                    change,
+                    size,
+                })
+            })
+            .collect(),
+    };
+
+    let receipt_path = receipts_dir.join(format!("{tool_name}.json"));
+    let result = fs::create_dir_all(receipts_dir).and_then(|_| {
+        let content = serde_json::to_string_pretty(&receipt).map_err(io::Error::other)?;
+        fs::write(&receipt_path, content)
+    });
+    match result {
+        Ok(()) => {
+            log_debug!(
+                "[SDB::Receipts] Recorded {} file(s) for {} in {}",
+                receipt.files.len(),
+                tool_name,
+                receipt_path.display()
+            );
+            tool_state.receipt = Some(receipt_path.to_string_lossy().into_owned());
+        }
+        Err(e) => log_warn!(
+            "[SDB::Receipts] Failed to write the receipt of {}: {}",
+            tool_name,
+            e
+        ),
+    }
+}
+
+/// Deletes the files the installation of a removed tool created, and its receipt.
+///
+/// Files changed since the installation, and files another tool's receipt also lists, are
+/// kept. Returns the paths that were deleted.
+pub fn remove_created_files(state: &DevBoxState, tool_name: &str) -> Vec<PathBuf> {
+    let Some(tool_state) = state.tools.get(tool_name) else {
+        return Vec::new();
+    };
+    let Some(receipt) = Receipt::of(tool_state) else {
+        return Vec::new();
+    };
+
+    let others: Vec<Receipt> = state
+        .tools
+        .iter()
+        .filter(|(name, _)| name.as_str() != tool_name)
+        .filter_map(|(_, other)| Receipt::of(other))
+        .collect();
+
+    let mut removed = Vec::new();
+    for file in &receipt.files {
+        let path = PathBuf::from(&file.path);
+        if file.change != FileChange::Created
+            || others.iter().any(|other| other.file(&path).is_some())
+        {
+            continue;
+        }
+        match file.integrity() {
+            FileIntegrity::Intact => match fs::remove_file(&path) {
+                Ok(()) => removed.push(path),
+                Err(e) => log_warn!("[SDB::Receipts] Failed to remove {}: {}", file.path, e),
+            },
+            FileIntegrity::Changed => log_warn!(
+                "[SDB::Receipts] Keeping {}: it changed since {} was installed",
+                file.path,
+                tool_name
+            ),
+            FileIntegrity::Missing => {}
+        }
+    }
+
+    if let Some(receipt_path) = &tool_state.receipt {
+        let _ = fs::remove_file(receipt_path);
+    }
+    removed
+}
+
+/// Tools whose receipt lists `path`, with the recorded entry.
+pub fn owners<'a>(state: &'a DevBoxState, path: &Path) -> Vec<(&'a str, ReceiptFile)> {
+    let mut owners: Vec<(&str, ReceiptFile)> = state
+        .tools
+        .iter()
+        .filter_map(|(name, tool_state)| {
+            let receipt = Receipt::of(tool_state)?;
+            Some((name.as_str(), receipt.file(path)?.clone()))
+        })
+        .collect();
+    owners.sort_by_key(|(name, _)| *name);
+    owners
+}
+
+/// Directories installers write executables, completions and man pages to.
+fn watched_directories() -> Vec<PathBuf> {
+    let mut directories: Vec<PathBuf> = PathResolver::get_user_bin_dir().into_iter().collect();
+    if let Some(home) = dirs::home_dir() {
+        let cargo_home = std::env::var_os("CARGO_HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| home.join(".cargo"));
+        let go_bin = std::env::var_os("GOBIN")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| home.join("go/bin"));
+        directories.extend([
+            cargo_home.join("bin"),
+            go_bin,
+            home.join(".local/bin"),
+            home.join(".local/share/man"),
+            home.join(".local/share/zsh/site-functions"),
+            home.join(".local/share/bash-completion/completions"),
+            home.join(".config/fish/completions"),
+            home.join(".zfunc"),
+        ]);
+    }
+    directories.retain(|directory| directory.is_dir());
+    directories
+}
+
+/// Paths an installation reports in the tool's state.
+fn reported_paths(tool_name: &str, tool_state: &ToolState) -> Vec<PathBuf> {
+    let mut paths = vec![PathBuf::from(&tool_state.install_path)];
+    if let Some(shim) = &tool_state.shim {
+        paths.push(PathBuf::from(&shim.path));
+    }
+    for asset in tool_state.assets.iter().flatten() {
+        paths.extend(asset.installed_paths.iter().map(PathBuf::from));
+    }
+    if let Some(configuration) = &tool_state.configuration_manager {
+        paths.extend(
+            configuration
+                .tools_configuration_paths
+                .iter()
+                .map(|path| PathBuf::from(shellexpand::tilde(path).into_owned())),
+        );
+    }
+    if tool_state.install_method == "brew" {
+        paths.extend(brew_files(tool_name));
+    }
+    paths
+}
+
+/// Files Homebrew installed for a formula or cask.
+fn brew_files(tool_name: &str) -> Vec<PathBuf> {
+    let Ok(output) = Command::new("brew").args(["list", tool_name]).output() else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        // Casks list artifacts as "/Applications/App.app (123 files, 45MB)"
+        .map(|line| line.split(" (").next().unwrap_or(line).trim())
+        .filter(|line| line.starts_with('/'))
+        .map(PathBuf::from)
+        .collect()
+}
+
+fn scan(roots: &[PathBuf]) -> HashMap<PathBuf, (SystemTime, u64)> {
+    // Downloads are extracted in the work directory, which may live in the bin directory
+    let work_dir = PathResolver::temp_root();
+    roots
+        .iter()
+        .flat_map(|root| {
+            WalkDir::new(root)
+                .max_depth(MAX_SCAN_DEPTH)
+                .into_iter()
+                .filter_entry(|entry| entry.path() != work_dir)
+                .filter_map(Result::ok)
+        })
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            Some((
+                entry.into_path(),
+                (metadata.modified().ok()?, metadata.len()),
+            ))
+        })
+        .collect()
+}
+
+pub(crate) fn sha256_file(path: &Path) -> io::Result<String> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn write_receipt(dir: &Path, tool: &str, files: &[(&Path, FileChange)]) -> ToolState {
+        let receipt = Receipt {
+            tool: tool.to_string(),
+            version: "1.0.0".to_string(),
+            install_method: "github".to_string(),
+            recorded_at: current_timestamp(),
+            files: files
+                .iter()
+                .map(|(path, change)| ReceiptFile {
+                    path: path.display().to_string(),
+                    change: *change,
+                    size: fs::metadata(path).unwrap().len(),
+                    sha256: sha256_file(path).unwrap(),
+                })
+                .collect(),
+        };
+        let receipt_path = dir.join(format!("{tool}.json"));
+        fs::write(&receipt_path, serde_json::to_string(&receipt).unwrap()).unwrap();
+        serde_json::from_value(json!({
+            "version": "1.0.0",
+            "install_path": files[0].0,
+            "installed_by_devbox": true,
+            "install_method": "github",
+            "package_type": "binary",
+            "receipt": receipt_path,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_snapshot_detects_created_and_modified_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("existing"), "v1").unwrap();
+        fs::write(dir.path().join("untouched"), "v1").unwrap();
+        let roots = vec![dir.path().to_path_buf()];
+        let snapshot = FileSnapshot {
+            files: scan(&roots),
+            roots,
+        };
+
+        fs::write(dir.path().join("existing"), "v2 with a different size").unwrap();
+        fs::write(dir.path().join("new"), "").unwrap();
+        let mut changes = snapshot.changes();
+        changes.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(
+            changes,
+            [
+                (dir.path().join("existing"), FileChange::Modified),
+                (dir.path().join("new"), FileChange::Created),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_remove_deletes_only_unchanged_files_it_created() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = |name: &str| {
+            let path = dir.path().join(name);
+            fs::write(&path, name).unwrap();
+            path
+        };
+        let (binary, edited, shared, config) =
+            (file("tool"), file("tool.1"), file("shared"), file("config"));
+
+        let mut state = DevBoxState::default();
+        state.tools.insert(
+            "tool".to_string(),
+            write_receipt(
+                dir.path(),
+                "tool",
+                &[
+                    (&binary, FileChange::Created),
+                    (&edited, FileChange::Created),
+                    (&shared, FileChange::Created),
+                    (&config, FileChange::Modified),
+                ],
+            ),
+        );
+        state.tools.insert(
+            "other".to_string(),
+            write_receipt(dir.path(), "other", &[(&shared, FileChange::Created)]),
+        );
+        fs::write(&edited, "edited by the user").unwrap();
+
+        let owners = owners(&state, &shared);
+        assert_eq!(
+            owners.iter().map(|(name, _)| *name).collect::<Vec<_>>(),
+            ["other", "tool"]
+        );
+
+        assert_eq!(remove_created_files(&state, "tool"), vec![binary.clone()]);
+        assert!(!binary.exists());
+        assert!(edited.exists() && shared.exists() && config.exists());
+        assert!(!dir.path().join("tool.json").exists());
+    }
+}
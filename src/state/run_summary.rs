@@ -0,0 +1,347 @@
+// This module keeps a small summary of the last `now` run next to `state.json`.
+//
+// The summary is what `setup-devbox prompt-status` reads to tell, without loading
+// configuration or state, whether the devbox is in sync: it records which tools failed,
+// which tools `check-updates` found updates for, and the modification time of the state
+// file right after the run. A state file changed since then (e.g. by `add`, `remove` or
+// `reset`) means the devbox has not been provisioned since.
+//
+// The summary of the run before is kept as `previous-run.json`, so `now --diff-last` can
+// highlight what changed between the two (newly failing tools, version bumps).
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+use colored::Colorize;
+
+use crate::core::timestamps::current_timestamp;
+use crate::log_debug;
+use crate::schemas::state_file::DevBoxState;
+use crate::schemas::tools_types::InstallationSummary;
+
+/// Summary of the last completed `now` run.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RunSummary {
+    /// When the run finished (RFC 3339).
+    pub finished_at: String,
+    /// Modification time of the state file after the run, in milliseconds since the epoch.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_modified: Option<u64>,
+    /// Tools that failed, with the reason.
+    #[serde(default)]
+    pub failed_tools: BTreeMap<String, String>,
+    /// Tools `check-updates` reported a newer version for.
+    #[serde(default)]
+    pub updates_available: Vec<String>,
+    /// Version of every tool in the state after the run.
+    #[serde(default)]
+    pub tool_versions: BTreeMap<String, String>,
+}
+
+/// A difference between two recorded runs.
+#[derive(Debug, PartialEq)]
+pub enum RunChange {
+    /// Failed in this run but not in the previous one.
+    NewlyFailing { name: String, reason: String },
+    /// Failed in the previous run and no longer does.
+    Recovered { name: String },
+    /// Installed with a different version than in the previous run.
+    VersionChanged {
+        name: String,
+        from: String,
+        to: String,
+    },
+    /// Present after this run but not after the previous one.
+    Added { name: String, version: String },
+    /// Present after the previous run but not after this one.
+    Removed { name: String },
+}
+
+/// Provisioning status derived from the last run summary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProvisioningStatus {
+    /// The last run succeeded and nothing changed since.
+    InSync,
+    /// State changed since the last run, or updates are available.
+    OutOfDate,
+    /// The last run had failing tools.
+    Failed(usize),
+    /// No run has been recorded yet.
+    Unknown,
+}
+
+impl RunSummary {
+    /// Loads the summary at `path`, if one was recorded.
+    pub fn load(path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Path the summary of the run before the last one is kept at.
+    pub fn previous_path(path: &Path) -> PathBuf {
+        path.with_file_name("previous-run.json")
+    }
+
+    /// Records the outcome of a finished run and keeps the one it replaces as the previous
+    /// run. Updates reported by `check-updates` are kept, except for tools this run
+    /// installed or updated.
+    pub fn record(
+        path: &Path,
+        state_file: &Path,
+        state: &DevBoxState,
+        tools: Option<&InstallationSummary>,
+    ) -> Self {
+        if path.exists()
+            && let Err(e) = fs::rename(path, Self::previous_path(path))
+        {
+            log_debug!(
+                "[SDB::RunSummary] Failed to keep the previous run summary: {}",
+                e
+            );
+        }
+        let mut updates_available = Self::load(&Self::previous_path(path))
+            .map(|previous| previous.updates_available)
+            .unwrap_or_default();
+        let mut failed_tools = BTreeMap::new();
+        if let Some(tools) = tools {
+            updates_available.retain(|name| {
+                !tools.installed_tools.contains(name) && !tools.updated_tools.contains(name)
+            });
+            failed_tools.extend(tools.failed_tools.iter().cloned());
+        }
+
+        let summary = Self {
+            finished_at: current_timestamp(),
+            state_modified: modified_millis(state_file),
+            failed_tools,
+            updates_available,
+            tool_versions: state
+                .tools
+                .iter()
+                .map(|(name, tool)| (name.clone(), tool.version.clone()))
+                .collect(),
+        };
+        summary.save(path);
+        summary
+    }
+
+    /// Lists what changed from the `previous` run to this one.
+    pub fn diff(&self, previous: &Self) -> Vec<RunChange> {
+        let mut changes = Vec::new();
+        for (name, reason) in &self.failed_tools {
+            if !previous.failed_tools.contains_key(name) {
+                changes.push(RunChange::NewlyFailing {
+                    name: name.clone(),
+                    reason: reason.clone(),
+                });
+            }
+        }
+        for name in previous.failed_tools.keys() {
+            if !self.failed_tools.contains_key(name) {
+                changes.push(RunChange::Recovered { name: name.clone() });
+            }
+        }
+        for (name, version) in &self.tool_versions {
+            match previous.tool_versions.get(name) {
+                Some(from) if from != version => changes.push(RunChange::VersionChanged {
+                    name: name.clone(),
+                    from: from.clone(),
+                    to: version.clone(),
+                }),
+                Some(_) => {}
+                None => changes.push(RunChange::Added {
+                    name: name.clone(),
+                    version: version.clone(),
+                }),
+            }
+        }
+        for name in previous.tool_versions.keys() {
+            if !self.tool_versions.contains_key(name) {
+                changes.push(RunChange::Removed { name: name.clone() });
+            }
+        }
+        changes
+    }
+
+    /// Prints the changes since `previous` (`--diff-last`).
+    pub fn display_diff(&self, previous: Option<&Self>) {
+        println!();
+        println!(
+            "{} Changes since previous run {}",
+            "==============".cyan(),
+            "===============".cyan()
+        );
+        let Some(previous) = previous else {
+            println!("  No previous run recorded yet.");
+            println!("{}", "=".repeat(57).cyan());
+            return;
+        };
+
+        let changes = self.diff(previous);
+        println!(
+            "  Previous run finished at {}",
+            previous.finished_at.dimmed()
+        );
+        if changes.is_empty() {
+            println!("  {}", "No changes.".green());
+        }
+        for change in changes {
+            match change {
+                RunChange::NewlyFailing { name, reason } => println!(
+                    "  {} {} now fails: {}",
+                    "✘".red().bold(),
+                    name.red().bold(),
+                    reason
+                ),
+                RunChange::Recovered { name } => {
+                    println!("  {} {} no longer fails", "✔".green(), name.green())
+                }
+                RunChange::VersionChanged { name, from, to } => println!(
+                    "  {} {} {} → {}",
+                    "↑".yellow(),
+                    name.bold(),
+                    from.dimmed(),
+                    to.yellow()
+                ),
+                RunChange::Added { name, version } => {
+                    println!("  {} {} {}", "+".green(), name.bold(), version)
+                }
+                RunChange::Removed { name } => println!("  {} {}", "-".red(), name.bold()),
+            }
+        }
+        println!("{}", "=".repeat(57).cyan());
+    }
+
+    /// Stores the tools `check-updates` found newer versions for.
+    pub fn record_updates_available(path: &Path, tools: Vec<String>) {
+        let mut summary = Self::load(path).unwrap_or_default();
+        summary.updates_available = tools;
+        summary.save(path);
+    }
+
+    /// Derives the provisioning status, given the current modification time of the state file.
+    pub fn status(summary: Option<&Self>, state_modified: Option<u64>) -> ProvisioningStatus {
+        match summary {
+            None => ProvisioningStatus::Unknown,
+            Some(summary) if !summary.failed_tools.is_empty() => {
+                ProvisioningStatus::Failed(summary.failed_tools.len())
+            }
+            Some(summary)
+                if summary.state_modified != state_modified
+                    || !summary.updates_available.is_empty() =>
+            {
+                ProvisioningStatus::OutOfDate
+            }
+            Some(_) => ProvisioningStatus::InSync,
+        }
+    }
+
+    /// Failures only affect the prompt segment, so they are logged and ignored.
+    fn save(&self, path: &Path) {
+        let result = serde_json::to_string_pretty(self)
+            .map_err(std::io::Error::other)
+            .and_then(|json| fs::write(path, json));
+        if let Err(e) = result {
+            log_debug!(
+                "[SDB::RunSummary] Failed to write {}: {}",
+                path.display(),
+                e
+            );
+        }
+    }
+}
+
+/// Modification time of a file in milliseconds since the epoch.
+pub fn modified_millis(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_millis() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let previous = RunSummary {
+            failed_tools: BTreeMap::from([("fd".to_string(), "timeout".to_string())]),
+            tool_versions: BTreeMap::from([
+                ("bat".to_string(), "0.24.0".to_string()),
+                ("jq".to_string(), "1.7".to_string()),
+            ]),
+            ..Default::default()
+        };
+        let current = RunSummary {
+            failed_tools: BTreeMap::from([("gh".to_string(), "404".to_string())]),
+            tool_versions: BTreeMap::from([
+                ("bat".to_string(), "0.25.0".to_string()),
+                ("fd".to_string(), "10.2.0".to_string()),
+            ]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            current.diff(&previous),
+            vec![
+                RunChange::NewlyFailing {
+                    name: "gh".to_string(),
+                    reason: "404".to_string()
+                },
+                RunChange::Recovered {
+                    name: "fd".to_string()
+                },
+                RunChange::VersionChanged {
+                    name: "bat".to_string(),
+                    from: "0.24.0".to_string(),
+                    to: "0.25.0".to_string()
+                },
+                RunChange::Added {
+                    name: "fd".to_string(),
+                    version: "10.2.0".to_string()
+                },
+                RunChange::Removed {
+                    name: "jq".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_status() {
+        let mut summary = RunSummary {
+            state_modified: Some(1_000),
+            ..Default::default()
+        };
+        assert_eq!(
+            RunSummary::status(None, Some(1_000)),
+            ProvisioningStatus::Unknown
+        );
+        assert_eq!(
+            RunSummary::status(Some(&summary), Some(1_000)),
+            ProvisioningStatus::InSync
+        );
+        assert_eq!(
+            RunSummary::status(Some(&summary), Some(2_000)),
+            ProvisioningStatus::OutOfDate
+        );
+
+        summary.updates_available.push("bat".to_string());
+        assert_eq!(
+            RunSummary::status(Some(&summary), Some(1_000)),
+            ProvisioningStatus::OutOfDate
+        );
+
+        summary
+            .failed_tools
+            .insert("fd".to_string(), "download failed".to_string());
+        assert_eq!(
+            RunSummary::status(Some(&summary), Some(1_000)),
+            ProvisioningStatus::Failed(1)
+        );
+    }
+}
@@ -0,0 +1,38 @@
+//! Checks that the library facade reports broken files as errors instead of exiting.
+
+use std::fs;
+
+use setup_devbox::{ApplyOptions, Error, PathResolver};
+
+#[test]
+fn test_broken_files_are_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("config.yaml");
+    let state = dir.path().join("state.json");
+    fs::write(&config, "tools: [unterminated\n").unwrap();
+    fs::write(&state, "{ not json").unwrap();
+
+    let paths = PathResolver::new(
+        Some(config.display().to_string()),
+        Some(state.display().to_string()),
+    )
+    .unwrap();
+
+    assert!(matches!(
+        setup_devbox::load_config(&paths),
+        Err(Error::Config(_))
+    ));
+    assert!(matches!(
+        setup_devbox::load_state(&paths),
+        Err(Error::State(_))
+    ));
+    assert!(setup_devbox::plan(&paths, false).is_err());
+    let options = ApplyOptions {
+        dry_run: true,
+        ..ApplyOptions::default()
+    };
+    assert!(matches!(
+        setup_devbox::apply(&paths, &options),
+        Err(Error::Apply(_))
+    ));
+}
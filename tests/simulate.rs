@@ -0,0 +1,69 @@
+//! Runs the `plan` and `now` pipelines end to end against simulated installers.
+
+use std::fs;
+
+use setup_devbox::{ApplyOptions, PathResolver, ToolAction};
+
+#[test]
+fn test_simulated_plan_and_apply() {
+    let dir = tempfile::tempdir().unwrap();
+    let fixtures = dir.path().join("simulate.yaml");
+    fs::write(
+        &fixtures,
+        "tools:\n  ripgrep:\n    latest: 14.1.1\n  bat:\n    fail: connection reset by peer\n",
+    )
+    .unwrap();
+    let config = dir.path().join("tools.yaml");
+    fs::write(
+        &config,
+        "tools:\n\
+         - name: ripgrep\n  source: github\n  repo: BurntSushi/ripgrep\n  version: latest\n  rename_to: rg\n\
+         - name: bat\n  source: cargo\n  version: 0.24.0\n\
+         - name: jq\n  source: brew\n  version: 1.7.1\n",
+    )
+    .unwrap();
+
+    setup_devbox::simulate(&fixtures).unwrap();
+    let paths = PathResolver::new(Some(config.display().to_string()), None).unwrap();
+    assert_eq!(paths.state_file(), dir.path().join("state.json"));
+
+    let planned = setup_devbox::plan(&paths, false).unwrap();
+    assert_eq!(planned.len(), 3);
+    assert!(
+        planned
+            .iter()
+            .all(|tool| matches!(tool.action, ToolAction::Install))
+    );
+
+    let outcome = setup_devbox::apply(&paths, &ApplyOptions::default()).unwrap();
+    assert!(!outcome.succeeded());
+    assert_eq!(outcome.failed.len(), 1);
+    assert_eq!(outcome.failed[0].0, "bat");
+    let state = setup_devbox::load_state(&paths).unwrap();
+    let ripgrep = &state.tools["ripgrep"];
+    assert_eq!(ripgrep.version, "14.1.1");
+    assert_eq!(
+        ripgrep.install_path,
+        dir.path().join("bin/rg").display().to_string()
+    );
+    assert!(!dir.path().join("bin").exists());
+    assert_eq!(state.tools["jq"].version, "1.7.1");
+    assert!(!state.tools.contains_key("bat"));
+
+    // A second run only retries the failed tool
+    let planned = setup_devbox::plan(&paths, false).unwrap();
+    let pending: Vec<&str> = planned
+        .iter()
+        .filter(|tool| matches!(tool.action, ToolAction::Install | ToolAction::Update))
+        .map(|tool| tool.name.as_str())
+        .collect();
+    assert_eq!(pending, ["bat"]);
+
+    // A newer upstream version updates `latest` tools when forced
+    let planned = setup_devbox::plan(&paths, true).unwrap();
+    assert!(
+        planned
+            .iter()
+            .any(|tool| tool.name == "ripgrep" && matches!(tool.action, ToolAction::Update))
+    );
+}